@@ -0,0 +1,166 @@
+//! Protocol addresses - the accounts every instruction builder reads from
+
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token;
+use laminar::lst_rate::{StakePoolHeader, SPL_STAKE_POOL_PROGRAM_ID};
+use laminar::state::GlobalState;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::pda::{
+  collateral_vault_address, collateral_vault_authority_address, global_state_address, psm_vault_address,
+  stability_pool_address, vault_authority_address,
+};
+
+/// Every protocol address an instruction may touch.
+#[derive(Clone, Copy, Debug)]
+pub struct Addresses {
+  pub global_state: Pubkey,
+  pub vault_authority: Pubkey,
+  pub vault: Pubkey,
+  pub amusd_mint: Pubkey,
+  pub asol_mint: Pubkey,
+  pub lst_mint: Pubkey,
+  pub treasury: Pubkey,
+  pub treasury_amusd_account: Pubkey,
+  pub treasury_asol_account: Pubkey,
+  /// Pyth price update passed to the user instructions; `None` while pricing uses the mock
+  pub price_update: Option<Pubkey>,
+  /// Switchboard pull feed passed to the user instructions; `None` unless that feed is configured
+  pub switchboard_feed: Option<Pubkey>,
+  /// SPL stake pool passed to `sync_exchange_rate`; `None` while the LST rate is the mock
+  pub stake_pool: Option<Pubkey>,
+  /// Marinade state passed to `sync_exchange_rate`; `None` unless mSOL is priced from it
+  pub marinade_state: Option<Pubkey>,
+  /// Sanctum calculator program and LST state passed to `sync_exchange_rate`; `None` unless configured
+  pub lst_calculator: Option<(Pubkey, Pubkey)>,
+  /// CollateralVault passed to the user instructions; `None` while they move the primary LST
+  pub collateral_vault: Option<Pubkey>,
+  pub stability_pool: Pubkey,
+  pub stability_pool_amusd_vault: Pubkey,
+  pub stability_pool_lst_vault: Pubkey,
+  /// Token program that owns every mint in the protocol
+  pub token_program: Pubkey,
+  /// PSM stablecoin passed to the PSM swaps; `None` until `initialize_psm`
+  pub psm_usdc_mint: Option<Pubkey>,
+  pub psm_vault: Pubkey,
+  /// Savings Pool and its amUSD vault passed to mint_amusd / redeem_amusd;
+  /// `None` until `initialize_savings_pool`
+  pub savings_pool: Option<(Pubkey, Pubkey)>,
+  /// Pass the caller's UserPosition to the four user instructions
+  pub user_positions: bool,
+}
+
+impl Addresses {
+  pub fn derive(amusd_mint: Pubkey, asol_mint: Pubkey, lst_mint: Pubkey, treasury: Pubkey) -> Self {
+    Self::derive_for_token_program(amusd_mint, asol_mint, lst_mint, treasury, spl_token::ID)
+  }
+
+  /// `derive` for a protocol whose mints are all owned by `token_program`.
+  pub fn derive_for_token_program(
+    amusd_mint: Pubkey,
+    asol_mint: Pubkey,
+    lst_mint: Pubkey,
+    treasury: Pubkey,
+    token_program: Pubkey,
+  ) -> Self {
+    let vault_authority = vault_authority_address();
+    let stability_pool = stability_pool_address();
+    let ata = |wallet: &Pubkey, mint: &Pubkey| get_associated_token_address_with_program_id(wallet, mint, &token_program);
+    Self {
+      global_state: global_state_address(),
+      vault_authority,
+      vault: ata(&vault_authority, &lst_mint),
+      amusd_mint,
+      asol_mint,
+      lst_mint,
+      treasury,
+      treasury_amusd_account: ata(&treasury, &amusd_mint),
+      treasury_asol_account: ata(&treasury, &asol_mint),
+      price_update: None,
+      switchboard_feed: None,
+      stake_pool: None,
+      marinade_state: None,
+      lst_calculator: None,
+      collateral_vault: None,
+      stability_pool,
+      stability_pool_amusd_vault: ata(&stability_pool, &amusd_mint),
+      stability_pool_lst_vault: ata(&stability_pool, &lst_mint),
+      token_program,
+      psm_usdc_mint: None,
+      psm_vault: psm_vault_address(),
+      savings_pool: None,
+      user_positions: false,
+    }
+  }
+
+  /// The same protocol with the user instructions' vault leg moved to the
+  /// `CollateralVault` registered for `lst_mint`.
+  pub fn with_collateral(&self, lst_mint: Pubkey) -> Self {
+    let vault_authority = collateral_vault_authority_address(&lst_mint);
+    Self {
+      lst_mint,
+      vault_authority,
+      vault: get_associated_token_address_with_program_id(&vault_authority, &lst_mint, &self.token_program),
+      collateral_vault: Some(collateral_vault_address(&lst_mint)),
+      ..*self
+    }
+  }
+}
+
+impl Addresses {
+  /// Addresses of a live deployment, read off its `GlobalState`. Oracle and
+  /// LST-rate accounts are filled in for every feed the state has configured;
+  /// the Savings Pool and user positions are left for the caller to opt into.
+  pub fn from_global_state(state: &GlobalState, token_program: Pubkey) -> Self {
+    let configured = |key: Pubkey| (key != Pubkey::default()).then_some(key);
+    Self {
+      price_update: configured(state.pyth_price_feed),
+      switchboard_feed: configured(state.switchboard_feed),
+      stake_pool: configured(state.lst_stake_pool),
+      marinade_state: configured(state.marinade_state),
+      lst_calculator: configured(state.lst_calculator_program).map(|program| (program, state.lst_calculator_state)),
+      psm_usdc_mint: configured(state.psm_usdc_mint),
+      ..Self::derive_for_token_program(
+        state.amusd_mint,
+        state.asol_mint,
+        state.supported_lst_mint,
+        state.treasury,
+        token_program,
+      )
+    }
+  }
+}
+
+/// User-side token accounts passed to the four user instructions.
+#[derive(Clone, Copy, Debug)]
+pub struct UserAccounts {
+  pub user: Pubkey,
+  pub lst_account: Pubkey,
+  pub amusd_account: Pubkey,
+  pub asol_account: Pubkey,
+}
+
+/// Stake pool accounts `redeem_*_to_sol` needs to unstake the primary LST
+/// through the pool's `WithdrawSol`, and `zap_mint_*` to stake into it
+/// through its `DepositSol`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakePoolExit {
+  pub stake_pool: Pubkey,
+  pub withdraw_authority: Pubkey,
+  pub reserve_stake: Pubkey,
+  pub manager_fee_account: Pubkey,
+}
+
+impl StakePoolExit {
+  /// Accounts of the pool at `stake_pool`, read off its header.
+  pub fn from_header(stake_pool: Pubkey, header: &StakePoolHeader) -> Self {
+    let (withdraw_authority, _) =
+      Pubkey::find_program_address(&[stake_pool.as_ref(), b"withdraw"], &SPL_STAKE_POOL_PROGRAM_ID);
+    Self {
+      stake_pool,
+      withdraw_authority,
+      reserve_stake: header.reserve_stake,
+      manager_fee_account: header.manager_fee_account,
+    }
+  }
+}
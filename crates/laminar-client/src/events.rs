@@ -0,0 +1,48 @@
+//! Event decoding - typed views over Laminar's `emit_cpi!` event stream
+//! Inputs are each event's `Event::data` bytes (discriminator first), as
+//! carried by the self-CPI inner instructions after the event tag.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::Event;
+use laminar::events::HaircutApplied;
+use solana_sdk::pubkey::Pubkey;
+
+/// Decode `data` as `E`; `None` for any other event type or a payload that
+/// does not match its discriminator.
+pub fn decode_event<E: Event>(data: &[u8]) -> Option<E> {
+  data
+    .strip_prefix(E::DISCRIMINATOR)
+    .and_then(|body| E::try_from_slice(body).ok())
+}
+
+/// Par shortfall amUSD redeemers took through the haircut
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HaircutReport {
+  /// Shortfall each redeemer bore after insurance, in lamports
+  pub shortfall_by_user: BTreeMap<Pubkey, u64>,
+  /// Sum of `shortfall_by_user`
+  pub total_shortfall_lamports: u64,
+  /// Lamports the insurance fund added back toward par
+  pub insurance_lamports: u64,
+  pub redemptions: u64,
+  /// Lifetime shortfall as of the last `HaircutApplied`, read from the event
+  pub cumulative_shortfall_lamports: u64,
+  /// Some redemption in the stream tripped the episode breaker
+  pub breaker_tripped: bool,
+}
+
+/// Aggregate every `HaircutApplied` among `events`, in order; other event
+/// types are skipped.
+pub fn haircut_report<'a>(events: impl IntoIterator<Item = &'a [u8]>) -> HaircutReport {
+  let mut report = HaircutReport::default();
+  for haircut in events.into_iter().filter_map(decode_event::<HaircutApplied>) {
+    *report.shortfall_by_user.entry(haircut.user).or_default() += haircut.shortfall_lamports;
+    report.total_shortfall_lamports += haircut.shortfall_lamports;
+    report.insurance_lamports += haircut.insurance_lamports;
+    report.redemptions += 1;
+    report.cumulative_shortfall_lamports = haircut.cumulative_shortfall_lamports;
+    report.breaker_tripped |= haircut.breaker_tripped;
+  }
+  report
+}
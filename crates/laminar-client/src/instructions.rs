@@ -0,0 +1,1941 @@
+//! Instruction builders - one per Laminar entrypoint
+//! Each builder fills the Anchor account struct from `Addresses` and the
+//! caller's accounts, so account order and writability always match the IDL.
+
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account};
+use laminar::instructions::{DustToken, LaunchConfig, MintAmusdArgs, MintAsolArgs, RedeemAmusdArgs, RedeemAsolArgs, TradeQuote};
+use laminar::lst_rate::{SPL_STAKE_POOL_PROGRAM_ID, STAKE_PROGRAM_ID};
+use laminar::state::{ParamChange, RedemptionTicket};
+use solana_sdk::{
+  instruction::{AccountMeta, Instruction},
+  pubkey::Pubkey,
+  sysvar,
+};
+
+use crate::addresses::{Addresses, StakePoolExit, UserAccounts};
+use crate::pda::*;
+use crate::rpc::ClientError;
+
+fn laminar_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+  Instruction {
+    program_id: laminar::ID,
+    accounts: accounts.to_account_metas(None),
+    data: data.data(),
+  }
+}
+
+/// Flag `key` as a signer on an account the struct leaves unchecked.
+fn mark_signer(ix: &mut Instruction, key: &Pubkey) {
+  for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == *key) {
+    meta.is_signer = true;
+  }
+}
+
+pub fn initialize_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  min_cr_bps: u64,
+  target_cr_bps: u64,
+  mock_sol_price_usd: u64,
+  mock_lst_to_sol_rate: u64,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::Initialize {
+      authority: *authority,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      vault: addresses.vault,
+      lst_mint: addresses.lst_mint,
+      vault_authority: addresses.vault_authority,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::Initialize {
+      min_cr_bps,
+      target_cr_bps,
+      mock_sol_price_usd,
+      mock_lst_to_sol_rate,
+    },
+  )
+}
+
+pub fn update_mock_prices_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_sol_price_usd: u64,
+  new_lst_to_sol_rate: u64,
+  new_oracle_confidence_usd: u64,
+  force: bool,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMockPrices {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMockPrices {
+      new_sol_price_usd,
+      new_lst_to_sol_rate,
+      new_oracle_confidence_usd,
+      force,
+    },
+  )
+}
+
+pub fn update_mock_oracle_confidence_ix(addresses: &Addresses, authority: &Pubkey, new_confidence_usd: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMockOracleConfidence {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMockOracleConfidence { new_confidence_usd },
+  )
+}
+
+pub fn update_max_oracle_age_ix(addresses: &Addresses, authority: &Pubkey, new_max_oracle_age_seconds: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMaxOracleAge {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMaxOracleAge { new_max_oracle_age_seconds },
+  )
+}
+
+pub fn update_ema_half_life_ix(addresses: &Addresses, authority: &Pubkey, new_ema_half_life_slots: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateEmaHalfLife {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateEmaHalfLife { new_ema_half_life_slots },
+  )
+}
+
+pub fn update_rebalance_discount_ix(addresses: &Addresses, authority: &Pubkey, new_rebalance_discount_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateRebalanceDiscount {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateRebalanceDiscount { new_rebalance_discount_bps },
+  )
+}
+
+pub fn update_recovery_bonus_ix(addresses: &Addresses, authority: &Pubkey, new_recovery_bonus_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateRecoveryBonus {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateRecoveryBonus { new_recovery_bonus_bps },
+  )
+}
+
+pub fn update_fee_split_ix(addresses: &Addresses, authority: &Pubkey, new_fee_split_insurance_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateFeeSplit {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateFeeSplit { new_fee_split_insurance_bps },
+  )
+}
+
+pub fn update_max_price_move_ix(addresses: &Addresses, authority: &Pubkey, new_max_price_move_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMaxPriceMove {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMaxPriceMove { new_max_price_move_bps },
+  )
+}
+
+pub fn update_oracle_bounds_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  min_sol_price_usd: u64,
+  max_sol_price_usd: u64,
+  min_lst_rate: u64,
+  max_lst_rate: u64,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateOracleBounds {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateOracleBounds { min_sol_price_usd, max_sol_price_usd, min_lst_rate, max_lst_rate },
+  )
+}
+
+pub fn emergency_pause_ix(addresses: &Addresses, authority: &Pubkey, mint_paused: bool, redeem_paused: bool) -> Instruction {
+  laminar_ix(
+    laminar::accounts::EmergencyPause {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::EmergencyPause { mint_paused, redeem_paused },
+  )
+}
+
+pub fn initiate_settlement_ix(addresses: &Addresses, authority: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::InitiateSettlement {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::InitiateSettlement {},
+  )
+}
+
+pub fn socialize_bad_debt_ix(addresses: &Addresses, authority: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SocializeBadDebt {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SocializeBadDebt {},
+  )
+}
+
+pub fn sync_exchange_rate_ix(addresses: &Addresses) -> Instruction {
+  let mut ix = laminar_ix(
+    laminar::accounts::SyncExchangeRate {
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      stake_pool: addresses.stake_pool,
+      marinade_state: addresses.marinade_state,
+      lst_calculator: addresses.lst_calculator.map(|(program, _)| program),
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SyncExchangeRate {},
+  );
+  // Calculator `LstToSol` accounts: LST mint, then the LST state.
+  if let Some((_, lst_state)) = addresses.lst_calculator {
+    ix.accounts.push(AccountMeta::new_readonly(addresses.lst_mint, false));
+    ix.accounts.push(AccountMeta::new_readonly(lst_state, false));
+  }
+  ix
+}
+
+pub fn set_pyth_price_feed_ix(addresses: &Addresses, authority: &Pubkey, new_pyth_price_feed: Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetPythPriceFeed {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetPythPriceFeed { new_pyth_price_feed },
+  )
+}
+
+pub fn set_switchboard_feed_ix(addresses: &Addresses, authority: &Pubkey, new_switchboard_feed: Pubkey, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetSwitchboardFeed {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetSwitchboardFeed { new_switchboard_feed },
+  )
+}
+
+pub fn set_lst_stake_pool_ix(addresses: &Addresses, authority: &Pubkey, new_lst_stake_pool: Pubkey, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetLstStakePool {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetLstStakePool { new_lst_stake_pool },
+  )
+}
+
+pub fn set_marinade_state_ix(addresses: &Addresses, authority: &Pubkey, new_marinade_state: Pubkey, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetMarinadeState {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetMarinadeState { new_marinade_state },
+  )
+}
+
+pub fn set_haircut_breaker_ix(addresses: &Addresses, authority: &Pubkey, new_max_episode_haircut_lamports: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetHaircutBreaker {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetHaircutBreaker { new_max_episode_haircut_lamports },
+  )
+}
+
+pub fn set_lst_calculator_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_lst_calculator_program: Pubkey,
+  new_lst_calculator_state: Pubkey,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetLstCalculator {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetLstCalculator { new_lst_calculator_program, new_lst_calculator_state },
+  )
+}
+
+pub fn set_price_source_ix(addresses: &Addresses, authority: &Pubkey, new_price_source: u8, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetPriceSource {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetPriceSource { new_price_source },
+  )
+}
+
+pub fn set_secondary_price_source_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_secondary_price_source: u8,
+  new_max_oracle_deviation_bps: u64,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetSecondaryPriceSource {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetSecondaryPriceSource { new_secondary_price_source, new_max_oracle_deviation_bps },
+  )
+}
+
+pub fn set_fallback_price_source_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_fallback_price_source: Option<u8>,
+  new_fallback_uncertainty_bps: u64,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetFallbackPriceSource {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetFallbackPriceSource { new_fallback_price_source, new_fallback_uncertainty_bps },
+  )
+}
+
+pub fn migrate_global_state_ix(addresses: &Addresses, authority: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::MigrateGlobalState {
+      authority: *authority,
+      global_state: addresses.global_state,
+      vault_authority: addresses.vault_authority,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::MigrateGlobalState {},
+  )
+}
+
+pub fn update_parameters_ix(addresses: &Addresses, authority: &Pubkey, new_min_cr_bps: u64, new_target_cr_bps: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateParameters {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateParameters { new_min_cr_bps, new_target_cr_bps },
+  )
+}
+
+pub fn configure_launch_ix(addresses: &Addresses, authority: &Pubkey, config: LaunchConfig) -> Instruction {
+  let treasury = config.treasury;
+  let treasury_is_pda = config.treasury_is_pda;
+  let ata = |mint: &Pubkey| get_associated_token_address_with_program_id(&treasury, mint, &addresses.token_program);
+  let mut ix = laminar_ix(
+    laminar::accounts::ConfigureLaunch {
+      authority: *authority,
+      global_state: addresses.global_state,
+      treasury,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      treasury_amusd_account: ata(&addresses.amusd_mint),
+      treasury_asol_account: ata(&addresses.asol_mint),
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ConfigureLaunch { config },
+  );
+  if !treasury_is_pda {
+    mark_signer(&mut ix, &treasury);
+  }
+  ix
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_fee_parameters_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  fee_amusd_mint_bps: u64,
+  fee_amusd_redeem_bps: u64,
+  fee_asol_mint_bps: u64,
+  fee_asol_redeem_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateFeeParameters {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateFeeParameters {
+      fee_amusd_mint_bps,
+      fee_amusd_redeem_bps,
+      fee_asol_mint_bps,
+      fee_asol_redeem_bps,
+      fee_min_multiplier_bps,
+      fee_max_multiplier_bps,
+    },
+  )
+}
+
+/// Move fee collection to `new_treasury`, creating its amUSD/aSOL ATAs if needed.
+/// A wallet treasury (`treasury_is_pda` false) must co-sign.
+pub fn set_treasury_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_treasury: &Pubkey,
+  treasury_is_pda: bool,
+  treasury_owner_program: Pubkey,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  let ata = |mint: &Pubkey| get_associated_token_address_with_program_id(new_treasury, mint, &addresses.token_program);
+  let mut ix = laminar_ix(
+    laminar::accounts::SetTreasury {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      new_treasury: *new_treasury,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      new_treasury_amusd_account: ata(&addresses.amusd_mint),
+      new_treasury_asol_account: ata(&addresses.asol_mint),
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetTreasury { treasury_is_pda, treasury_owner_program },
+  );
+  // `new_treasury` is unchecked in the struct; a wallet proves itself by signing.
+  if !treasury_is_pda {
+    mark_signer(&mut ix, new_treasury);
+  }
+  ix
+}
+
+pub fn update_oracle_parameters_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  max_oracle_staleness_slots: u64,
+  max_conf_bps: u64,
+  uncertainty_max_bps: u64,
+  max_lst_stale_epochs: u64,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateOracleParameters {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateOracleParameters {
+      max_oracle_staleness_slots,
+      max_conf_bps,
+      uncertainty_max_bps,
+      max_lst_stale_epochs,
+    },
+  )
+}
+
+pub fn update_rounding_reserve_cap_ix(addresses: &Addresses, authority: &Pubkey, new_max_rounding_reserve_lamports: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateRoundingReserveCap {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateRoundingReserveCap { new_max_rounding_reserve_lamports },
+  )
+}
+
+pub fn propose_authority_ix(addresses: &Addresses, authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ProposeAuthority {
+      authority: *authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ProposeAuthority { new_authority },
+  )
+}
+
+pub fn accept_authority_ix(addresses: &Addresses, pending_authority: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::AcceptAuthority {
+      pending_authority: *pending_authority,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::AcceptAuthority {},
+  )
+}
+
+pub fn update_max_pause_duration_ix(addresses: &Addresses, authority: &Pubkey, new_max_pause_duration_slots: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMaxPauseDuration {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMaxPauseDuration { new_max_pause_duration_slots },
+  )
+}
+
+pub fn force_unpause_redeem_ix(addresses: &Addresses, caller: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ForceUnpauseRedeem {
+      caller: *caller,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ForceUnpauseRedeem {},
+  )
+}
+
+pub fn queue_parameter_update_ix(addresses: &Addresses, authority: &Pubkey, change: ParamChange) -> Instruction {
+  laminar_ix(
+    laminar::accounts::QueueParameterUpdate {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change: pending_param_change_address(change.kind()),
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::QueueParameterUpdate { change },
+  )
+}
+
+/// Apply the queued change of `kind`; its rent goes back to `proposer`.
+pub fn execute_parameter_update_ix(addresses: &Addresses, executor: &Pubkey, proposer: &Pubkey, kind: u8) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ExecuteParameterUpdate {
+      executor: *executor,
+      global_state: addresses.global_state,
+      pending_param_change: pending_param_change_address(kind),
+      proposer: *proposer,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ExecuteParameterUpdate { kind },
+  )
+}
+
+pub fn cancel_parameter_update_ix(addresses: &Addresses, authority: &Pubkey, kind: u8) -> Instruction {
+  laminar_ix(
+    laminar::accounts::CancelParameterUpdate {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change: pending_param_change_address(kind),
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::CancelParameterUpdate { kind },
+  )
+}
+
+pub fn health_check_ix(addresses: &Addresses, caller: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::HealthCheck {
+      caller: *caller,
+      global_state: addresses.global_state,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::HealthCheck {},
+  )
+}
+
+/// Burn a sub-minimum amUSD or aSOL balance from `user` into the rounding reserve.
+pub fn donate_dust_ix(addresses: &Addresses, user: &UserAccounts, token: DustToken, amount: u64) -> Instruction {
+  let (token_mint, user_token_account) = match token {
+    DustToken::AmUSD => (addresses.amusd_mint, user.amusd_account),
+    DustToken::Asol => (addresses.asol_mint, user.asol_account),
+  };
+  laminar_ix(
+    laminar::accounts::DonateDust {
+      user: user.user,
+      global_state: addresses.global_state,
+      token_mint,
+      user_token_account,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::DonateDust { token, amount },
+  )
+}
+
+fn mint_amusd_accounts(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  recipient_amusd_account: Option<Pubkey>,
+  entry: Option<&StakePoolExit>,
+) -> laminar::accounts::MintAmUSD {
+  laminar::accounts::MintAmUSD {
+    user: user.user,
+    global_state: addresses.global_state,
+    amusd_mint: addresses.amusd_mint,
+    user_amusd_account: user.amusd_account,
+    treasury_amusd_account: addresses.treasury_amusd_account,
+    treasury: addresses.treasury,
+    user_lst_account: user.lst_account,
+    vault: addresses.vault,
+    vault_authority: addresses.vault_authority,
+    lst_mint: addresses.lst_mint,
+    token_program: addresses.token_program,
+    associated_token_program: spl_associated_token_account::ID,
+    system_program: system_program::ID,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    collateral_vault: addresses.collateral_vault,
+    savings_pool: addresses.savings_pool.map(|(pool, _)| pool),
+    savings_amusd_vault: addresses.savings_pool.map(|(_, vault)| vault),
+    user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+    recipient_amusd_account,
+    stake_pool: entry.map(|entry| entry.stake_pool),
+    stake_pool_withdraw_authority: entry.map(|entry| entry.withdraw_authority),
+    reserve_stake: entry.map(|entry| entry.reserve_stake),
+    manager_fee_account: entry.map(|entry| entry.manager_fee_account),
+    stake_pool_mint: entry.map(|_| addresses.lst_mint),
+    stake_pool_program: entry.map(|_| SPL_STAKE_POOL_PROGRAM_ID),
+    event_authority: event_authority_address(),
+    program: laminar::ID,
+  }
+}
+
+pub fn mint_amusd_ix(addresses: &Addresses, user: &UserAccounts, lst_amount: u64, min_amusd_out: u64) -> Instruction {
+  laminar_ix(
+    mint_amusd_accounts(addresses, user, None, None),
+    laminar::instruction::MintAmusd { lst_amount, min_amusd_out },
+  )
+}
+
+/// `mint_amusd_ix` accounts calling `zap_mint_amusd`; `addresses` must be the wSOL leg.
+pub fn zap_mint_amusd_ix(addresses: &Addresses, user: &UserAccounts, lamports: u64, min_amusd_out: u64) -> Instruction {
+  Instruction {
+    data: laminar::instruction::ZapMintAmusd { lamports, min_amusd_out }.data(),
+    ..mint_amusd_ix(addresses, user, lamports, min_amusd_out)
+  }
+}
+
+/// `zap_mint_amusd` into the primary LST, staked for through `entry`'s pool.
+pub fn zap_mint_amusd_stake_ix(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  entry: &StakePoolExit,
+  lamports: u64,
+  min_amusd_out: u64,
+) -> Instruction {
+  laminar_ix(
+    mint_amusd_accounts(addresses, user, None, Some(entry)),
+    laminar::instruction::ZapMintAmusd { lamports, min_amusd_out },
+  )
+}
+
+/// `mint_amusd_ix` accounts calling `mint_amusd_v2` with `args`.
+pub fn mint_amusd_v2_ix(addresses: &Addresses, user: &UserAccounts, args: MintAmusdArgs) -> Instruction {
+  Instruction {
+    data: laminar::instruction::MintAmusdV2 { args: args.clone() }.data(),
+    ..mint_amusd_ix(addresses, user, args.lst_amount, args.min_amusd_out)
+  }
+}
+
+/// `mint_amusd_v2_ix` minting to `recipient_amusd_account`.
+pub fn mint_amusd_to_recipient_ix(addresses: &Addresses, user: &UserAccounts, args: MintAmusdArgs, recipient_amusd_account: &Pubkey) -> Instruction {
+  laminar_ix(
+    mint_amusd_accounts(addresses, user, Some(*recipient_amusd_account), None),
+    laminar::instruction::MintAmusdV2 { args },
+  )
+}
+
+fn redeem_amusd_accounts(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  recipient_lst_account: Option<Pubkey>,
+  exit: Option<&StakePoolExit>,
+) -> laminar::accounts::RedeemAmUSD {
+  laminar::accounts::RedeemAmUSD {
+    user: user.user,
+    global_state: addresses.global_state,
+    amusd_mint: addresses.amusd_mint,
+    user_amusd_account: user.amusd_account,
+    treasury: addresses.treasury,
+    treasury_amusd_account: addresses.treasury_amusd_account,
+    user_lst_account: user.lst_account,
+    vault: addresses.vault,
+    vault_authority: addresses.vault_authority,
+    lst_mint: addresses.lst_mint,
+    token_program: addresses.token_program,
+    associated_token_program: spl_associated_token_account::ID,
+    system_program: system_program::ID,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    collateral_vault: addresses.collateral_vault,
+    savings_pool: addresses.savings_pool.map(|(pool, _)| pool),
+    savings_amusd_vault: addresses.savings_pool.map(|(_, vault)| vault),
+    user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+    recipient_lst_account,
+    stake_pool: exit.map(|exit| exit.stake_pool),
+    stake_pool_withdraw_authority: exit.map(|exit| exit.withdraw_authority),
+    reserve_stake: exit.map(|exit| exit.reserve_stake),
+    manager_fee_account: exit.map(|exit| exit.manager_fee_account),
+    stake_history: exit.map(|_| sysvar::stake_history::ID),
+    stake_program: exit.map(|_| STAKE_PROGRAM_ID),
+    stake_pool_program: exit.map(|_| SPL_STAKE_POOL_PROGRAM_ID),
+    stake_pool_mint: exit.map(|_| addresses.lst_mint),
+    sol_unwrap_account: None,
+    event_authority: event_authority_address(),
+    program: laminar::ID,
+  }
+}
+
+/// Unchecked against the haircut; callers outside this crate go through
+/// `redeem_amusd_quoted_ix`.
+pub(crate) fn redeem_amusd_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, min_lst_out: u64) -> Instruction {
+  laminar_ix(
+    redeem_amusd_accounts(addresses, user, None, None),
+    laminar::instruction::RedeemAmusd { amusd_amount, min_lst_out },
+  )
+}
+
+/// `redeem_amusd_ix` accounts calling `redeem_amusd_to_sol`; `addresses` must be the wSOL leg.
+pub fn redeem_amusd_to_sol_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, min_sol_out: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::RedeemAmUSD {
+      sol_unwrap_account: Some(sol_unwrap_address(&user.user)),
+      ..redeem_amusd_accounts(addresses, user, None, None)
+    },
+    laminar::instruction::RedeemAmusdToSol { amusd_amount, min_sol_out },
+  )
+}
+
+/// `redeem_amusd_to_sol` out of the primary LST, unstaked through `exit`'s pool.
+pub fn redeem_amusd_unstake_ix(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  exit: &StakePoolExit,
+  amusd_amount: u64,
+  min_sol_out: u64,
+) -> Instruction {
+  laminar_ix(
+    redeem_amusd_accounts(addresses, user, None, Some(exit)),
+    laminar::instruction::RedeemAmusdToSol { amusd_amount, min_sol_out },
+  )
+}
+
+/// `redeem_amusd_ix` accounts calling `redeem_amusd_v2` with `args`.
+pub fn redeem_amusd_v2_ix(addresses: &Addresses, user: &UserAccounts, args: RedeemAmusdArgs) -> Instruction {
+  Instruction {
+    data: laminar::instruction::RedeemAmusdV2 { args: args.clone() }.data(),
+    ..redeem_amusd_ix(addresses, user, args.amusd_amount, args.min_lst_out)
+  }
+}
+
+/// `redeem_amusd_v2_ix` paying the LST out to `recipient_lst_account`.
+pub fn redeem_amusd_to_recipient_ix(addresses: &Addresses, user: &UserAccounts, args: RedeemAmusdArgs, recipient_lst_account: &Pubkey) -> Instruction {
+  laminar_ix(
+    redeem_amusd_accounts(addresses, user, Some(*recipient_lst_account), None),
+    laminar::instruction::RedeemAmusdV2 { args },
+  )
+}
+
+fn mint_asol_accounts(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  recipient_asol_account: Option<Pubkey>,
+  entry: Option<&StakePoolExit>,
+) -> laminar::accounts::MintAsol {
+  laminar::accounts::MintAsol {
+    user: user.user,
+    global_state: addresses.global_state,
+    asol_mint: addresses.asol_mint,
+    user_asol_account: user.asol_account,
+    treasury_asol_account: addresses.treasury_asol_account,
+    treasury: addresses.treasury,
+    user_lst_account: user.lst_account,
+    vault: addresses.vault,
+    vault_authority: addresses.vault_authority,
+    lst_mint: addresses.lst_mint,
+    token_program: addresses.token_program,
+    associated_token_program: spl_associated_token_account::ID,
+    system_program: system_program::ID,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    collateral_vault: addresses.collateral_vault,
+    user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+    recipient_asol_account,
+    stake_pool: entry.map(|entry| entry.stake_pool),
+    stake_pool_withdraw_authority: entry.map(|entry| entry.withdraw_authority),
+    reserve_stake: entry.map(|entry| entry.reserve_stake),
+    manager_fee_account: entry.map(|entry| entry.manager_fee_account),
+    stake_pool_mint: entry.map(|_| addresses.lst_mint),
+    stake_pool_program: entry.map(|_| SPL_STAKE_POOL_PROGRAM_ID),
+    event_authority: event_authority_address(),
+    program: laminar::ID,
+  }
+}
+
+pub fn mint_asol_ix(addresses: &Addresses, user: &UserAccounts, lst_amount: u64, min_asol_out: u64) -> Instruction {
+  laminar_ix(
+    mint_asol_accounts(addresses, user, None, None),
+    laminar::instruction::MintAsol { lst_amount, min_asol_out },
+  )
+}
+
+/// `mint_asol_ix` accounts calling `zap_mint_asol`; `addresses` must be the wSOL leg.
+pub fn zap_mint_asol_ix(addresses: &Addresses, user: &UserAccounts, lamports: u64, min_asol_out: u64) -> Instruction {
+  Instruction {
+    data: laminar::instruction::ZapMintAsol { lamports, min_asol_out }.data(),
+    ..mint_asol_ix(addresses, user, lamports, min_asol_out)
+  }
+}
+
+/// `zap_mint_asol` into the primary LST, staked for through `entry`'s pool.
+pub fn zap_mint_asol_stake_ix(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  entry: &StakePoolExit,
+  lamports: u64,
+  min_asol_out: u64,
+) -> Instruction {
+  laminar_ix(
+    mint_asol_accounts(addresses, user, None, Some(entry)),
+    laminar::instruction::ZapMintAsol { lamports, min_asol_out },
+  )
+}
+
+/// `mint_asol_ix` accounts calling `mint_asol_v2` with `args`.
+pub fn mint_asol_v2_ix(addresses: &Addresses, user: &UserAccounts, args: MintAsolArgs) -> Instruction {
+  Instruction {
+    data: laminar::instruction::MintAsolV2 { args: args.clone() }.data(),
+    ..mint_asol_ix(addresses, user, args.lst_amount, args.min_asol_out)
+  }
+}
+
+/// `mint_asol_v2_ix` minting to `recipient_asol_account`.
+pub fn mint_asol_to_recipient_ix(addresses: &Addresses, user: &UserAccounts, args: MintAsolArgs, recipient_asol_account: &Pubkey) -> Instruction {
+  laminar_ix(
+    mint_asol_accounts(addresses, user, Some(*recipient_asol_account), None),
+    laminar::instruction::MintAsolV2 { args },
+  )
+}
+
+pub fn mint_pair_ix(addresses: &Addresses, user: &UserAccounts, lst_amount: u64, min_amusd_out: u64, min_asol_out: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::MintPair {
+      user: user.user,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      user_amusd_account: user.amusd_account,
+      user_asol_account: user.asol_account,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury_asol_account: addresses.treasury_asol_account,
+      treasury: addresses.treasury,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      collateral_vault: addresses.collateral_vault,
+      savings_pool: addresses.savings_pool.map(|(pool, _)| pool),
+      savings_amusd_vault: addresses.savings_pool.map(|(_, vault)| vault),
+      user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::MintPair { lst_amount, min_amusd_out, min_asol_out },
+  )
+}
+
+/// `asol_amount` 0 lets the program size the aSOL leg to hold CR.
+pub fn redeem_pair_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, asol_amount: u64, min_lst_out: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::RedeemPair {
+      user: user.user,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      user_amusd_account: user.amusd_account,
+      user_asol_account: user.asol_account,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury_asol_account: addresses.treasury_asol_account,
+      treasury: addresses.treasury,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      collateral_vault: addresses.collateral_vault,
+      savings_pool: addresses.savings_pool.map(|(pool, _)| pool),
+      savings_amusd_vault: addresses.savings_pool.map(|(_, vault)| vault),
+      user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::RedeemPair { amusd_amount, asol_amount, min_lst_out },
+  )
+}
+
+fn convert_accounts(addresses: &Addresses, user: &UserAccounts) -> laminar::accounts::Convert {
+  laminar::accounts::Convert {
+    user: user.user,
+    global_state: addresses.global_state,
+    amusd_mint: addresses.amusd_mint,
+    asol_mint: addresses.asol_mint,
+    user_amusd_account: user.amusd_account,
+    user_asol_account: user.asol_account,
+    treasury_amusd_account: addresses.treasury_amusd_account,
+    treasury_asol_account: addresses.treasury_asol_account,
+    treasury: addresses.treasury,
+    token_program: addresses.token_program,
+    associated_token_program: spl_associated_token_account::ID,
+    system_program: system_program::ID,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+    event_authority: event_authority_address(),
+    program: laminar::ID,
+  }
+}
+
+pub fn convert_amusd_to_asol_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, min_asol_out: u64) -> Instruction {
+  laminar_ix(
+    convert_accounts(addresses, user),
+    laminar::instruction::ConvertAmusdToAsol { amusd_amount, min_asol_out },
+  )
+}
+
+pub fn convert_asol_to_amusd_ix(addresses: &Addresses, user: &UserAccounts, asol_amount: u64, min_amusd_out: u64) -> Instruction {
+  laminar_ix(
+    convert_accounts(addresses, user),
+    laminar::instruction::ConvertAsolToAmusd { asol_amount, min_amusd_out },
+  )
+}
+
+fn quote_trade_accounts(addresses: &Addresses) -> laminar::accounts::QuoteTrade {
+  laminar::accounts::QuoteTrade {
+    global_state: addresses.global_state,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    collateral_vault: addresses.collateral_vault,
+  }
+}
+
+pub fn quote_mint_amusd_ix(addresses: &Addresses, lst_amount: u64) -> Instruction {
+  laminar_ix(quote_trade_accounts(addresses), laminar::instruction::QuoteMintAmusd { lst_amount })
+}
+
+pub fn quote_redeem_amusd_ix(addresses: &Addresses, amusd_amount: u64) -> Instruction {
+  laminar_ix(quote_trade_accounts(addresses), laminar::instruction::QuoteRedeemAmusd { amusd_amount })
+}
+
+pub fn quote_mint_asol_ix(addresses: &Addresses, lst_amount: u64) -> Instruction {
+  laminar_ix(quote_trade_accounts(addresses), laminar::instruction::QuoteMintAsol { lst_amount })
+}
+
+pub fn quote_redeem_asol_ix(addresses: &Addresses, asol_amount: u64) -> Instruction {
+  laminar_ix(quote_trade_accounts(addresses), laminar::instruction::QuoteRedeemAsol { asol_amount })
+}
+
+/// Refuse a quote that pays below par unless the caller acknowledged it.
+fn check_haircut(quote: &TradeQuote, acknowledge_haircut: bool) -> Result<(), ClientError> {
+  if quote.insolvency_mode && !acknowledge_haircut {
+    return Err(ClientError::HaircutNotAcknowledged {
+      haircut_bps: quote.haircut_bps,
+      par_net: quote.par_net,
+      net: quote.net,
+    });
+  }
+  Ok(())
+}
+
+/// `redeem_amusd` for a `quote_redeem_amusd_ix` result, with `quote.net`
+/// as the slippage bound. Refused in insolvency mode without `acknowledge_haircut`.
+pub fn redeem_amusd_quoted_ix(addresses: &Addresses, user: &UserAccounts, quote: &TradeQuote, acknowledge_haircut: bool) -> Result<Instruction, ClientError> {
+  check_haircut(quote, acknowledge_haircut)?;
+  Ok(redeem_amusd_ix(addresses, user, quote.gross, quote.net))
+}
+
+/// `redeem_asol` for a `quote_redeem_asol_ix` result, with `quote.net`
+/// as the slippage bound. Refused in insolvency mode without `acknowledge_haircut`.
+pub fn redeem_asol_quoted_ix(addresses: &Addresses, user: &UserAccounts, quote: &TradeQuote, acknowledge_haircut: bool) -> Result<Instruction, ClientError> {
+  check_haircut(quote, acknowledge_haircut)?;
+  Ok(redeem_asol_ix(addresses, user, quote.gross, quote.net))
+}
+
+pub fn view_protocol_health_ix(addresses: &Addresses) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ViewProtocolHealth { global_state: addresses.global_state },
+    laminar::instruction::ViewProtocolHealth {},
+  )
+}
+
+pub fn fee_curve_ix(addresses: &Addresses, action: u8) -> Instruction {
+  laminar_ix(
+    laminar::accounts::FeeCurve { global_state: addresses.global_state },
+    laminar::instruction::FeeCurve { action },
+  )
+}
+
+fn redeem_asol_accounts(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  recipient_lst_account: Option<Pubkey>,
+  exit: Option<&StakePoolExit>,
+) -> laminar::accounts::RedeemAsol {
+  laminar::accounts::RedeemAsol {
+    user: user.user,
+    global_state: addresses.global_state,
+    asol_mint: addresses.asol_mint,
+    user_asol_account: user.asol_account,
+    treasury: addresses.treasury,
+    treasury_asol_account: addresses.treasury_asol_account,
+    user_lst_account: user.lst_account,
+    vault: addresses.vault,
+    vault_authority: addresses.vault_authority,
+    lst_mint: addresses.lst_mint,
+    token_program: addresses.token_program,
+    associated_token_program: spl_associated_token_account::ID,
+    system_program: system_program::ID,
+    clock: sysvar::clock::ID,
+    price_update: addresses.price_update,
+    switchboard_feed: addresses.switchboard_feed,
+    collateral_vault: addresses.collateral_vault,
+    user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+    recipient_lst_account,
+    stake_pool: exit.map(|exit| exit.stake_pool),
+    stake_pool_withdraw_authority: exit.map(|exit| exit.withdraw_authority),
+    reserve_stake: exit.map(|exit| exit.reserve_stake),
+    manager_fee_account: exit.map(|exit| exit.manager_fee_account),
+    stake_history: exit.map(|_| sysvar::stake_history::ID),
+    stake_program: exit.map(|_| STAKE_PROGRAM_ID),
+    stake_pool_program: exit.map(|_| SPL_STAKE_POOL_PROGRAM_ID),
+    stake_pool_mint: exit.map(|_| addresses.lst_mint),
+    sol_unwrap_account: None,
+    event_authority: event_authority_address(),
+    program: laminar::ID,
+  }
+}
+
+/// Unchecked against insolvency; callers outside this crate go through
+/// `redeem_asol_quoted_ix`.
+pub(crate) fn redeem_asol_ix(addresses: &Addresses, user: &UserAccounts, asol_amount: u64, min_lst_out: u64) -> Instruction {
+  laminar_ix(
+    redeem_asol_accounts(addresses, user, None, None),
+    laminar::instruction::RedeemAsol { asol_amount, min_lst_out },
+  )
+}
+
+/// `redeem_asol_ix` accounts calling `redeem_asol_to_sol`; `addresses` must be the wSOL leg.
+pub fn redeem_asol_to_sol_ix(addresses: &Addresses, user: &UserAccounts, asol_amount: u64, min_sol_out: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::RedeemAsol {
+      sol_unwrap_account: Some(sol_unwrap_address(&user.user)),
+      ..redeem_asol_accounts(addresses, user, None, None)
+    },
+    laminar::instruction::RedeemAsolToSol { asol_amount, min_sol_out },
+  )
+}
+
+/// `redeem_asol_to_sol` out of the primary LST, unstaked through `exit`'s pool.
+pub fn redeem_asol_unstake_ix(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  exit: &StakePoolExit,
+  asol_amount: u64,
+  min_sol_out: u64,
+) -> Instruction {
+  laminar_ix(
+    redeem_asol_accounts(addresses, user, None, Some(exit)),
+    laminar::instruction::RedeemAsolToSol { asol_amount, min_sol_out },
+  )
+}
+
+/// `redeem_asol_ix` accounts calling `redeem_asol_v2` with `args`.
+pub fn redeem_asol_v2_ix(addresses: &Addresses, user: &UserAccounts, args: RedeemAsolArgs) -> Instruction {
+  Instruction {
+    data: laminar::instruction::RedeemAsolV2 { args: args.clone() }.data(),
+    ..redeem_asol_ix(addresses, user, args.asol_amount, args.min_lst_out)
+  }
+}
+
+/// `redeem_asol_v2_ix` paying the LST out to `recipient_lst_account`.
+pub fn redeem_asol_to_recipient_ix(addresses: &Addresses, user: &UserAccounts, args: RedeemAsolArgs, recipient_lst_account: &Pubkey) -> Instruction {
+  laminar_ix(
+    redeem_asol_accounts(addresses, user, Some(*recipient_lst_account), None),
+    laminar::instruction::RedeemAsolV2 { args },
+  )
+}
+
+/// Queue `amount` of `token_mint` (amUSD or aSOL) as ticket `sequence`, which
+/// must be the protocol's `next_redemption_ticket`.
+pub fn request_redemption_ix(
+  addresses: &Addresses,
+  user: &UserAccounts,
+  token_mint: &Pubkey,
+  sequence: u64,
+  amount: u64,
+  min_lst_out: u64,
+) -> Instruction {
+  let ticket = redemption_ticket_address(sequence);
+  let user_token_account = if *token_mint == addresses.asol_mint { user.asol_account } else { user.amusd_account };
+  laminar_ix(
+    laminar::accounts::RequestRedemption {
+      user: user.user,
+      global_state: addresses.global_state,
+      token_mint: *token_mint,
+      user_token_account,
+      redemption_ticket: ticket,
+      escrow: get_associated_token_address_with_program_id(&ticket, token_mint, &addresses.token_program),
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      user_position: addresses.user_positions.then(|| user_position_address(&user.user)),
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::RequestRedemption { amount, min_lst_out },
+  )
+}
+
+pub fn cancel_redemption_ix(addresses: &Addresses, caller: &Pubkey, ticket: &RedemptionTicket) -> Instruction {
+  let ticket_address = redemption_ticket_address(ticket.sequence);
+  let ata = |wallet: &Pubkey| get_associated_token_address_with_program_id(wallet, &ticket.token_mint, &addresses.token_program);
+  laminar_ix(
+    laminar::accounts::CancelRedemption {
+      caller: *caller,
+      global_state: addresses.global_state,
+      redemption_ticket: ticket_address,
+      owner: ticket.owner,
+      token_mint: ticket.token_mint,
+      escrow: ata(&ticket_address),
+      owner_token_account: ata(&ticket.owner),
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::CancelRedemption {},
+  )
+}
+
+pub fn process_redemption_ix(addresses: &Addresses, cranker: &Pubkey, ticket: &RedemptionTicket) -> Instruction {
+  let ticket_address = redemption_ticket_address(ticket.sequence);
+  let treasury_token_account = if ticket.token_mint == addresses.asol_mint {
+    addresses.treasury_asol_account
+  } else {
+    addresses.treasury_amusd_account
+  };
+  laminar_ix(
+    laminar::accounts::ProcessRedemption {
+      cranker: *cranker,
+      global_state: addresses.global_state,
+      redemption_ticket: ticket_address,
+      owner: ticket.owner,
+      token_mint: ticket.token_mint,
+      escrow: get_associated_token_address_with_program_id(&ticket_address, &ticket.token_mint, &addresses.token_program),
+      treasury: addresses.treasury,
+      treasury_token_account,
+      owner_lst_account: get_associated_token_address_with_program_id(&ticket.owner, &addresses.lst_mint, &addresses.token_program),
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      collateral_vault: addresses.collateral_vault,
+      savings_pool: addresses.savings_pool.map(|(pool, _)| pool),
+      savings_amusd_vault: addresses.savings_pool.map(|(_, vault)| vault),
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ProcessRedemption {},
+  )
+}
+
+pub fn fund_insurance_ix(addresses: &Addresses, user: &UserAccounts, lst_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::FundInsurance {
+      user: user.user,
+      global_state: addresses.global_state,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::FundInsurance { lst_amount },
+  )
+}
+
+pub fn flash_loan_lst_ix(addresses: &Addresses, borrower: &Pubkey, receiver_lst_account: &Pubkey, lst_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::FlashLoanLst {
+      borrower: *borrower,
+      global_state: addresses.global_state,
+      receiver_lst_account: *receiver_lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      instructions_sysvar: sysvar::instructions::ID,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::FlashLoanLst { lst_amount },
+  )
+}
+
+pub fn add_collateral_type_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  lst_to_sol_rate: u64,
+  collateral_weight_bps: u64,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::AddCollateralType {
+      authority: *authority,
+      global_state: addresses.global_state,
+      collateral_vault: addresses.collateral_vault.expect("collateral addresses"),
+      vault: addresses.vault,
+      lst_mint: addresses.lst_mint,
+      vault_authority: addresses.vault_authority,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::AddCollateralType { lst_to_sol_rate, collateral_weight_bps },
+  )
+}
+
+pub fn set_collateral_status_ix(addresses: &Addresses, authority: &Pubkey, new_status: u8, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::SetCollateralStatus {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      collateral_vault: addresses.collateral_vault.expect("collateral addresses"),
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::SetCollateralStatus { lst_mint: addresses.lst_mint, new_status },
+  )
+}
+
+/// `from` and `to` are leg addresses: `Addresses` itself for the primary LST,
+/// `with_collateral` for a registered one. The admin's ATAs settle both sides.
+pub fn migrate_collateral_ix(from: &Addresses, to: &Addresses, authority: &Pubkey, lst_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::MigrateCollateral {
+      authority: *authority,
+      global_state: from.global_state,
+      from_mint: from.lst_mint,
+      from_collateral_vault: from.collateral_vault,
+      from_vault: from.vault,
+      from_vault_authority: from.vault_authority,
+      authority_from_account: get_associated_token_address_with_program_id(authority, &from.lst_mint, &from.token_program),
+      to_mint: to.lst_mint,
+      to_collateral_vault: to.collateral_vault,
+      to_vault: to.vault,
+      to_vault_authority: to.vault_authority,
+      authority_to_account: get_associated_token_address_with_program_id(authority, &to.lst_mint, &to.token_program),
+      token_program: from.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::MigrateCollateral { lst_amount, from_lst: from.lst_mint, to_lst: to.lst_mint },
+  )
+}
+
+/// `collateral_vaults` go in as remaining accounts, in the order given.
+pub fn refresh_tvl_ix(addresses: &Addresses, caller: &Pubkey, collateral_vaults: &[Pubkey]) -> Instruction {
+  let mut ix = laminar_ix(
+    laminar::accounts::RefreshTvl {
+      caller: *caller,
+      global_state: addresses.global_state,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::RefreshTvl {},
+  );
+  ix.accounts.extend(collateral_vaults.iter().map(|vault| AccountMeta::new_readonly(*vault, false)));
+  ix
+}
+
+pub fn flash_repay_lst_ix(addresses: &Addresses, user: &UserAccounts) -> Instruction {
+  laminar_ix(
+    laminar::accounts::FlashRepayLst {
+      user: user.user,
+      global_state: addresses.global_state,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::FlashRepayLst {},
+  )
+}
+
+pub fn claim_settlement_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, asol_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ClaimSettlement {
+      user: user.user,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      asol_mint: addresses.asol_mint,
+      user_amusd_account: user.amusd_account,
+      user_asol_account: user.asol_account,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ClaimSettlement { amusd_amount, asol_amount },
+  )
+}
+
+pub fn initialize_stability_pool_ix(addresses: &Addresses, authority: &Pubkey) -> Instruction {
+  laminar_ix(
+    laminar::accounts::InitializeStabilityPool {
+      authority: *authority,
+      global_state: addresses.global_state,
+      stability_pool: addresses.stability_pool,
+      pool_amusd_vault: addresses.stability_pool_amusd_vault,
+      pool_lst_vault: addresses.stability_pool_lst_vault,
+      amusd_mint: addresses.amusd_mint,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::InitializeStabilityPool {},
+  )
+}
+
+pub fn initialize_psm_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  usdc_mint: &Pubkey,
+  psm_fee_bps: u64,
+  psm_debt_ceiling: u64,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::InitializePsm {
+      authority: *authority,
+      global_state: addresses.global_state,
+      psm_vault: addresses.psm_vault,
+      usdc_mint: *usdc_mint,
+      vault_authority: addresses.vault_authority,
+      token_program: addresses.token_program,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::InitializePsm { psm_fee_bps, psm_debt_ceiling },
+  )
+}
+
+pub fn update_psm_parameters_ix(addresses: &Addresses, authority: &Pubkey, new_psm_fee_bps: u64, new_psm_debt_ceiling: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdatePsmParameters {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdatePsmParameters { new_psm_fee_bps, new_psm_debt_ceiling },
+  )
+}
+
+/// `user_usdc_account` holds `addresses.psm_usdc_mint`, which must be set.
+pub fn psm_swap_usdc_for_amusd_ix(addresses: &Addresses, user: &UserAccounts, user_usdc_account: &Pubkey, usdc_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::PsmSwapUsdcForAmusd {
+      user: user.user,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      user_amusd_account: user.amusd_account,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury: addresses.treasury,
+      user_usdc_account: *user_usdc_account,
+      psm_vault: addresses.psm_vault,
+      usdc_mint: addresses.psm_usdc_mint.expect("PSM not initialized"),
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::PsmSwapUsdcForAmusd { usdc_amount },
+  )
+}
+
+/// `user_usdc_account` holds `addresses.psm_usdc_mint`, which must be set.
+pub fn psm_swap_amusd_for_usdc_ix(addresses: &Addresses, user: &UserAccounts, user_usdc_account: &Pubkey, amusd_amount: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::PsmSwapAmusdForUsdc {
+      user: user.user,
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      user_amusd_account: user.amusd_account,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury: addresses.treasury,
+      user_usdc_account: *user_usdc_account,
+      psm_vault: addresses.psm_vault,
+      usdc_mint: addresses.psm_usdc_mint.expect("PSM not initialized"),
+      vault_authority: addresses.vault_authority,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::PsmSwapAmusdForUsdc { amusd_amount },
+  )
+}
+
+pub fn initialize_savings_pool_ix(addresses: &Addresses, authority: &Pubkey) -> Instruction {
+  let savings_pool = savings_pool_address();
+  laminar_ix(
+    laminar::accounts::InitializeSavingsPool {
+      authority: *authority,
+      global_state: addresses.global_state,
+      savings_pool,
+      pool_amusd_vault: get_associated_token_address_with_program_id(&savings_pool, &addresses.amusd_mint, &addresses.token_program),
+      amusd_mint: addresses.amusd_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::InitializeSavingsPool {},
+  )
+}
+
+pub fn update_savings_fee_split_ix(addresses: &Addresses, authority: &Pubkey, new_savings_fee_split_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateSavingsFeeSplit {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      savings_pool: savings_pool_address(),
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateSavingsFeeSplit { new_savings_fee_split_bps },
+  )
+}
+
+/// `addresses.savings_pool` must be set.
+pub fn deposit_savings_ix(addresses: &Addresses, user: &UserAccounts, amount: u64) -> Instruction {
+  let (savings_pool, pool_amusd_vault) = addresses.savings_pool.expect("Savings Pool not initialized");
+  laminar_ix(
+    laminar::accounts::DepositSavings {
+      user: user.user,
+      global_state: addresses.global_state,
+      savings_pool,
+      savings_deposit: savings_deposit_address(&user.user),
+      pool_amusd_vault,
+      user_amusd_account: user.amusd_account,
+      amusd_mint: addresses.amusd_mint,
+      token_program: addresses.token_program,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::DepositSavings { amount },
+  )
+}
+
+/// `addresses.savings_pool` must be set.
+pub fn withdraw_savings_ix(addresses: &Addresses, user: &UserAccounts, shares: u64) -> Instruction {
+  let (savings_pool, pool_amusd_vault) = addresses.savings_pool.expect("Savings Pool not initialized");
+  laminar_ix(
+    laminar::accounts::WithdrawSavings {
+      user: user.user,
+      global_state: addresses.global_state,
+      savings_pool,
+      savings_deposit: savings_deposit_address(&user.user),
+      pool_amusd_vault,
+      user_amusd_account: user.amusd_account,
+      amusd_mint: addresses.amusd_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::WithdrawSavings { shares },
+  )
+}
+
+pub fn accrue_stability_fee_ix(addresses: &Addresses) -> Instruction {
+  laminar_ix(
+    laminar::accounts::AccrueStabilityFee {
+      global_state: addresses.global_state,
+      amusd_mint: addresses.amusd_mint,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury: addresses.treasury,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::AccrueStabilityFee {},
+  )
+}
+
+pub fn update_stability_fee_ix(addresses: &Addresses, authority: &Pubkey, new_stability_fee_bps_per_year: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateStabilityFee {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      amusd_mint: addresses.amusd_mint,
+      treasury_amusd_account: addresses.treasury_amusd_account,
+      treasury: addresses.treasury,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateStabilityFee { new_stability_fee_bps_per_year },
+  )
+}
+
+pub fn update_supply_caps_ix(addresses: &Addresses, authority: &Pubkey, new_amusd_supply_cap: u64, new_asol_supply_cap: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateSupplyCaps {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateSupplyCaps { new_amusd_supply_cap, new_asol_supply_cap },
+  )
+}
+
+pub fn update_tvl_cap_ix(addresses: &Addresses, authority: &Pubkey, new_max_total_lst_amount: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateTvlCap {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateTvlCap { new_max_total_lst_amount },
+  )
+}
+
+pub fn update_transaction_limits_ix(
+  addresses: &Addresses,
+  authority: &Pubkey,
+  new_max_single_deposit_lst: u64,
+  new_max_single_redeem_units: u64,
+  pending_param_change: Option<Pubkey>,
+) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateTransactionLimits {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateTransactionLimits { new_max_single_deposit_lst, new_max_single_redeem_units },
+  )
+}
+
+pub fn update_per_user_cap_ix(addresses: &Addresses, authority: &Pubkey, new_per_user_amusd_cap: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdatePerUserCap {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdatePerUserCap { new_per_user_amusd_cap },
+  )
+}
+
+pub fn update_mint_round_ix(addresses: &Addresses, authority: &Pubkey, new_max_asol_mint_per_round: u64, new_mint_round_slots: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMintRound {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMintRound { new_max_asol_mint_per_round, new_mint_round_slots },
+  )
+}
+
+pub fn update_min_action_gap_ix(addresses: &Addresses, authority: &Pubkey, new_min_action_gap_slots: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateMinActionGap {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateMinActionGap { new_min_action_gap_slots },
+  )
+}
+
+pub fn update_asol_redeem_cooldown_ix(addresses: &Addresses, authority: &Pubkey, new_asol_redeem_cooldown_slots: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdateAsolRedeemCooldown {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdateAsolRedeemCooldown { new_asol_redeem_cooldown_slots },
+  )
+}
+
+pub fn update_pair_cr_tolerance_ix(addresses: &Addresses, authority: &Pubkey, new_pair_cr_tolerance_bps: u64, pending_param_change: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::UpdatePairCrTolerance {
+      authority: *authority,
+      global_state: addresses.global_state,
+      pending_param_change,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::UpdatePairCrTolerance { new_pair_cr_tolerance_bps },
+  )
+}
+
+/// `epoch_record` is needed only for a position left in an emptied epoch.
+pub fn deposit_stability_ix(addresses: &Addresses, user: &UserAccounts, amount: u64, epoch_record: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::DepositStability {
+      user: user.user,
+      global_state: addresses.global_state,
+      stability_pool: addresses.stability_pool,
+      stability_deposit: stability_deposit_address(&user.user),
+      epoch_record,
+      pool_amusd_vault: addresses.stability_pool_amusd_vault,
+      user_amusd_account: user.amusd_account,
+      amusd_mint: addresses.amusd_mint,
+      token_program: addresses.token_program,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::DepositStability { amount },
+  )
+}
+
+pub fn withdraw_stability_ix(addresses: &Addresses, user: &UserAccounts, amount: u64, epoch_record: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::WithdrawStability {
+      user: user.user,
+      global_state: addresses.global_state,
+      stability_pool: addresses.stability_pool,
+      stability_deposit: stability_deposit_address(&user.user),
+      epoch_record,
+      pool_amusd_vault: addresses.stability_pool_amusd_vault,
+      pool_lst_vault: addresses.stability_pool_lst_vault,
+      user_amusd_account: user.amusd_account,
+      user_lst_account: user.lst_account,
+      amusd_mint: addresses.amusd_mint,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::WithdrawStability { amount },
+  )
+}
+
+/// `epoch` is the pool's current epoch, whose record is written if the drawdown empties it.
+pub fn trigger_drawdown_ix(addresses: &Addresses, keeper: &Pubkey, epoch: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::TriggerDrawdown {
+      keeper: *keeper,
+      global_state: addresses.global_state,
+      stability_pool: addresses.stability_pool,
+      epoch_record: stability_epoch_record_address(epoch),
+      pool_amusd_vault: addresses.stability_pool_amusd_vault,
+      pool_lst_vault: addresses.stability_pool_lst_vault,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      amusd_mint: addresses.amusd_mint,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      system_program: system_program::ID,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::TriggerDrawdown {},
+  )
+}
+
+pub fn claim_stability_gains_ix(addresses: &Addresses, user: &UserAccounts, epoch_record: Option<Pubkey>) -> Instruction {
+  laminar_ix(
+    laminar::accounts::ClaimStabilityGains {
+      user: user.user,
+      global_state: addresses.global_state,
+      stability_pool: addresses.stability_pool,
+      stability_deposit: stability_deposit_address(&user.user),
+      epoch_record,
+      pool_lst_vault: addresses.stability_pool_lst_vault,
+      user_lst_account: user.lst_account,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      clock: sysvar::clock::ID,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::ClaimStabilityGains {},
+  )
+}
+
+pub fn rebalance_ix(addresses: &Addresses, user: &UserAccounts, max_lst_amount: u64, min_asol_out: u64) -> Instruction {
+  laminar_ix(
+    laminar::accounts::Rebalance {
+      user: user.user,
+      global_state: addresses.global_state,
+      asol_mint: addresses.asol_mint,
+      user_asol_account: user.asol_account,
+      treasury_asol_account: addresses.treasury_asol_account,
+      treasury: addresses.treasury,
+      user_lst_account: user.lst_account,
+      vault: addresses.vault,
+      vault_authority: addresses.vault_authority,
+      lst_mint: addresses.lst_mint,
+      token_program: addresses.token_program,
+      associated_token_program: spl_associated_token_account::ID,
+      clock: sysvar::clock::ID,
+      price_update: addresses.price_update,
+      switchboard_feed: addresses.switchboard_feed,
+      event_authority: event_authority_address(),
+      program: laminar::ID,
+    },
+    laminar::instruction::Rebalance { max_lst_amount, min_asol_out },
+  )
+}
+
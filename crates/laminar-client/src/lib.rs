@@ -0,0 +1,23 @@
+//! Laminar client - typed instruction builders for Rust integrators
+//! Builders take the protocol's `Addresses` plus the caller's accounts and
+//! return a ready-to-sign `Instruction`; account metas come from the
+//! program's own Anchor structs, so they cannot drift from the IDL.
+//!
+//! ```ignore
+//! let rpc = RpcClient::new("http://127.0.0.1:8899".to_string());
+//! let state = laminar_client::fetch_global_state(&rpc)?;
+//! let addresses = Addresses::from_global_state(&state, spl_token::ID);
+//! let ix = laminar_client::mint_asol_ix(&addresses, &user_accounts, lst_amount, min_asol_out);
+//! ```
+
+pub mod addresses;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod rpc;
+
+pub use addresses::{Addresses, StakePoolExit, UserAccounts};
+pub use events::*;
+pub use instructions::*;
+pub use pda::*;
+pub use rpc::*;
@@ -0,0 +1,78 @@
+//! PDA derivation for every account Laminar owns or signs for
+
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use laminar::state::{
+  GLOBAL_STATE_SEED, PENDING_PARAM_CHANGE_SEED, PSM_VAULT_SEED, REDEMPTION_TICKET_SEED, SAVINGS_DEPOSIT_SEED,
+  SAVINGS_POOL_SEED, SOL_UNWRAP_SEED, STABILITY_DEPOSIT_SEED, STABILITY_EPOCH_SEED, STABILITY_POOL_SEED,
+  USER_POSITION_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED,
+};
+use laminar::events::EVENT_AUTHORITY_SEED;
+use solana_sdk::pubkey::Pubkey;
+
+pub fn global_state_address() -> Pubkey {
+  Pubkey::find_program_address(&[GLOBAL_STATE_SEED], &laminar::ID).0
+}
+
+/// Authority over the primary LST vault
+pub fn vault_authority_address() -> Pubkey {
+  Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED], &laminar::ID).0
+}
+
+/// Signer of the self-CPI every event is emitted through
+pub fn event_authority_address() -> Pubkey {
+  Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &laminar::ID).0
+}
+
+pub fn stability_pool_address() -> Pubkey {
+  Pubkey::find_program_address(&[STABILITY_POOL_SEED], &laminar::ID).0
+}
+
+pub fn psm_vault_address() -> Pubkey {
+  Pubkey::find_program_address(&[PSM_VAULT_SEED], &laminar::ID).0
+}
+
+/// The queued change of `kind` (see `ParamChange::kind`)
+pub fn pending_param_change_address(kind: u8) -> Pubkey {
+  Pubkey::find_program_address(&[PENDING_PARAM_CHANGE_SEED, &kind.to_le_bytes()], &laminar::ID).0
+}
+
+/// `wallet`'s associated token account for `mint` under `token_program`
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+  get_associated_token_address_with_program_id(wallet, mint, token_program)
+}
+
+pub fn stability_deposit_address(user: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[STABILITY_DEPOSIT_SEED, user.as_ref()], &laminar::ID).0
+}
+
+pub fn stability_epoch_record_address(epoch: u64) -> Pubkey {
+  Pubkey::find_program_address(&[STABILITY_EPOCH_SEED, &epoch.to_le_bytes()], &laminar::ID).0
+}
+
+pub fn savings_pool_address() -> Pubkey {
+  Pubkey::find_program_address(&[SAVINGS_POOL_SEED], &laminar::ID).0
+}
+
+pub fn savings_deposit_address(user: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[SAVINGS_DEPOSIT_SEED, user.as_ref()], &laminar::ID).0
+}
+
+pub fn user_position_address(user: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[USER_POSITION_SEED, user.as_ref()], &laminar::ID).0
+}
+
+pub fn redemption_ticket_address(sequence: u64) -> Pubkey {
+  Pubkey::find_program_address(&[REDEMPTION_TICKET_SEED, &sequence.to_le_bytes()], &laminar::ID).0
+}
+
+pub fn sol_unwrap_address(user: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[SOL_UNWRAP_SEED, user.as_ref()], &laminar::ID).0
+}
+
+pub fn collateral_vault_address(lst_mint: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[VAULT_SEED, lst_mint.as_ref()], &laminar::ID).0
+}
+
+pub fn collateral_vault_authority_address(lst_mint: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED, lst_mint.as_ref()], &laminar::ID).0
+}
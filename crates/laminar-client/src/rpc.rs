@@ -0,0 +1,128 @@
+//! RPC helpers - account fetches and return-data simulation
+
+use std::fmt;
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use laminar::state::{
+  CollateralVault, GlobalState, RedemptionTicket, SavingsDeposit, SavingsPool, StabilityDeposit, StabilityPool,
+  UserPosition,
+};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+  instruction::Instruction,
+  pubkey::Pubkey,
+  signature::{Keypair, Signer},
+  transaction::Transaction,
+};
+
+use crate::pda::*;
+
+#[derive(Debug)]
+pub enum ClientError {
+  /// The RPC request itself failed (including a missing account)
+  Rpc(Box<solana_rpc_client_api::client_error::Error>),
+  /// The account exists but is not the expected Laminar type: wrong
+  /// discriminator, wrong owner or a layout from another program version
+  Deserialize { address: Pubkey, error: String },
+  /// A simulated transaction failed or returned undecodable data
+  Simulation { error: String, logs: Vec<String> },
+  /// The quoted redeem pays below par and the caller did not pass
+  /// `acknowledge_haircut`
+  HaircutNotAcknowledged { haircut_bps: u64, par_net: u64, net: u64 },
+}
+
+impl fmt::Display for ClientError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ClientError::Rpc(error) => write!(f, "rpc request failed: {}", error),
+      ClientError::Deserialize { address, error } => write!(f, "{} failed to deserialize: {}", address, error),
+      ClientError::Simulation { error, logs } => write!(f, "simulation failed: {}\n{}", error, logs.join("\n")),
+      ClientError::HaircutNotAcknowledged { haircut_bps, par_net, net } => write!(
+        f,
+        "redeem pays {} instead of {} at par ({} bps haircut) and was not acknowledged",
+        net, par_net, haircut_bps
+      ),
+    }
+  }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<solana_rpc_client_api::client_error::Error> for ClientError {
+  fn from(error: solana_rpc_client_api::client_error::Error) -> Self {
+    ClientError::Rpc(Box::new(error))
+  }
+}
+
+/// Decode raw account bytes as `T`, checking its Anchor discriminator.
+pub fn decode<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T, ClientError> {
+  T::try_deserialize(&mut &data[..]).map_err(|error| ClientError::Deserialize {
+    address: *address,
+    error: error.to_string(),
+  })
+}
+
+/// Fetch and decode the account at `address`, rejecting accounts Laminar does not own.
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T, ClientError> {
+  let account = rpc.get_account(address)?;
+  if account.owner != laminar::ID {
+    return Err(ClientError::Deserialize {
+      address: *address,
+      error: format!("owned by {}, not the Laminar program", account.owner),
+    });
+  }
+  decode(address, &account.data)
+}
+
+pub fn fetch_global_state(rpc: &RpcClient) -> Result<GlobalState, ClientError> {
+  fetch(rpc, &global_state_address())
+}
+
+pub fn fetch_collateral_vault(rpc: &RpcClient, lst_mint: &Pubkey) -> Result<CollateralVault, ClientError> {
+  fetch(rpc, &collateral_vault_address(lst_mint))
+}
+
+pub fn fetch_user_position(rpc: &RpcClient, user: &Pubkey) -> Result<UserPosition, ClientError> {
+  fetch(rpc, &user_position_address(user))
+}
+
+pub fn fetch_redemption_ticket(rpc: &RpcClient, sequence: u64) -> Result<RedemptionTicket, ClientError> {
+  fetch(rpc, &redemption_ticket_address(sequence))
+}
+
+pub fn fetch_stability_pool(rpc: &RpcClient) -> Result<StabilityPool, ClientError> {
+  fetch(rpc, &stability_pool_address())
+}
+
+pub fn fetch_stability_deposit(rpc: &RpcClient, user: &Pubkey) -> Result<StabilityDeposit, ClientError> {
+  fetch(rpc, &stability_deposit_address(user))
+}
+
+pub fn fetch_savings_pool(rpc: &RpcClient) -> Result<SavingsPool, ClientError> {
+  fetch(rpc, &savings_pool_address())
+}
+
+pub fn fetch_savings_deposit(rpc: &RpcClient, user: &Pubkey) -> Result<SavingsDeposit, ClientError> {
+  fetch(rpc, &savings_deposit_address(user))
+}
+
+/// Simulate `ixs` with `payer` and decode the program's return data as `T`,
+/// e.g. a `TradeQuote` from `quote_mint_asol_ix`. Nothing is committed.
+pub fn simulate_return<T: AnchorDeserialize>(rpc: &RpcClient, ixs: &[Instruction], payer: &Keypair) -> Result<T, ClientError> {
+  let blockhash = rpc.get_latest_blockhash()?;
+  let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], blockhash);
+  let simulated = rpc.simulate_transaction(&tx)?.value;
+  let logs = simulated.logs.unwrap_or_default();
+  if let Some(error) = simulated.err {
+    return Err(ClientError::Simulation { error: format!("{:?}", error), logs });
+  }
+  let Some(return_data) = simulated.return_data else {
+    return Err(ClientError::Simulation { error: "no return data".to_string(), logs });
+  };
+  STANDARD
+    .decode(&return_data.data.0)
+    .map_err(|error| error.to_string())
+    .and_then(|data| T::try_from_slice(&data).map_err(|error| error.to_string()))
+    .map_err(|error| ClientError::Simulation { error: format!("undecodable return data: {}", error), logs })
+}
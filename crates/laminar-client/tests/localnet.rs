@@ -0,0 +1,671 @@
+//! Localnet walk through every Laminar instruction, built only with laminar-client.
+//! Needs a fresh validator with the program deployed (`anchor localnet`), then:
+//!
+//!   cargo test -p laminar-client --test localnet -- --ignored
+//!
+//! `LAMINAR_RPC_URL` overrides the endpoint and `ANCHOR_WALLET` the payer, which
+//! becomes the protocol authority. SPL setup (mints, ATAs, approvals) goes
+//! straight to the token programs; every Laminar instruction comes from the client.
+
+use std::thread::sleep;
+use std::time::Duration;
+
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token::spl_token;
+use laminar::constants::{DEFAULT_MIN_CR_BPS, DEFAULT_TARGET_CR_BPS};
+use laminar::instructions::{
+    DustToken, FeeCurveSample, LaunchConfig, MintAmusdArgs, MintAsolArgs, ProtocolHealth, RedeemAmusdArgs,
+    RedeemAsolArgs, TradeQuote,
+};
+use laminar::math::{BPS_PRECISION, SOL_PRECISION, USD_PRECISION};
+use laminar::state::{CollateralStatus, GlobalState, ParamChange};
+use laminar_client::*;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+const INITIAL_SOL_PRICE_USD: u64 = 100 * USD_PRECISION;
+const INITIAL_LST_TO_SOL_RATE: u64 = 1_050_000_000;
+const USDC_DECIMALS: u8 = 6;
+
+struct Localnet {
+    rpc: RpcClient,
+    authority: Keypair,
+    user: Keypair,
+    addresses: Addresses,
+}
+
+impl Localnet {
+    fn connect() -> Self {
+        let url = std::env::var("LAMINAR_RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8899".to_string());
+        let wallet = std::env::var("ANCHOR_WALLET")
+            .unwrap_or_else(|_| format!("{}/.config/solana/id.json", std::env::var("HOME").unwrap_or_default()));
+        let authority = read_keypair_file(&wallet).unwrap_or_else(|err| panic!("payer keypair {wallet}: {err}"));
+        let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+        assert!(
+            fetch_global_state(&rpc).is_err(),
+            "GlobalState already exists; run against a fresh validator"
+        );
+
+        let lst_mint = Pubkey::new_unique();
+        let addresses = Addresses::derive(Pubkey::default(), Pubkey::default(), lst_mint, authority.pubkey());
+        Self { rpc, authority, user: Keypair::new(), addresses }
+    }
+
+    fn send(&self, label: &str, ixs: &[Instruction], extra_signers: &[&Keypair]) {
+        let mut signers = vec![&self.authority];
+        signers.extend_from_slice(extra_signers);
+        let blockhash = self.rpc.get_latest_blockhash().unwrap();
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.authority.pubkey()), &signers, blockhash);
+        if let Err(err) = self.rpc.send_and_confirm_transaction(&tx) {
+            panic!("{label} failed: {err}");
+        }
+    }
+
+    fn state(&self) -> GlobalState {
+        fetch_global_state(&self.rpc).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn balance(&self, token_account: &Pubkey) -> u64 {
+        self.rpc
+            .get_token_account_balance(token_account)
+            .map(|balance| balance.amount.parse().unwrap())
+            .unwrap_or(0)
+    }
+
+    fn user_accounts(&self) -> UserAccounts {
+        let ata = |mint: &Pubkey| associated_token_address(&self.user.pubkey(), mint, &self.addresses.token_program);
+        UserAccounts {
+            user: self.user.pubkey(),
+            lst_account: ata(&self.addresses.lst_mint),
+            amusd_account: ata(&self.addresses.amusd_mint),
+            asol_account: ata(&self.addresses.asol_mint),
+        }
+    }
+
+    /// The mock price goes stale after `max_oracle_age_seconds`; re-post it
+    /// before each phase so wall-clock time on localnet does not fail them.
+    fn refresh_oracle(&self) {
+        let state = self.state();
+        let authority = self.authority.pubkey();
+        self.send(
+            "refresh_oracle",
+            &[
+                update_mock_prices_ix(
+                    &self.addresses,
+                    &authority,
+                    state.mock_sol_price_usd,
+                    state.mock_lst_to_sol_rate,
+                    state.mock_oracle_confidence_usd,
+                    false,
+                ),
+                sync_exchange_rate_ix(&self.addresses),
+            ],
+            &[],
+        );
+    }
+
+    fn set_sol_price(&self, sol_price_usd: u64) {
+        let state = self.state();
+        let ix = update_mock_prices_ix(
+            &self.addresses,
+            &self.authority.pubkey(),
+            sol_price_usd,
+            state.mock_lst_to_sol_rate,
+            state.mock_oracle_confidence_usd,
+            false,
+        );
+        self.send("update_mock_prices", &[ix], &[]);
+    }
+
+    fn create_mint(&self, mint: &Keypair, decimals: u8) {
+        let rent = self.rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN).unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &self.authority.pubkey(),
+                &mint.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &self.authority.pubkey(), None, decimals)
+                .unwrap(),
+        ];
+        self.send("create_mint", &ixs, &[mint]);
+    }
+
+    fn create_ata(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let ix = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &self.authority.pubkey(),
+            wallet,
+            mint,
+            &spl_token::ID,
+        );
+        self.send("create_ata", &[ix], &[]);
+        associated_token_address(wallet, mint, &spl_token::ID)
+    }
+
+    fn mint_to(&self, mint: &Pubkey, to: &Pubkey, amount: u64) {
+        let ix = spl_token::instruction::mint_to(&spl_token::ID, mint, to, &self.authority.pubkey(), &[], amount).unwrap();
+        self.send("mint_to", &[ix], &[]);
+    }
+
+    fn wait_slots(&self, slots: u64) {
+        let target = self.rpc.get_slot().unwrap() + slots;
+        while self.rpc.get_slot().unwrap() < target {
+            sleep(Duration::from_millis(200));
+        }
+    }
+
+    /// SOL price at which the primary book sits at `cr_bps`
+    fn price_for_cr(&self, cr_bps: u64) -> u64 {
+        let state = self.state();
+        let tvl = state.total_lst_amount as u128 * state.mock_lst_to_sol_rate as u128 / SOL_PRECISION as u128;
+        let price = cr_bps as u128 * state.amusd_supply as u128 * SOL_PRECISION as u128 / (tvl * BPS_PRECISION as u128);
+        u64::try_from(price).unwrap()
+    }
+}
+
+#[test]
+#[ignore = "needs a localnet validator with laminar deployed"]
+fn every_instruction_through_the_client() {
+    let mut net = Localnet::connect();
+    let authority = net.authority.pubkey();
+    let user = net.user.insecure_clone();
+
+    // Setup: LST and stablecoin mints, a funded user
+    let lst_mint = Keypair::new();
+    let usdc_mint = Keypair::new();
+    let amusd_mint = Keypair::new();
+    let asol_mint = Keypair::new();
+    net.create_mint(&lst_mint, 9);
+    net.create_mint(&usdc_mint, USDC_DECIMALS);
+    net.addresses = Addresses::derive(amusd_mint.pubkey(), asol_mint.pubkey(), lst_mint.pubkey(), authority);
+    net.send(
+        "fund_user",
+        &[system_instruction::transfer(&authority, &user.pubkey(), 100 * SOL_PRECISION)],
+        &[],
+    );
+
+    // initialize + configure_launch while pre-launch
+    net.send(
+        "initialize",
+        &[
+            initialize_ix(
+                &net.addresses,
+                &authority,
+                DEFAULT_MIN_CR_BPS,
+                DEFAULT_TARGET_CR_BPS,
+                INITIAL_SOL_PRICE_USD,
+                INITIAL_LST_TO_SOL_RATE,
+            ),
+            sync_exchange_rate_ix(&net.addresses),
+        ],
+        &[&amusd_mint, &asol_mint],
+    );
+    let state = net.state();
+    assert_eq!(state.authority, authority);
+    assert_eq!(state.amusd_mint, amusd_mint.pubkey());
+    let config = LaunchConfig {
+        min_cr_bps: state.min_cr_bps,
+        target_cr_bps: state.target_cr_bps,
+        fee_amusd_mint_bps: state.fee_amusd_mint_bps,
+        fee_amusd_redeem_bps: state.fee_amusd_redeem_bps,
+        fee_asol_mint_bps: state.fee_asol_mint_bps,
+        fee_asol_redeem_bps: state.fee_asol_redeem_bps,
+        fee_min_multiplier_bps: state.fee_min_multiplier_bps,
+        fee_max_multiplier_bps: state.fee_max_multiplier_bps,
+        max_oracle_staleness_slots: state.max_oracle_staleness_slots,
+        max_conf_bps: state.max_conf_bps,
+        uncertainty_max_bps: state.uncertainty_max_bps,
+        max_lst_stale_epochs: state.max_lst_stale_epochs,
+        max_oracle_deviation_bps: state.max_oracle_deviation_bps,
+        max_price_move_bps: state.max_price_move_bps,
+        fallback_uncertainty_bps: state.fallback_uncertainty_bps,
+        ema_half_life_slots: state.ema_half_life_slots,
+        max_rounding_reserve_lamports: state.max_rounding_reserve_lamports,
+        nav_floor_lamports: state.nav_floor_lamports,
+        max_asol_mint_per_round: state.max_asol_mint_per_round,
+        mint_round_slots: state.mint_round_slots,
+        amusd_supply_cap: state.amusd_supply_cap,
+        asol_supply_cap: state.asol_supply_cap,
+        max_total_lst_amount: state.max_total_lst_amount,
+        max_single_deposit_lst: state.max_single_deposit_lst,
+        max_single_redeem_units: state.max_single_redeem_units,
+        per_user_amusd_cap: state.per_user_amusd_cap,
+        fee_split_insurance_bps: state.fee_split_insurance_bps,
+        savings_fee_split_bps: state.savings_fee_split_bps,
+        stability_fee_bps_per_year: state.stability_fee_bps_per_year,
+        min_action_gap_slots: state.min_action_gap_slots,
+        asol_redeem_cooldown_slots: state.asol_redeem_cooldown_slots,
+        param_timelock_slots: state.param_timelock_slots,
+        max_pause_duration_slots: state.max_pause_duration_slots,
+        rebalance_discount_bps: state.rebalance_discount_bps,
+        recovery_bonus_bps: state.recovery_bonus_bps,
+        max_flash_loan_bps: state.max_flash_loan_bps,
+        psm_fee_bps: state.psm_fee_bps,
+        psm_debt_ceiling: state.psm_debt_ceiling,
+        pair_cr_tolerance_bps: state.pair_cr_tolerance_bps,
+        treasury: state.treasury,
+        treasury_is_pda: false,
+        treasury_owner_program: Pubkey::default(),
+    };
+    net.send(
+        "configure_launch",
+        &[
+            emergency_pause_ix(&net.addresses, &authority, true, true),
+            configure_launch_ix(&net.addresses, &authority, config),
+            emergency_pause_ix(&net.addresses, &authority, false, false),
+        ],
+        &[],
+    );
+    // The client derives the same addresses a live deployment reports
+    let live = Addresses::from_global_state(&net.state(), spl_token::ID);
+    assert_eq!(live.vault, net.addresses.vault);
+    assert_eq!(live.treasury_asol_account, net.addresses.treasury_asol_account);
+
+    let accounts = net.user_accounts();
+    for mint in [lst_mint.pubkey(), amusd_mint.pubkey(), asol_mint.pubkey(), usdc_mint.pubkey()] {
+        net.create_ata(&user.pubkey(), &mint);
+    }
+    let authority_lst = net.create_ata(&authority, &lst_mint.pubkey());
+    net.mint_to(&lst_mint.pubkey(), &accounts.lst_account, 10_000 * SOL_PRECISION);
+    net.mint_to(&lst_mint.pubkey(), &authority_lst, 10_000 * SOL_PRECISION);
+
+    // Read-only views
+    let quote: TradeQuote = simulate_return(&net.rpc, &[quote_mint_asol_ix(&net.addresses, 1_000 * SOL_PRECISION)], &user).unwrap();
+    assert!(quote.net > 0);
+    let curve: FeeCurveSample = simulate_return(&net.rpc, &[fee_curve_ix(&net.addresses, 0)], &user).unwrap();
+    assert_eq!(curve.action, 0);
+
+    // Mints
+    net.send(
+        "mints",
+        &[
+            mint_asol_ix(&net.addresses, &accounts, 1_000 * SOL_PRECISION, quote.net),
+            mint_asol_v2_ix(&net.addresses, &accounts, MintAsolArgs::v1(10 * SOL_PRECISION, 0)),
+            mint_amusd_ix(&net.addresses, &accounts, 100 * SOL_PRECISION, 0),
+            mint_amusd_v2_ix(&net.addresses, &accounts, MintAmusdArgs::v1(10 * SOL_PRECISION, 0)),
+            mint_pair_ix(&net.addresses, &accounts, 10 * SOL_PRECISION, 0, 0),
+        ],
+        &[&user],
+    );
+    let amusd = net.balance(&accounts.amusd_account);
+    let asol = net.balance(&accounts.asol_account);
+    assert!(amusd > 0 && asol > 0);
+
+    for ix in [
+        quote_mint_amusd_ix(&net.addresses, SOL_PRECISION),
+        quote_redeem_amusd_ix(&net.addresses, amusd / 10),
+        quote_redeem_asol_ix(&net.addresses, asol / 10),
+    ] {
+        let quote: TradeQuote = simulate_return(&net.rpc, &[ix], &user).unwrap();
+        assert!(quote.net > 0);
+    }
+    let health: ProtocolHealth = simulate_return(&net.rpc, &[view_protocol_health_ix(&net.addresses)], &user).unwrap();
+    assert!(health.cr_bps > DEFAULT_TARGET_CR_BPS);
+
+    // Redeems and conversions; the plain redeems are built from a fresh quote
+    let quote: TradeQuote = simulate_return(&net.rpc, &[quote_redeem_amusd_ix(&net.addresses, amusd / 20)], &user).unwrap();
+    net.send("redeem_amusd", &[redeem_amusd_quoted_ix(&net.addresses, &accounts, &quote, false).unwrap()], &[&user]);
+    let quote: TradeQuote = simulate_return(&net.rpc, &[quote_redeem_asol_ix(&net.addresses, asol / 20)], &user).unwrap();
+    net.send("redeem_asol", &[redeem_asol_quoted_ix(&net.addresses, &accounts, &quote, false).unwrap()], &[&user]);
+    net.send(
+        "redeems",
+        &[
+            redeem_amusd_v2_ix(&net.addresses, &accounts, RedeemAmusdArgs::v1(amusd / 20, 0)),
+            redeem_asol_v2_ix(&net.addresses, &accounts, RedeemAsolArgs::v1(asol / 20, 0)),
+            redeem_pair_ix(&net.addresses, &accounts, amusd / 20, 0, 0),
+            convert_amusd_to_asol_ix(&net.addresses, &accounts, amusd / 20, 0),
+            convert_asol_to_amusd_ix(&net.addresses, &accounts, asol / 20, 0),
+        ],
+        &[&user],
+    );
+
+    // Redemption queue: one processed ticket, one cancelled and cleared
+    let amusd = net.balance(&accounts.amusd_account);
+    let asol = net.balance(&accounts.asol_account);
+    let sequence = net.state().next_redemption_ticket;
+    net.send(
+        "request_redemption",
+        &[request_redemption_ix(&net.addresses, &accounts, &amusd_mint.pubkey(), sequence, amusd / 10, 0)],
+        &[&user],
+    );
+    let ticket = fetch_redemption_ticket(&net.rpc, sequence).unwrap();
+    net.send("process_redemption", &[process_redemption_ix(&net.addresses, &authority, &ticket)], &[]);
+    net.send(
+        "request_redemption",
+        &[request_redemption_ix(&net.addresses, &accounts, &asol_mint.pubkey(), sequence + 1, asol / 10, 0)],
+        &[&user],
+    );
+    let ticket = fetch_redemption_ticket(&net.rpc, sequence + 1).unwrap();
+    net.send("cancel_redemption", &[cancel_redemption_ix(&net.addresses, &user.pubkey(), &ticket)], &[&user]);
+    let ticket = fetch_redemption_ticket(&net.rpc, sequence + 1).unwrap();
+    net.send("process_redemption", &[process_redemption_ix(&net.addresses, &authority, &ticket)], &[]);
+    assert_eq!(net.state().redemption_queue_head, sequence + 2);
+
+    // Dust, flash loan, keepers
+    net.refresh_oracle();
+    net.send(
+        "dust_and_flash_loan",
+        &[
+            donate_dust_ix(&net.addresses, &accounts, DustToken::AmUSD, 1),
+            flash_loan_lst_ix(&net.addresses, &user.pubkey(), &accounts.lst_account, SOL_PRECISION),
+            flash_repay_lst_ix(&net.addresses, &accounts),
+        ],
+        &[&user],
+    );
+    net.send(
+        "keepers",
+        &[health_check_ix(&net.addresses, &user.pubkey()), accrue_stability_fee_ix(&net.addresses)],
+        &[&user],
+    );
+
+    // Savings Pool
+    net.send(
+        "initialize_savings_pool",
+        &[
+            initialize_savings_pool_ix(&net.addresses, &authority),
+            update_savings_fee_split_ix(&net.addresses, &authority, 1_000, None),
+        ],
+        &[],
+    );
+    let savings_pool = savings_pool_address();
+    net.addresses.savings_pool =
+        Some((savings_pool, associated_token_address(&savings_pool, &amusd_mint.pubkey(), &spl_token::ID)));
+    let amusd = net.balance(&accounts.amusd_account);
+    net.send("deposit_savings", &[deposit_savings_ix(&net.addresses, &accounts, amusd / 10)], &[&user]);
+    let shares = fetch_savings_deposit(&net.rpc, &user.pubkey()).unwrap().shares;
+    assert_eq!(fetch_savings_pool(&net.rpc).unwrap().total_shares, shares);
+    net.send("withdraw_savings", &[withdraw_savings_ix(&net.addresses, &accounts, shares / 2)], &[&user]);
+
+    // Stability Pool; the deposit left behind is drawn down below
+    let amusd = net.balance(&accounts.amusd_account);
+    net.send(
+        "stability_pool",
+        &[
+            initialize_stability_pool_ix(&net.addresses, &authority),
+            deposit_stability_ix(&net.addresses, &accounts, amusd / 2, None),
+            claim_stability_gains_ix(&net.addresses, &accounts, None),
+            withdraw_stability_ix(&net.addresses, &accounts, amusd / 20, None),
+        ],
+        &[&user],
+    );
+    assert!(fetch_stability_deposit(&net.rpc, &user.pubkey()).unwrap().shares > 0);
+
+    // PSM
+    let user_usdc = associated_token_address(&user.pubkey(), &usdc_mint.pubkey(), &spl_token::ID);
+    net.mint_to(&usdc_mint.pubkey(), &user_usdc, 1_000 * USD_PRECISION);
+    net.send(
+        "initialize_psm",
+        &[
+            initialize_psm_ix(&net.addresses, &authority, &usdc_mint.pubkey(), 10, 1_000_000 * USD_PRECISION),
+            update_psm_parameters_ix(&net.addresses, &authority, 10, 1_000_000 * USD_PRECISION, None),
+        ],
+        &[],
+    );
+    net.addresses.psm_usdc_mint = Some(usdc_mint.pubkey());
+    net.send(
+        "psm_swaps",
+        &[
+            psm_swap_usdc_for_amusd_ix(&net.addresses, &accounts, &user_usdc, 100 * USD_PRECISION),
+            psm_swap_amusd_for_usdc_ix(&net.addresses, &accounts, &user_usdc, 50 * USD_PRECISION),
+        ],
+        &[&user],
+    );
+
+    // Admin setters, each re-applying the live value
+    let state = net.state();
+    net.send(
+        "risk_and_fees",
+        &[
+            update_parameters_ix(&net.addresses, &authority, state.min_cr_bps, state.target_cr_bps),
+            update_fee_parameters_ix(
+                &net.addresses,
+                &authority,
+                state.fee_amusd_mint_bps,
+                state.fee_amusd_redeem_bps,
+                state.fee_asol_mint_bps,
+                state.fee_asol_redeem_bps,
+                state.fee_min_multiplier_bps,
+                state.fee_max_multiplier_bps,
+            ),
+            update_fee_split_ix(&net.addresses, &authority, state.fee_split_insurance_bps, None),
+            update_stability_fee_ix(&net.addresses, &authority, state.stability_fee_bps_per_year, None),
+            update_rebalance_discount_ix(&net.addresses, &authority, state.rebalance_discount_bps, None),
+            update_recovery_bonus_ix(&net.addresses, &authority, state.recovery_bonus_bps, None),
+            update_rounding_reserve_cap_ix(&net.addresses, &authority, state.max_rounding_reserve_lamports, None),
+            update_pair_cr_tolerance_ix(&net.addresses, &authority, state.pair_cr_tolerance_bps, None),
+            set_haircut_breaker_ix(&net.addresses, &authority, state.max_episode_haircut_lamports, None),
+        ],
+        &[],
+    );
+    net.send(
+        "limits",
+        &[
+            update_supply_caps_ix(&net.addresses, &authority, state.amusd_supply_cap, state.asol_supply_cap, None),
+            update_tvl_cap_ix(&net.addresses, &authority, state.max_total_lst_amount, None),
+            update_transaction_limits_ix(
+                &net.addresses,
+                &authority,
+                state.max_single_deposit_lst,
+                state.max_single_redeem_units,
+                None,
+            ),
+            update_per_user_cap_ix(&net.addresses, &authority, state.per_user_amusd_cap, None),
+            update_mint_round_ix(&net.addresses, &authority, state.max_asol_mint_per_round, state.mint_round_slots, None),
+            update_min_action_gap_ix(&net.addresses, &authority, state.min_action_gap_slots, None),
+            update_asol_redeem_cooldown_ix(&net.addresses, &authority, state.asol_redeem_cooldown_slots, None),
+        ],
+        &[],
+    );
+    net.send(
+        "oracle",
+        &[
+            update_oracle_parameters_ix(
+                &net.addresses,
+                &authority,
+                state.max_oracle_staleness_slots,
+                state.max_conf_bps,
+                state.uncertainty_max_bps,
+                state.max_lst_stale_epochs,
+            ),
+            update_oracle_bounds_ix(
+                &net.addresses,
+                &authority,
+                state.min_sol_price_usd,
+                state.max_sol_price_usd,
+                state.min_lst_rate,
+                state.max_lst_rate,
+                None,
+            ),
+            update_mock_oracle_confidence_ix(&net.addresses, &authority, state.mock_oracle_confidence_usd, None),
+            update_max_oracle_age_ix(&net.addresses, &authority, state.max_oracle_age_seconds, None),
+            update_max_price_move_ix(&net.addresses, &authority, state.max_price_move_bps, None),
+            // Price moves below must land in one update
+            update_ema_half_life_ix(&net.addresses, &authority, 0, None),
+            set_price_source_ix(&net.addresses, &authority, state.price_source, None),
+            set_secondary_price_source_ix(
+                &net.addresses,
+                &authority,
+                state.secondary_price_source,
+                state.max_oracle_deviation_bps,
+                None,
+            ),
+            set_fallback_price_source_ix(
+                &net.addresses,
+                &authority,
+                state.fallback_source(),
+                state.fallback_uncertainty_bps,
+                None,
+            ),
+        ],
+        &[],
+    );
+    net.send(
+        "rate_sources",
+        &[
+            set_pyth_price_feed_ix(&net.addresses, &authority, Pubkey::default()),
+            set_switchboard_feed_ix(&net.addresses, &authority, Pubkey::default(), None),
+            set_lst_stake_pool_ix(&net.addresses, &authority, Pubkey::default(), None),
+            set_marinade_state_ix(&net.addresses, &authority, Pubkey::default(), None),
+            set_lst_calculator_ix(&net.addresses, &authority, Pubkey::default(), Pubkey::default(), None),
+            migrate_global_state_ix(&net.addresses, &authority),
+        ],
+        &[],
+    );
+
+    // Governance: timelocked change, authority handover and treasury rotation
+    let risk = ParamChange::Risk { min_cr_bps: state.min_cr_bps, target_cr_bps: state.target_cr_bps };
+    let timelock = ParamChange::Timelock { param_timelock_slots: 0 };
+    let timelock_kind = timelock.kind();
+    net.send(
+        "parameter_updates",
+        &[
+            queue_parameter_update_ix(&net.addresses, &authority, risk.clone()),
+            execute_parameter_update_ix(&net.addresses, &user.pubkey(), &authority, risk.kind()),
+            queue_parameter_update_ix(&net.addresses, &authority, timelock),
+            cancel_parameter_update_ix(&net.addresses, &authority, timelock_kind),
+        ],
+        &[&user],
+    );
+    let successor = Keypair::new();
+    net.send(
+        "authority_handover",
+        &[
+            propose_authority_ix(&net.addresses, &authority, successor.pubkey()),
+            accept_authority_ix(&net.addresses, &successor.pubkey()),
+            propose_authority_ix(&net.addresses, &successor.pubkey(), authority),
+            accept_authority_ix(&net.addresses, &authority),
+        ],
+        &[&successor],
+    );
+    assert_eq!(net.state().authority, authority);
+    let interim_treasury = Keypair::new();
+    net.send(
+        "set_treasury",
+        &[
+            set_treasury_ix(&net.addresses, &authority, &interim_treasury.pubkey(), false, Pubkey::default(), None),
+            set_treasury_ix(&net.addresses, &authority, &authority, false, Pubkey::default(), None),
+        ],
+        &[&interim_treasury],
+    );
+    assert_eq!(net.state().treasury, authority);
+
+    // Redeem pause that outlives its limit is lifted by anyone
+    net.send(
+        "pause_redeem",
+        &[
+            update_max_pause_duration_ix(&net.addresses, &authority, 1, None),
+            emergency_pause_ix(&net.addresses, &authority, false, true),
+        ],
+        &[],
+    );
+    net.wait_slots(2);
+    net.send("force_unpause_redeem", &[force_unpause_redeem_ix(&net.addresses, &user.pubkey())], &[&user]);
+    net.send(
+        "update_max_pause_duration",
+        &[update_max_pause_duration_ix(&net.addresses, &authority, state.max_pause_duration_slots, None)],
+        &[],
+    );
+    assert!(!net.state().redeem_paused);
+
+    // wSOL collateral leg: zaps in and out, then migrated back to the primary LST
+    net.refresh_oracle();
+    let wsol = spl_token::native_mint::ID;
+    let wsol_addresses = net.addresses.with_collateral(wsol);
+    let wsol_accounts = UserAccounts {
+        lst_account: associated_token_address(&user.pubkey(), &wsol, &spl_token::ID),
+        ..accounts
+    };
+    net.send(
+        "add_collateral_type",
+        &[add_collateral_type_ix(&wsol_addresses, &authority, SOL_PRECISION, BPS_PRECISION)],
+        &[],
+    );
+    net.create_ata(&user.pubkey(), &wsol);
+    let amusd_before = net.balance(&accounts.amusd_account);
+    let asol_before = net.balance(&accounts.asol_account);
+    net.send(
+        "zaps",
+        &[
+            zap_mint_asol_ix(&wsol_addresses, &wsol_accounts, 5 * SOL_PRECISION, 0),
+            zap_mint_amusd_ix(&wsol_addresses, &wsol_accounts, 5 * SOL_PRECISION, 0),
+        ],
+        &[&user],
+    );
+    let zapped_amusd = net.balance(&accounts.amusd_account) - amusd_before;
+    let zapped_asol = net.balance(&accounts.asol_account) - asol_before;
+    net.send(
+        "redeem_amusd_to_sol",
+        &[redeem_amusd_to_sol_ix(&wsol_addresses, &wsol_accounts, zapped_amusd / 2, 0)],
+        &[&user],
+    );
+    net.create_ata(&user.pubkey(), &wsol);
+    net.send(
+        "redeem_asol_to_sol",
+        &[redeem_asol_to_sol_ix(&wsol_addresses, &wsol_accounts, zapped_asol / 2, 0)],
+        &[&user],
+    );
+    let collateral_vault = fetch_collateral_vault(&net.rpc, &wsol).unwrap();
+    net.create_ata(&authority, &wsol);
+    net.send(
+        "retire_wsol",
+        &[
+            refresh_tvl_ix(&net.addresses, &authority, &[collateral_vault_address(&wsol)]),
+            set_collateral_status_ix(&wsol_addresses, &authority, CollateralStatus::DepositsDisabled as u8, None),
+            migrate_collateral_ix(&wsol_addresses, &net.addresses, &authority, collateral_vault.lst_amount),
+        ],
+        &[],
+    );
+    assert_eq!(fetch_collateral_vault(&net.rpc, &wsol).unwrap().lst_amount, 0);
+    assert_eq!(net.state().collateral_tvl_lamports, 0);
+
+    // Below min CR: rebalance from the treasury's aSOL, then a Stability Pool drawdown
+    net.refresh_oracle();
+    net.set_sol_price(net.price_for_cr(DEFAULT_MIN_CR_BPS - 1_000));
+    let treasury_asol = net.balance(&net.addresses.treasury_asol_account);
+    let approve = spl_token::instruction::approve(
+        &spl_token::ID,
+        &net.addresses.treasury_asol_account,
+        &net.addresses.global_state,
+        &authority,
+        &[],
+        treasury_asol,
+    )
+    .unwrap();
+    net.send("approve_rebalance_budget", &[approve], &[]);
+    net.send(
+        "rebalance",
+        &[rebalance_ix(&net.addresses, &accounts, SOL_PRECISION, 0)],
+        &[&user],
+    );
+    let epoch = fetch_stability_pool(&net.rpc).unwrap().epoch;
+    net.send("trigger_drawdown", &[trigger_drawdown_ix(&net.addresses, &user.pubkey(), epoch)], &[&user]);
+
+    // Below 100%: socialize the shortfall, then top up insurance
+    net.set_sol_price(net.price_for_cr(BPS_PRECISION - 1_000));
+    net.send("socialize_bad_debt", &[socialize_bad_debt_ix(&net.addresses, &authority)], &[]);
+    assert!(net.state().redemption_rate_bps < BPS_PRECISION);
+    net.send("fund_insurance", &[fund_insurance_ix(&net.addresses, &accounts, SOL_PRECISION)], &[&user]);
+
+    // Global settlement and the final claim
+    net.send("initiate_settlement", &[initiate_settlement_ix(&net.addresses, &authority)], &[]);
+    let amusd = net.balance(&accounts.amusd_account);
+    let asol = net.balance(&accounts.asol_account);
+    let lst_before = net.balance(&accounts.lst_account);
+    net.send(
+        "claim_settlement",
+        &[claim_settlement_ix(&net.addresses, &accounts, amusd, asol)],
+        &[&user],
+    );
+    assert!(net.balance(&accounts.lst_account) > lst_before);
+    assert!(net.state().settled);
+}
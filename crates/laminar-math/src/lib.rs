@@ -0,0 +1,2333 @@
+//! Pure mathematical functions for laminar protocol
+//! All functions are deterministic and use fixed-point arithmetic
+//! No dependencies, no allocation and no `std`, so risk dashboards and
+//! simulators get the program's exact math without pulling in Anchor. The
+//! program re-exports all of it as `laminar::math`.
+//!
+//! Quoting a 10 LST amUSD mint from raw numbers, with the book at its
+//! target CR so the base fee applies unscaled:
+//!
+//! ```
+//! use laminar_math::*;
+//!
+//! let lst_amount = 10 * SOL_PRECISION;
+//! let lst_to_sol_rate = 1_050_000_000; // 1.05 SOL per LST
+//! let sol_price_usd = 100 * USD_PRECISION;
+//!
+//! let sol_value = compute_tvl_sol(lst_amount, lst_to_sol_rate).unwrap();
+//! let amusd_gross = mul_div_down(sol_value, sol_price_usd, SOL_PRECISION).unwrap();
+//! assert_eq!(amusd_gross, 1_050 * USD_PRECISION);
+//!
+//! let fee_bps = compute_dynamic_fee_bps(
+//!     50,                  // base amUSD mint fee
+//!     FeeAction::AmusdMint,
+//!     15_000,              // current CR
+//!     13_000,              // min CR
+//!     15_000,              // target CR
+//!     BPS_PRECISION,       // fee floor multiplier
+//!     MAX_FEE_MULTIPLIER_BPS,
+//!     0,                   // oracle uncertainty
+//!     20_000,
+//! )
+//! .unwrap();
+//! let (amusd_to_user, fee) = apply_fee(amusd_gross, fee_bps).unwrap();
+//! assert_eq!((fee_bps, fee), (50, 5_250_000));
+//! assert_eq!(amusd_to_user, 1_044_750_000);
+//! ```
+
+#![cfg_attr(not(test), no_std)]
+
+// PRECISION CONSTANTS
+pub const SOL_PRECISION: u64 = 1_000_000_000;  // 1e9 lamports
+pub const USD_PRECISION: u64 = 1_000_000;       // 1e6 (6 decimals)
+pub const BPS_PRECISION: u64 = 10_000;          // 100% = 10000 bps
+
+// MINIMUM AMOUNTS
+pub const MIN_LST_DEPOSIT: u64 = 100_000;       // 0.0001 SOL (100k lamports)
+pub const MIN_AMUSD_MINT: u64 = 1_000;          // 0.001 USD (1k micro-USD)
+pub const MIN_ASOL_MINT: u64 = 1_000_000;       // 0.001 SOL (1M lamports)
+pub const MIN_NAV_LAMPORTS: u64 = 1_000;        // Minimum NAV for safe operations
+
+// Dynamic fee multiplier cap when CR < target (1x = 10_000 bps)
+pub const MAX_FEE_MULTIPLIER_BPS: u64 = 40_000; // 4x max
+
+// amUSD decimals the PSM's USDC side is rescaled to
+pub const AMUSD_DECIMALS: u8 = 6;
+// Stability fee accrual year
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;         // 365 days
+
+
+/// Multiply two u64 values and divide by a third, rounding up
+/// Used for conservative calculations that favor protocol solvency
+/// Returns None in overflow
+#[inline]
+pub fn mul_div_up(a: u64, b: u64, c: u64) -> Option<u64> {
+  if c == 0 {
+    return None;
+  }
+
+  let result = (a as u128)
+    .checked_mul(b as u128)?
+    .checked_add((c - 1) as u128)? // we add (c - 1) before division to round up
+    .checked_div(c as u128)?;
+
+  u64::try_from(result).ok()
+}
+
+/// Multiply two u64 values and divide by a third, rounding DOWN
+/// Used for conservative calculations that favor protocol solvency 
+/// Returns None on Overflow
+#[inline]
+pub fn mul_div_down(a: u64, b: u64, c: u64) -> Option<u64> {
+  if c == 0 {
+    return None;
+  }
+
+  let result = (a as u128)
+    .checked_mul(b as u128)?
+    .checked_div(c as u128)?;
+
+  u64::try_from(result).ok()
+}
+
+/// Compute total value locked (TVL) in SOL terms
+/// 
+/// # Arguments 
+/// * `collateral_lamports` - Total collateral held by protocol in lamports
+/// * `lst_to_sol_rate` - Exchange rate from LST to SOL (with SOL_PRECISION)
+/// 
+/// # Returns
+/// TVL in lamports (SOL base units)
+#[inline]
+pub fn compute_tvl_sol(collateral_lamports: u64, lst_to_sol_rate: u64) -> Option<u64> {
+  mul_div_down(collateral_lamports, lst_to_sol_rate, SOL_PRECISION)
+}
+
+/// Compute book TVL across collateral vaults
+///
+/// One vault valued by `compute_tvl_sol`, plus the already-valued SOL of
+/// every other vault (`other_tvl_lamports`). Each vault rounds down on its
+/// own, so the book is never worth more than its parts.
+#[inline]
+pub fn compute_book_tvl_sol(lst_amount: u64, lst_to_sol_rate: u64, other_tvl_lamports: u64) -> Option<u64> {
+  compute_tvl_sol(lst_amount, lst_to_sol_rate)?.checked_add(other_tvl_lamports)
+}
+
+/// Compute the SOL value a collateral vault contributes to CR
+///
+/// `compute_tvl_sol` scaled by the vault's collateral weight, rounded down.
+/// Redemptions still pay out at the unweighted value; only the CR (and the
+/// decisions made on it) see the haircut.
+///
+/// # Arguments
+/// * `lst_amount` - LST held in the vault (9 decimals)
+/// * `lst_to_sol_rate` - LST->SOL rate (SOL_PRECISION = 1.0)
+/// * `collateral_weight_bps` - Weight of the vault, BPS_PRECISION = 100%
+#[inline]
+pub fn compute_weighted_tvl_sol(lst_amount: u64, lst_to_sol_rate: u64, collateral_weight_bps: u64) -> Option<u64> {
+  mul_div_down(compute_tvl_sol(lst_amount, lst_to_sol_rate)?, collateral_weight_bps, BPS_PRECISION)
+}
+
+/// Compute SOL-denominated liabilities owed to amUSD holders
+/// 
+/// # Arguments
+/// * `amusd_supply` - Total amUSD supply (with USD_PRECISION)
+/// * `sol_price_usd` - SOL price in USD (with USD_PRECISION)
+/// 
+/// # Returns 
+/// Liability in lamports (SOL base units), rounded up for conservative solvency accounting.
+pub fn compute_liability_sol(amusd_supply: u64, sol_price_usd: u64) -> Option<u64> {
+  if sol_price_usd == 0 {
+    return None;
+  }
+
+  // Convert amUSD (USD terms) to SOL terms
+  // Conservative: liabilities must round up, never down
+  // liability_sol = (amusd_supply / sol_price_usd) * SOL_PRECISION
+  mul_div_up(amusd_supply, SOL_PRECISION, sol_price_usd)
+}
+
+/// Compute SOL-denominated liabilities after a bad-debt write-down
+/// 
+/// # Arguments
+/// * `amusd_supply` - Total amUSD supply (with USD_PRECISION)
+/// * `sol_price_usd` - SOL price in USD (with USD_PRECISION)
+/// * `redemption_rate_bps` - USD each amUSD redeems for, in bps of par
+/// 
+/// # Returns
+/// Liability in lamports, rounded up at both steps. Equal to
+/// `compute_liability_sol` at par.
+pub fn compute_liability_at_rate_sol(amusd_supply: u64, sol_price_usd: u64, redemption_rate_bps: u64) -> Option<u64> {
+  let amusd_value = mul_div_up(amusd_supply, redemption_rate_bps, BPS_PRECISION)?;
+  compute_liability_sol(amusd_value, sol_price_usd)
+}
+
+/// Compute determisnistic rounding delta between conservative and user outputs
+/// 
+/// # Arguments
+/// * `conservative_output` - output from conservative rounding and user (down)
+/// * `user_favoring_output` - output from user-favoring path (up)
+/// 
+/// # Returns
+/// Delta in output units (`user_favoring_output` - `conservative output`)
+pub fn compute_rounding_delta_units(
+  conservative_output: u64,
+  use_favouring_output: u64,
+) -> Option<u64> {
+  use_favouring_output.checked_sub(conservative_output)
+}
+
+
+/// Convert micro-USD dust to lamports with conservative round-up.
+///
+/// # Arguments
+/// * `usd_dust_micro` - Dust in micro-USD units
+/// * `sol_price_usd` - SOL price in micro-USD
+///
+/// # Returns
+/// Lamports equivalent, rounded up.
+pub fn usd_dust_to_lamports_up(usd_dust_micro: u64, sol_price_usd: u64) -> Option<u64> {
+  if usd_dust_micro == 0 {
+    return Some(0);
+  }
+
+  mul_div_up(usd_dust_micro, SOL_PRECISION, sol_price_usd)
+}
+
+/// Convert LST-unit dust to lamports with conservative round-up.
+///
+/// # Arguments
+/// * `lst_dust_units` - Dust in LST base units (9 decimals)
+/// * `lst_to_sol_rate` - LST->SOL rate (SOL_PRECISION scale)
+///
+/// # Returns
+/// Lamports equivalent, rounded up.
+pub fn lst_dust_to_lamports_up(lst_dust_units: u64, lst_to_sol_rate: u64) -> Option<u64> {
+  if lst_dust_units == 0 {
+    return Some(0);
+  }
+  mul_div_up(lst_dust_units, lst_to_sol_rate, SOL_PRECISION)
+}
+
+/// Convert aSOL-unit dust to lamports with conservative round-up.
+///
+/// # Arguments
+/// * `asol_dust_units` - Dust in aSOL base units (9 decimals)
+/// * `nav_lamports` - aSOL NAV in lamports per aSOL (SOL_PRECISION scale)
+///
+/// # Returns
+/// Lamports equivalent, rounded up.
+pub fn asol_dust_to_lamports_up(asol_dust_units: u64, nav_lamports: u64) -> Option<u64> {
+  if asol_dust_units == 0 {
+    return Some(0);
+  }
+  mul_div_up(asol_dust_units, nav_lamports, SOL_PRECISION)
+}
+
+/// Compute SOL-denominated equity owned by aSOL holders
+/// 
+/// # Arguments 
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilities in lamports
+/// 
+/// # Returns
+/// Equity in lamports (returns 0 if TVL < liabilty to prevent negative equity) 
+pub fn compute_equity_sol(tvl: u64, liability: u64) -> u64 {
+  // Prevent negative equity - if insolvent, equity is zero
+  tvl.saturating_sub(liability)
+}
+
+
+///Compute collateral ratio in basis points 
+/// 
+/// # Arguments 
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilities in lamports 
+/// 
+/// # Returns 
+/// CR in basis points (e.g., 15000 = 150%)
+/// Returns u64::MAX if liability is 0 (infinite CR - no debt exists)
+pub fn compute_cr_bps(tvl: u64, liability: u64) -> u64 {
+  if liability == 0 {
+    return u64::MAX; // No debt = undefined CR (treated as infinite)
+  }
+
+  // CR = (TVL / Liability) * BPS_PRECISION
+  mul_div_down(tvl, BPS_PRECISION, liability).unwrap_or(u64::MAX)
+}
+
+/// Compute accounting equity in SOL lamports, including rounding reserve
+/// 
+/// Accounting identity: 
+/// E = TVL - Liability - RoundingReserve
+/// 
+/// # Arguments
+/// * `tvl` - Total collateral value in lamports
+/// * `liability` - Total liabilities in lamports
+/// * `rounding_reserve` - Non-claimable rounding reserve in lamports 
+/// 
+/// # Returns 
+/// Signed accounting equity in lamports (can be negative during insolvency)
+pub fn compute_accounting_equity_sol(
+  tvl: u64,
+  liability: u64,
+  rounding_reserve: u64,
+) -> Option<i128> {
+  (tvl as i128)
+    .checked_sub(liability as i128)?
+    .checked_sub(rounding_reserve as i128)
+}
+
+/// Lamports held back from claimable equity
+/// 
+/// Pass this wherever an equity or NAV helper takes `rounding_reserve`, so
+/// the insurance fund backs amUSD without ever accruing to aSOL.
+/// 
+/// # Arguments
+/// * `rounding_reserve` - Rounding reserve in lamports
+/// * `insurance_fund` - Insurance fund in lamports
+/// 
+/// # Returns
+/// `rounding_reserve + insurance_fund`
+pub fn compute_non_claimable_sol(rounding_reserve: u64, insurance_fund: u64) -> Option<u64> {
+  rounding_reserve.checked_add(insurance_fund)
+}
+
+/// Compute claimable equity in SOL lamports 
+/// 
+/// This clamps negative accounting equity to zero for user-claim purposes 
+/// 
+/// # Arguments 
+/// * `tvl` - Total collateral value in lamports
+/// * `liability` - Total liabilities in lamports
+/// * `rounding_reserve` - Non-claimable rounding reserve in lamports 
+/// 
+/// # Returns 
+/// Claimable equity in lamports (`max(accounting_equity, 0`).
+pub fn compute_claimable_equity_sol(
+  tvl: u64,
+  liability: u64,
+  rounding_reserve: u64,
+) -> Option<u64> {
+  let equity = compute_accounting_equity_sol(tvl, liability, rounding_reserve)?;
+  if equity <= 0 {
+    Some(0)
+  } else {
+    u64::try_from(equity).ok()
+  }
+}
+
+/// Compute Net Asset Value (NAV) of amUSD in SOL terms
+/// amUSD is always worth $1, so NAV = SOL_PRECISION / SOL_price
+/// 
+/// # Arguments 
+/// * `sol_price_usd` - SOL price in USD (with USD_PRECISION, e.g., 100_000_000 = $100)
+/// 
+/// # Returns 
+/// NAV in lamports per amUSD unit (1e6 amUSD = $1)
+/// Example: If SOL = $100, 1 amUSD (1e6 units) = 10_000_000 lamports (0.01 SOL)
+pub fn nav_amusd(sol_price_usd: u64) -> Option<u64> {
+  if sol_price_usd == 0 {
+    return None;
+  }
+
+  // nav = (1 USD * SOL_PRECISION) / sol_price_usd
+  // Since 1 USD = USD_PRECISION, we get: 
+  mul_div_down(USD_PRECISION, SOL_PRECISION, sol_price_usd)
+}
+
+/// Compute reserve-aware NAV of aSOL using claimable equity.
+/// 
+/// # Arguments
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilties in lamports
+/// * `asol_supply` - Total aSOL supply (SOL_PRECISION units)
+/// 
+/// # Returns
+/// NAV in lamports per aSOL unit.
+/// Returns `None` if `asol_supply == 0` 
+pub fn nav_asol_with_reserve(
+  tvl: u64,
+  liability: u64,
+  rounding_reserve: u64,
+  asol_supply: u64,
+) -> Option<u64> {
+  if asol_supply == 0 {
+    return None;
+  }
+
+  let claimable_equity = compute_claimable_equity_sol(tvl, liability, rounding_reserve)?;
+  mul_div_down(claimable_equity, SOL_PRECISION, asol_supply)
+}
+
+/// Compute Net Asset Value (NAV) of aSOL
+/// aSOL represents residual equity after amUSD debt is satisfied
+/// 
+/// # Arguments
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilities in lamports
+/// * `asol_supply` - Total aSOL supply (with SOL_PRECISION)
+/// 
+/// # Returns
+/// NAV in lamports per aSOL unit
+/// Returns Some(0) if TVL < liability (prevents negative equity propagation)
+/// Returns None if aSOL supply is 0 (edge case: first mint)
+pub fn nav_asol(tvl: u64, liability: u64, asol_supply: u64) -> Option<u64> {
+    if asol_supply == 0 {
+        return None; // First mint case - will be handled specially
+    }
+    
+    let equity = compute_equity_sol(tvl, liability);
+    
+    // nav_asol = equity / asol_supply (both in lamports)
+    mul_div_down(equity, SOL_PRECISION, asol_supply)
+}
+
+/// fee action categories used by the dynamic fee engine
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeAction {
+  /// Debt creation (risk-increasing when CR is low)
+  AmusdMint,
+  /// Debt repayment (risk-reducing when CR is low)
+  AmUSDRedeem,
+  /// Equity injection (risk-reducing when CR is low)
+  AsolMint,
+  /// Equity exit (risk-increasing when CR is low)
+  AsolRedeem,
+}
+
+impl FeeAction {
+  /// True for actions that should become more expensive when CR deteriorates.
+  #[inline]
+  pub fn is_risk_increasing(self) -> bool {
+    matches!(self, FeeAction::AmusdMint | FeeAction::AsolRedeem)
+  }
+}
+
+/// Wire encoding for instruction arguments, in declaration order
+impl TryFrom<u8> for FeeAction {
+  type Error = ();
+
+  fn try_from(value: u8) -> Result<Self, ()> {
+    match value {
+      0 => Ok(FeeAction::AmusdMint),
+      1 => Ok(FeeAction::AmUSDRedeem),
+      2 => Ok(FeeAction::AsolMint),
+      3 => Ok(FeeAction::AsolRedeem),
+      _ => Err(()),
+    }
+  }
+}
+
+/// uncertainty_up_bps = BPS + (uncertainity_intex_bps * BPS / k)
+pub const UNCERTAINTY_K_BPS: u64 = 1_000;
+
+/// Ceiling on the derived uncertainty index: a confidence as wide as the price.
+pub const MAX_UNCERTAINTY_INDEX_BPS: u64 = BPS_PRECISION;
+
+/// Uncertainty index added per 100% flash-loan utilization: lending the
+/// whole vault counts like a 10% oracle confidence band.
+pub const FLASH_UTILIZATION_UNCERTAINTY_WEIGHT_BPS: u64 = 1_000;
+
+/// Clamp helper for u64.
+#[inline]
+pub fn clamp_u64(value: u64, min_value: u64, max_value:u64) -> u64 {
+  value.max(min_value).min(max_value)
+} 
+
+/// Derive CR-based multiplier
+/// 
+/// Returns multiplier in bps (10_000 = 1.0x)
+pub fn derive_cr_multiplier_bps(
+  action: FeeAction,
+  cr_bps: u64,
+  min_cr_bps: u64,
+  target_cr_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+) -> Option<u64> {
+  // Enforce parameter bounds required by spec.
+  if min_cr_bps >= target_cr_bps {
+    return None;
+  }
+  if fee_min_multiplier_bps > BPS_PRECISION || fee_max_multiplier_bps < BPS_PRECISION {
+    return None;
+  }
+  if fee_min_multiplier_bps > fee_max_multiplier_bps {
+    return None;
+  }
+
+  if cr_bps == u64::MAX {
+    return Some(BPS_PRECISION);
+  }
+
+  let cr_mult = if action.is_risk_increasing() {
+    // expensive when CR falls.
+    if cr_bps >= target_cr_bps {
+      BPS_PRECISION
+    } else if cr_bps <= min_cr_bps {
+      fee_max_multiplier_bps
+    } else {
+      let distance = target_cr_bps.checked_sub(cr_bps)?;
+      let range = target_cr_bps.checked_sub(min_cr_bps)?;
+      let delta = fee_max_multiplier_bps.checked_sub(BPS_PRECISION)?;
+
+      let step = mul_div_down(distance, delta, range)?;
+      BPS_PRECISION.checked_add(step)?
+    }
+  } else {
+    // cheap when CR falls.
+    if cr_bps >= target_cr_bps {
+      BPS_PRECISION
+    } else if cr_bps <= min_cr_bps {
+      fee_min_multiplier_bps
+    } else {
+      let distance = target_cr_bps.checked_sub(cr_bps)?;
+      let range = target_cr_bps.checked_sub(min_cr_bps)?;
+      let delta = BPS_PRECISION.checked_sub(fee_min_multiplier_bps)?;
+
+      let step = mul_div_down(distance, delta, range)?;
+      BPS_PRECISION.checked_sub(step)?
+    }
+  };
+
+  Some(clamp_u64(cr_mult, fee_min_multiplier_bps, fee_max_multiplier_bps))
+}
+
+/// Derive the uncertainty index from an oracle snapshot
+/// 
+/// uncertainty_index_bps = ceil(confidence * BPS / price), clamped to
+/// `MAX_UNCERTAINTY_INDEX_BPS`. Rounded up so a noisy oracle never looks
+/// calmer than it is. None when the price is zero.
+pub fn derive_uncertainty_index_bps(confidence_usd: u64, price_usd: u64) -> Option<u64> {
+  if price_usd == 0 {
+    return None;
+  }
+
+  // Compute on u128 so an absurd confidence clamps instead of overflowing.
+  let index = (confidence_usd as u128)
+    .checked_mul(BPS_PRECISION as u128)?
+    .div_ceil(price_usd as u128);
+
+  Some(index.min(MAX_UNCERTAINTY_INDEX_BPS as u128) as u64)
+}
+
+/// Mix flash-loan utilization into the oracle uncertainty index
+/// 
+/// index = oracle_index + ceil(min(utilization, BPS) * weight / BPS), clamped
+/// to `MAX_UNCERTAINTY_INDEX_BPS`. Rounded up like the oracle index, so any
+/// flash activity in the slot is visible in the fee.
+pub fn combine_uncertainty_sources(oracle_uncertainty_bps: u64, flash_loan_utilization_bps: u64) -> Option<u64> {
+  let flash_uncertainty_bps = mul_div_up(
+    flash_loan_utilization_bps.min(BPS_PRECISION),
+    FLASH_UTILIZATION_UNCERTAINTY_WEIGHT_BPS,
+    BPS_PRECISION,
+  )?;
+  let index = oracle_uncertainty_bps.checked_add(flash_uncertainty_bps)?;
+  Some(index.min(MAX_UNCERTAINTY_INDEX_BPS))
+}
+
+/// Derive uncertainity multiplier
+/// 
+/// for risk increasing actions: applies capped uncertainity uplift
+/// for risk reducing actions: neutral
+pub fn derive_uncertainty_multiplier_bps(
+  action: FeeAction,
+  uncertainty_index_bps: u64,
+  uncertainty_max_bps: u64,
+) -> Option<u64> {
+  if uncertainty_max_bps < BPS_PRECISION {
+    return None;
+  }
+
+  if !action.is_risk_increasing(){
+    return Some(BPS_PRECISION);
+  }
+
+  let uncertainity_delta = mul_div_down(uncertainty_index_bps, BPS_PRECISION, UNCERTAINTY_K_BPS)?;
+  let unc_up = BPS_PRECISION.checked_add(uncertainity_delta)?;
+
+  Some(clamp_u64(unc_up, BPS_PRECISION, uncertainty_max_bps))
+}
+
+/// CR and uncertainity multiplier wuth precedence and clamps
+pub fn compose_fee_multiplier_bps(
+  action: FeeAction,
+  cr_multiplier_bps: u64,
+  unc_multipier_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+) -> Option<u64> {
+  if fee_min_multiplier_bps > BPS_PRECISION || fee_max_multiplier_bps < BPS_PRECISION {
+    return None;
+  }
+
+  if fee_min_multiplier_bps > fee_max_multiplier_bps {
+    return None;
+  }
+
+  let mut total = mul_div_down(cr_multiplier_bps, unc_multipier_bps, BPS_PRECISION)?;
+
+  if action.is_risk_increasing() {
+    total = total.max(BPS_PRECISION)
+  } else {
+    total = total.min(BPS_PRECISION);
+  }
+
+  total = clamp_u64(total, fee_min_multiplier_bps, fee_max_multiplier_bps);
+  Some(total)
+}
+
+/// A dynamic fee with the multipliers that produced it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DynamicFee {
+  pub fee_bps: u64,
+  /// From `derive_cr_multiplier_bps`; 10_000 = 1.0x
+  pub cr_multiplier_bps: u64,
+  /// From `derive_uncertainty_multiplier_bps`; 10_000 = 1.0x
+  pub uncertainty_multiplier_bps: u64,
+}
+
+impl DynamicFee {
+  /// No fee charged; both multipliers neutral
+  pub const NONE: Self = Self {
+    fee_bps: 0,
+    cr_multiplier_bps: BPS_PRECISION,
+    uncertainty_multiplier_bps: BPS_PRECISION,
+  };
+}
+
+/// final dynamic fee in bps for a canonical action
+/// 
+/// Effective fee = floor(base_fee_bps * multiplier_total_bps / BPS)
+#[allow(clippy::too_many_arguments)]
+pub fn compute_dynamic_fee_bps(
+  base_fee_bps: u64,
+  action: FeeAction,
+  cr_bps: u64,
+  min_cr_bps: u64,
+  target_cr_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+  uncertainty_index_bps: u64,
+  uncertainty_max_bps: u64,
+) -> Option<u64> {
+  compute_dynamic_fee(
+    base_fee_bps,
+    action,
+    cr_bps,
+    min_cr_bps,
+    target_cr_bps,
+    fee_min_multiplier_bps,
+    fee_max_multiplier_bps,
+    uncertainty_index_bps,
+    uncertainty_max_bps,
+  )
+  .map(|fee| fee.fee_bps)
+}
+
+/// `compute_dynamic_fee_bps` with its CR and uncertainty multipliers. A zero
+/// base fee short-circuits to `DynamicFee::NONE`.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_dynamic_fee(
+  base_fee_bps: u64,
+  action: FeeAction,
+  cr_bps: u64,
+  min_cr_bps: u64,
+  target_cr_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+  uncertainty_index_bps: u64,
+  uncertainty_max_bps: u64,
+) -> Option<DynamicFee> {
+  if base_fee_bps == 0 {
+    return Some(DynamicFee::NONE);
+  }
+
+  let cr_multiplier = derive_cr_multiplier_bps(action, cr_bps, min_cr_bps, target_cr_bps, fee_min_multiplier_bps, fee_max_multiplier_bps)?;
+
+  let unc_multiplier = derive_uncertainty_multiplier_bps(action, uncertainty_index_bps, uncertainty_max_bps)?;
+
+  let total_multplier = compose_fee_multiplier_bps(action, cr_multiplier, unc_multiplier, fee_min_multiplier_bps, fee_max_multiplier_bps)?;
+
+  Some(DynamicFee {
+    fee_bps: mul_div_down(base_fee_bps, total_multplier, BPS_PRECISION)?,
+    cr_multiplier_bps: cr_multiplier,
+    uncertainty_multiplier_bps: unc_multiplier,
+  })
+}
+
+/// Dynamic fee adjustment when CR deteriorates (CR < target)
+/// - For actions that should become MORE expensive when CR is low
+/// - Returns base fee when CR >= target or if CR is infinite (no debt)
+pub fn fee_bps_increase_when_low(
+  base_fee_bps: u64,
+  cr_bps: u64,
+  target_cr_bps: u64,
+) -> u64 {
+  if base_fee_bps == 0 {
+    return 0;
+  }
+  if cr_bps == u64::MAX || cr_bps >= target_cr_bps {
+    return base_fee_bps;
+  }
+
+  // Scale up: fee = base * (target / cr)
+  let scaled = mul_div_up(base_fee_bps, target_cr_bps, cr_bps).unwrap_or(base_fee_bps);
+  let max_fee = mul_div_down(base_fee_bps, MAX_FEE_MULTIPLIER_BPS, BPS_PRECISION)
+    .unwrap_or(u64::MAX);
+
+  scaled.min(max_fee)
+}
+
+/// Dynamic fee adjustment when CR deteriorates (CR < target)
+/// - For actions that should become CHEAPER when CR is low
+/// - Returns base fee when CR >= target or if CR is infinite (no debt)
+pub fn fee_bps_decrease_when_low(
+  base_fee_bps: u64,
+  cr_bps: u64,
+  target_cr_bps: u64,
+) -> u64 {
+  if base_fee_bps == 0 {
+    return 0;
+  }
+  if cr_bps == u64::MAX || cr_bps >= target_cr_bps {
+    return base_fee_bps;
+  }
+
+  // Scale down: fee = base * (cr / target)
+  mul_div_down(base_fee_bps, cr_bps, target_cr_bps).unwrap_or(0)
+}
+
+/// Derive the amUSD redemption haircut from the post-drawdown CR
+/// 
+/// # Arguments
+/// * `cr_bps` - Collateral ratio in basis points after stability drawdown
+/// 
+/// # Returns
+/// (insolvency_mode, haircut_bps)
+/// Haircut is `BPS_PRECISION` (par) while CR >= 100%, otherwise the CR itself.
+pub fn derive_redeem_haircut_bps(cr_bps: u64) -> (bool, u64) {
+  let insolvency_mode = cr_bps < BPS_PRECISION;
+  let haircut_bps = if insolvency_mode { cr_bps } else { BPS_PRECISION };
+  (insolvency_mode, haircut_bps)
+}
+
+/// CR the amUSD haircut is derived from: TVL net of the insurance fund
+/// 
+/// The fund tops redemptions up separately, so counting it here as well
+/// would spend it twice.
+/// 
+/// # Arguments
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilities in lamports
+/// * `insurance_fund` - Insurance fund in lamports
+/// 
+/// # Returns
+/// CR in basis points; `u64::MAX` with no liability
+pub fn compute_uninsured_cr_bps(tvl: u64, liability: u64, insurance_fund: u64) -> u64 {
+  compute_cr_bps(tvl.saturating_sub(insurance_fund), liability)
+}
+
+/// CR the dynamic fee curve reads: TVL net of deprecated collateral
+/// 
+/// A deprecated collateral type still backs the book until it is drained,
+/// but its value should not hold fees down while it runs off.
+/// 
+/// # Arguments
+/// * `tvl` - Total value locked in lamports
+/// * `liability` - Total liabilities in lamports
+/// * `deprecated_tvl` - Part of `tvl` held in deprecated collateral vaults
+/// 
+/// # Returns
+/// CR in basis points; `u64::MAX` with no liability
+pub fn compute_fee_cr_bps(tvl: u64, liability: u64, deprecated_tvl: u64) -> u64 {
+  compute_cr_bps(tvl.saturating_sub(deprecated_tvl), liability)
+}
+
+/// Insurance top-up for one haircut redemption
+/// 
+/// # Arguments
+/// * `par_sol` - Par value of the redemption in lamports
+/// * `haircut_sol` - Haircut value before insurance
+/// * `insurance_fund` - Insurance fund in lamports
+/// 
+/// # Returns
+/// Lamports the fund adds back, at most the shortfall to par.
+pub fn compute_insurance_topup(par_sol: u64, haircut_sol: u64, insurance_fund: u64) -> Option<u64> {
+  Some(par_sol.checked_sub(haircut_sol)?.min(insurance_fund))
+}
+
+/// Relative disagreement between two prices of the same asset
+/// 
+/// # Arguments
+/// * `price_a` - First price (any fixed-point scale)
+/// * `price_b` - Second price, same scale as `price_a`
+/// 
+/// # Returns
+/// `|a - b| / min(a, b)` in bps, rounded up so a borderline pair never looks
+/// tighter than it is. None when either price is zero.
+pub fn compute_price_deviation_bps(price_a: u64, price_b: u64) -> Option<u64> {
+  let low = price_a.min(price_b);
+  if low == 0 {
+    return None;
+  }
+  let diff = price_a.max(price_b) - low;
+  let deviation = (diff as u128)
+    .checked_mul(BPS_PRECISION as u128)?
+    .div_ceil(low as u128);
+  Some(u64::try_from(deviation).unwrap_or(u64::MAX))
+}
+
+/// Weight an EMA keeps on its previous value after `elapsed_slots`
+/// 
+/// 2^(-elapsed / half_life) in bps: exact at whole half-lives and linear in
+/// between, so it stays integer-only and monotone. A zero half-life keeps no
+/// weight, i.e. the EMA tracks spot.
+pub fn compute_ema_decay_bps(elapsed_slots: u64, half_life_slots: u64) -> Option<u64> {
+  if half_life_slots == 0 {
+    return Some(0);
+  }
+  let halvings = elapsed_slots / half_life_slots;
+  if halvings >= u64::BITS as u64 {
+    return Some(0);
+  }
+  let whole = BPS_PRECISION >> halvings;
+
+  // Within one half-life the weight falls from `whole` to `whole / 2`.
+  let rem = (elapsed_slots % half_life_slots) as u128;
+  let span = (half_life_slots as u128).checked_mul(2)?;
+  let decay = (whole as u128).checked_mul(span - rem)? / span;
+  u64::try_from(decay).ok()
+}
+
+/// Fold `spot` into an EMA over `elapsed_slots`
+/// 
+/// # Arguments
+/// * `prev_ema` - EMA at the previous update; zero seeds it with `spot`
+/// * `spot` - Price that held since the previous update
+/// * `elapsed_slots` - Slots since the previous update
+/// * `half_life_slots` - Half-life; zero makes the EMA equal `spot`
+/// 
+/// # Returns
+/// The convex combination of `prev_ema` and `spot`, rounded down.
+pub fn compute_ema_price(prev_ema: u64, spot: u64, elapsed_slots: u64, half_life_slots: u64) -> Option<u64> {
+  if prev_ema == 0 {
+    return Some(spot);
+  }
+  let decay = compute_ema_decay_bps(elapsed_slots, half_life_slots)? as u128;
+  let ema = (prev_ema as u128)
+    .checked_mul(decay)?
+    .checked_add((spot as u128).checked_mul(BPS_PRECISION as u128 - decay)?)?
+    / BPS_PRECISION as u128;
+  u64::try_from(ema).ok()
+}
+
+/// Stability fee accrued on amUSD debt
+/// 
+/// # Arguments
+/// * `amusd_supply` - amUSD outstanding over the whole period
+/// * `stability_fee_bps_per_year` - Annual rate, simple interest
+/// * `elapsed_seconds` - Seconds since the last accrual
+/// 
+/// # Returns
+/// `amusd_supply * rate * elapsed / year`, rounded down. Linear within one
+/// call, so it is exact for the supply the period actually saw; folding each
+/// accrual into supply compounds at the call granularity.
+pub fn compute_stability_fee_accrual(amusd_supply: u64, stability_fee_bps_per_year: u64, elapsed_seconds: u64) -> Option<u64> {
+  let accrued = (amusd_supply as u128)
+    .checked_mul(stability_fee_bps_per_year as u128)?
+    .checked_mul(elapsed_seconds as u128)?
+    / (BPS_PRECISION as u128 * SECONDS_PER_YEAR as u128);
+  u64::try_from(accrued).ok()
+}
+
+/// Stability Pool shares issued for an amUSD deposit
+/// 
+/// # Arguments
+/// * `amusd_amount` - amUSD being deposited
+/// * `total_pool_amusd` - amUSD held by the pool before the deposit
+/// * `total_shares` - Shares outstanding before the deposit
+/// 
+/// # Returns
+/// Shares at the current amUSD-per-share rate, rounded down so a deposit
+/// never dilutes existing depositors. The first deposit is issued 1:1.
+/// None when shares are outstanding against an empty pool.
+pub fn compute_stability_shares(amusd_amount: u64, total_pool_amusd: u64, total_shares: u64) -> Option<u64> {
+  if total_shares == 0 {
+    return Some(amusd_amount);
+  }
+  mul_div_down(amusd_amount, total_shares, total_pool_amusd)
+}
+
+/// amUSD a block of Stability Pool shares is worth, rounded down
+pub fn compute_stability_share_value(shares: u64, total_pool_amusd: u64, total_shares: u64) -> Option<u64> {
+  if total_shares == 0 {
+    return Some(0);
+  }
+  mul_div_down(shares, total_pool_amusd, total_shares)
+}
+
+/// Stability Pool shares burned to withdraw `amusd_amount`, rounded up so a
+/// withdrawal never takes value from the depositors who stay.
+pub fn compute_stability_shares_to_burn(amusd_amount: u64, total_pool_amusd: u64, total_shares: u64) -> Option<u64> {
+  mul_div_up(amusd_amount, total_shares, total_pool_amusd)
+}
+
+/// Savings Pool shares issued for an amUSD deposit, rounded down
+/// 
+/// Fees raise `total_pool_amusd` without issuing shares, so later deposits
+/// buy fewer shares per amUSD. The first deposit, or one into a pool whose
+/// shares are all gone, is 1:1.
+pub fn compute_savings_shares(amusd_amount: u64, total_pool_amusd: u64, total_shares: u64) -> Option<u64> {
+  if total_shares == 0 {
+    return Some(amusd_amount);
+  }
+  mul_div_down(amusd_amount, total_shares, total_pool_amusd)
+}
+
+/// amUSD paid out for burning `shares` of the Savings Pool, rounded down so
+/// a withdrawal never takes value from the depositors who stay.
+pub fn compute_savings_withdrawal(shares: u64, total_pool_amusd: u64, total_shares: u64) -> Option<u64> {
+  if total_shares == 0 {
+    return None;
+  }
+  mul_div_down(shares, total_pool_amusd, total_shares)
+}
+
+/// Part of an amUSD treasury fee routed to the Savings Pool, rounded down
+/// in favor of the treasury
+pub fn compute_savings_fee(amusd_treasury_fee: u64, savings_fee_split_bps: u64) -> Option<u64> {
+  if savings_fee_split_bps > BPS_PRECISION {
+    return None;
+  }
+  mul_div_down(amusd_treasury_fee, savings_fee_split_bps, BPS_PRECISION)
+}
+
+/// Fixed-point scale of the Stability Pool LST-gain-per-share accumulator
+pub const STABILITY_GAIN_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Accumulator increment when `lst_gain` LST is shared over `total_shares`
+/// 
+/// This is the pool's S in Liquity's P/S scheme; the share price plays the
+/// role of P. Rounded down, so the sum of depositor gains never exceeds what
+/// the pool received.
+pub fn compute_stability_gain_per_share(lst_gain: u64, total_shares: u64) -> Option<u128> {
+  if total_shares == 0 {
+    return None;
+  }
+  (lst_gain as u128)
+    .checked_mul(STABILITY_GAIN_SCALE)?
+    .checked_div(total_shares as u128)
+}
+
+/// LST accrued to `shares` since their accumulator snapshot, rounded down
+pub fn compute_stability_gain(shares: u64, gain_per_share: u128, snapshot: u128) -> Option<u64> {
+  let delta = gain_per_share.checked_sub(snapshot)?;
+  let gain = (shares as u128).checked_mul(delta)? / STABILITY_GAIN_SCALE;
+  u64::try_from(gain).ok()
+}
+
+/// Stability Pool drawdown against a sub-min CR
+/// 
+/// # Arguments
+/// * `total_lst_amount` - Vault LST before the drawdown
+/// * `lst_to_sol_rate` - LST->SOL rate
+/// * `other_tvl_lamports` - SOL value held in other collateral vaults
+/// * `amusd_supply` - amUSD supply before the drawdown (includes the pool)
+/// * `sol_price_usd` - Redemption-side SOL price
+/// * `pool_amusd` - amUSD the pool can burn
+/// * `min_cr_bps` - CR the drawdown restores
+/// 
+/// # Returns
+/// (amusd_burned, lst_to_pool). Nothing while CR >= min. From 100% up the
+/// pool burns just enough at par to lift CR back to min (two lamports of
+/// slack cover post-trade rounding), capped at the pool. Below 100% it burns
+/// everything at the same haircut holders would take, which leaves CR where
+/// it was and the remaining holders no worse off.
+pub fn compute_stability_drawdown(
+  total_lst_amount: u64,
+  lst_to_sol_rate: u64,
+  other_tvl_lamports: u64,
+  amusd_supply: u64,
+  sol_price_usd: u64,
+  pool_amusd: u64,
+  min_cr_bps: u64,
+) -> Option<(u64, u64)> {
+  let tvl = compute_book_tvl_sol(total_lst_amount, lst_to_sol_rate, other_tvl_lamports)?;
+  let liability = compute_liability_sol(amusd_supply, sol_price_usd)?;
+  let cr_bps = compute_cr_bps(tvl, liability);
+  let pool_amusd = pool_amusd.min(amusd_supply);
+  if cr_bps >= min_cr_bps || pool_amusd == 0 {
+    return Some((0, 0));
+  }
+
+  let (insolvency_mode, haircut_bps) = derive_redeem_haircut_bps(cr_bps);
+  let amusd_burned = if insolvency_mode || min_cr_bps <= BPS_PRECISION {
+    pool_amusd
+  } else {
+    // SOL s with (tvl - s) / (liability - s) >= min_cr
+    let shortfall = (min_cr_bps as u128)
+      .checked_mul(liability as u128 + 2)?
+      .checked_sub((BPS_PRECISION as u128).checked_mul(tvl.saturating_sub(2) as u128)?)?;
+    let sol_needed = shortfall.div_ceil((min_cr_bps - BPS_PRECISION) as u128);
+    let amusd_needed = sol_needed
+      .checked_mul(sol_price_usd as u128)?
+      .div_ceil(SOL_PRECISION as u128);
+    u64::try_from(amusd_needed).unwrap_or(u64::MAX).min(pool_amusd)
+  };
+
+  let sol_at_par = mul_div_down(amusd_burned, SOL_PRECISION, sol_price_usd)?;
+  let sol_paid = mul_div_down(sol_at_par, haircut_bps, BPS_PRECISION)?;
+  let lst_to_pool = mul_div_down(sol_paid, SOL_PRECISION, lst_to_sol_rate)?.min(total_lst_amount);
+  Some((amusd_burned, lst_to_pool))
+}
+
+/// LST a rebalance must add to lift CR to exactly `min_cr_bps`
+/// 
+/// # Arguments
+/// * `total_lst_amount` - Vault LST before the rebalance
+/// * `lst_to_sol_rate` - LST->SOL rate
+/// * `other_tvl_lamports` - SOL value held in other collateral vaults
+/// * `amusd_supply` - amUSD supply (unchanged by a rebalance)
+/// * `sol_price_usd` - Mint-side SOL price
+/// * `min_cr_bps` - CR the rebalance stops at
+/// 
+/// # Returns
+/// The smallest deposit after which `compute_cr_bps` reads `min_cr_bps` or
+/// more; zero once CR is already there.
+pub fn compute_rebalance_lst_needed(
+  total_lst_amount: u64,
+  lst_to_sol_rate: u64,
+  other_tvl_lamports: u64,
+  amusd_supply: u64,
+  sol_price_usd: u64,
+  min_cr_bps: u64,
+) -> Option<u64> {
+  if lst_to_sol_rate == 0 {
+    return None;
+  }
+  let liability = compute_liability_sol(amusd_supply, sol_price_usd)?;
+  // floor(tvl * BPS / liability) >= min  <=>  tvl >= ceil(min * liability / BPS)
+  let tvl_needed = mul_div_up(min_cr_bps, liability, BPS_PRECISION)?.saturating_sub(other_tvl_lamports);
+  let lst_needed = mul_div_up(tvl_needed, SOL_PRECISION, lst_to_sol_rate)?;
+  Some(lst_needed.saturating_sub(total_lst_amount))
+}
+
+/// aSOL paid on top of a NAV-priced rebalance so the caller's effective price
+/// is `nav * (1 - discount_bps)`
+/// 
+/// # Arguments
+/// * `asol_at_nav` - aSOL the deposit buys at NAV
+/// * `discount_bps` - Discount to NAV, below 100%
+/// 
+/// # Returns
+/// Bonus aSOL, rounded down. None for a discount of 100% or more.
+pub fn compute_rebalance_bonus(asol_at_nav: u64, discount_bps: u64) -> Option<u64> {
+  if discount_bps >= BPS_PRECISION {
+    return None;
+  }
+  mul_div_down(asol_at_nav, discount_bps, BPS_PRECISION - discount_bps)
+}
+
+/// aSOL added to a recovery-mode mint so fresh equity is priced below NAV
+/// 
+/// # Arguments
+/// * `asol_at_nav` - aSOL the deposit buys at NAV, fee included
+/// * `asol_supply` - aSOL supply before the mint
+/// * `recovery_bonus_bps` - Discount to NAV while CR < min
+/// * `max_dilution_bps` - Largest NAV drop one mint may cause existing holders
+/// 
+/// # Returns
+/// The discount bonus, cut back so that post-mint NAV stays at or above
+/// `nav * (1 - max_dilution_bps)`. None for either bound at 100% or more.
+pub fn compute_recovery_bonus_asol(
+  asol_at_nav: u64,
+  asol_supply: u64,
+  recovery_bonus_bps: u64,
+  max_dilution_bps: u64,
+) -> Option<u64> {
+  let bonus = compute_rebalance_bonus(asol_at_nav, recovery_bonus_bps)?;
+  // NAV' = equity / (S + a + bonus) >= NAV * (1 - d)  <=>  bonus <= (S + a) * d / (1 - d)
+  let dilution_cap = compute_rebalance_bonus(asol_supply.checked_add(asol_at_nav)?, max_dilution_bps)?;
+  Some(bonus.min(dilution_cap))
+}
+
+/// Split the vault between the tranches at global settlement
+/// 
+/// # Arguments
+/// * `total_lst_amount` - Vault LST
+/// * `lst_to_sol_rate` - Frozen LST->SOL rate
+/// * `amusd_supply` - Outstanding amUSD
+/// * `asol_supply` - Outstanding aSOL
+/// * `sol_price_usd` - Frozen SOL price
+/// 
+/// # Returns
+/// `(amusd_lst, asol_lst)`, summing to `total_lst_amount`. amUSD is senior
+/// and takes par (rounded up) or the whole vault if that is less; aSOL gets
+/// the rest, reserve included. A tranche with no supply left cedes its share.
+pub fn compute_settlement_pools(
+  total_lst_amount: u64,
+  lst_to_sol_rate: u64,
+  amusd_supply: u64,
+  asol_supply: u64,
+  sol_price_usd: u64,
+) -> Option<(u64, u64)> {
+  let amusd_lst = if asol_supply == 0 {
+    total_lst_amount
+  } else {
+    let liability = compute_liability_sol(amusd_supply, sol_price_usd)?;
+    mul_div_up(liability, SOL_PRECISION, lst_to_sol_rate)?.min(total_lst_amount)
+  };
+  Some((amusd_lst, total_lst_amount - amusd_lst))
+}
+
+/// Redemption rate that writes amUSD down to what the book can pay
+/// 
+/// # Arguments
+/// * `redemption_rate_bps` - Current rate, in bps of par
+/// * `cr_bps` - CR at the current rate, below 100%
+/// 
+/// # Returns
+/// The rate scaled by CR, rounded down, less one bps of slack so the
+/// rounded-up liability cannot leave CR a hair under 100%. Applied once, it
+/// spreads the shortfall pro-rata over every holder. Unchanged from 100% up.
+pub fn compute_socialized_rate_bps(redemption_rate_bps: u64, cr_bps: u64) -> Option<u64> {
+  if cr_bps >= BPS_PRECISION {
+    return Some(redemption_rate_bps);
+  }
+  Some(mul_div_down(redemption_rate_bps, cr_bps, BPS_PRECISION)?.saturating_sub(1))
+}
+
+/// Reserve credit for a voluntary dust donation
+/// 
+/// # Arguments
+/// * `donated_value_lamports` - Donated value, rounded down
+/// * `claimable_equity_after` - Claimable equity once the donation is burned
+/// * `current_rounding_reserve` - Reserve before the credit
+/// * `max_rounding_reserve` - Reserve cap
+/// 
+/// # Returns
+/// Lamports moved into the reserve. Never more than the donation, never more
+/// than the claimable equity backing it, never past the cap; whatever is not
+/// credited stays with claimable equity.
+pub fn compute_dust_donation_credit(
+  donated_value_lamports: u64,
+  claimable_equity_after: u64,
+  current_rounding_reserve: u64,
+  max_rounding_reserve: u64,
+) -> u64 {
+  let headroom = max_rounding_reserve.saturating_sub(current_rounding_reserve);
+  donated_value_lamports.min(claimable_equity_after).min(headroom)
+}
+
+/// Move `amount` from `from_decimals` to `to_decimals` base units, rounding down
+pub fn rescale_decimals_down(amount: u64, from_decimals: u8, to_decimals: u8) -> Option<u64> {
+  if from_decimals >= to_decimals {
+    let divisor = 10u64.checked_pow(u32::from(from_decimals - to_decimals))?;
+    Some(amount / divisor)
+  } else {
+    amount.checked_mul(10u64.checked_pow(u32::from(to_decimals - from_decimals))?)
+  }
+}
+
+/// PSM swap of USDC for amUSD at par
+/// 
+/// # Returns
+/// (amusd_minted, amusd_to_user, fee). `amusd_minted` is the deposit
+/// rescaled to amUSD decimals, rounded down, so every amUSD minted (the
+/// treasury's fee included) is backed by at least its face value in USDC.
+pub fn compute_psm_swap_in(usdc_amount: u64, usdc_decimals: u8, fee_bps: u64) -> Option<(u64, u64, u64)> {
+  let amusd_minted = rescale_decimals_down(usdc_amount, usdc_decimals, AMUSD_DECIMALS)?;
+  let (amusd_to_user, fee) = apply_fee(amusd_minted, fee_bps)?;
+  Some((amusd_minted, amusd_to_user, fee))
+}
+
+/// PSM swap of amUSD for USDC at par
+/// 
+/// # Returns
+/// (amusd_burned, usdc_out, fee). The fee stays amUSD and goes to the
+/// treasury; the rest is burned and paid out rescaled to USDC decimals,
+/// rounded down, so the vault never pays more than the amUSD it retires.
+pub fn compute_psm_swap_out(amusd_amount: u64, usdc_decimals: u8, fee_bps: u64) -> Option<(u64, u64, u64)> {
+  let (amusd_burned, fee) = apply_fee(amusd_amount, fee_bps)?;
+  let usdc_out = rescale_decimals_down(amusd_burned, AMUSD_DECIMALS, usdc_decimals)?;
+  Some((amusd_burned, usdc_out, fee))
+}
+
+/// Apply a fee to an amount and return net amount + fee
+/// 
+/// Arguments
+/// * `amount` - Gross amount before fee
+/// * `fee_bps` - Fee in basis points (e.g., 50 = 0.5%)
+/// 
+/// # Returns 
+/// (net_amount, fee_amount)
+pub fn apply_fee(amount: u64, fee_bps: u64) -> Option<(u64, u64)> {
+  let fee_amount = mul_div_down(amount, fee_bps, BPS_PRECISION)?;
+  let net_amount = amount.checked_sub(fee_amount)?;
+  Some((net_amount, fee_amount))
+}
+
+/// Split a fee between the treasury and the insurance fund
+/// 
+/// Arguments
+/// * `fee` - Fee in the fee-bearing token
+/// * `split_insurance_bps` - Share routed to insurance
+/// 
+/// # Returns 
+/// (treasury_fee, insurance_fee); the insurance share rounds down
+pub fn split_fee(fee: u64, split_insurance_bps: u64) -> Option<(u64, u64)> {
+  if split_insurance_bps > BPS_PRECISION {
+    return None;
+  }
+  apply_fee(fee, split_insurance_bps)
+}
+
+/// Room left under a supply cap once `new_supply` is outstanding
+/// 
+/// Arguments
+/// * `new_supply` - Supply after the operation
+/// * `cap` - Configured ceiling; 0 means unlimited
+/// 
+/// # Returns 
+/// `u64::MAX` when uncapped, None when `new_supply` is over the cap
+pub fn compute_cap_headroom(new_supply: u64, cap: u64) -> Option<u64> {
+  if cap == 0 {
+    return Some(u64::MAX);
+  }
+  cap.checked_sub(new_supply)
+}
+
+/// Largest input up to `requested` that an order can be filled at
+/// 
+/// Arguments
+/// * `requested` - Input the caller asked for
+/// * `fits` - Whether an input can be filled; must hold for every input
+///   below one it holds for, except possibly `requested` itself
+/// 
+/// # Returns 
+/// `requested` when it fits, otherwise the largest smaller input that does
+/// (0 when none does)
+pub fn compute_partial_fill(requested: u64, fits: impl Fn(u64) -> bool) -> u64 {
+  if fits(requested) {
+    return requested;
+  }
+  let (mut low, mut high) = (0u64, requested);
+  while high - low > 1 {
+    let mid = low + (high - low) / 2;
+    if fits(mid) {
+      low = mid;
+    } else {
+      high = mid;
+    }
+  }
+  low
+}
+
+/// Split a deposit between the amUSD and aSOL legs of a paired mint so CR
+/// is unchanged
+/// 
+/// # Arguments
+/// * `lst_amount` - Total LST deposited
+/// * `collateral_weight_bps` - Weight of the deposited LST
+/// * `weighted_tvl` - Weighted TVL of the whole book before the deposit
+/// * `liability` - amUSD liability in SOL before the deposit
+/// 
+/// # Returns
+/// `(lst_to_amusd, lst_to_asol)`, summing to `lst_amount`. The amUSD leg
+/// adds weighted value `w * D` against new debt `a`, so CR holds when
+/// `a = w * D * liability / weighted_tvl`; the LST->SOL rate cancels. The
+/// amUSD leg rounds down, so rounding only ever nudges CR up. All of it goes
+/// to aSOL while there is no debt. None when `weighted_tvl` is zero or CR is
+/// below the LST's weight, where no split holds CR.
+pub fn compute_cr_neutral_split(
+  lst_amount: u64,
+  collateral_weight_bps: u64,
+  weighted_tvl: u64,
+  liability: u64,
+) -> Option<(u64, u64)> {
+  if liability == 0 {
+    return Some((0, lst_amount));
+  }
+  if weighted_tvl == 0 {
+    return None;
+  }
+  let lst_to_amusd = (lst_amount as u128)
+    .checked_mul(collateral_weight_bps as u128)?
+    .checked_mul(liability as u128)?
+    .checked_div((BPS_PRECISION as u128).checked_mul(weighted_tvl as u128)?)?;
+  let lst_to_amusd = u64::try_from(lst_to_amusd).ok().filter(|&amusd| amusd <= lst_amount)?;
+  Some((lst_to_amusd, lst_amount - lst_to_amusd))
+}
+
+/// Size the aSOL leg of a paired redemption so CR returns to where it was
+/// before the amUSD leg
+///
+/// # Arguments
+/// * `weighted_tvl_before` - Weighted TVL of the whole book before the pair
+/// * `liability_before` - amUSD liability in SOL before the pair
+/// * `weighted_tvl` - Weighted TVL after the amUSD leg
+/// * `liability` - amUSD liability after the amUSD leg
+/// * `collateral_weight_bps` - Weight of the LST paid out
+/// * `asol_nav` - aSOL NAV in lamports per aSOL unit
+///
+/// # Returns
+/// aSOL units whose payout takes the weighted TVL back down to
+/// `weighted_tvl_before * liability / liability_before`. Rounds down at
+/// every step, so the pair leaves CR at or above where it started. Zero
+/// when the amUSD leg did not lift CR. None when there was no debt, the
+/// weight is zero or aSOL is worthless, where no aSOL leg restores CR.
+pub fn compute_cr_neutral_redeem(
+  weighted_tvl_before: u64,
+  liability_before: u64,
+  weighted_tvl: u64,
+  liability: u64,
+  collateral_weight_bps: u64,
+  asol_nav: u64,
+) -> Option<u64> {
+  if liability_before == 0 || collateral_weight_bps == 0 || asol_nav == 0 {
+    return None;
+  }
+  let weighted_target = mul_div_up(weighted_tvl_before, liability, liability_before)?;
+  let weighted_excess = weighted_tvl.saturating_sub(weighted_target);
+  let sol_out = mul_div_down(weighted_excess, BPS_PRECISION, collateral_weight_bps)?;
+  mul_div_down(sol_out, SOL_PRECISION, asol_nav)
+}
+
+/// aSOL worth `amusd_amount` of debt, with amUSD at $1 and aSOL at NAV
+///
+/// # Arguments
+/// * `amusd_amount` - amUSD burned (USD_PRECISION units)
+/// * `sol_price_usd` - SOL price in USD (USD_PRECISION)
+/// * `asol_nav` - aSOL NAV in lamports per aSOL unit
+///
+/// # Returns
+/// aSOL units, rounded down at both steps so the conversion never mints
+/// more equity than the burned debt was worth. None when either price is zero.
+pub fn compute_amusd_to_asol(amusd_amount: u64, sol_price_usd: u64, asol_nav: u64) -> Option<u64> {
+  if sol_price_usd == 0 || asol_nav == 0 {
+    return None;
+  }
+  let sol_value = mul_div_down(amusd_amount, SOL_PRECISION, sol_price_usd)?;
+  mul_div_down(sol_value, SOL_PRECISION, asol_nav)
+}
+
+/// amUSD worth `asol_amount` of equity, with aSOL at NAV and amUSD at $1
+///
+/// # Arguments
+/// * `asol_amount` - aSOL burned (SOL_PRECISION units)
+/// * `asol_nav` - aSOL NAV in lamports per aSOL unit
+/// * `sol_price_usd` - SOL price in USD (USD_PRECISION)
+///
+/// # Returns
+/// amUSD units, rounded down at both steps so the conversion never mints
+/// more debt than the burned equity was worth.
+pub fn compute_asol_to_amusd(asol_amount: u64, asol_nav: u64, sol_price_usd: u64) -> Option<u64> {
+  let sol_value = mul_div_down(asol_amount, asol_nav, SOL_PRECISION)?;
+  mul_div_down(sol_value, sol_price_usd, SOL_PRECISION)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_up_rounding() {
+        // 10 * 3 / 4 = 7.5, should round up to 8
+        assert_eq!(mul_div_up(10, 3, 4), Some(8));
+        
+        // Exact division should not add rounding
+        assert_eq!(mul_div_up(10, 4, 4), Some(10));
+    }
+
+    #[test]
+    fn test_mul_div_down_rounding() {
+        // 10 * 3 / 4 = 7.5, should round down to 7
+        assert_eq!(mul_div_down(10, 3, 4), Some(7));
+        
+        // Exact division
+        assert_eq!(mul_div_down(10, 4, 4), Some(10));
+    }
+
+    #[test]
+    fn test_mul_div_zero_divisor() {
+        // Division by zero should return None
+        assert_eq!(mul_div_up(10, 3, 0), None);
+        assert_eq!(mul_div_down(10, 3, 0), None);
+    }
+
+    #[test]
+    fn test_compute_cr_bps_basic() {
+        // TVL = 200 SOL, Liability = 100 SOL
+        // CR = 200 / 100 = 200% = 20000 bps
+        let tvl = 200 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        assert_eq!(compute_cr_bps(tvl, liability), 20_000);
+    }
+
+    #[test]
+    fn test_compute_cr_bps_exactly_150_percent() {
+        // TVL = 150 SOL, Liability = 100 SOL
+        // CR = 150% = 15000 bps
+        let tvl = 150 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        assert_eq!(compute_cr_bps(tvl, liability), 15_000);
+    }
+
+    #[test]
+    fn test_compute_cr_bps_undercollateralized() {
+        // TVL = 120 SOL, Liability = 100 SOL
+        // CR = 120% = 12000 bps
+        let tvl = 120 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        assert_eq!(compute_cr_bps(tvl, liability), 12_000);
+    }
+
+    #[test]
+    fn test_compute_cr_bps_zero_liability() {
+        // No debt = CR is undefined, return 0
+        let tvl = 100 * SOL_PRECISION;
+        assert_eq!(compute_cr_bps(tvl, 0), u64::MAX);
+    }
+
+    #[test]
+    fn test_compute_equity_sol_positive() {
+        // TVL = 200 SOL, Liability = 100 SOL
+        // Equity = 100 SOL
+        let tvl = 200 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        assert_eq!(compute_equity_sol(tvl, liability), 100 * SOL_PRECISION);
+    }
+
+    #[test]
+    fn test_compute_equity_sol_zero_when_insolvent() {
+        // TVL = 80 SOL, Liability = 100 SOL
+        // Equity = 0 (not negative)
+        let tvl = 80 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        assert_eq!(compute_equity_sol(tvl, liability), 0);
+    }
+
+    #[test]
+    fn test_nav_asol_at_various_leverage() {
+        // Scenario: TVL = 200 SOL, Liability = 100 SOL, aSOL supply = 100
+        let tvl = 200 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        let asol_supply = 100 * SOL_PRECISION;
+        
+        // Equity = 100 SOL, NAV = 100/100 = 1 SOL per aSOL
+        assert_eq!(nav_asol(tvl, liability, asol_supply), Some(SOL_PRECISION));
+    }
+
+    #[test]
+    fn test_nav_asol_high_leverage() {
+        // Scenario: TVL = 200 SOL, Liability = 180 SOL, aSOL supply = 20
+        let tvl = 200 * SOL_PRECISION;
+        let liability = 180 * SOL_PRECISION;
+        let asol_supply = 20 * SOL_PRECISION;
+        
+        // Equity = 20 SOL, NAV = 20/20 = 1 SOL per aSOL
+        assert_eq!(nav_asol(tvl, liability, asol_supply), Some(SOL_PRECISION));
+    }
+
+    #[test]
+    fn test_nav_asol_zero_when_insolvent() {
+        // TVL < Liability should return NAV = 0
+        let tvl = 90 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        let asol_supply = 50 * SOL_PRECISION;
+        
+        assert_eq!(nav_asol(tvl, liability, asol_supply), Some(0));
+    }
+
+    #[test]
+    fn test_nav_asol_zero_supply_edge_case() {
+        // First mint case - no aSOL exists yet
+        let tvl = 100 * SOL_PRECISION;
+        let liability = 0;
+        let asol_supply = 0;
+        
+        assert_eq!(nav_asol(tvl, liability, asol_supply), None);
+    }
+
+    #[test]
+    fn test_simulate_40_percent_price_drop() {
+        // Initial state: TVL = 200 SOL, Liability = 100 SOL
+        let initial_tvl = 200 * SOL_PRECISION;
+        let liability = 100 * SOL_PRECISION;
+        let asol_supply = 100 * SOL_PRECISION;
+        
+        // Initial CR = 200%
+        assert_eq!(compute_cr_bps(initial_tvl, liability), 20_000);
+        
+        // Initial aSOL NAV = 1.0 SOL
+        assert_eq!(nav_asol(initial_tvl, liability, asol_supply), Some(SOL_PRECISION));
+        
+        // Simulate 40% SOL price drop (TVL drops to 120 SOL)
+        let crashed_tvl = 120 * SOL_PRECISION;
+        
+        // New CR = 120%
+        assert_eq!(compute_cr_bps(crashed_tvl, liability), 12_000);
+        
+        // New aSOL NAV = (120 - 100) / 100 = 0.2 SOL
+        // Equity absorbed the entire loss
+        let new_nav = nav_asol(crashed_tvl, liability, asol_supply);
+        assert_eq!(new_nav, Some(SOL_PRECISION / 5)); // 0.2 SOL
+    }
+
+    #[test]
+    fn test_simulate_60_percent_price_drop() {
+        // Initial state: TVL = 200 SOL, Liability = 100 SOL
+        let liability = 100 * SOL_PRECISION;
+        let asol_supply = 100 * SOL_PRECISION;
+        
+        // Simulate 60% SOL price drop (TVL drops to 80 SOL)
+        let crashed_tvl = 80 * SOL_PRECISION;
+        
+        // New CR = 80% (insolvent!)
+        assert_eq!(compute_cr_bps(crashed_tvl, liability), 8_000);
+        
+        // aSOL NAV should be 0 (TVL < Liability)
+        assert_eq!(nav_asol(crashed_tvl, liability, asol_supply), Some(0));
+    }
+
+    #[test]
+    fn test_apply_fee_half_percent() {
+        let amount = 1_000_000;
+        let fee_bps = 50; // 0.5%
+        
+        let (net, fee) = apply_fee(amount, fee_bps).unwrap();
+        
+        assert_eq!(fee, 5_000); // 0.5% of 1M
+        assert_eq!(net, 995_000);
+        assert_eq!(net + fee, amount); // Conservation check
+    }
+
+    #[test]
+    fn test_apply_fee_zero() {
+        let amount = 1_000_000;
+        let fee_bps = 0;
+        
+        let (net, fee) = apply_fee(amount, fee_bps).unwrap();
+        
+        assert_eq!(fee, 0);
+        assert_eq!(net, amount);
+    }
+
+    #[test]
+    fn test_split_fee() {
+        assert_eq!(split_fee(1_000, 0), Some((1_000, 0)));
+        assert_eq!(split_fee(1_000, 2_500), Some((750, 250)));
+        assert_eq!(split_fee(999, 5_000), Some((500, 499)));
+        assert_eq!(split_fee(1_000, BPS_PRECISION), Some((0, 1_000)));
+        assert_eq!(split_fee(1_000, BPS_PRECISION + 1), None);
+    }
+
+    #[test]
+    fn test_compute_cap_headroom() {
+        assert_eq!(compute_cap_headroom(u64::MAX, 0), Some(u64::MAX));
+        assert_eq!(compute_cap_headroom(400, 1_000), Some(600));
+        assert_eq!(compute_cap_headroom(1_000, 1_000), Some(0));
+        assert_eq!(compute_cap_headroom(1_001, 1_000), None);
+    }
+
+    #[test]
+    fn test_compute_partial_fill() {
+        assert_eq!(compute_partial_fill(1_000, |units| units <= 1_000), 1_000);
+        assert_eq!(compute_partial_fill(1_000, |units| units * 3 <= 1_000), 333);
+        assert_eq!(compute_partial_fill(1_000, |_| false), 0);
+        // A full exit may fit where a slightly smaller order does not
+        assert_eq!(compute_partial_fill(1_000, |units| units == 1_000 || units <= 10), 1_000);
+        assert_eq!(compute_partial_fill(u64::MAX, |units| units < u64::MAX / 2), u64::MAX / 2 - 1);
+    }
+
+    #[test]
+    fn test_compute_cr_neutral_split() {
+        // CR 150% at full weight: two thirds of the deposit backs new debt
+        assert_eq!(compute_cr_neutral_split(3_000, BPS_PRECISION, 150 * SOL_PRECISION, 100 * SOL_PRECISION), Some((2_000, 1_000)));
+        // An 80% weight buys less debt per LST
+        assert_eq!(compute_cr_neutral_split(3_000, 8_000, 150 * SOL_PRECISION, 100 * SOL_PRECISION), Some((1_600, 1_400)));
+        // The amUSD leg rounds down
+        assert_eq!(compute_cr_neutral_split(10, BPS_PRECISION, 3, 1), Some((3, 7)));
+        // No debt, all equity
+        assert_eq!(compute_cr_neutral_split(3_000, BPS_PRECISION, 150 * SOL_PRECISION, 0), Some((0, 3_000)));
+        assert_eq!(compute_cr_neutral_split(3_000, BPS_PRECISION, 0, 100 * SOL_PRECISION), None);
+        // CR under the deposit's weight cannot be held
+        assert_eq!(compute_cr_neutral_split(3_000, 8_000, 70 * SOL_PRECISION, 100 * SOL_PRECISION), None);
+    }
+
+    #[test]
+    fn test_compute_cr_neutral_split_holds_cr() {
+        let rate = 1_050_000_000;
+        let price = 100 * USD_PRECISION;
+        let supply = 1_000_000 * USD_PRECISION; // 10_000 SOL liability
+        for book_lst in [11_000 * SOL_PRECISION, 16_000 * SOL_PRECISION, 40_000 * SOL_PRECISION] {
+            for deposit in [SOL_PRECISION / 10, 7 * SOL_PRECISION, 5_000 * SOL_PRECISION] {
+                let tvl = compute_tvl_sol(book_lst, rate).unwrap();
+                let liability = compute_liability_sol(supply, price).unwrap();
+                let (to_amusd, to_asol) = compute_cr_neutral_split(deposit, BPS_PRECISION, tvl, liability).unwrap();
+                assert_eq!(to_amusd + to_asol, deposit);
+
+                let amusd_minted = mul_div_down(compute_tvl_sol(to_amusd, rate).unwrap(), price, SOL_PRECISION).unwrap();
+                let new_tvl = compute_tvl_sol(book_lst + deposit, rate).unwrap();
+                let new_liability = compute_liability_sol(supply + amusd_minted, price).unwrap();
+                let (before, after) = (compute_cr_bps(tvl, liability), compute_cr_bps(new_tvl, new_liability));
+                assert!(before.abs_diff(after) <= 1, "{before} -> {after}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_cr_neutral_redeem() {
+        let nav = 2 * SOL_PRECISION;
+        // 150 / 100 -> 140 / 80 leaves 20 SOL over the 120 SOL target: 10 aSOL at NAV 2
+        assert_eq!(
+            compute_cr_neutral_redeem(150 * SOL_PRECISION, 100 * SOL_PRECISION, 140 * SOL_PRECISION, 80 * SOL_PRECISION, BPS_PRECISION, nav),
+            Some(10 * SOL_PRECISION)
+        );
+        // At 80% weight each SOL paid out only drops 0.8 weighted SOL
+        assert_eq!(
+            compute_cr_neutral_redeem(150 * SOL_PRECISION, 100 * SOL_PRECISION, 140 * SOL_PRECISION, 80 * SOL_PRECISION, 8_000, nav),
+            Some(12_500_000_000)
+        );
+        // The amUSD leg left CR where it was, or lowered it
+        assert_eq!(compute_cr_neutral_redeem(150, 100, 120, 80, BPS_PRECISION, nav), Some(0));
+        assert_eq!(compute_cr_neutral_redeem(150, 100, 110, 80, BPS_PRECISION, nav), Some(0));
+        // The target rounds up and the size down
+        assert_eq!(compute_cr_neutral_redeem(3, 2, 4, 1, BPS_PRECISION, SOL_PRECISION), Some(2));
+        assert_eq!(compute_cr_neutral_redeem(150, 0, 140, 0, BPS_PRECISION, nav), None);
+        assert_eq!(compute_cr_neutral_redeem(150, 100, 140, 80, 0, nav), None);
+        assert_eq!(compute_cr_neutral_redeem(150, 100, 140, 80, BPS_PRECISION, 0), None);
+    }
+
+    #[test]
+    fn test_compute_cr_neutral_redeem_holds_cr() {
+        let rate = 1_050_000_000;
+        let price = 100 * USD_PRECISION;
+        let supply = 1_000_000 * USD_PRECISION; // 10_000 SOL liability
+        let asol_supply = 2_000 * SOL_PRECISION;
+        for book_lst in [11_000 * SOL_PRECISION, 16_000 * SOL_PRECISION, 40_000 * SOL_PRECISION] {
+            for redeemed in [USD_PRECISION, 7_000 * USD_PRECISION, 500_000 * USD_PRECISION] {
+                let tvl = compute_tvl_sol(book_lst, rate).unwrap();
+                let liability = compute_liability_sol(supply, price).unwrap();
+                let nav = nav_asol_with_reserve(tvl, liability, 0, asol_supply).unwrap();
+
+                let lst_out = mul_div_down(compute_liability_sol(redeemed, price).unwrap(), SOL_PRECISION, rate).unwrap();
+                let mid_tvl = compute_tvl_sol(book_lst - lst_out, rate).unwrap();
+                let new_liability = compute_liability_sol(supply - redeemed, price).unwrap();
+                let asol = compute_cr_neutral_redeem(tvl, liability, mid_tvl, new_liability, BPS_PRECISION, nav).unwrap();
+
+                let asol_lst_out = mul_div_down(mul_div_down(asol, nav, SOL_PRECISION).unwrap(), SOL_PRECISION, rate).unwrap();
+                let new_tvl = compute_tvl_sol(book_lst - lst_out - asol_lst_out, rate).unwrap();
+                let (before, after) = (compute_cr_bps(tvl, liability), compute_cr_bps(new_tvl, new_liability));
+                assert!(after >= before && after - before <= 1, "{before} -> {after}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_amusd_to_asol() {
+        let price = 100 * USD_PRECISION;
+        // $150 is 1.5 SOL, at NAV 1.2 that is 1.25 aSOL
+        assert_eq!(compute_amusd_to_asol(150 * USD_PRECISION, price, 1_200_000_000), Some(1_250_000_000));
+        // Rounds down at both steps: $0.000001 is 10 lamports, 3 aSOL units at NAV 3
+        assert_eq!(compute_amusd_to_asol(1, price, 3 * SOL_PRECISION), Some(3));
+        assert_eq!(compute_amusd_to_asol(0, price, SOL_PRECISION), Some(0));
+        assert_eq!(compute_amusd_to_asol(USD_PRECISION, 0, SOL_PRECISION), None);
+        assert_eq!(compute_amusd_to_asol(USD_PRECISION, price, 0), None);
+    }
+
+    #[test]
+    fn test_compute_asol_to_amusd() {
+        let price = 100 * USD_PRECISION;
+        // 1.25 aSOL at NAV 1.2 is 1.5 SOL, $150
+        assert_eq!(compute_asol_to_amusd(1_250_000_000, 1_200_000_000, price), Some(150 * USD_PRECISION));
+        // Under 10 lamports is under one amUSD unit at $100
+        assert_eq!(compute_asol_to_amusd(9, SOL_PRECISION, price), Some(0));
+        assert_eq!(compute_asol_to_amusd(SOL_PRECISION, 0, price), Some(0));
+        assert_eq!(compute_asol_to_amusd(u64::MAX, u64::MAX, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_conversion_round_trip_never_gains() {
+        let nav = 1_234_567_891;
+        for price in [USD_PRECISION, 97 * USD_PRECISION + 13, 250 * USD_PRECISION] {
+            for amusd in [1, 7_777, 100 * USD_PRECISION, 1_000_003 * USD_PRECISION] {
+                let asol = compute_amusd_to_asol(amusd, price, nav).unwrap();
+                assert!(compute_asol_to_amusd(asol, nav, price).unwrap() <= amusd);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_book_tvl_sol() {
+        let rate = 1_050_000_000;
+        assert_eq!(compute_book_tvl_sol(10 * SOL_PRECISION, rate, 0), compute_tvl_sol(10 * SOL_PRECISION, rate));
+        assert_eq!(compute_book_tvl_sol(10 * SOL_PRECISION, rate, 7), Some(10_500_000_007));
+        // Each vault rounds on its own: 1 lamport at 1.05 is worth 1
+        assert_eq!(compute_book_tvl_sol(1, rate, 1), Some(2));
+        assert_eq!(compute_book_tvl_sol(u64::MAX, SOL_PRECISION, 1), None);
+    }
+
+    #[test]
+    fn test_compute_weighted_tvl_sol() {
+        let rate = 1_050_000_000;
+        // Full weight is the unweighted value
+        assert_eq!(compute_weighted_tvl_sol(10 * SOL_PRECISION, rate, BPS_PRECISION), compute_tvl_sol(10 * SOL_PRECISION, rate));
+        // 10.5 SOL at 80% is 8.4 SOL
+        assert_eq!(compute_weighted_tvl_sol(10 * SOL_PRECISION, rate, 8_000), Some(8_400_000_000));
+        assert_eq!(compute_weighted_tvl_sol(10 * SOL_PRECISION, rate, 0), Some(0));
+        // Rounds down twice: 3 lamports at 1.05 is 3, at 50% is 1
+        assert_eq!(compute_weighted_tvl_sol(3, rate, 5_000), Some(1));
+        assert_eq!(compute_weighted_tvl_sol(u64::MAX, 2 * SOL_PRECISION, BPS_PRECISION), None);
+    }
+
+    #[test]
+    fn test_compute_liability_sol() {
+        // amUSD supply = 100,000 (with USD_PRECISION = 1e6)
+        // SOL price = $100 (with USD_PRECISION = 1e6)
+        // Expected liability = 100,000 / 100 = 1,000 SOL = 1,000 * SOL_PRECISION lamports
+        
+        let amusd_supply = 100_000 * USD_PRECISION;
+        let sol_price = 100 * USD_PRECISION;
+        
+        let liability = compute_liability_sol(amusd_supply, sol_price).unwrap();
+        assert_eq!(liability, 1_000 * SOL_PRECISION);
+    }
+
+    #[test]
+    fn test_compute_liability_at_rate_sol() {
+        let amusd_supply = 100_000 * USD_PRECISION;
+        let sol_price = 100 * USD_PRECISION;
+        assert_eq!(
+            compute_liability_at_rate_sol(amusd_supply, sol_price, BPS_PRECISION),
+            compute_liability_sol(amusd_supply, sol_price)
+        );
+        assert_eq!(compute_liability_at_rate_sol(amusd_supply, sol_price, 9_000).unwrap(), 900 * SOL_PRECISION);
+        // One base unit at a 0.01% write-down still owes its full par liability.
+        assert_eq!(compute_liability_at_rate_sol(1, sol_price, 9_999), compute_liability_sol(1, sol_price));
+        assert_eq!(compute_liability_at_rate_sol(amusd_supply, sol_price, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_compute_socialized_rate_bps() {
+        assert_eq!(compute_socialized_rate_bps(BPS_PRECISION, 9_000), Some(8_999));
+        assert_eq!(compute_socialized_rate_bps(9_000, 5_000), Some(4_499));
+        assert_eq!(compute_socialized_rate_bps(9_999, 9_999), Some(9_997));
+        assert_eq!(compute_socialized_rate_bps(BPS_PRECISION, 0), Some(0));
+        // Nothing to write down from 100% up.
+        assert_eq!(compute_socialized_rate_bps(9_000, BPS_PRECISION), Some(9_000));
+        assert_eq!(compute_socialized_rate_bps(9_000, 12_000), Some(9_000));
+
+        let tvl = 900 * SOL_PRECISION;
+        let amusd_supply = 100_000 * USD_PRECISION;
+        let sol_price = 100 * USD_PRECISION;
+        let cr_bps = compute_cr_bps(tvl, compute_liability_sol(amusd_supply, sol_price).unwrap());
+        assert_eq!(cr_bps, 9_000);
+        let rate = compute_socialized_rate_bps(BPS_PRECISION, cr_bps).unwrap();
+        let liability = compute_liability_at_rate_sol(amusd_supply, sol_price, rate).unwrap();
+        assert_eq!(compute_cr_bps(tvl, liability), BPS_PRECISION + 1);
+    }
+
+    #[test]
+    fn test_nav_amusd() {
+        // SOL price = $100
+        // amUSD NAV should be 1/100 = 0.01 SOL = 0.01 * SOL_PRECISION lamports
+        
+        let sol_price = 100 * USD_PRECISION;
+        let nav = nav_amusd(sol_price).unwrap();
+        
+        assert_eq!(nav, SOL_PRECISION / 100);
+    }
+
+    #[test]
+    fn test_fee_bps_increase_when_low() {
+        let base = 100u64;
+        let target = 15_000u64;
+
+        // At or above target, fee stays base
+        assert_eq!(fee_bps_increase_when_low(base, 15_000, target), base);
+        assert_eq!(fee_bps_increase_when_low(base, 20_000, target), base);
+
+        // Below target, fee scales up: base * (target / cr)
+        assert_eq!(fee_bps_increase_when_low(base, 10_000, target), 150);
+
+        // Extreme low CR should be capped by MAX_FEE_MULTIPLIER_BPS (4x)
+        assert_eq!(fee_bps_increase_when_low(base, 1_000, target), 400);
+    }
+
+    #[test]
+    fn test_fee_bps_decrease_when_low() {
+        let base = 100u64;
+        let target = 15_000u64;
+
+        // At or above target, fee stays base
+        assert_eq!(fee_bps_decrease_when_low(base, 15_000, target), base);
+        assert_eq!(fee_bps_decrease_when_low(base, 20_000, target), base);
+
+        // Below target, fee scales down: base * (cr / target)
+        assert_eq!(fee_bps_decrease_when_low(base, 10_000, target), 66);
+
+        // Very low CR can reduce fee to zero
+        assert_eq!(fee_bps_decrease_when_low(base, 0, target), 0);
+    }
+
+    #[test]
+    fn test_compute_liability_sol_rounds_up_fractional_case() {
+        // $1 / $3 => 333_333_333.333... lamports, must ceil.
+        let amusd_supply = USD_PRECISION;
+        let sol_price = 3 * USD_PRECISION;
+
+        let liability = compute_liability_sol(amusd_supply, sol_price).unwrap();
+        assert_eq!(liability, 333_333_334);
+    }
+
+    #[test]
+    fn test_compute_rounding_delta_units() {
+        assert_eq!(compute_rounding_delta_units(100, 100), Some(0));
+        assert_eq!(compute_rounding_delta_units(100, 101), Some(1));
+    }
+
+    #[test]
+    fn test_usd_dust_to_lamports_up() {
+        // 1 micro-USD at $100/SOL => 10 lamports (ceil)
+        let lamports = usd_dust_to_lamports_up(1, 100 * USD_PRECISION).unwrap();
+        assert_eq!(lamports, 10);
+    }
+
+    #[test]
+    fn test_compute_dynamic_fee_reports_its_multipliers() {
+        // Yellow midpoint with a 2% confidence band: 1.5x from CR, 1.2x from uncertainty.
+        let fee = compute_dynamic_fee(100, FeeAction::AmusdMint, 14_000, 13_000, 15_000, 5_000, 20_000, 200, 20_000).unwrap();
+        assert_eq!(fee.cr_multiplier_bps, 15_000);
+        assert_eq!(fee.uncertainty_multiplier_bps, 12_000);
+        assert_eq!(fee.fee_bps, 180);
+        assert_eq!(
+            compute_dynamic_fee_bps(100, FeeAction::AmusdMint, 14_000, 13_000, 15_000, 5_000, 20_000, 200, 20_000),
+            Some(fee.fee_bps)
+        );
+
+        // Risk-reducing actions ignore uncertainty.
+        let fee = compute_dynamic_fee(100, FeeAction::AmUSDRedeem, 14_000, 13_000, 15_000, 5_000, 20_000, 200, 20_000).unwrap();
+        assert_eq!(fee.cr_multiplier_bps, 7_500);
+        assert_eq!(fee.uncertainty_multiplier_bps, BPS_PRECISION);
+        assert_eq!(fee.fee_bps, 75);
+
+        // No base fee, no multipliers.
+        assert_eq!(
+            compute_dynamic_fee(0, FeeAction::AmusdMint, 14_000, 13_000, 15_000, 5_000, 20_000, 200, 20_000),
+            Some(DynamicFee::NONE)
+        );
+    }
+
+        #[test]
+    fn test_dynamic_fee_curve_all_actions_green_yellow_red() {
+        let base = 100u64;
+        let min_cr = 13_000u64;
+        let target_cr = 15_000u64;
+        let mmin = 5_000u64;  // 0.5x
+        let mmax = 20_000u64; // 2.0x
+        let unc_idx = 0u64;
+        let unc_max = 20_000u64;
+
+        // Green (CR >= target): base fee
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmusdMint, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolRedeem, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmUSDRedeem, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolMint, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+
+        // Yellow midpoint CR=14_000 (halfway): 1.5x for risk-increasing, 0.75x for risk-reducing.
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmusdMint, 14_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(150)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolRedeem, 14_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(150)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmUSDRedeem, 14_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(75)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolMint, 14_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(75)
+        );
+
+        // Red (CR <= min): clamp to extreme multipliers.
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmusdMint, 12_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(200)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolRedeem, 12_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(200)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmUSDRedeem, 12_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(50)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolMint, 12_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(50)
+        );
+    }
+
+    #[test]
+    fn test_uncertainty_applies_only_to_risk_increasing_actions() {
+        let base = 100u64;
+        let min_cr = 13_000u64;
+        let target_cr = 15_000u64;
+        let mmin = 10_000u64;
+        let mmax = 40_000u64;
+
+        // uncertainty_up = 10_000 + floor(500 * 10_000 / 1000) = 15_000 (1.5x)
+        let unc_idx = 500u64;
+        let unc_max = 20_000u64;
+
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmusdMint, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(150)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolRedeem, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(150)
+        );
+
+        // Risk reducing should stay neutral under uncertainty.
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AmUSDRedeem, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+        assert_eq!(
+            compute_dynamic_fee_bps(base, FeeAction::AsolMint, 16_000, min_cr, target_cr, mmin, mmax, unc_idx, unc_max),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_derive_uncertainty_index_bps() {
+        // $0.15 on $150 is 10 bps
+        assert_eq!(derive_uncertainty_index_bps(150_000, 150_000_000), Some(10));
+        assert_eq!(derive_uncertainty_index_bps(0, 150_000_000), Some(0));
+        // Rounds up
+        assert_eq!(derive_uncertainty_index_bps(1, 150_000_000), Some(1));
+        // Clamped at a confidence as wide as the price
+        assert_eq!(derive_uncertainty_index_bps(150_000_000, 150_000_000), Some(MAX_UNCERTAINTY_INDEX_BPS));
+        assert_eq!(derive_uncertainty_index_bps(u64::MAX, 1), Some(MAX_UNCERTAINTY_INDEX_BPS));
+        assert_eq!(derive_uncertainty_index_bps(1, 0), None);
+
+        // A wider interval makes risk-increasing actions dearer
+        let calm = derive_uncertainty_index_bps(0, 150_000_000).unwrap();
+        let noisy = derive_uncertainty_index_bps(2_250_000, 150_000_000).unwrap();
+        assert_eq!(noisy, 150);
+        assert!(
+            derive_uncertainty_multiplier_bps(FeeAction::AmusdMint, noisy, 20_000)
+                > derive_uncertainty_multiplier_bps(FeeAction::AmusdMint, calm, 20_000)
+        );
+    }
+
+    #[test]
+    fn test_combine_uncertainty_sources() {
+        // No flash activity leaves the oracle index alone
+        assert_eq!(combine_uncertainty_sources(150, 0), Some(150));
+        assert_eq!(combine_uncertainty_sources(0, 0), Some(0));
+        // Half the vault lent adds half the weight
+        assert_eq!(combine_uncertainty_sources(0, 5_000), Some(500));
+        assert_eq!(combine_uncertainty_sources(150, 5_000), Some(650));
+        // Rounds up: 1 bps of utilization is still visible
+        assert_eq!(combine_uncertainty_sources(0, 1), Some(1));
+        // Utilization is capped at 100%, the sum at the index ceiling
+        assert_eq!(combine_uncertainty_sources(0, u64::MAX), Some(FLASH_UTILIZATION_UNCERTAINTY_WEIGHT_BPS));
+        assert_eq!(combine_uncertainty_sources(MAX_UNCERTAINTY_INDEX_BPS, 5_000), Some(MAX_UNCERTAINTY_INDEX_BPS));
+        assert_eq!(combine_uncertainty_sources(u64::MAX, 1), None);
+
+        // Heavy flash use makes risk-increasing actions dearer, never cheaper ones
+        let calm = combine_uncertainty_sources(0, 0).unwrap();
+        let busy = combine_uncertainty_sources(0, 5_000).unwrap();
+        assert!(
+            derive_uncertainty_multiplier_bps(FeeAction::AmusdMint, busy, 20_000)
+                > derive_uncertainty_multiplier_bps(FeeAction::AmusdMint, calm, 20_000)
+        );
+        assert_eq!(
+            derive_uncertainty_multiplier_bps(FeeAction::AmUSDRedeem, busy, 20_000),
+            Some(BPS_PRECISION)
+        );
+    }
+
+    #[test]
+    fn test_derive_redeem_haircut_bps() {
+        // Solvent: par redemption
+        assert_eq!(derive_redeem_haircut_bps(u64::MAX), (false, BPS_PRECISION));
+        assert_eq!(derive_redeem_haircut_bps(BPS_PRECISION), (false, BPS_PRECISION));
+
+        // Insolvent: haircut equals CR
+        assert_eq!(derive_redeem_haircut_bps(9_500), (true, 9_500));
+        assert_eq!(derive_redeem_haircut_bps(0), (true, 0));
+    }
+
+    #[test]
+    fn test_compute_price_deviation_bps() {
+        assert_eq!(compute_price_deviation_bps(100_000_000, 100_000_000), Some(0));
+        // Symmetric and relative to the lower price
+        assert_eq!(compute_price_deviation_bps(100_000_000, 101_000_000), Some(100));
+        assert_eq!(compute_price_deviation_bps(101_000_000, 100_000_000), Some(100));
+        // Rounds up: 1 / 3 of a bp counts as a full bp
+        assert_eq!(compute_price_deviation_bps(30_000, 30_001), Some(1));
+        assert_eq!(compute_price_deviation_bps(1, u64::MAX), Some(u64::MAX));
+        assert_eq!(compute_price_deviation_bps(0, 100), None);
+    }
+
+    #[test]
+    fn test_compute_ema_decay_bps() {
+        assert_eq!(compute_ema_decay_bps(0, 100), Some(BPS_PRECISION));
+        assert_eq!(compute_ema_decay_bps(50, 100), Some(7_500));
+        assert_eq!(compute_ema_decay_bps(100, 100), Some(5_000));
+        assert_eq!(compute_ema_decay_bps(200, 100), Some(2_500));
+        assert_eq!(compute_ema_decay_bps(u64::MAX, 1), Some(0));
+        // Disabled: no memory at all
+        assert_eq!(compute_ema_decay_bps(0, 0), Some(0));
+
+        // Monotone non-increasing in elapsed slots
+        let mut prev = BPS_PRECISION;
+        for elapsed in 0..1_000 {
+            let decay = compute_ema_decay_bps(elapsed, 150).unwrap();
+            assert!(decay <= prev);
+            prev = decay;
+        }
+    }
+
+    #[test]
+    fn test_compute_ema_price() {
+        // One half-life moves halfway to spot
+        assert_eq!(compute_ema_price(100_000_000, 90_000_000, 100, 100), Some(95_000_000));
+        assert_eq!(compute_ema_price(100_000_000, 90_000_000, 0, 100), Some(100_000_000));
+        assert_eq!(compute_ema_price(100_000_000, 90_000_000, 100_000, 100), Some(90_000_000));
+        // Seeding and the disabled half-life both land on spot
+        assert_eq!(compute_ema_price(0, 90_000_000, 0, 100), Some(90_000_000));
+        assert_eq!(compute_ema_price(100_000_000, 90_000_000, 0, 0), Some(90_000_000));
+        // Always between the two inputs
+        assert_eq!(compute_ema_price(u64::MAX, u64::MAX, 7, 3), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_compute_stability_fee_accrual() {
+        // 5% a year on 1M amUSD over a full year
+        let supply = 1_000_000 * USD_PRECISION;
+        assert_eq!(compute_stability_fee_accrual(supply, 500, SECONDS_PER_YEAR), Some(50_000 * USD_PRECISION));
+        // Linear in time: half a year accrues half
+        assert_eq!(compute_stability_fee_accrual(supply, 500, SECONDS_PER_YEAR / 2), Some(25_000 * USD_PRECISION));
+        // Nothing without a rate, a supply or elapsed time
+        assert_eq!(compute_stability_fee_accrual(supply, 0, SECONDS_PER_YEAR), Some(0));
+        assert_eq!(compute_stability_fee_accrual(0, 500, SECONDS_PER_YEAR), Some(0));
+        assert_eq!(compute_stability_fee_accrual(supply, 500, 0), Some(0));
+        // Rounds down: one second of 5% on 1 amUSD is under one base unit
+        assert_eq!(compute_stability_fee_accrual(USD_PRECISION, 500, 1), Some(0));
+        // Too large for u64
+        assert_eq!(compute_stability_fee_accrual(u64::MAX, 10_000, 2 * SECONDS_PER_YEAR), None);
+
+        // Splitting a period never accrues more than accruing it in one call
+        let whole = compute_stability_fee_accrual(supply, 500, 86_400).unwrap();
+        let halves = 2 * compute_stability_fee_accrual(supply, 500, 43_200).unwrap();
+        assert!(halves <= whole && whole - halves <= 1);
+    }
+
+    #[test]
+    fn test_compute_dust_donation_credit() {
+        // Fully credited when there is headroom and backing
+        assert_eq!(compute_dust_donation_credit(10, 1_000, 0, 10_000), 10);
+        // Cap overflow stays with claimable equity
+        assert_eq!(compute_dust_donation_credit(10, 1_000, 9_995, 10_000), 5);
+        assert_eq!(compute_dust_donation_credit(10, 1_000, 10_000, 10_000), 0);
+        // Never credit more than the claimable equity backing it (insolvency)
+        assert_eq!(compute_dust_donation_credit(10, 3, 0, 10_000), 3);
+        assert_eq!(compute_dust_donation_credit(10, 0, 0, 10_000), 0);
+    }
+
+    #[test]
+    fn test_invalid_multiplier_bounds_fail() {
+        let result = compute_dynamic_fee_bps(
+            100,
+            FeeAction::AmusdMint,
+            14_000,
+            13_000,
+            15_000,
+            12_000, 
+            9_000,  
+            0,
+            20_000,
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_compute_stability_shares() {
+        // First deposit is 1:1
+        assert_eq!(compute_stability_shares(1_000, 0, 0), Some(1_000));
+
+        // At par, shares track amUSD
+        assert_eq!(compute_stability_shares(500, 1_000, 1_000), Some(500));
+
+        // After the pool absorbed a loss each share is worth less, so the
+        // same deposit buys more shares
+        assert_eq!(compute_stability_shares(500, 800, 1_000), Some(625));
+
+        // Rounds down in favor of existing depositors
+        assert_eq!(compute_stability_shares(1, 3, 2), Some(0));
+
+        // Shares against an empty pool cannot be priced
+        assert_eq!(compute_stability_shares(500, 0, 1_000), None);
+    }
+
+    #[test]
+    fn test_compute_stability_shares_to_burn() {
+        assert_eq!(compute_stability_shares_to_burn(500, 1_000, 1_000), Some(500));
+        // 500 amUSD at 0.8 amUSD/share needs 625 shares
+        assert_eq!(compute_stability_shares_to_burn(500, 800, 1_000), Some(625));
+        // Rounds up against the withdrawer
+        assert_eq!(compute_stability_shares_to_burn(1, 3, 2), Some(1));
+        assert_eq!(compute_stability_shares_to_burn(1, 0, 1_000), None);
+    }
+
+    #[test]
+    fn test_compute_savings_shares() {
+        // First deposit is 1:1
+        assert_eq!(compute_savings_shares(1_000, 0, 0), Some(1_000));
+
+        // Fees raised the share price to 1.25, so the same deposit buys fewer shares
+        assert_eq!(compute_savings_shares(500, 1_250, 1_000), Some(400));
+
+        // Rounds down in favor of existing depositors
+        assert_eq!(compute_savings_shares(1, 3, 2), Some(0));
+    }
+
+    #[test]
+    fn test_compute_savings_withdrawal() {
+        assert_eq!(compute_savings_withdrawal(400, 1_250, 1_000), Some(500));
+        // Rounds down against the withdrawer
+        assert_eq!(compute_savings_withdrawal(1, 3, 2), Some(1));
+        assert_eq!(compute_savings_withdrawal(1, 0, 0), None);
+
+        // Deposit then withdraw never returns more than went in
+        let shares = compute_savings_shares(7, 10, 3).unwrap();
+        let pool = 10 + 7;
+        assert!(compute_savings_withdrawal(shares, pool, 3 + shares).unwrap() <= 7);
+    }
+
+    #[test]
+    fn test_compute_savings_fee() {
+        assert_eq!(compute_savings_fee(1_000, 0), Some(0));
+        assert_eq!(compute_savings_fee(1_000, 2_500), Some(250));
+        assert_eq!(compute_savings_fee(1_000, BPS_PRECISION), Some(1_000));
+        // Rounds down in favor of the treasury
+        assert_eq!(compute_savings_fee(3, 5_000), Some(1));
+        assert_eq!(compute_savings_fee(1_000, BPS_PRECISION + 1), None);
+    }
+
+    #[test]
+    fn test_compute_stability_gain() {
+        let per_share = compute_stability_gain_per_share(300, 1_000).unwrap();
+        assert_eq!(compute_stability_gain(250, per_share, 0), Some(75));
+        assert_eq!(compute_stability_gain(750, per_share, 0), Some(225));
+
+        // Nothing accrues past the snapshot
+        assert_eq!(compute_stability_gain(750, per_share, per_share), Some(0));
+
+        // Uneven splits round down and never over-distribute
+        let per_share = compute_stability_gain_per_share(10, 3).unwrap();
+        assert_eq!(compute_stability_gain(1, per_share, 0), Some(3));
+        assert_eq!(compute_stability_gain(2, per_share, 0), Some(6));
+
+        assert_eq!(compute_stability_gain_per_share(10, 0), None);
+        assert_eq!(compute_stability_gain(1, 0, 1), None);
+    }
+
+    #[test]
+    fn test_compute_stability_drawdown() {
+        let rate = 1_050_000_000;
+        let price = 100 * USD_PRECISION;
+
+        // CR >= min: nothing to do
+        assert_eq!(compute_stability_drawdown(1_000 * SOL_PRECISION, rate, 0, 50_000 * USD_PRECISION, price, 10_000 * USD_PRECISION, 13_000), Some((0, 0)));
+
+        // At 120% a partial drawdown lifts CR back to min
+        let supply = 87_500 * USD_PRECISION; // liability 875 SOL, CR 120%
+        let (burned, lst_out) = compute_stability_drawdown(1_000 * SOL_PRECISION, rate, 0, supply, price, 50_000 * USD_PRECISION, 13_000).unwrap();
+        assert!(burned > 0 && burned < 50_000 * USD_PRECISION);
+        let tvl = compute_tvl_sol(1_000 * SOL_PRECISION - lst_out, rate).unwrap();
+        let liability = compute_liability_sol(supply - burned, price).unwrap();
+        assert!(compute_cr_bps(tvl, liability) >= 13_000);
+        // Paid at par
+        assert_eq!(lst_out, mul_div_down(mul_div_down(burned, SOL_PRECISION, price).unwrap(), SOL_PRECISION, rate).unwrap());
+
+        // A small pool is used up entirely
+        assert_eq!(compute_stability_drawdown(1_000 * SOL_PRECISION, rate, 0, supply, price, 100 * USD_PRECISION, 13_000).unwrap().0, 100 * USD_PRECISION);
+
+        // Below 100%: whole pool at the holders' haircut (CR 80%)
+        let supply = 131_250 * USD_PRECISION; // liability 1312.5 SOL
+        let (burned, lst_out) = compute_stability_drawdown(1_000 * SOL_PRECISION, rate, 0, supply, price, 10_000 * USD_PRECISION, 13_000).unwrap();
+        assert_eq!(burned, 10_000 * USD_PRECISION);
+        assert_eq!(lst_out, mul_div_down(80 * SOL_PRECISION, SOL_PRECISION, rate).unwrap());
+
+        // Empty pool
+        assert_eq!(compute_stability_drawdown(1_000 * SOL_PRECISION, rate, 0, supply, price, 0, 13_000), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_compute_rebalance_lst_needed() {
+        let rate = 1_050_000_000;
+        let price = 100 * USD_PRECISION;
+        let supply = 87_500 * USD_PRECISION; // liability 875 SOL, CR 120%
+
+        let needed = compute_rebalance_lst_needed(1_000 * SOL_PRECISION, rate, 0, supply, price, 13_000).unwrap();
+        assert!(needed > 0);
+        let cr_at = |lst: u64| compute_cr_bps(compute_tvl_sol(lst, rate).unwrap(), compute_liability_sol(supply, price).unwrap());
+        assert!(cr_at(1_000 * SOL_PRECISION + needed) >= 13_000);
+        assert!(cr_at(1_000 * SOL_PRECISION + needed - 1) < 13_000);
+
+        // Already at min
+        assert_eq!(compute_rebalance_lst_needed(1_000 * SOL_PRECISION + needed, rate, 0, supply, price, 13_000), Some(0));
+        assert_eq!(compute_rebalance_lst_needed(1_000 * SOL_PRECISION, 0, 0, supply, price, 13_000), None);
+
+        // Other vaults cover part of the gap
+        let with_other = compute_rebalance_lst_needed(1_000 * SOL_PRECISION, rate, 21 * SOL_PRECISION, supply, price, 13_000).unwrap();
+        assert_eq!(with_other, needed - 20 * SOL_PRECISION);
+        assert_eq!(compute_rebalance_lst_needed(1_000 * SOL_PRECISION, rate, 200 * SOL_PRECISION, supply, price, 13_000), Some(0));
+    }
+
+    #[test]
+    fn test_compute_rebalance_bonus() {
+        assert_eq!(compute_rebalance_bonus(1_000_000, 0), Some(0));
+        // 2% off NAV: 1/0.98 - 1 extra
+        assert_eq!(compute_rebalance_bonus(980_000, 200), Some(20_000));
+        assert_eq!(compute_rebalance_bonus(1_000, 100), Some(10));
+        assert_eq!(compute_rebalance_bonus(1_000, BPS_PRECISION), None);
+    }
+
+    #[test]
+    fn test_compute_recovery_bonus_asol() {
+        assert_eq!(compute_recovery_bonus_asol(1_000_000, 100_000_000, 0, 50), Some(0));
+        // Small mint into a large supply: the full discount fits under the cap
+        assert_eq!(compute_recovery_bonus_asol(980_000, 100_000_000, 200, 50), Some(20_000));
+        // Large mint into a small supply: dilution cap binds
+        // (1_000_000 + 1_000_000) * 50 / 9_950 = 10_050
+        assert_eq!(compute_recovery_bonus_asol(1_000_000, 1_000_000, 1_000, 50), Some(10_050));
+        assert_eq!(compute_recovery_bonus_asol(1_000, 0, BPS_PRECISION, 50), None);
+        assert_eq!(compute_recovery_bonus_asol(1_000, 0, 100, BPS_PRECISION), None);
+    }
+
+    #[test]
+    fn test_insurance_topup_and_uninsured_cr() {
+        // TVL 110 SOL of which 20 is insurance, 100 SOL of liability
+        assert_eq!(compute_uninsured_cr_bps(110 * SOL_PRECISION, 100 * SOL_PRECISION, 20 * SOL_PRECISION), 9_000);
+        assert_eq!(compute_uninsured_cr_bps(110 * SOL_PRECISION, 100 * SOL_PRECISION, 0), 11_000);
+        assert_eq!(compute_uninsured_cr_bps(10, 100, 20), 0);
+        // Shortfall of 10 against a fund of 4, then 20
+        assert_eq!(compute_insurance_topup(100, 90, 4), Some(4));
+        assert_eq!(compute_insurance_topup(100, 90, 20), Some(10));
+        assert_eq!(compute_insurance_topup(90, 100, 20), None);
+        assert_eq!(compute_non_claimable_sol(5, 7), Some(12));
+    }
+
+    #[test]
+    fn test_compute_fee_cr_bps() {
+        assert_eq!(compute_fee_cr_bps(150 * SOL_PRECISION, 100 * SOL_PRECISION, 0), 15_000);
+        assert_eq!(compute_fee_cr_bps(150 * SOL_PRECISION, 100 * SOL_PRECISION, 30 * SOL_PRECISION), 12_000);
+        assert_eq!(compute_fee_cr_bps(10, 100, 20), 0);
+        assert_eq!(compute_fee_cr_bps(10, 0, 20), u64::MAX);
+    }
+
+    #[test]
+    fn test_compute_settlement_pools() {
+        let rate = 1_000_000_000;
+        let price = 100 * USD_PRECISION;
+        // 1_000 amUSD at $100 is 10 SOL of par
+        assert_eq!(compute_settlement_pools(25 * SOL_PRECISION, rate, 1_000 * USD_PRECISION, 1, price), Some((10 * SOL_PRECISION, 15 * SOL_PRECISION)));
+        // Insolvent: amUSD takes everything
+        assert_eq!(compute_settlement_pools(8 * SOL_PRECISION, rate, 1_000 * USD_PRECISION, 1, price), Some((8 * SOL_PRECISION, 0)));
+        // No aSOL left: amUSD sweeps the equity dust too
+        assert_eq!(compute_settlement_pools(25 * SOL_PRECISION, rate, 1_000 * USD_PRECISION, 0, price), Some((25 * SOL_PRECISION, 0)));
+        // No amUSD left: everything is aSOL's
+        assert_eq!(compute_settlement_pools(7, rate, 0, 5, price), Some((0, 7)));
+    }
+
+    #[test]
+    fn test_compute_stability_share_value() {
+        assert_eq!(compute_stability_share_value(0, 0, 0), Some(0));
+        assert_eq!(compute_stability_share_value(500, 1_000, 1_000), Some(500));
+        assert_eq!(compute_stability_share_value(625, 800, 1_000), Some(500));
+
+        // Shares never redeem for more than they were issued against
+        let shares = compute_stability_shares(7, 1_000, 999).unwrap();
+        assert!(compute_stability_share_value(shares, 1_007, 999 + shares).unwrap() <= 7);
+    }
+
+    #[test]
+    fn test_rescale_decimals_down() {
+        assert_eq!(rescale_decimals_down(1_234_567, 6, 6), Some(1_234_567));
+        assert_eq!(rescale_decimals_down(1_234_567, 6, 2), Some(123));
+        assert_eq!(rescale_decimals_down(123, 2, 6), Some(1_230_000));
+        assert_eq!(rescale_decimals_down(u64::MAX, 0, 9), None);
+    }
+
+    #[test]
+    fn test_compute_psm_swaps() {
+        // 1_000 USDC at 6 decimals, 10 bps fee
+        assert_eq!(compute_psm_swap_in(1_000 * USD_PRECISION, 6, 10), Some((1_000 * USD_PRECISION, 999 * USD_PRECISION, USD_PRECISION)));
+        assert_eq!(compute_psm_swap_out(1_000 * USD_PRECISION, 6, 10), Some((999 * USD_PRECISION, 999 * USD_PRECISION, USD_PRECISION)));
+        // An 8-decimal stablecoin loses its sub-micro dust to the vault
+        assert_eq!(compute_psm_swap_in(123_456_789, 8, 0), Some((1_234_567, 1_234_567, 0)));
+        assert_eq!(compute_psm_swap_out(1_234_567, 8, 0), Some((1_234_567, 123_456_700, 0)));
+    }
+
+    /// Backing, in USDC base units, of `amusd` at par, rounded up
+    fn psm_backing_needed(amusd: u64, usdc_decimals: u8) -> u128 {
+        let scale = 10u128.pow(u32::from(usdc_decimals));
+        let amusd_scale = 10u128.pow(u32::from(AMUSD_DECIMALS));
+        (amusd as u128 * scale).div_ceil(amusd_scale)
+    }
+
+    #[test]
+    fn test_psm_never_leaks_value() {
+        let amounts = [1u64, 7, 999, 1_000, 123_456, 1_000_001, 987_654_321, 50_000 * USD_PRECISION];
+        let fees = [0u64, 1, 10, 100 /* MAX_PSM_FEE_BPS */];
+        for usdc_decimals in [2u8, 6, 8, 9] {
+            for &fee_bps in &fees {
+                let mut vault_usdc = 0u128;
+                let mut outstanding = 0u64;
+                for &usdc_in in &amounts {
+                    let (minted, to_user, fee) = compute_psm_swap_in(usdc_in, usdc_decimals, fee_bps).unwrap();
+                    assert_eq!(to_user + fee, minted);
+                    vault_usdc += usdc_in as u128;
+                    outstanding += minted;
+                    // The vault backs everything outstanding at par
+                    assert!(vault_usdc >= psm_backing_needed(outstanding, usdc_decimals));
+
+                    // Swapping straight back never returns more than went in
+                    let (burned, usdc_out, back_fee) = compute_psm_swap_out(to_user, usdc_decimals, fee_bps).unwrap();
+                    assert_eq!(burned + back_fee, to_user);
+                    assert!(usdc_out <= usdc_in);
+                    vault_usdc -= usdc_out as u128;
+                    outstanding -= burned;
+                    assert!(vault_usdc >= psm_backing_needed(outstanding, usdc_decimals));
+                }
+            }
+        }
+    }
+
+
+}
@@ -0,0 +1,54 @@
+//! Mock Sanctum SOL value calculator
+//! The compiled `mock_sol_value_calculator.so` is loaded at the id of a real
+//! calculator so Laminar's allowlist accepts it; its LST state is a plain
+//! account written directly.
+
+use std::path::PathBuf;
+
+use litesvm::LiteSVM;
+use mock_sol_value_calculator::lst_state_data;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::FixtureError;
+
+/// Overrides the path of the compiled mock calculator.
+pub const CALCULATOR_SO_ENV: &str = "MOCK_CALCULATOR_SO";
+
+/// Calculator id the mock is loaded at (the SPL stake pool calculator)
+pub const MOCK_CALCULATOR_ID: Pubkey = laminar::lst_rate::SANCTUM_CALCULATOR_PROGRAMS[0];
+
+/// `mock_sol_value_calculator.so` location: `$MOCK_CALCULATOR_SO`, else this workspace's `target/deploy`.
+pub fn calculator_so_path() -> PathBuf {
+  std::env::var_os(CALCULATOR_SO_ENV)
+    .map(PathBuf::from)
+    .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/mock_sol_value_calculator.so")))
+}
+
+/// Load the mock calculator at `program_id`.
+pub fn load_mock_calculator(svm: &mut LiteSVM, program_id: Pubkey) -> Result<(), FixtureError> {
+  let path = calculator_so_path();
+  svm
+    .add_program_from_file(program_id, &path)
+    .map_err(|_| FixtureError::ProgramNotFound(path))
+}
+
+/// Create or overwrite the mock LST state read by the calculator.
+pub fn write_lst_state(
+  svm: &mut LiteSVM,
+  address: &Pubkey,
+  total_lamports: u64,
+  pool_token_supply: u64,
+  spread_lamports: u64,
+) -> Result<(), FixtureError> {
+  let data = lst_state_data(total_lamports, pool_token_supply, spread_lamports);
+  let account = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner: Pubkey::new_unique(),
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(*address, account)
+    .map_err(|_| FixtureError::Scenario("lst state account rejected by LiteSVM"))
+}
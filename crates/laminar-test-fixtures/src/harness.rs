@@ -0,0 +1,461 @@
+//! LiteSVM plumbing - program loading, SPL setup and account reads
+//! Everything here goes through real transactions; no account bytes are written directly.
+//! Laminar instruction builders and PDAs come from `laminar-client`, re-exported here.
+
+use std::path::PathBuf;
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Event, InstructionData};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+  transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use laminar::instructions::{RedeemAmusdArgs, RedeemAsolArgs};
+use laminar::state::{
+  CollateralVault, GlobalState, RedemptionTicket, SavingsDeposit, SavingsPool, StabilityDeposit, StabilityPool,
+  UserPosition,
+};
+use litesvm::LiteSVM;
+use solana_sdk::{
+  instruction::Instruction,
+  program_pack::Pack,
+  pubkey::Pubkey,
+  signature::{Keypair, Signer},
+  sysvar,
+  transaction::Transaction,
+};
+
+use crate::FixtureError;
+
+pub use laminar_client::instructions::*;
+pub use laminar_client::pda::*;
+pub use laminar_client::{Addresses, StakePoolExit, UserAccounts};
+
+/// `redeem_amusd` with the caller's own `min_lst_out`, skipping the client's
+/// haircut acknowledgement so tests can drive the instruction directly.
+pub fn redeem_amusd_ix(addresses: &Addresses, user: &UserAccounts, amusd_amount: u64, min_lst_out: u64) -> Instruction {
+  Instruction {
+    data: laminar::instruction::RedeemAmusd { amusd_amount, min_lst_out }.data(),
+    ..redeem_amusd_v2_ix(addresses, user, RedeemAmusdArgs::v1(amusd_amount, min_lst_out))
+  }
+}
+
+/// `redeem_asol` with the caller's own `min_lst_out`; see `redeem_amusd_ix`.
+pub fn redeem_asol_ix(addresses: &Addresses, user: &UserAccounts, asol_amount: u64, min_lst_out: u64) -> Instruction {
+  Instruction {
+    data: laminar::instruction::RedeemAsol { asol_amount, min_lst_out }.data(),
+    ..redeem_asol_v2_ix(addresses, user, RedeemAsolArgs::v1(asol_amount, min_lst_out))
+  }
+}
+
+/// Overrides the path of the compiled `laminar.so` loaded into LiteSVM.
+pub const PROGRAM_SO_ENV: &str = "LAMINAR_PROGRAM_SO";
+
+/// `laminar.so` location: `$LAMINAR_PROGRAM_SO`, else this workspace's `target/deploy`.
+pub fn program_so_path() -> PathBuf {
+  std::env::var_os(PROGRAM_SO_ENV)
+    .map(PathBuf::from)
+    .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/laminar.so")))
+}
+
+/// Load the Laminar program into `svm` at `laminar::ID`.
+pub fn load_program(svm: &mut LiteSVM) -> Result<(), FixtureError> {
+  let path = program_so_path();
+  svm
+    .add_program_from_file(laminar::ID, &path)
+    .map_err(|_| FixtureError::ProgramNotFound(path))
+}
+
+/// Sign and send `ixs` with `payer` first, then expire the blockhash so an
+/// identical follow-up transaction is not deduplicated.
+pub fn send(
+  svm: &mut LiteSVM,
+  label: &'static str,
+  ixs: &[Instruction],
+  payer: &Keypair,
+  extra_signers: &[&Keypair],
+) -> Result<(), FixtureError> {
+  send_with_events(svm, label, ixs, payer, extra_signers).map(|_| ())
+}
+
+/// `send`, returning every event Laminar emitted, in order. Events are read
+/// from the `emit_cpi!` inner instructions, each as its `Event::data`
+/// (discriminator first); decode them with `events_of`.
+pub fn send_with_events(
+  svm: &mut LiteSVM,
+  label: &'static str,
+  ixs: &[Instruction],
+  payer: &Keypair,
+  extra_signers: &[&Keypair],
+) -> Result<Vec<Vec<u8>>, FixtureError> {
+  let mut signers = vec![payer];
+  signers.extend_from_slice(extra_signers);
+
+  let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &signers, svm.latest_blockhash());
+  let account_keys = tx.message.account_keys.clone();
+  let result = svm.send_transaction(tx);
+  svm.expire_blockhash();
+
+  let meta = result.map_err(|failed| FixtureError::Transaction {
+    label,
+    error: format!("{:?}", failed.err),
+    logs: failed.meta.logs,
+  })?;
+  Ok(
+    meta
+      .inner_instructions
+      .iter()
+      .flatten()
+      .filter(|inner| account_keys[inner.instruction.program_id_index as usize] == laminar::ID)
+      .filter_map(|inner| inner.instruction.data.strip_prefix(EVENT_IX_TAG_LE))
+      .map(<[u8]>::to_vec)
+      .collect(),
+  )
+}
+
+/// Every `E` among `events` from `send_with_events`, in order; other event
+/// types are skipped.
+pub fn events_of<E: Event>(events: &[Vec<u8>]) -> Vec<E> {
+  events
+    .iter()
+    .filter_map(|data| data.strip_prefix(E::DISCRIMINATOR))
+    .map(|body| E::try_from_slice(body).expect("event payload matches its discriminator"))
+    .collect()
+}
+
+/// Simulate `ixs` with `payer` and decode the program's return data as `T`.
+/// Nothing is committed, so the slot and blockhash stay as they were.
+pub fn simulate_return<T: AnchorDeserialize>(
+  svm: &LiteSVM,
+  label: &'static str,
+  ixs: &[Instruction],
+  payer: &Keypair,
+) -> Result<T, FixtureError> {
+  let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], svm.latest_blockhash());
+  let simulated = svm.simulate_transaction(tx).map_err(|failed| FixtureError::Transaction {
+    label,
+    error: format!("{:?}", failed.err),
+    logs: failed.meta.logs,
+  })?;
+  T::try_from_slice(&simulated.meta.return_data.data).map_err(|err| FixtureError::Transaction {
+    label,
+    error: format!("undecodable return data: {err}"),
+    logs: simulated.meta.logs,
+  })
+}
+
+pub fn airdrop(svm: &mut LiteSVM, to: &Pubkey, lamports: u64) -> Result<(), FixtureError> {
+  svm
+    .airdrop(to, lamports)
+    .map(|_| ())
+    .map_err(|failed| FixtureError::Transaction {
+      label: "airdrop",
+      error: format!("{:?}", failed.err),
+      logs: failed.meta.logs,
+    })
+}
+
+/// Create a classic SPL mint with 9 decimals (the only LST precision Laminar accepts).
+pub fn create_lst_mint(svm: &mut LiteSVM, payer: &Keypair, mint: &Keypair, mint_authority: &Pubkey) -> Result<(), FixtureError> {
+  create_lst_mint_with_freeze_authority(svm, payer, mint, mint_authority, None)
+}
+
+/// `create_lst_mint` whose `freeze_authority` can freeze any account of the mint.
+pub fn create_lst_mint_with_freeze_authority(
+  svm: &mut LiteSVM,
+  payer: &Keypair,
+  mint: &Keypair,
+  mint_authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+) -> Result<(), FixtureError> {
+  create_spl_mint(svm, "create_lst_mint", payer, mint, mint_authority, freeze_authority, 9)
+}
+
+/// Create a classic SPL stablecoin mint with `decimals` for the PSM to swap against.
+pub fn create_usdc_mint(svm: &mut LiteSVM, payer: &Keypair, mint: &Keypair, mint_authority: &Pubkey, decimals: u8) -> Result<(), FixtureError> {
+  create_spl_mint(svm, "create_usdc_mint", payer, mint, mint_authority, None, decimals)
+}
+
+fn create_spl_mint(
+  svm: &mut LiteSVM,
+  label: &'static str,
+  payer: &Keypair,
+  mint: &Keypair,
+  mint_authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+  decimals: u8,
+) -> Result<(), FixtureError> {
+  let space = spl_token::state::Mint::LEN;
+  let rent = svm.minimum_balance_for_rent_exemption(space);
+  let ixs = [
+    system_instruction::create_account(&payer.pubkey(), &mint.pubkey(), rent, space as u64, &spl_token::ID),
+    spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), mint_authority, freeze_authority, decimals)
+      .expect("static mint args"),
+  ];
+  send(svm, label, &ixs, payer, &[mint])
+}
+
+/// Create a 9-decimal Token-2022 mint whose transfers withhold
+/// `transfer_fee_bps` (capped at `maximum_fee`) from what the recipient gets.
+pub fn create_transfer_fee_lst_mint(
+  svm: &mut LiteSVM,
+  payer: &Keypair,
+  mint: &Keypair,
+  mint_authority: &Pubkey,
+  transfer_fee_bps: u16,
+  maximum_fee: u64,
+) -> Result<(), FixtureError> {
+  let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[ExtensionType::TransferFeeConfig])
+    .expect("static extension list");
+  let rent = svm.minimum_balance_for_rent_exemption(space);
+  let ixs = [
+    system_instruction::create_account(&payer.pubkey(), &mint.pubkey(), rent, space as u64, &spl_token_2022::ID),
+    spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config(
+      &spl_token_2022::ID,
+      &mint.pubkey(),
+      Some(mint_authority),
+      Some(mint_authority),
+      transfer_fee_bps,
+      maximum_fee,
+    )
+    .expect("static transfer fee args"),
+    spl_token_2022::instruction::initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), mint_authority, None, 9)
+      .expect("static mint args"),
+  ];
+  send(svm, "create_transfer_fee_lst_mint", &ixs, payer, &[mint])
+}
+
+/// Fee the mint withholds from a transfer of `amount` this epoch; zero for
+/// mints without a transfer fee.
+pub fn transfer_fee(svm: &LiteSVM, mint: &Pubkey, amount: u64) -> u64 {
+  let epoch = svm.get_sysvar::<sysvar::clock::Clock>().epoch;
+  svm
+    .get_account(mint)
+    .filter(|account| account.owner == spl_token_2022::ID)
+    .and_then(|account| {
+      let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).ok()?;
+      let config = state.get_extension::<TransferFeeConfig>().ok()?;
+      config.calculate_epoch_fee(epoch, amount)
+    })
+    .unwrap_or(0)
+}
+
+/// Token program that owns `account`; classic SPL Token when it does not exist yet.
+pub fn token_program_of(svm: &LiteSVM, account: &Pubkey) -> Pubkey {
+  svm.get_account(account).map_or(spl_token::ID, |account| account.owner)
+}
+
+/// Write the wSOL mint, which LiteSVM does not ship with. A no-op once present.
+pub fn install_native_mint(svm: &mut LiteSVM) -> Result<(), FixtureError> {
+  let address = spl_token::native_mint::ID;
+  if svm.get_account(&address).is_some() {
+    return Ok(());
+  }
+  let mint = spl_token::state::Mint {
+    mint_authority: None.into(),
+    supply: 0,
+    decimals: spl_token::native_mint::DECIMALS,
+    is_initialized: true,
+    freeze_authority: None.into(),
+  };
+  let mut data = vec![0; spl_token::state::Mint::LEN];
+  spl_token::state::Mint::pack(mint, &mut data).expect("static native mint");
+  let account = solana_sdk::account::Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner: spl_token::ID,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(address, account)
+    .map_err(|_| FixtureError::Scenario("native mint rejected by LiteSVM"))
+}
+
+/// Wrap `lamports` into `owner`'s wSOL ATA the way wallets do: a plain SOL
+/// transfer, then `sync_native` unless `sync` is false. Returns the ATA.
+pub fn wrap_sol(svm: &mut LiteSVM, owner: &Keypair, lamports: u64, sync: bool) -> Result<Pubkey, FixtureError> {
+  let mint = spl_token::native_mint::ID;
+  let ata = get_associated_token_address_with_program_id(&owner.pubkey(), &mint, &spl_token::ID);
+  if svm.get_account(&ata).is_none() {
+    create_ata(svm, owner, &owner.pubkey(), &mint)?;
+  }
+  let mut ixs = vec![system_instruction::transfer(&owner.pubkey(), &ata, lamports)];
+  if sync {
+    ixs.push(spl_token::instruction::sync_native(&spl_token::ID, &ata).expect("static sync_native args"));
+  }
+  send(svm, "wrap_sol", &ixs, owner, &[])?;
+  Ok(ata)
+}
+
+/// `wallet`'s ATA for `mint` under whichever token program owns the mint.
+pub fn ata_address(svm: &LiteSVM, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+  get_associated_token_address_with_program_id(wallet, mint, &token_program_of(svm, mint))
+}
+
+/// Create `wallet`'s ATA for `mint`, paid by `payer`. Returns the ATA address.
+pub fn create_ata(svm: &mut LiteSVM, payer: &Keypair, wallet: &Pubkey, mint: &Pubkey) -> Result<Pubkey, FixtureError> {
+  let ix = spl_associated_token_account::instruction::create_associated_token_account(
+    &payer.pubkey(),
+    wallet,
+    mint,
+    &token_program_of(svm, mint),
+  );
+  send(svm, "create_ata", &[ix], payer, &[])?;
+  Ok(ata_address(svm, wallet, mint))
+}
+
+pub fn mint_tokens(svm: &mut LiteSVM, mint_authority: &Keypair, mint: &Pubkey, to: &Pubkey, amount: u64) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::mint_to(&token_program_of(svm, mint), mint, to, &mint_authority.pubkey(), &[], amount)
+    .expect("static mint_to args");
+  send(svm, "mint_lst", &[ix], mint_authority, &[])
+}
+
+/// Plain transfer of a classic SPL or Token-2022 balance. A Token-2022 mint
+/// with a transfer fee refuses it; move those with `transfer_checked`.
+pub fn transfer_tokens(svm: &mut LiteSVM, owner: &Keypair, from: &Pubkey, to: &Pubkey, amount: u64) -> Result<(), FixtureError> {
+  #[allow(deprecated)]
+  let ix = spl_token_2022::instruction::transfer(&token_program_of(svm, from), from, to, &owner.pubkey(), &[], amount)
+    .expect("static transfer args");
+  send(svm, "transfer_tokens", &[ix], owner, &[])
+}
+
+pub fn approve_tokens(svm: &mut LiteSVM, owner: &Keypair, account: &Pubkey, delegate: &Pubkey, amount: u64) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::approve(&token_program_of(svm, account), account, delegate, &owner.pubkey(), &[], amount)
+    .expect("static approve args");
+  send(svm, "approve_tokens", &[ix], owner, &[])
+}
+
+pub fn revoke_tokens(svm: &mut LiteSVM, owner: &Keypair, account: &Pubkey) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::revoke(&token_program_of(svm, account), account, &owner.pubkey(), &[])
+    .expect("static revoke args");
+  send(svm, "revoke_tokens", &[ix], owner, &[])
+}
+
+/// Hand `mint`'s mint authority from `mint_authority` to `new_authority`.
+pub fn set_mint_authority(svm: &mut LiteSVM, mint_authority: &Keypair, mint: &Pubkey, new_authority: &Pubkey) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::set_authority(
+    &token_program_of(svm, mint),
+    mint,
+    Some(new_authority),
+    spl_token_2022::instruction::AuthorityType::MintTokens,
+    &mint_authority.pubkey(),
+    &[],
+  )
+  .expect("static set_authority args");
+  send(svm, "set_mint_authority", &[ix], mint_authority, &[])
+}
+
+/// Freeze `account` of `mint` as the mint's freeze authority.
+pub fn freeze_token_account(svm: &mut LiteSVM, freeze_authority: &Keypair, account: &Pubkey, mint: &Pubkey) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::freeze_account(&token_program_of(svm, mint), account, mint, &freeze_authority.pubkey(), &[])
+    .expect("static freeze_account args");
+  send(svm, "freeze_token_account", &[ix], freeze_authority, &[])
+}
+
+pub fn thaw_token_account(svm: &mut LiteSVM, freeze_authority: &Keypair, account: &Pubkey, mint: &Pubkey) -> Result<(), FixtureError> {
+  let ix = spl_token_2022::instruction::thaw_account(&token_program_of(svm, mint), account, mint, &freeze_authority.pubkey(), &[])
+    .expect("static thaw_account args");
+  send(svm, "thaw_token_account", &[ix], freeze_authority, &[])
+}
+
+/// Token amount of a classic SPL or Token-2022 account; zero if it does not exist.
+pub fn token_balance(svm: &LiteSVM, token_account: &Pubkey) -> u64 {
+  svm
+    .get_account(token_account)
+    .and_then(|account| {
+      StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+        .ok()
+        .map(|account| account.base.amount)
+    })
+    .unwrap_or(0)
+}
+
+pub fn mint_supply(svm: &LiteSVM, mint: &Pubkey) -> u64 {
+  svm
+    .get_account(mint)
+    .and_then(|account| {
+      StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+        .ok()
+        .map(|mint| mint.base.supply)
+    })
+    .unwrap_or(0)
+}
+
+pub fn current_slot(svm: &LiteSVM) -> u64 {
+  svm.get_sysvar::<sysvar::clock::Clock>().slot
+}
+
+pub fn current_unix_timestamp(svm: &LiteSVM) -> i64 {
+  svm.get_sysvar::<sysvar::clock::Clock>().unix_timestamp
+}
+
+pub fn read_global_state(svm: &LiteSVM, addresses: &Addresses) -> Result<GlobalState, FixtureError> {
+  let account = svm
+    .get_account(&addresses.global_state)
+    .ok_or(FixtureError::Scenario("global_state account missing"))?;
+  GlobalState::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("global_state failed to deserialize"))
+}
+
+/// The `CollateralVault` at `addresses.collateral_vault`
+pub fn read_collateral_vault(svm: &LiteSVM, addresses: &Addresses) -> Result<CollateralVault, FixtureError> {
+  let account = addresses
+    .collateral_vault
+    .and_then(|address| svm.get_account(&address))
+    .ok_or(FixtureError::Scenario("collateral_vault account missing"))?;
+  CollateralVault::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("collateral_vault failed to deserialize"))
+}
+
+pub fn read_stability_pool(svm: &LiteSVM, addresses: &Addresses) -> Result<StabilityPool, FixtureError> {
+  let account = svm
+    .get_account(&addresses.stability_pool)
+    .ok_or(FixtureError::Scenario("stability_pool account missing"))?;
+  StabilityPool::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("stability_pool failed to deserialize"))
+}
+
+pub fn read_stability_deposit(svm: &LiteSVM, user: &Pubkey) -> Result<StabilityDeposit, FixtureError> {
+  let account = svm
+    .get_account(&stability_deposit_address(user))
+    .ok_or(FixtureError::Scenario("stability_deposit account missing"))?;
+  StabilityDeposit::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("stability_deposit failed to deserialize"))
+}
+
+pub fn read_savings_pool(svm: &LiteSVM) -> Result<SavingsPool, FixtureError> {
+  let account = svm
+    .get_account(&savings_pool_address())
+    .ok_or(FixtureError::Scenario("savings_pool account missing"))?;
+  SavingsPool::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("savings_pool failed to deserialize"))
+}
+
+pub fn read_savings_deposit(svm: &LiteSVM, user: &Pubkey) -> Result<SavingsDeposit, FixtureError> {
+  let account = svm
+    .get_account(&savings_deposit_address(user))
+    .ok_or(FixtureError::Scenario("savings_deposit account missing"))?;
+  SavingsDeposit::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("savings_deposit failed to deserialize"))
+}
+
+pub fn read_user_position(svm: &LiteSVM, user: &Pubkey) -> Result<UserPosition, FixtureError> {
+  let account = svm
+    .get_account(&user_position_address(user))
+    .ok_or(FixtureError::Scenario("user_position account missing"))?;
+  UserPosition::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("user_position failed to deserialize"))
+}
+
+pub fn read_redemption_ticket(svm: &LiteSVM, sequence: u64) -> Result<RedemptionTicket, FixtureError> {
+  let account = svm
+    .get_account(&redemption_ticket_address(sequence))
+    .ok_or(FixtureError::Scenario("redemption_ticket account missing"))?;
+  RedemptionTicket::try_deserialize(&mut account.data.as_slice())
+    .map_err(|_| FixtureError::Scenario("redemption_ticket failed to deserialize"))
+}
+
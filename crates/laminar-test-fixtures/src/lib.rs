@@ -0,0 +1,1226 @@
+//! Laminar test fixtures - named protocol scenarios on LiteSVM
+//! Each scenario is reached by executing real Laminar and SPL instructions
+//! against a fresh LiteSVM instance, never by writing account bytes. Amounts
+//! are derived from `laminar::quote`, so scripts keep working when fees or
+//! risk parameters change, and every fixture carries the model state the
+//! chain is expected to hold.
+//!
+//! ```ignore
+//! let mut svm = laminar_test_fixtures::new_svm()?;
+//! let fixture = laminar_test_fixtures::build(&mut svm, Scenario::NearMinCr)?;
+//! fixture.assert_matches_chain(&svm)?;
+//! ```
+//!
+//! The compiled program is loaded from `$LAMINAR_PROGRAM_SO`, falling back to
+//! this workspace's `target/deploy/laminar.so` (run `anchor build` first).
+
+use std::fmt;
+use std::path::PathBuf;
+
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token;
+use laminar::constants::{FLASH_LOAN_FEE_BPS, MIN_PROTOCOL_TVL, REDEEM_ALL};
+use laminar::instructions::{RedeemAmusdArgs, RedeemAsolArgs};
+use laminar::math::{
+  compute_partial_fill, compute_tvl_sol, compute_weighted_tvl_sol, mul_div_up, BPS_PRECISION, SOL_PRECISION,
+};
+use laminar::oracle::PriceSource;
+use laminar::quote::{
+  quote_mint_amusd, quote_mint_asol, quote_redeem_amusd, quote_redeem_asol, Quote, QuoteState,
+};
+use laminar::state::CollateralStatus;
+use litesvm::LiteSVM;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::{Keypair, Signer}};
+
+pub mod calculator;
+pub mod harness;
+pub mod marinade;
+pub mod pyth;
+pub mod scenarios;
+pub mod stake_pool;
+pub mod switchboard;
+
+pub use harness::{Addresses, UserAccounts};
+pub use scenarios::{build, build_with_transfer_fee, Scenario};
+
+use harness::*;
+
+#[derive(Debug)]
+pub enum FixtureError {
+  /// `laminar.so` (or a mock program) could not be loaded from the given path
+  ProgramNotFound(PathBuf),
+  /// A scripted transaction failed
+  Transaction { label: &'static str, error: String, logs: Vec<String> },
+  /// The scenario could not be reached from the current parameters
+  Scenario(&'static str),
+}
+
+impl fmt::Display for FixtureError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FixtureError::ProgramNotFound(path) => {
+        write!(
+          f,
+          "program not found at {} (run `anchor build` and `cargo build-sbf`, or set {} / {} / {})",
+          path.display(),
+          PROGRAM_SO_ENV,
+          calculator::CALCULATOR_SO_ENV,
+          stake_pool::STAKE_POOL_SO_ENV
+        )
+      }
+      FixtureError::Transaction { label, error, logs } => {
+        write!(f, "{} failed: {}\n{}", label, error, logs.join("\n"))
+      }
+      FixtureError::Scenario(reason) => write!(f, "scenario unreachable: {}", reason),
+    }
+  }
+}
+
+impl std::error::Error for FixtureError {}
+
+/// Fresh LiteSVM with the Laminar program loaded and the clock past slot 0.
+pub fn new_svm() -> Result<LiteSVM, FixtureError> {
+  let mut svm = LiteSVM::new();
+  load_program(&mut svm)?;
+  svm.warp_to_slot(1);
+  Ok(svm)
+}
+
+/// A funded wallet with its LST/amUSD/aSOL ATAs.
+pub struct Actor {
+  pub name: &'static str,
+  pub keypair: Keypair,
+  pub lst_account: Pubkey,
+  pub amusd_account: Pubkey,
+  pub asol_account: Pubkey,
+}
+
+impl Actor {
+  pub fn accounts(&self) -> UserAccounts {
+    UserAccounts {
+      user: self.keypair.pubkey(),
+      lst_account: self.lst_account,
+      amusd_account: self.amusd_account,
+      asol_account: self.asol_account,
+    }
+  }
+}
+
+/// A second LST registered through `add_collateral_type`, with the model's view of its vault.
+#[derive(Clone, Copy, Debug)]
+pub struct Collateral {
+  /// The fixture's addresses with the vault leg moved to this collateral
+  pub addresses: Addresses,
+  pub lst_to_sol_rate: u64,
+  pub collateral_weight_bps: u64,
+  /// LST the model expects in this collateral's vault
+  pub lst_amount: u64,
+  pub status: CollateralStatus,
+}
+
+#[derive(Clone, Copy)]
+enum Tranche {
+  AmUSD,
+  Asol,
+}
+
+/// A protocol brought into a named scenario, plus everything needed to keep scripting it.
+pub struct Fixture {
+  pub scenario: Scenario,
+  pub addresses: Addresses,
+  /// Protocol admin, treasury and LST mint authority
+  pub authority: Keypair,
+  pub actors: Vec<Actor>,
+  /// Model state the chain must match; advanced by every scripted action
+  pub expected: QuoteState,
+  /// On-chain `operation_counter` after initialize and after every scripted action
+  pub operation_counters: Vec<u64>,
+  /// Collateral types added by `add_collateral_type`, in registration order
+  pub collaterals: Vec<Collateral>,
+}
+
+impl Fixture {
+  /// Look up an actor by name. Panics on an unknown name.
+  pub fn actor(&self, name: &str) -> &Actor {
+    self
+      .actors
+      .iter()
+      .find(|actor| actor.name == name)
+      .unwrap_or_else(|| panic!("fixture {:?} has no actor {:?}", self.scenario, name))
+  }
+
+  pub fn balances(&self, svm: &LiteSVM, name: &str) -> (u64, u64, u64) {
+    let actor = self.actor(name);
+    (
+      token_balance(svm, &actor.lst_account),
+      token_balance(svm, &actor.amusd_account),
+      token_balance(svm, &actor.asol_account),
+    )
+  }
+
+  /// Mint amUSD for `actor` with `min_amusd_out` pinned to the quote, so any
+  /// handler/model divergence fails the transaction. The quote prices what the
+  /// vault receives after the LST mint's transfer fee, if it has one.
+  pub fn mint_amusd(&mut self, svm: &mut LiteSVM, actor: &str, lst_amount: u64) -> Result<Quote, FixtureError> {
+    self.expect_stability_fee_accrual(svm)?;
+    let lst_received = lst_amount - transfer_fee(svm, &self.addresses.lst_mint, lst_amount);
+    let quote = quote_mint_amusd(&self.expected, lst_received).ok_or(FixtureError::Scenario("mint_amusd quote failed"))?;
+    let ix = mint_amusd_ix(&self.addresses, &self.actor(actor).accounts(), lst_amount, quote.to_user);
+    send(svm, "mint_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `zap_mint_amusd` of `lamports` native SOL into the primary LST, staked
+  /// for through `entry`'s pool at its current balances.
+  pub fn zap_mint_amusd_stake(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    entry: &StakePoolExit,
+    lamports: u64,
+  ) -> Result<Quote, FixtureError> {
+    self.expect_stability_fee_accrual(svm)?;
+    let lst_amount = stake_pool::pool_tokens_for_deposit(svm, &entry.stake_pool, lamports)?;
+    let quote = quote_mint_amusd(&self.expected, lst_amount).ok_or(FixtureError::Scenario("zap_mint_amusd quote failed"))?;
+    let ix = zap_mint_amusd_stake_ix(&self.addresses, &self.actor(actor).accounts(), entry, lamports, quote.to_user);
+    send(svm, "zap_mint_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  pub fn redeem_amusd(&mut self, svm: &mut LiteSVM, actor: &str, amusd_amount: u64) -> Result<Quote, FixtureError> {
+    self.expect_stability_fee_accrual(svm)?;
+    let quote = quote_redeem_amusd(&self.expected, amusd_amount).ok_or(FixtureError::Scenario("redeem_amusd quote failed"))?;
+    let ix = redeem_amusd_ix(&self.addresses, &self.actor(actor).accounts(), amusd_amount, quote.to_user);
+    send(svm, "redeem_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `redeem_amusd_to_sol` out of the primary LST, unstaked through `exit`'s
+  /// pool. The pool prices the exit, so `min_sol_out` is the caller's.
+  pub fn redeem_amusd_unstake(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    exit: &StakePoolExit,
+    amusd_amount: u64,
+    min_sol_out: u64,
+  ) -> Result<Quote, FixtureError> {
+    self.expect_stability_fee_accrual(svm)?;
+    let quote = quote_redeem_amusd(&self.expected, amusd_amount).ok_or(FixtureError::Scenario("redeem_amusd quote failed"))?;
+    let ix = redeem_amusd_unstake_ix(&self.addresses, &self.actor(actor).accounts(), exit, amusd_amount, min_sol_out);
+    send(svm, "redeem_amusd_to_sol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `redeem_amusd` of `REDEEM_ALL`, quoted at `actor`'s whole amUSD balance.
+  pub fn redeem_all_amusd(&mut self, svm: &mut LiteSVM, actor: &str) -> Result<Quote, FixtureError> {
+    self.expect_stability_fee_accrual(svm)?;
+    let balance = self.balances(svm, actor).1;
+    let quote = quote_redeem_amusd(&self.expected, balance).ok_or(FixtureError::Scenario("redeem_amusd quote failed"))?;
+    let ix = redeem_amusd_ix(&self.addresses, &self.actor(actor).accounts(), REDEEM_ALL, quote.to_user);
+    send(svm, "redeem_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  pub fn mint_asol(&mut self, svm: &mut LiteSVM, actor: &str, lst_amount: u64) -> Result<Quote, FixtureError> {
+    let lst_received = lst_amount - transfer_fee(svm, &self.addresses.lst_mint, lst_amount);
+    let quote = quote_mint_asol(&self.expected, lst_received).ok_or(FixtureError::Scenario("mint_asol quote failed"))?;
+    let ix = mint_asol_ix(&self.addresses, &self.actor(actor).accounts(), lst_amount, quote.to_user);
+    send(svm, "mint_asol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// `zap_mint_asol` of `lamports` native SOL into the primary LST, staked
+  /// for through `entry`'s pool at its current balances.
+  pub fn zap_mint_asol_stake(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    entry: &StakePoolExit,
+    lamports: u64,
+  ) -> Result<Quote, FixtureError> {
+    let lst_amount = stake_pool::pool_tokens_for_deposit(svm, &entry.stake_pool, lamports)?;
+    let quote = quote_mint_asol(&self.expected, lst_amount).ok_or(FixtureError::Scenario("zap_mint_asol quote failed"))?;
+    let ix = zap_mint_asol_stake_ix(&self.addresses, &self.actor(actor).accounts(), entry, lamports, quote.to_user);
+    send(svm, "zap_mint_asol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  pub fn redeem_asol(&mut self, svm: &mut LiteSVM, actor: &str, asol_amount: u64) -> Result<Quote, FixtureError> {
+    let quote = quote_redeem_asol(&self.expected, asol_amount).ok_or(FixtureError::Scenario("redeem_asol quote failed"))?;
+    let ix = redeem_asol_ix(&self.addresses, &self.actor(actor).accounts(), asol_amount, quote.to_user);
+    send(svm, "redeem_asol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// `redeem_asol_to_sol` out of the primary LST, unstaked through `exit`'s pool.
+  pub fn redeem_asol_unstake(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    exit: &StakePoolExit,
+    asol_amount: u64,
+    min_sol_out: u64,
+  ) -> Result<Quote, FixtureError> {
+    let quote = quote_redeem_asol(&self.expected, asol_amount).ok_or(FixtureError::Scenario("redeem_asol quote failed"))?;
+    let ix = redeem_asol_unstake_ix(&self.addresses, &self.actor(actor).accounts(), exit, asol_amount, min_sol_out);
+    send(svm, "redeem_asol_to_sol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// `redeem_asol` of `REDEEM_ALL`, quoted at `actor`'s whole aSOL balance.
+  pub fn redeem_all_asol(&mut self, svm: &mut LiteSVM, actor: &str) -> Result<Quote, FixtureError> {
+    let balance = self.balances(svm, actor).2;
+    let quote = quote_redeem_asol(&self.expected, balance).ok_or(FixtureError::Scenario("redeem_asol quote failed"))?;
+    let ix = redeem_asol_ix(&self.addresses, &self.actor(actor).accounts(), REDEEM_ALL, quote.to_user);
+    send(svm, "redeem_asol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit(svm, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// Queue `amount` of `actor`'s amUSD, or aSOL with `asol`, and return the
+  /// ticket's sequence number. The book is untouched until it is processed.
+  pub fn request_redemption(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    asol: bool,
+    amount: u64,
+    min_lst_out: u64,
+  ) -> Result<u64, FixtureError> {
+    let sequence = read_global_state(svm, &self.addresses)?.next_redemption_ticket;
+    let token_mint = if asol { self.addresses.asol_mint } else { self.addresses.amusd_mint };
+    let ix = request_redemption_ix(&self.addresses, &self.actor(actor).accounts(), &token_mint, sequence, amount, min_lst_out);
+    send(svm, "request_redemption", &[ix], &self.actor(actor).keypair, &[])?;
+    self.record_operation_counter(svm)?;
+    Ok(sequence)
+  }
+
+  /// Cancel ticket `sequence` with `caller` signing.
+  pub fn cancel_redemption(&self, svm: &mut LiteSVM, caller: &str, sequence: u64) -> Result<(), FixtureError> {
+    let ticket = read_redemption_ticket(svm, sequence)?;
+    let ix = cancel_redemption_ix(&self.addresses, &self.actor(caller).keypair.pubkey(), &ticket);
+    send(svm, "cancel_redemption", &[ix], &self.actor(caller).keypair, &[])
+  }
+
+  /// Crank ticket `sequence`, quoting a live ticket against the model like
+  /// `redeem_amusd` / `redeem_asol`. A cancelled ticket is only cleared and
+  /// returns `None`.
+  pub fn process_redemption(&mut self, svm: &mut LiteSVM, sequence: u64) -> Result<Option<Quote>, FixtureError> {
+    let ticket = read_redemption_ticket(svm, sequence)?;
+    let ix = process_redemption_ix(&self.addresses, &self.authority.pubkey(), &ticket);
+    if ticket.amount == 0 {
+      send(svm, "process_redemption", &[ix], &self.authority, &[])?;
+      self.record_operation_counter(svm)?;
+      return Ok(None);
+    }
+    let (quote, tranche) = if ticket.token_mint == self.addresses.asol_mint {
+      (quote_redeem_asol(&self.expected, ticket.amount), Tranche::Asol)
+    } else {
+      self.expect_stability_fee_accrual(svm)?;
+      (quote_redeem_amusd(&self.expected, ticket.amount), Tranche::AmUSD)
+    };
+    let quote = quote.ok_or(FixtureError::Scenario("process_redemption quote failed"))?;
+    send(svm, "process_redemption", &[ix], &self.authority, &[])?;
+    self.commit(svm, &quote, tranche)?;
+    Ok(Some(quote))
+  }
+
+  /// Register a fresh 9-decimal LST as full-weight collateral at
+  /// `lst_to_sol_rate` and give every actor an ATA holding `lst_per_actor`
+  /// of it; returns the mint.
+  pub fn add_collateral_type(&mut self, svm: &mut LiteSVM, lst_to_sol_rate: u64, lst_per_actor: u64) -> Result<Pubkey, FixtureError> {
+    self.add_weighted_collateral_type(svm, lst_to_sol_rate, BPS_PRECISION, lst_per_actor)
+  }
+
+  /// `add_collateral_type` with CR counting `collateral_weight_bps` of the vault.
+  pub fn add_weighted_collateral_type(
+    &mut self,
+    svm: &mut LiteSVM,
+    lst_to_sol_rate: u64,
+    collateral_weight_bps: u64,
+    lst_per_actor: u64,
+  ) -> Result<Pubkey, FixtureError> {
+    self.add_collateral_mint(svm, lst_to_sol_rate, collateral_weight_bps, lst_per_actor, None)
+  }
+
+  /// `add_collateral_type` for a mint the fixture authority can freeze.
+  pub fn add_freezable_collateral_type(&mut self, svm: &mut LiteSVM, lst_to_sol_rate: u64, lst_per_actor: u64) -> Result<Pubkey, FixtureError> {
+    let freeze_authority = self.authority.pubkey();
+    self.add_collateral_mint(svm, lst_to_sol_rate, BPS_PRECISION, lst_per_actor, Some(&freeze_authority))
+  }
+
+  fn add_collateral_mint(
+    &mut self,
+    svm: &mut LiteSVM,
+    lst_to_sol_rate: u64,
+    collateral_weight_bps: u64,
+    lst_per_actor: u64,
+    freeze_authority: Option<&Pubkey>,
+  ) -> Result<Pubkey, FixtureError> {
+    let mint = Keypair::new();
+    create_lst_mint_with_freeze_authority(svm, &self.authority, &mint, &self.authority.pubkey(), freeze_authority)?;
+    self.register_collateral(svm, mint.pubkey(), lst_to_sol_rate, collateral_weight_bps)?;
+    for actor in &self.actors {
+      let lst_account = self.ata(&actor.keypair.pubkey(), &mint.pubkey());
+      mint_tokens(svm, &self.authority, &mint.pubkey(), &lst_account, lst_per_actor)?;
+    }
+    Ok(mint.pubkey())
+  }
+
+  /// Register wSOL as a collateral type at par and full weight. Actors get
+  /// empty wSOL ATAs; fund them with `harness::wrap_sol`.
+  pub fn add_wsol_collateral(&mut self, svm: &mut LiteSVM) -> Result<Pubkey, FixtureError> {
+    install_native_mint(svm)?;
+    let mint = spl_token::native_mint::ID;
+    self.register_collateral(svm, mint, SOL_PRECISION, BPS_PRECISION)?;
+    Ok(mint)
+  }
+
+  fn register_collateral(
+    &mut self,
+    svm: &mut LiteSVM,
+    lst_mint: Pubkey,
+    lst_to_sol_rate: u64,
+    collateral_weight_bps: u64,
+  ) -> Result<(), FixtureError> {
+    let addresses = self.addresses.with_collateral(lst_mint);
+    let ix = add_collateral_type_ix(&addresses, &self.authority.pubkey(), lst_to_sol_rate, collateral_weight_bps);
+    send(svm, "add_collateral_type", &[ix], &self.authority, &[])?;
+    for actor in &self.actors {
+      create_ata(svm, &self.authority, &actor.keypair.pubkey(), &lst_mint)?;
+    }
+    self.collaterals.push(Collateral {
+      addresses,
+      lst_to_sol_rate,
+      collateral_weight_bps,
+      lst_amount: 0,
+      status: CollateralStatus::Active,
+    });
+    self.record_operation_counter(svm)
+  }
+
+  /// `mint_amusd` against the collateral at `lst_mint` instead of the primary LST.
+  pub fn mint_amusd_with_collateral(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    lst_mint: &Pubkey,
+    lst_amount: u64,
+  ) -> Result<Quote, FixtureError> {
+    let (index, state) = self.collateral_quote_state(lst_mint)?;
+    let lst_received = lst_amount - transfer_fee(svm, lst_mint, lst_amount);
+    let quote = quote_mint_amusd(&state, lst_received).ok_or(FixtureError::Scenario("mint_amusd quote failed"))?;
+    let ix = mint_amusd_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, lst_mint), lst_amount, quote.to_user);
+    send(svm, "mint_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `zap_mint_amusd` of `lamports` native SOL into the wSOL leg registered
+  /// by `add_wsol_collateral`.
+  pub fn zap_mint_amusd(&mut self, svm: &mut LiteSVM, actor: &str, lamports: u64) -> Result<Quote, FixtureError> {
+    let wsol = spl_token::native_mint::ID;
+    let (index, state) = self.collateral_quote_state(&wsol)?;
+    let quote = quote_mint_amusd(&state, lamports).ok_or(FixtureError::Scenario("zap_mint_amusd quote failed"))?;
+    let ix = zap_mint_amusd_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, &wsol), lamports, quote.to_user);
+    send(svm, "zap_mint_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `zap_mint_asol` of `lamports` native SOL into the wSOL leg.
+  pub fn zap_mint_asol(&mut self, svm: &mut LiteSVM, actor: &str, lamports: u64) -> Result<Quote, FixtureError> {
+    let wsol = spl_token::native_mint::ID;
+    let (index, state) = self.collateral_quote_state(&wsol)?;
+    let quote = quote_mint_asol(&state, lamports).ok_or(FixtureError::Scenario("zap_mint_asol quote failed"))?;
+    let ix = zap_mint_asol_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, &wsol), lamports, quote.to_user);
+    send(svm, "zap_mint_asol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// `redeem_amusd_to_sol` out of the wSOL leg; the payout is unwrapped into
+  /// the actor's wallet and their wSOL account keeps whatever it held.
+  pub fn redeem_amusd_to_sol(&mut self, svm: &mut LiteSVM, actor: &str, amusd_amount: u64) -> Result<Quote, FixtureError> {
+    let wsol = spl_token::native_mint::ID;
+    let (index, state) = self.collateral_quote_state(&wsol)?;
+    let quote = quote_redeem_amusd(&state, amusd_amount).ok_or(FixtureError::Scenario("redeem_amusd_to_sol quote failed"))?;
+    let ix = redeem_amusd_to_sol_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, &wsol), amusd_amount, quote.to_user);
+    send(svm, "redeem_amusd_to_sol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `redeem_asol_to_sol` out of the wSOL leg.
+  pub fn redeem_asol_to_sol(&mut self, svm: &mut LiteSVM, actor: &str, asol_amount: u64) -> Result<Quote, FixtureError> {
+    let wsol = spl_token::native_mint::ID;
+    let (index, state) = self.collateral_quote_state(&wsol)?;
+    let quote = quote_redeem_asol(&state, asol_amount).ok_or(FixtureError::Scenario("redeem_asol_to_sol quote failed"))?;
+    let ix = redeem_asol_to_sol_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, &wsol), asol_amount, quote.to_user);
+    send(svm, "redeem_asol_to_sol", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::Asol)?;
+    Ok(quote)
+  }
+
+  /// `redeem_amusd` paid out in the collateral at `lst_mint`.
+  pub fn redeem_amusd_with_collateral(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    lst_mint: &Pubkey,
+    amusd_amount: u64,
+  ) -> Result<Quote, FixtureError> {
+    let (index, state) = self.collateral_quote_state(lst_mint)?;
+    let quote = quote_redeem_amusd(&state, amusd_amount).ok_or(FixtureError::Scenario("redeem_amusd quote failed"))?;
+    let ix = redeem_amusd_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, lst_mint), amusd_amount, quote.to_user);
+    send(svm, "redeem_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::AmUSD)?;
+    Ok(quote)
+  }
+
+  /// `redeem_amusd_v2` with `allow_partial`, paid out in the collateral at
+  /// `lst_mint`. Returns the amUSD actually redeemed and its quote.
+  pub fn redeem_amusd_partial_with_collateral(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    lst_mint: &Pubkey,
+    amusd_amount: u64,
+  ) -> Result<(u64, Quote), FixtureError> {
+    let (index, state) = self.collateral_quote_state(lst_mint)?;
+    let filled = partial_fill(&state, amusd_amount, quote_redeem_amusd);
+    let quote = quote_redeem_amusd(&state, filled).ok_or(FixtureError::Scenario("redeem_amusd quote failed"))?;
+    let args = RedeemAmusdArgs { allow_partial: true, ..RedeemAmusdArgs::v1(amusd_amount, quote.to_user) };
+    let ix = redeem_amusd_v2_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, lst_mint), args);
+    send(svm, "redeem_amusd_v2", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::AmUSD)?;
+    Ok((filled, quote))
+  }
+
+  /// `redeem_asol_v2` with `allow_partial`, paid out in the collateral at
+  /// `lst_mint`. Returns the aSOL actually redeemed and its quote.
+  pub fn redeem_asol_partial_with_collateral(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    lst_mint: &Pubkey,
+    asol_amount: u64,
+  ) -> Result<(u64, Quote), FixtureError> {
+    let (index, state) = self.collateral_quote_state(lst_mint)?;
+    let filled = partial_fill(&state, asol_amount, quote_redeem_asol);
+    let quote = quote_redeem_asol(&state, filled).ok_or(FixtureError::Scenario("redeem_asol quote failed"))?;
+    let args = RedeemAsolArgs { allow_partial: true, ..RedeemAsolArgs::v1(asol_amount, quote.to_user) };
+    let ix = redeem_asol_v2_ix(&self.collaterals[index].addresses, &self.collateral_accounts(actor, lst_mint), args);
+    send(svm, "redeem_asol_v2", &[ix], &self.actor(actor).keypair, &[])?;
+    self.commit_collateral(svm, index, &quote, Tranche::Asol)?;
+    Ok((filled, quote))
+  }
+
+  /// Move the collateral at `lst_mint` to `status`.
+  pub fn set_collateral_status(&mut self, svm: &mut LiteSVM, lst_mint: &Pubkey, status: CollateralStatus) -> Result<(), FixtureError> {
+    let (index, _) = self.collateral_quote_state(lst_mint)?;
+    let ix = set_collateral_status_ix(&self.collaterals[index].addresses, &self.authority.pubkey(), status as u8, None);
+    send(svm, "set_collateral_status", &[ix], &self.authority, &[])?;
+    self.collaterals[index].status = status;
+    self.sync_collateral_tvl()?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Admin `migrate_collateral` of `lst_amount` out of the `from` vault into
+  /// the `to` one; either may be the primary LST. The admin is minted the
+  /// replacement LST first. Returns the LST the `to` vault received.
+  pub fn migrate_collateral(&mut self, svm: &mut LiteSVM, from: &Pubkey, to: &Pubkey, lst_amount: u64) -> Result<u64, FixtureError> {
+    let (from_addresses, from_rate) = self.leg(from)?;
+    let (to_addresses, to_rate) = self.leg(to)?;
+    let to_lst_amount = mul_div_up(lst_amount, from_rate, SOL_PRECISION)
+      .and_then(|value| mul_div_up(value, SOL_PRECISION, to_rate))
+      .ok_or(FixtureError::Scenario("migration amount overflow"))?;
+    let authority = self.authority.pubkey();
+    for mint in [from, to] {
+      if svm.get_account(&self.ata(&authority, mint)).is_none() {
+        create_ata(svm, &self.authority, &authority, mint)?;
+      }
+    }
+    mint_tokens(svm, &self.authority, to, &self.ata(&authority, to), to_lst_amount)?;
+    let to_lst_received = to_lst_amount - transfer_fee(svm, to, to_lst_amount);
+    let ix = migrate_collateral_ix(&from_addresses, &to_addresses, &authority, lst_amount);
+    send(svm, "migrate_collateral", &[ix], &self.authority, &[])?;
+    *self.leg_lst_amount_mut(from)? -= lst_amount;
+    *self.leg_lst_amount_mut(to)? += to_lst_received;
+    self.sync_collateral_tvl()?;
+    self.record_operation_counter(svm)?;
+    Ok(to_lst_received)
+  }
+
+  /// `actor`'s accounts with the LST leg switched to its `lst_mint` ATA.
+  pub fn collateral_accounts(&self, actor: &str, lst_mint: &Pubkey) -> UserAccounts {
+    let accounts = self.actor(actor).accounts();
+    UserAccounts { lst_account: self.ata(&accounts.user, lst_mint), ..accounts }
+  }
+
+  /// `wallet`'s ATA for `mint` under the protocol's token program.
+  fn ata(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, &self.addresses.token_program)
+  }
+
+  /// Leg addresses and LST rate for `lst_mint`, primary or registered.
+  fn leg(&self, lst_mint: &Pubkey) -> Result<(Addresses, u64), FixtureError> {
+    if *lst_mint == self.addresses.lst_mint {
+      return Ok((self.addresses, self.expected.lst_to_sol_rate));
+    }
+    let (index, _) = self.collateral_quote_state(lst_mint)?;
+    Ok((self.collaterals[index].addresses, self.collaterals[index].lst_to_sol_rate))
+  }
+
+  fn leg_lst_amount_mut(&mut self, lst_mint: &Pubkey) -> Result<&mut u64, FixtureError> {
+    if *lst_mint == self.addresses.lst_mint {
+      return Ok(&mut self.expected.total_lst_amount);
+    }
+    let (index, _) = self.collateral_quote_state(lst_mint)?;
+    Ok(&mut self.collaterals[index].lst_amount)
+  }
+
+  fn collateral_quote_state(&self, lst_mint: &Pubkey) -> Result<(usize, QuoteState), FixtureError> {
+    let index = self
+      .collaterals
+      .iter()
+      .position(|collateral| collateral.addresses.lst_mint == *lst_mint)
+      .ok_or(FixtureError::Scenario("collateral type not registered"))?;
+    let collateral = &self.collaterals[index];
+    let state = self
+      .expected
+      .through_vault(collateral.lst_amount, collateral.lst_to_sol_rate, collateral.collateral_weight_bps)
+      .ok_or(FixtureError::Scenario("collateral quote state overflow"))?;
+    Ok((index, state))
+  }
+
+  /// Move the mock oracle and refresh the LST snapshot.
+  pub fn set_prices(&mut self, svm: &mut LiteSVM, sol_price_usd: u64, lst_to_sol_rate: u64) -> Result<(), FixtureError> {
+    let ixs = [
+      update_mock_prices_ix(&self.addresses, &self.authority.pubkey(), sol_price_usd, lst_to_sol_rate, 0, false),
+      sync_exchange_rate_ix(&self.addresses),
+    ];
+    send(svm, "set_prices", &ixs, &self.authority, &[])?;
+    self.expected.sol_price_usd = sol_price_usd;
+    self.expected.lst_to_sol_rate = lst_to_sol_rate;
+    self.expected.uncertainty_index_bps = read_global_state(svm, &self.addresses)?.uncertainty_index_bps;
+    self.record_operation_counter(svm)
+  }
+
+  /// `set_prices` through the `force` path, past `max_price_move_bps`.
+  pub fn force_prices(&mut self, svm: &mut LiteSVM, sol_price_usd: u64, lst_to_sol_rate: u64) -> Result<(), FixtureError> {
+    let ixs = [
+      update_mock_prices_ix(&self.addresses, &self.authority.pubkey(), sol_price_usd, lst_to_sol_rate, 0, true),
+      sync_exchange_rate_ix(&self.addresses),
+    ];
+    send(svm, "force_prices", &ixs, &self.authority, &[])?;
+    self.expected.sol_price_usd = sol_price_usd;
+    self.expected.lst_to_sol_rate = lst_to_sol_rate;
+    self.expected.uncertainty_index_bps = read_global_state(svm, &self.addresses)?.uncertainty_index_bps;
+    self.record_operation_counter(svm)
+  }
+
+  /// Widen or tighten the mock oracle confidence interval; the uncertainty
+  /// index follows it.
+  pub fn set_oracle_confidence(&mut self, svm: &mut LiteSVM, confidence_usd: u64) -> Result<(), FixtureError> {
+    let ix = update_mock_oracle_confidence_ix(&self.addresses, &self.authority.pubkey(), confidence_usd, None);
+    send(svm, "update_mock_oracle_confidence", &[ix], &self.authority, &[])?;
+    self.expected.uncertainty_index_bps = read_global_state(svm, &self.addresses)?.uncertainty_index_bps;
+    self.record_operation_counter(svm)
+  }
+
+  /// Bound the wall-clock age of any price used.
+  pub fn set_max_oracle_age(&mut self, svm: &mut LiteSVM, max_oracle_age_seconds: u64) -> Result<(), FixtureError> {
+    let ix = update_max_oracle_age_ix(&self.addresses, &self.authority.pubkey(), max_oracle_age_seconds, None);
+    send(svm, "update_max_oracle_age", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Smooth the mock SOL price with an EMA of this half-life; zero disables it.
+  pub fn set_ema_half_life(&mut self, svm: &mut LiteSVM, ema_half_life_slots: u64) -> Result<(), FixtureError> {
+    let ix = update_ema_half_life_ix(&self.addresses, &self.authority.pubkey(), ema_half_life_slots, None);
+    send(svm, "update_ema_half_life", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Cap the discount to NAV a rebalance pays from the treasury.
+  pub fn set_rebalance_discount(&mut self, svm: &mut LiteSVM, rebalance_discount_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_rebalance_discount_ix(&self.addresses, &self.authority.pubkey(), rebalance_discount_bps, None);
+    send(svm, "update_rebalance_discount", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  pub fn set_recovery_bonus(&mut self, svm: &mut LiteSVM, recovery_bonus_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_recovery_bonus_ix(&self.addresses, &self.authority.pubkey(), recovery_bonus_bps, None);
+    send(svm, "update_recovery_bonus", &[ix], &self.authority, &[])?;
+    self.expected.recovery_bonus_bps = recovery_bonus_bps;
+    self.record_operation_counter(svm)
+  }
+
+  pub fn set_fee_split(&mut self, svm: &mut LiteSVM, fee_split_insurance_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_fee_split_ix(&self.addresses, &self.authority.pubkey(), fee_split_insurance_bps, None);
+    send(svm, "update_fee_split", &[ix], &self.authority, &[])?;
+    self.expected.fee_split_insurance_bps = fee_split_insurance_bps;
+    self.record_operation_counter(svm)
+  }
+
+  /// Let rebalances draw up to `amount` of the treasury's aSOL (the treasury
+  /// is the authority in every scenario).
+  pub fn approve_rebalance_budget(&self, svm: &mut LiteSVM, amount: u64) -> Result<(), FixtureError> {
+    approve_tokens(svm, &self.authority, &self.addresses.treasury_asol_account, &self.addresses.global_state, amount)
+  }
+
+  /// Permissionless `rebalance` by `actor`; the model follows the chain.
+  pub fn rebalance(&mut self, svm: &mut LiteSVM, actor: &str, max_lst_amount: u64, min_asol_out: u64) -> Result<(), FixtureError> {
+    let ix = rebalance_ix(&self.addresses, &self.actor(actor).accounts(), max_lst_amount, min_asol_out);
+    send(svm, "rebalance", &[ix], &self.actor(actor).keypair, &[])?;
+    let global_state = read_global_state(svm, &self.addresses)?;
+    self.expected.total_lst_amount = global_state.total_lst_amount;
+    self.expected.asol_supply = global_state.asol_supply;
+    self.expected.rounding_reserve_lamports = global_state.rounding_reserve_lamports;
+    self.record_operation_counter(svm)
+  }
+
+  /// Bound the per-update mock price move; zero disables the breaker.
+  pub fn set_max_price_move(&mut self, svm: &mut LiteSVM, max_price_move_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_max_price_move_ix(&self.addresses, &self.authority.pubkey(), max_price_move_bps, None);
+    send(svm, "update_max_price_move", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the absolute SOL price and LST rate sanity bounds.
+  pub fn set_oracle_bounds(
+    &mut self,
+    svm: &mut LiteSVM,
+    min_sol_price_usd: u64,
+    max_sol_price_usd: u64,
+    min_lst_rate: u64,
+    max_lst_rate: u64,
+  ) -> Result<(), FixtureError> {
+    let ix = update_oracle_bounds_ix(
+      &self.addresses,
+      &self.authority.pubkey(),
+      min_sol_price_usd,
+      max_sol_price_usd,
+      min_lst_rate,
+      max_lst_rate,
+      None,
+    );
+    send(svm, "update_oracle_bounds", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  pub fn emergency_pause(&mut self, svm: &mut LiteSVM, mint_paused: bool, redeem_paused: bool) -> Result<(), FixtureError> {
+    let ix = emergency_pause_ix(&self.addresses, &self.authority.pubkey(), mint_paused, redeem_paused);
+    send(svm, "emergency_pause", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Configure the Pyth update at `feed` (selected when pricing from the mock),
+  /// or clear it with `Pubkey::default()`. Later user instructions pass `feed`
+  /// as `price_update`.
+  pub fn set_pyth_price_feed(&mut self, svm: &mut LiteSVM, feed: Pubkey) -> Result<(), FixtureError> {
+    let ix = set_pyth_price_feed_ix(&self.addresses, &self.authority.pubkey(), feed);
+    send(svm, "set_pyth_price_feed", &[ix], &self.authority, &[])?;
+    self.addresses.price_update = (feed != Pubkey::default()).then_some(feed);
+    self.record_operation_counter(svm)
+  }
+
+  /// Configure the Switchboard pull feed at `feed` (selected when pricing from
+  /// the mock), or clear it with `Pubkey::default()`. Later user instructions
+  /// pass `feed` as `switchboard_feed`.
+  pub fn set_switchboard_feed(&mut self, svm: &mut LiteSVM, feed: Pubkey) -> Result<(), FixtureError> {
+    let ix = set_switchboard_feed_ix(&self.addresses, &self.authority.pubkey(), feed, None);
+    send(svm, "set_switchboard_feed", &[ix], &self.authority, &[])?;
+    self.addresses.switchboard_feed = (feed != Pubkey::default()).then_some(feed);
+    self.record_operation_counter(svm)
+  }
+
+  /// Derive the LST rate from the stake pool at `pool` (or back to the mock
+  /// rate with `Pubkey::default()`) and sync it into GlobalState.
+  pub fn set_lst_stake_pool(&mut self, svm: &mut LiteSVM, pool: Pubkey) -> Result<(), FixtureError> {
+    self.addresses.stake_pool = (pool != Pubkey::default()).then_some(pool);
+    let ixs = [
+      set_lst_stake_pool_ix(&self.addresses, &self.authority.pubkey(), pool, None),
+      sync_exchange_rate_ix(&self.addresses),
+    ];
+    send(svm, "set_lst_stake_pool", &ixs, &self.authority, &[])?;
+    self.expected.lst_to_sol_rate = read_global_state(svm, &self.addresses)?.mock_lst_to_sol_rate;
+    self.record_operation_counter(svm)
+  }
+
+  /// Price mSOL from the Marinade state at `state` (or back to the mock rate
+  /// with `Pubkey::default()`) and sync it into GlobalState.
+  pub fn set_marinade_state(&mut self, svm: &mut LiteSVM, state: Pubkey) -> Result<(), FixtureError> {
+    self.addresses.marinade_state = (state != Pubkey::default()).then_some(state);
+    let ixs = [
+      set_marinade_state_ix(&self.addresses, &self.authority.pubkey(), state, None),
+      sync_exchange_rate_ix(&self.addresses),
+    ];
+    send(svm, "set_marinade_state", &ixs, &self.authority, &[])?;
+    self.expected.lst_to_sol_rate = read_global_state(svm, &self.addresses)?.mock_lst_to_sol_rate;
+    self.record_operation_counter(svm)
+  }
+
+  /// Bound the per-episode haircut shortfall; also resumes a tripped amUSD redeem.
+  pub fn set_haircut_breaker(&mut self, svm: &mut LiteSVM, max_episode_haircut_lamports: u64) -> Result<(), FixtureError> {
+    let ix = set_haircut_breaker_ix(&self.addresses, &self.authority.pubkey(), max_episode_haircut_lamports, None);
+    send(svm, "set_haircut_breaker", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Select the SOL/USD source among the configured feeds.
+  pub fn set_price_source(&mut self, svm: &mut LiteSVM, source: PriceSource) -> Result<(), FixtureError> {
+    let ix = set_price_source_ix(&self.addresses, &self.authority.pubkey(), source as u8, None);
+    send(svm, "set_price_source", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Cross-check SOL/USD against `source` (`PriceSource::Mock` turns it off).
+  pub fn set_secondary_price_source(
+    &mut self,
+    svm: &mut LiteSVM,
+    source: PriceSource,
+    max_oracle_deviation_bps: u64,
+  ) -> Result<(), FixtureError> {
+    let ix = set_secondary_price_source_ix(&self.addresses, &self.authority.pubkey(), source as u8, max_oracle_deviation_bps, None);
+    send(svm, "set_secondary_price_source", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Let redemptions price from `source` while the primary is stale (`None`
+  /// turns it off), charging `fallback_uncertainty_bps` extra uncertainty.
+  pub fn set_fallback_price_source(
+    &mut self,
+    svm: &mut LiteSVM,
+    source: Option<PriceSource>,
+    fallback_uncertainty_bps: u64,
+  ) -> Result<(), FixtureError> {
+    let ix = set_fallback_price_source_ix(
+      &self.addresses,
+      &self.authority.pubkey(),
+      source.map(|source| source as u8),
+      fallback_uncertainty_bps,
+      None,
+    );
+    send(svm, "set_fallback_price_source", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Price the LST through the calculator `program` over `lst_state` (or back
+  /// to the mock rate with `Pubkey::default()`) and sync it into GlobalState.
+  pub fn set_lst_calculator(&mut self, svm: &mut LiteSVM, program: Pubkey, lst_state: Pubkey) -> Result<(), FixtureError> {
+    self.addresses.lst_calculator = (program != Pubkey::default()).then_some((program, lst_state));
+    let ixs = [
+      set_lst_calculator_ix(&self.addresses, &self.authority.pubkey(), program, lst_state, None),
+      sync_exchange_rate_ix(&self.addresses),
+    ];
+    send(svm, "set_lst_calculator", &ixs, &self.authority, &[])?;
+    self.expected.lst_to_sol_rate = read_global_state(svm, &self.addresses)?.mock_lst_to_sol_rate;
+    self.record_operation_counter(svm)
+  }
+
+  /// Create a stablecoin with `usdc_decimals`, give every actor an ATA holding
+  /// `usdc_per_actor` of it and open the PSM on it; returns the mint. The PSM
+  /// keeps out of the LST book, so `expected` is unchanged.
+  pub fn init_psm(
+    &mut self,
+    svm: &mut LiteSVM,
+    usdc_decimals: u8,
+    psm_fee_bps: u64,
+    psm_debt_ceiling: u64,
+    usdc_per_actor: u64,
+  ) -> Result<Pubkey, FixtureError> {
+    let mint = Keypair::new();
+    create_usdc_mint(svm, &self.authority, &mint, &self.authority.pubkey(), usdc_decimals)?;
+    for actor in &self.actors {
+      let usdc_account = create_ata(svm, &self.authority, &actor.keypair.pubkey(), &mint.pubkey())?;
+      mint_tokens(svm, &self.authority, &mint.pubkey(), &usdc_account, usdc_per_actor)?;
+    }
+    let ix = initialize_psm_ix(&self.addresses, &self.authority.pubkey(), &mint.pubkey(), psm_fee_bps, psm_debt_ceiling);
+    send(svm, "initialize_psm", &[ix], &self.authority, &[])?;
+    self.addresses.psm_usdc_mint = Some(mint.pubkey());
+    self.record_operation_counter(svm)?;
+    Ok(mint.pubkey())
+  }
+
+  /// `actor`'s ATA for the PSM stablecoin. Panics before `init_psm`.
+  pub fn usdc_account(&self, actor: &str) -> Pubkey {
+    let mint = self.addresses.psm_usdc_mint.expect("PSM not initialized");
+    get_associated_token_address_with_program_id(&self.actor(actor).keypair.pubkey(), &mint, &spl_token::ID)
+  }
+
+  /// USDC held by `actor`
+  pub fn usdc_balance(&self, svm: &LiteSVM, actor: &str) -> u64 {
+    token_balance(svm, &self.usdc_account(actor))
+  }
+
+  pub fn psm_swap_usdc_for_amusd(&mut self, svm: &mut LiteSVM, actor: &str, usdc_amount: u64) -> Result<(), FixtureError> {
+    let ix = psm_swap_usdc_for_amusd_ix(&self.addresses, &self.actor(actor).accounts(), &self.usdc_account(actor), usdc_amount);
+    send(svm, "psm_swap_usdc_for_amusd", &[ix], &self.actor(actor).keypair, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  pub fn psm_swap_amusd_for_usdc(&mut self, svm: &mut LiteSVM, actor: &str, amusd_amount: u64) -> Result<(), FixtureError> {
+    let ix = psm_swap_amusd_for_usdc_ix(&self.addresses, &self.actor(actor).accounts(), &self.usdc_account(actor), amusd_amount);
+    send(svm, "psm_swap_amusd_for_usdc", &[ix], &self.actor(actor).keypair, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the annual stability fee; whatever accrued at the old rate is
+  /// minted to the treasury first.
+  pub fn set_stability_fee(&mut self, svm: &mut LiteSVM, stability_fee_bps_per_year: u64) -> Result<(), FixtureError> {
+    let ix = update_stability_fee_ix(&self.addresses, &self.authority.pubkey(), stability_fee_bps_per_year, None);
+    send(svm, "update_stability_fee", &[ix], &self.authority, &[])?;
+    self.expected.amusd_supply = read_global_state(svm, &self.addresses)?.amusd_supply;
+    self.record_operation_counter(svm)
+  }
+
+  /// Permissionless `accrue_stability_fee`, with the authority as payer.
+  pub fn accrue_stability_fee(&mut self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    let ix = accrue_stability_fee_ix(&self.addresses);
+    send(svm, "accrue_stability_fee", &[ix], &self.authority, &[])?;
+    self.expected.amusd_supply = read_global_state(svm, &self.addresses)?.amusd_supply;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the amUSD and aSOL supply caps; 0 lifts a cap.
+  pub fn set_supply_caps(&mut self, svm: &mut LiteSVM, amusd_supply_cap: u64, asol_supply_cap: u64) -> Result<(), FixtureError> {
+    let ix = update_supply_caps_ix(&self.addresses, &self.authority.pubkey(), amusd_supply_cap, asol_supply_cap, None);
+    send(svm, "update_supply_caps", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the LST ceiling mints may fill a vault to; 0 lifts it.
+  pub fn set_tvl_cap(&mut self, svm: &mut LiteSVM, max_total_lst_amount: u64) -> Result<(), FixtureError> {
+    let ix = update_tvl_cap_ix(&self.addresses, &self.authority.pubkey(), max_total_lst_amount, None);
+    send(svm, "update_tvl_cap", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the per-transaction deposit (LST) and redemption (amUSD/aSOL) limits; 0 lifts a limit.
+  pub fn set_transaction_limits(
+    &mut self,
+    svm: &mut LiteSVM,
+    max_single_deposit_lst: u64,
+    max_single_redeem_units: u64,
+  ) -> Result<(), FixtureError> {
+    let ix = update_transaction_limits_ix(&self.addresses, &self.authority.pubkey(), max_single_deposit_lst, max_single_redeem_units, None);
+    send(svm, "update_transaction_limits", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the per-wallet amUSD cap. Mints pass each caller's UserPosition
+  /// from here on, since a non-zero cap requires it.
+  pub fn set_per_user_amusd_cap(&mut self, svm: &mut LiteSVM, per_user_amusd_cap: u64) -> Result<(), FixtureError> {
+    let ix = update_per_user_cap_ix(&self.addresses, &self.authority.pubkey(), per_user_amusd_cap, None);
+    send(svm, "update_per_user_cap", &[ix], &self.authority, &[])?;
+    self.addresses.user_positions = true;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the per-round gross aSOL mint cap (0 lifts it) and the round length.
+  pub fn set_mint_round(&mut self, svm: &mut LiteSVM, max_asol_mint_per_round: u64, mint_round_slots: u64) -> Result<(), FixtureError> {
+    let ix = update_mint_round_ix(&self.addresses, &self.authority.pubkey(), max_asol_mint_per_round, mint_round_slots, None);
+    send(svm, "update_mint_round", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the mint/redeem gap per wallet. User instructions pass each
+  /// caller's UserPosition from here on, since a non-zero gap requires it.
+  pub fn set_min_action_gap(&mut self, svm: &mut LiteSVM, min_action_gap_slots: u64) -> Result<(), FixtureError> {
+    let ix = update_min_action_gap_ix(&self.addresses, &self.authority.pubkey(), min_action_gap_slots, None);
+    send(svm, "update_min_action_gap", &[ix], &self.authority, &[])?;
+    self.addresses.user_positions = true;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the aSOL redeem cooldown. User instructions pass each caller's
+  /// UserPosition from here on, since a non-zero cooldown requires it.
+  pub fn set_asol_redeem_cooldown(&mut self, svm: &mut LiteSVM, asol_redeem_cooldown_slots: u64) -> Result<(), FixtureError> {
+    let ix = update_asol_redeem_cooldown_ix(&self.addresses, &self.authority.pubkey(), asol_redeem_cooldown_slots, None);
+    send(svm, "update_asol_redeem_cooldown", &[ix], &self.authority, &[])?;
+    self.addresses.user_positions = true;
+    self.record_operation_counter(svm)
+  }
+
+  /// Set the largest CR move a redeem_pair may cause.
+  pub fn set_pair_cr_tolerance(&mut self, svm: &mut LiteSVM, pair_cr_tolerance_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_pair_cr_tolerance_ix(&self.addresses, &self.authority.pubkey(), pair_cr_tolerance_bps, None);
+    send(svm, "update_pair_cr_tolerance", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Model the stability fee mint_amusd and redeem_amusd accrue before they
+  /// price, so their quotes see the same supply the chain will.
+  fn expect_stability_fee_accrual(&mut self, svm: &LiteSVM) -> Result<(), FixtureError> {
+    let mut state = read_global_state(svm, &self.addresses)?;
+    let accrued = state
+      .accrue_stability_fee(current_unix_timestamp(svm))
+      .map_err(|_| FixtureError::Scenario("stability fee accrual overflow"))?;
+    self.expected.amusd_supply += accrued;
+    Ok(())
+  }
+
+  /// Create the Savings Pool and route `savings_fee_split_bps` of every
+  /// amUSD treasury fee into it. The fee only changes who holds it, so
+  /// `expected` is unchanged.
+  pub fn init_savings_pool(&mut self, svm: &mut LiteSVM, savings_fee_split_bps: u64) -> Result<(), FixtureError> {
+    let ix = initialize_savings_pool_ix(&self.addresses, &self.authority.pubkey());
+    send(svm, "initialize_savings_pool", &[ix], &self.authority, &[])?;
+    let savings_pool = savings_pool_address();
+    let pool_amusd_vault =
+      get_associated_token_address_with_program_id(&savings_pool, &self.addresses.amusd_mint, &self.addresses.token_program);
+    self.addresses.savings_pool = Some((savings_pool, pool_amusd_vault));
+    self.set_savings_fee_split(svm, savings_fee_split_bps)
+  }
+
+  pub fn set_savings_fee_split(&mut self, svm: &mut LiteSVM, savings_fee_split_bps: u64) -> Result<(), FixtureError> {
+    let ix = update_savings_fee_split_ix(&self.addresses, &self.authority.pubkey(), savings_fee_split_bps, None);
+    send(svm, "update_savings_fee_split", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Move `amount` of `actor`'s amUSD into the Savings Pool. Leaves
+  /// GlobalState untouched.
+  pub fn deposit_savings(&self, svm: &mut LiteSVM, actor: &str, amount: u64) -> Result<(), FixtureError> {
+    let ix = deposit_savings_ix(&self.addresses, &self.actor(actor).accounts(), amount);
+    send(svm, "deposit_savings", &[ix], &self.actor(actor).keypair, &[])
+  }
+
+  /// Burn `shares` of `actor`'s Savings Pool position for amUSD.
+  pub fn withdraw_savings(&self, svm: &mut LiteSVM, actor: &str, shares: u64) -> Result<(), FixtureError> {
+    let ix = withdraw_savings_ix(&self.addresses, &self.actor(actor).accounts(), shares);
+    send(svm, "withdraw_savings", &[ix], &self.actor(actor).keypair, &[])
+  }
+
+  /// Create the Stability Pool. Pool instructions leave GlobalState untouched,
+  /// so they add nothing to `operation_counters`.
+  pub fn init_stability_pool(&self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    let ix = initialize_stability_pool_ix(&self.addresses, &self.authority.pubkey());
+    send(svm, "initialize_stability_pool", &[ix], &self.authority, &[])
+  }
+
+  /// Move `amount` of `actor`'s amUSD into the Stability Pool.
+  pub fn deposit_stability(&self, svm: &mut LiteSVM, actor: &str, amount: u64) -> Result<(), FixtureError> {
+    let epoch_record = self.stale_epoch_record(svm, actor)?;
+    let ix = deposit_stability_ix(&self.addresses, &self.actor(actor).accounts(), amount, epoch_record);
+    send(svm, "deposit_stability", &[ix], &self.actor(actor).keypair, &[])
+  }
+
+  /// Withdraw `amount` of `actor`'s Stability Pool amUSD plus any LST gains.
+  pub fn withdraw_stability(&self, svm: &mut LiteSVM, actor: &str, amount: u64) -> Result<(), FixtureError> {
+    let epoch_record = self.stale_epoch_record(svm, actor)?;
+    let ix = withdraw_stability_ix(&self.addresses, &self.actor(actor).accounts(), amount, epoch_record);
+    send(svm, "withdraw_stability", &[ix], &self.actor(actor).keypair, &[])
+  }
+
+  /// Pay out `actor`'s accrued Stability Pool LST gains.
+  pub fn claim_stability_gains(&self, svm: &mut LiteSVM, actor: &str) -> Result<(), FixtureError> {
+    let epoch_record = self.stale_epoch_record(svm, actor)?;
+    let ix = claim_stability_gains_ix(&self.addresses, &self.actor(actor).accounts(), epoch_record);
+    send(svm, "claim_stability_gains", &[ix], &self.actor(actor).keypair, &[])
+  }
+
+  /// Permissionless `trigger_drawdown`, with the authority as keeper.
+  pub fn trigger_drawdown(&mut self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    let epoch = read_stability_pool(svm, &self.addresses)?.epoch;
+    let ix = trigger_drawdown_ix(&self.addresses, &self.authority.pubkey(), epoch);
+    send(svm, "trigger_drawdown", &[ix], &self.authority, &[])?;
+    let global_state = read_global_state(svm, &self.addresses)?;
+    self.expected.total_lst_amount = global_state.total_lst_amount;
+    self.expected.amusd_supply = global_state.amusd_supply;
+    self.record_operation_counter(svm)
+  }
+
+  /// Record account for `actor`'s position if a drawdown emptied its epoch.
+  fn stale_epoch_record(&self, svm: &LiteSVM, actor: &str) -> Result<Option<Pubkey>, FixtureError> {
+    let Ok(deposit) = read_stability_deposit(svm, &self.actor(actor).keypair.pubkey()) else {
+      return Ok(None);
+    };
+    let pool = read_stability_pool(svm, &self.addresses)?;
+    Ok((deposit.epoch != pool.epoch).then(|| stability_epoch_record_address(deposit.epoch)))
+  }
+
+  /// Enter global settlement; the model switches to the frozen snapshot.
+  pub fn initiate_settlement(&mut self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    let ix = initiate_settlement_ix(&self.addresses, &self.authority.pubkey());
+    send(svm, "initiate_settlement", &[ix], &self.authority, &[])?;
+    let state = read_global_state(svm, &self.addresses)?;
+    self.expected.settled = true;
+    self.expected.sol_price_usd = state.settlement_sol_price_usd;
+    self.expected.lst_to_sol_rate = state.settlement_lst_to_sol_rate;
+    self.record_operation_counter(svm)
+  }
+
+  /// Write amUSD down to the current CR; returns the new redemption rate.
+  pub fn socialize_bad_debt(&mut self, svm: &mut LiteSVM) -> Result<u64, FixtureError> {
+    let ix = socialize_bad_debt_ix(&self.addresses, &self.authority.pubkey());
+    send(svm, "socialize_bad_debt", &[ix], &self.authority, &[])?;
+    self.expected.redemption_rate_bps = read_global_state(svm, &self.addresses)?.redemption_rate_bps;
+    self.record_operation_counter(svm)?;
+    Ok(self.expected.redemption_rate_bps)
+  }
+
+  /// `claim_settlement` for `actor`; returns the LST it received.
+  pub fn claim_settlement(&mut self, svm: &mut LiteSVM, actor: &str, amusd_amount: u64, asol_amount: u64) -> Result<u64, FixtureError> {
+    let lst_before = self.balances(svm, actor).0;
+    let ix = claim_settlement_ix(&self.addresses, &self.actor(actor).accounts(), amusd_amount, asol_amount);
+    send(svm, "claim_settlement", &[ix], &self.actor(actor).keypair, &[])?;
+    let state = read_global_state(svm, &self.addresses)?;
+    self.expected.total_lst_amount = state.total_lst_amount;
+    self.expected.amusd_supply = state.amusd_supply;
+    self.expected.asol_supply = state.asol_supply;
+    self.expected.rounding_reserve_lamports = state.rounding_reserve_lamports;
+    self.expected.insurance_fund_lamports = state.insurance_fund_lamports;
+    self.record_operation_counter(svm)?;
+    Ok(self.balances(svm, actor).0 - lst_before)
+  }
+
+  /// `fund_insurance` from `actor`'s LST; returns the lamports credited to the fund.
+  pub fn fund_insurance(&mut self, svm: &mut LiteSVM, actor: &str, lst_amount: u64) -> Result<u64, FixtureError> {
+    let ix = fund_insurance_ix(&self.addresses, &self.actor(actor).accounts(), lst_amount);
+    let lst_received = lst_amount - transfer_fee(svm, &self.addresses.lst_mint, lst_amount);
+    send(svm, "fund_insurance", &[ix], &self.actor(actor).keypair, &[])?;
+    let value = compute_tvl_sol(lst_received, self.expected.lst_to_sol_rate).ok_or(FixtureError::Scenario("fund_insurance value overflow"))?;
+    self.expected.total_lst_amount += lst_received;
+    self.expected.insurance_fund_lamports += value;
+    self.record_operation_counter(svm)?;
+    Ok(value)
+  }
+
+  /// One transaction of `flash_loan_lst` to `actor`, then `between`, then
+  /// `flash_repay_lst` from `actor`; returns the fee credited to equity.
+  /// Steps to the next slot afterwards, so the loan's utilization surcharge
+  /// does not price the next scripted action.
+  pub fn flash_loan(
+    &mut self,
+    svm: &mut LiteSVM,
+    actor: &str,
+    lst_amount: u64,
+    between: &[Instruction],
+  ) -> Result<u64, FixtureError> {
+    let accounts = self.actor(actor).accounts();
+    let mut ixs = vec![flash_loan_lst_ix(&self.addresses, &accounts.user, &accounts.lst_account, lst_amount)];
+    ixs.extend_from_slice(between);
+    ixs.push(flash_repay_lst_ix(&self.addresses, &accounts));
+    send(svm, "flash_loan", &ixs, &self.actor(actor).keypair, &[])?;
+    let fee = mul_div_up(lst_amount, FLASH_LOAN_FEE_BPS, BPS_PRECISION).ok_or(FixtureError::Scenario("flash loan fee overflow"))?;
+    self.expected.total_lst_amount += fee;
+    self.record_operation_counter(svm)?;
+    svm.warp_to_slot(current_slot(svm) + 1);
+    Ok(fee)
+  }
+
+  /// Permissionless `sync_exchange_rate`, re-reading the configured rate source.
+  pub fn sync_exchange_rate(&mut self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    send(svm, "sync_exchange_rate", &[sync_exchange_rate_ix(&self.addresses)], &self.authority, &[])?;
+    self.expected.lst_to_sol_rate = read_global_state(svm, &self.addresses)?.mock_lst_to_sol_rate;
+    self.record_operation_counter(svm)
+  }
+
+  /// Permissionless `refresh_tvl` over every registered collateral vault.
+  pub fn refresh_tvl(&mut self, svm: &mut LiteSVM) -> Result<(), FixtureError> {
+    let mut collateral_vaults: Vec<Pubkey> =
+      self.collaterals.iter().filter_map(|collateral| collateral.addresses.collateral_vault).collect();
+    collateral_vaults.sort();
+    let ix = refresh_tvl_ix(&self.addresses, &self.authority.pubkey(), &collateral_vaults);
+    send(svm, "refresh_tvl", &[ix], &self.authority, &[])?;
+    self.record_operation_counter(svm)
+  }
+
+  /// Check the on-chain GlobalState and vault balance against `expected`.
+  pub fn assert_matches_chain(&self, svm: &LiteSVM) -> Result<(), FixtureError> {
+    let global_state = read_global_state(svm, &self.addresses)?;
+    if QuoteState::from(&global_state) != self.expected {
+      return Err(FixtureError::Scenario("on-chain GlobalState diverged from fixture model"));
+    }
+    if token_balance(svm, &self.addresses.vault) != self.expected.total_lst_amount {
+      return Err(FixtureError::Scenario("vault balance diverged from total_lst_amount"));
+    }
+    for collateral in &self.collaterals {
+      let collateral_vault = read_collateral_vault(svm, &collateral.addresses)?;
+      if collateral_vault.lst_amount != collateral.lst_amount || collateral_vault.status != collateral.status as u8 {
+        return Err(FixtureError::Scenario("CollateralVault diverged from fixture model"));
+      }
+      if token_balance(svm, &collateral.addresses.vault) != collateral.lst_amount {
+        return Err(FixtureError::Scenario("collateral vault balance diverged from lst_amount"));
+      }
+    }
+    if !self.operation_counters.windows(2).all(|pair| pair[0] < pair[1]) {
+      return Err(FixtureError::Scenario("operation_counter was not strictly monotonic"));
+    }
+    if self.operation_counters.last() != Some(&global_state.operation_counter) {
+      return Err(FixtureError::Scenario("operation_counter moved outside the recorded history"));
+    }
+    Ok(())
+  }
+
+  fn record_operation_counter(&mut self, svm: &LiteSVM) -> Result<(), FixtureError> {
+    let global_state = read_global_state(svm, &self.addresses)?;
+    self.operation_counters.push(global_state.operation_counter);
+    Ok(())
+  }
+
+  fn commit(&mut self, svm: &LiteSVM, quote: &Quote, tranche: Tranche) -> Result<(), FixtureError> {
+    self.expected.total_lst_amount = quote.new_total_lst_amount;
+    self.commit_book(svm, quote, tranche)
+  }
+
+  /// `quote` was taken through collateral `index`, so its LST total is that vault's.
+  fn commit_collateral(&mut self, svm: &LiteSVM, index: usize, quote: &Quote, tranche: Tranche) -> Result<(), FixtureError> {
+    self.collaterals[index].lst_amount = quote.new_total_lst_amount;
+    self.sync_collateral_tvl()?;
+    self.commit_book(svm, quote, tranche)
+  }
+
+  /// Re-derive the book's collateral TVL, weighted TVL and deprecated part from the vaults.
+  fn sync_collateral_tvl(&mut self) -> Result<(), FixtureError> {
+    let tvl = |weighted: bool, deprecated_only: bool| {
+      self
+        .collaterals
+        .iter()
+        .filter(|collateral| !deprecated_only || collateral.status == CollateralStatus::Deprecated)
+        .try_fold(0u64, |tvl, collateral| {
+          let weight_bps = if weighted { collateral.collateral_weight_bps } else { BPS_PRECISION };
+          tvl.checked_add(compute_weighted_tvl_sol(collateral.lst_amount, collateral.lst_to_sol_rate, weight_bps)?)
+        })
+        .ok_or(FixtureError::Scenario("collateral TVL overflow"))
+    };
+    self.expected.other_tvl_lamports = tvl(false, false)?;
+    self.expected.other_weighted_tvl_lamports = tvl(true, false)?;
+    self.expected.deprecated_tvl_lamports = tvl(true, true)?;
+    Ok(())
+  }
+
+  fn commit_book(&mut self, svm: &LiteSVM, quote: &Quote, tranche: Tranche) -> Result<(), FixtureError> {
+    match tranche {
+      Tranche::AmUSD => self.expected.amusd_supply = quote.new_supply,
+      Tranche::Asol => self.expected.asol_supply = quote.new_supply,
+    }
+    self.expected.rounding_reserve_lamports = quote.new_rounding_reserve;
+    self.expected.insurance_fund_lamports = quote.new_insurance_fund;
+    self.record_operation_counter(svm)
+  }
+}
+
+/// Largest part of `requested` a redemption with `allow_partial` fills: paid
+/// out of the quoted vault alone, which it leaves empty or at MIN_PROTOCOL_TVL.
+pub fn partial_fill(state: &QuoteState, requested: u64, quote: fn(&QuoteState, u64) -> Option<Quote>) -> u64 {
+  compute_partial_fill(requested, |units| {
+    quote(state, units).is_some_and(|quote| {
+      quote.to_user <= state.total_lst_amount
+        && (state.settled || quote.new_total_lst_amount >= MIN_PROTOCOL_TVL || quote.new_total_lst_amount == 0)
+    })
+  })
+}
@@ -0,0 +1,45 @@
+//! Crafted Marinade state accounts
+//! Only the fields Laminar reads (discriminator, mSOL mint, `msol_price`) are
+//! meaningful; the rest of the `State` layout is zero-filled.
+
+use laminar::lst_rate::{
+  MARINADE_MSOL_MINT_OFFSET, MARINADE_MSOL_PRICE_OFFSET, MARINADE_PRICE_DENOMINATOR, MARINADE_STATE_DISCRIMINATOR,
+};
+use laminar::math::SOL_PRECISION;
+use litesvm::LiteSVM;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::FixtureError;
+
+/// Size of Marinade's `State` account including the discriminator
+const MARINADE_STATE_LEN: usize = 8 + 1_000;
+
+/// 32.32 `msol_price` for a rate at `SOL_PRECISION` scale
+pub fn msol_price_for_rate(lst_to_sol_rate: u64) -> u64 {
+  ((lst_to_sol_rate as u128 * MARINADE_PRICE_DENOMINATOR as u128) / SOL_PRECISION as u128) as u64
+}
+
+/// Create or overwrite the Marinade state at `address`, owned by `owner`.
+pub fn write_marinade_state(
+  svm: &mut LiteSVM,
+  address: &Pubkey,
+  msol_mint: &Pubkey,
+  msol_price: u64,
+  owner: Pubkey,
+) -> Result<(), FixtureError> {
+  let mut data = vec![0u8; MARINADE_STATE_LEN];
+  data[..8].copy_from_slice(&MARINADE_STATE_DISCRIMINATOR);
+  data[MARINADE_MSOL_MINT_OFFSET..MARINADE_MSOL_MINT_OFFSET + 32].copy_from_slice(msol_mint.as_ref());
+  data[MARINADE_MSOL_PRICE_OFFSET..MARINADE_MSOL_PRICE_OFFSET + 8].copy_from_slice(&msol_price.to_le_bytes());
+
+  let account = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(*address, account)
+    .map_err(|_| FixtureError::Scenario("marinade state account rejected by LiteSVM"))
+}
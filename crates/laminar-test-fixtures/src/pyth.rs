@@ -0,0 +1,77 @@
+//! Crafted Pyth `PriceUpdateV2` accounts
+//! Price updates are owned by the Pyth receiver program, so unlike Laminar
+//! state they are written directly rather than reached through transactions.
+
+use anchor_lang::AccountSerialize;
+use litesvm::LiteSVM;
+use pyth_solana_receiver_sdk::price_update::{PriceFeedMessage, PriceUpdateV2, VerificationLevel};
+use solana_sdk::{account::Account, pubkey::Pubkey, sysvar::clock::Clock};
+
+use crate::FixtureError;
+
+/// Exponent real SOL/USD feeds publish with
+pub const PYTH_EXPONENT: i32 = -8;
+
+/// Contents of a crafted price update.
+#[derive(Clone, Copy, Debug)]
+pub struct PythPrice {
+  pub price: i64,
+  pub conf: u64,
+  pub exponent: i32,
+  pub posted_slot: u64,
+  pub fully_verified: bool,
+}
+
+impl PythPrice {
+  /// Fully verified update quoting `price_usd` +/- `confidence_usd` (micro-USD).
+  pub fn from_micro_usd(price_usd: u64, confidence_usd: u64, posted_slot: u64) -> Self {
+    // micro-USD (1e-6) -> 1e-8
+    Self {
+      price: (price_usd * 100) as i64,
+      conf: confidence_usd * 100,
+      exponent: PYTH_EXPONENT,
+      posted_slot,
+      fully_verified: true,
+    }
+  }
+}
+
+/// Create or overwrite the price update at `address`.
+pub fn write_price_update(svm: &mut LiteSVM, address: &Pubkey, price: &PythPrice) -> Result<(), FixtureError> {
+  let publish_time = svm.get_sysvar::<Clock>().unix_timestamp;
+  let update = PriceUpdateV2 {
+    write_authority: Pubkey::new_unique(),
+    verification_level: if price.fully_verified {
+      VerificationLevel::Full
+    } else {
+      VerificationLevel::Partial { num_signatures: 1 }
+    },
+    price_message: PriceFeedMessage {
+      feed_id: [0xef; 32],
+      price: price.price,
+      conf: price.conf,
+      exponent: price.exponent,
+      publish_time,
+      prev_publish_time: publish_time,
+      ema_price: price.price,
+      ema_conf: price.conf,
+    },
+    posted_slot: price.posted_slot,
+  };
+
+  let mut data = Vec::with_capacity(PriceUpdateV2::LEN);
+  update
+    .try_serialize(&mut data)
+    .map_err(|_| FixtureError::Scenario("price update failed to serialize"))?;
+
+  let account = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner: pyth_solana_receiver_sdk::ID,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(*address, account)
+    .map_err(|_| FixtureError::Scenario("price update account rejected by LiteSVM"))
+}
@@ -0,0 +1,256 @@
+//! Named scenario scripts
+//! Scenarios build on each other (Healthy -> NearMinCr -> Insolvent -> PostHaircut,
+//! Healthy -> DustHolders) and size every step from the quote module instead of
+//! hardcoded amounts.
+
+use laminar::constants::{DEFAULT_MIN_CR_BPS, DEFAULT_TARGET_CR_BPS, MIN_AMUSD_MINT, MIN_ASOL_MINT};
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol, BPS_PRECISION, MIN_LST_DEPOSIT, SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_mint_amusd, QuoteState};
+use litesvm::LiteSVM;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::harness::*;
+use crate::{Actor, Fixture, FixtureError};
+
+pub const WHALE: &str = "whale";
+pub const MINNOW: &str = "minnow";
+/// Holds `MIN_AMUSD_MINT - 1` amUSD
+pub const DUST_AMUSD: &str = "dust_amusd";
+/// Holds `MIN_ASOL_MINT - 1` aSOL
+pub const DUST_ASOL: &str = "dust_asol";
+/// Holds one base unit of each tranche
+pub const DUST_BOTH: &str = "dust_both";
+
+pub const INITIAL_SOL_PRICE_USD: u64 = 100 * USD_PRECISION;
+pub const INITIAL_LST_TO_SOL_RATE: u64 = 1_050_000_000;
+
+pub const WHALE_LST: u64 = 100_000 * SOL_PRECISION;
+pub const MINNOW_LST: u64 = 10 * SOL_PRECISION;
+
+/// CR the Healthy scenario settles at
+pub const HEALTHY_CR_BPS: u64 = DEFAULT_TARGET_CR_BPS + 5_000;
+/// NearMinCr settles within this many bps above `min_cr_bps`
+pub const NEAR_MIN_CR_BUFFER_BPS: u64 = 50;
+/// CR the Insolvent scenario's price drop targets
+pub const INSOLVENT_CR_BPS: u64 = 9_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scenario {
+  /// Whale and minnow hold both tranches; CR ~ HEALTHY_CR_BPS
+  HealthyProtocol,
+  /// Whale levered the book down to just above min_cr_bps
+  NearMinCr,
+  /// NearMinCr after a SOL price drop to ~INSOLVENT_CR_BPS
+  Insolvent,
+  /// Insolvent after amUSD holders redeemed through the haircut
+  PostHaircut,
+  /// Healthy plus wallets holding sub-minimum balances
+  DustHolders,
+  /// Initialized with funded actors and nothing minted, so the next
+  /// mint_asol takes the bootstrap path
+  Fresh,
+}
+
+impl Scenario {
+  /// Every scenario with scripted history; `Fresh` has none
+  pub const ALL: [Scenario; 5] = [
+    Scenario::HealthyProtocol,
+    Scenario::NearMinCr,
+    Scenario::Insolvent,
+    Scenario::PostHaircut,
+    Scenario::DustHolders,
+  ];
+}
+
+/// Bring a fresh `svm` (see `crate::new_svm`) into `scenario`.
+pub fn build(svm: &mut LiteSVM, scenario: Scenario) -> Result<Fixture, FixtureError> {
+  build_on(svm, scenario, None)
+}
+
+/// `build` with the protocol on Token-2022 and an LST mint that withholds
+/// `transfer_fee_bps` of every transfer.
+pub fn build_with_transfer_fee(svm: &mut LiteSVM, scenario: Scenario, transfer_fee_bps: u16) -> Result<Fixture, FixtureError> {
+  build_on(svm, scenario, Some(transfer_fee_bps))
+}
+
+fn build_on(svm: &mut LiteSVM, scenario: Scenario, transfer_fee_bps: Option<u16>) -> Result<Fixture, FixtureError> {
+  let mut fixture = initialize(svm, scenario, transfer_fee_bps)?;
+
+  if scenario != Scenario::Fresh {
+    healthy(svm, &mut fixture)?;
+  }
+  match scenario {
+    Scenario::HealthyProtocol | Scenario::Fresh => {}
+    Scenario::NearMinCr => near_min_cr(svm, &mut fixture)?,
+    Scenario::Insolvent => {
+      near_min_cr(svm, &mut fixture)?;
+      insolvent(svm, &mut fixture)?;
+    }
+    Scenario::PostHaircut => {
+      near_min_cr(svm, &mut fixture)?;
+      insolvent(svm, &mut fixture)?;
+      post_haircut(svm, &mut fixture)?;
+    }
+    Scenario::DustHolders => dust_holders(svm, &mut fixture)?,
+  }
+
+  fixture.assert_matches_chain(svm)?;
+  Ok(fixture)
+}
+
+/// Post-mint CR of an amUSD mint of `lst_amount`
+fn cr_after_amusd_mint(state: &QuoteState, lst_amount: u64) -> Option<u64> {
+  let quote = quote_mint_amusd(state, lst_amount)?;
+  let tvl = compute_tvl_sol(quote.new_total_lst_amount, state.lst_to_sol_rate)?;
+  let liability = compute_liability_sol(quote.new_supply, state.sol_price_usd)?;
+  Some(compute_cr_bps(tvl, liability))
+}
+
+/// Largest LST deposit in `[MIN_LST_DEPOSIT, max_lst]` whose amUSD mint keeps CR >= `floor_cr_bps`.
+/// Post-mint CR falls monotonically with deposit size while CR > 100%.
+pub fn max_amusd_mint_for_cr(state: &QuoteState, floor_cr_bps: u64, max_lst: u64) -> Option<u64> {
+  if cr_after_amusd_mint(state, MIN_LST_DEPOSIT)? < floor_cr_bps {
+    return None;
+  }
+
+  let (mut lo, mut hi) = (MIN_LST_DEPOSIT, max_lst);
+  while lo < hi {
+    let mid = lo + (hi - lo).div_ceil(2);
+    match cr_after_amusd_mint(state, mid) {
+      Some(cr) if cr >= floor_cr_bps => lo = mid,
+      _ => hi = mid - 1,
+    }
+  }
+  Some(lo)
+}
+
+/// SOL price at which the current book sits at `cr_bps`.
+pub fn price_for_cr(state: &QuoteState, cr_bps: u64) -> Option<u64> {
+  let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate)? as u128;
+  // CR = tvl * BPS / (amusd * SOL / price)  =>  price = CR * amusd * SOL / (tvl * BPS)
+  let numerator = (cr_bps as u128)
+    .checked_mul(state.amusd_supply as u128)?
+    .checked_mul(SOL_PRECISION as u128)?;
+  let denominator = tvl.checked_mul(BPS_PRECISION as u128)?;
+  if denominator == 0 {
+    return None;
+  }
+  u64::try_from(numerator / denominator).ok().filter(|price| *price > 0)
+}
+
+fn new_actor(
+  svm: &mut LiteSVM,
+  name: &'static str,
+  addresses: &Addresses,
+  lst_mint_authority: &Keypair,
+  lst_amount: u64,
+) -> Result<Actor, FixtureError> {
+  let keypair = Keypair::new();
+  airdrop(svm, &keypair.pubkey(), 100 * SOL_PRECISION)?;
+
+  let lst_account = create_ata(svm, &keypair, &keypair.pubkey(), &addresses.lst_mint)?;
+  let amusd_account = create_ata(svm, &keypair, &keypair.pubkey(), &addresses.amusd_mint)?;
+  let asol_account = create_ata(svm, &keypair, &keypair.pubkey(), &addresses.asol_mint)?;
+  if lst_amount > 0 {
+    mint_tokens(svm, lst_mint_authority, &addresses.lst_mint, &lst_account, lst_amount)?;
+  }
+
+  Ok(Actor { name, keypair, lst_account, amusd_account, asol_account })
+}
+
+fn initialize(svm: &mut LiteSVM, scenario: Scenario, transfer_fee_bps: Option<u16>) -> Result<Fixture, FixtureError> {
+  let authority = Keypair::new();
+  airdrop(svm, &authority.pubkey(), 1_000 * SOL_PRECISION)?;
+
+  let lst_mint = Keypair::new();
+  let amusd_mint = Keypair::new();
+  let asol_mint = Keypair::new();
+  match transfer_fee_bps {
+    None => create_lst_mint(svm, &authority, &lst_mint, &authority.pubkey())?,
+    Some(bps) => create_transfer_fee_lst_mint(svm, &authority, &lst_mint, &authority.pubkey(), bps, u64::MAX)?,
+  }
+
+  // initialize() makes the authority the treasury and creates amUSD/aSOL
+  // under the LST's token program
+  let addresses = Addresses::derive_for_token_program(
+    amusd_mint.pubkey(),
+    asol_mint.pubkey(),
+    lst_mint.pubkey(),
+    authority.pubkey(),
+    token_program_of(svm, &lst_mint.pubkey()),
+  );
+
+  let ixs = [
+    initialize_ix(&addresses, &authority.pubkey(), DEFAULT_MIN_CR_BPS, DEFAULT_TARGET_CR_BPS, INITIAL_SOL_PRICE_USD, INITIAL_LST_TO_SOL_RATE),
+    sync_exchange_rate_ix(&addresses),
+  ];
+  send(svm, "initialize", &ixs, &authority, &[&amusd_mint, &asol_mint])?;
+
+  let actors = vec![
+    new_actor(svm, WHALE, &addresses, &authority, WHALE_LST)?,
+    new_actor(svm, MINNOW, &addresses, &authority, MINNOW_LST)?,
+  ];
+  let global_state = read_global_state(svm, &addresses)?;
+  let expected = QuoteState::from(&global_state);
+  let operation_counters = vec![global_state.operation_counter];
+
+  Ok(Fixture { scenario, addresses, authority, actors, expected, operation_counters, collaterals: Vec::new() })
+}
+
+fn healthy(svm: &mut LiteSVM, fixture: &mut Fixture) -> Result<(), FixtureError> {
+  fixture.mint_asol(svm, WHALE, 1_000 * SOL_PRECISION)?;
+  fixture.mint_asol(svm, MINNOW, SOL_PRECISION)?;
+  fixture.mint_amusd(svm, MINNOW, SOL_PRECISION)?;
+
+  let whale_lst = fixture.balances(svm, WHALE).0;
+  let lst_amount = max_amusd_mint_for_cr(&fixture.expected, HEALTHY_CR_BPS, whale_lst)
+    .ok_or(FixtureError::Scenario("cannot reach HEALTHY_CR_BPS"))?;
+  fixture.mint_amusd(svm, WHALE, lst_amount)?;
+  Ok(())
+}
+
+fn near_min_cr(svm: &mut LiteSVM, fixture: &mut Fixture) -> Result<(), FixtureError> {
+  let floor_cr_bps = fixture.expected.min_cr_bps + NEAR_MIN_CR_BUFFER_BPS;
+  let whale_lst = fixture.balances(svm, WHALE).0;
+  let lst_amount = max_amusd_mint_for_cr(&fixture.expected, floor_cr_bps, whale_lst)
+    .ok_or(FixtureError::Scenario("cannot reach min_cr_bps + buffer"))?;
+  fixture.mint_amusd(svm, WHALE, lst_amount)?;
+  Ok(())
+}
+
+fn insolvent(svm: &mut LiteSVM, fixture: &mut Fixture) -> Result<(), FixtureError> {
+  let price = price_for_cr(&fixture.expected, INSOLVENT_CR_BPS)
+    .ok_or(FixtureError::Scenario("no SOL price yields INSOLVENT_CR_BPS"))?;
+  let lst_to_sol_rate = fixture.expected.lst_to_sol_rate;
+  fixture.set_prices(svm, price, lst_to_sol_rate)
+}
+
+fn post_haircut(svm: &mut LiteSVM, fixture: &mut Fixture) -> Result<(), FixtureError> {
+  let minnow_amusd = fixture.balances(svm, MINNOW).1;
+  fixture.redeem_amusd(svm, MINNOW, minnow_amusd)?;
+
+  let whale_amusd = fixture.balances(svm, WHALE).1;
+  fixture.redeem_amusd(svm, WHALE, whale_amusd / 2)?;
+  Ok(())
+}
+
+fn dust_holders(svm: &mut LiteSVM, fixture: &mut Fixture) -> Result<(), FixtureError> {
+  let holdings = [
+    (DUST_AMUSD, MIN_AMUSD_MINT - 1, 0),
+    (DUST_ASOL, 0, MIN_ASOL_MINT - 1),
+    (DUST_BOTH, 1, 1),
+  ];
+
+  for (name, amusd, asol) in holdings {
+    let actor = new_actor(svm, name, &fixture.addresses, &fixture.authority, 0)?;
+    let whale = fixture.actor(WHALE);
+    if amusd > 0 {
+      transfer_tokens(svm, &whale.keypair, &whale.amusd_account, &actor.amusd_account, amusd)?;
+    }
+    if asol > 0 {
+      transfer_tokens(svm, &whale.keypair, &whale.asol_account, &actor.asol_account, asol)?;
+    }
+    fixture.actors.push(actor);
+  }
+  Ok(())
+}
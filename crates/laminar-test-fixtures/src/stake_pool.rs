@@ -0,0 +1,148 @@
+//! Crafted SPL stake pool accounts
+//! Stake pools belong to the SPL stake pool program and are written directly.
+//! Only the leading `StakePoolHeader` fields Laminar reads are meaningful.
+//!
+//! Redeem-to-SOL needs a pool program to unstake through: the compiled
+//! `mock_spl_stake_pool.so` is loaded at the SPL stake pool id, and
+//! `write_withdrawable_stake_pool` lays out a pool it can pay out of. The SOL
+//! zap stakes through the same pool once `open_stake_pool_deposits` has
+//! handed it the LST's mint authority.
+
+use std::path::PathBuf;
+
+use anchor_lang::AnchorDeserialize;
+use laminar::lst_rate::{StakePoolHeader, SPL_STAKE_POOL_PROGRAM_ID};
+use laminar_client::StakePoolExit;
+use litesvm::LiteSVM;
+use mock_spl_stake_pool::SOL_WITHDRAWAL_FEE_OFFSET;
+use solana_sdk::{account::Account, pubkey::Pubkey, signature::Keypair, sysvar::clock::Clock};
+
+use crate::harness::{create_ata, set_mint_authority};
+use crate::FixtureError;
+
+/// Overrides the path of the compiled mock stake pool.
+pub const STAKE_POOL_SO_ENV: &str = "MOCK_STAKE_POOL_SO";
+
+/// Header of an initialized pool for `pool_mint` with the given balances.
+pub fn stake_pool_header(pool_mint: Pubkey, total_lamports: u64, pool_token_supply: u64, last_update_epoch: u64) -> StakePoolHeader {
+  StakePoolHeader {
+    account_type: 1,
+    manager: Pubkey::new_unique(),
+    staker: Pubkey::new_unique(),
+    stake_deposit_authority: Pubkey::new_unique(),
+    stake_withdraw_bump_seed: 255,
+    validator_list: Pubkey::new_unique(),
+    reserve_stake: Pubkey::new_unique(),
+    pool_mint,
+    manager_fee_account: Pubkey::new_unique(),
+    token_program_id: anchor_spl::token::ID,
+    total_lamports,
+    pool_token_supply,
+    last_update_epoch,
+  }
+}
+
+/// Create or overwrite the stake pool at `address`, owned by `owner`.
+pub fn write_stake_pool(svm: &mut LiteSVM, address: &Pubkey, header: &StakePoolHeader, owner: Pubkey) -> Result<(), FixtureError> {
+  write_stake_pool_with_fee(svm, address, header, owner, 0)
+}
+
+fn write_stake_pool_with_fee(
+  svm: &mut LiteSVM,
+  address: &Pubkey,
+  header: &StakePoolHeader,
+  owner: Pubkey,
+  sol_withdrawal_fee_bps: u64,
+) -> Result<(), FixtureError> {
+  let mut data = anchor_lang::prelude::borsh::to_vec(header)
+    .map_err(|_| FixtureError::Scenario("stake pool header failed to serialize"))?;
+  // Stand-in for the fee and deposit-authority fields that follow the header.
+  data.resize(data.len() + 256, 0);
+  data[SOL_WITHDRAWAL_FEE_OFFSET..SOL_WITHDRAWAL_FEE_OFFSET + 8].copy_from_slice(&sol_withdrawal_fee_bps.to_le_bytes());
+
+  let account = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(*address, account)
+    .map_err(|_| FixtureError::Scenario("stake pool account rejected by LiteSVM"))
+}
+
+/// Write an SPL-owned stake pool.
+pub fn write_spl_stake_pool(svm: &mut LiteSVM, address: &Pubkey, header: &StakePoolHeader) -> Result<(), FixtureError> {
+  write_stake_pool(svm, address, header, SPL_STAKE_POOL_PROGRAM_ID)
+}
+
+/// `mock_spl_stake_pool.so` location: `$MOCK_STAKE_POOL_SO`, else this workspace's `target/deploy`.
+pub fn stake_pool_so_path() -> PathBuf {
+  std::env::var_os(STAKE_POOL_SO_ENV)
+    .map(PathBuf::from)
+    .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/mock_spl_stake_pool.so")))
+}
+
+/// Load the mock stake pool at the SPL stake pool program id.
+pub fn load_mock_stake_pool(svm: &mut LiteSVM) -> Result<(), FixtureError> {
+  let path = stake_pool_so_path();
+  svm
+    .add_program_from_file(SPL_STAKE_POOL_PROGRAM_ID, &path)
+    .map_err(|_| FixtureError::ProgramNotFound(path))
+}
+
+/// Write a pool the mock can unstake out of: its reserve holds
+/// `header.total_lamports` and its manager fee account is an ATA of
+/// `header.pool_mint` paid for by `payer`. `sol_withdrawal_fee_bps` of every
+/// `WithdrawSol` goes to the manager.
+pub fn write_withdrawable_stake_pool(
+  svm: &mut LiteSVM,
+  payer: &Keypair,
+  address: &Pubkey,
+  mut header: StakePoolHeader,
+  sol_withdrawal_fee_bps: u64,
+) -> Result<StakePoolExit, FixtureError> {
+  header.manager_fee_account = create_ata(svm, payer, &header.manager, &header.pool_mint)?;
+  let reserve = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(0) + header.total_lamports,
+    data: Vec::new(),
+    owner: SPL_STAKE_POOL_PROGRAM_ID,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(header.reserve_stake, reserve)
+    .map_err(|_| FixtureError::Scenario("reserve stake account rejected by LiteSVM"))?;
+  write_stake_pool_with_fee(svm, address, &header, SPL_STAKE_POOL_PROGRAM_ID, sol_withdrawal_fee_bps)?;
+  Ok(StakePoolExit::from_header(*address, &header))
+}
+
+/// Let `exit`'s pool mint its LST for `DepositSol`: the mock mints under the
+/// pool's withdraw authority, as the real program does.
+pub fn open_stake_pool_deposits(
+  svm: &mut LiteSVM,
+  mint_authority: &Keypair,
+  pool_mint: &Pubkey,
+  exit: &StakePoolExit,
+) -> Result<(), FixtureError> {
+  set_mint_authority(svm, mint_authority, pool_mint, &exit.withdraw_authority)
+}
+
+/// Pool tokens the mock mints for a `DepositSol` of `lamports` into the pool
+/// at `address`, at its current balances.
+pub fn pool_tokens_for_deposit(svm: &LiteSVM, address: &Pubkey, lamports: u64) -> Result<u64, FixtureError> {
+  let account = svm.get_account(address).ok_or(FixtureError::Scenario("stake pool account missing"))?;
+  let header = StakePoolHeader::deserialize(&mut &account.data[..])
+    .map_err(|_| FixtureError::Scenario("stake pool header failed to deserialize"))?;
+  if header.total_lamports == 0 {
+    return Err(FixtureError::Scenario("stake pool holds no lamports"));
+  }
+  Ok((lamports as u128 * header.pool_token_supply as u128 / header.total_lamports as u128) as u64)
+}
+
+pub fn set_epoch(svm: &mut LiteSVM, epoch: u64) {
+  let mut clock = svm.get_sysvar::<Clock>();
+  clock.epoch = epoch;
+  svm.set_sysvar(&clock);
+}
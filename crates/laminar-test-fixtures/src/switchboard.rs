@@ -0,0 +1,62 @@
+//! Crafted Switchboard on-demand pull feeds
+//! Like Pyth updates, feeds belong to an external program and are written
+//! directly. Only the aggregated `result` the adapter reads is populated.
+
+use laminar::switchboard::{
+  ON_DEMAND_MAINNET_PID, PULL_FEED_ACCOUNT_LEN, PULL_FEED_DISCRIMINATOR, PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET,
+  PULL_FEED_RESULT_NUM_SAMPLES_OFFSET, PULL_FEED_RESULT_SLOT_OFFSET, PULL_FEED_RESULT_STD_DEV_OFFSET,
+  PULL_FEED_RESULT_VALUE_OFFSET,
+};
+use litesvm::LiteSVM;
+use solana_sdk::{account::Account, pubkey::Pubkey, sysvar::clock::Clock};
+
+use crate::FixtureError;
+
+/// 18-decimal fixed point used by Switchboard results
+pub const SWITCHBOARD_ONE: i128 = 1_000_000_000_000_000_000;
+
+/// Contents of a crafted feed result.
+#[derive(Clone, Copy, Debug)]
+pub struct SwitchboardPrice {
+  pub value: i128,
+  pub std_dev: i128,
+  pub slot: u64,
+  pub num_samples: u8,
+}
+
+impl SwitchboardPrice {
+  /// Feed result of `price_usd` with standard deviation `std_dev_usd` (micro-USD).
+  pub fn from_micro_usd(price_usd: u64, std_dev_usd: u64, slot: u64) -> Self {
+    // micro-USD (1e-6) -> 1e-18
+    let scale = SWITCHBOARD_ONE / 1_000_000;
+    Self {
+      value: price_usd as i128 * scale,
+      std_dev: std_dev_usd as i128 * scale,
+      slot,
+      num_samples: 3,
+    }
+  }
+}
+
+/// Create or overwrite the pull feed at `address`.
+pub fn write_pull_feed(svm: &mut LiteSVM, address: &Pubkey, price: &SwitchboardPrice) -> Result<(), FixtureError> {
+  let mut data = vec![0u8; PULL_FEED_ACCOUNT_LEN];
+  data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+  data[PULL_FEED_RESULT_VALUE_OFFSET..][..16].copy_from_slice(&price.value.to_le_bytes());
+  data[PULL_FEED_RESULT_STD_DEV_OFFSET..][..16].copy_from_slice(&price.std_dev.to_le_bytes());
+  data[PULL_FEED_RESULT_NUM_SAMPLES_OFFSET] = price.num_samples;
+  data[PULL_FEED_RESULT_SLOT_OFFSET..][..8].copy_from_slice(&price.slot.to_le_bytes());
+  let timestamp = svm.get_sysvar::<Clock>().unix_timestamp;
+  data[PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET..][..8].copy_from_slice(&timestamp.to_le_bytes());
+
+  let account = Account {
+    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+    data,
+    owner: ON_DEMAND_MAINNET_PID,
+    executable: false,
+    rent_epoch: 0,
+  };
+  svm
+    .set_account(*address, account)
+    .map_err(|_| FixtureError::Scenario("pull feed account rejected by LiteSVM"))
+}
@@ -0,0 +1,93 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_mint_asol, quote_redeem_asol};
+use laminar_test_fixtures::harness::{mint_asol_ix, read_user_position, redeem_asol_ix, send};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+fn healthy_with_gap(min_action_gap_slots: u64) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.set_min_action_gap(&mut svm, min_action_gap_slots).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// Move to `slot` and refresh the LST snapshot there.
+fn warp_to(svm: &mut LiteSVM, fixture: &mut Fixture, slot: u64) {
+    svm.warp_to_slot(slot);
+    fixture.sync_exchange_rate(svm).unwrap();
+}
+
+fn last_mint_slot(svm: &LiteSVM, fixture: &Fixture, actor: &str) -> u64 {
+    read_user_position(svm, &fixture.actor(actor).keypair.pubkey()).unwrap().last_mint_slot
+}
+
+#[test]
+fn mint_and_redeem_in_one_transaction_is_rejected() {
+    let (mut svm, fixture) = healthy_with_gap(1);
+    let actor = fixture.actor(MINNOW);
+    let accounts = actor.accounts();
+    let minted = quote_mint_asol(&fixture.expected, SOL_PRECISION).unwrap().to_user;
+    let redeemed = quote_redeem_asol(&fixture.expected, minted).unwrap().to_user;
+
+    let ixs = [
+        mint_asol_ix(&fixture.addresses, &accounts, SOL_PRECISION, minted),
+        redeem_asol_ix(&fixture.addresses, &accounts, minted, redeemed),
+    ];
+    assert_fails_with(send(&mut svm, "mint_then_redeem", &ixs, &actor.keypair, &[]), "ActionTooSoon");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_succeeds_from_the_next_slot() {
+    let (mut svm, mut fixture) = healthy_with_gap(1);
+    let quote = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let minted_at = last_mint_slot(&svm, &fixture, MINNOW);
+
+    assert_fails_with(fixture.redeem_asol(&mut svm, MINNOW, quote.to_user), "ActionTooSoon");
+    warp_to(&mut svm, &mut fixture, minted_at + 1);
+    fixture.redeem_asol(&mut svm, MINNOW, quote.to_user).unwrap();
+
+    // And the other way round: no mint in the slot of a redeem.
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "ActionTooSoon");
+    warp_to(&mut svm, &mut fixture, minted_at + 2);
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn gap_is_counted_in_slots_per_wallet() {
+    let (mut svm, mut fixture) = healthy_with_gap(10);
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let minted_at = last_mint_slot(&svm, &fixture, MINNOW);
+
+    // Another wallet is not held by the minnow's mint.
+    fixture.redeem_amusd(&mut svm, WHALE, 10 * USD_PRECISION).unwrap();
+
+    warp_to(&mut svm, &mut fixture, minted_at + 9);
+    assert_fails_with(fixture.redeem_amusd(&mut svm, MINNOW, 10 * USD_PRECISION), "ActionTooSoon");
+    warp_to(&mut svm, &mut fixture, minted_at + 10);
+    fixture.redeem_amusd(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn position_is_required_while_a_gap_is_set() {
+    let (mut svm, mut fixture) = healthy_with_gap(1);
+    fixture.addresses.user_positions = false;
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, 10 * USD_PRECISION), "UserPositionRequired");
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, SOL_PRECISION), "UserPositionRequired");
+
+    fixture.set_min_action_gap(&mut svm, 0).unwrap();
+    fixture.addresses.user_positions = false;
+    fixture.redeem_amusd(&mut svm, WHALE, 10 * USD_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,69 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::read_user_position;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+const COOLDOWN_SLOTS: u64 = 25;
+
+fn healthy_with_cooldown() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.set_asol_redeem_cooldown(&mut svm, COOLDOWN_SLOTS).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// Move to `slot` and refresh the LST snapshot there.
+fn warp_to(svm: &mut LiteSVM, fixture: &mut Fixture, slot: u64) {
+    svm.warp_to_slot(slot);
+    fixture.sync_exchange_rate(svm).unwrap();
+}
+
+#[test]
+fn redeem_opens_exactly_when_the_cooldown_ends() {
+    let (mut svm, mut fixture) = healthy_with_cooldown();
+    let quote = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let minted_at = read_user_position(&svm, &fixture.actor(MINNOW).keypair.pubkey()).unwrap().last_asol_mint_slot;
+
+    warp_to(&mut svm, &mut fixture, minted_at + COOLDOWN_SLOTS - 1);
+    assert_fails_with(fixture.redeem_asol(&mut svm, MINNOW, quote.to_user), "AsolRedeemCooldownActive");
+
+    warp_to(&mut svm, &mut fixture, minted_at + COOLDOWN_SLOTS);
+    fixture.redeem_asol(&mut svm, MINNOW, quote.to_user).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn cooldown_binds_only_the_minting_wallet_and_only_asol() {
+    let (mut svm, mut fixture) = healthy_with_cooldown();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // The minnow's amUSD, and the whale's aSOL, redeem as before.
+    fixture.redeem_amusd(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.redeem_asol(&mut svm, MINNOW, SOL_PRECISION / 10), "AsolRedeemCooldownActive");
+
+    // Minting amUSD does not start an aSOL cooldown.
+    fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_cooldown_preserves_current_behavior() {
+    let (mut svm, mut fixture) = healthy_with_cooldown();
+    fixture.set_asol_redeem_cooldown(&mut svm, 0).unwrap();
+    fixture.addresses.user_positions = false;
+
+    let quote = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, MINNOW, quote.to_user).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,79 @@
+use laminar::math::{
+    compute_cr_bps, compute_liability_at_rate_sol, compute_socialized_rate_bps, compute_tvl_sol, BPS_PRECISION,
+    SOL_PRECISION,
+};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn fixture_in(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn cr_bps(state: &QuoteState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_at_rate_sol(state.amusd_supply, state.sol_price_usd, state.redemption_rate_bps).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn socialized_holders_redeem_at_identical_per_amusd_payouts() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::Insolvent);
+    let cr_before = cr_bps(&fixture.expected);
+    assert!(cr_before < BPS_PRECISION);
+
+    let rate = fixture.socialize_bad_debt(&mut svm).unwrap();
+    assert_eq!(rate, compute_socialized_rate_bps(BPS_PRECISION, cr_before).unwrap());
+    let cr_after = cr_bps(&fixture.expected);
+    assert!((BPS_PRECISION..BPS_PRECISION + 5).contains(&cr_after), "CR after write-down: {cr_after}");
+
+    // The first holder out gets no better a rate than the next one.
+    let amount = fixture.balances(&svm, MINNOW).1 / 2;
+    let first = fixture.redeem_amusd(&mut svm, MINNOW, amount).unwrap();
+    let second = fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    assert!(first.to_user > 0);
+    assert_eq!(first.to_user, second.to_user);
+    assert_eq!(first.fee, second.fee);
+
+    // Neither redemption pushed the book back under 100%.
+    assert!(cr_bps(&fixture.expected) >= BPS_PRECISION);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.redemption_rate_bps, rate);
+    assert_eq!(state.episode_haircut_shortfall_lamports, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn written_down_amusd_cannot_be_minted() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::Insolvent);
+    fixture.socialize_bad_debt(&mut svm).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION), "AmusdWrittenDown");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn socialize_bad_debt_requires_cr_below_100_percent() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::NearMinCr);
+    assert_fails_with(fixture.socialize_bad_debt(&mut svm), "NoBadDebt");
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().redemption_rate_bps, BPS_PRECISION);
+}
+
+#[test]
+fn socialize_bad_debt_waits_for_the_insurance_fund() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::Insolvent);
+    fixture.fund_insurance(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert!(cr_bps(&fixture.expected) < BPS_PRECISION);
+    assert_fails_with(fixture.socialize_bad_debt(&mut svm), "InsuranceNotExhausted");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
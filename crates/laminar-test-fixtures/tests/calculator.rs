@@ -0,0 +1,87 @@
+use laminar::math::SOL_PRECISION;
+use laminar_test_fixtures::calculator::{load_mock_calculator, write_lst_state, MOCK_CALCULATOR_ID};
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::scenarios::{INITIAL_LST_TO_SOL_RATE, MINNOW};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+/// Healthy fixture with the mock calculator deployed and an LST state at 1.06
+fn calculator_fixture() -> (LiteSVM, Fixture, Pubkey) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    load_mock_calculator(&mut svm, MOCK_CALCULATOR_ID).unwrap_or_else(|err| panic!("{err}"));
+
+    let lst_state = Pubkey::new_unique();
+    write_lst_state(&mut svm, &lst_state, 1_060 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0).unwrap();
+    (svm, fixture, lst_state)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn calculator_rate_lands_in_global_state() {
+    let (mut svm, mut fixture, lst_state) = calculator_fixture();
+    assert_eq!(fixture.expected.lst_to_sol_rate, INITIAL_LST_TO_SOL_RATE);
+
+    fixture.set_lst_calculator(&mut svm, MOCK_CALCULATOR_ID, lst_state).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.mock_lst_to_sol_rate, 1_060_000_000);
+    assert_eq!(state.last_tvl_update_slot, current_slot(&svm));
+
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // A later sync refreshes both the rate and the freshness slot.
+    svm.warp_to_slot(current_slot(&svm) + 50);
+    write_lst_state(&mut svm, &lst_state, 1_070 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0).unwrap();
+    fixture.sync_exchange_rate(&mut svm).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.mock_lst_to_sol_rate, 1_070_000_000);
+    assert_eq!(state.last_tvl_update_slot, current_slot(&svm));
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn lower_bound_of_the_calculator_range_is_used() {
+    let (mut svm, mut fixture, lst_state) = calculator_fixture();
+    write_lst_state(&mut svm, &lst_state, 1_060 * SOL_PRECISION, 1_000 * SOL_PRECISION, 5_000_000).unwrap();
+    fixture.set_lst_calculator(&mut svm, MOCK_CALCULATOR_ID, lst_state).unwrap();
+    assert_eq!(fixture.expected.lst_to_sol_rate, 1_060_000_000);
+}
+
+#[test]
+fn rate_jump_outside_the_sanity_band_is_rejected() {
+    let (mut svm, mut fixture, lst_state) = calculator_fixture();
+    // 1.05 -> 1.08 is ~2.9%, past the 2% band
+    write_lst_state(&mut svm, &lst_state, 1_080 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0).unwrap();
+    assert_fails_with(fixture.set_lst_calculator(&mut svm, MOCK_CALCULATOR_ID, lst_state), "LstRateOutOfBand");
+
+    // Downward jumps are rejected just the same.
+    write_lst_state(&mut svm, &lst_state, 1_020 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0).unwrap();
+    assert_fails_with(fixture.set_lst_calculator(&mut svm, MOCK_CALCULATOR_ID, lst_state), "LstRateOutOfBand");
+}
+
+#[test]
+fn unknown_calculator_programs_are_rejected() {
+    let (mut svm, mut fixture, lst_state) = calculator_fixture();
+    let impostor = Pubkey::new_unique();
+    load_mock_calculator(&mut svm, impostor).unwrap();
+    assert_fails_with(fixture.set_lst_calculator(&mut svm, impostor, lst_state), "UnknownLstCalculator");
+}
+
+#[test]
+fn calculator_must_be_given_the_configured_lst_state() {
+    let (mut svm, mut fixture, lst_state) = calculator_fixture();
+    fixture.set_lst_calculator(&mut svm, MOCK_CALCULATOR_ID, lst_state).unwrap();
+
+    let other = Pubkey::new_unique();
+    write_lst_state(&mut svm, &other, 1_070 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0).unwrap();
+    fixture.addresses.lst_calculator = Some((MOCK_CALCULATOR_ID, other));
+    assert_fails_with(fixture.sync_exchange_rate(&mut svm), "InvalidLstCalculatorState");
+}
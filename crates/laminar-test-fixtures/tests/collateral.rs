@@ -0,0 +1,278 @@
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use laminar::math::{compute_tvl_sol, compute_weighted_tvl_sol, BPS_PRECISION, SOL_PRECISION};
+use laminar::state::{CollateralStatus, GlobalState};
+use laminar_test_fixtures::harness::{
+    add_collateral_type_ix, collateral_vault_address, freeze_token_account, migrate_collateral_ix, mint_amusd_ix,
+    read_collateral_vault, read_global_state, refresh_tvl_ix, send, thaw_token_account, token_balance, Addresses,
+};
+use laminar_test_fixtures::scenarios::WHALE;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+const SECOND_LST_RATE: u64 = 1_100_000_000;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+/// Two collateral types beside the primary LST, each holding a deposit.
+fn two_collateral_types() -> (LiteSVM, Fixture, Vec<Pubkey>) {
+    let (mut svm, mut fixture) = healthy();
+    for weight_bps in [BPS_PRECISION, 7_500] {
+        let mint = fixture.add_weighted_collateral_type(&mut svm, SECOND_LST_RATE, weight_bps, 100 * SOL_PRECISION).unwrap();
+        fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 30 * SOL_PRECISION).unwrap();
+    }
+    let mut vaults: Vec<Pubkey> =
+        fixture.collaterals.iter().map(|collateral| collateral.addresses.collateral_vault.unwrap()).collect();
+    vaults.sort();
+    (svm, fixture, vaults)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn amusd_mints_and_redeems_against_a_second_lst() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    let primary_vault = token_balance(&svm, &fixture.addresses.vault);
+    let amusd_before = fixture.balances(&svm, WHALE).1;
+    let deposit = 50 * SOL_PRECISION;
+
+    let quote = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, deposit).unwrap();
+    assert_eq!(fixture.balances(&svm, WHALE).1 - amusd_before, quote.to_user);
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), primary_vault);
+
+    let collateral = fixture.collaterals[0];
+    assert_eq!(read_collateral_vault(&svm, &collateral.addresses).unwrap().lst_amount, deposit);
+    assert_eq!(token_balance(&svm, &collateral.addresses.vault), deposit);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.collateral_type_count, 1);
+    assert_eq!(state.collateral_tvl_lamports, compute_tvl_sol(deposit, SECOND_LST_RATE).unwrap());
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    let lst_before = token_balance(&svm, &fixture.collateral_accounts(WHALE, &mint).lst_account);
+    let quote = fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, quote.to_user / 2).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.collateral_accounts(WHALE, &mint).lst_account) - lst_before, quote.to_user);
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), primary_vault);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_mint_already_backing_the_protocol_cannot_be_added() {
+    let (mut svm, mut fixture) = healthy();
+    let primary = fixture.addresses.with_collateral(fixture.addresses.lst_mint);
+    let ix = add_collateral_type_ix(&primary, &fixture.authority.pubkey(), SECOND_LST_RATE, BPS_PRECISION);
+    assert_fails_with(send(&mut svm, "add_collateral_type", &[ix], &fixture.authority, &[]), "CollateralAlreadyRegistered");
+
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 0).unwrap();
+    let ix = add_collateral_type_ix(&fixture.addresses.with_collateral(mint), &fixture.authority.pubkey(), SECOND_LST_RATE, BPS_PRECISION);
+    assert_fails_with(send(&mut svm, "add_collateral_type", &[ix], &fixture.authority, &[]), "already in use");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_collateral_vault_for_another_mint_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    let mismatched = Addresses { collateral_vault: Some(collateral_vault_address(&mint)), ..fixture.addresses };
+    let ix = mint_amusd_ix(&mismatched, &fixture.actor(WHALE).accounts(), SOL_PRECISION, 1);
+    assert_fails_with(send(&mut svm, "mint_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]), "ConstraintSeeds");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn settlement_waits_for_collateral_vaults_to_empty() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 10 * SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.initiate_settlement(&mut svm), "CollateralVaultsNotEmpty");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_disabled_collateral_type_refuses_deposits_but_pays_redemptions() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    let minted = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 20 * SOL_PRECISION).unwrap();
+
+    fixture.set_collateral_status(&mut svm, &mint, CollateralStatus::DepositsDisabled).unwrap();
+    assert_fails_with(
+        fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, SOL_PRECISION),
+        "CollateralDepositsDisabled",
+    );
+    fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, minted.to_user / 2).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_deprecated_collateral_type_still_backs_the_book_until_drained() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    let minted = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 20 * SOL_PRECISION).unwrap();
+
+    fixture.set_collateral_status(&mut svm, &mint, CollateralStatus::Deprecated).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.deprecated_collateral_tvl_lamports, state.collateral_tvl_lamports);
+    assert!(state.collateral_tvl_lamports > 0);
+    assert_fails_with(
+        fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, SOL_PRECISION),
+        "CollateralDepositsDisabled",
+    );
+
+    // Redemptions keep draining it, and both totals follow the vault down.
+    fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, minted.to_user / 2).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.deprecated_collateral_tvl_lamports, state.collateral_tvl_lamports);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    fixture.set_collateral_status(&mut svm, &mint, CollateralStatus::Active).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().deprecated_collateral_tvl_lamports, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_collateral_weight_discounts_cr_but_not_the_balance_sheet() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_weighted_collateral_type(&mut svm, SECOND_LST_RATE, 8_000, 100 * SOL_PRECISION).unwrap();
+    let deposit = 50 * SOL_PRECISION;
+    fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, deposit).unwrap();
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.collateral_tvl_lamports, compute_tvl_sol(deposit, SECOND_LST_RATE).unwrap());
+    assert_eq!(state.weighted_collateral_tvl_lamports, compute_weighted_tvl_sol(deposit, SECOND_LST_RATE, 8_000).unwrap());
+    assert!(state.weighted_collateral_tvl_lamports < state.collateral_tvl_lamports);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Redemptions pay the unweighted rate and keep both totals in step.
+    let amusd = fixture.balances(&svm, WHALE).1 / 4;
+    fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, amusd).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_collateral_weight_must_be_a_share_of_the_vault() {
+    let (mut svm, mut fixture) = healthy();
+    assert_fails_with(fixture.add_weighted_collateral_type(&mut svm, SECOND_LST_RATE, 0, 0), "InvalidParameter");
+    assert_fails_with(
+        fixture.add_weighted_collateral_type(&mut svm, SECOND_LST_RATE, BPS_PRECISION + 1, 0),
+        "InvalidParameter",
+    );
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn refresh_tvl_rebuilds_drifted_collateral_totals() {
+    let (mut svm, mut fixture, _) = two_collateral_types();
+    fixture.refresh_tvl(&mut svm).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    let global_state = fixture.addresses.global_state;
+    let mut account = svm.get_account(&global_state).unwrap();
+    let mut state = GlobalState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    state.collateral_tvl_lamports = 0;
+    state.weighted_collateral_tvl_lamports = 0;
+    let mut data = Vec::with_capacity(account.data.len());
+    state.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    svm.set_account(global_state, account).unwrap();
+    assert!(fixture.assert_matches_chain(&svm).is_err());
+
+    fixture.refresh_tvl(&mut svm).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn refresh_tvl_needs_every_vault_once() {
+    let (mut svm, fixture, vaults) = two_collateral_types();
+    let caller = fixture.authority.pubkey();
+    for passed in [vec![vaults[0]], vec![vaults[0], vaults[0]], vec![vaults[1], vaults[0]]] {
+        let ix = refresh_tvl_ix(&fixture.addresses, &caller, &passed);
+        assert_fails_with(send(&mut svm, "refresh_tvl", &[ix], &fixture.authority, &[]), "CollateralVaultsIncomplete");
+    }
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn migrate_collateral_moves_half_the_vault_without_touching_claims() {
+    let (mut svm, mut fixture) = healthy();
+    let primary = fixture.addresses.lst_mint;
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 0).unwrap();
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+    let tvl_before = compute_tvl_sol(before.total_lst_amount, before.mock_lst_to_sol_rate).unwrap();
+    let half = before.total_lst_amount / 2;
+
+    let deposited = fixture.migrate_collateral(&mut svm, &primary, &mint, half).unwrap();
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), before.total_lst_amount - half);
+    assert_eq!(token_balance(&svm, &fixture.collaterals[0].addresses.vault), deposited);
+    assert_eq!((after.amusd_supply, after.asol_supply), (before.amusd_supply, before.asol_supply));
+    let tvl_after = compute_tvl_sol(after.total_lst_amount, after.mock_lst_to_sol_rate).unwrap() + after.collateral_tvl_lamports;
+    assert!(tvl_after >= tvl_before);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // And back again, out of the collateral vault.
+    fixture.migrate_collateral(&mut svm, &mint, &primary, deposited / 2).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn migrate_collateral_refuses_a_closed_destination_or_a_self_swap() {
+    let (mut svm, mut fixture) = healthy();
+    let primary = fixture.addresses.lst_mint;
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 0).unwrap();
+    fixture.set_collateral_status(&mut svm, &mint, CollateralStatus::DepositsDisabled).unwrap();
+    assert_fails_with(fixture.migrate_collateral(&mut svm, &primary, &mint, SOL_PRECISION), "CollateralDepositsDisabled");
+
+    // Same vault on both sides; refused before any transfer.
+    let ix = migrate_collateral_ix(&fixture.addresses, &fixture.addresses, &fixture.authority.pubkey(), SOL_PRECISION);
+    assert!(send(&mut svm, "migrate_collateral", &[ix], &fixture.authority, &[]).is_err());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn freezable_collateral_is_flagged_at_registration() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 0).unwrap();
+    fixture.add_freezable_collateral_type(&mut svm, SECOND_LST_RATE, 0).unwrap();
+    let flags: Vec<bool> = fixture
+        .collaterals
+        .iter()
+        .map(|collateral| read_collateral_vault(&svm, &collateral.addresses).unwrap().lst_mint_has_freeze_authority)
+        .collect();
+    assert_eq!(flags, [false, true]);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn frozen_collateral_vault_fails_redeems_without_touching_state() {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_freezable_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    let minted = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 30 * SOL_PRECISION).unwrap();
+    let vault = fixture.collaterals[0].addresses.vault;
+    freeze_token_account(&mut svm, &fixture.authority, &vault, &mint).unwrap();
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+
+    assert_fails_with(fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, minted.to_user), "VaultFrozen");
+    assert_fails_with(fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, SOL_PRECISION), "VaultFrozen");
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(after.operation_counter, before.operation_counter);
+    assert_eq!(
+        (after.rounding_reserve_lamports, after.insurance_fund_lamports),
+        (before.rounding_reserve_lamports, before.insurance_fund_lamports)
+    );
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // The primary vault is untouched, and thawing restores the leg.
+    fixture.redeem_amusd(&mut svm, WHALE, minted.to_user / 2).unwrap();
+    thaw_token_account(&mut svm, &fixture.authority, &vault, &mint).unwrap();
+    fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &mint, minted.to_user / 2).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,83 @@
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::{convert_amusd_to_asol_ix, convert_asol_to_amusd_ix, read_global_state, send};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn cr_bps(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    let state = QuoteState::from(&read_global_state(svm, &fixture.addresses).unwrap());
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn convert_amusd_to_asol(svm: &mut LiteSVM, fixture: &Fixture, actor: &str, amusd_amount: u64, min_asol_out: u64) -> Result<(), FixtureError> {
+    let ix = convert_amusd_to_asol_ix(&fixture.addresses, &fixture.actor(actor).accounts(), amusd_amount, min_asol_out);
+    send(svm, "convert_amusd_to_asol", &[ix], &fixture.actor(actor).keypair, &[])
+}
+
+fn convert_asol_to_amusd(svm: &mut LiteSVM, fixture: &Fixture, actor: &str, asol_amount: u64, min_amusd_out: u64) -> Result<(), FixtureError> {
+    let ix = convert_asol_to_amusd_ix(&fixture.addresses, &fixture.actor(actor).accounts(), asol_amount, min_amusd_out);
+    send(svm, "convert_asol_to_amusd", &[ix], &fixture.actor(actor).keypair, &[])
+}
+
+#[test]
+fn amusd_to_asol_raises_cr_without_moving_collateral() {
+    let (mut svm, fixture) = healthy();
+    let lst_before = read_global_state(&svm, &fixture.addresses).unwrap().total_lst_amount;
+    let (_, amusd_before, asol_before) = fixture.balances(&svm, MINNOW);
+    let cr_before = cr_bps(&svm, &fixture);
+
+    convert_amusd_to_asol(&mut svm, &fixture, MINNOW, amusd_before / 2, 1).unwrap();
+
+    assert!(cr_bps(&svm, &fixture) > cr_before);
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().total_lst_amount, lst_before);
+    let (_, amusd_after, asol_after) = fixture.balances(&svm, MINNOW);
+    assert_eq!(amusd_after, amusd_before - amusd_before / 2);
+    assert!(asol_after > asol_before);
+}
+
+#[test]
+fn asol_to_amusd_lowers_cr_without_moving_collateral() {
+    let (mut svm, fixture) = healthy();
+    let lst_before = read_global_state(&svm, &fixture.addresses).unwrap().total_lst_amount;
+    let (_, amusd_before, asol_before) = fixture.balances(&svm, MINNOW);
+    let cr_before = cr_bps(&svm, &fixture);
+
+    convert_asol_to_amusd(&mut svm, &fixture, MINNOW, asol_before / 2, 1).unwrap();
+
+    assert!(cr_bps(&svm, &fixture) < cr_before);
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().total_lst_amount, lst_before);
+    let (_, amusd_after, asol_after) = fixture.balances(&svm, MINNOW);
+    assert_eq!(asol_after, asol_before - asol_before / 2);
+    assert!(amusd_after > amusd_before);
+}
+
+#[test]
+fn asol_to_amusd_cannot_push_cr_below_min() {
+    let (mut svm, fixture) = healthy();
+    let asol = fixture.balances(&svm, WHALE).2;
+    assert_fails_with(convert_asol_to_amusd(&mut svm, &fixture, WHALE, asol, 0), "CollateralRatioTooLow");
+}
+
+#[test]
+fn min_out_bounds_both_directions() {
+    let (mut svm, fixture) = healthy();
+    let (_, amusd, asol) = fixture.balances(&svm, MINNOW);
+    assert_fails_with(convert_amusd_to_asol(&mut svm, &fixture, MINNOW, amusd / 2, u64::MAX), "SlippageExceeded");
+    assert_fails_with(convert_asol_to_amusd(&mut svm, &fixture, MINNOW, asol / 2, u64::MAX), "SlippageExceeded");
+}
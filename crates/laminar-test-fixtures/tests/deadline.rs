@@ -0,0 +1,74 @@
+use laminar::instructions::{MintAmusdArgs, MintAsolArgs, RedeemAmusdArgs, RedeemAsolArgs};
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::{
+    current_slot, mint_amusd_v2_ix, mint_asol_v2_ix, redeem_amusd_v2_ix, redeem_asol_v2_ix, send,
+};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::instruction::Instruction;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// The four user instructions for MINNOW, each carrying `deadline_slot`.
+fn orders(fixture: &Fixture, deadline_slot: u64) -> [(&'static str, Instruction); 4] {
+    let addresses = &fixture.addresses;
+    let accounts = fixture.actor(MINNOW).accounts();
+    [
+        (
+            "mint_amusd_v2",
+            mint_amusd_v2_ix(addresses, &accounts, MintAmusdArgs { deadline_slot, ..MintAmusdArgs::v1(SOL_PRECISION / 10, 1) }),
+        ),
+        (
+            "redeem_amusd_v2",
+            redeem_amusd_v2_ix(addresses, &accounts, RedeemAmusdArgs { deadline_slot, ..RedeemAmusdArgs::v1(USD_PRECISION, 0) }),
+        ),
+        (
+            "mint_asol_v2",
+            mint_asol_v2_ix(addresses, &accounts, MintAsolArgs { deadline_slot, ..MintAsolArgs::v1(SOL_PRECISION / 10, 1) }),
+        ),
+        (
+            "redeem_asol_v2",
+            redeem_asol_v2_ix(addresses, &accounts, RedeemAsolArgs { deadline_slot, ..RedeemAsolArgs::v1(SOL_PRECISION / 100, 0) }),
+        ),
+    ]
+}
+
+#[test]
+fn deadline_in_the_past_fails_every_user_instruction() {
+    let (mut svm, fixture) = healthy();
+    let deadline_slot = current_slot(&svm) - 1;
+    for (label, ix) in orders(&fixture, deadline_slot) {
+        assert_fails_with(send(&mut svm, label, &[ix], &fixture.actor(MINNOW).keypair, &[]), "DeadlineExceeded");
+    }
+}
+
+#[test]
+fn deadline_at_the_current_slot_passes() {
+    let (mut svm, fixture) = healthy();
+    let deadline_slot = current_slot(&svm);
+    for (label, ix) in orders(&fixture, deadline_slot) {
+        send(&mut svm, label, &[ix], &fixture.actor(MINNOW).keypair, &[]).unwrap();
+    }
+}
+
+#[test]
+fn zero_deadline_is_ignored() {
+    let (mut svm, mut fixture) = healthy();
+    svm.warp_to_slot(current_slot(&svm) + 1_000);
+    fixture.sync_exchange_rate(&mut svm).unwrap();
+    for (label, ix) in orders(&fixture, 0) {
+        send(&mut svm, label, &[ix], &fixture.actor(MINNOW).keypair, &[]).unwrap();
+    }
+}
@@ -0,0 +1,101 @@
+use laminar::instructions::RedeemAmusdArgs;
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::quote::quote_redeem_amusd;
+use laminar_test_fixtures::harness::{
+    airdrop, approve_tokens, create_ata, redeem_amusd_ix, redeem_amusd_to_recipient_ix, redeem_asol_ix, revoke_tokens, send,
+    token_balance, UserAccounts,
+};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// A funded keeper wallet with its own LST account, signing against
+/// MINNOW's amUSD and aSOL accounts.
+fn keeper(svm: &mut LiteSVM, fixture: &Fixture) -> (Keypair, UserAccounts) {
+    let keeper = Keypair::new();
+    airdrop(svm, &keeper.pubkey(), SOL_PRECISION).unwrap();
+    let lst_account = create_ata(svm, &keeper, &keeper.pubkey(), &fixture.addresses.lst_mint).unwrap();
+    let minnow = fixture.actor(MINNOW).accounts();
+    let accounts = UserAccounts { user: keeper.pubkey(), lst_account, ..minnow };
+    (keeper, accounts)
+}
+
+#[test]
+fn approved_delegate_redeems_on_behalf_of_the_owner() {
+    let (mut svm, fixture) = healthy();
+    let (keeper, accounts) = keeper(&mut svm, &fixture);
+    let minnow = fixture.actor(MINNOW);
+    let (_, amusd_before, asol_before) = fixture.balances(&svm, MINNOW);
+
+    let amusd_amount = 10 * USD_PRECISION;
+    approve_tokens(&mut svm, &minnow.keypair, &minnow.amusd_account, &keeper.pubkey(), amusd_amount).unwrap();
+    let quote = quote_redeem_amusd(&fixture.expected, amusd_amount).unwrap();
+    let ix = redeem_amusd_ix(&fixture.addresses, &accounts, amusd_amount, 0);
+    send(&mut svm, "redeem_amusd", &[ix], &keeper, &[]).unwrap();
+    assert_eq!(token_balance(&svm, &accounts.lst_account), quote.to_user);
+    assert_eq!(fixture.balances(&svm, MINNOW).1, amusd_before - amusd_amount);
+
+    let asol_amount = SOL_PRECISION / 10;
+    approve_tokens(&mut svm, &minnow.keypair, &minnow.asol_account, &keeper.pubkey(), asol_amount).unwrap();
+    let ix = redeem_asol_ix(&fixture.addresses, &accounts, asol_amount, 0);
+    send(&mut svm, "redeem_asol", &[ix], &keeper, &[]).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).2, asol_before - asol_amount);
+}
+
+#[test]
+fn delegate_can_route_proceeds_back_to_the_owner() {
+    let (mut svm, fixture) = healthy();
+    let (keeper, accounts) = keeper(&mut svm, &fixture);
+    let minnow = fixture.actor(MINNOW);
+    let (lst_before, _, _) = fixture.balances(&svm, MINNOW);
+
+    let amusd_amount = 10 * USD_PRECISION;
+    approve_tokens(&mut svm, &minnow.keypair, &minnow.amusd_account, &keeper.pubkey(), amusd_amount).unwrap();
+    let quote = quote_redeem_amusd(&fixture.expected, amusd_amount).unwrap();
+    let args = RedeemAmusdArgs::v1(amusd_amount, 0);
+    let ix = redeem_amusd_to_recipient_ix(&fixture.addresses, &accounts, args, &minnow.lst_account);
+    send(&mut svm, "redeem_amusd_v2", &[ix], &keeper, &[]).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).0, lst_before + quote.to_user);
+    assert_eq!(token_balance(&svm, &accounts.lst_account), 0);
+}
+
+#[test]
+fn delegate_cannot_redeem_past_its_allowance() {
+    let (mut svm, fixture) = healthy();
+    let (keeper, accounts) = keeper(&mut svm, &fixture);
+    let minnow = fixture.actor(MINNOW);
+
+    let asol_amount = SOL_PRECISION / 10;
+    approve_tokens(&mut svm, &minnow.keypair, &minnow.asol_account, &keeper.pubkey(), asol_amount - 1).unwrap();
+    let ix = redeem_asol_ix(&fixture.addresses, &accounts, asol_amount, 0);
+    assert_fails_with(send(&mut svm, "redeem_asol", &[ix], &keeper, &[]), "InsufficientSupply");
+}
+
+#[test]
+fn revoked_or_missing_delegate_fails() {
+    let (mut svm, fixture) = healthy();
+    let (keeper, accounts) = keeper(&mut svm, &fixture);
+    let minnow = fixture.actor(MINNOW);
+
+    let amusd_amount = 10 * USD_PRECISION;
+    let ix = redeem_amusd_ix(&fixture.addresses, &accounts, amusd_amount, 0);
+    assert_fails_with(send(&mut svm, "redeem_amusd", std::slice::from_ref(&ix), &keeper, &[]), "InvalidSourceAuthority");
+
+    approve_tokens(&mut svm, &minnow.keypair, &minnow.amusd_account, &keeper.pubkey(), amusd_amount).unwrap();
+    revoke_tokens(&mut svm, &minnow.keypair, &minnow.amusd_account).unwrap();
+    assert_fails_with(send(&mut svm, "redeem_amusd", &[ix], &keeper, &[]), "InvalidSourceAuthority");
+}
@@ -0,0 +1,65 @@
+use laminar::constants::REDEEM_ALL;
+use laminar::math::{mul_div_down, MIN_LST_DEPOSIT, SOL_PRECISION};
+use laminar_test_fixtures::harness::{redeem_asol_ix, send};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+/// Lamports of value the dust positions below are worth.
+const DUST_LAMPORTS: u64 = 500;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// MINNOW redeems down to `dust` aSOL, about DUST_LAMPORTS at NAV.
+fn leave_asol_dust(svm: &mut LiteSVM, fixture: &mut Fixture) -> u64 {
+    let dust = DUST_LAMPORTS;
+    let asol = fixture.balances(svm, MINNOW).2;
+    fixture.redeem_asol(svm, MINNOW, asol - dust).unwrap();
+    dust
+}
+
+#[test]
+fn asol_dust_position_redeems_to_zero() {
+    let (mut svm, mut fixture) = healthy();
+    let dust = leave_asol_dust(&mut svm, &mut fixture);
+    assert_fails_with(fixture.redeem_asol(&mut svm, MINNOW, dust - 1), "RedeemOutputTooSmall");
+
+    let quote = fixture.redeem_asol(&mut svm, MINNOW, dust).unwrap();
+    assert!(quote.to_user > 0 && quote.to_user < MIN_LST_DEPOSIT);
+    assert_eq!(fixture.balances(&svm, MINNOW).2, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn amusd_dust_position_redeems_to_zero_through_redeem_all() {
+    let (mut svm, mut fixture) = healthy();
+    let dust = mul_div_down(DUST_LAMPORTS, fixture.expected.sol_price_usd, SOL_PRECISION).unwrap();
+    let amusd = fixture.balances(&svm, MINNOW).1;
+    fixture.redeem_amusd(&mut svm, MINNOW, amusd - dust).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).1, dust);
+
+    let quote = fixture.redeem_all_amusd(&mut svm, MINNOW).unwrap();
+    assert!(quote.to_user > 0 && quote.to_user < MIN_LST_DEPOSIT);
+    assert_eq!(fixture.balances(&svm, MINNOW).1, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn dust_exit_still_honours_min_lst_out() {
+    let (mut svm, mut fixture) = healthy();
+    leave_asol_dust(&mut svm, &mut fixture);
+    let accounts = fixture.actor(MINNOW).accounts();
+    let ix = redeem_asol_ix(&fixture.addresses, &accounts, REDEEM_ALL, MIN_LST_DEPOSIT);
+    assert_fails_with(send(&mut svm, "redeem_asol", &[ix], &fixture.actor(MINNOW).keypair, &[]), "SlippageExceeded");
+}
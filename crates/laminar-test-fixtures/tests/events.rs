@@ -0,0 +1,128 @@
+use laminar::events::{AmUSDMinted, EmergencyPause, ExchangeRateSynced, RoundingFallback};
+use laminar::math::{FeeAction, SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_mint_amusd, quote_redeem_amusd};
+use laminar_test_fixtures::harness::{
+    current_slot, emergency_pause_ix, events_of, mint_amusd_ix, read_global_state, redeem_amusd_ix, send_with_events,
+    sync_exchange_rate_ix,
+};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+#[test]
+fn mint_event_is_read_from_the_event_cpi() {
+    let (mut svm, fixture) = healthy();
+    let minnow = fixture.actor(MINNOW);
+    let (lst_before, amusd_before, _) = fixture.balances(&svm, MINNOW);
+
+    let lst_amount = SOL_PRECISION / 10;
+    let quote = quote_mint_amusd(&fixture.expected, lst_amount).unwrap();
+    let ix = mint_amusd_ix(&fixture.addresses, &minnow.accounts(), lst_amount, 1);
+    let events = send_with_events(&mut svm, "mint_amusd", &[ix], &minnow.keypair, &[]).unwrap();
+
+    let minted = events_of::<AmUSDMinted>(&events);
+    assert_eq!(minted.len(), 1);
+    let (lst_after, amusd_after, _) = fixture.balances(&svm, MINNOW);
+    assert_eq!(minted[0].user, minnow.keypair.pubkey());
+    assert_eq!(minted[0].recipient, minnow.amusd_account);
+    assert_eq!(minted[0].lst_deposited, lst_before - lst_after);
+    assert_eq!(minted[0].amusd_minted, amusd_after - amusd_before);
+    assert_eq!(minted[0].fee_bps, quote.fee_bps);
+    assert_eq!(minted[0].cr_multiplier_bps, quote.cr_multiplier_bps);
+    assert_eq!(minted[0].uncertainty_multiplier_bps, quote.uncertainty_multiplier_bps);
+    assert!(events_of::<EmergencyPause>(&events).is_empty());
+}
+
+#[test]
+fn sequential_mints_emit_consecutive_operation_counters() {
+    let (mut svm, fixture) = healthy();
+    let minnow = fixture.actor(MINNOW);
+    let before = read_global_state(&svm, &fixture.addresses).unwrap().operation_counter;
+
+    // Distinct amounts keep the two transactions from sharing a signature.
+    let mut counters = Vec::new();
+    for lst_amount in [SOL_PRECISION / 10, SOL_PRECISION / 5] {
+        let ix = mint_amusd_ix(&fixture.addresses, &minnow.accounts(), lst_amount, 1);
+        let events = send_with_events(&mut svm, "mint_amusd", &[ix], &minnow.keypair, &[]).unwrap();
+        let minted = events_of::<AmUSDMinted>(&events);
+        assert_eq!(minted.len(), 1);
+        counters.push(minted[0].operation_counter);
+    }
+
+    assert_eq!(counters, vec![before + 1, before + 2]);
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().operation_counter, before + 2);
+}
+
+#[test]
+fn admin_event_is_read_from_the_event_cpi() {
+    let (mut svm, fixture) = healthy();
+
+    let ix = emergency_pause_ix(&fixture.addresses, &fixture.authority.pubkey(), true, false);
+    let events = send_with_events(&mut svm, "emergency_pause", &[ix], &fixture.authority, &[]).unwrap();
+
+    let paused = events_of::<EmergencyPause>(&events);
+    assert_eq!(paused.len(), 1);
+    assert_eq!(paused[0].authority, fixture.authority.pubkey());
+    assert!(paused[0].mint_paused);
+    assert!(!paused[0].redeem_paused);
+    assert_eq!(
+        paused[0].operation_counter,
+        read_global_state(&svm, &fixture.addresses).unwrap().operation_counter
+    );
+}
+
+#[test]
+fn explicit_sync_emits_exchange_rate_synced() {
+    let (mut svm, fixture) = healthy();
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+    svm.warp_to_slot(current_slot(&svm) + 10);
+
+    let ix = sync_exchange_rate_ix(&fixture.addresses);
+    let events = send_with_events(&mut svm, "sync_exchange_rate", &[ix], &fixture.authority, &[]).unwrap();
+
+    let synced = events_of::<ExchangeRateSynced>(&events);
+    assert_eq!(synced.len(), 1);
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(synced[0].slot, current_slot(&svm));
+    assert_eq!(synced[0].previous_update_slot, before.last_tvl_update_slot);
+    assert_eq!(synced[0].lst_to_sol_rate, after.mock_lst_to_sol_rate);
+    assert_eq!(synced[0].operation_counter, before.operation_counter + 1);
+    assert_eq!(synced[0].operation_counter, after.operation_counter);
+}
+
+#[test]
+fn redeem_emits_rounding_fallback_once_the_reserve_is_drained() {
+    let (mut svm, mut fixture) = healthy();
+    // Each 1 amUSD redeem at a 1.05 LST rate rounds up by one LST unit,
+    // paid from the reserve, until the reserve can no longer cover it.
+    let amusd_amount = USD_PRECISION;
+    let mut quote = quote_redeem_amusd(&fixture.expected, amusd_amount).unwrap();
+    for _ in 0..1_000 {
+        if quote.rounding_fallback_debit.is_some() {
+            break;
+        }
+        fixture.redeem_amusd(&mut svm, WHALE, amusd_amount).unwrap();
+        quote = quote_redeem_amusd(&fixture.expected, amusd_amount).unwrap();
+    }
+    let requested_debit = quote.rounding_fallback_debit.expect("reserve drained");
+
+    let whale = fixture.actor(WHALE);
+    let ix = redeem_amusd_ix(&fixture.addresses, &whale.accounts(), amusd_amount, quote.to_user);
+    let events = send_with_events(&mut svm, "redeem_amusd", &[ix], &whale.keypair, &[]).unwrap();
+
+    let fallback = events_of::<RoundingFallback>(&events);
+    assert_eq!(fallback.len(), 1);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(fallback[0].instruction, FeeAction::AmUSDRedeem as u8);
+    assert_eq!(fallback[0].requested_debit, requested_debit);
+    assert_eq!(fallback[0].reserve_available, state.rounding_reserve_lamports);
+    assert!(fallback[0].reserve_available < fallback[0].requested_debit);
+    assert_eq!(fallback[0].operation_counter, state.operation_counter);
+}
@@ -0,0 +1,127 @@
+use laminar::math::{
+    compute_liability_sol, compute_non_claimable_sol, compute_tvl_sol, mul_div_down, nav_asol_with_reserve, BPS_PRECISION,
+    SOL_PRECISION,
+};
+use laminar::quote::{quote_mint_amusd, quote_mint_asol, quote_redeem_amusd, quote_redeem_asol, Quote, QuoteState};
+use laminar_test_fixtures::harness::{read_global_state, token_balance};
+use laminar_test_fixtures::scenarios::WHALE;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+const SPLIT_BPS: u64 = 2_500;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn treasury_balances(svm: &LiteSVM, fixture: &Fixture) -> (u64, u64) {
+    (
+        token_balance(svm, &fixture.addresses.treasury_amusd_account),
+        token_balance(svm, &fixture.addresses.treasury_asol_account),
+    )
+}
+
+fn insurance_fund(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().insurance_fund_lamports
+}
+
+/// The whole fee `quote` would charge with every unit going to the treasury
+fn unsplit(state: &QuoteState, quote: fn(&QuoteState, u64) -> Option<Quote>, amount: u64) -> u64 {
+    quote(&QuoteState { fee_split_insurance_bps: 0, ..*state }, amount).unwrap().fee
+}
+
+fn nav(state: &QuoteState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    let non_claimable = compute_non_claimable_sol(state.rounding_reserve_lamports, state.insurance_fund_lamports).unwrap();
+    nav_asol_with_reserve(tvl, liability, non_claimable, state.asol_supply).unwrap()
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn fee_split_routes_exact_shares_on_every_user_instruction() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_fee_split(&mut svm, SPLIT_BPS).unwrap();
+    let amount = 10 * SOL_PRECISION;
+
+    // mint_amusd: the insurance share is never minted.
+    let fee = unsplit(&fixture.expected, quote_mint_amusd, amount);
+    let (treasury_before, _) = treasury_balances(&svm, &fixture);
+    let fund_before = insurance_fund(&svm, &fixture);
+    let quote = fixture.mint_amusd(&mut svm, WHALE, amount).unwrap();
+    assert!(fee > 0);
+    assert_eq!(quote.insurance_fee, fee * SPLIT_BPS / BPS_PRECISION);
+    assert_eq!(quote.fee, fee - quote.insurance_fee);
+    assert_eq!(treasury_balances(&svm, &fixture).0 - treasury_before, quote.fee);
+    let credit = mul_div_down(quote.insurance_fee, SOL_PRECISION, fixture.expected.sol_price_usd).unwrap();
+    assert_eq!(insurance_fund(&svm, &fixture) - fund_before, credit);
+
+    // redeem_amusd: the insurance share is burned with the net amount.
+    let redeem = fixture.balances(&svm, WHALE).1 / 10;
+    let fee = unsplit(&fixture.expected, quote_redeem_amusd, redeem);
+    let (treasury_before, _) = treasury_balances(&svm, &fixture);
+    let fund_before = insurance_fund(&svm, &fixture);
+    let quote = fixture.redeem_amusd(&mut svm, WHALE, redeem).unwrap();
+    assert!(fee > 0);
+    assert_eq!(quote.insurance_fee, fee * SPLIT_BPS / BPS_PRECISION);
+    assert_eq!(quote.fee, fee - quote.insurance_fee);
+    assert_eq!(treasury_balances(&svm, &fixture).0 - treasury_before, quote.fee);
+    let credit = mul_div_down(quote.insurance_fee, SOL_PRECISION, fixture.expected.sol_price_usd).unwrap();
+    assert_eq!(insurance_fund(&svm, &fixture) - fund_before, credit);
+
+    // mint_asol and redeem_asol: the share is valued at the pre-trade NAV.
+    let fee = unsplit(&fixture.expected, quote_mint_asol, amount);
+    let nav_before = nav(&fixture.expected);
+    let (_, treasury_before) = treasury_balances(&svm, &fixture);
+    let fund_before = insurance_fund(&svm, &fixture);
+    let quote = fixture.mint_asol(&mut svm, WHALE, amount).unwrap();
+    assert!(fee > 0);
+    assert_eq!(quote.insurance_fee, fee * SPLIT_BPS / BPS_PRECISION);
+    assert_eq!(quote.fee, fee - quote.insurance_fee);
+    assert_eq!(treasury_balances(&svm, &fixture).1 - treasury_before, quote.fee);
+    let credit = mul_div_down(quote.insurance_fee, nav_before, SOL_PRECISION).unwrap();
+    assert_eq!(insurance_fund(&svm, &fixture) - fund_before, credit);
+
+    let redeem = fixture.balances(&svm, WHALE).2 / 100;
+    let fee = unsplit(&fixture.expected, quote_redeem_asol, redeem);
+    let nav_before = nav(&fixture.expected);
+    let (_, treasury_before) = treasury_balances(&svm, &fixture);
+    let fund_before = insurance_fund(&svm, &fixture);
+    let quote = fixture.redeem_asol(&mut svm, WHALE, redeem).unwrap();
+    assert!(fee > 0);
+    assert_eq!(quote.insurance_fee, fee * SPLIT_BPS / BPS_PRECISION);
+    assert_eq!(quote.fee, fee - quote.insurance_fee);
+    assert_eq!(treasury_balances(&svm, &fixture).1 - treasury_before, quote.fee);
+    let credit = mul_div_down(quote.insurance_fee, nav_before, SOL_PRECISION).unwrap();
+    assert_eq!(insurance_fund(&svm, &fixture) - fund_before, credit);
+
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_split_keeps_every_fee_with_the_treasury() {
+    let (mut svm, mut fixture) = healthy();
+    let (treasury_before, _) = treasury_balances(&svm, &fixture);
+
+    let quote = fixture.mint_amusd(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    assert_eq!(quote.insurance_fee, 0);
+    assert_eq!(treasury_balances(&svm, &fixture).0 - treasury_before, quote.fee);
+    assert_eq!(insurance_fund(&svm, &fixture), 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn fee_split_above_100_percent_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    assert_fails_with(fixture.set_fee_split(&mut svm, BPS_PRECISION + 1), "InvalidParameter");
+    fixture.set_fee_split(&mut svm, BPS_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,120 @@
+use laminar::constants::{DEFAULT_MAX_FLASH_LOAN_BPS, FLASH_LOAN_FEE_BPS};
+use laminar::math::{combine_uncertainty_sources, mul_div_up, BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::{quote_mint_amusd, QuoteState};
+use laminar_test_fixtures::harness::{
+    current_slot, flash_loan_lst_ix, flash_repay_lst_ix, mint_amusd_ix, read_global_state, redeem_asol_ix, send,
+    token_balance,
+};
+use laminar_test_fixtures::scenarios::WHALE;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn repaid_flash_loan_credits_the_fee_to_the_vault() {
+    let (mut svm, mut fixture) = healthy();
+    let total_before = fixture.expected.total_lst_amount;
+    let loan = total_before / 4;
+
+    let fee = fixture.flash_loan(&mut svm, WHALE, loan, &[]).unwrap();
+    assert_eq!(fee, mul_div_up(loan, FLASH_LOAN_FEE_BPS, BPS_PRECISION).unwrap());
+    assert!(fee > 0);
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.flash_outstanding_lamports, 0);
+    assert_eq!(state.total_lst_amount, total_before + fee);
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), state.total_lst_amount);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn flash_loan_without_a_later_repay_is_rejected() {
+    let (mut svm, fixture) = healthy();
+    let whale = fixture.actor(WHALE);
+    let accounts = whale.accounts();
+    let ix = flash_loan_lst_ix(&fixture.addresses, &accounts.user, &accounts.lst_account, 1_000);
+    assert_fails_with(send(&mut svm, "flash_loan_lst", &[ix], &whale.keypair, &[]), "FlashRepayMissing");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn flash_loan_above_the_cap_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let cap = fixture.expected.total_lst_amount * DEFAULT_MAX_FLASH_LOAN_BPS / BPS_PRECISION;
+    assert_fails_with(fixture.flash_loan(&mut svm, WHALE, cap + 1, &[]), "FlashLoanCapExceeded");
+    fixture.flash_loan(&mut svm, WHALE, cap, &[]).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn vault_instructions_are_blocked_while_a_loan_is_outstanding() {
+    let (mut svm, mut fixture) = healthy();
+    let redeem = fixture.balances(&svm, WHALE).2 / 10;
+    let ix = redeem_asol_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), redeem, 1);
+    let loan = fixture.expected.total_lst_amount / 10;
+    assert_fails_with(fixture.flash_loan(&mut svm, WHALE, loan, &[ix]), "FlashLoanOutstanding");
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().flash_outstanding_lamports, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn flash_utilization_raises_the_amusd_mint_fee_in_the_same_slot() {
+    let (mut svm, fixture) = healthy();
+    let whale = fixture.actor(WHALE);
+    let accounts = whale.accounts();
+    let amount = 10 * SOL_PRECISION;
+    let loan = fixture.expected.total_lst_amount * DEFAULT_MAX_FLASH_LOAN_BPS / BPS_PRECISION;
+    let utilization = mul_div_up(loan, BPS_PRECISION, fixture.expected.total_lst_amount).unwrap();
+
+    // The repay fee is in the vault before the mint is priced.
+    let repaid = QuoteState {
+        total_lst_amount: fixture.expected.total_lst_amount + mul_div_up(loan, FLASH_LOAN_FEE_BPS, BPS_PRECISION).unwrap(),
+        ..fixture.expected
+    };
+    let calm = quote_mint_amusd(&repaid, amount).unwrap();
+    let busy = quote_mint_amusd(
+        &QuoteState {
+            uncertainty_index_bps: combine_uncertainty_sources(repaid.uncertainty_index_bps, utilization).unwrap(),
+            ..repaid
+        },
+        amount,
+    )
+    .unwrap();
+    assert!(busy.fee > calm.fee, "busy fee {} vs calm fee {}", busy.fee, calm.fee);
+
+    let flash_then_mint = |min_amusd_out| {
+        [
+            flash_loan_lst_ix(&fixture.addresses, &accounts.user, &accounts.lst_account, loan),
+            flash_repay_lst_ix(&fixture.addresses, &accounts),
+            mint_amusd_ix(&fixture.addresses, &accounts, amount, min_amusd_out),
+        ]
+    };
+
+    // Priced as if no flash liquidity had moved, the mint misses its floor.
+    assert_fails_with(
+        send(&mut svm, "flash_then_mint", &flash_then_mint(calm.to_user), &whale.keypair, &[]),
+        "SlippageExceeded",
+    );
+
+    let amusd_before = token_balance(&svm, &accounts.amusd_account);
+    send(&mut svm, "flash_then_mint", &flash_then_mint(busy.to_user), &whale.keypair, &[]).unwrap();
+    assert_eq!(token_balance(&svm, &accounts.amusd_account) - amusd_before, busy.to_user);
+
+    // The surcharge belongs to this slot only.
+    let slot = current_slot(&svm);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.flash_loan_utilization_at(slot), utilization);
+    assert_eq!(state.flash_loan_utilization_at(slot + 1), 0);
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+
+use laminar::math::{
+    compute_liability_sol, compute_tvl_sol, compute_uninsured_cr_bps, mul_div_down, BPS_PRECISION, SOL_PRECISION,
+};
+use laminar::quote::QuoteState;
+use laminar_client::haircut_report;
+use laminar_test_fixtures::harness::{read_global_state, redeem_amusd_ix, send_with_events};
+use laminar_test_fixtures::scenarios::{price_for_cr, HEALTHY_CR_BPS, MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+const REDEMPTIONS: usize = 12;
+
+fn insolvent() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::Insolvent).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn cr_bps(state: &QuoteState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_uninsured_cr_bps(tvl, liability, state.insurance_fund_lamports)
+}
+
+/// Par shortfall in SOL lamports of one haircut redemption before any insurance top-up, as the handler computes it
+fn expected_shortfall(state: &QuoteState, amusd_amount: u64) -> u64 {
+    let par = mul_div_down(amusd_amount, SOL_PRECISION, state.sol_price_usd).unwrap();
+    par - mul_div_down(par, cr_bps(state), BPS_PRECISION).unwrap()
+}
+
+/// Small whale redemption, one of many in the episode
+fn small_redeem(fixture: &Fixture, svm: &LiteSVM) -> u64 {
+    fixture.balances(svm, WHALE).1 / (2 * REDEMPTIONS as u64)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn many_small_haircuts_accumulate_the_par_shortfall() {
+    let (mut svm, mut fixture) = insolvent();
+    let amount = small_redeem(&fixture, &svm);
+
+    let mut total = 0;
+    for _ in 0..REDEMPTIONS {
+        assert!(cr_bps(&fixture.expected) < BPS_PRECISION);
+        total += expected_shortfall(&fixture.expected, amount);
+        fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    }
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(total > 0);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, total);
+    assert_eq!(state.episode_haircut_shortfall_lamports, total);
+    assert!(!state.amusd_redeem_paused_by_haircut);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn recovering_above_100_percent_closes_the_episode() {
+    let (mut svm, mut fixture) = insolvent();
+    let amount = small_redeem(&fixture, &svm);
+    for _ in 0..3 {
+        fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    }
+    let lifetime = read_global_state(&svm, &fixture.addresses).unwrap().cumulative_haircut_shortfall_lamports;
+
+    // SOL recovers; the next redemption is at par and ends the episode.
+    let price = price_for_cr(&fixture.expected, HEALTHY_CR_BPS).unwrap();
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.episode_haircut_shortfall_lamports, 0);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, lifetime);
+
+    // A second crash starts a fresh episode on top of the lifetime total.
+    let price = price_for_cr(&fixture.expected, 9_000).unwrap();
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    let shortfall = expected_shortfall(&fixture.expected, amount);
+    fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.episode_haircut_shortfall_lamports, shortfall);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, lifetime + shortfall);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn breaker_pauses_amusd_redemption_until_governance_acts() {
+    let (mut svm, mut fixture) = insolvent();
+    let amount = small_redeem(&fixture, &svm);
+
+    // Trips on the fourth redemption of roughly equal size.
+    let bound = 3 * expected_shortfall(&fixture.expected, amount) + 1;
+    fixture.set_haircut_breaker(&mut svm, bound).unwrap();
+
+    let mut redeemed = 0;
+    while !read_global_state(&svm, &fixture.addresses).unwrap().amusd_redeem_paused_by_haircut {
+        fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+        redeemed += 1;
+        assert!(redeemed <= REDEMPTIONS, "breaker never tripped");
+    }
+    assert!(redeemed >= 3);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(state.episode_haircut_shortfall_lamports > bound);
+
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, amount), "HaircutBreakerTripped");
+
+    // Governance disables the breaker; redemption resumes in the same episode.
+    fixture.set_haircut_breaker(&mut svm, 0).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn insurance_fund_tops_haircut_redemptions_back_to_par() {
+    let (mut svm, mut fixture) = insolvent();
+    let amount = small_redeem(&fixture, &svm);
+    let uninsured_cr = cr_bps(&fixture.expected);
+
+    // Enough to cover one redemption's shortfall in full and part of the next.
+    let first = expected_shortfall(&fixture.expected, amount);
+    let fund = fixture.fund_insurance(&mut svm, WHALE, first * 3 / 2).unwrap();
+    assert!(fund > first);
+    assert_eq!(cr_bps(&fixture.expected), uninsured_cr);
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().insurance_fund_lamports, fund);
+
+    fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.insurance_fund_lamports, fund - first);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, 0);
+
+    let second = expected_shortfall(&fixture.expected, amount);
+    assert!(second > fund - first);
+    fixture.redeem_amusd(&mut svm, WHALE, amount).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.insurance_fund_lamports, 0);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, second - (fund - first));
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn haircut_report_attributes_the_shortfall_to_each_redeemer() {
+    let (mut svm, fixture) = insolvent();
+
+    let mut events = Vec::new();
+    let mut expected = BTreeMap::<_, u64>::new();
+    for actor in [WHALE, MINNOW, WHALE, MINNOW, WHALE] {
+        let before = read_global_state(&svm, &fixture.addresses).unwrap().cumulative_haircut_shortfall_lamports;
+        let accounts = fixture.actor(actor).accounts();
+        let ix = redeem_amusd_ix(&fixture.addresses, &accounts, fixture.balances(&svm, actor).1 / 4, 0);
+        events.extend(send_with_events(&mut svm, "redeem_amusd", &[ix], &fixture.actor(actor).keypair, &[]).unwrap());
+        let after = read_global_state(&svm, &fixture.addresses).unwrap().cumulative_haircut_shortfall_lamports;
+        *expected.entry(accounts.user).or_default() += after - before;
+    }
+
+    let report = haircut_report(events.iter().map(Vec::as_slice));
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(report.redemptions, 5);
+    assert!(report.total_shortfall_lamports > 0);
+    assert_eq!(report.shortfall_by_user, expected);
+    assert_eq!(report.total_shortfall_lamports, state.cumulative_haircut_shortfall_lamports);
+    assert_eq!(report.cumulative_shortfall_lamports, state.cumulative_haircut_shortfall_lamports);
+    assert_eq!(report.insurance_lamports, 0);
+    assert!(!report.breaker_tripped);
+}
@@ -0,0 +1,95 @@
+use laminar::lst_rate::MARINADE_PROGRAM_ID;
+use laminar::math::SOL_PRECISION;
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::marinade::{msol_price_for_rate, write_marinade_state};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::stake_pool::{stake_pool_header, write_spl_stake_pool};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn sync_writes_msol_price_into_global_state() {
+    let (mut svm, mut fixture) = healthy();
+    let state = Pubkey::new_unique();
+    let lst_mint = fixture.addresses.lst_mint;
+    write_marinade_state(&mut svm, &state, &lst_mint, msol_price_for_rate(1_250_000_000), MARINADE_PROGRAM_ID).unwrap();
+
+    fixture.set_marinade_state(&mut svm, state).unwrap();
+    let global_state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(global_state.mock_lst_to_sol_rate, 1_250_000_000);
+    assert_eq!(global_state.last_tvl_update_slot, current_slot(&svm));
+
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Admin cannot override the live rate.
+    let price = fixture.expected.sol_price_usd;
+    assert_fails_with(fixture.set_prices(&mut svm, price, 1_300_000_000), "InvalidParameter");
+}
+
+#[test]
+fn spoofed_state_at_the_wrong_address_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let state = Pubkey::new_unique();
+    let lst_mint = fixture.addresses.lst_mint;
+    write_marinade_state(&mut svm, &state, &lst_mint, msol_price_for_rate(1_250_000_000), MARINADE_PROGRAM_ID).unwrap();
+    fixture.set_marinade_state(&mut svm, state).unwrap();
+
+    // Byte-identical account with an inflated price, at an address the admin never configured
+    let spoof = Pubkey::new_unique();
+    write_marinade_state(&mut svm, &spoof, &lst_mint, msol_price_for_rate(5 * SOL_PRECISION), MARINADE_PROGRAM_ID).unwrap();
+    fixture.addresses.marinade_state = Some(spoof);
+    assert_fails_with(fixture.sync_exchange_rate(&mut svm), "InvalidMarinadeState");
+
+    // Omitting the account is no way around it either.
+    fixture.addresses.marinade_state = None;
+    assert_fails_with(fixture.sync_exchange_rate(&mut svm), "InvalidMarinadeState");
+}
+
+#[test]
+fn configured_state_must_be_marinade_owned_and_for_the_supported_mint() {
+    let (mut svm, mut fixture) = healthy();
+    let state = Pubkey::new_unique();
+    let lst_mint = fixture.addresses.lst_mint;
+    let msol_price = msol_price_for_rate(1_250_000_000);
+
+    write_marinade_state(&mut svm, &state, &lst_mint, msol_price, Pubkey::new_unique()).unwrap();
+    assert_fails_with(fixture.set_marinade_state(&mut svm, state), "InvalidMarinadeState");
+
+    write_marinade_state(&mut svm, &state, &Pubkey::new_unique(), msol_price, MARINADE_PROGRAM_ID).unwrap();
+    assert_fails_with(fixture.set_marinade_state(&mut svm, state), "InvalidMarinadeState");
+}
+
+#[test]
+fn marinade_and_stake_pool_sources_are_exclusive() {
+    let (mut svm, mut fixture) = healthy();
+    let lst_mint = fixture.addresses.lst_mint;
+    let state = Pubkey::new_unique();
+    write_marinade_state(&mut svm, &state, &lst_mint, msol_price_for_rate(1_250_000_000), MARINADE_PROGRAM_ID).unwrap();
+    fixture.set_marinade_state(&mut svm, state).unwrap();
+
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, 0);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, pool), "InvalidParameter");
+    fixture.addresses.stake_pool = None;
+
+    fixture.set_marinade_state(&mut svm, Pubkey::default()).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+    assert_eq!(fixture.expected.lst_to_sol_rate, 1_100_000_000);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,87 @@
+use laminar::instructions::{MintAmusdArgs, RedeemAsolArgs};
+use laminar::math::{mul_div_up, BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::{quote_mint_amusd, quote_redeem_asol, Quote};
+use laminar_test_fixtures::harness::{mint_amusd_v2_ix, read_global_state, redeem_asol_v2_ix, send};
+use laminar_test_fixtures::scenarios::{price_for_cr, MINNOW};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// Fee bps a quote charged on `gross` fee-bearing tokens.
+fn quoted_fee_bps(quote: &Quote, gross: u64) -> u64 {
+    mul_div_up(quote.fee + quote.insurance_fee, BPS_PRECISION, gross).unwrap()
+}
+
+/// Drop the SOL price until CR sits between min and target, where
+/// risk-increasing fees scale up.
+fn drop_cr_below_target(svm: &mut LiteSVM, fixture: &mut Fixture) {
+    let state = read_global_state(svm, &fixture.addresses).unwrap();
+    let price = price_for_cr(&fixture.expected, (state.min_cr_bps + state.target_cr_bps) / 2).unwrap();
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(svm, price, rate).unwrap();
+}
+
+fn mint_amusd(svm: &mut LiteSVM, fixture: &Fixture, lst_amount: u64, max_fee_bps: u64) -> Result<(), FixtureError> {
+    let args = MintAmusdArgs { max_fee_bps, ..MintAmusdArgs::v1(lst_amount, 1) };
+    let ix = mint_amusd_v2_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args);
+    send(svm, "mint_amusd_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+fn redeem_asol(svm: &mut LiteSVM, fixture: &Fixture, asol_amount: u64, max_fee_bps: u64) -> Result<(), FixtureError> {
+    let args = RedeemAsolArgs { max_fee_bps, ..RedeemAsolArgs::v1(asol_amount, 0) };
+    let ix = redeem_asol_v2_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args);
+    send(svm, "redeem_asol_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+#[test]
+fn mint_amusd_at_the_quoted_fee_passes() {
+    let (mut svm, fixture) = healthy();
+    let lst_amount = SOL_PRECISION / 10;
+    let quote = quote_mint_amusd(&fixture.expected, lst_amount).unwrap();
+    let fee_bps = quoted_fee_bps(&quote, quote.to_user + quote.fee + quote.insurance_fee);
+    assert!(fee_bps > 0);
+
+    assert_fails_with(mint_amusd(&mut svm, &fixture, lst_amount, fee_bps - 1), "FeeExceedsMax");
+    mint_amusd(&mut svm, &fixture, lst_amount, fee_bps).unwrap();
+}
+
+#[test]
+fn price_drop_after_quote_trips_mint_amusd_max_fee() {
+    let (mut svm, mut fixture) = healthy();
+    let lst_amount = SOL_PRECISION / 10;
+    let quote = quote_mint_amusd(&fixture.expected, lst_amount).unwrap();
+    let fee_bps = quoted_fee_bps(&quote, quote.to_user + quote.fee + quote.insurance_fee);
+
+    drop_cr_below_target(&mut svm, &mut fixture);
+    assert_fails_with(mint_amusd(&mut svm, &fixture, lst_amount, fee_bps), "FeeExceedsMax");
+
+    let requote = quote_mint_amusd(&fixture.expected, lst_amount).unwrap();
+    let new_fee_bps = quoted_fee_bps(&requote, requote.to_user + requote.fee + requote.insurance_fee);
+    assert!(new_fee_bps > fee_bps);
+    mint_amusd(&mut svm, &fixture, lst_amount, new_fee_bps).unwrap();
+}
+
+#[test]
+fn price_drop_after_quote_trips_redeem_asol_max_fee() {
+    let (mut svm, mut fixture) = healthy();
+    let asol_amount = SOL_PRECISION / 10;
+    let quote = quote_redeem_asol(&fixture.expected, asol_amount).unwrap();
+    let fee_bps = quoted_fee_bps(&quote, asol_amount);
+    assert!(fee_bps > 0);
+
+    drop_cr_below_target(&mut svm, &mut fixture);
+    assert_fails_with(redeem_asol(&mut svm, &fixture, asol_amount, fee_bps), "FeeExceedsMax");
+    redeem_asol(&mut svm, &fixture, asol_amount, 0).unwrap();
+}
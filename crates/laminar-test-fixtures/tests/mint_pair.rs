@@ -0,0 +1,80 @@
+use laminar::constants::MAX_PAIR_CR_DRIFT_BPS;
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol, SOL_PRECISION};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::{mint_asol_ix, mint_pair_ix, read_global_state, send};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn cr_bps(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    let state = QuoteState::from(&read_global_state(svm, &fixture.addresses).unwrap());
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn mint_pair(
+    svm: &mut LiteSVM,
+    fixture: &Fixture,
+    actor: &str,
+    lst_amount: u64,
+    min_amusd_out: u64,
+    min_asol_out: u64,
+) -> Result<(), FixtureError> {
+    let ix = mint_pair_ix(&fixture.addresses, &fixture.actor(actor).accounts(), lst_amount, min_amusd_out, min_asol_out);
+    send(svm, "mint_pair", &[ix], &fixture.actor(actor).keypair, &[])
+}
+
+#[test]
+fn pair_mint_leaves_cr_unchanged() {
+    let (mut svm, fixture) = healthy();
+    let (lst_before, amusd_before, asol_before) = fixture.balances(&svm, MINNOW);
+    let cr_before = cr_bps(&svm, &fixture);
+
+    let lst_amount = SOL_PRECISION / 10;
+    mint_pair(&mut svm, &fixture, MINNOW, lst_amount, 1, 1).unwrap();
+
+    let cr_after = cr_bps(&svm, &fixture);
+    assert!(cr_before.abs_diff(cr_after) <= MAX_PAIR_CR_DRIFT_BPS, "CR {cr_before} -> {cr_after}");
+    let (lst_after, amusd_after, asol_after) = fixture.balances(&svm, MINNOW);
+    assert_eq!(lst_after, lst_before - lst_amount);
+    assert!(amusd_after > amusd_before);
+    assert!(asol_after > asol_before);
+}
+
+#[test]
+fn pair_mint_the_size_of_the_book_holds_cr_where_a_lone_mint_does_not() {
+    let lst_amount = 500 * SOL_PRECISION;
+
+    let (mut svm, fixture) = healthy();
+    let cr_before = cr_bps(&svm, &fixture);
+    mint_pair(&mut svm, &fixture, WHALE, lst_amount, 1, 1).unwrap();
+    let cr_after = cr_bps(&svm, &fixture);
+    assert!(cr_before.abs_diff(cr_after) <= MAX_PAIR_CR_DRIFT_BPS, "CR {cr_before} -> {cr_after}");
+
+    let (mut svm, fixture) = healthy();
+    let ix = mint_asol_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), lst_amount, 1);
+    send(&mut svm, "mint_asol", &[ix], &fixture.actor(WHALE).keypair, &[]).unwrap();
+    assert!(cr_bps(&svm, &fixture) > cr_before + MAX_PAIR_CR_DRIFT_BPS);
+}
+
+#[test]
+fn each_leg_honors_its_minimum() {
+    let (mut svm, fixture) = healthy();
+    let lst_amount = SOL_PRECISION / 10;
+    assert_fails_with(mint_pair(&mut svm, &fixture, MINNOW, lst_amount, u64::MAX, 1), "SlippageExceeded");
+    assert_fails_with(mint_pair(&mut svm, &fixture, MINNOW, lst_amount, 1, u64::MAX), "SlippageExceeded");
+}
@@ -0,0 +1,75 @@
+use laminar::instructions::{MintAmusdArgs, MintAsolArgs};
+use laminar::math::SOL_PRECISION;
+use laminar::quote::{quote_mint_amusd, quote_mint_asol};
+use laminar_test_fixtures::harness::{create_ata, mint_amusd_to_recipient_ix, mint_asol_to_recipient_ix, send, token_balance};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn mint_amusd_to(svm: &mut LiteSVM, fixture: &Fixture, lst_amount: u64, recipient: &Pubkey) -> Result<(), FixtureError> {
+    let args = MintAmusdArgs::v1(lst_amount, 1);
+    let ix = mint_amusd_to_recipient_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args, recipient);
+    send(svm, "mint_amusd_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+fn mint_asol_to(svm: &mut LiteSVM, fixture: &Fixture, lst_amount: u64, recipient: &Pubkey) -> Result<(), FixtureError> {
+    let args = MintAsolArgs::v1(lst_amount, 1);
+    let ix = mint_asol_to_recipient_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args, recipient);
+    send(svm, "mint_asol_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+#[test]
+fn amusd_mint_lands_in_another_wallets_ata() {
+    let (mut svm, fixture) = healthy();
+    let recipient = fixture.actor(WHALE).amusd_account;
+    let (minnow_lst, minnow_amusd, _) = fixture.balances(&svm, MINNOW);
+    let whale_amusd = token_balance(&svm, &recipient);
+
+    let lst_amount = SOL_PRECISION / 10;
+    let quote = quote_mint_amusd(&fixture.expected, lst_amount).unwrap();
+    mint_amusd_to(&mut svm, &fixture, lst_amount, &recipient).unwrap();
+
+    assert_eq!(token_balance(&svm, &recipient), whale_amusd + quote.to_user);
+    let (lst_after, amusd_after, _) = fixture.balances(&svm, MINNOW);
+    assert_eq!(lst_after, minnow_lst - lst_amount);
+    assert_eq!(amusd_after, minnow_amusd);
+}
+
+#[test]
+fn asol_mint_lands_in_a_fresh_wallets_ata() {
+    let (mut svm, fixture) = healthy();
+    let wallet = Pubkey::new_unique();
+    let recipient = create_ata(&mut svm, &fixture.actor(MINNOW).keypair, &wallet, &fixture.addresses.asol_mint).unwrap();
+    let (_, _, minnow_asol) = fixture.balances(&svm, MINNOW);
+
+    let lst_amount = SOL_PRECISION / 10;
+    let quote = quote_mint_asol(&fixture.expected, lst_amount).unwrap();
+    mint_asol_to(&mut svm, &fixture, lst_amount, &recipient).unwrap();
+
+    assert_eq!(token_balance(&svm, &recipient), quote.to_user);
+    assert_eq!(fixture.balances(&svm, MINNOW).2, minnow_asol);
+}
+
+#[test]
+fn recipient_must_hold_the_minted_token() {
+    let (mut svm, fixture) = healthy();
+    let lst_amount = SOL_PRECISION / 10;
+    let asol_account = fixture.actor(WHALE).asol_account;
+    let amusd_account = fixture.actor(WHALE).amusd_account;
+    assert_fails_with(mint_amusd_to(&mut svm, &fixture, lst_amount, &asol_account), "ConstraintTokenMint");
+    assert_fails_with(mint_asol_to(&mut svm, &fixture, lst_amount, &amusd_account), "ConstraintTokenMint");
+}
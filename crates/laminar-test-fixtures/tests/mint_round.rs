@@ -0,0 +1,94 @@
+use laminar::math::SOL_PRECISION;
+use laminar::quote::{quote_mint_asol, Quote};
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+const ROUND_SLOTS: u64 = 50;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// aSOL a healthy-protocol mint adds before fees are taken out
+fn gross(quote: &Quote) -> u64 {
+    quote.to_user + quote.fee + quote.insurance_fee
+}
+
+/// Move to `slot` and refresh the LST snapshot there.
+fn warp_to(svm: &mut LiteSVM, fixture: &mut Fixture, slot: u64) {
+    svm.warp_to_slot(slot);
+    fixture.sync_exchange_rate(svm).unwrap();
+}
+
+#[test]
+fn round_cap_resets_when_the_round_elapses() {
+    let (mut svm, mut fixture) = healthy();
+    let cap = gross(&quote_mint_asol(&fixture.expected, 10 * SOL_PRECISION).unwrap());
+    fixture.set_mint_round(&mut svm, cap, ROUND_SLOTS).unwrap();
+
+    // Leave the round the scenario's own mints were counted in.
+    let slot = current_slot(&svm) + ROUND_SLOTS;
+    warp_to(&mut svm, &mut fixture, slot);
+
+    // A mint filling the round exactly is allowed.
+    let quote = fixture.mint_asol(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.asol_minted_this_round, gross(&quote));
+    assert_eq!(state.asol_minted_this_round, cap);
+    let round_start = state.current_round_start_slot;
+
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "MintRoundCapExceeded");
+    warp_to(&mut svm, &mut fixture, round_start + ROUND_SLOTS - 1);
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "MintRoundCapExceeded");
+
+    // First slot of the next round: capacity is back.
+    warp_to(&mut svm, &mut fixture, round_start + ROUND_SLOTS);
+    let quote = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.current_round_start_slot, round_start + ROUND_SLOTS);
+    assert_eq!(state.asol_minted_this_round, gross(&quote));
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn round_total_counts_every_wallet() {
+    let (mut svm, mut fixture) = healthy();
+    let per_mint = gross(&quote_mint_asol(&fixture.expected, SOL_PRECISION).unwrap());
+    fixture.set_mint_round(&mut svm, 3 * per_mint, ROUND_SLOTS).unwrap();
+    let slot = current_slot(&svm) + ROUND_SLOTS;
+    warp_to(&mut svm, &mut fixture, slot);
+
+    fixture.mint_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, 2 * SOL_PRECISION), "MintRoundCapExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_cap_is_unlimited_and_zero_length_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    assert_fails_with(fixture.set_mint_round(&mut svm, 0, 0), "InvalidParameter");
+
+    let per_mint = gross(&quote_mint_asol(&fixture.expected, SOL_PRECISION).unwrap());
+    fixture.set_mint_round(&mut svm, per_mint, ROUND_SLOTS).unwrap();
+    let slot = current_slot(&svm) + ROUND_SLOTS;
+    warp_to(&mut svm, &mut fixture, slot);
+    fixture.mint_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, SOL_PRECISION), "MintRoundCapExceeded");
+
+    // Lifting the cap admits the same mint within the same round.
+    fixture.set_mint_round(&mut svm, 0, ROUND_SLOTS).unwrap();
+    fixture.mint_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,67 @@
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use laminar::constants::OPERATION_COUNTER_CEILING;
+use laminar::math::SOL_PRECISION;
+use laminar::state::GlobalState;
+use laminar_test_fixtures::harness::{migrate_global_state_ix, read_global_state, send};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+/// Simulate a migration that wrote a bad counter.
+fn corrupt_operation_counter(svm: &mut LiteSVM, global_state: &Pubkey, value: u64) {
+    let mut account = svm.get_account(global_state).unwrap();
+    let mut state = GlobalState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    state.operation_counter = value;
+    let mut data = Vec::with_capacity(account.data.len());
+    state.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    svm.set_account(*global_state, account).unwrap();
+}
+
+#[test]
+fn every_scenario_history_is_strictly_monotonic() {
+    for scenario in Scenario::ALL {
+        let mut svm = new_svm().unwrap();
+        let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{scenario:?}: {err}"));
+        // initialize plus at least one scripted action per scenario
+        assert!(fixture.operation_counters.len() > 1, "{scenario:?}");
+        fixture.assert_matches_chain(&svm).unwrap();
+    }
+}
+
+#[test]
+fn corrupt_counter_is_caught_in_preflight_and_reset_by_migration() {
+    let mut svm = new_svm().unwrap();
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap();
+    let global_state = fixture.addresses.global_state;
+
+    corrupt_operation_counter(&mut svm, &global_state, OPERATION_COUNTER_CEILING);
+    let err = fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap_err();
+    assert!(err.to_string().contains("OperationCounterCorrupt"), "{err}");
+
+    let ix = migrate_global_state_ix(&fixture.addresses, &fixture.authority.pubkey());
+    send(&mut svm, "migrate_global_state", &[ix], &fixture.authority, &[]).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().operation_counter, 0);
+
+    // The reset is an explicit discontinuity; monotonicity restarts from it.
+    fixture.operation_counters = vec![0];
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn saturated_counter_fails_instead_of_freezing() {
+    let mut svm = new_svm().unwrap();
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap();
+    let global_state = fixture.addresses.global_state;
+
+    // Past the preflight ceiling the user path already refuses; admin paths
+    // that only increment must fail rather than saturate.
+    corrupt_operation_counter(&mut svm, &global_state, u64::MAX);
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    let err = fixture.set_prices(&mut svm, price, rate).unwrap_err();
+    assert!(err.to_string().contains("CounterSaturated"), "{err}");
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().operation_counter, u64::MAX);
+}
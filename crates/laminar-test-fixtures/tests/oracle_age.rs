@@ -0,0 +1,57 @@
+use laminar::constants::DEFAULT_MAX_ORACLE_AGE_SECONDS;
+use laminar::math::SOL_PRECISION;
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::sysvar::clock::Clock;
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// Move the wall clock without producing slots, as when the cluster stalls.
+fn advance_unix_timestamp(svm: &mut LiteSVM, seconds: i64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += seconds;
+    svm.set_sysvar(&clock);
+}
+
+#[test]
+fn ancient_timestamp_in_a_fresh_slot_is_stale() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    // Exactly at the bound is still fresh.
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    advance_unix_timestamp(&mut svm, DEFAULT_MAX_ORACLE_AGE_SECONDS as i64);
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    advance_unix_timestamp(&mut svm, 1);
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OraclePriceStale");
+
+    // Refreshing the snapshot restarts both clocks.
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn max_oracle_age_is_governance_tunable() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    advance_unix_timestamp(&mut svm, 120);
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OraclePriceStale");
+
+    fixture.set_max_oracle_age(&mut svm, 120).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.set_max_oracle_age(&mut svm, 0), "InvalidParameter");
+}
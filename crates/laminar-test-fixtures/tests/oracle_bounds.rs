@@ -0,0 +1,55 @@
+use laminar::constants::{DEFAULT_MAX_LST_RATE, DEFAULT_MAX_SOL_PRICE_USD, DEFAULT_MIN_LST_RATE, DEFAULT_MIN_SOL_PRICE_USD};
+use laminar::math::SOL_PRECISION;
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn mock_updates_outside_the_default_bounds_are_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    assert_fails_with(fixture.set_prices(&mut svm, 1, rate), "OraclePriceOutOfBounds");
+    assert_fails_with(fixture.set_prices(&mut svm, DEFAULT_MAX_SOL_PRICE_USD + 1, rate), "OraclePriceOutOfBounds");
+    assert_fails_with(fixture.set_prices(&mut svm, price, 100 * SOL_PRECISION), "OraclePriceOutOfBounds");
+    assert_fails_with(fixture.set_prices(&mut svm, price, DEFAULT_MIN_LST_RATE - 1), "OraclePriceOutOfBounds");
+
+    fixture.set_prices(&mut svm, DEFAULT_MIN_SOL_PRICE_USD, DEFAULT_MAX_LST_RATE).unwrap();
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn tightened_bounds_stop_pricing_actions() {
+    let (mut svm, mut fixture) = healthy();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    // The cached price now sits just above the allowed range.
+    fixture.set_oracle_bounds(&mut svm, DEFAULT_MIN_SOL_PRICE_USD, price - 1, DEFAULT_MIN_LST_RATE, DEFAULT_MAX_LST_RATE).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OraclePriceOutOfBounds");
+
+    // Same for the cached LST rate.
+    fixture.set_oracle_bounds(&mut svm, DEFAULT_MIN_SOL_PRICE_USD, price, rate + 1, DEFAULT_MAX_LST_RATE).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OraclePriceOutOfBounds");
+
+    fixture.set_oracle_bounds(&mut svm, DEFAULT_MIN_SOL_PRICE_USD, price, rate, rate).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    assert_fails_with(fixture.set_oracle_bounds(&mut svm, price + 1, price, rate, rate), "InvalidParameter");
+}
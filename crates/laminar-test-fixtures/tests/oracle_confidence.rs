@@ -0,0 +1,33 @@
+use laminar::math::SOL_PRECISION;
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, FixtureError, Scenario};
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn wide_confidence_blocks_mints_until_tightened() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    let price = state.mock_sol_price_usd;
+    let max_conf_usd = price * state.max_conf_bps / 10_000;
+
+    // One micro-USD past max_conf_bps of price.
+    fixture.set_oracle_confidence(&mut svm, max_conf_usd + 1).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.mock_oracle_confidence_usd, max_conf_usd + 1);
+    assert_eq!(state.last_oracle_update_slot, current_slot(&svm));
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OracleConfidenceTooHigh");
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "OracleConfidenceTooHigh");
+
+    fixture.set_oracle_confidence(&mut svm, max_conf_usd).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
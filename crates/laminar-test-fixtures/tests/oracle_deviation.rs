@@ -0,0 +1,127 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::oracle::PriceSource;
+use laminar_test_fixtures::harness::current_slot;
+use laminar_test_fixtures::pyth::{write_price_update, PythPrice};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::switchboard::{write_pull_feed, SwitchboardPrice};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+/// Healthy fixture priced from Pyth and cross-checked against Switchboard
+/// within 1%. Both feeds start at the mock price.
+fn cross_checked_fixture() -> (LiteSVM, Fixture, u64) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let mock_price = fixture.expected.sol_price_usd;
+    let slot = current_slot(&svm);
+
+    let pyth = Pubkey::new_unique();
+    write_price_update(&mut svm, &pyth, &PythPrice::from_micro_usd(mock_price, 0, slot)).unwrap();
+    let switchboard = Pubkey::new_unique();
+    write_pull_feed(&mut svm, &switchboard, &SwitchboardPrice::from_micro_usd(mock_price, 0, slot)).unwrap();
+
+    fixture.set_pyth_price_feed(&mut svm, pyth).unwrap();
+    fixture.set_switchboard_feed(&mut svm, switchboard).unwrap();
+    fixture.set_secondary_price_source(&mut svm, PriceSource::Switchboard, 100).unwrap();
+    (svm, fixture, mock_price)
+}
+
+fn set_feeds(svm: &mut LiteSVM, fixture: &Fixture, pyth_price: u64, switchboard_price: u64) {
+    let slot = current_slot(svm);
+    let pyth = fixture.addresses.price_update.unwrap();
+    write_price_update(svm, &pyth, &PythPrice::from_micro_usd(pyth_price, 0, slot)).unwrap();
+    let switchboard = fixture.addresses.switchboard_feed.unwrap();
+    write_pull_feed(svm, &switchboard, &SwitchboardPrice::from_micro_usd(switchboard_price, 0, slot)).unwrap();
+}
+
+fn set_feeds_pyth_only(svm: &mut LiteSVM, fixture: &Fixture, pyth_price: u64) {
+    let pyth = fixture.addresses.price_update.unwrap();
+    write_price_update(svm, &pyth, &PythPrice::from_micro_usd(pyth_price, 0, current_slot(svm))).unwrap();
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn agreeing_feeds_price_each_action_conservatively() {
+    let (mut svm, mut fixture, mock_price) = cross_checked_fixture();
+    let low = mock_price;
+    let high = mock_price * 1_005 / 1_000;
+    set_feeds(&mut svm, &fixture, low, high);
+
+    // min_out is pinned to the quote, so each action proves which price it used.
+    fixture.expected.sol_price_usd = low;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+
+    fixture.expected.sol_price_usd = high;
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // Swapping which feed is high does not change the choice.
+    set_feeds(&mut svm, &fixture, high, low);
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.expected.sol_price_usd = low;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn diverging_feeds_halt_pricing_until_they_agree() {
+    let (mut svm, mut fixture, mock_price) = cross_checked_fixture();
+    set_feeds(&mut svm, &fixture, mock_price, mock_price * 102 / 100);
+
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OracleDeviation");
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION), "OracleDeviation");
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "OracleDeviation");
+    assert_fails_with(fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION), "OracleDeviation");
+
+    // Widening the bound admits the same pair.
+    fixture.set_secondary_price_source(&mut svm, PriceSource::Switchboard, 200).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn one_feed_missing_falls_back_to_the_primary() {
+    let (mut svm, mut fixture, mock_price) = cross_checked_fixture();
+
+    // A configured secondary must be supplied; omitting it is not a bypass.
+    let switchboard = fixture.addresses.switchboard_feed.take();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+    fixture.addresses.switchboard_feed = switchboard;
+
+    // Its feed cannot be cleared while it is cross-checked...
+    assert_fails_with(fixture.set_switchboard_feed(&mut svm, Pubkey::default()), "InvalidParameter");
+    // ...nor selected as the primary.
+    assert_fails_with(fixture.set_price_source(&mut svm, PriceSource::Switchboard), "InvalidParameter");
+
+    // With the cross-check off and the feed gone, Pyth prices alone.
+    fixture.set_secondary_price_source(&mut svm, PriceSource::Mock, 100).unwrap();
+    fixture.set_switchboard_feed(&mut svm, Pubkey::default()).unwrap();
+    set_feeds_pyth_only(&mut svm, &fixture, mock_price * 11 / 10);
+    fixture.expected.sol_price_usd = mock_price * 11 / 10;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    // The chain still holds the mock price the model compares against.
+    fixture.expected.sol_price_usd = mock_price;
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn secondary_must_be_another_configured_feed() {
+    let (mut svm, mut fixture, _) = cross_checked_fixture();
+    fixture.set_secondary_price_source(&mut svm, PriceSource::Mock, 100).unwrap();
+
+    assert_fails_with(fixture.set_secondary_price_source(&mut svm, PriceSource::Pyth, 100), "InvalidParameter");
+    assert_fails_with(fixture.set_secondary_price_source(&mut svm, PriceSource::StakePool, 100), "InvalidParameter");
+    assert_fails_with(fixture.set_secondary_price_source(&mut svm, PriceSource::Switchboard, 0), "InvalidParameter");
+
+    fixture.set_switchboard_feed(&mut svm, Pubkey::default()).unwrap();
+    assert_fails_with(fixture.set_secondary_price_source(&mut svm, PriceSource::Switchboard, 100), "InvalidPriceFeed");
+}
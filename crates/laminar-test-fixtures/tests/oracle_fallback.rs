@@ -0,0 +1,79 @@
+use laminar::constants::DEFAULT_MAX_ORACLE_STALENESS_SLOTS;
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::oracle::PriceSource;
+use laminar::quote::quote_redeem_asol;
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::pyth::{write_price_update, PythPrice};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+const SURCHARGE_BPS: u64 = 500;
+
+/// Healthy fixture priced from Pyth whose update has gone stale while the
+/// mock snapshot and LST rate are fresh.
+fn stale_pyth_fixture() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let feed = Pubkey::new_unique();
+    let price = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_price_update(&mut svm, &feed, &price).unwrap();
+    fixture.set_pyth_price_feed(&mut svm, feed).unwrap();
+
+    svm.warp_to_slot(current_slot(&svm) + DEFAULT_MAX_ORACLE_STALENESS_SLOTS + 1);
+    fixture.set_prices(&mut svm, fixture.expected.sol_price_usd, fixture.expected.lst_to_sol_rate).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn stale_primary_without_fallback_blocks_everything() {
+    let (mut svm, mut fixture) = stale_pyth_fixture();
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION), "PythPriceStale");
+    assert_fails_with(fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION), "PythPriceStale");
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "PythPriceStale");
+}
+
+#[test]
+fn stale_primary_redeems_through_the_fallback_with_a_surcharge() {
+    let (mut svm, mut fixture) = stale_pyth_fixture();
+    fixture.set_fallback_price_source(&mut svm, Some(PriceSource::Mock), SURCHARGE_BPS).unwrap();
+
+    // Mints still refuse stale data.
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "PythPriceStale");
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "PythPriceStale");
+
+    // Redemptions price from the mock, with the surcharge in the uncertainty
+    // index. min_out is pinned to the surcharged quote.
+    let calm_fee = quote_redeem_asol(&fixture.expected, SOL_PRECISION).unwrap().fee;
+    fixture.expected.uncertainty_index_bps += SURCHARGE_BPS;
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    let surcharged = fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.expected.uncertainty_index_bps -= SURCHARGE_BPS;
+    assert!(surcharged.fee > calm_fee, "fee {} should exceed {}", surcharged.fee, calm_fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn fallback_configuration_is_validated() {
+    let (mut svm, mut fixture) = stale_pyth_fixture();
+    // The primary cannot be its own fallback.
+    assert_fails_with(fixture.set_fallback_price_source(&mut svm, Some(PriceSource::Pyth), 0), "InvalidParameter");
+    // An unconfigured feed cannot be the fallback.
+    assert_fails_with(
+        fixture.set_fallback_price_source(&mut svm, Some(PriceSource::Switchboard), 0),
+        "InvalidPriceFeed",
+    );
+    assert_fails_with(fixture.set_fallback_price_source(&mut svm, Some(PriceSource::Mock), 10_001), "InvalidParameter");
+
+    fixture.set_fallback_price_source(&mut svm, Some(PriceSource::Mock), 0).unwrap();
+    fixture.set_fallback_price_source(&mut svm, None, 0).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().fallback_source(), None);
+}
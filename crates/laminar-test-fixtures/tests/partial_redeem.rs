@@ -0,0 +1,103 @@
+use laminar::constants::MIN_PROTOCOL_TVL;
+use laminar::instructions::RedeemAmusdArgs;
+use laminar::math::{MIN_LST_DEPOSIT, SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_redeem_amusd, quote_redeem_asol, Quote, QuoteState};
+use laminar_test_fixtures::harness::{redeem_amusd_ix, redeem_amusd_v2_ix, send};
+use laminar_test_fixtures::scenarios::WHALE;
+use laminar_test_fixtures::{build, new_svm, partial_fill, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+const SECOND_LST_RATE: u64 = 1_100_000_000;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+/// A second collateral type whose vault holds only a small deposit.
+fn shallow_collateral() -> (LiteSVM, Fixture, Pubkey) {
+    let (mut svm, mut fixture) = healthy();
+    let mint = fixture.add_collateral_type(&mut svm, SECOND_LST_RATE, 100 * SOL_PRECISION).unwrap();
+    fixture.mint_amusd_with_collateral(&mut svm, WHALE, &mint, 5 * SOL_PRECISION).unwrap();
+    (svm, fixture, mint)
+}
+
+fn quote_state(fixture: &Fixture, mint: &Pubkey) -> QuoteState {
+    let collateral = fixture.collaterals.iter().find(|collateral| collateral.addresses.lst_mint == *mint).unwrap();
+    fixture
+        .expected
+        .through_vault(collateral.lst_amount, collateral.lst_to_sol_rate, collateral.collateral_weight_bps)
+        .unwrap()
+}
+
+/// `filled` is the largest input the leg can pay: one unit more would
+/// overdraw it or leave it under MIN_PROTOCOL_TVL.
+fn assert_at_fill_boundary(state: &QuoteState, filled: u64, quote: fn(&QuoteState, u64) -> Option<Quote>) {
+    let at = quote(state, filled).unwrap();
+    assert!(at.new_total_lst_amount >= MIN_PROTOCOL_TVL);
+    if let Some(over) = quote(state, filled + 1) {
+        assert!(over.new_total_lst_amount < MIN_PROTOCOL_TVL);
+    }
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn amusd_redeem_fills_up_to_the_vault_floor() {
+    let (mut svm, mut fixture, mint) = shallow_collateral();
+    let (_, amusd_before, _) = fixture.balances(&svm, WHALE);
+    let requested = 10_000 * USD_PRECISION;
+    let ix = redeem_amusd_ix(&fixture.collaterals[0].addresses, &fixture.collateral_accounts(WHALE, &mint), requested, MIN_LST_DEPOSIT);
+    assert_fails_with(send(&mut svm, "redeem_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]), "InsufficientCollateral");
+
+    let state = quote_state(&fixture, &mint);
+    let (filled, quote) = fixture.redeem_amusd_partial_with_collateral(&mut svm, WHALE, &mint, requested).unwrap();
+    assert!(filled < requested);
+    assert_at_fill_boundary(&state, filled, quote_redeem_amusd);
+    assert_eq!(fixture.balances(&svm, WHALE).1, amusd_before - filled);
+    assert_eq!(fixture.collaterals[0].lst_amount, quote.new_total_lst_amount);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_redeem_fills_up_to_the_vault_floor() {
+    let (mut svm, mut fixture, mint) = shallow_collateral();
+    let (_, _, asol_before) = fixture.balances(&svm, WHALE);
+    let requested = 100 * SOL_PRECISION;
+
+    let state = quote_state(&fixture, &mint);
+    let (filled, _) = fixture.redeem_asol_partial_with_collateral(&mut svm, WHALE, &mint, requested).unwrap();
+    assert!(filled < requested);
+    assert_at_fill_boundary(&state, filled, quote_redeem_asol);
+    assert_eq!(fixture.balances(&svm, WHALE).2, asol_before - filled);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn covered_redeem_fills_in_full() {
+    let (mut svm, mut fixture, mint) = shallow_collateral();
+    let requested = 100 * USD_PRECISION;
+    let (filled, _) = fixture.redeem_amusd_partial_with_collateral(&mut svm, WHALE, &mint, requested).unwrap();
+    assert_eq!(filled, requested);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn partial_fill_slippage_is_checked_against_the_filled_output() {
+    let (mut svm, fixture, mint) = shallow_collateral();
+    let requested = 10_000 * USD_PRECISION;
+    let state = quote_state(&fixture, &mint);
+    let filled = partial_fill(&state, requested, quote_redeem_amusd);
+    let filled_out = quote_redeem_amusd(&state, filled).unwrap().to_user;
+
+    let args = RedeemAmusdArgs { allow_partial: true, ..RedeemAmusdArgs::v1(requested, filled_out + 1) };
+    let ix = redeem_amusd_v2_ix(&fixture.collaterals[0].addresses, &fixture.collateral_accounts(WHALE, &mint), args);
+    assert_fails_with(send(&mut svm, "redeem_amusd_v2", &[ix], &fixture.actor(WHALE).keypair, &[]), "SlippageExceeded");
+}
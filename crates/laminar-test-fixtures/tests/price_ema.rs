@@ -0,0 +1,34 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Scenario};
+
+#[test]
+fn sudden_drop_redeems_amusd_at_the_ema_and_mints_at_spot() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let old_price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_ema_half_life(&mut svm, 150).unwrap();
+
+    let new_price = old_price * 9 / 10;
+    fixture.set_prices(&mut svm, new_price, rate).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.ema_sol_price_usd, old_price);
+
+    // Collateral side: min(spot, ema) is the new, lower spot.
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // Liability side: max(spot, ema) is still the pre-drop EMA, so each
+    // amUSD claims less SOL. min_out is pinned to the quote at that price.
+    fixture.expected.sol_price_usd = old_price;
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.expected.sol_price_usd = new_price;
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // One half-life later the EMA has moved halfway to spot.
+    svm.warp_to_slot(current_slot(&svm) + 150);
+    fixture.sync_exchange_rate(&mut svm).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.ema_sol_price_usd, (old_price + new_price) / 2);
+}
@@ -0,0 +1,79 @@
+use laminar::math::SOL_PRECISION;
+use laminar::state::GlobalState;
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+/// Healthy fixture with the breaker bounding each mock update to 20%.
+fn bounded_fixture() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.set_max_price_move(&mut svm, 2_000).unwrap();
+    (svm, fixture)
+}
+
+fn state(svm: &LiteSVM, fixture: &Fixture) -> GlobalState {
+    read_global_state(svm, &fixture.addresses).unwrap()
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn moves_within_the_bound_are_accepted() {
+    let (mut svm, mut fixture) = bounded_fixture();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    fixture.set_prices(&mut svm, price * 12 / 10, rate).unwrap();
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn fat_finger_price_is_rejected() {
+    let (mut svm, mut fixture) = bounded_fixture();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    // A misplaced decimal in either direction trips the breaker.
+    assert_fails_with(fixture.set_prices(&mut svm, price * 10, rate), "PriceMoveTooLarge");
+    assert_fails_with(fixture.set_prices(&mut svm, price / 10, rate), "PriceMoveTooLarge");
+    assert_fails_with(fixture.set_prices(&mut svm, price, rate * 2), "PriceMoveTooLarge");
+    assert_eq!(state(&svm, &fixture).mock_sol_price_usd, price);
+}
+
+#[test]
+fn force_requires_mint_and_redeem_paused() {
+    let (mut svm, mut fixture) = bounded_fixture();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    assert_fails_with(fixture.force_prices(&mut svm, price / 2, rate), "ForcedPriceUpdateNotPaused");
+    fixture.emergency_pause(&mut svm, true, false).unwrap();
+    assert_fails_with(fixture.force_prices(&mut svm, price / 2, rate), "ForcedPriceUpdateNotPaused");
+
+    fixture.emergency_pause(&mut svm, true, true).unwrap();
+    fixture.force_prices(&mut svm, price / 2, rate).unwrap();
+    assert_eq!(state(&svm, &fixture).mock_sol_price_usd, price / 2);
+
+    fixture.emergency_pause(&mut svm, false, false).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_bound_disables_the_breaker() {
+    let (mut svm, mut fixture) = bounded_fixture();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    fixture.set_max_price_move(&mut svm, 0).unwrap();
+    fixture.set_prices(&mut svm, price * 10, rate).unwrap();
+    assert_fails_with(fixture.set_max_price_move(&mut svm, 10_001), "InvalidParameter");
+}
@@ -0,0 +1,145 @@
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::oracle::PriceSource;
+use laminar::state::GlobalState;
+use laminar_test_fixtures::harness::{current_slot, migrate_global_state_ix, read_global_state, send, set_price_source_ix};
+use laminar_test_fixtures::pyth::{write_price_update, PythPrice};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::stake_pool::{set_epoch, stake_pool_header, write_spl_stake_pool};
+use laminar_test_fixtures::switchboard::{write_pull_feed, SwitchboardPrice};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+/// Healthy fixture with a Pyth feed 10% above and a Switchboard feed 10%
+/// below the mock price, both configured. Pyth is selected.
+fn two_feed_fixture() -> (LiteSVM, Fixture, u64) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let mock_price = fixture.expected.sol_price_usd;
+    let slot = current_slot(&svm);
+
+    let pyth = Pubkey::new_unique();
+    write_price_update(&mut svm, &pyth, &PythPrice::from_micro_usd(mock_price * 11 / 10, 0, slot)).unwrap();
+    let switchboard = Pubkey::new_unique();
+    write_pull_feed(&mut svm, &switchboard, &SwitchboardPrice::from_micro_usd(mock_price * 9 / 10, 0, slot)).unwrap();
+
+    fixture.set_pyth_price_feed(&mut svm, pyth).unwrap();
+    fixture.set_switchboard_feed(&mut svm, switchboard).unwrap();
+    (svm, fixture, mock_price)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn price_source(svm: &LiteSVM, fixture: &Fixture) -> u8 {
+    read_global_state(svm, &fixture.addresses).unwrap().price_source
+}
+
+#[test]
+fn switching_the_source_switches_the_consulted_feed() {
+    let (mut svm, mut fixture, mock_price) = two_feed_fixture();
+    assert_eq!(price_source(&svm, &fixture), PriceSource::Pyth as u8);
+
+    // min_out is pinned to the quote at the expected price, so each mint
+    // proves which feed the program read.
+    fixture.expected.sol_price_usd = mock_price * 11 / 10;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+
+    fixture.set_price_source(&mut svm, PriceSource::Switchboard).unwrap();
+    fixture.expected.sol_price_usd = mock_price * 9 / 10;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn only_the_selected_feed_is_checked() {
+    let (mut svm, mut fixture, mock_price) = two_feed_fixture();
+    fixture.set_price_source(&mut svm, PriceSource::Switchboard).unwrap();
+    fixture.expected.sol_price_usd = mock_price * 9 / 10;
+
+    // A noisy Pyth update is irrelevant while Switchboard is selected.
+    let noisy = PythPrice::from_micro_usd(mock_price, mock_price / 2, current_slot(&svm));
+    write_price_update(&mut svm, &fixture.addresses.price_update.unwrap(), &noisy).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // ...and the Switchboard account is required even with Pyth supplied.
+    fixture.addresses.switchboard_feed = None;
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+}
+
+#[test]
+fn selection_requires_a_configured_feed() {
+    let mut svm = new_svm().unwrap();
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap();
+
+    for source in [PriceSource::Pyth, PriceSource::Switchboard, PriceSource::StakePool] {
+        assert_fails_with(fixture.set_price_source(&mut svm, source), "InvalidPriceFeed");
+    }
+    let ix = set_price_source_ix(&fixture.addresses, &fixture.authority.pubkey(), 4, None);
+    assert_fails_with(send(&mut svm, "set_price_source", &[ix], &fixture.authority, &[]), "InvalidPriceFeed");
+    assert_eq!(price_source(&svm, &fixture), PriceSource::Mock as u8);
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn mock_is_not_selectable_over_a_configured_feed() {
+    let (mut svm, mut fixture, mock_price) = two_feed_fixture();
+    assert_fails_with(fixture.set_price_source(&mut svm, PriceSource::Mock), "InvalidParameter");
+    assert_fails_with(fixture.set_pyth_price_feed(&mut svm, Pubkey::default()), "InvalidParameter");
+
+    // Clearing the unselected feed, then the live one, returns to the mock.
+    fixture.set_switchboard_feed(&mut svm, Pubkey::default()).unwrap();
+    fixture.set_pyth_price_feed(&mut svm, Pubkey::default()).unwrap();
+    assert_eq!(price_source(&svm, &fixture), PriceSource::Mock as u8);
+    fixture.expected.sol_price_usd = mock_price;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn stake_pool_source_holds_the_pool_in_place() {
+    let mut svm = new_svm().unwrap();
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap();
+    set_epoch(&mut svm, 700);
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_050 * SOL_PRECISION, 1_000 * SOL_PRECISION, 700);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+
+    fixture.set_price_source(&mut svm, PriceSource::StakePool).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, Pubkey::default()), "InvalidParameter");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn migration_restores_the_implied_feed_selection() {
+    let (mut svm, mut fixture, mock_price) = two_feed_fixture();
+    fixture.set_switchboard_feed(&mut svm, Pubkey::default()).unwrap();
+
+    // State written before `price_source` existed: feed set, byte zero.
+    let global_state = fixture.addresses.global_state;
+    let mut account = svm.get_account(&global_state).unwrap();
+    let mut state = GlobalState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    state.price_source = PriceSource::Mock as u8;
+    let mut data = Vec::with_capacity(account.data.len());
+    state.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    svm.set_account(global_state, account).unwrap();
+
+    let ix = migrate_global_state_ix(&fixture.addresses, &fixture.authority.pubkey());
+    send(&mut svm, "migrate_global_state", &[ix], &fixture.authority, &[]).unwrap();
+    assert_eq!(price_source(&svm, &fixture), PriceSource::Pyth as u8);
+
+    fixture.expected.sol_price_usd = mock_price * 11 / 10;
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
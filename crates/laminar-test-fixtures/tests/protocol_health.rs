@@ -0,0 +1,53 @@
+use laminar::math::{compute_cr_bps, compute_liability_at_rate_sol, compute_tvl_sol, mul_div_down, SOL_PRECISION};
+use laminar::ProtocolHealth;
+use laminar_test_fixtures::harness::{read_global_state, simulate_return, view_protocol_health_ix};
+use laminar_test_fixtures::scenarios::{price_for_cr, MINNOW};
+use laminar_test_fixtures::{build, new_svm, Fixture, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn view(svm: &LiteSVM, fixture: &Fixture) -> ProtocolHealth {
+    let ix = view_protocol_health_ix(&fixture.addresses);
+    simulate_return(svm, "view_protocol_health", &[ix], &fixture.actor(MINNOW).keypair).unwrap()
+}
+
+/// CR straight off the raw account, primary vault only.
+fn raw_cr_bps(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    let state = read_global_state(svm, &fixture.addresses).unwrap();
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.mock_lst_to_sol_rate).unwrap();
+    let liability = compute_liability_at_rate_sol(state.amusd_supply, state.mock_sol_price_usd, state.redemption_rate_bps).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+#[test]
+fn cr_matches_the_raw_account() {
+    let (mut svm, mut fixture) = healthy();
+    assert_eq!(view(&svm, &fixture).cr_bps, raw_cr_bps(&svm, &fixture));
+
+    // Still in step once prices move.
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    let price = price_for_cr(&fixture.expected, (state.min_cr_bps + state.target_cr_bps) / 2).unwrap();
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    assert_eq!(view(&svm, &fixture).cr_bps, raw_cr_bps(&svm, &fixture));
+}
+
+#[test]
+fn balance_sheet_adds_up() {
+    let (svm, fixture) = healthy();
+    let health = view(&svm, &fixture);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+
+    assert_eq!(health.rounding_reserve, state.rounding_reserve_lamports);
+    assert_eq!(
+        health.claimable_equity,
+        health.tvl - health.liability - state.rounding_reserve_lamports - state.insurance_fund_lamports
+    );
+    assert_eq!(Some(health.nav_asol), mul_div_down(health.claimable_equity, SOL_PRECISION, state.asol_supply));
+    assert!(health.nav_amusd > 0);
+}
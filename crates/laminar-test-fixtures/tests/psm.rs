@@ -0,0 +1,144 @@
+use laminar::math::{rescale_decimals_down, USD_PRECISION};
+use laminar_test_fixtures::harness::{
+    mint_supply, psm_swap_usdc_for_amusd_ix, read_global_state, send, token_balance, update_psm_parameters_ix,
+};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+/// 0.1% on either swap direction
+const PSM_FEE_BPS: u64 = 10;
+const PSM_DEBT_CEILING: u64 = 1_000_000 * USD_PRECISION;
+const USDC_PER_ACTOR: u64 = 100_000 * USD_PRECISION;
+
+fn healthy_with_psm() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.init_psm(&mut svm, 6, PSM_FEE_BPS, PSM_DEBT_CEILING, USDC_PER_ACTOR).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn psm_amusd_outstanding(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().psm_amusd_outstanding
+}
+
+/// Every amUSD in circulation is LST-backed debt or PSM-backed debt.
+fn assert_amusd_supply_splits(svm: &LiteSVM, fixture: &Fixture) {
+    let state = read_global_state(svm, &fixture.addresses).unwrap();
+    assert_eq!(mint_supply(svm, &fixture.addresses.amusd_mint), state.amusd_supply + state.psm_amusd_outstanding);
+}
+
+#[test]
+fn usdc_swaps_in_at_par_and_stays_off_the_lst_book() {
+    let (mut svm, mut fixture) = healthy_with_psm();
+    let book_before = read_global_state(&svm, &fixture.addresses).unwrap();
+    let amusd_before = fixture.balances(&svm, WHALE).1;
+    let treasury_before = token_balance(&svm, &fixture.addresses.treasury_amusd_account);
+
+    fixture.psm_swap_usdc_for_amusd(&mut svm, WHALE, 1_000 * USD_PRECISION).unwrap();
+    assert_eq!(fixture.balances(&svm, WHALE).1 - amusd_before, 999 * USD_PRECISION);
+    assert_eq!(token_balance(&svm, &fixture.addresses.treasury_amusd_account) - treasury_before, USD_PRECISION);
+    assert_eq!(token_balance(&svm, &fixture.addresses.psm_vault), 1_000 * USD_PRECISION);
+    assert_eq!(psm_amusd_outstanding(&svm, &fixture), 1_000 * USD_PRECISION);
+
+    // amUSD supply, TVL and so CR of the LST book are untouched.
+    let book_after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(book_after.amusd_supply, book_before.amusd_supply);
+    assert_eq!(book_after.total_lst_amount, book_before.total_lst_amount);
+    assert_amusd_supply_splits(&svm, &fixture);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn amusd_swaps_back_out_for_usdc() {
+    let (mut svm, mut fixture) = healthy_with_psm();
+    fixture.psm_swap_usdc_for_amusd(&mut svm, WHALE, 1_000 * USD_PRECISION).unwrap();
+    let usdc_before = fixture.usdc_balance(&svm, WHALE);
+
+    fixture.psm_swap_amusd_for_usdc(&mut svm, WHALE, 500 * USD_PRECISION).unwrap();
+    // 0.1% stays with the treasury as amUSD; the rest is burned and paid at par.
+    let paid = 500 * USD_PRECISION - USD_PRECISION / 2;
+    assert_eq!(fixture.usdc_balance(&svm, WHALE) - usdc_before, paid);
+    assert_eq!(psm_amusd_outstanding(&svm, &fixture), 1_000 * USD_PRECISION - paid);
+    assert_eq!(token_balance(&svm, &fixture.addresses.psm_vault), 1_000 * USD_PRECISION - paid);
+    assert_amusd_supply_splits(&svm, &fixture);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn debt_ceiling_caps_usdc_swaps_in() {
+    let (mut svm, mut fixture) = healthy_with_psm();
+    let ix = update_psm_parameters_ix(&fixture.addresses, &fixture.authority.pubkey(), PSM_FEE_BPS, 500 * USD_PRECISION, None);
+    send(&mut svm, "update_psm_parameters", &[ix], &fixture.authority, &[]).unwrap();
+    let ix = psm_swap_usdc_for_amusd_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), &fixture.usdc_account(WHALE), 600 * USD_PRECISION);
+    assert_fails_with(
+        send(&mut svm, "psm_swap_usdc_for_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]),
+        "PsmDebtCeilingExceeded",
+    );
+    assert_eq!(psm_amusd_outstanding(&svm, &fixture), 0);
+
+    let ix = update_psm_parameters_ix(&fixture.addresses, &fixture.authority.pubkey(), PSM_FEE_BPS, PSM_DEBT_CEILING, None);
+    send(&mut svm, "update_psm_parameters", &[ix], &fixture.authority, &[]).unwrap();
+    fixture.psm_swap_usdc_for_amusd(&mut svm, WHALE, 600 * USD_PRECISION).unwrap();
+    assert_eq!(psm_amusd_outstanding(&svm, &fixture), 600 * USD_PRECISION);
+}
+
+#[test]
+fn swaps_out_stop_at_what_the_psm_backs() {
+    let (mut svm, mut fixture) = healthy_with_psm();
+    fixture.psm_swap_usdc_for_amusd(&mut svm, MINNOW, 100 * USD_PRECISION).unwrap();
+    // The whale's amUSD is LST-backed; the PSM only pays for its own 100.
+    assert!(fixture.balances(&svm, WHALE).1 > 200 * USD_PRECISION);
+    assert_fails_with(fixture.psm_swap_amusd_for_usdc(&mut svm, WHALE, 200 * USD_PRECISION), "PsmLiquidityExhausted");
+    fixture.psm_swap_amusd_for_usdc(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    assert_amusd_supply_splits(&svm, &fixture);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn psm_never_leaks_value() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    // An 8-decimal stablecoin so every swap rounds
+    let usdc_decimals = 8;
+    fixture.init_psm(&mut svm, usdc_decimals, PSM_FEE_BPS, PSM_DEBT_CEILING, 10_000 * 100_000_000).unwrap();
+    let actors = [WHALE, MINNOW];
+    let value = |svm: &LiteSVM, fixture: &Fixture, actor: &str| {
+        rescale_decimals_down(fixture.usdc_balance(svm, actor), usdc_decimals, 6).unwrap() + fixture.balances(svm, actor).1
+    };
+    let start = actors.map(|actor| value(&svm, &fixture, actor));
+
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    for step in 0..24 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let actor = actors[step % 2];
+        let outstanding = psm_amusd_outstanding(&svm, &fixture);
+        if seed % 3 == 0 && outstanding > USD_PRECISION {
+            let amusd = (seed >> 8) % outstanding.min(fixture.balances(&svm, actor).1) + 1;
+            fixture.psm_swap_amusd_for_usdc(&mut svm, actor, amusd).unwrap();
+        } else {
+            let usdc = (seed >> 8) % (500 * 100_000_000) + 123_456;
+            fixture.psm_swap_usdc_for_amusd(&mut svm, actor, usdc).unwrap();
+        }
+
+        let backing = rescale_decimals_down(token_balance(&svm, &fixture.addresses.psm_vault), usdc_decimals, 6).unwrap();
+        assert!(backing >= psm_amusd_outstanding(&svm, &fixture));
+        assert_amusd_supply_splits(&svm, &fixture);
+    }
+
+    // Fees and rounding only ever cost the swapper.
+    for (actor, start) in actors.iter().zip(start) {
+        assert!(value(&svm, &fixture, actor) <= start);
+    }
+    fixture.assert_matches_chain(&svm).unwrap();
+}
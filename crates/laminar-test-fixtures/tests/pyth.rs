@@ -0,0 +1,118 @@
+use laminar::constants::{DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_ORACLE_STALENESS_SLOTS};
+use laminar::math::{BPS_PRECISION, SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::current_slot;
+use laminar_test_fixtures::pyth::{write_price_update, PythPrice};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+/// Healthy fixture priced from a fresh Pyth update at the mock price.
+fn pyth_fixture() -> (LiteSVM, Fixture, Pubkey) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+
+    let feed = Pubkey::new_unique();
+    let price = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_price_update(&mut svm, &feed, &price).unwrap();
+    fixture.set_pyth_price_feed(&mut svm, feed).unwrap();
+    (svm, fixture, feed)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn fresh_pyth_price_prices_all_four_instructions() {
+    let (mut svm, mut fixture, _) = pyth_fixture();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn pyth_price_replaces_the_mock_price() {
+    let (mut svm, mut fixture, feed) = pyth_fixture();
+    // Move SOL/USD up 10% on the feed only; the mock price in GlobalState is untouched.
+    fixture.expected.sol_price_usd = fixture.expected.sol_price_usd * 11 / 10;
+    let price = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_price_update(&mut svm, &feed, &price).unwrap();
+
+    // min_out is pinned to the quote at the feed price; pricing off the
+    // lower mock price would mint less and trip slippage.
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+}
+
+#[test]
+fn stale_pyth_update_is_rejected() {
+    let (mut svm, mut fixture, feed) = pyth_fixture();
+    let slot = current_slot(&svm) + DEFAULT_MAX_ORACLE_STALENESS_SLOTS + 1;
+    svm.warp_to_slot(slot);
+    fixture.set_prices(&mut svm, fixture.expected.sol_price_usd, fixture.expected.lst_to_sol_rate).unwrap();
+
+    let stale = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, slot - DEFAULT_MAX_ORACLE_STALENESS_SLOTS - 1);
+    write_price_update(&mut svm, &feed, &stale).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "PythPriceStale");
+    assert_fails_with(fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION), "PythPriceStale");
+
+    // Exactly at the bound is still fresh.
+    let edge = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, slot - DEFAULT_MAX_ORACLE_STALENESS_SLOTS);
+    write_price_update(&mut svm, &feed, &edge).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn wide_pyth_confidence_is_rejected() {
+    let (mut svm, mut fixture, feed) = pyth_fixture();
+    let price_usd = fixture.expected.sol_price_usd;
+    let max_conf_usd = price_usd * DEFAULT_MAX_CONF_BPS / BPS_PRECISION;
+
+    let wide = PythPrice::from_micro_usd(price_usd, max_conf_usd + 1, current_slot(&svm));
+    write_price_update(&mut svm, &feed, &wide).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "OracleConfidenceTooHigh");
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, USD_PRECISION), "OracleConfidenceTooHigh");
+
+    let at_bound = PythPrice::from_micro_usd(price_usd, max_conf_usd, current_slot(&svm));
+    write_price_update(&mut svm, &feed, &at_bound).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+}
+
+#[test]
+fn malformed_or_missing_feed_is_rejected() {
+    let (mut svm, mut fixture, feed) = pyth_fixture();
+
+    let partial = PythPrice { fully_verified: false, ..PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm)) };
+    write_price_update(&mut svm, &feed, &partial).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+
+    let negative = PythPrice { price: -1, ..PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm)) };
+    write_price_update(&mut svm, &feed, &negative).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+
+    // A different account than the configured feed.
+    let impostor = Pubkey::new_unique();
+    let fresh = PythPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_price_update(&mut svm, &impostor, &fresh).unwrap();
+    fixture.addresses.price_update = Some(impostor);
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+
+    fixture.addresses.price_update = None;
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn default_feed_restores_mock_pricing() {
+    let (mut svm, mut fixture, _) = pyth_fixture();
+    fixture.set_pyth_price_feed(&mut svm, Pubkey::default()).unwrap();
+    assert!(fixture.addresses.price_update.is_none());
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,154 @@
+use laminar::instructions::TradeQuote;
+use laminar::math::{BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::{
+    mint_amusd_ix, mint_asol_ix, quote_mint_amusd_ix, quote_mint_asol_ix, quote_redeem_amusd_ix, quote_redeem_asol_ix,
+    read_global_state, redeem_amusd_ix, redeem_amusd_quoted_ix, redeem_asol_ix, redeem_asol_quoted_ix, send,
+    simulate_return,
+};
+use laminar_client::ClientError;
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::instruction::Instruction;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn insolvent() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::Insolvent).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn simulate_quote(svm: &LiteSVM, fixture: &Fixture, ix: Instruction) -> Result<TradeQuote, FixtureError> {
+    simulate_return(svm, "quote", &[ix], &fixture.actor(MINNOW).keypair)
+}
+
+/// Execute `ix`, then check the chain ended where `quote` said it would;
+/// `received` picks the balance the user is paid in.
+fn execute_and_compare(
+    svm: &mut LiteSVM,
+    fixture: &Fixture,
+    label: &'static str,
+    ix: Instruction,
+    quote: &TradeQuote,
+    received: fn((u64, u64, u64)) -> u64,
+) {
+    let before = received(fixture.balances(svm, MINNOW));
+    send(svm, label, &[ix], &fixture.actor(MINNOW).keypair, &[]).unwrap();
+    let after = received(fixture.balances(svm, MINNOW));
+    assert_eq!(after - before, quote.net, "{label} delivered a different amount than quoted");
+
+    let book = QuoteState::from(&read_global_state(svm, &fixture.addresses).unwrap());
+    assert_eq!(book.cr_bps(), Some(quote.cr_bps), "{label} left a different CR than quoted");
+    assert_eq!(book.nav().unwrap_or(0), quote.nav, "{label} left a different NAV than quoted");
+}
+
+#[test]
+fn mint_amusd_executes_as_quoted() {
+    let (mut svm, fixture) = healthy();
+    let lst_amount = SOL_PRECISION;
+    let quote = simulate_quote(&svm, &fixture, quote_mint_amusd_ix(&fixture.addresses, lst_amount)).unwrap();
+    assert!(quote.fee_bps > 0);
+    assert_eq!(quote.gross, quote.net + quote.fee);
+
+    let ix = mint_amusd_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), lst_amount, quote.net);
+    execute_and_compare(&mut svm, &fixture, "mint_amusd", ix, &quote, |(_, amusd, _)| amusd);
+}
+
+#[test]
+fn redeem_amusd_executes_as_quoted() {
+    let (mut svm, fixture) = healthy();
+    let amusd_amount = fixture.balances(&svm, MINNOW).1 / 2;
+    let quote = simulate_quote(&svm, &fixture, quote_redeem_amusd_ix(&fixture.addresses, amusd_amount)).unwrap();
+    assert_eq!(quote.gross, amusd_amount);
+
+    assert!(!quote.insolvency_mode);
+    assert_eq!(quote.haircut_bps, BPS_PRECISION);
+    assert_eq!(quote.par_net, quote.net);
+
+    let ix = redeem_amusd_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), amusd_amount, quote.net);
+    execute_and_compare(&mut svm, &fixture, "redeem_amusd", ix, &quote, |(lst, _, _)| lst);
+}
+
+#[test]
+fn haircut_redeem_quotes_below_par_and_executes_as_quoted() {
+    let (mut svm, fixture) = insolvent();
+    let amusd_amount = fixture.balances(&svm, MINNOW).1 / 2;
+    let quote = simulate_quote(&svm, &fixture, quote_redeem_amusd_ix(&fixture.addresses, amusd_amount)).unwrap();
+
+    let book = QuoteState::from(&read_global_state(&svm, &fixture.addresses).unwrap());
+    assert!(quote.insolvency_mode);
+    assert_eq!(quote.fee_bps, 0);
+    assert_eq!(quote.haircut_bps, book.uninsured_cr_bps().unwrap());
+    assert!(quote.haircut_bps < BPS_PRECISION);
+    assert_eq!(quote.par_net, book.par_lst_value(amusd_amount).unwrap());
+    assert!(quote.net < quote.par_net);
+
+    let ix = redeem_amusd_quoted_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), &quote, true).unwrap();
+    execute_and_compare(&mut svm, &fixture, "redeem_amusd", ix, &quote, |(lst, _, _)| lst);
+}
+
+#[test]
+fn haircut_redeem_is_not_built_without_acknowledgement() {
+    let (svm, fixture) = insolvent();
+    let accounts = fixture.actor(MINNOW).accounts();
+    let amusd_amount = fixture.balances(&svm, MINNOW).1 / 2;
+    let quote = simulate_quote(&svm, &fixture, quote_redeem_amusd_ix(&fixture.addresses, amusd_amount)).unwrap();
+
+    match redeem_amusd_quoted_ix(&fixture.addresses, &accounts, &quote, false) {
+        Err(ClientError::HaircutNotAcknowledged { haircut_bps, par_net, net }) => {
+            assert_eq!((haircut_bps, par_net, net), (quote.haircut_bps, quote.par_net, quote.net));
+        }
+        other => panic!("expected HaircutNotAcknowledged, got {other:?}"),
+    }
+    assert_eq!(
+        redeem_amusd_quoted_ix(&fixture.addresses, &accounts, &quote, true).unwrap(),
+        redeem_amusd_ix(&fixture.addresses, &accounts, amusd_amount, quote.net)
+    );
+
+    // At par the acknowledgement is not needed.
+    let (svm, fixture) = healthy();
+    let accounts = fixture.actor(MINNOW).accounts();
+    let asol_amount = fixture.balances(&svm, MINNOW).2 / 2;
+    let quote = simulate_quote(&svm, &fixture, quote_redeem_asol_ix(&fixture.addresses, asol_amount)).unwrap();
+    assert!(!quote.insolvency_mode);
+    assert_eq!(
+        redeem_asol_quoted_ix(&fixture.addresses, &accounts, &quote, false).unwrap(),
+        redeem_asol_ix(&fixture.addresses, &accounts, asol_amount, quote.net)
+    );
+}
+
+#[test]
+fn mint_asol_executes_as_quoted() {
+    let (mut svm, fixture) = healthy();
+    let lst_amount = SOL_PRECISION;
+    let quote = simulate_quote(&svm, &fixture, quote_mint_asol_ix(&fixture.addresses, lst_amount)).unwrap();
+
+    let ix = mint_asol_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), lst_amount, quote.net);
+    execute_and_compare(&mut svm, &fixture, "mint_asol", ix, &quote, |(_, _, asol)| asol);
+}
+
+#[test]
+fn redeem_asol_executes_as_quoted() {
+    let (mut svm, fixture) = healthy();
+    let asol_amount = fixture.balances(&svm, MINNOW).2 / 2;
+    let quote = simulate_quote(&svm, &fixture, quote_redeem_asol_ix(&fixture.addresses, asol_amount)).unwrap();
+    assert_eq!(quote.gross, asol_amount);
+
+    let ix = redeem_asol_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), asol_amount, quote.net);
+    execute_and_compare(&mut svm, &fixture, "redeem_asol", ix, &quote, |(lst, _, _)| lst);
+}
+
+#[test]
+fn zero_amount_is_rejected() {
+    let (svm, fixture) = healthy();
+    match simulate_quote(&svm, &fixture, quote_redeem_asol_ix(&fixture.addresses, 0)) {
+        Ok(_) => panic!("expected ZeroAmount"),
+        Err(err) => assert!(err.to_string().contains("ZeroAmount"), "expected ZeroAmount, got {err}"),
+    }
+}
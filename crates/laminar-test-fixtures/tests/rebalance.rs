@@ -0,0 +1,143 @@
+use laminar::constants::{DEFAULT_REBALANCE_DISCOUNT_BPS, MAX_REBALANCE_DISCOUNT_BPS};
+use laminar::math::{
+    compute_cr_bps, compute_liability_sol, compute_rebalance_bonus, compute_rebalance_lst_needed, compute_tvl_sol,
+    nav_asol_with_reserve, SOL_PRECISION,
+};
+use laminar::state::GlobalState;
+use laminar_test_fixtures::harness::{read_global_state, token_balance, transfer_tokens};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn fixture_in(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+/// Healthy book after SOL falls 40%: CR ~12000, under the 13000 minimum.
+fn after_price_crash() -> (LiteSVM, Fixture) {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 60 / 100, rate).unwrap();
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(cr_bps(&state) < state.min_cr_bps && cr_bps(&state) > 10_000);
+    (svm, fixture)
+}
+
+fn cr_bps(state: &GlobalState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.mock_lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.mock_sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn nav(state: &GlobalState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.mock_lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.mock_sol_price_usd).unwrap();
+    nav_asol_with_reserve(tvl, liability, state.rounding_reserve_lamports, state.asol_supply).unwrap()
+}
+
+fn lst_needed(state: &GlobalState) -> u64 {
+    compute_rebalance_lst_needed(
+        state.total_lst_amount,
+        state.mock_lst_to_sol_rate,
+        state.weighted_collateral_tvl_lamports,
+        state.amusd_supply,
+        state.mock_sol_price_usd,
+        state.min_cr_bps,
+    )
+    .unwrap()
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn rebalance_restores_min_cr_after_40pct_price_drop() {
+    let (mut svm, mut fixture) = after_price_crash();
+
+    // Fund the treasury past the discount and let rebalances draw on it.
+    let whale = fixture.actor(WHALE);
+    transfer_tokens(&mut svm, &whale.keypair, &whale.asol_account, &fixture.addresses.treasury_asol_account, 50 * SOL_PRECISION).unwrap();
+    fixture.approve_rebalance_budget(&mut svm, u64::MAX).unwrap();
+
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+    let needed = lst_needed(&before);
+    let (whale_lst, _, whale_asol) = fixture.balances(&svm, WHALE);
+    let treasury_asol = token_balance(&svm, &fixture.addresses.treasury_asol_account);
+
+    // Offer twice what is needed; only the shortfall is taken.
+    fixture.rebalance(&mut svm, WHALE, 2 * needed, 0).unwrap();
+
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(whale_lst - fixture.balances(&svm, WHALE).0, needed);
+    assert_eq!(after.total_lst_amount, before.total_lst_amount + needed);
+    assert_eq!(cr_bps(&after), after.min_cr_bps);
+
+    // Minted at NAV; the discount is paid by the treasury
+    let asol_minted = after.asol_supply - before.asol_supply;
+    let bonus = fixture.balances(&svm, WHALE).2 - whale_asol - asol_minted;
+    assert!(bonus > 0);
+    assert_eq!(bonus, compute_rebalance_bonus(asol_minted, DEFAULT_REBALANCE_DISCOUNT_BPS).unwrap());
+    assert_eq!(treasury_asol - token_balance(&svm, &fixture.addresses.treasury_asol_account), bonus);
+
+    // Neither tranche paid for it: amUSD debt is untouched and NAV did not fall.
+    assert_eq!(after.amusd_supply, before.amusd_supply);
+    assert!(nav(&after) >= nav(&before));
+
+    assert_fails_with(fixture.rebalance(&mut svm, WHALE, needed, 0), "RebalanceNotNeeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn rebalance_without_treasury_allowance_fills_at_nav_in_parts() {
+    let (mut svm, mut fixture) = after_price_crash();
+
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+    let needed = lst_needed(&before);
+    let (_, _, whale_asol) = fixture.balances(&svm, WHALE);
+
+    fixture.rebalance(&mut svm, WHALE, needed / 2, 0).unwrap();
+    let partial = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(cr_bps(&partial) < partial.min_cr_bps);
+    assert_eq!(fixture.balances(&svm, WHALE).2 - whale_asol, partial.asol_supply - before.asol_supply);
+
+    fixture.rebalance(&mut svm, WHALE, lst_needed(&partial), 0).unwrap();
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(cr_bps(&after), after.min_cr_bps);
+    assert_eq!(after.total_lst_amount, before.total_lst_amount + needed);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn rebalance_is_refused_above_min_cr_and_while_the_pool_can_absorb() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    assert_fails_with(fixture.rebalance(&mut svm, WHALE, SOL_PRECISION, 0), "RebalanceNotNeeded");
+
+    fixture.init_stability_pool(&mut svm).unwrap();
+    let minnow_amusd = fixture.balances(&svm, MINNOW).1;
+    fixture.deposit_stability(&mut svm, MINNOW, minnow_amusd).unwrap();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 60 / 100, rate).unwrap();
+
+    assert_fails_with(fixture.rebalance(&mut svm, WHALE, SOL_PRECISION, 0), "StabilityDrawdownPending");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn rebalance_discount_is_bounded() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().rebalance_discount_bps, DEFAULT_REBALANCE_DISCOUNT_BPS);
+
+    assert_fails_with(fixture.set_rebalance_discount(&mut svm, MAX_REBALANCE_DISCOUNT_BPS + 1), "InvalidParameter");
+    fixture.set_rebalance_discount(&mut svm, MAX_REBALANCE_DISCOUNT_BPS).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().rebalance_discount_bps, MAX_REBALANCE_DISCOUNT_BPS);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,113 @@
+use laminar::constants::MAX_RECOVERY_BONUS_BPS;
+use laminar::math::SOL_PRECISION;
+use laminar::quote::{quote_mint_asol, QuoteState};
+use laminar_test_fixtures::harness::{current_slot, read_global_state};
+use laminar_test_fixtures::scenarios::{price_for_cr, MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn fixture() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn price_drop_below_min_cr_enters_and_recovery_exits() {
+    let (mut svm, mut fixture) = fixture();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(!state.recovery_mode);
+
+    let healthy_price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    let crash_price = price_for_cr(&fixture.expected, state.min_cr_bps - 1_000).unwrap();
+    fixture.set_prices(&mut svm, crash_price, rate).unwrap();
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(state.recovery_mode);
+    assert_eq!(state.recovery_mode_entered_slot, current_slot(&svm));
+
+    fixture.set_prices(&mut svm, healthy_price, rate).unwrap();
+    let exited = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(!exited.recovery_mode);
+    // The entry slot stays behind for observability.
+    assert_eq!(exited.recovery_mode_entered_slot, state.recovery_mode_entered_slot);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn recovery_mode_closes_risk_increasing_actions_only() {
+    let (mut svm, mut fixture) = fixture();
+    let healthy_price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    let min_cr_bps = fixture.expected.min_cr_bps;
+
+    let crash_price = price_for_cr(&fixture.expected, min_cr_bps - 1_000).unwrap();
+    fixture.set_prices(&mut svm, crash_price, rate).unwrap();
+
+    // Debt creation and equity exits are refused up front...
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "RecoveryMode");
+    assert_fails_with(fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION), "RecoveryMode");
+
+    // ...while the actions that lift CR stay open.
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let minnow_amusd = fixture.balances(&svm, MINNOW).1;
+    fixture.redeem_amusd(&mut svm, MINNOW, minnow_amusd / 2).unwrap();
+    assert!(read_global_state(&svm, &fixture.addresses).unwrap().recovery_mode);
+
+    fixture.set_prices(&mut svm, healthy_price, rate).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn equity_injection_that_lifts_cr_to_min_exits_recovery_mode() {
+    let (mut svm, mut fixture) = fixture();
+    let rate = fixture.expected.lst_to_sol_rate;
+    let min_cr_bps = fixture.expected.min_cr_bps;
+
+    let crash_price = price_for_cr(&fixture.expected, min_cr_bps - 100).unwrap();
+    fixture.set_prices(&mut svm, crash_price, rate).unwrap();
+    assert!(read_global_state(&svm, &fixture.addresses).unwrap().recovery_mode);
+
+    // Fresh equity well past the shortfall pulls CR back over min.
+    fixture.mint_asol(&mut svm, WHALE, 500 * SOL_PRECISION).unwrap();
+    assert!(!read_global_state(&svm, &fixture.addresses).unwrap().recovery_mode);
+
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn recovery_bonus_only_applies_below_min_cr() {
+    let (mut svm, mut fixture) = fixture();
+    assert_fails_with(fixture.set_recovery_bonus(&mut svm, MAX_RECOVERY_BONUS_BPS + 1), "InvalidParameter");
+    fixture.set_recovery_bonus(&mut svm, 200).unwrap();
+    let without_bonus = |state: &QuoteState| quote_mint_asol(&QuoteState { recovery_bonus_bps: 0, ..*state }, SOL_PRECISION).unwrap();
+
+    // Above min the quote is the plain NAV mint.
+    let plain = without_bonus(&fixture.expected);
+    assert_eq!(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap(), plain);
+
+    let rate = fixture.expected.lst_to_sol_rate;
+    let crash_price = price_for_cr(&fixture.expected, fixture.expected.min_cr_bps - 1_000).unwrap();
+    fixture.set_prices(&mut svm, crash_price, rate).unwrap();
+
+    // Below min the user gets extra aSOL, minted as new supply.
+    let plain = without_bonus(&fixture.expected);
+    let (_, _, asol_before) = fixture.balances(&svm, MINNOW);
+    let quote = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let bonus = quote.to_user - plain.to_user;
+    assert!(bonus > 0);
+    assert_eq!(quote.new_supply - plain.new_supply, bonus);
+    assert_eq!(fixture.balances(&svm, MINNOW).2 - asol_before, quote.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,60 @@
+use laminar::constants::REDEEM_ALL;
+use laminar::math::MIN_LST_DEPOSIT;
+use laminar_test_fixtures::harness::{redeem_amusd_ix, redeem_asol_ix, send};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn redeem_all_amusd_exits_the_whole_balance_with_fees() {
+    let (mut svm, mut fixture) = healthy();
+    let (lst_before, amusd_before, _) = fixture.balances(&svm, MINNOW);
+    assert!(amusd_before > 0);
+
+    let quote = fixture.redeem_all_amusd(&mut svm, MINNOW).unwrap();
+    assert!(quote.fee + quote.insurance_fee > 0);
+    let (lst_after, amusd_after, _) = fixture.balances(&svm, MINNOW);
+    assert_eq!(amusd_after, 0);
+    assert_eq!(lst_after, lst_before + quote.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_all_asol_exits_the_whole_balance_with_fees() {
+    let (mut svm, mut fixture) = healthy();
+    let (lst_before, _, asol_before) = fixture.balances(&svm, MINNOW);
+    assert!(asol_before > 0);
+
+    let quote = fixture.redeem_all_asol(&mut svm, MINNOW).unwrap();
+    assert!(quote.fee + quote.insurance_fee > 0);
+    let (lst_after, _, asol_after) = fixture.balances(&svm, MINNOW);
+    assert_eq!(asol_after, 0);
+    assert_eq!(lst_after, lst_before + quote.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_all_of_an_empty_balance_is_a_zero_amount() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.redeem_all_amusd(&mut svm, MINNOW).unwrap();
+    fixture.redeem_all_asol(&mut svm, MINNOW).unwrap();
+
+    let accounts = fixture.actor(MINNOW).accounts();
+    let ix = redeem_amusd_ix(&fixture.addresses, &accounts, REDEEM_ALL, MIN_LST_DEPOSIT);
+    assert_fails_with(send(&mut svm, "redeem_amusd", &[ix], &fixture.actor(MINNOW).keypair, &[]), "ZeroAmount");
+    let ix = redeem_asol_ix(&fixture.addresses, &accounts, REDEEM_ALL, MIN_LST_DEPOSIT);
+    assert_fails_with(send(&mut svm, "redeem_asol", &[ix], &fixture.actor(MINNOW).keypair, &[]), "ZeroAmount");
+}
@@ -0,0 +1,45 @@
+use laminar::math::SOL_PRECISION;
+use laminar::quote::quote_redeem_asol;
+use laminar_test_fixtures::harness::{redeem_asol_ix, send};
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn redeem_asol_raw(svm: &mut LiteSVM, fixture: &Fixture, asol_amount: u64, min_lst_out: u64) -> Result<(), FixtureError> {
+    let ix = redeem_asol_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), asol_amount, min_lst_out);
+    send(svm, "redeem_asol", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+#[test]
+fn zero_min_lst_out_sets_no_bound() {
+    let (mut svm, fixture) = healthy();
+    let amount = SOL_PRECISION / 10;
+    let quote = quote_redeem_asol(&fixture.expected, amount).unwrap();
+    let (lst_before, _, _) = fixture.balances(&svm, MINNOW);
+
+    redeem_asol_raw(&mut svm, &fixture, amount, 0).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).0, lst_before + quote.to_user);
+}
+
+#[test]
+fn dust_output_and_slippage_fail_with_their_own_errors() {
+    let (mut svm, fixture) = healthy();
+    assert_fails_with(redeem_asol_raw(&mut svm, &fixture, 1_000, 0), "RedeemOutputTooSmall");
+
+    let amount = SOL_PRECISION / 10;
+    let quote = quote_redeem_asol(&fixture.expected, amount).unwrap();
+    assert_fails_with(redeem_asol_raw(&mut svm, &fixture, amount, quote.to_user + 1), "SlippageExceeded");
+}
@@ -0,0 +1,82 @@
+use laminar::constants::DEFAULT_PAIR_CR_TOLERANCE_BPS;
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol, BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::{read_global_state, redeem_pair_ix, send};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn cr_bps(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    let state = QuoteState::from(&read_global_state(svm, &fixture.addresses).unwrap());
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn redeem_pair(
+    svm: &mut LiteSVM,
+    fixture: &Fixture,
+    actor: &str,
+    amusd_amount: u64,
+    asol_amount: u64,
+    min_lst_out: u64,
+) -> Result<(), FixtureError> {
+    let ix = redeem_pair_ix(&fixture.addresses, &fixture.actor(actor).accounts(), amusd_amount, asol_amount, min_lst_out);
+    send(svm, "redeem_pair", &[ix], &fixture.actor(actor).keypair, &[])
+}
+
+#[test]
+fn auto_sized_pair_redeem_leaves_cr_unchanged() {
+    let (mut svm, fixture) = healthy();
+    let (lst_before, amusd_before, asol_before) = fixture.balances(&svm, MINNOW);
+    let cr_before = cr_bps(&svm, &fixture);
+
+    redeem_pair(&mut svm, &fixture, MINNOW, amusd_before / 2, 0, 1).unwrap();
+
+    let cr_after = cr_bps(&svm, &fixture);
+    assert!(cr_before.abs_diff(cr_after) <= DEFAULT_PAIR_CR_TOLERANCE_BPS, "CR {cr_before} -> {cr_after}");
+    let (lst_after, amusd_after, asol_after) = fixture.balances(&svm, MINNOW);
+    assert_eq!(amusd_after, amusd_before - amusd_before / 2);
+    assert!(asol_after < asol_before);
+    assert!(lst_after > lst_before);
+}
+
+#[test]
+fn an_unbalanced_pair_is_rejected() {
+    let (mut svm, fixture) = healthy();
+    let asol = fixture.balances(&svm, WHALE).2;
+    assert_fails_with(redeem_pair(&mut svm, &fixture, WHALE, 1, asol / 2, 0), "PairCrDrift");
+}
+
+#[test]
+fn the_tolerance_is_configurable() {
+    let (mut svm, mut fixture) = healthy();
+    let (_, amusd, asol) = fixture.balances(&svm, WHALE);
+    fixture.set_pair_cr_tolerance(&mut svm, BPS_PRECISION).unwrap();
+    redeem_pair(&mut svm, &fixture, WHALE, amusd / 100, asol / 100, 0).unwrap();
+
+    fixture.set_pair_cr_tolerance(&mut svm, 0).unwrap();
+    assert_fails_with(redeem_pair(&mut svm, &fixture, WHALE, amusd / 100, SOL_PRECISION, 0), "PairCrDrift");
+
+    assert_fails_with(fixture.set_pair_cr_tolerance(&mut svm, BPS_PRECISION + 1), "InvalidParameter");
+}
+
+#[test]
+fn min_lst_out_bounds_both_payouts() {
+    let (mut svm, fixture) = healthy();
+    let amusd = fixture.balances(&svm, MINNOW).1;
+    assert_fails_with(redeem_pair(&mut svm, &fixture, MINNOW, amusd / 2, 0, u64::MAX), "SlippageExceeded");
+}
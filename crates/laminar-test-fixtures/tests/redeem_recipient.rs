@@ -0,0 +1,74 @@
+use laminar::instructions::{RedeemAmusdArgs, RedeemAsolArgs};
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_redeem_amusd, quote_redeem_asol};
+use laminar_test_fixtures::harness::{create_ata, redeem_amusd_to_recipient_ix, redeem_asol_to_recipient_ix, send, token_balance};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn redeem_amusd_to(svm: &mut LiteSVM, fixture: &Fixture, amusd_amount: u64, recipient: &Pubkey) -> Result<(), FixtureError> {
+    let args = RedeemAmusdArgs::v1(amusd_amount, 0);
+    let ix = redeem_amusd_to_recipient_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args, recipient);
+    send(svm, "redeem_amusd_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+fn redeem_asol_to(svm: &mut LiteSVM, fixture: &Fixture, asol_amount: u64, recipient: &Pubkey) -> Result<(), FixtureError> {
+    let args = RedeemAsolArgs::v1(asol_amount, 0);
+    let ix = redeem_asol_to_recipient_ix(&fixture.addresses, &fixture.actor(MINNOW).accounts(), args, recipient);
+    send(svm, "redeem_asol_v2", &[ix], &fixture.actor(MINNOW).keypair, &[])
+}
+
+#[test]
+fn redeem_pays_a_third_party_ata() {
+    let (mut svm, fixture) = healthy();
+    let recipient = fixture.actor(WHALE).lst_account;
+    let (minnow_lst, minnow_amusd, minnow_asol) = fixture.balances(&svm, MINNOW);
+    let whale_lst = token_balance(&svm, &recipient);
+
+    let amusd_amount = 10 * USD_PRECISION;
+    let amusd_quote = quote_redeem_amusd(&fixture.expected, amusd_amount).unwrap();
+    redeem_amusd_to(&mut svm, &fixture, amusd_amount, &recipient).unwrap();
+    assert_eq!(token_balance(&svm, &recipient), whale_lst + amusd_quote.to_user);
+
+    let asol_amount = SOL_PRECISION / 10;
+    let before = token_balance(&svm, &recipient);
+    redeem_asol_to(&mut svm, &fixture, asol_amount, &recipient).unwrap();
+    assert!(token_balance(&svm, &recipient) > before);
+
+    // The signer pays the tokens in and receives nothing back.
+    assert_eq!(fixture.balances(&svm, MINNOW), (minnow_lst, minnow_amusd - amusd_amount, minnow_asol - asol_amount));
+}
+
+#[test]
+fn redeem_pays_a_pda_owned_token_account() {
+    let (mut svm, fixture) = healthy();
+    let (custodian, _) = Pubkey::find_program_address(&[b"custody"], &laminar::ID);
+    let recipient = create_ata(&mut svm, &fixture.actor(MINNOW).keypair, &custodian, &fixture.addresses.lst_mint).unwrap();
+
+    let asol_amount = SOL_PRECISION / 10;
+    let quote = quote_redeem_asol(&fixture.expected, asol_amount).unwrap();
+    redeem_asol_to(&mut svm, &fixture, asol_amount, &recipient).unwrap();
+    assert_eq!(token_balance(&svm, &recipient), quote.to_user);
+}
+
+#[test]
+fn recipient_must_hold_the_redeemed_lst() {
+    let (mut svm, fixture) = healthy();
+    let wrong_mint = fixture.actor(WHALE).amusd_account;
+    assert_fails_with(redeem_amusd_to(&mut svm, &fixture, 10 * USD_PRECISION, &wrong_mint), "ConstraintTokenMint");
+    assert_fails_with(redeem_asol_to(&mut svm, &fixture, SOL_PRECISION / 10, &wrong_mint), "ConstraintTokenMint");
+}
@@ -0,0 +1,91 @@
+use laminar::constants::REDEMPTION_TICKET_EXPIRY_SLOTS;
+use laminar::math::{MIN_LST_DEPOSIT, SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::{read_global_state, read_redemption_ticket, redemption_ticket_address};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+/// Move to `slot` and refresh the LST snapshot there.
+fn warp_to(svm: &mut LiteSVM, fixture: &mut Fixture, slot: u64) {
+    svm.warp_to_slot(slot);
+    fixture.sync_exchange_rate(svm).unwrap();
+}
+
+#[test]
+fn paused_redemption_is_queued_and_processed_after_unpause() {
+    let (mut svm, mut fixture) = healthy();
+    let amount = 50 * USD_PRECISION;
+    fixture.emergency_pause(&mut svm, false, true).unwrap();
+    assert_fails_with(fixture.redeem_amusd(&mut svm, MINNOW, amount), "RedeemPaused");
+
+    let (lst_before, amusd_before, _) = fixture.balances(&svm, MINNOW);
+    let sequence = fixture.request_redemption(&mut svm, MINNOW, false, amount, MIN_LST_DEPOSIT).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).1, amusd_before - amount);
+    assert_fails_with(fixture.process_redemption(&mut svm, sequence), "RedeemPaused");
+
+    fixture.emergency_pause(&mut svm, false, false).unwrap();
+    let quote = fixture.process_redemption(&mut svm, sequence).unwrap().expect("live ticket");
+    assert_eq!(fixture.balances(&svm, MINNOW).0, lst_before + quote.to_user);
+    assert!(svm.get_account(&redemption_ticket_address(sequence)).is_none());
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().redemption_queue_head, sequence + 1);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn tickets_are_processed_in_request_order() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.emergency_pause(&mut svm, false, true).unwrap();
+    let first = fixture.request_redemption(&mut svm, MINNOW, false, 10 * USD_PRECISION, MIN_LST_DEPOSIT).unwrap();
+    let second = fixture.request_redemption(&mut svm, WHALE, true, SOL_PRECISION, MIN_LST_DEPOSIT).unwrap();
+    fixture.emergency_pause(&mut svm, false, false).unwrap();
+
+    assert_fails_with(fixture.process_redemption(&mut svm, second), "RedemptionOutOfOrder");
+    fixture.process_redemption(&mut svm, first).unwrap();
+    fixture.process_redemption(&mut svm, second).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn owner_cancels_and_the_queue_clears_the_ticket() {
+    let (mut svm, mut fixture) = healthy();
+    let (_, amusd_before, _) = fixture.balances(&svm, MINNOW);
+    let sequence = fixture.request_redemption(&mut svm, MINNOW, false, 10 * USD_PRECISION, MIN_LST_DEPOSIT).unwrap();
+
+    assert_fails_with(fixture.cancel_redemption(&mut svm, WHALE, sequence), "RedemptionCancelNotAllowed");
+    fixture.cancel_redemption(&mut svm, MINNOW, sequence).unwrap();
+    assert_eq!(fixture.balances(&svm, MINNOW).1, amusd_before);
+    assert_eq!(read_redemption_ticket(&svm, sequence).unwrap().amount, 0);
+    assert_fails_with(fixture.cancel_redemption(&mut svm, MINNOW, sequence), "RedemptionTicketCancelled");
+
+    assert!(fixture.process_redemption(&mut svm, sequence).unwrap().is_none());
+    assert!(svm.get_account(&redemption_ticket_address(sequence)).is_none());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn anyone_may_return_an_expired_head_ticket() {
+    let (mut svm, mut fixture) = healthy();
+    let sequence = fixture.request_redemption(&mut svm, MINNOW, true, SOL_PRECISION, MIN_LST_DEPOSIT).unwrap();
+    let request_slot = read_redemption_ticket(&svm, sequence).unwrap().request_slot;
+
+    warp_to(&mut svm, &mut fixture, request_slot + REDEMPTION_TICKET_EXPIRY_SLOTS - 1);
+    assert_fails_with(fixture.cancel_redemption(&mut svm, WHALE, sequence), "RedemptionCancelNotAllowed");
+
+    warp_to(&mut svm, &mut fixture, request_slot + REDEMPTION_TICKET_EXPIRY_SLOTS);
+    fixture.cancel_redemption(&mut svm, WHALE, sequence).unwrap();
+    assert!(fixture.process_redemption(&mut svm, sequence).unwrap().is_none());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,145 @@
+use laminar::math::{compute_savings_fee, compute_savings_withdrawal, BPS_PRECISION, SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::{read_savings_deposit, read_savings_pool, token_balance};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+/// Half of every amUSD treasury fee goes to savers
+const SAVINGS_FEE_SPLIT_BPS: u64 = 5_000;
+
+fn healthy_with_savings() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.init_savings_pool(&mut svm, SAVINGS_FEE_SPLIT_BPS).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn savings_vault_balance(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    token_balance(svm, &fixture.addresses.savings_pool.unwrap().1)
+}
+
+fn shares_of(svm: &LiteSVM, fixture: &Fixture, actor: &str) -> u64 {
+    read_savings_deposit(svm, &fixture.actor(actor).keypair.pubkey()).unwrap().shares
+}
+
+#[test]
+fn depositors_accrue_mint_fees_in_proportion() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    fixture.deposit_savings(&mut svm, WHALE, 30 * USD_PRECISION).unwrap();
+    fixture.deposit_savings(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    assert_eq!(shares_of(&svm, &fixture, WHALE), 3 * shares_of(&svm, &fixture, MINNOW));
+
+    let treasury_before = token_balance(&svm, &fixture.addresses.treasury_amusd_account);
+    let mut savings_fees = 0;
+    let mut treasury_fees = 0;
+    for lst_amount in [SOL_PRECISION, 3 * SOL_PRECISION, 7 * SOL_PRECISION, 2 * SOL_PRECISION] {
+        let quote = fixture.mint_amusd(&mut svm, WHALE, lst_amount).unwrap();
+        let savings_fee = compute_savings_fee(quote.fee, SAVINGS_FEE_SPLIT_BPS).unwrap();
+        assert!(savings_fee > 0);
+        savings_fees += savings_fee;
+        treasury_fees += quote.fee - savings_fee;
+    }
+
+    // The fee is only re-routed: the treasury keeps the rest and the book is unchanged.
+    assert_eq!(token_balance(&svm, &fixture.addresses.treasury_amusd_account) - treasury_before, treasury_fees);
+    let pool = read_savings_pool(&svm).unwrap();
+    assert_eq!(pool.total_pool_amusd, 40 * USD_PRECISION + savings_fees);
+    assert_eq!(savings_vault_balance(&svm, &fixture), pool.total_pool_amusd);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Withdrawing every share pays each depositor its stake plus its share of the fees.
+    let whale_before = fixture.balances(&svm, WHALE).1;
+    let minnow_before = fixture.balances(&svm, MINNOW).1;
+    let minnow_shares = shares_of(&svm, &fixture, MINNOW);
+    let whale_shares = shares_of(&svm, &fixture, WHALE);
+    fixture.withdraw_savings(&mut svm, MINNOW, minnow_shares).unwrap();
+    fixture.withdraw_savings(&mut svm, WHALE, whale_shares).unwrap();
+    let minnow_gain = fixture.balances(&svm, MINNOW).1 - minnow_before - 10 * USD_PRECISION;
+    let whale_gain = fixture.balances(&svm, WHALE).1 - whale_before - 30 * USD_PRECISION;
+
+    assert_eq!(minnow_gain, savings_fees / 4);
+    assert!(whale_gain.abs_diff(3 * minnow_gain) <= 3, "whale {whale_gain}, minnow {minnow_gain}");
+    // Rounding stays with the pool, never the other way.
+    assert!(whale_gain + minnow_gain <= savings_fees);
+    assert_eq!(savings_vault_balance(&svm, &fixture), savings_fees - whale_gain - minnow_gain);
+}
+
+#[test]
+fn redeem_fees_are_split_with_savers() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    fixture.deposit_savings(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+
+    let treasury_before = token_balance(&svm, &fixture.addresses.treasury_amusd_account);
+    let quote = fixture.redeem_amusd(&mut svm, WHALE, 1_000 * USD_PRECISION).unwrap();
+    let savings_fee = compute_savings_fee(quote.fee, SAVINGS_FEE_SPLIT_BPS).unwrap();
+    assert!(savings_fee > 0);
+
+    assert_eq!(token_balance(&svm, &fixture.addresses.treasury_amusd_account) - treasury_before, quote.fee - savings_fee);
+    assert_eq!(savings_vault_balance(&svm, &fixture), 10 * USD_PRECISION + savings_fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn later_depositors_buy_in_at_the_raised_share_price() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    fixture.deposit_savings(&mut svm, WHALE, 10 * USD_PRECISION).unwrap();
+    fixture.mint_amusd(&mut svm, WHALE, 5 * SOL_PRECISION).unwrap();
+
+    let pool = read_savings_pool(&svm).unwrap();
+    assert!(pool.total_pool_amusd > pool.total_shares);
+    fixture.deposit_savings(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+
+    // The newcomer's shares are worth no more than it paid in.
+    let minnow_shares = shares_of(&svm, &fixture, MINNOW);
+    assert!(minnow_shares < shares_of(&svm, &fixture, WHALE));
+    let pool = read_savings_pool(&svm).unwrap();
+    let value = compute_savings_withdrawal(minnow_shares, pool.total_pool_amusd, pool.total_shares).unwrap();
+    assert!(value <= 10 * USD_PRECISION);
+    assert!(10 * USD_PRECISION - value <= 2);
+}
+
+#[test]
+fn an_empty_pool_leaves_the_fee_with_the_treasury() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    let treasury_before = token_balance(&svm, &fixture.addresses.treasury_amusd_account);
+
+    let quote = fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION).unwrap();
+
+    assert_eq!(token_balance(&svm, &fixture.addresses.treasury_amusd_account) - treasury_before, quote.fee);
+    assert_eq!(read_savings_pool(&svm).unwrap().total_pool_amusd, 0);
+    assert_eq!(savings_vault_balance(&svm, &fixture), 0);
+}
+
+#[test]
+fn split_requires_the_savings_accounts() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    fixture.addresses.savings_pool = None;
+    assert_fails_with(fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION), "SavingsPoolRequired");
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION), "SavingsPoolRequired");
+
+    // With the split cleared mint and redeem no longer need them.
+    fixture.set_savings_fee_split(&mut svm, 0).unwrap();
+    fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION).unwrap();
+}
+
+#[test]
+fn split_is_capped_at_the_whole_fee() {
+    let (mut svm, mut fixture) = healthy_with_savings();
+    assert_fails_with(fixture.set_savings_fee_split(&mut svm, BPS_PRECISION + 1), "InvalidParameter");
+}
+
+#[test]
+fn withdrawals_are_limited_to_the_position() {
+    let (mut svm, fixture) = healthy_with_savings();
+    fixture.deposit_savings(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    let shares = shares_of(&svm, &fixture, MINNOW);
+    assert_fails_with(fixture.withdraw_savings(&mut svm, MINNOW, shares + 1), "InsufficientSavingsDeposit");
+}
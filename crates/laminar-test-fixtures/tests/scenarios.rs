@@ -0,0 +1,87 @@
+use laminar::constants::{MIN_AMUSD_MINT, MIN_ASOL_MINT};
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol, BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::scenarios::{
+    DUST_AMUSD, DUST_ASOL, DUST_BOTH, HEALTHY_CR_BPS, INSOLVENT_CR_BPS, MINNOW, NEAR_MIN_CR_BUFFER_BPS, WHALE,
+};
+use laminar_test_fixtures::{build, new_svm, Fixture, Scenario};
+use litesvm::LiteSVM;
+
+fn fixture(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{scenario:?}: {err}"));
+    (svm, fixture)
+}
+
+fn cr_bps(state: &QuoteState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+#[test]
+fn every_scenario_matches_its_model_state() {
+    for scenario in Scenario::ALL {
+        let (svm, fixture) = fixture(scenario);
+        fixture.assert_matches_chain(&svm).unwrap();
+        assert!(fixture.expected.asol_supply > 0, "{scenario:?}");
+    }
+}
+
+#[test]
+fn healthy_protocol_settles_at_healthy_cr() {
+    let (svm, fixture) = fixture(Scenario::HealthyProtocol);
+    let cr = cr_bps(&fixture.expected);
+    assert!((HEALTHY_CR_BPS..HEALTHY_CR_BPS + 100).contains(&cr), "cr={cr}");
+
+    let (_, whale_amusd, whale_asol) = fixture.balances(&svm, WHALE);
+    let (_, minnow_amusd, minnow_asol) = fixture.balances(&svm, MINNOW);
+    assert!(whale_amusd > minnow_amusd && minnow_amusd > 0);
+    assert!(whale_asol > minnow_asol && minnow_asol > 0);
+}
+
+#[test]
+fn near_min_cr_sits_just_above_min_and_blocks_more_leverage() {
+    let (mut svm, mut fixture) = fixture(Scenario::NearMinCr);
+    let min_cr = fixture.expected.min_cr_bps;
+    let cr = cr_bps(&fixture.expected);
+    assert!(cr >= min_cr + NEAR_MIN_CR_BUFFER_BPS, "cr={cr}");
+    assert!(cr < min_cr + 2 * NEAR_MIN_CR_BUFFER_BPS, "cr={cr}");
+
+    // A deposit large enough to cross min_cr_bps must be rejected on chain.
+    assert!(fixture.mint_amusd(&mut svm, WHALE, 1_000 * SOL_PRECISION).is_err());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn insolvent_protocol_is_below_par_and_rejects_amusd_mints() {
+    let (mut svm, mut fixture) = fixture(Scenario::Insolvent);
+    let cr = cr_bps(&fixture.expected);
+    assert!(cr < BPS_PRECISION, "cr={cr}");
+    assert!(cr.abs_diff(INSOLVENT_CR_BPS) <= 10, "cr={cr}");
+
+    assert!(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).is_err());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn post_haircut_paid_minnow_out_below_par() {
+    let (svm, fixture) = fixture(Scenario::PostHaircut);
+    let (minnow_lst, minnow_amusd, _) = fixture.balances(&svm, MINNOW);
+    assert_eq!(minnow_amusd, 0);
+    assert!(minnow_lst > 0);
+    assert!(cr_bps(&fixture.expected) < BPS_PRECISION);
+    assert!(fixture.balances(&svm, WHALE).1 > 0);
+}
+
+#[test]
+fn dust_holders_hold_sub_minimum_balances() {
+    let (mut svm, mut fixture) = fixture(Scenario::DustHolders);
+    assert_eq!(fixture.balances(&svm, DUST_AMUSD).1, MIN_AMUSD_MINT - 1);
+    assert_eq!(fixture.balances(&svm, DUST_ASOL).2, MIN_ASOL_MINT - 1);
+    assert_eq!(fixture.balances(&svm, DUST_BOTH), (0, 1, 1));
+
+    // Dust short of the whole balance is too small to redeem.
+    assert!(fixture.redeem_amusd(&mut svm, DUST_AMUSD, MIN_AMUSD_MINT - 2).is_err());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,169 @@
+use laminar::invariants::derive_rounding_bound_lamports;
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_tvl_sol, mul_div_down, BPS_PRECISION, MIN_LST_DEPOSIT, SOL_PRECISION};
+use laminar::quote::QuoteState;
+use laminar_test_fixtures::harness::{read_global_state, redeem_asol_ix, send, token_balance, transfer_tokens};
+use laminar_test_fixtures::scenarios::{DUST_AMUSD, DUST_ASOL, DUST_BOTH, MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+/// `scenario` with the treasury's fee tokens handed to WHALE, so every
+/// outstanding token has a wallet that can redeem it.
+fn fixture_in(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{err}"));
+    let whale = fixture.actor(WHALE);
+    for (from, to) in [
+        (fixture.addresses.treasury_amusd_account, whale.amusd_account),
+        (fixture.addresses.treasury_asol_account, whale.asol_account),
+    ] {
+        let amount = token_balance(&svm, &from);
+        if amount > 0 {
+            transfer_tokens(&mut svm, &fixture.authority, &from, &to, amount).unwrap();
+        }
+    }
+    (svm, fixture)
+}
+
+fn cr_bps(state: &QuoteState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+/// LST at par for `amusd_amount`, rounded down, before any haircut
+fn par_sol(state: &QuoteState, amusd_amount: u64) -> u64 {
+    mul_div_down(amusd_amount, SOL_PRECISION, state.sol_price_usd).unwrap()
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn settlement_freezes_the_snapshot_and_closes_minting_for_good() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    fixture.initiate_settlement(&mut svm).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(state.settled && state.mint_paused && !state.redeem_paused);
+    assert_eq!((state.settlement_sol_price_usd, state.settlement_lst_to_sol_rate), (price, rate));
+
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "MintPaused");
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "MintPaused");
+    assert_fails_with(fixture.emergency_pause(&mut svm, false, false), "ProtocolSettled");
+    assert_fails_with(fixture.set_prices(&mut svm, price / 2, rate), "ProtocolSettled");
+    assert_fails_with(fixture.sync_exchange_rate(&mut svm), "ProtocolSettled");
+    assert_fails_with(fixture.initiate_settlement(&mut svm), "ProtocolSettled");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn solvent_settlement_pays_par_and_frozen_nav_and_the_last_redeemer_sweeps() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    fixture.initiate_settlement(&mut svm).unwrap();
+
+    // amUSD: par at the frozen price, no fee.
+    let minnow_amusd = fixture.balances(&svm, MINNOW).1;
+    let par_lst = mul_div_down(par_sol(&fixture.expected, minnow_amusd), SOL_PRECISION, fixture.expected.lst_to_sol_rate).unwrap();
+    let quote = fixture.redeem_amusd(&mut svm, MINNOW, minnow_amusd).unwrap();
+    assert_eq!(quote.fee, 0);
+    assert!(quote.to_user >= par_lst && quote.to_user <= par_lst + 2);
+    let whale_amusd = fixture.balances(&svm, WHALE).1;
+    fixture.redeem_amusd(&mut svm, WHALE, whale_amusd).unwrap();
+
+    // aSOL: frozen NAV, no fee.
+    let minnow_asol = fixture.balances(&svm, MINNOW).2;
+    let quote = fixture.redeem_asol(&mut svm, MINNOW, minnow_asol).unwrap();
+    assert_eq!(quote.fee, 0);
+    assert_eq!(quote.new_supply, fixture.balances(&svm, WHALE).2);
+
+    // The last aSOL out takes everything left, reserve dust included.
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+    let (whale_lst, _, whale_asol) = fixture.balances(&svm, WHALE);
+    let quote = fixture.redeem_asol(&mut svm, WHALE, whale_asol).unwrap();
+    assert_eq!(quote.to_user, vault_before);
+    assert_eq!(fixture.balances(&svm, WHALE).0 - whale_lst, vault_before);
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), 0);
+    assert_eq!((state.total_lst_amount, state.amusd_supply, state.asol_supply), (0, 0, 0));
+    assert_eq!(state.rounding_reserve_lamports, 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn insolvent_settlement_haircuts_amusd_and_the_last_amusd_out_sweeps() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::Insolvent);
+    fixture.initiate_settlement(&mut svm).unwrap();
+    let cr = cr_bps(&fixture.expected);
+    assert!(cr < BPS_PRECISION);
+
+    // aSOL has no claim while amUSD is under water.
+    let minnow = fixture.actor(MINNOW);
+    let asol = fixture.balances(&svm, MINNOW).2;
+    let ix = redeem_asol_ix(&fixture.addresses, &minnow.accounts(), asol, MIN_LST_DEPOSIT);
+    assert_fails_with(send(&mut svm, "redeem_asol", &[ix], &minnow.keypair, &[]), "InsolventProtocol");
+
+    // amUSD takes the haircut pro rata, with no fee on top.
+    let minnow_amusd = fixture.balances(&svm, MINNOW).1;
+    let haircut_sol = mul_div_down(par_sol(&fixture.expected, minnow_amusd), cr, BPS_PRECISION).unwrap();
+    let haircut_lst = mul_div_down(haircut_sol, SOL_PRECISION, fixture.expected.lst_to_sol_rate).unwrap();
+    let quote = fixture.redeem_amusd(&mut svm, MINNOW, minnow_amusd).unwrap();
+    assert_eq!(quote.fee, 0);
+    assert_eq!(quote.to_user, haircut_lst);
+
+    // With aSOL worthless, the last amUSD out takes the rounding dust too.
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+    let whale_amusd = fixture.balances(&svm, WHALE).1;
+    let quote = fixture.redeem_amusd(&mut svm, WHALE, whale_amusd).unwrap();
+    assert_eq!(quote.to_user, vault_before);
+
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), 0);
+    assert_eq!((state.total_lst_amount, state.amusd_supply), (0, 0));
+    assert!(state.asol_supply > 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn claim_settlement_is_closed_until_the_protocol_settles() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::HealthyProtocol);
+    let (_, amusd, asol) = fixture.balances(&svm, MINNOW);
+    assert_fails_with(fixture.claim_settlement(&mut svm, MINNOW, amusd, asol), "ProtocolNotSettled");
+
+    fixture.initiate_settlement(&mut svm).unwrap();
+    assert_fails_with(fixture.claim_settlement(&mut svm, MINNOW, 0, 0), "ZeroAmount");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn mixed_holders_claiming_pro_rata_drain_the_vault() {
+    let (mut svm, mut fixture) = fixture_in(Scenario::DustHolders);
+    fixture.initiate_settlement(&mut svm).unwrap();
+    let bound = derive_rounding_bound_lamports(2, 1, fixture.expected.sol_price_usd).unwrap();
+
+    // Part of each mixed position first, below-minimum dust in between, so
+    // neither tranche's last claim lands on the same holder.
+    let mut received = 0;
+    for (actor, fraction) in [(MINNOW, 2), (DUST_BOTH, 1), (WHALE, 2), (DUST_AMUSD, 1), (DUST_ASOL, 1), (MINNOW, 1), (WHALE, 1)] {
+        let (_, amusd, asol) = fixture.balances(&svm, actor);
+        received += fixture.claim_settlement(&mut svm, actor, amusd / fraction, asol / fraction).unwrap();
+        fixture.assert_matches_chain(&svm).unwrap();
+    }
+
+    for actor in [WHALE, MINNOW, DUST_AMUSD, DUST_ASOL, DUST_BOTH] {
+        let (_, amusd, asol) = fixture.balances(&svm, actor);
+        assert_eq!((amusd, asol), (0, 0), "{actor} still holds tokens");
+    }
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!((state.amusd_supply, state.asol_supply), (0, 0));
+    assert!(token_balance(&svm, &fixture.addresses.vault) <= bound);
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault), state.total_lst_amount);
+    assert!(received > 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
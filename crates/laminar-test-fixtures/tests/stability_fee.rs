@@ -0,0 +1,144 @@
+use laminar::constants::{MAX_STABILITY_FEE_BPS_PER_YEAR, SECONDS_PER_YEAR};
+use laminar::math::{compute_stability_fee_accrual, SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::{mint_supply, read_global_state, token_balance};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::sysvar::clock::Clock;
+
+/// 5% a year
+const STABILITY_FEE_BPS_PER_YEAR: u64 = 500;
+const DAY: u64 = 86_400;
+
+fn healthy_with_fee() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    fixture.set_stability_fee(&mut svm, STABILITY_FEE_BPS_PER_YEAR).unwrap();
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn advance_unix_timestamp(svm: &mut LiteSVM, seconds: u64) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += seconds as i64;
+    svm.set_sysvar(&clock);
+}
+
+fn treasury_amusd(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    token_balance(svm, &fixture.addresses.treasury_amusd_account)
+}
+
+#[test]
+fn crank_mints_the_fee_accrued_over_elapsed_time() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    let supply_before = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+    let treasury_before = treasury_amusd(&svm, &fixture);
+
+    advance_unix_timestamp(&mut svm, 30 * DAY);
+    fixture.accrue_stability_fee(&mut svm).unwrap();
+
+    let accrued = compute_stability_fee_accrual(supply_before, STABILITY_FEE_BPS_PER_YEAR, 30 * DAY).unwrap();
+    assert!(accrued > 0);
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.amusd_supply, supply_before + accrued);
+    assert_eq!(treasury_amusd(&svm, &fixture) - treasury_before, accrued);
+    assert_eq!(mint_supply(&svm, &fixture.addresses.amusd_mint), state.amusd_mint_supply().unwrap());
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Nothing more is owed until time passes again.
+    fixture.accrue_stability_fee(&mut svm).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply, supply_before + accrued);
+}
+
+#[test]
+fn accruals_compound_at_call_granularity() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    let supply_start = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+
+    // A year in twelve steps compounds each step's fee into the next step's debt.
+    let step = SECONDS_PER_YEAR / 12;
+    let mut modeled = supply_start;
+    for _ in 0..12 {
+        advance_unix_timestamp(&mut svm, step);
+        fixture.accrue_stability_fee(&mut svm).unwrap();
+        modeled += compute_stability_fee_accrual(modeled, STABILITY_FEE_BPS_PER_YEAR, step).unwrap();
+        assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply, modeled);
+    }
+
+    let simple = supply_start + compute_stability_fee_accrual(supply_start, STABILITY_FEE_BPS_PER_YEAR, 12 * step).unwrap();
+    assert!(modeled > simple);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn mint_and_redeem_accrue_before_pricing() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    let supply_before = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+
+    advance_unix_timestamp(&mut svm, 7 * DAY);
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    let treasury_before = treasury_amusd(&svm, &fixture);
+    let quote = fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // The week's fee was folded in before the mint was priced against it.
+    let accrued = compute_stability_fee_accrual(supply_before, STABILITY_FEE_BPS_PER_YEAR, 7 * DAY).unwrap();
+    assert_eq!(treasury_amusd(&svm, &fixture) - treasury_before, accrued + quote.fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    advance_unix_timestamp(&mut svm, DAY);
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    let supply_before = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+    let treasury_before = treasury_amusd(&svm, &fixture);
+    let quote = fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    let accrued = compute_stability_fee_accrual(supply_before, STABILITY_FEE_BPS_PER_YEAR, DAY).unwrap();
+    assert_eq!(treasury_amusd(&svm, &fixture) - treasury_before, accrued + quote.fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn a_new_rate_applies_only_from_when_it_is_set() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    let supply_before = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+
+    advance_unix_timestamp(&mut svm, 10 * DAY);
+    fixture.set_stability_fee(&mut svm, 0).unwrap();
+    let accrued = compute_stability_fee_accrual(supply_before, STABILITY_FEE_BPS_PER_YEAR, 10 * DAY).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply, supply_before + accrued);
+
+    // With the fee off, time accrues nothing, and re-enabling it is not retroactive.
+    advance_unix_timestamp(&mut svm, 10 * DAY);
+    fixture.accrue_stability_fee(&mut svm).unwrap();
+    fixture.set_stability_fee(&mut svm, STABILITY_FEE_BPS_PER_YEAR).unwrap();
+    fixture.accrue_stability_fee(&mut svm).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply, supply_before + accrued);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn rate_is_capped() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    assert_fails_with(
+        fixture.set_stability_fee(&mut svm, MAX_STABILITY_FEE_BPS_PER_YEAR + 1),
+        "InvalidParameter",
+    );
+    fixture.set_stability_fee(&mut svm, MAX_STABILITY_FEE_BPS_PER_YEAR).unwrap();
+}
+
+#[test]
+fn settled_book_stops_accruing() {
+    let (mut svm, mut fixture) = healthy_with_fee();
+    fixture.initiate_settlement(&mut svm).unwrap();
+    let supply_before = read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply;
+
+    advance_unix_timestamp(&mut svm, 30 * DAY);
+    fixture.accrue_stability_fee(&mut svm).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().amusd_supply, supply_before);
+}
@@ -0,0 +1,274 @@
+use laminar::math::{compute_cr_bps, compute_liability_sol, compute_stability_share_value, compute_tvl_sol, USD_PRECISION};
+use laminar::state::GlobalState;
+use laminar_test_fixtures::harness::{
+    read_global_state, read_stability_deposit, read_stability_pool, stability_epoch_record_address, token_balance,
+    transfer_tokens,
+};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+/// `scenario` with an empty Stability Pool.
+fn pool_fixture_in(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{err}"));
+    fixture.init_stability_pool(&mut svm).unwrap();
+    (svm, fixture)
+}
+
+fn pool_fixture() -> (LiteSVM, Fixture) {
+    pool_fixture_in(Scenario::HealthyProtocol)
+}
+
+fn cr_bps(state: &GlobalState) -> u64 {
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.mock_lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.mock_sol_price_usd).unwrap();
+    compute_cr_bps(tvl, liability)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn depositors_receive_shares_pro_rata() {
+    let (mut svm, fixture) = pool_fixture();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let minnow = fixture.actor(MINNOW).keypair.pubkey();
+    let (_, whale_amusd, _) = fixture.balances(&svm, WHALE);
+
+    fixture.deposit_stability(&mut svm, WHALE, 30 * USD_PRECISION).unwrap();
+    fixture.deposit_stability(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    fixture.deposit_stability(&mut svm, WHALE, 10 * USD_PRECISION).unwrap();
+
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    let whale_deposit = read_stability_deposit(&svm, &whale).unwrap();
+    let minnow_deposit = read_stability_deposit(&svm, &minnow).unwrap();
+
+    assert_eq!(pool.total_pool_amusd, 50 * USD_PRECISION);
+    assert_eq!(pool.total_shares, whale_deposit.shares + minnow_deposit.shares);
+    assert_eq!(whale_deposit.owner, whale);
+    assert_eq!(whale_deposit.shares, 4 * minnow_deposit.shares);
+    assert_eq!(
+        compute_stability_share_value(minnow_deposit.shares, pool.total_pool_amusd, pool.total_shares),
+        Some(10 * USD_PRECISION)
+    );
+
+    // The amUSD moved into the pool vault; supply (the liability) is unchanged.
+    assert_eq!(token_balance(&svm, &fixture.addresses.stability_pool_amusd_vault), pool.total_pool_amusd);
+    assert_eq!(fixture.balances(&svm, WHALE).1, whale_amusd - 40 * USD_PRECISION);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn deposit_requires_a_positive_covered_amount() {
+    let (mut svm, fixture) = pool_fixture();
+    let (_, minnow_amusd, _) = fixture.balances(&svm, MINNOW);
+
+    assert_fails_with(fixture.deposit_stability(&mut svm, MINNOW, 0), "ZeroAmount");
+    assert_fails_with(fixture.deposit_stability(&mut svm, MINNOW, minnow_amusd + 1), "InsufficientSupply");
+    assert_eq!(read_stability_pool(&svm, &fixture.addresses).unwrap().total_shares, 0);
+}
+
+#[test]
+fn pool_can_only_be_initialized_once() {
+    let (mut svm, fixture) = pool_fixture();
+    assert!(fixture.init_stability_pool(&mut svm).is_err());
+}
+
+#[test]
+fn withdrawals_burn_shares_and_return_amusd() {
+    let (mut svm, fixture) = pool_fixture();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let minnow = fixture.actor(MINNOW).keypair.pubkey();
+    let (_, whale_amusd, _) = fixture.balances(&svm, WHALE);
+    let (_, minnow_amusd, _) = fixture.balances(&svm, MINNOW);
+
+    fixture.deposit_stability(&mut svm, WHALE, 30 * USD_PRECISION).unwrap();
+    fixture.deposit_stability(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+
+    fixture.withdraw_stability(&mut svm, WHALE, 12 * USD_PRECISION).unwrap();
+    fixture.withdraw_stability(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert_eq!(pool.total_pool_amusd, 18 * USD_PRECISION);
+    assert_eq!(read_stability_deposit(&svm, &whale).unwrap().shares, pool.total_shares);
+    assert_eq!(read_stability_deposit(&svm, &minnow).unwrap().shares, 0);
+    assert_eq!(token_balance(&svm, &fixture.addresses.stability_pool_amusd_vault), pool.total_pool_amusd);
+    assert_eq!(fixture.balances(&svm, WHALE).1, whale_amusd - 18 * USD_PRECISION);
+    assert_eq!(fixture.balances(&svm, MINNOW).1, minnow_amusd);
+
+    // No drawdown has happened, so there are no LST gains to pay.
+    assert_eq!(pool.total_pool_lst, 0);
+    assert_fails_with(fixture.withdraw_stability(&mut svm, MINNOW, 1), "InsufficientStabilityDeposit");
+    assert_fails_with(fixture.withdraw_stability(&mut svm, WHALE, 18 * USD_PRECISION + 1), "InsufficientStabilityDeposit");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn donations_to_the_pool_vault_do_not_block_the_pool() {
+    let (mut svm, fixture) = pool_fixture();
+    let whale = fixture.actor(WHALE);
+    let minnow = fixture.actor(MINNOW);
+    let vault = fixture.addresses.stability_pool_amusd_vault;
+
+    fixture.deposit_stability(&mut svm, WHALE, 30 * USD_PRECISION).unwrap();
+    transfer_tokens(&mut svm, &minnow.keypair, &minnow.amusd_account, &vault, USD_PRECISION).unwrap();
+
+    // The donation is not credited to anyone's shares.
+    fixture.deposit_stability(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    fixture.withdraw_stability(&mut svm, WHALE, 30 * USD_PRECISION).unwrap();
+
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert_eq!(pool.total_pool_amusd, 10 * USD_PRECISION);
+    assert_eq!(read_stability_deposit(&svm, &whale.keypair.pubkey()).unwrap().shares, 0);
+    assert_eq!(token_balance(&svm, &vault), pool.total_pool_amusd + USD_PRECISION);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn withdrawals_close_while_cr_is_below_min() {
+    let (mut svm, mut fixture) = pool_fixture_in(Scenario::NearMinCr);
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+
+    fixture.deposit_stability(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+
+    fixture.set_prices(&mut svm, price * 8 / 10, rate).unwrap();
+    assert_fails_with(
+        fixture.withdraw_stability(&mut svm, WHALE, 100 * USD_PRECISION),
+        "StabilityWithdrawalBelowMinCr",
+    );
+
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.withdraw_stability(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+}
+
+#[test]
+fn drawdown_runs_before_any_redemption_below_min_cr() {
+    let (mut svm, mut fixture) = pool_fixture_in(Scenario::NearMinCr);
+    let (_, whale_amusd, _) = fixture.balances(&svm, WHALE);
+    fixture.deposit_stability(&mut svm, WHALE, whale_amusd / 2).unwrap();
+
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 95 / 100, rate).unwrap();
+    let before = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert!(cr_bps(&before) < before.min_cr_bps);
+
+    assert_fails_with(fixture.redeem_amusd(&mut svm, MINNOW, USD_PRECISION), "StabilityDrawdownPending");
+
+    fixture.trigger_drawdown(&mut svm).unwrap();
+    let after = read_global_state(&svm, &fixture.addresses).unwrap();
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert!(cr_bps(&after) >= after.min_cr_bps);
+    assert!(pool.total_pool_amusd > 0 && pool.total_pool_amusd < whale_amusd / 2);
+    assert_eq!(after.stability_pool_amusd, pool.total_pool_amusd);
+    assert_eq!(before.amusd_supply - after.amusd_supply, whale_amusd / 2 - pool.total_pool_amusd);
+    assert_eq!(token_balance(&svm, &fixture.addresses.stability_pool_lst_vault), before.total_lst_amount - after.total_lst_amount);
+    assert_eq!(pool.epoch, 0);
+
+    // Nothing left to absorb; the remainder redeems normally.
+    assert_fails_with(fixture.trigger_drawdown(&mut svm), "StabilityDrawdownNotNeeded");
+    fixture.redeem_amusd(&mut svm, MINNOW, USD_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn settlement_closes_the_pool_to_deposits_and_drawdowns() {
+    let (mut svm, mut fixture) = pool_fixture_in(Scenario::NearMinCr);
+    let (_, whale_amusd, _) = fixture.balances(&svm, WHALE);
+    fixture.deposit_stability(&mut svm, WHALE, whale_amusd / 2).unwrap();
+
+    // Settle below min CR, where a drawdown would otherwise be due.
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 95 / 100, rate).unwrap();
+    fixture.initiate_settlement(&mut svm).unwrap();
+    let before = read_stability_pool(&svm, &fixture.addresses).unwrap();
+
+    assert_fails_with(fixture.deposit_stability(&mut svm, MINNOW, USD_PRECISION), "ProtocolSettled");
+    assert_fails_with(fixture.trigger_drawdown(&mut svm), "ProtocolSettled");
+
+    let after = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert_eq!(after.total_pool_amusd, before.total_pool_amusd);
+    assert_eq!(after.total_shares, before.total_shares);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn drawdown_that_empties_the_pool_closes_the_epoch() {
+    let (mut svm, mut fixture) = pool_fixture_in(Scenario::NearMinCr);
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    fixture.deposit_stability(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 7 / 10, rate).unwrap();
+    fixture.trigger_drawdown(&mut svm).unwrap();
+
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert_eq!((pool.total_pool_amusd, pool.total_shares, pool.epoch), (0, 0, 1));
+    assert!(pool.total_pool_lst > 0);
+    assert!(svm.get_account(&stability_epoch_record_address(0)).is_some());
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().stability_pool_amusd, 0);
+
+    // With the pool empty, redemptions proceed through the haircut path.
+    fixture.redeem_amusd(&mut svm, MINNOW, USD_PRECISION).unwrap();
+
+    // The old position is worthless in amUSD; a new deposit settles its
+    // gains against the epoch-0 record and moves it into epoch 1.
+    fixture.deposit_stability(&mut svm, WHALE, 10 * USD_PRECISION).unwrap();
+    let deposit = read_stability_deposit(&svm, &whale).unwrap();
+    assert_eq!(deposit.epoch, 1);
+    assert_eq!(deposit.shares, 10 * USD_PRECISION);
+    assert!(deposit.pending_lst_gain > 0 && deposit.pending_lst_gain <= pool.total_pool_lst);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn drawdown_gains_are_claimed_pro_rata() {
+    let (mut svm, mut fixture) = pool_fixture_in(Scenario::NearMinCr);
+    let (_, whale_amusd, _) = fixture.balances(&svm, WHALE);
+    let (_, minnow_amusd, _) = fixture.balances(&svm, MINNOW);
+
+    // Whale holds three times the minnow's shares.
+    let minnow_stake = minnow_amusd.min(whale_amusd / 8);
+    fixture.deposit_stability(&mut svm, MINNOW, minnow_stake).unwrap();
+    fixture.deposit_stability(&mut svm, WHALE, 3 * minnow_stake).unwrap();
+
+    let price = fixture.expected.sol_price_usd;
+    let rate = fixture.expected.lst_to_sol_rate;
+    fixture.set_prices(&mut svm, price * 98 / 100, rate).unwrap();
+    fixture.trigger_drawdown(&mut svm).unwrap();
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+
+    let pool = read_stability_pool(&svm, &fixture.addresses).unwrap();
+    assert!(pool.total_pool_lst > 0 && pool.total_pool_amusd > 0);
+
+    let (whale_lst, whale_amusd_before, _) = fixture.balances(&svm, WHALE);
+    let (minnow_lst, _, _) = fixture.balances(&svm, MINNOW);
+    fixture.claim_stability_gains(&mut svm, WHALE).unwrap();
+    fixture.claim_stability_gains(&mut svm, MINNOW).unwrap();
+    let whale_gain = fixture.balances(&svm, WHALE).0 - whale_lst;
+    let minnow_gain = fixture.balances(&svm, MINNOW).0 - minnow_lst;
+
+    assert!(minnow_gain > 0);
+    assert!(whale_gain.abs_diff(3 * minnow_gain) <= 3, "whale {whale_gain} vs minnow {minnow_gain}");
+    assert!(whale_gain + minnow_gain <= pool.total_pool_lst);
+    assert_eq!(
+        token_balance(&svm, &fixture.addresses.stability_pool_lst_vault),
+        pool.total_pool_lst - whale_gain - minnow_gain
+    );
+
+    // Claiming leaves the amUSD position alone and cannot be repeated.
+    assert_eq!(fixture.balances(&svm, WHALE).1, whale_amusd_before);
+    assert_eq!(read_stability_pool(&svm, &fixture.addresses).unwrap().total_pool_amusd, pool.total_pool_amusd);
+    assert_fails_with(fixture.claim_stability_gains(&mut svm, WHALE), "NoStabilityGains");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
@@ -0,0 +1,268 @@
+use laminar::constants::DEFAULT_MAX_ORACLE_STALENESS_SLOTS;
+use laminar::math::{BPS_PRECISION, SOL_PRECISION};
+use laminar::quote::{quote_redeem_amusd, quote_redeem_asol};
+use laminar_test_fixtures::harness::{
+    current_slot, read_global_state, redeem_amusd_to_sol_ix, redeem_amusd_unstake_ix, send, token_balance, zap_mint_amusd_ix,
+    zap_mint_amusd_stake_ix, StakePoolExit,
+};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::stake_pool::{
+    load_mock_stake_pool, open_stake_pool_deposits, set_epoch, stake_pool_header, write_spl_stake_pool, write_stake_pool,
+    write_withdrawable_stake_pool,
+};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+const EPOCH: u64 = 700;
+
+/// SOL withdrawal fee the mock pool charges on every unstake
+const UNSTAKE_FEE_BPS: u64 = 30;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    set_epoch(&mut svm, EPOCH);
+    (svm, fixture)
+}
+
+/// Healthy fixture priced off a mock pool at the fixture's 1.05 rate that
+/// charges `UNSTAKE_FEE_BPS` to unstake.
+fn withdrawable() -> (LiteSVM, Fixture, StakePoolExit) {
+    let (mut svm, mut fixture) = healthy();
+    load_mock_stake_pool(&mut svm).unwrap_or_else(|err| panic!("{err}"));
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_050 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    let exit = write_withdrawable_stake_pool(&mut svm, &fixture.authority, &pool, header, UNSTAKE_FEE_BPS).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+    (svm, fixture, exit)
+}
+
+/// Lamports the mock pool pays for `lst`: its fee is kept in pool tokens and
+/// the rest is burned at 1.05.
+fn unstaked(lst: u64) -> u64 {
+    let burned = lst - lst * UNSTAKE_FEE_BPS / BPS_PRECISION;
+    burned * 1_050 / 1_000
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn sync_writes_the_pool_rate_into_global_state() {
+    let (mut svm, mut fixture) = healthy();
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.mock_lst_to_sol_rate, 1_100_000_000);
+    assert_eq!(state.last_tvl_update_slot, current_slot(&svm));
+
+    // Pricing follows the pool rate (min_out pinned to the quote at 1.1).
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Rewards accrue on the next sync.
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_111 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    fixture.sync_exchange_rate(&mut svm).unwrap();
+    assert_eq!(fixture.expected.lst_to_sol_rate, 1_111_000_000);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn pool_lagging_more_than_max_lst_stale_epochs_is_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let pool = Pubkey::new_unique();
+    let stale = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH - 2);
+    write_spl_stake_pool(&mut svm, &pool, &stale).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, pool), "LstRateStale");
+
+    // One epoch behind is within the default bound.
+    let lagging = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH - 1);
+    write_spl_stake_pool(&mut svm, &pool, &lagging).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+}
+
+#[test]
+fn spoofed_or_mismatched_pools_are_rejected() {
+    let (mut svm, mut fixture) = healthy();
+    let pool = Pubkey::new_unique();
+
+    // Pool for a different LST
+    let wrong_mint = stake_pool_header(Pubkey::new_unique(), 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &wrong_mint).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, pool), "InvalidStakePool");
+
+    // Right layout, wrong owner program
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_stake_pool(&mut svm, &pool, &header, Pubkey::new_unique()).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, pool), "InvalidStakePool");
+
+    // Empty pool
+    let empty = stake_pool_header(fixture.addresses.lst_mint, 0, 0, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &empty).unwrap();
+    assert_fails_with(fixture.set_lst_stake_pool(&mut svm, pool), "InvalidStakePool");
+
+    // Configured pool, but a different account passed to sync
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+    let impostor = Pubkey::new_unique();
+    write_spl_stake_pool(&mut svm, &impostor, &header).unwrap();
+    fixture.addresses.stake_pool = Some(impostor);
+    assert_fails_with(fixture.sync_exchange_rate(&mut svm), "InvalidStakePool");
+}
+
+#[test]
+fn user_paths_do_not_extend_pool_rate_freshness() {
+    let (mut svm, mut fixture) = healthy();
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+    let synced_at = current_slot(&svm);
+
+    // Keep the SOL/USD snapshot fresh independently of the LST rate.
+    let (price, rate) = (fixture.expected.sol_price_usd, fixture.expected.lst_to_sol_rate);
+    svm.warp_to_slot(synced_at + DEFAULT_MAX_ORACLE_STALENESS_SLOTS);
+    fixture.set_prices(&mut svm, price, rate).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    svm.warp_to_slot(synced_at + DEFAULT_MAX_ORACLE_STALENESS_SLOTS + 1);
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "LstRateStale");
+
+    fixture.sync_exchange_rate(&mut svm).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn admin_cannot_override_a_live_pool_rate() {
+    let (mut svm, mut fixture) = healthy();
+    let pool = Pubkey::new_unique();
+    let header = stake_pool_header(fixture.addresses.lst_mint, 1_100 * SOL_PRECISION, 1_000 * SOL_PRECISION, EPOCH);
+    write_spl_stake_pool(&mut svm, &pool, &header).unwrap();
+    fixture.set_lst_stake_pool(&mut svm, pool).unwrap();
+
+    let price = fixture.expected.sol_price_usd;
+    assert_fails_with(fixture.set_prices(&mut svm, price, 1_200_000_000), "InvalidParameter");
+
+    // Switching back to the mock hands the rate to the admin again.
+    fixture.set_lst_stake_pool(&mut svm, Pubkey::default()).unwrap();
+    fixture.set_prices(&mut svm, price, 1_200_000_000).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn unstake_to_sol_holds_min_sol_out_to_what_the_pool_paid_after_its_fee() {
+    let (mut svm, mut fixture, exit) = withdrawable();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let minted = fixture.mint_amusd(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    let quote = quote_redeem_amusd(&fixture.expected, minted.to_user).unwrap();
+    let sol_out = unstaked(quote.to_user);
+    assert!(sol_out < quote.to_user * 1_050 / 1_000);
+
+    // A floor the LST clears at par but the fee pushes out of reach.
+    let ix = redeem_amusd_unstake_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), &exit, minted.to_user, sol_out + 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], &fixture.actor(WHALE).keypair, &[]), "SlippageExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    let lst_before = fixture.balances(&svm, WHALE).0;
+    let sol_before = svm.get_balance(&whale).unwrap();
+    fixture.redeem_amusd_unstake(&mut svm, WHALE, &exit, minted.to_user, sol_out).unwrap();
+
+    // All the LST paid out was unstaked; only the transaction fee is off the SOL.
+    assert_eq!(fixture.balances(&svm, WHALE).0, lst_before);
+    let gained = svm.get_balance(&whale).unwrap() - sol_before;
+    assert!(gained <= sol_out && sol_out - gained <= 5_000);
+    assert_eq!(token_balance(&svm, &exit.manager_fee_account), quote.to_user * UNSTAKE_FEE_BPS / BPS_PRECISION);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_unstakes_to_sol_through_the_pool() {
+    let (mut svm, mut fixture, exit) = withdrawable();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let minted = fixture.mint_asol(&mut svm, WHALE, 5 * SOL_PRECISION).unwrap();
+    let quote = quote_redeem_asol(&fixture.expected, minted.to_user).unwrap();
+    let sol_before = svm.get_balance(&whale).unwrap();
+
+    fixture.redeem_asol_unstake(&mut svm, WHALE, &exit, minted.to_user, unstaked(quote.to_user)).unwrap();
+    assert!(svm.get_balance(&whale).unwrap() - sol_before + 5_000 >= unstaked(quote.to_user));
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn unstake_to_sol_needs_the_configured_pool_accounts() {
+    let (mut svm, mut fixture, exit) = withdrawable();
+    let minted = fixture.mint_amusd(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    let accounts = fixture.actor(WHALE).accounts();
+    let keypair = &fixture.actor(WHALE).keypair;
+
+    // No pool accounts at all
+    let ix = redeem_amusd_to_sol_ix(&fixture.addresses, &accounts, minted.to_user, 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], keypair, &[]), "InvalidStakePool");
+
+    // A reserve the pool does not name
+    let spoofed = StakePoolExit { reserve_stake: Pubkey::new_unique(), ..exit };
+    let ix = redeem_amusd_unstake_ix(&fixture.addresses, &accounts, &spoofed, minted.to_user, 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], keypair, &[]), "InvalidStakePool");
+
+    // Some other pool than the configured one
+    let impostor = StakePoolExit { stake_pool: Pubkey::new_unique(), ..exit };
+    let ix = redeem_amusd_unstake_ix(&fixture.addresses, &accounts, &impostor, minted.to_user, 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], keypair, &[]), "InvalidStakePool");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn sol_zaps_stake_into_the_primary_lst_through_the_pool() {
+    let (mut svm, mut fixture, entry) = withdrawable();
+    open_stake_pool_deposits(&mut svm, &fixture.authority, &fixture.addresses.lst_mint, &entry).unwrap();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let lst_before = fixture.balances(&svm, WHALE).0;
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+    let reserve_before = svm.get_balance(&entry.reserve_stake).unwrap();
+    let sol_before = svm.get_balance(&whale).unwrap();
+
+    // 21 SOL stakes for 20 LST at 1.05, all of it deposited as collateral.
+    fixture.zap_mint_amusd_stake(&mut svm, WHALE, &entry, 21 * SOL_PRECISION).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault) - vault_before, 20 * SOL_PRECISION);
+    assert_eq!(svm.get_balance(&entry.reserve_stake).unwrap() - reserve_before, 21 * SOL_PRECISION);
+    assert_eq!(fixture.balances(&svm, WHALE).0, lst_before);
+    let spent = sol_before - svm.get_balance(&whale).unwrap();
+    assert!(spent >= 21 * SOL_PRECISION && spent - 21 * SOL_PRECISION <= 5_000);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    fixture.zap_mint_asol_stake(&mut svm, WHALE, &entry, 5 * SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zap_into_the_primary_lst_needs_the_configured_pool_accounts() {
+    let (mut svm, fixture, entry) = withdrawable();
+    open_stake_pool_deposits(&mut svm, &fixture.authority, &fixture.addresses.lst_mint, &entry).unwrap();
+    let accounts = fixture.actor(WHALE).accounts();
+    let keypair = &fixture.actor(WHALE).keypair;
+
+    // No pool accounts at all
+    let ix = zap_mint_amusd_ix(&fixture.addresses, &accounts, SOL_PRECISION, 0);
+    assert_fails_with(send(&mut svm, "zap_mint_amusd", &[ix], keypair, &[]), "InvalidStakePool");
+
+    // A reserve the pool does not name
+    let spoofed = StakePoolExit { reserve_stake: Pubkey::new_unique(), ..entry };
+    let ix = zap_mint_amusd_stake_ix(&fixture.addresses, &accounts, &spoofed, SOL_PRECISION, 0);
+    assert_fails_with(send(&mut svm, "zap_mint_amusd", &[ix], keypair, &[]), "InvalidStakePool");
+
+    // Some other pool than the configured one
+    let impostor = StakePoolExit { stake_pool: Pubkey::new_unique(), ..entry };
+    let ix = zap_mint_amusd_stake_ix(&fixture.addresses, &accounts, &impostor, SOL_PRECISION, 0);
+    assert_fails_with(send(&mut svm, "zap_mint_amusd", &[ix], keypair, &[]), "InvalidStakePool");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,126 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar::quote::{quote_mint_amusd, quote_mint_asol};
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn fixture(scenario: Scenario) -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, scenario).unwrap_or_else(|err| panic!("{scenario:?}: {err}"));
+    (svm, fixture)
+}
+
+fn healthy() -> (LiteSVM, Fixture) {
+    fixture(Scenario::HealthyProtocol)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn amusd_supply(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().amusd_supply
+}
+
+fn asol_supply(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().asol_supply
+}
+
+#[test]
+fn mint_may_land_exactly_on_the_cap() {
+    let (mut svm, mut fixture) = healthy();
+    let quote = quote_mint_amusd(&fixture.expected, SOL_PRECISION).unwrap();
+
+    // One unit short of room, including the treasury fee, is refused outright.
+    fixture.set_supply_caps(&mut svm, quote.new_supply - 1, 0).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "DebtCeilingExceeded");
+
+    fixture.set_supply_caps(&mut svm, quote.new_supply, 0).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_eq!(amusd_supply(&svm, &fixture), quote.new_supply);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // With no headroom left even the smallest mint is over.
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, 1_000), "DebtCeilingExceeded");
+}
+
+#[test]
+fn redemptions_ignore_the_cap() {
+    let (mut svm, mut fixture) = healthy();
+    let supply = amusd_supply(&svm, &fixture);
+
+    // A cap under the live supply stops mints but never traps holders.
+    fixture.set_supply_caps(&mut svm, supply / 2, 0).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.redeem_amusd(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    assert_eq!(amusd_supply(&svm, &fixture), supply - 110 * USD_PRECISION);
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "DebtCeilingExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_cap_is_unlimited() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_supply_caps(&mut svm, 1, 0).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION), "DebtCeilingExceeded");
+
+    fixture.set_supply_caps(&mut svm, 0, 0).unwrap();
+    fixture.mint_amusd(&mut svm, WHALE, 50 * SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_mint_may_land_exactly_on_the_cap() {
+    let (mut svm, mut fixture) = healthy();
+    let quote = quote_mint_asol(&fixture.expected, 10 * SOL_PRECISION).unwrap();
+
+    fixture.set_supply_caps(&mut svm, 0, quote.new_supply - 1).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, 10 * SOL_PRECISION), "AsolSupplyCapExceeded");
+
+    fixture.set_supply_caps(&mut svm, 0, quote.new_supply).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, 10 * SOL_PRECISION).unwrap();
+    assert_eq!(asol_supply(&svm, &fixture), quote.new_supply);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Redemptions are never held to the cap.
+    fixture.set_supply_caps(&mut svm, 0, 1).unwrap();
+    fixture.redeem_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_treasury_fee_counts_toward_the_cap() {
+    let (mut svm, mut fixture) = healthy();
+    let supply = asol_supply(&svm, &fixture);
+    let quote = quote_mint_asol(&fixture.expected, 10 * SOL_PRECISION).unwrap();
+    assert!(quote.fee > 0);
+
+    // Room for the user's aSOL alone is not enough.
+    fixture.set_supply_caps(&mut svm, 0, supply + quote.to_user).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, 10 * SOL_PRECISION), "AsolSupplyCapExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn bootstrap_mint_is_held_to_the_asol_cap() {
+    let (mut svm, mut fixture) = fixture(Scenario::Fresh);
+    assert_eq!(fixture.expected.asol_supply, 0);
+
+    // The first mint prices at 1 aSOL per SOL; its gross still counts in full.
+    let quote = quote_mint_asol(&fixture.expected, 100 * SOL_PRECISION).unwrap();
+    fixture.set_supply_caps(&mut svm, 0, quote.new_supply - 1).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, 100 * SOL_PRECISION), "AsolSupplyCapExceeded");
+    assert_eq!(asol_supply(&svm, &fixture), 0);
+
+    fixture.set_supply_caps(&mut svm, 0, quote.new_supply).unwrap();
+    fixture.mint_asol(&mut svm, WHALE, 100 * SOL_PRECISION).unwrap();
+    assert_eq!(asol_supply(&svm, &fixture), quote.new_supply);
+
+    // The cap now binds the ordinary NAV-priced path as well.
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "AsolSupplyCapExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
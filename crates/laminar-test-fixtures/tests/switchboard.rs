@@ -0,0 +1,107 @@
+use laminar::constants::{DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_ORACLE_STALENESS_SLOTS};
+use laminar::math::{BPS_PRECISION, SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::current_slot;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::switchboard::{write_pull_feed, SwitchboardPrice};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+/// Healthy fixture priced from a fresh Switchboard feed at the mock price.
+fn switchboard_fixture() -> (LiteSVM, Fixture, Pubkey) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+
+    let feed = Pubkey::new_unique();
+    let price = SwitchboardPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_pull_feed(&mut svm, &feed, &price).unwrap();
+    fixture.set_switchboard_feed(&mut svm, feed).unwrap();
+    (svm, fixture, feed)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn fresh_switchboard_feed_prices_all_four_instructions() {
+    let (mut svm, mut fixture, _) = switchboard_fixture();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 100 * USD_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn switchboard_value_replaces_the_mock_price() {
+    let (mut svm, mut fixture, feed) = switchboard_fixture();
+    fixture.expected.sol_price_usd = fixture.expected.sol_price_usd * 11 / 10;
+    let price = SwitchboardPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm));
+    write_pull_feed(&mut svm, &feed, &price).unwrap();
+
+    // min_out is pinned to the quote at the feed price.
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+}
+
+#[test]
+fn stale_switchboard_result_is_rejected() {
+    let (mut svm, mut fixture, feed) = switchboard_fixture();
+    let slot = current_slot(&svm) + DEFAULT_MAX_ORACLE_STALENESS_SLOTS + 1;
+    svm.warp_to_slot(slot);
+    fixture.set_prices(&mut svm, fixture.expected.sol_price_usd, fixture.expected.lst_to_sol_rate).unwrap();
+
+    let stale = SwitchboardPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, slot - DEFAULT_MAX_ORACLE_STALENESS_SLOTS - 1);
+    write_pull_feed(&mut svm, &feed, &stale).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "OraclePriceStale");
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, USD_PRECISION), "OraclePriceStale");
+
+    let edge = SwitchboardPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, slot - DEFAULT_MAX_ORACLE_STALENESS_SLOTS);
+    write_pull_feed(&mut svm, &feed, &edge).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn high_variance_switchboard_result_is_rejected() {
+    let (mut svm, mut fixture, feed) = switchboard_fixture();
+    let price_usd = fixture.expected.sol_price_usd;
+    let max_std_dev_usd = price_usd * DEFAULT_MAX_CONF_BPS / BPS_PRECISION;
+
+    let noisy = SwitchboardPrice::from_micro_usd(price_usd, max_std_dev_usd + 1, current_slot(&svm));
+    write_pull_feed(&mut svm, &feed, &noisy).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "OracleConfidenceTooHigh");
+    assert_fails_with(fixture.redeem_asol(&mut svm, WHALE, SOL_PRECISION), "OracleConfidenceTooHigh");
+
+    let at_bound = SwitchboardPrice::from_micro_usd(price_usd, max_std_dev_usd, current_slot(&svm));
+    write_pull_feed(&mut svm, &feed, &at_bound).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+}
+
+#[test]
+fn empty_or_missing_switchboard_feed_is_rejected() {
+    let (mut svm, mut fixture, feed) = switchboard_fixture();
+
+    let no_quorum = SwitchboardPrice { num_samples: 0, ..SwitchboardPrice::from_micro_usd(fixture.expected.sol_price_usd, 0, current_slot(&svm)) };
+    write_pull_feed(&mut svm, &feed, &no_quorum).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+
+    fixture.addresses.switchboard_feed = None;
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "InvalidPriceFeed");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn configuring_pyth_keeps_switchboard_selected() {
+    let (mut svm, mut fixture, _) = switchboard_fixture();
+    // Only the mock source is replaced by a newly configured feed.
+    fixture.set_pyth_price_feed(&mut svm, Pubkey::new_unique()).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+
+    // Clearing the live feed cannot fall back to the mock past a configured Pyth feed.
+    assert_fails_with(fixture.set_switchboard_feed(&mut svm, Pubkey::default()), "InvalidParameter");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,80 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+const MAX_DEPOSIT_LST: u64 = 5 * SOL_PRECISION;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn total_lst_amount(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().total_lst_amount
+}
+
+#[test]
+fn deposit_limit_is_inclusive_on_both_mints() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_transaction_limits(&mut svm, MAX_DEPOSIT_LST, 0).unwrap();
+
+    assert_fails_with(fixture.mint_amusd(&mut svm, WHALE, MAX_DEPOSIT_LST + 1), "DepositTooLarge");
+    fixture.mint_amusd(&mut svm, WHALE, MAX_DEPOSIT_LST).unwrap();
+
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, MAX_DEPOSIT_LST + 1), "DepositTooLarge");
+    fixture.mint_asol(&mut svm, WHALE, MAX_DEPOSIT_LST).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn oversized_deposit_must_be_split_across_transactions() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_transaction_limits(&mut svm, MAX_DEPOSIT_LST, 0).unwrap();
+    let lst_before = total_lst_amount(&svm, &fixture);
+
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, 2 * MAX_DEPOSIT_LST), "DepositTooLarge");
+    assert_eq!(total_lst_amount(&svm, &fixture), lst_before);
+
+    // The same collateral goes in as two deposits at the limit.
+    fixture.mint_asol(&mut svm, WHALE, MAX_DEPOSIT_LST).unwrap();
+    fixture.mint_asol(&mut svm, WHALE, MAX_DEPOSIT_LST).unwrap();
+    assert_eq!(total_lst_amount(&svm, &fixture), lst_before + 2 * MAX_DEPOSIT_LST);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_limit_is_in_the_redeemed_token() {
+    let (mut svm, mut fixture) = healthy();
+
+    let max_amusd = 50 * USD_PRECISION;
+    fixture.set_transaction_limits(&mut svm, 0, max_amusd).unwrap();
+    assert_fails_with(fixture.redeem_amusd(&mut svm, WHALE, max_amusd + 1), "RedeemTooLarge");
+    fixture.redeem_amusd(&mut svm, WHALE, max_amusd).unwrap();
+
+    let max_asol = SOL_PRECISION / 2;
+    fixture.set_transaction_limits(&mut svm, 0, max_asol).unwrap();
+    assert_fails_with(fixture.redeem_asol(&mut svm, MINNOW, max_asol + 1), "RedeemTooLarge");
+    fixture.redeem_asol(&mut svm, MINNOW, max_asol).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zero_limits_are_unlimited() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_transaction_limits(&mut svm, MAX_DEPOSIT_LST, USD_PRECISION).unwrap();
+    fixture.set_transaction_limits(&mut svm, 0, 0).unwrap();
+
+    fixture.mint_asol(&mut svm, WHALE, 4 * MAX_DEPOSIT_LST).unwrap();
+    fixture.redeem_amusd(&mut svm, WHALE, 500 * USD_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
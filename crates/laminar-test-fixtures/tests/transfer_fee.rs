@@ -0,0 +1,89 @@
+use anchor_spl::token_2022::spl_token_2022;
+use laminar::math::{compute_tvl_sol, SOL_PRECISION};
+use laminar::quote::quote_mint_amusd;
+use laminar_test_fixtures::harness::{mint_amusd_ix, send, token_balance, transfer_fee};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build_with_transfer_fee, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+/// 1% of every LST transfer is withheld
+const TRANSFER_FEE_BPS: u16 = 100;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture =
+        build_with_transfer_fee(&mut svm, Scenario::HealthyProtocol, TRANSFER_FEE_BPS).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn protocol_runs_on_token_2022() {
+    let (svm, fixture) = healthy();
+    for mint in [fixture.addresses.lst_mint, fixture.addresses.amusd_mint, fixture.addresses.asol_mint] {
+        assert_eq!(svm.get_account(&mint).unwrap().owner, spl_token_2022::ID);
+    }
+    assert!(transfer_fee(&svm, &fixture.addresses.lst_mint, SOL_PRECISION) > 0);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn amusd_is_minted_against_what_the_vault_receives() {
+    let (mut svm, mut fixture) = healthy();
+    let deposit = 10 * SOL_PRECISION;
+    let fee = transfer_fee(&svm, &fixture.addresses.lst_mint, deposit);
+    assert_eq!(fee, deposit / 100);
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+    let gross_quote = quote_mint_amusd(&fixture.expected, deposit).unwrap();
+
+    let minted = fixture.mint_amusd(&mut svm, WHALE, deposit).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault) - vault_before, deposit - fee);
+    assert!(minted.to_user < gross_quote.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_mint_and_insurance_book_only_the_received_lst() {
+    let (mut svm, mut fixture) = healthy();
+    let deposit = 4 * SOL_PRECISION;
+    let received = deposit - transfer_fee(&svm, &fixture.addresses.lst_mint, deposit);
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+
+    fixture.mint_asol(&mut svm, MINNOW, deposit).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.addresses.vault) - vault_before, received);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    let credited = fixture.fund_insurance(&mut svm, MINNOW, deposit).unwrap();
+    assert_eq!(credited, compute_tvl_sol(received, fixture.expected.lst_to_sol_rate).unwrap());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn slippage_quoted_on_the_sent_amount_is_refused() {
+    let (mut svm, fixture) = healthy();
+    let deposit = 10 * SOL_PRECISION;
+    let gross_quote = quote_mint_amusd(&fixture.expected, deposit).unwrap();
+    let ix = mint_amusd_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), deposit, gross_quote.to_user);
+    assert_fails_with(send(&mut svm, "mint_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]), "SlippageExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redemptions_pay_the_fee_out_of_the_holders_lst() {
+    let (mut svm, mut fixture) = healthy();
+    let minted = fixture.mint_amusd(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    let vault_before = token_balance(&svm, &fixture.addresses.vault);
+    let lst_before = fixture.balances(&svm, WHALE).0;
+
+    let redeemed = fixture.redeem_amusd(&mut svm, WHALE, minted.to_user).unwrap();
+    let fee = transfer_fee(&svm, &fixture.addresses.lst_mint, redeemed.to_user);
+    assert_eq!(vault_before - token_balance(&svm, &fixture.addresses.vault), redeemed.to_user);
+    assert_eq!(fixture.balances(&svm, WHALE).0 - lst_before, redeemed.to_user - fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
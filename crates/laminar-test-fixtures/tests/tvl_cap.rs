@@ -0,0 +1,69 @@
+use laminar::math::{SOL_PRECISION, USD_PRECISION};
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn total_lst_amount(svm: &LiteSVM, fixture: &Fixture) -> u64 {
+    read_global_state(svm, &fixture.addresses).unwrap().total_lst_amount
+}
+
+#[test]
+fn raising_the_cap_admits_a_rejected_amusd_deposit() {
+    let (mut svm, mut fixture) = healthy();
+    let deposit = SOL_PRECISION;
+    let landing = total_lst_amount(&svm, &fixture) + deposit;
+
+    fixture.set_tvl_cap(&mut svm, landing - 1).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, deposit), "TvlCapExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Landing exactly on the cap passes.
+    fixture.set_tvl_cap(&mut svm, landing).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, deposit).unwrap();
+    assert_eq!(total_lst_amount(&svm, &fixture), landing);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn raising_the_cap_admits_a_rejected_asol_deposit() {
+    let (mut svm, mut fixture) = healthy();
+    let deposit = 10 * SOL_PRECISION;
+    let landing = total_lst_amount(&svm, &fixture) + deposit;
+
+    fixture.set_tvl_cap(&mut svm, landing - 1).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, WHALE, deposit), "TvlCapExceeded");
+
+    fixture.set_tvl_cap(&mut svm, landing).unwrap();
+    fixture.mint_asol(&mut svm, WHALE, deposit).unwrap();
+    assert_eq!(total_lst_amount(&svm, &fixture), landing);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redemptions_ignore_the_cap() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_tvl_cap(&mut svm, 1).unwrap();
+
+    fixture.redeem_amusd(&mut svm, MINNOW, 10 * USD_PRECISION).unwrap();
+    fixture.redeem_asol(&mut svm, MINNOW, SOL_PRECISION / 10).unwrap();
+    assert_fails_with(fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION), "TvlCapExceeded");
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    fixture.set_tvl_cap(&mut svm, 0).unwrap();
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,33 @@
+use laminar::math::{derive_uncertainty_index_bps, SOL_PRECISION};
+use laminar::quote::quote_mint_amusd;
+use laminar_test_fixtures::harness::read_global_state;
+use laminar_test_fixtures::scenarios::MINNOW;
+use laminar_test_fixtures::{build, new_svm, Scenario};
+
+#[test]
+fn wide_confidence_raises_the_amusd_mint_fee() {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let mut fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.uncertainty_index_bps, 0);
+    let calm_fee = quote_mint_amusd(&fixture.expected, SOL_PRECISION).unwrap().fee;
+
+    // Widest confidence the freshness guard still accepts.
+    let confidence_usd = state.mock_sol_price_usd * state.max_conf_bps / 10_000;
+    fixture.set_oracle_confidence(&mut svm, confidence_usd).unwrap();
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(
+        state.uncertainty_index_bps,
+        derive_uncertainty_index_bps(confidence_usd, state.mock_sol_price_usd).unwrap()
+    );
+    assert!(state.uncertainty_index_bps > 0);
+
+    // min_out is pinned to the quote, so the mint proves the chain charged it.
+    let noisy = fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert!(noisy.fee > calm_fee, "fee {} should exceed calm fee {}", noisy.fee, calm_fee);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // A calm oracle brings the index straight back down.
+    fixture.set_oracle_confidence(&mut svm, 0).unwrap();
+    assert_eq!(read_global_state(&svm, &fixture.addresses).unwrap().uncertainty_index_bps, 0);
+}
@@ -0,0 +1,100 @@
+use laminar::math::SOL_PRECISION;
+use laminar::quote::quote_mint_amusd;
+use laminar_test_fixtures::harness::{read_user_position, user_position_address};
+use laminar_test_fixtures::scenarios::{MINNOW, WHALE};
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+fn amusd_minted(svm: &LiteSVM, fixture: &Fixture, actor: &str) -> u64 {
+    read_user_position(svm, &fixture.actor(actor).keypair.pubkey()).unwrap().amusd_minted
+}
+
+/// A per-wallet cap with room for two and a half 1-LST mints
+fn cap_for_two_mints(fixture: &Fixture) -> u64 {
+    let per_mint = quote_mint_amusd(&fixture.expected, SOL_PRECISION).unwrap().to_user;
+    per_mint * 5 / 2
+}
+
+#[test]
+fn cap_binds_cumulative_mints_from_one_wallet() {
+    let (mut svm, mut fixture) = healthy();
+    let cap = cap_for_two_mints(&fixture);
+    fixture.set_per_user_amusd_cap(&mut svm, cap).unwrap();
+
+    let first = fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_eq!(amusd_minted(&svm, &fixture, MINNOW), first.to_user);
+    let second = fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_eq!(amusd_minted(&svm, &fixture, MINNOW), first.to_user + second.to_user);
+
+    // The third mint would cross the cap even though each mint alone fits.
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "PerUserCapExceeded");
+    assert_eq!(amusd_minted(&svm, &fixture, MINNOW), first.to_user + second.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // Raising the cap lets the same wallet continue.
+    fixture.set_per_user_amusd_cap(&mut svm, 2 * cap).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn wallets_are_capped_independently() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_per_user_amusd_cap(&mut svm, cap_for_two_mints(&fixture)).unwrap();
+
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "PerUserCapExceeded");
+
+    // The whale's earlier mints predate its position, which starts empty.
+    let quote = fixture.mint_amusd(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    assert_eq!(amusd_minted(&svm, &fixture, WHALE), quote.to_user);
+    assert_ne!(
+        user_position_address(&fixture.actor(WHALE).keypair.pubkey()),
+        user_position_address(&fixture.actor(MINNOW).keypair.pubkey())
+    );
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn position_is_required_only_for_capped_amusd_mints() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.set_per_user_amusd_cap(&mut svm, cap_for_two_mints(&fixture)).unwrap();
+    fixture.addresses.user_positions = false;
+
+    assert_fails_with(fixture.mint_amusd(&mut svm, MINNOW, SOL_PRECISION), "UserPositionRequired");
+    fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    assert!(svm.get_account(&user_position_address(&fixture.actor(MINNOW).keypair.pubkey())).is_none());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn position_tracks_both_tranches_for_its_owner() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.addresses.user_positions = true;
+
+    let asol = fixture.mint_asol(&mut svm, MINNOW, SOL_PRECISION).unwrap();
+    let amusd = fixture.mint_amusd(&mut svm, MINNOW, 2 * SOL_PRECISION).unwrap();
+
+    let owner = fixture.actor(MINNOW).keypair.pubkey();
+    let position = read_user_position(&svm, &owner).unwrap();
+    assert_eq!(position.owner, owner);
+    assert_eq!(position.lst_deposited, 3 * SOL_PRECISION);
+    assert_eq!(position.asol_minted, asol.to_user);
+    assert_eq!(position.amusd_minted, amusd.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
@@ -0,0 +1,189 @@
+use anchor_spl::token::spl_token;
+use laminar::math::{compute_tvl_sol, BPS_PRECISION, SOL_PRECISION};
+use laminar_test_fixtures::harness::{
+    add_collateral_type_ix, install_native_mint, read_global_state, redeem_amusd_to_sol_ix, send, sol_unwrap_address,
+    token_balance, wrap_sol, zap_mint_amusd_ix,
+};
+use laminar_test_fixtures::scenarios::WHALE;
+use laminar_test_fixtures::{build, new_svm, Fixture, FixtureError, Scenario};
+use litesvm::LiteSVM;
+use solana_sdk::signature::Signer;
+
+fn healthy() -> (LiteSVM, Fixture) {
+    let mut svm = new_svm().unwrap_or_else(|err| panic!("{err}"));
+    let fixture = build(&mut svm, Scenario::HealthyProtocol).unwrap_or_else(|err| panic!("{err}"));
+    (svm, fixture)
+}
+
+fn assert_fails_with<T>(result: Result<T, FixtureError>, code: &str) {
+    match result {
+        Ok(_) => panic!("expected {code}"),
+        Err(err) => assert!(err.to_string().contains(code), "expected {code}, got {err}"),
+    }
+}
+
+#[test]
+fn amusd_mints_against_wsol_and_redeems_back_to_it() {
+    let (mut svm, mut fixture) = healthy();
+    let wsol = fixture.add_wsol_collateral(&mut svm).unwrap();
+    let deposit = 40 * SOL_PRECISION;
+    let wsol_account = wrap_sol(&mut svm, &fixture.actor(WHALE).keypair, deposit, true).unwrap();
+    let amusd_before = fixture.balances(&svm, WHALE).1;
+
+    let minted = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &wsol, deposit).unwrap();
+    assert_eq!(token_balance(&svm, &wsol_account), 0);
+    assert_eq!(fixture.balances(&svm, WHALE).1 - amusd_before, minted.to_user);
+    // One wSOL is one lamport of TVL.
+    let state = read_global_state(&svm, &fixture.addresses).unwrap();
+    assert_eq!(state.collateral_tvl_lamports, compute_tvl_sol(deposit, SOL_PRECISION).unwrap());
+    assert_eq!(state.collateral_tvl_lamports, deposit);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    let redeemed = fixture.redeem_amusd_with_collateral(&mut svm, WHALE, &wsol, minted.to_user).unwrap();
+    assert_eq!(token_balance(&svm, &wsol_account), redeemed.to_user);
+    assert_eq!(token_balance(&svm, &fixture.collaterals[0].addresses.vault), deposit - redeemed.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn sol_sent_without_sync_native_is_still_spendable() {
+    let (mut svm, mut fixture) = healthy();
+    let wsol = fixture.add_wsol_collateral(&mut svm).unwrap();
+    let deposit = 5 * SOL_PRECISION;
+    let wsol_account = wrap_sol(&mut svm, &fixture.actor(WHALE).keypair, deposit, false).unwrap();
+    assert_eq!(token_balance(&svm, &wsol_account), 0);
+
+    fixture.mint_amusd_with_collateral(&mut svm, WHALE, &wsol, deposit).unwrap();
+    assert_eq!(token_balance(&svm, &fixture.collaterals[0].addresses.vault), deposit);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn wsol_only_registers_at_par() {
+    let (mut svm, fixture) = healthy();
+    install_native_mint(&mut svm).unwrap();
+    let addresses = fixture.addresses.with_collateral(spl_token::native_mint::ID);
+    let ix = add_collateral_type_ix(&addresses, &fixture.authority.pubkey(), 1_050_000_000, BPS_PRECISION);
+    assert_fails_with(send(&mut svm, "add_collateral_type", &[ix], &fixture.authority, &[]), "InvalidParameter");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zap_mints_both_tranches_straight_from_native_sol() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.add_wsol_collateral(&mut svm).unwrap();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let wsol_account = fixture.collateral_accounts(WHALE, &spl_token::native_mint::ID).lst_account;
+    let (_, amusd_before, asol_before) = fixture.balances(&svm, WHALE);
+    let sol_before = svm.get_balance(&whale).unwrap();
+
+    let amusd = fixture.zap_mint_amusd(&mut svm, WHALE, 20 * SOL_PRECISION).unwrap();
+    let asol = fixture.zap_mint_asol(&mut svm, WHALE, 5 * SOL_PRECISION).unwrap();
+    let (_, amusd_after, asol_after) = fixture.balances(&svm, WHALE);
+    assert_eq!(amusd_after - amusd_before, amusd.to_user);
+    assert_eq!(asol_after - asol_before, asol.to_user);
+    // Everything wrapped went to the vault; only fees are spent beyond it.
+    assert_eq!(token_balance(&svm, &wsol_account), 0);
+    assert_eq!(token_balance(&svm, &fixture.collaterals[0].addresses.vault), 25 * SOL_PRECISION);
+    assert!(sol_before - svm.get_balance(&whale).unwrap() >= 25 * SOL_PRECISION);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn zap_only_targets_the_wsol_leg_among_collaterals() {
+    let (mut svm, mut fixture) = healthy();
+    let lst = fixture.add_collateral_type(&mut svm, 1_020_000_000, 10 * SOL_PRECISION).unwrap();
+    let accounts = fixture.collateral_accounts(WHALE, &lst);
+    let ix = zap_mint_amusd_ix(&fixture.collaterals[0].addresses, &accounts, SOL_PRECISION, 0);
+    assert_fails_with(send(&mut svm, "zap_mint_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]), "UnsupportedLST");
+
+    // The primary LST goes through its stake pool, so it needs one.
+    let ix = zap_mint_amusd_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), SOL_PRECISION, 0);
+    assert_fails_with(send(&mut svm, "zap_mint_amusd", &[ix], &fixture.actor(WHALE).keypair, &[]), "InvalidStakePool");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn amusd_redeems_straight_to_native_sol() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.add_wsol_collateral(&mut svm).unwrap();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let wsol_account = fixture.collateral_accounts(WHALE, &spl_token::native_mint::ID).lst_account;
+    let minted = fixture.zap_mint_amusd(&mut svm, WHALE, 20 * SOL_PRECISION).unwrap();
+    let rent = svm.get_balance(&wsol_account).unwrap();
+    let sol_before = svm.get_balance(&whale).unwrap();
+
+    let redeemed = fixture.redeem_amusd_to_sol(&mut svm, WHALE, minted.to_user).unwrap();
+    // The payout passes through the user's wSOL account, which stays open.
+    assert_eq!(svm.get_balance(&wsol_account).unwrap(), rent);
+    assert_eq!(token_balance(&svm, &wsol_account), 0);
+    assert!(svm.get_account(&sol_unwrap_address(&whale)).is_none_or(|account| account.lamports == 0));
+    // The payout, less the transaction fee.
+    let gained = svm.get_balance(&whale).unwrap() - sol_before;
+    assert!(gained <= redeemed.to_user && gained + 10_000 > redeemed.to_user);
+    assert_eq!(token_balance(&svm, &fixture.collaterals[0].addresses.vault), 20 * SOL_PRECISION - redeemed.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn asol_redeems_straight_to_native_sol() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.add_wsol_collateral(&mut svm).unwrap();
+    let whale = fixture.actor(WHALE).keypair.pubkey();
+    let minted = fixture.zap_mint_asol(&mut svm, WHALE, 5 * SOL_PRECISION).unwrap();
+    let sol_before = svm.get_balance(&whale).unwrap();
+
+    let redeemed = fixture.redeem_asol_to_sol(&mut svm, WHALE, minted.to_user).unwrap();
+    let gained = svm.get_balance(&whale).unwrap() - sol_before;
+    assert!(gained <= redeemed.to_user && gained + 10_000 > redeemed.to_user);
+    fixture.assert_matches_chain(&svm).unwrap();
+
+    // The unwrap account is gone again, so the next redemption recreates it.
+    let minted = fixture.zap_mint_asol(&mut svm, WHALE, SOL_PRECISION).unwrap();
+    fixture.redeem_asol_to_sol(&mut svm, WHALE, minted.to_user).unwrap();
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_to_sol_leaves_wsol_already_held_wrapped() {
+    let (mut svm, mut fixture) = healthy();
+    fixture.add_wsol_collateral(&mut svm).unwrap();
+    let held = 3 * SOL_PRECISION;
+    let wsol_account = wrap_sol(&mut svm, &fixture.actor(WHALE).keypair, held, true).unwrap();
+    let minted = fixture.zap_mint_amusd(&mut svm, WHALE, 10 * SOL_PRECISION).unwrap();
+    assert_eq!(token_balance(&svm, &wsol_account), held);
+
+    fixture.redeem_amusd_to_sol(&mut svm, WHALE, minted.to_user).unwrap();
+    assert_eq!(token_balance(&svm, &wsol_account), held);
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_to_sol_holds_the_payout_to_min_sol_out() {
+    let (mut svm, mut fixture) = healthy();
+    let wsol = fixture.add_wsol_collateral(&mut svm).unwrap();
+    let deposit = 20 * SOL_PRECISION;
+    let minted = fixture.zap_mint_amusd(&mut svm, WHALE, deposit).unwrap();
+    let accounts = fixture.collateral_accounts(WHALE, &wsol);
+
+    // Mint and redeem fees keep the round trip short of the SOL put in.
+    let ix = redeem_amusd_to_sol_ix(&fixture.collaterals[0].addresses, &accounts, minted.to_user, deposit);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], &fixture.actor(WHALE).keypair, &[]), "SlippageExceeded");
+    assert!(svm.get_account(&accounts.lst_account).is_some());
+    fixture.assert_matches_chain(&svm).unwrap();
+}
+
+#[test]
+fn redeem_to_sol_only_targets_the_wsol_leg_among_collaterals() {
+    let (mut svm, mut fixture) = healthy();
+    let lst = fixture.add_collateral_type(&mut svm, 1_020_000_000, 10 * SOL_PRECISION).unwrap();
+    let minted = fixture.mint_amusd_with_collateral(&mut svm, WHALE, &lst, 5 * SOL_PRECISION).unwrap();
+    let accounts = fixture.collateral_accounts(WHALE, &lst);
+    let ix = redeem_amusd_to_sol_ix(&fixture.collaterals[0].addresses, &accounts, minted.to_user, 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], &fixture.actor(WHALE).keypair, &[]), "UnsupportedLST");
+
+    // The primary LST goes through its stake pool, so it needs one.
+    let ix = redeem_amusd_to_sol_ix(&fixture.addresses, &fixture.actor(WHALE).accounts(), minted.to_user, 1);
+    assert_fails_with(send(&mut svm, "redeem_amusd_to_sol", &[ix], &fixture.actor(WHALE).keypair, &[]), "InvalidStakePool");
+    fixture.assert_matches_chain(&svm).unwrap();
+}
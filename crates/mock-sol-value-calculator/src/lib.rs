@@ -0,0 +1,63 @@
+//! Mock Sanctum SOL value calculator for LiteSVM tests
+//! Implements only `LstToSol`. Accounts are `[lst_mint, lst_state, ..]`;
+//! `lst_state` holds three little-endian u64s `(total_lamports,
+//! pool_token_supply, spread_lamports)` and the returned range is
+//! `[floor(amount * total / supply), floor + spread]`.
+//!
+//! Build with `cargo build-sbf` and load it at a known calculator program id.
+
+use anchor_lang::solana_program::{
+  account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data, program_error::ProgramError,
+  pubkey::Pubkey,
+};
+
+#[cfg(not(feature = "no-entrypoint"))]
+anchor_lang::solana_program::entrypoint!(process_instruction);
+
+/// `LstToSol` discriminator of the calculator interface
+pub const LST_TO_SOL_IX_DISCM: u8 = 0;
+
+/// Size of the mock LST state account
+pub const LST_STATE_LEN: usize = 24;
+
+/// Mock LST state bytes
+pub fn lst_state_data(total_lamports: u64, pool_token_supply: u64, spread_lamports: u64) -> Vec<u8> {
+  [total_lamports, pool_token_supply, spread_lamports]
+    .iter()
+    .flat_map(|word| word.to_le_bytes())
+    .collect()
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+  let mut word = [0u8; 8];
+  word.copy_from_slice(&data[offset..offset + 8]);
+  u64::from_le_bytes(word)
+}
+
+pub fn process_instruction(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+  let (&discm, args) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+  if discm != LST_TO_SOL_IX_DISCM || args.len() != 8 {
+    return Err(ProgramError::InvalidInstructionData);
+  }
+  let amount = read_u64(args, 0);
+
+  let lst_state = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+  let state = lst_state.try_borrow_data()?;
+  if state.len() < LST_STATE_LEN {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  let (total_lamports, pool_token_supply, spread_lamports) = (read_u64(&state, 0), read_u64(&state, 8), read_u64(&state, 16));
+  if pool_token_supply == 0 {
+    return Err(ProgramError::InvalidAccountData);
+  }
+
+  let min = u64::try_from(amount as u128 * total_lamports as u128 / pool_token_supply as u128)
+    .map_err(|_| ProgramError::ArithmeticOverflow)?;
+  let max = min.checked_add(spread_lamports).ok_or(ProgramError::ArithmeticOverflow)?;
+
+  let mut range = [0u8; 16];
+  range[..8].copy_from_slice(&min.to_le_bytes());
+  range[8..].copy_from_slice(&max.to_le_bytes());
+  set_return_data(&range);
+  Ok(())
+}
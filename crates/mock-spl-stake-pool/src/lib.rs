@@ -0,0 +1,216 @@
+//! Mock SPL stake pool for LiteSVM tests
+//! Implements only `DepositSol` and `WithdrawSol`, over the leading
+//! `StakePool` fields Laminar reads. The SOL withdrawal fee is a little-endian u64 in bps at
+//! `SOL_WITHDRAWAL_FEE_OFFSET`, right after `last_update_epoch`, instead of
+//! the real pool's `Fee` further down the account.
+//!
+//! `pool_tokens * fee / 10_000` go to the manager fee account and the rest
+//! are burned for `floor(burned * total_lamports / pool_token_supply)`
+//! lamports out of the reserve, which must be owned by this program. The
+//! withdraw authority, sysvars and stake program are not checked.
+//!
+//! `DepositSol` charges no fee: the lamports go to the reserve and
+//! `floor(lamports * pool_token_supply / total_lamports)` pool tokens are
+//! minted to the destination under the withdraw authority, which must be the
+//! pool mint's mint authority. The referrer account is ignored.
+//!
+//! Build with `cargo build-sbf` and load it at the SPL stake pool program id.
+
+use anchor_lang::solana_program::{
+  account_info::AccountInfo,
+  entrypoint::ProgramResult,
+  instruction::{AccountMeta, Instruction},
+  program::{invoke, invoke_signed},
+  program_error::ProgramError,
+  pubkey::Pubkey,
+};
+
+#[cfg(not(feature = "no-entrypoint"))]
+anchor_lang::solana_program::entrypoint!(process_instruction);
+
+/// `StakePoolInstruction::DepositSol` tag
+pub const DEPOSIT_SOL_IX_DISCM: u8 = 14;
+
+/// `StakePoolInstruction::WithdrawSol` tag
+pub const WITHDRAW_SOL_IX_DISCM: u8 = 16;
+
+/// Seed of the pool's withdraw authority, after the pool address
+pub const AUTHORITY_WITHDRAW: &[u8] = b"withdraw";
+
+pub const RESERVE_STAKE_OFFSET: usize = 130;
+pub const POOL_MINT_OFFSET: usize = 162;
+pub const MANAGER_FEE_ACCOUNT_OFFSET: usize = 194;
+pub const TOTAL_LAMPORTS_OFFSET: usize = 258;
+pub const POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+/// Mock-only SOL withdrawal fee, in bps
+pub const SOL_WITHDRAWAL_FEE_OFFSET: usize = 282;
+
+const BPS: u128 = 10_000;
+
+/// SPL token `Transfer`, `MintTo` and `Burn` tags
+const TOKEN_TRANSFER_IX: u8 = 3;
+const TOKEN_MINT_TO_IX: u8 = 7;
+const TOKEN_BURN_IX: u8 = 8;
+
+/// System program `Transfer` tag
+const SYSTEM_TRANSFER_IX: u32 = 2;
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+  let mut word = [0u8; 8];
+  word.copy_from_slice(&data[offset..offset + 8]);
+  u64::from_le_bytes(word)
+}
+
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+  data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn read_key(data: &[u8], offset: usize) -> Pubkey {
+  let mut key = [0u8; 32];
+  key.copy_from_slice(&data[offset..offset + 32]);
+  Pubkey::new_from_array(key)
+}
+
+/// `Transfer`, `MintTo` or `Burn` of `amount` signed by `authority`; `from`
+/// and `to` are the source and destination, either of which is the mint.
+fn token_ix(tag: u8, token_program: &Pubkey, from: &Pubkey, to: &Pubkey, authority: &Pubkey, amount: u64) -> Instruction {
+  let mut data = Vec::with_capacity(9);
+  data.push(tag);
+  data.extend_from_slice(&amount.to_le_bytes());
+  Instruction {
+    program_id: *token_program,
+    accounts: vec![
+      AccountMeta::new(*from, false),
+      AccountMeta::new(*to, false),
+      AccountMeta::new_readonly(*authority, true),
+    ],
+    data,
+  }
+}
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+  let (&discm, args) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+  if args.len() != 8 {
+    return Err(ProgramError::InvalidInstructionData);
+  }
+  match discm {
+    DEPOSIT_SOL_IX_DISCM => deposit_sol(program_id, accounts, read_u64(args, 0)),
+    WITHDRAW_SOL_IX_DISCM => withdraw_sol(accounts, read_u64(args, 0)),
+    _ => Err(ProgramError::InvalidInstructionData),
+  }
+}
+
+/// Check the header's reserve, mint and manager fee account against the
+/// accounts passed and return its `(total_lamports, pool_token_supply, fee_bps)`.
+fn read_pool(
+  stake_pool: &AccountInfo,
+  reserve_stake: &AccountInfo,
+  manager_fee_account: &AccountInfo,
+  pool_mint: &AccountInfo,
+) -> Result<(u64, u64, u64), ProgramError> {
+  let pool = stake_pool.try_borrow_data()?;
+  if pool.len() < SOL_WITHDRAWAL_FEE_OFFSET + 8 {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  if read_key(&pool, RESERVE_STAKE_OFFSET) != *reserve_stake.key
+    || read_key(&pool, POOL_MINT_OFFSET) != *pool_mint.key
+    || read_key(&pool, MANAGER_FEE_ACCOUNT_OFFSET) != *manager_fee_account.key
+  {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  Ok((
+    read_u64(&pool, TOTAL_LAMPORTS_OFFSET),
+    read_u64(&pool, POOL_TOKEN_SUPPLY_OFFSET),
+    read_u64(&pool, SOL_WITHDRAWAL_FEE_OFFSET),
+  ))
+}
+
+fn deposit_sol(program_id: &Pubkey, accounts: &[AccountInfo], lamports: u64) -> ProgramResult {
+  let [stake_pool, withdraw_authority, reserve_stake, lamports_from, pool_tokens_to, manager_fee_account, _referrer, pool_mint, system_program, token_program, ..] =
+    accounts
+  else {
+    return Err(ProgramError::NotEnoughAccountKeys);
+  };
+  if !lamports_from.is_signer {
+    return Err(ProgramError::MissingRequiredSignature);
+  }
+  let (authority, bump) = Pubkey::find_program_address(&[stake_pool.key.as_ref(), AUTHORITY_WITHDRAW], program_id);
+  if authority != *withdraw_authority.key {
+    return Err(ProgramError::InvalidSeeds);
+  }
+
+  let (total_lamports, pool_token_supply, _) = read_pool(stake_pool, reserve_stake, manager_fee_account, pool_mint)?;
+  if total_lamports == 0 {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  let minted = u64::try_from(lamports as u128 * pool_token_supply as u128 / total_lamports as u128)
+    .map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+  let mut transfer = Vec::with_capacity(12);
+  transfer.extend_from_slice(&SYSTEM_TRANSFER_IX.to_le_bytes());
+  transfer.extend_from_slice(&lamports.to_le_bytes());
+  invoke(
+    &Instruction {
+      program_id: *system_program.key,
+      accounts: vec![AccountMeta::new(*lamports_from.key, true), AccountMeta::new(*reserve_stake.key, false)],
+      data: transfer,
+    },
+    &[lamports_from.clone(), reserve_stake.clone(), system_program.clone()],
+  )?;
+  invoke_signed(
+    &token_ix(TOKEN_MINT_TO_IX, token_program.key, pool_mint.key, pool_tokens_to.key, withdraw_authority.key, minted),
+    &[pool_mint.clone(), pool_tokens_to.clone(), withdraw_authority.clone(), token_program.clone()],
+    &[&[stake_pool.key.as_ref(), AUTHORITY_WITHDRAW, &[bump]]],
+  )?;
+
+  let mut pool = stake_pool.try_borrow_mut_data()?;
+  write_u64(&mut pool, TOTAL_LAMPORTS_OFFSET, total_lamports.checked_add(lamports).ok_or(ProgramError::ArithmeticOverflow)?);
+  write_u64(&mut pool, POOL_TOKEN_SUPPLY_OFFSET, pool_token_supply.checked_add(minted).ok_or(ProgramError::ArithmeticOverflow)?);
+  Ok(())
+}
+
+fn withdraw_sol(accounts: &[AccountInfo], pool_tokens: u64) -> ProgramResult {
+  let [stake_pool, _withdraw_authority, user_authority, pool_tokens_from, reserve_stake, lamports_to, manager_fee_account, pool_mint, _clock, _stake_history, _stake_program, token_program, ..] =
+    accounts
+  else {
+    return Err(ProgramError::NotEnoughAccountKeys);
+  };
+  if !user_authority.is_signer {
+    return Err(ProgramError::MissingRequiredSignature);
+  }
+
+  let (total_lamports, pool_token_supply, fee_bps) = read_pool(stake_pool, reserve_stake, manager_fee_account, pool_mint)?;
+  if pool_token_supply == 0 {
+    return Err(ProgramError::InvalidAccountData);
+  }
+
+  let fee_tokens = u64::try_from(pool_tokens as u128 * fee_bps as u128 / BPS).map_err(|_| ProgramError::ArithmeticOverflow)?;
+  let burned = pool_tokens.checked_sub(fee_tokens).ok_or(ProgramError::ArithmeticOverflow)?;
+  let lamports = u64::try_from(burned as u128 * total_lamports as u128 / pool_token_supply as u128)
+    .map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+  if fee_tokens > 0 {
+    invoke(
+      &token_ix(TOKEN_TRANSFER_IX, token_program.key, pool_tokens_from.key, manager_fee_account.key, user_authority.key, fee_tokens),
+      &[pool_tokens_from.clone(), manager_fee_account.clone(), user_authority.clone(), token_program.clone()],
+    )?;
+  }
+  invoke(
+    &token_ix(TOKEN_BURN_IX, token_program.key, pool_tokens_from.key, pool_mint.key, user_authority.key, burned),
+    &[pool_tokens_from.clone(), pool_mint.clone(), user_authority.clone(), token_program.clone()],
+  )?;
+
+  **reserve_stake.try_borrow_mut_lamports()? = reserve_stake
+    .lamports()
+    .checked_sub(lamports)
+    .ok_or(ProgramError::InsufficientFunds)?;
+  **lamports_to.try_borrow_mut_lamports()? = lamports_to
+    .lamports()
+    .checked_add(lamports)
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+  let mut pool = stake_pool.try_borrow_mut_data()?;
+  write_u64(&mut pool, TOTAL_LAMPORTS_OFFSET, total_lamports.saturating_sub(lamports));
+  write_u64(&mut pool, POOL_TOKEN_SUPPLY_OFFSET, pool_token_supply - burned);
+  Ok(())
+}
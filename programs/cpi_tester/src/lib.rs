@@ -52,6 +52,7 @@ pub mod cpi_tester {
                 AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
                 AccountMeta::new_readonly(ctx.accounts.cpi_tester_program.key(), false),
                 AccountMeta::new_readonly(ctx.accounts.laminar_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.laminar_event_authority.key(), false),
             ],
             data: crate::instruction::CpiMintAsol {
                 lst_amount,
@@ -77,11 +78,59 @@ pub mod cpi_tester {
             ctx.accounts.clock.to_account_info(),
             ctx.accounts.cpi_tester_program.to_account_info(),
             ctx.accounts.laminar_program.to_account_info(),
+            ctx.accounts.laminar_event_authority.to_account_info(),
         ];
 
         invoke(&ix, &infos)?;
         Ok(())
     }
+
+    /// Direct CPI into Laminar's public aSOL redeem entrypoint.
+    ///
+    /// Used as the re-entry leg of a flash loan: the borrower routes the
+    /// loaned LST through this proxy back into `redeem_asol` before repaying.
+    /// Laminar must reject it, leaving the vault untouched.
+    pub fn cpi_redeem_asol(
+        ctx: Context<ProxyRedeemAsol>,
+        asol_amount: u64,
+        min_lst_out: u64,
+    ) -> Result<()> {
+        let cpi_accounts = laminar::cpi::accounts::RedeemAsol {
+            user: ctx.accounts.user.to_account_info(),
+            global_state: ctx.accounts.global_state.to_account_info(),
+            asol_mint: ctx.accounts.asol_mint.to_account_info(),
+            user_asol_account: ctx.accounts.user_asol_account.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            treasury_asol_account: ctx.accounts.treasury_asol_account.to_account_info(),
+            user_lst_account: ctx.accounts.user_lst_account.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            vault_authority: ctx.accounts.vault_authority.to_account_info(),
+            lst_mint: ctx.accounts.lst_mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            clock: ctx.accounts.clock.to_account_info(),
+            price_update: None,
+            switchboard_feed: None,
+            collateral_vault: None,
+            user_position: None,
+            recipient_lst_account: None,
+            stake_pool: None,
+            stake_pool_withdraw_authority: None,
+            reserve_stake: None,
+            manager_fee_account: None,
+            stake_history: None,
+            stake_program: None,
+            stake_pool_program: None,
+            stake_pool_mint: None,
+            sol_unwrap_account: None,
+            event_authority: ctx.accounts.laminar_event_authority.to_account_info(),
+            program: ctx.accounts.laminar_program.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(ctx.accounts.laminar_program.to_account_info(), cpi_accounts);
+        laminar::cpi::redeem_asol(cpi_ctx, asol_amount, min_lst_out)
+    }
 }
 
 /// Forwards CPI into Laminar mint_asol public entrypoint.
@@ -107,6 +156,19 @@ fn forward_mint_asol_cpi(
         associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
         system_program: ctx.accounts.system_program.to_account_info(),
         clock: ctx.accounts.clock.to_account_info(),
+        price_update: None,
+        switchboard_feed: None,
+        collateral_vault: None,
+        user_position: None,
+        recipient_asol_account: None,
+        stake_pool: None,
+        stake_pool_withdraw_authority: None,
+        reserve_stake: None,
+        manager_fee_account: None,
+        stake_pool_mint: None,
+        stake_pool_program: None,
+        event_authority: ctx.accounts.laminar_event_authority.to_account_info(),
+        program: ctx.accounts.laminar_program.to_account_info(),
     };
 
     let cpi_ctx = CpiContext::new(ctx.accounts.laminar_program.to_account_info(), cpi_accounts);
@@ -183,4 +245,82 @@ pub struct ProxyMintAsol<'info> {
 
     /// Laminar program account for CPI target.
     pub laminar_program: Program<'info, Laminar>,
+
+    /// Signer of Laminar's event self-CPI.
+    /// CHECK: Seeds-constrained to Laminar's event authority PDA.
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = laminar_program.key())]
+    pub laminar_event_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProxyRedeemAsol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Laminar GlobalState account.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub global_state: UncheckedAccount<'info>,
+
+    /// Laminar aSOL mint.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub asol_mint: UncheckedAccount<'info>,
+
+    /// User's aSOL ATA.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub user_asol_account: UncheckedAccount<'info>,
+
+    /// Laminar treasury authority.
+    /// CHECK: Validated by Laminar program during CPI.
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Treasury aSOL ATA.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub treasury_asol_account: UncheckedAccount<'info>,
+
+    /// User's LST ATA.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub user_lst_account: UncheckedAccount<'info>,
+
+    /// Laminar vault ATA.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Laminar vault authority PDA.
+    /// CHECK: Validated by Laminar program during CPI.
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Supported LST mint.
+    /// CHECK: Validated by Laminar program during CPI.
+    #[account(mut)]
+    pub lst_mint: UncheckedAccount<'info>,
+
+    /// Token program account.
+    /// CHECK: Laminar validates expected token program.
+    pub token_program: UncheckedAccount<'info>,
+
+    /// ATA program account.
+    /// CHECK: Laminar validates expected ATA program.
+    pub associated_token_program: UncheckedAccount<'info>,
+
+    /// System program account.
+    /// CHECK: Laminar validates expected system program.
+    pub system_program: UncheckedAccount<'info>,
+
+    /// Clock sysvar.
+    /// CHECK: Laminar reads clock slot for freshness checks.
+    pub clock: UncheckedAccount<'info>,
+
+    /// Laminar program account for CPI target.
+    pub laminar_program: Program<'info, Laminar>,
+
+    /// Signer of Laminar's event self-CPI.
+    /// CHECK: Seeds-constrained to Laminar's event authority PDA.
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = laminar_program.key())]
+    pub laminar_event_authority: UncheckedAccount<'info>,
 }
@@ -0,0 +1,476 @@
+//! Collateral legs for the user instructions
+//! mint/redeem move one LST per call: the primary one held in GlobalState,
+//! or one registered through `add_collateral_type` and passed as its
+//! `CollateralVault`. A leg is that vault's LST and rate plus the SOL value
+//! of every other vault, so the handlers keep their single-vault math while
+//! CR, NAV and the balance sheet see the whole book. CR takes each vault at
+//! its collateral weight; everything that pays out uses the unweighted value.
+//!
+//! The other vaults' value comes from the totals `GlobalState` caches, which
+//! `commit` keeps in step on every move and `refresh_tvl` rebuilds from the
+//! vaults themselves.
+//!
+//! Deposits go through `deposit_lst` before they are priced, so a Token-2022
+//! LST with a transfer fee is booked at what the vault actually received.
+//!
+//! The wSOL leg also takes and pays native SOL: zaps wrap it in with
+//! `wrap_sol_deposit`, redeem-to-SOL unwraps the payout with `unwrap_sol_payout`
+//! through a temporary account, so wSOL the user already held stays wrapped.
+//! The primary LST is zapped into and redeemed to SOL through its stake pool
+//! instead, with `stake_sol_deposit` and `unstake_lst_payout`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token_interface::{
+  self, CloseAccount, InitializeAccount3, Mint, SyncNative, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::LaminarError;
+use crate::lst_rate::{StakePoolDeposit, StakePoolWithdraw};
+use crate::math::{compute_book_tvl_sol, compute_tvl_sol, compute_weighted_tvl_sol, BPS_PRECISION};
+use crate::quote::QuoteState;
+use crate::state::{CollateralStatus, CollateralVault, GlobalState, SOL_UNWRAP_SEED, VAULT_AUTHORITY_SEED};
+
+/// The vault an instruction moves LST through, valued against the book
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollateralLeg {
+  /// LST held in this leg's vault
+  pub lst_amount: u64,
+  /// LST->SOL rate of this leg's mint
+  pub lst_to_sol_rate: u64,
+  /// SOL value of every other vault
+  pub other_tvl_lamports: u64,
+  /// Share of this leg's SOL value counted toward CR
+  pub collateral_weight_bps: u64,
+  /// Weighted SOL value of every other vault
+  pub other_weighted_tvl_lamports: u64,
+  /// Bump of this leg's vault authority PDA
+  pub vault_authority_bump: u8,
+}
+
+impl CollateralLeg {
+  /// `primary_lst_to_sol_rate` is the rate the caller values the primary
+  /// vault at (the settlement snapshot once settled).
+  pub fn resolve(
+    global_state: &GlobalState,
+    collateral_vault: Option<&Account<CollateralVault>>,
+    primary_lst_to_sol_rate: u64,
+  ) -> Result<Self> {
+    let Some(collateral_vault) = collateral_vault else {
+      return Ok(Self {
+        lst_amount: global_state.total_lst_amount,
+        lst_to_sol_rate: primary_lst_to_sol_rate,
+        other_tvl_lamports: global_state.collateral_tvl_lamports,
+        collateral_weight_bps: BPS_PRECISION,
+        other_weighted_tvl_lamports: global_state.weighted_collateral_tvl_lamports,
+        vault_authority_bump: global_state.vault_authority_bump,
+      });
+    };
+
+    // Settlement freezes and sweeps the primary vault only.
+    require!(!global_state.settled, LaminarError::ProtocolSettled);
+
+    let primary_tvl = compute_tvl_sol(global_state.total_lst_amount, primary_lst_to_sol_rate)
+      .ok_or(LaminarError::MathOverflow)?;
+    let other_tvl_lamports = global_state.collateral_tvl_lamports
+      .checked_sub(collateral_vault.tvl_sol()?)
+      .and_then(|other| other.checked_add(primary_tvl))
+      .ok_or(LaminarError::MathOverflow)?;
+    // The primary LST always counts at full weight.
+    let other_weighted_tvl_lamports = global_state.weighted_collateral_tvl_lamports
+      .checked_sub(collateral_vault.weighted_tvl_sol()?)
+      .and_then(|other| other.checked_add(primary_tvl))
+      .ok_or(LaminarError::MathOverflow)?;
+
+    Ok(Self {
+      lst_amount: collateral_vault.lst_amount,
+      lst_to_sol_rate: collateral_vault.lst_to_sol_rate,
+      other_tvl_lamports,
+      collateral_weight_bps: collateral_vault.collateral_weight_bps,
+      other_weighted_tvl_lamports,
+      vault_authority_bump: collateral_vault.vault_authority_bump,
+    })
+  }
+
+  /// Book TVL with this leg's vault holding `lst_amount`
+  pub fn tvl(&self, lst_amount: u64) -> Result<u64> {
+    compute_book_tvl_sol(lst_amount, self.lst_to_sol_rate, self.other_tvl_lamports)
+      .ok_or(error!(LaminarError::MathOverflow))
+  }
+
+  /// Weighted book TVL, the one CR is taken on, with this leg's vault holding `lst_amount`
+  pub fn weighted_tvl(&self, lst_amount: u64) -> Result<u64> {
+    compute_weighted_tvl_sol(lst_amount, self.lst_to_sol_rate, self.collateral_weight_bps)
+      .and_then(|tvl| tvl.checked_add(self.other_weighted_tvl_lamports))
+      .ok_or(error!(LaminarError::MathOverflow))
+  }
+
+  /// The book `quote` prices through this leg, at the SOL price and
+  /// uncertainty index the caller resolved
+  pub fn quote_state(&self, global_state: &GlobalState, sol_price_usd: u64, uncertainty_index_bps: u64) -> QuoteState {
+    QuoteState {
+      sol_price_usd,
+      uncertainty_index_bps,
+      total_lst_amount: self.lst_amount,
+      lst_to_sol_rate: self.lst_to_sol_rate,
+      other_tvl_lamports: self.other_tvl_lamports,
+      collateral_weight_bps: self.collateral_weight_bps,
+      other_weighted_tvl_lamports: self.other_weighted_tvl_lamports,
+      ..QuoteState::from(global_state)
+    }
+  }
+
+  /// Write the leg's new LST amount back, keeping the cached
+  /// `collateral_tvl_lamports` equal to the sum of the vaults it covers.
+  pub fn commit(
+    global_state: &mut GlobalState,
+    collateral_vault: Option<&mut Account<CollateralVault>>,
+    new_lst_amount: u64,
+  ) -> Result<()> {
+    let Some(collateral_vault) = collateral_vault else {
+      global_state.total_lst_amount = new_lst_amount;
+      return Ok(());
+    };
+
+    let old_tvl = collateral_vault.tvl_sol()?;
+    let old_weighted_tvl = collateral_vault.weighted_tvl_sol()?;
+    collateral_vault.lst_amount = new_lst_amount;
+    let new_tvl = collateral_vault.tvl_sol()?;
+    let new_weighted_tvl = collateral_vault.weighted_tvl_sol()?;
+    global_state.collateral_tvl_lamports = global_state.collateral_tvl_lamports
+      .checked_sub(old_tvl)
+      .and_then(|tvl| tvl.checked_add(new_tvl))
+      .ok_or(LaminarError::MathOverflow)?;
+    global_state.weighted_collateral_tvl_lamports = global_state.weighted_collateral_tvl_lamports
+      .checked_sub(old_weighted_tvl)
+      .and_then(|tvl| tvl.checked_add(new_weighted_tvl))
+      .ok_or(LaminarError::MathOverflow)?;
+    if collateral_vault.is_deprecated() {
+      global_state.deprecated_collateral_tvl_lamports = global_state.deprecated_collateral_tvl_lamports
+        .checked_sub(old_weighted_tvl)
+        .and_then(|tvl| tvl.checked_add(new_weighted_tvl))
+        .ok_or(LaminarError::MathOverflow)?;
+    }
+    Ok(())
+  }
+}
+
+/// The three `GlobalState` collateral totals, rebuilt vault by vault
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollateralTotals {
+  /// Sum of `tvl_sol` (`collateral_tvl_lamports`)
+  pub tvl_lamports: u64,
+  /// Sum of `weighted_tvl_sol` (`weighted_collateral_tvl_lamports`)
+  pub weighted_tvl_lamports: u64,
+  /// Weighted value of the deprecated vaults (`deprecated_collateral_tvl_lamports`)
+  pub deprecated_tvl_lamports: u64,
+}
+
+impl CollateralTotals {
+  /// What `global_state` currently caches
+  pub fn cached(global_state: &GlobalState) -> Self {
+    Self {
+      tvl_lamports: global_state.collateral_tvl_lamports,
+      weighted_tvl_lamports: global_state.weighted_collateral_tvl_lamports,
+      deprecated_tvl_lamports: global_state.deprecated_collateral_tvl_lamports,
+    }
+  }
+
+  pub fn add_vault(&mut self, collateral_vault: &CollateralVault) -> Result<()> {
+    let weighted_tvl = collateral_vault.weighted_tvl_sol()?;
+    self.tvl_lamports = self.tvl_lamports
+      .checked_add(collateral_vault.tvl_sol()?)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.weighted_tvl_lamports = self.weighted_tvl_lamports
+      .checked_add(weighted_tvl)
+      .ok_or(LaminarError::MathOverflow)?;
+    if collateral_vault.is_deprecated() {
+      self.deprecated_tvl_lamports = self.deprecated_tvl_lamports
+        .checked_add(weighted_tvl)
+        .ok_or(LaminarError::MathOverflow)?;
+    }
+    Ok(())
+  }
+
+  pub fn store(&self, global_state: &mut GlobalState) {
+    global_state.collateral_tvl_lamports = self.tvl_lamports;
+    global_state.weighted_collateral_tvl_lamports = self.weighted_tvl_lamports;
+    global_state.deprecated_collateral_tvl_lamports = self.deprecated_tvl_lamports;
+  }
+}
+
+/// Mints only land in the primary vault or an `Active` collateral type.
+pub fn assert_accepts_deposits(collateral_vault: Option<&Account<CollateralVault>>) -> Result<()> {
+  if let Some(collateral_vault) = collateral_vault {
+    require!(
+      collateral_vault.status == CollateralStatus::Active as u8,
+      LaminarError::CollateralDepositsDisabled
+    );
+  }
+  Ok(())
+}
+
+/// Stored bumps must re-derive to the vault authority passed in, and a
+/// secondary leg's vault must be the one registered for its mint.
+pub fn validate_leg_accounts(
+  global_state: &GlobalState,
+  global_state_key: &Pubkey,
+  collateral_vault: Option<&Account<CollateralVault>>,
+  vault_authority_key: &Pubkey,
+  vault_key: &Pubkey,
+) -> Result<()> {
+  match collateral_vault {
+    None => global_state.validate_bumps(global_state_key, vault_authority_key),
+    Some(collateral_vault) => {
+      global_state.validate_global_state_bump(global_state_key)?;
+      collateral_vault.validate_bumps(&collateral_vault.key(), vault_authority_key)?;
+      require_keys_eq!(collateral_vault.vault, *vault_key, LaminarError::InvalidAccountState);
+      Ok(())
+    }
+  }
+}
+
+/// LST the leg's vault token account must hold once its CPIs settle
+pub fn expected_vault_balance(global_state: &GlobalState, collateral_vault: Option<&Account<CollateralVault>>) -> u64 {
+  collateral_vault.map_or(global_state.total_lst_amount, |collateral_vault| collateral_vault.lst_amount)
+}
+
+/// Whether pricing this leg needs the primary LST's synced rate to be fresh.
+/// A wSOL leg is valued at par; once the primary vault is empty no
+/// stake-pool rate in the book is left to go stale.
+pub fn needs_lst_snapshot(global_state: &GlobalState, collateral_vault: Option<&Account<CollateralVault>>) -> bool {
+  !(global_state.total_lst_amount == 0 && collateral_vault.is_some_and(|collateral_vault| collateral_vault.is_native()))
+}
+
+/// SOL sent straight to a wSOL account only shows in its token amount after
+/// `sync_native`, so sync a depositor's wSOL before reading its balance.
+pub fn sync_native_deposit<'info>(
+  collateral_vault: Option<&Account<CollateralVault>>,
+  user_lst_account: &mut InterfaceAccount<'info, TokenAccount>,
+  token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+  if !collateral_vault.is_some_and(|collateral_vault| collateral_vault.is_native()) {
+    return Ok(());
+  }
+  token_interface::sync_native(CpiContext::new(
+    token_program.to_account_info(),
+    SyncNative { account: user_lst_account.to_account_info() },
+  ))?;
+  user_lst_account.reload()
+}
+
+/// SOL zap into the wSOL leg: move `lamports` of the signer's native SOL into
+/// its wSOL account. The mint path's `sync_native_deposit` then counts them as
+/// the deposit.
+pub fn wrap_sol_deposit<'info>(
+  collateral_vault: Option<&Account<CollateralVault>>,
+  user: &Signer<'info>,
+  user_lst_account: &InterfaceAccount<'info, TokenAccount>,
+  system_program: &Program<'info, System>,
+  lamports: u64,
+) -> Result<()> {
+  assert_native_leg(collateral_vault)?;
+  system_program::transfer(
+    CpiContext::new(
+      system_program.to_account_info(),
+      Transfer { from: user.to_account_info(), to: user_lst_account.to_account_info() },
+    ),
+    lamports,
+  )
+}
+
+/// Of the registered legs only wSOL can be zapped into or redeemed to native
+/// SOL; the primary LST goes through its stake pool.
+pub fn assert_native_leg(collateral_vault: Option<&Account<CollateralVault>>) -> Result<()> {
+  require!(
+    collateral_vault.is_some_and(|collateral_vault| collateral_vault.is_native()),
+    LaminarError::UnsupportedLST
+  );
+  Ok(())
+}
+
+/// Accounts redeem-to-SOL unwraps the wSOL leg's payout through
+pub struct SolUnwrap<'info> {
+  /// wSOL mint of the leg
+  pub wsol_mint: AccountInfo<'info>,
+  pub wsol_decimals: u8,
+  /// [SOL_UNWRAP_SEED, user] PDA; a token account only within the instruction
+  pub unwrap_account: AccountInfo<'info>,
+  pub unwrap_bump: u8,
+  pub token_program: AccountInfo<'info>,
+  pub system_program: AccountInfo<'info>,
+}
+
+/// Redeem-to-SOL out of the wSOL leg: move what the redemption paid in since
+/// `balance_before` into a temporary wSOL account of this program and close
+/// that to the signer, leaving the rest of their wSOL account as it was. The
+/// SOL delivered is held to `min_sol_out`.
+pub fn unwrap_sol_payout<'info>(
+  unwrap: &SolUnwrap<'info>,
+  user: AccountInfo<'info>,
+  user_lst_account: &mut InterfaceAccount<'info, TokenAccount>,
+  balance_before: u64,
+  min_sol_out: u64,
+) -> Result<u64> {
+  user_lst_account.reload()?;
+  let sol_out = user_lst_account.amount
+    .checked_sub(balance_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(sol_out >= min_sol_out, LaminarError::SlippageExceeded);
+
+  let user_key = user.key();
+  let unwrap_seeds: &[&[u8]] = &[SOL_UNWRAP_SEED, user_key.as_ref(), &[unwrap.unwrap_bump]];
+  let signer = &[unwrap_seeds];
+  create_sol_unwrap_account(unwrap, &user, signer)?;
+  token_interface::initialize_account3(CpiContext::new(
+    unwrap.token_program.clone(),
+    InitializeAccount3 {
+      account: unwrap.unwrap_account.clone(),
+      mint: unwrap.wsol_mint.clone(),
+      authority: unwrap.unwrap_account.clone(),
+    },
+  ))?;
+  token_interface::transfer_checked(
+    CpiContext::new(
+      unwrap.token_program.clone(),
+      TransferChecked {
+        from: user_lst_account.to_account_info(),
+        mint: unwrap.wsol_mint.clone(),
+        to: unwrap.unwrap_account.clone(),
+        authority: user.clone(),
+      },
+    ),
+    sol_out,
+    unwrap.wsol_decimals,
+  )?;
+  // Closing a native account pays out its rent and its wSOL alike.
+  token_interface::close_account(CpiContext::new_with_signer(
+    unwrap.token_program.clone(),
+    CloseAccount {
+      account: unwrap.unwrap_account.clone(),
+      destination: user,
+      authority: unwrap.unwrap_account.clone(),
+    },
+    signer,
+  ))?;
+  Ok(sol_out)
+}
+
+/// Allocate the unwrap PDA as a token account, tolerating lamports sent to
+/// its address ahead of time (a bare `create_account` would fail and block
+/// the redemption). Any such lamports are unwrapped to the user with the rest.
+fn create_sol_unwrap_account<'info>(unwrap: &SolUnwrap<'info>, user: &AccountInfo<'info>, signer: &[&[&[u8]]]) -> Result<()> {
+  let space = anchor_spl::token::TokenAccount::LEN;
+  let rent = Rent::get()?.minimum_balance(space);
+  let system_program = unwrap.system_program.clone();
+  let account = unwrap.unwrap_account.clone();
+
+  let current_lamports = account.lamports();
+  if current_lamports == 0 {
+    return system_program::create_account(
+      CpiContext::new_with_signer(system_program, CreateAccount { from: user.clone(), to: account }, signer),
+      rent,
+      space as u64,
+      unwrap.token_program.key,
+    );
+  }
+
+  let top_up = rent.saturating_sub(current_lamports);
+  if top_up > 0 {
+    system_program::transfer(
+      CpiContext::new(system_program.clone(), Transfer { from: user.clone(), to: account.clone() }),
+      top_up,
+    )?;
+  }
+  system_program::allocate(
+    CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: account.clone() }, signer),
+    space as u64,
+  )?;
+  system_program::assign(
+    CpiContext::new_with_signer(system_program, Assign { account_to_assign: account }, signer),
+    unwrap.token_program.key,
+  )
+}
+
+/// SOL zap into the primary LST: stake `lamports` of the signer's native SOL
+/// through the stake pool and return the pool tokens that reached their LST
+/// account, after the pool's deposit fee. The mint path deposits those.
+pub fn stake_sol_deposit<'info>(
+  stake_pool: &StakePoolDeposit<'info>,
+  user: AccountInfo<'info>,
+  user_lst_account: &mut InterfaceAccount<'info, TokenAccount>,
+  lamports: u64,
+) -> Result<u64> {
+  let balance_before = user_lst_account.amount;
+  stake_pool.deposit_sol(&user, &user_lst_account.to_account_info(), lamports)?;
+  user_lst_account.reload()?;
+  let lst_in = user_lst_account.amount
+    .checked_sub(balance_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  Ok(lst_in)
+}
+
+/// Redeem-to-SOL out of the primary LST: unstake what the redemption paid in
+/// since `balance_before` through the stake pool. The lamports the pool
+/// delivered, after its withdrawal fee, are held to `min_sol_out`.
+pub fn unstake_lst_payout<'info>(
+  stake_pool: &StakePoolWithdraw<'info>,
+  user: AccountInfo<'info>,
+  user_lst_account: &mut InterfaceAccount<'info, TokenAccount>,
+  balance_before: u64,
+  min_sol_out: u64,
+) -> Result<u64> {
+  user_lst_account.reload()?;
+  let lst_out = user_lst_account.amount
+    .checked_sub(balance_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  let lamports_before = user.lamports();
+  stake_pool.withdraw_sol(&user, &user_lst_account.to_account_info(), lst_out)?;
+  let sol_out = user.lamports()
+    .checked_sub(lamports_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(sol_out >= min_sol_out, LaminarError::SlippageExceeded);
+  Ok(sol_out)
+}
+
+/// Moves `amount` of the leg's LST into its vault and returns what the vault
+/// actually gained. A Token-2022 transfer fee is withheld out of the
+/// destination's balance, so this can be less than `amount`.
+pub fn deposit_lst<'info>(
+  token_program: &Interface<'info, TokenInterface>,
+  lst_mint: &InterfaceAccount<'info, Mint>,
+  from: &InterfaceAccount<'info, TokenAccount>,
+  vault: &mut InterfaceAccount<'info, TokenAccount>,
+  authority: AccountInfo<'info>,
+  amount: u64,
+) -> Result<u64> {
+  let balance_before = vault.amount;
+  token_interface::transfer_checked(
+    CpiContext::new(
+      token_program.to_account_info(),
+      TransferChecked {
+        from: from.to_account_info(),
+        mint: lst_mint.to_account_info(),
+        to: vault.to_account_info(),
+        authority,
+      },
+    ),
+    amount,
+    lst_mint.decimals,
+  )?;
+  vault.reload()?;
+  let received = vault.amount
+    .checked_sub(balance_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  Ok(received)
+}
+
+/// Signer seeds for a leg's vault authority: `[VAULT_AUTHORITY_SEED]` for the
+/// primary vault, `[VAULT_AUTHORITY_SEED, lst_mint]` for a `CollateralVault`.
+pub fn vault_authority_seeds<'a>(lst_mint: &'a Pubkey, bump: &'a [u8; 1], per_mint: bool) -> Vec<&'a [u8]> {
+  if per_mint {
+    vec![VAULT_AUTHORITY_SEED, lst_mint.as_ref(), bump]
+  } else {
+    vec![VAULT_AUTHORITY_SEED, bump]
+  }
+}
@@ -1,32 +1,62 @@
 //! Protocol-wide constants
 //! Centralized location for all configuration values
+//!
+//! Constants marked `#[constant]` are exported into the IDL so clients read
+//! them from there instead of hardcoding them. The attribute only takes
+//! literal-typed `const` items, so values owned by `laminar-math` are
+//! restated here rather than re-exported.
 
-// PRECISION CONSTANTS
-pub const SOL_PRECISION: u64 = 1_000_000_000;  // 1e9 lamports
-pub const USD_PRECISION: u64 = 1_000_000;       // 1e6 (6 decimals)
-pub const BPS_PRECISION: u64 = 10_000;          // 100% = 10000 bps
+use anchor_lang::prelude::*;
+
+// PRECISION
+#[constant]
+pub const SOL_PRECISION: u64 = laminar_math::SOL_PRECISION;
+#[constant]
+pub const USD_PRECISION: u64 = laminar_math::USD_PRECISION;
+#[constant]
+pub const BPS_PRECISION: u64 = laminar_math::BPS_PRECISION;
+#[constant]
+pub const AMUSD_DECIMALS: u8 = laminar_math::AMUSD_DECIMALS;
+#[constant]
+pub const SECONDS_PER_YEAR: u64 = laminar_math::SECONDS_PER_YEAR;
 
 // MINIMUM AMOUNTS 
-pub const MIN_LST_DEPOSIT: u64 = 100_000;       // 0.0001 SOL (100k lamports)
-pub const MIN_AMUSD_MINT: u64 = 1_000;          // 0.001 USD (1k micro-USD)
-pub const MIN_ASOL_MINT: u64 = 1_000_000;       // 0.001 SOL (1M lamports)
+#[constant]
+pub const MIN_LST_DEPOSIT: u64 = laminar_math::MIN_LST_DEPOSIT;
+#[constant]
+pub const MIN_AMUSD_MINT: u64 = laminar_math::MIN_AMUSD_MINT;
+#[constant]
+pub const MIN_ASOL_MINT: u64 = laminar_math::MIN_ASOL_MINT;
+#[constant]
+pub const MIN_NAV_LAMPORTS: u64 = laminar_math::MIN_NAV_LAMPORTS;
+#[constant]
 pub const MIN_PROTOCOL_TVL: u64 = 1_000_000;    // 0.001 SOL minimum TVL
-pub const MIN_NAV_LAMPORTS: u64 = 1_000;        // Minimum NAV for safe operations
 
 // FEE CONFIGURATION 
+#[constant]
 pub const AMUSD_MINT_FEE_BPS: u64 = 50;         // 0.5%
+#[constant]
 pub const AMUSD_REDEEM_FEE_BPS: u64 = 25;       // 0.25%
+#[constant]
 pub const ASOL_MINT_FEE_BPS: u64 = 30;          // 0.3%
+#[constant]
 pub const ASOL_REDEEM_FEE_BPS: u64 = 15;        // 0.15%
 
-// Dynamic fee multiplier cap when CR < target (1x = 10_000 bps)
-pub const MAX_FEE_MULTIPLIER_BPS: u64 = 40_000; // 4x max
+// Upper bound for any configurable base fee
+#[constant]
+pub const MAX_BASE_FEE_BPS: u64 = 1_000;        // 10%
+
+// Samples returned by `fee_curve`; odd so target_cr lands on the grid
+pub const FEE_CURVE_POINTS: usize = 17;
 
 // SLIPPAGE LIMITS 
+#[constant]
 pub const MAX_SLIPPAGE_BPS: u64 = 500;          // 5% max slippage
 
 // RISK PARAMETERS 
+#[constant]
 pub const DEFAULT_MIN_CR_BPS: u64 = 13_000;     // 130%
+#[constant]
 pub const DEFAULT_TARGET_CR_BPS: u64 = 15_000;  // 150%
 
 pub const MIN_TOLERANCE: u64 = 1_000;
@@ -35,11 +65,83 @@ pub const TOLERANCE_BPS: u64 = 1;
 pub const DEFAULT_MAX_ROUNDING_RESERVE_LAMPORTS: u64 = 10_000;
 
 pub const DEFAULT_MAX_ORACLE_STALENESS_SLOTS: u64 = 150;
+pub const DEFAULT_MAX_ORACLE_AGE_SECONDS: u64 = 60;  // ~150 slots at 400ms
+pub const MAX_EMA_HALF_LIFE_SLOTS: u64 = 216_000;    // ~1 day at 400ms
+// Discount to NAV a rebalance may pay out of the treasury
+pub const DEFAULT_REBALANCE_DISCOUNT_BPS: u64 = 100;  // 1%
+pub const MAX_REBALANCE_DISCOUNT_BPS: u64 = 500;      // 5%
+// Discount to NAV on aSOL mints while CR < min (0 = off)
+pub const DEFAULT_RECOVERY_BONUS_BPS: u64 = 0;
+pub const MAX_RECOVERY_BONUS_BPS: u64 = 1_000;        // 10%
+// Largest NAV drop one recovery-mode mint may cause existing aSOL holders
+pub const MAX_RECOVERY_DILUTION_BPS: u64 = 50;        // 0.5%
+// Share of mint/redeem fees booked to the insurance fund (0 = all to treasury)
+pub const DEFAULT_FEE_SPLIT_INSURANCE_BPS: u64 = 0;
+// Largest share of the vault one flash loan may take
+pub const DEFAULT_MAX_FLASH_LOAN_BPS: u64 = 5_000;    // 50%
+// Fee on flash-loaned LST, credited to aSOL equity on repayment
+#[constant]
+pub const FLASH_LOAN_FEE_BPS: u64 = 5;                // 0.05%
 pub const DEFAULT_MAX_CONF_BPS: u64 = 150;
 pub const DEFAULT_UNCERTAINTY_MAX_BPS: u64 = 20_000;
 pub const DEFAULT_MAX_LST_STALE_EPOCHS: u64 = 1;
+// Allowed disagreement between the primary and secondary SOL/USD feeds
+pub const DEFAULT_MAX_ORACLE_DEVIATION_BPS: u64 = 100;  // 1%
+// Cap on the uncertainty surcharge for redemptions priced from the fallback oracle
+pub const MAX_FALLBACK_UNCERTAINTY_BPS: u64 = 10_000;
+// `fallback_price_source` value meaning no fallback is configured
+pub const NO_FALLBACK_PRICE_SOURCE: u8 = u8::MAX;
+// Absolute sanity bounds; anything outside is a data error, not a market move
+pub const DEFAULT_MIN_SOL_PRICE_USD: u64 = 10_000;             // $0.01
+pub const DEFAULT_MAX_SOL_PRICE_USD: u64 = 1_000_000_000_000;  // $1M
+pub const DEFAULT_MIN_LST_RATE: u64 = SOL_PRECISION / 2;       // 0.5 SOL per LST
+pub const DEFAULT_MAX_LST_RATE: u64 = 10 * SOL_PRECISION;      // 10 SOL per LST
+// Max move of a calculator-reported LST rate per sync (LST rates drift, they don't jump)
+pub const MAX_LST_RATE_DELTA_BPS: u64 = 200;  // 2%
 pub const DEFAULT_NAV_FLOOR_LAMPORTS: u64 = 1_000_000;
 pub const DEFAULT_MAX_ASOL_MINT_PER_ROUND: u64 = 50_000 * SOL_PRECISION;
+pub const DEFAULT_MINT_ROUND_SLOTS: u64 = 9_000;  // ~1 hour at 400ms slots
+// A queue-head ticket this old may be cancelled by anyone while redemptions are open
+#[constant]
+pub const REDEMPTION_TICKET_EXPIRY_SLOTS: u64 = 216_000;  // ~1 day at 400ms slots
+// Redeem amount meaning the caller's whole token balance at execution time
+#[constant]
+pub const REDEEM_ALL: u64 = u64::MAX;
+// Peg Stability Module fee cap
+#[constant]
+pub const MAX_PSM_FEE_BPS: u64 = 100;                 // 1%
+// Stability fee: cap on the annual rate
+#[constant]
+pub const MAX_STABILITY_FEE_BPS_PER_YEAR: u64 = 2_000; // 20% a year
+// Redeem pause after which anyone may lift it (~7 days of 400ms slots)
+pub const DEFAULT_MAX_PAUSE_DURATION_SLOTS: u64 = 1_512_000;
+// Upper bound on the parameter timelock so governance cannot brick itself (~30 days)
+pub const MAX_PARAM_TIMELOCK_SLOTS: u64 = 6_480_000;
+// operation_counter at or above this came from a corrupt write, not traffic (~2.8e14 ops)
+pub const OPERATION_COUNTER_CEILING: u64 = 1 << 48;
 
+// FEE MULTIPLIER BOUNDS
+#[constant]
+pub const MAX_FEE_MULTIPLIER_BPS: u64 = laminar_math::MAX_FEE_MULTIPLIER_BPS;
+#[constant]
 pub const DEFAULT_FEE_MIN_MULTIPLIER_BPS: u64 = BPS_PRECISION;
-pub const DEFAULT_FEE_MAX_MULTIPLIER_BPS: u64 = MAX_FEE_MULTIPLIER_BPS;
\ No newline at end of file
+#[constant]
+pub const DEFAULT_FEE_MAX_MULTIPLIER_BPS: u64 = MAX_FEE_MULTIPLIER_BPS;
+// INSTRUCTION ARGS VERSIONING
+#[constant]
+pub const CURRENT_ARGS_VERSION: u8 = 5;         // Latest *Args struct layout
+pub const ENTRYPOINT_V1: u8 = 1;                // Positional-args entrypoints
+pub const ENTRYPOINT_V2: u8 = 2;                // Versioned-args entrypoints
+pub const ENTRYPOINT_ZAP: u8 = 3;               // Native-SOL zap entrypoints
+pub const ENTRYPOINT_TO_SOL: u8 = 4;            // Redeem-to-native-SOL entrypoints
+pub const ENTRYPOINT_QUEUE: u8 = 5;             // Redemption-queue processing
+pub const ENTRYPOINT_PAIR: u8 = 6;              // Paired mint or redemption of both tranches
+
+// PAIRED MINT AND REDEMPTION
+// Largest CR move a paired mint may cause: floor rounding plus the amUSD
+// leg's insurance fee share, which is never minted as debt
+pub const MAX_PAIR_CR_DRIFT_BPS: u64 = 10;
+// Initial `pair_cr_tolerance_bps`; the two redeem legs price SOL on opposite
+// sides of the confidence band and keep their fees in the book, so a paired
+// exit drifts further than a paired mint
+pub const DEFAULT_PAIR_CR_TOLERANCE_BPS: u64 = 50;
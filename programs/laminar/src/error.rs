@@ -98,4 +98,223 @@ pub enum LaminarError {
 
   #[msg("LST exchange-rate snapshot is stale - refresh exchange rate before pricing actions")]
   LstRateStale,
-} 
\ No newline at end of file
+
+  #[msg("Launch configuration is only allowed while paused and before any collateral or supply exists")]
+  ProtocolAlreadyLaunched,
+
+  #[msg("Treasury must be a co-signing system wallet or an acknowledged program-owned address")]
+  InvalidTreasury,
+
+  #[msg("Unsupported instruction args version")]
+  UnsupportedArgsVersion,
+
+  #[msg("Stored PDA bump does not re-derive to the account address")]
+  CorruptedBumpSeed,
+
+  #[msg("Signer is not the pending authority")]
+  InvalidPendingAuthority,
+
+  #[msg("Redemptions are not paused")]
+  RedeemNotPaused,
+
+  #[msg("Redeem pause has not exceeded max_pause_duration_slots")]
+  PauseTimeoutNotElapsed,
+
+  #[msg("Queued parameter change has not reached its ETA slot")]
+  TimelockNotElapsed,
+
+  #[msg("Parameter timelock is active; queue the change instead")]
+  TimelockActive,
+
+  #[msg("Protocol vault token account is frozen by the LST freeze authority")]
+  VaultFrozen,
+
+  #[msg("Price feed account is missing, not the configured feed, or malformed")]
+  InvalidPriceFeed,
+
+  #[msg("Pyth price update is older than max_oracle_staleness_slots")]
+  PythPriceStale,
+
+  #[msg("operation_counter cannot advance past u64::MAX")]
+  CounterSaturated,
+
+  #[msg("operation_counter is above the sanity ceiling; run migrate_global_state")]
+  OperationCounterCorrupt,
+
+  #[msg("Stake pool account is not the configured SPL stake pool for the supported LST")]
+  InvalidStakePool,
+
+  #[msg("Marinade state account is not the configured state for the supported LST")]
+  InvalidMarinadeState,
+
+  #[msg("amUSD redemption paused: insolvency-episode haircut shortfall exceeded its bound")]
+  HaircutBreakerTripped,
+
+  #[msg("LST calculator program is not a known Sanctum SOL value calculator")]
+  UnknownLstCalculator,
+
+  #[msg("LST calculator accounts or return data do not match the configured LST state")]
+  InvalidLstCalculatorState,
+
+  #[msg("LST rate moved outside the per-sync sanity band")]
+  LstRateOutOfBand,
+
+  #[msg("Primary and secondary SOL/USD prices disagree by more than max_oracle_deviation_bps")]
+  OracleDeviation,
+
+  #[msg("Price update moves the mock SOL price or LST rate past max_price_move_bps")]
+  PriceMoveTooLarge,
+
+  #[msg("Forced price updates require both mint and redeem to be paused")]
+  ForcedPriceUpdateNotPaused,
+
+  #[msg("Oracle SOL price or LST rate is outside the configured sanity bounds")]
+  OraclePriceOutOfBounds,
+
+  #[msg("Stability Pool withdrawals are closed while CR is below min_cr_bps")]
+  StabilityWithdrawalBelowMinCr,
+
+  #[msg("Withdrawal exceeds the depositor's Stability Pool position")]
+  InsufficientStabilityDeposit,
+
+  #[msg("CR is below min_cr_bps and the Stability Pool can still absorb it; run trigger_drawdown first")]
+  StabilityDrawdownPending,
+
+  #[msg("CR is at or above min_cr_bps; there is nothing for the Stability Pool to absorb")]
+  StabilityDrawdownNotNeeded,
+
+  #[msg("Stability Pool position has no LST gains to claim")]
+  NoStabilityGains,
+
+  #[msg("Stability Pool position is from an emptied epoch; pass that epoch's record")]
+  StabilityEpochRecordRequired,
+
+  #[msg("CR is at or above min_cr_bps; there is nothing to rebalance")]
+  RebalanceNotNeeded,
+
+  #[msg("Recovery mode: CR is below min_cr_bps, so amUSD mints and aSOL redemptions are closed")]
+  RecoveryMode,
+
+  #[msg("Protocol is in global settlement; only redemptions remain")]
+  ProtocolSettled,
+
+  #[msg("Protocol is not in global settlement")]
+  ProtocolNotSettled,
+
+  #[msg("amUSD has been written down; minting is closed")]
+  AmusdWrittenDown,
+
+  #[msg("CR is at or above 100%; there is no bad debt to socialize")]
+  NoBadDebt,
+
+  #[msg("Insurance fund must be exhausted before bad debt is socialized")]
+  InsuranceNotExhausted,
+
+  #[msg("A flash loan is outstanding; repay it before touching the vault")]
+  FlashLoanOutstanding,
+
+  #[msg("Flash loan exceeds max_flash_loan_bps of the vault")]
+  FlashLoanCapExceeded,
+
+  #[msg("Flash loan must be repaid by a later flash_repay_lst in the same transaction")]
+  FlashRepayMissing,
+
+  #[msg("No flash loan is outstanding")]
+  NoFlashLoanOutstanding,
+
+  #[msg("Mint is already collateral (primary LST, amUSD or aSOL)")]
+  CollateralAlreadyRegistered,
+
+  #[msg("Secondary collateral vaults must be emptied before settlement")]
+  CollateralVaultsNotEmpty,
+
+  #[msg("Deposits are disabled for this collateral type")]
+  CollateralDepositsDisabled,
+
+  #[msg("Every registered CollateralVault must be passed once, in ascending key order")]
+  CollateralVaultsIncomplete,
+
+  #[msg("Collateral migration would reduce TVL beyond the rounding bound")]
+  MigrationReducesTvl,
+
+  #[msg("Peg Stability Module has not been initialized")]
+  PsmNotInitialized,
+
+  #[msg("PSM swap would take psm_amusd_outstanding past psm_debt_ceiling")]
+  PsmDebtCeilingExceeded,
+
+  #[msg("PSM swap exceeds the amUSD the PSM vault backs")]
+  PsmLiquidityExhausted,
+
+  #[msg("Savings Pool and its amUSD vault must be passed while savings_fee_split_bps is set")]
+  SavingsPoolRequired,
+
+  #[msg("Withdrawal exceeds the depositor's Savings Pool shares")]
+  InsufficientSavingsDeposit,
+
+  #[msg("Mint would take amusd_supply past amusd_supply_cap")]
+  DebtCeilingExceeded,
+
+  #[msg("Mint would take asol_supply past asol_supply_cap")]
+  AsolSupplyCapExceeded,
+
+  #[msg("Deposit would take the vault's LST past max_total_lst_amount")]
+  TvlCapExceeded,
+
+  #[msg("Deposit exceeds max_single_deposit_lst")]
+  DepositTooLarge,
+
+  #[msg("Redemption exceeds max_single_redeem_units")]
+  RedeemTooLarge,
+
+  #[msg("A per-wallet limit is set; this instruction needs the caller's UserPosition")]
+  UserPositionRequired,
+
+  #[msg("Mint would take the wallet's amUSD past per_user_amusd_cap")]
+  PerUserCapExceeded,
+
+  #[msg("Mint would take this round's aSOL past max_asol_mint_per_round")]
+  MintRoundCapExceeded,
+
+  #[msg("Mint and redeem by one wallet must be min_action_gap_slots apart")]
+  ActionTooSoon,
+
+  #[msg("aSOL minted by this wallet is still within asol_redeem_cooldown_slots")]
+  AsolRedeemCooldownActive,
+
+  #[msg("Redemption tickets are processed in request order; this one is not at the head of the queue")]
+  RedemptionOutOfOrder,
+
+  #[msg("Redemption ticket was already cancelled")]
+  RedemptionTicketCancelled,
+
+  #[msg("Only the owner may cancel this ticket until it expires at the head of an open queue")]
+  RedemptionCancelNotAllowed,
+
+  #[msg("Redemption output is below the protocol minimum of 0.0001 SOL equivalent")]
+  RedeemOutputTooSmall,
+
+  #[msg("Order deadline slot has passed")]
+  DeadlineExceeded,
+
+  #[msg("Dynamic fee exceeds the order's max_fee_bps")]
+  FeeExceedsMax,
+
+  #[msg("Redeem to SOL pays the caller and takes no recipient account")]
+  RecipientNotSupported,
+
+  #[msg("Signer is neither the owner nor an approved delegate of the source token account")]
+  InvalidSourceAuthority,
+
+  #[msg("Paired mint or redemption moved CR outside its tolerance")]
+  PairCrDrift,
+
+  #[msg("Queued parameter change does not match this update")]
+  ParamChangeMismatch,
+
+  #[msg("Queued change is applied by its own setter once the timelock elapses")]
+  ParamChangeAppliedBySetter,
+
+  #[msg("Redeem to SOL out of the wSOL leg needs its unwrap account")]
+  SolUnwrapAccountRequired,
+}
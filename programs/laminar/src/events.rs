@@ -1,6 +1,20 @@
+//! Program events
+//! Every event carries `operation_counter`, read from GlobalState when it is
+//! emitted. An instruction's own event reads it after the state update, so
+//! back-to-back instructions emit consecutive values and a gap means a missed
+//! transaction. Events from shared steps that run before that update (stability
+//! fee accrual, oracle fallback, price-source migration) carry the previous
+//! value; instructions that never bump the counter (pool deposits, ticket
+//! cancels) repeat the current one.
+
 use anchor_lang::prelude::*;
+use anchor_lang::Event;
+
+use crate::state::{ParamChange, RecoveryTransition};
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProtocolInitialized {
   pub authority: Pubkey,
   pub amusd_mint: Pubkey,
@@ -8,91 +22,1352 @@ pub struct ProtocolInitialized {
   pub supported_lst_mint: Pubkey,
   pub min_cr_bps: u64,
   pub target_cr_bps: u64,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AmUSDMinted {
   pub user: Pubkey,
+  /// Token account the minted tokens were paid to
+  pub recipient: Pubkey,
+  /// LST the vault received, net of any transfer fee
   pub lst_deposited: u64,
   pub amusd_minted: u64,
   pub fee: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// CR multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub cr_multiplier_bps: u64,
+  /// Oracle/flash-loan uncertainty multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub uncertainty_multiplier_bps: u64,
+  /// Fee share booked to the insurance fund, in the fee token; not part of `fee`
+  pub insurance_fee: u64,
+  /// Part of `fee` deposited into the Savings Pool rather than the treasury
+  pub savings_fee: u64,
+  /// Room left under `amusd_supply_cap` after this mint; u64::MAX when uncapped
+  pub amusd_supply_headroom: u64,
   pub old_tvl: u64,
   pub new_tvl: u64,
   pub old_cr_bps: u64,
   pub new_cr_bps: u64,
   pub sol_price_used: u64,
+  /// Deadline slot the order carried; 0 for none
+  pub deadline_slot: u64,
+  /// Order reference the caller supplied; 0 for none
+  pub client_order_id: u64,
+  /// 1 = positional-args entrypoint, 2 = versioned-args entrypoint, 3 = SOL zap,
+  /// 6 = paired mint
+  pub entrypoint_version: u8,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AmUSDRedeemed {
   pub user: Pubkey,
+  /// Token account the LST was paid to
+  pub recipient: Pubkey,
   pub amusd_burned: u64,
   pub lst_received: u64,
   pub fee: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// CR multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub cr_multiplier_bps: u64,
+  /// Oracle/flash-loan uncertainty multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub uncertainty_multiplier_bps: u64,
+  /// Fee share booked to the insurance fund, in the fee token; not part of `fee`
+  pub insurance_fee: u64,
+  /// Part of `fee` deposited into the Savings Pool rather than the treasury
+  pub savings_fee: u64,
   pub old_tvl: u64,
   pub new_tvl: u64,
   pub old_cr_bps: u64,
   pub new_cr_bps: u64,
   pub sol_price_used: u64,
+  /// Share of the requested amount redeemed; below BPS_PRECISION only for a partial fill
+  pub fill_bps: u64,
+  /// Deadline slot the order carried; 0 for none
+  pub deadline_slot: u64,
+  /// Order reference the caller supplied; 0 for none
+  pub client_order_id: u64,
+  /// 1 = positional-args entrypoint, 2 = versioned-args entrypoint, 4 = redeem to SOL,
+  /// 5 = redemption queue, 6 = paired redemption
+  pub entrypoint_version: u8,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsolMinted {
   pub user: Pubkey,
+  /// Token account the minted tokens were paid to
+  pub recipient: Pubkey,
+  /// LST the vault received, net of any transfer fee
   pub lst_deposited: u64,
   pub asol_minted: u64,
   pub fee: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// CR multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub cr_multiplier_bps: u64,
+  /// Oracle/flash-loan uncertainty multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub uncertainty_multiplier_bps: u64,
+  /// Fee share booked to the insurance fund, in the fee token; not part of `fee`
+  pub insurance_fee: u64,
+  /// Recovery-mode aSOL included in `asol_minted` (0 outside recovery)
+  pub recovery_bonus: u64,
+  /// Room left under `asol_supply_cap` after this mint; u64::MAX when uncapped
+  pub asol_supply_headroom: u64,
+  /// Gross aSOL the current mint round can still take; u64::MAX when uncapped
+  pub round_capacity_remaining: u64,
   pub nav: u64,
   pub old_tvl: u64,
   pub new_tvl: u64,
   pub old_equity: u64,
   pub new_equity: u64,
   pub leverage_multiple: u64,
+  /// Deadline slot the order carried; 0 for none
+  pub deadline_slot: u64,
+  /// Order reference the caller supplied; 0 for none
+  pub client_order_id: u64,
+  /// 1 = positional-args entrypoint, 2 = versioned-args entrypoint, 3 = SOL zap,
+  /// 6 = paired mint
+  pub entrypoint_version: u8,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsolRedeemed {
   pub user: Pubkey,
+  /// Token account the LST was paid to
+  pub recipient: Pubkey,
   pub asol_burned: u64,
   pub lst_received: u64,
   pub fee: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// CR multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub cr_multiplier_bps: u64,
+  /// Oracle/flash-loan uncertainty multiplier behind `fee_bps`, 10_000 = 1.0x
+  pub uncertainty_multiplier_bps: u64,
+  /// Fee share booked to the insurance fund, in the fee token; not part of `fee`
+  pub insurance_fee: u64,
   pub nav: u64,
   pub old_tvl: u64,
   pub new_tvl: u64,
   pub old_equity: u64,
   pub new_equity: u64,
+  /// Share of the requested amount redeemed; below BPS_PRECISION only for a partial fill
+  pub fill_bps: u64,
+  /// Deadline slot the order carried; 0 for none
+  pub deadline_slot: u64,
+  /// Order reference the caller supplied; 0 for none
+  pub client_order_id: u64,
+  /// 1 = positional-args entrypoint, 2 = versioned-args entrypoint, 4 = redeem to SOL,
+  /// 5 = redemption queue, 6 = paired redemption
+  pub entrypoint_version: u8,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmusdConvertedToAsol {
+  pub user: Pubkey,
+  pub amusd_burned: u64,
+  pub asol_minted: u64,
+  pub fee: u64,
+  /// Fee share booked to the insurance fund, in aSOL; not part of `fee`
+  pub insurance_fee: u64,
+  pub nav: u64,
+  pub sol_price_used: u64,
+  pub old_cr_bps: u64,
+  pub new_cr_bps: u64,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsolConvertedToAmusd {
+  pub user: Pubkey,
+  pub asol_burned: u64,
+  pub amusd_minted: u64,
+  pub fee: u64,
+  /// Fee share booked to the insurance fund, in amUSD; not part of `fee`
+  pub insurance_fee: u64,
+  pub nav: u64,
+  pub sol_price_used: u64,
+  pub old_cr_bps: u64,
+  pub new_cr_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmergencyPause {
   pub authority: Pubkey,
   pub mint_paused: bool,
   pub redeem_paused: bool,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OraclePriceUpdated {
   pub authority: Pubkey,
   pub old_sol_price: u64,
   pub new_sol_price: u64,
   pub old_lst_rate: u64,
   pub new_lst_rate: u64,
+  /// Symmetric relative move of the SOL price, in bps
+  pub sol_price_move_bps: u64,
+  /// Symmetric relative move of the LST rate, in bps
+  pub lst_rate_move_bps: u64,
+  /// Applied through the paused `force` path, past `max_price_move_bps`
+  pub forced: bool,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
 
 #[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExchangeRateSynced {
+  pub slot: u64,
+  /// Cached LST rate after the sync; unchanged without a live rate source
+  pub lst_to_sol_rate: u64,
+  /// `last_tvl_update_slot` before this sync
+  pub previous_update_slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParametersUpdated {
   pub authority: Pubkey,
   pub old_min_cr_bps: u64,
   pub new_min_cr_bps: u64,
   pub old_target_cr_bps: u64,
   pub new_target_cr_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigChanged {
+  pub authority: Pubkey,
+  pub field: String,
+  pub old_value: u64,
+  pub new_value: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeParametersUpdated {
+  pub authority: Pubkey,
+  pub old_fee_amusd_mint_bps: u64,
+  pub new_fee_amusd_mint_bps: u64,
+  pub old_fee_amusd_redeem_bps: u64,
+  pub new_fee_amusd_redeem_bps: u64,
+  pub old_fee_asol_mint_bps: u64,
+  pub new_fee_asol_mint_bps: u64,
+  pub old_fee_asol_redeem_bps: u64,
+  pub new_fee_asol_redeem_bps: u64,
+  pub old_fee_min_multiplier_bps: u64,
+  pub new_fee_min_multiplier_bps: u64,
+  pub old_fee_max_multiplier_bps: u64,
+  pub new_fee_max_multiplier_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryUpdated {
+  pub authority: Pubkey,
+  pub old_treasury: Pubkey,
+  pub new_treasury: Pubkey,
+  pub treasury_is_pda: bool,
+  pub treasury_owner_program: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleParametersUpdated {
+  pub authority: Pubkey,
+  pub old_max_oracle_staleness_slots: u64,
+  pub new_max_oracle_staleness_slots: u64,
+  pub old_max_conf_bps: u64,
+  pub new_max_conf_bps: u64,
+  pub old_uncertainty_max_bps: u64,
+  pub new_uncertainty_max_bps: u64,
+  pub old_max_lst_stale_epochs: u64,
+  pub new_max_lst_stale_epochs: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleBoundsUpdated {
+  pub authority: Pubkey,
+  pub old_min_sol_price_usd: u64,
+  pub new_min_sol_price_usd: u64,
+  pub old_max_sol_price_usd: u64,
+  pub new_max_sol_price_usd: u64,
+  pub old_min_lst_rate: u64,
+  pub new_min_lst_rate: u64,
+  pub old_max_lst_rate: u64,
+  pub new_max_lst_rate: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundingReserveCapUpdated {
+  pub authority: Pubkey,
+  pub old_max_rounding_reserve_lamports: u64,
+  pub new_max_rounding_reserve_lamports: u64,
+  pub rounding_reserve_lamports: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorityProposed {
+  pub authority: Pubkey,
+  pub previous_pending_authority: Pubkey,
+  pub pending_authority: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorityAccepted {
+  pub old_authority: Pubkey,
+  pub new_authority: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DustDonated {
+  pub user: Pubkey,
+  pub mint: Pubkey,
+  pub amount_burned: u64,
+  pub donated_value_lamports: u64,
+  pub reserve_credit: u64,
+  pub new_rounding_reserve: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedeemForceUnpaused {
+  pub caller: Pubkey,
+  pub pause_started_slot: u64,
+  pub max_pause_duration_slots: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxPauseDurationUpdated {
+  pub authority: Pubkey,
+  pub old_max_pause_duration_slots: u64,
+  pub new_max_pause_duration_slots: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterUpdateQueued {
+  pub authority: Pubkey,
+  pub change: ParamChange,
+  pub queued_slot: u64,
+  pub eta_slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterUpdateExecuted {
+  pub executor: Pubkey,
+  pub change: ParamChange,
+  pub eta_slot: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterUpdateCancelled {
+  pub authority: Pubkey,
+  pub change: ParamChange,
+  pub eta_slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VaultFrozenDetected {
+  pub caller: Pubkey,
+  pub vault: Pubkey,
+  pub lst_mint: Pubkey,
+  /// True when this check flipped `redeem_paused` on
+  pub redeem_paused_now: bool,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthSnapshot {
+  pub tvl_sol: u64,
+  pub liability_sol: u64,
+  pub cr_bps: u64,
+  pub total_lst_amount: u64,
+  pub vault_balance: u64,
+  pub rounding_reserve_lamports: u64,
+  pub insurance_fund_lamports: u64,
+  pub vault_frozen: bool,
+  pub lst_mint_has_freeze_authority: bool,
+  pub mint_paused: bool,
+  pub redeem_paused: bool,
+  pub slot: u64,
+  pub operation_counter: u64,
   pub timestamp: i64,
 }
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PythPriceFeedUpdated {
+  pub authority: Pubkey,
+  pub old_pyth_price_feed: Pubkey,
+  pub new_pyth_price_feed: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperationCounterReset {
+  pub authority: Pubkey,
+  pub old_operation_counter: u64,
+  pub new_operation_counter: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwitchboardFeedUpdated {
+  pub authority: Pubkey,
+  pub old_switchboard_feed: Pubkey,
+  pub new_switchboard_feed: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LstStakePoolUpdated {
+  pub authority: Pubkey,
+  pub old_lst_stake_pool: Pubkey,
+  pub new_lst_stake_pool: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarinadeStateUpdated {
+  pub authority: Pubkey,
+  pub old_marinade_state: Pubkey,
+  pub new_marinade_state: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HaircutApplied {
+  pub user: Pubkey,
+  pub amusd_burned: u64,
+  /// SOL value owed at par
+  pub par_sol_value: u64,
+  /// SOL value actually paid out
+  pub paid_sol_value: u64,
+  pub shortfall_lamports: u64,
+  /// Lamports the insurance fund added back toward par
+  pub insurance_lamports: u64,
+  pub haircut_bps: u64,
+  pub episode_shortfall_lamports: u64,
+  pub cumulative_shortfall_lamports: u64,
+  /// This redemption pushed the episode past `max_episode_haircut_lamports`
+  pub breaker_tripped: bool,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HaircutEpisodeClosed {
+  pub episode_shortfall_lamports: u64,
+  pub cumulative_shortfall_lamports: u64,
+  pub cr_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundingFallback {
+  /// `FeeAction` wire encoding of the redeem: 1 = redeem_amusd, 3 = redeem_asol
+  pub instruction: u8,
+  /// Lamports the user-favoring rounding would have taken from the reserve
+  pub requested_debit: u64,
+  /// Rounding reserve at the time, short of `requested_debit`
+  pub reserve_available: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HaircutBreakerUpdated {
+  pub authority: Pubkey,
+  pub old_max_episode_haircut_lamports: u64,
+  pub new_max_episode_haircut_lamports: u64,
+  /// A tripped breaker was reset by this update
+  pub redeem_resumed: bool,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LstCalculatorUpdated {
+  pub authority: Pubkey,
+  pub old_lst_calculator_program: Pubkey,
+  pub new_lst_calculator_program: Pubkey,
+  pub old_lst_calculator_state: Pubkey,
+  pub new_lst_calculator_state: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceSourceUpdated {
+  pub authority: Pubkey,
+  pub old_price_source: u8,
+  pub new_price_source: u8,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecondaryPriceSourceUpdated {
+  pub authority: Pubkey,
+  pub old_secondary_price_source: u8,
+  pub new_secondary_price_source: u8,
+  pub old_max_oracle_deviation_bps: u64,
+  pub new_max_oracle_deviation_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleConfidenceUpdated {
+  pub authority: Pubkey,
+  pub old_confidence_usd: u64,
+  pub new_confidence_usd: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FallbackPriceSourceUpdated {
+  pub authority: Pubkey,
+  pub old_fallback_price_source: Option<u8>,
+  pub new_fallback_price_source: Option<u8>,
+  pub old_fallback_uncertainty_bps: u64,
+  pub new_fallback_uncertainty_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FallbackOracleUsed {
+  pub user: Pubkey,
+  pub primary_price_source: u8,
+  pub fallback_price_source: u8,
+  pub sol_price_usd: u64,
+  pub uncertainty_index_bps: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmaHalfLifeUpdated {
+  pub authority: Pubkey,
+  pub old_ema_half_life_slots: u64,
+  pub new_ema_half_life_slots: u64,
+  pub ema_sol_price_usd: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxOracleAgeUpdated {
+  pub authority: Pubkey,
+  pub old_max_oracle_age_seconds: u64,
+  pub new_max_oracle_age_seconds: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxPriceMoveUpdated {
+  pub authority: Pubkey,
+  pub old_max_price_move_bps: u64,
+  pub new_max_price_move_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityPoolInitialized {
+  pub authority: Pubkey,
+  pub stability_pool: Pubkey,
+  pub amusd_vault: Pubkey,
+  pub lst_vault: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityDeposited {
+  pub user: Pubkey,
+  pub amusd_deposited: u64,
+  pub shares_issued: u64,
+  pub user_shares: u64,
+  pub total_pool_amusd: u64,
+  pub total_shares: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityWithdrawn {
+  pub user: Pubkey,
+  pub amusd_withdrawn: u64,
+  pub shares_burned: u64,
+  pub lst_gain_paid: u64,
+  pub user_shares: u64,
+  pub total_pool_amusd: u64,
+  pub total_shares: u64,
+  pub cr_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityDrawdown {
+  pub keeper: Pubkey,
+  pub amusd_burned: u64,
+  pub lst_to_pool: u64,
+  pub old_cr_bps: u64,
+  pub new_cr_bps: u64,
+  pub sol_price_used: u64,
+  pub total_pool_amusd: u64,
+  pub epoch: u64,
+  pub epoch_closed: bool,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityGainsClaimed {
+  pub user: Pubkey,
+  pub lst_claimed: u64,
+  /// Epoch the position was settled from
+  pub settled_epoch: u64,
+  /// Pool epoch the position now sits in
+  pub current_epoch: u64,
+  pub lst_gain_snapshot: u128,
+  pub user_shares: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rebalanced {
+  pub caller: Pubkey,
+  /// LST the vault received, net of any transfer fee
+  pub lst_deposited: u64,
+  /// aSOL minted at NAV
+  pub asol_minted: u64,
+  /// aSOL paid on top out of the treasury's delegation
+  pub treasury_bonus: u64,
+  pub nav: u64,
+  /// Effective discount to NAV the caller received
+  pub discount_bps: u64,
+  pub old_cr_bps: u64,
+  pub new_cr_bps: u64,
+  pub sol_price_used: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RebalanceDiscountUpdated {
+  pub authority: Pubkey,
+  pub old_rebalance_discount_bps: u64,
+  pub new_rebalance_discount_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryModeEntered {
+  pub cr_bps: u64,
+  pub min_cr_bps: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryModeExited {
+  pub cr_bps: u64,
+  pub min_cr_bps: u64,
+  pub entered_slot: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryBonusUpdated {
+  pub authority: Pubkey,
+  pub old_recovery_bonus_bps: u64,
+  pub new_recovery_bonus_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeSplitUpdated {
+  pub authority: Pubkey,
+  pub old_fee_split_insurance_bps: u64,
+  pub new_fee_split_insurance_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BadDebtSocialized {
+  pub authority: Pubkey,
+  pub old_redemption_rate_bps: u64,
+  pub new_redemption_rate_bps: u64,
+  pub cr_bps_before: u64,
+  pub cr_bps_after: u64,
+  pub amusd_supply: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementInitiated {
+  pub authority: Pubkey,
+  pub sol_price_usd: u64,
+  pub lst_to_sol_rate: u64,
+  pub total_lst_amount: u64,
+  pub amusd_supply: u64,
+  pub asol_supply: u64,
+  pub cr_bps: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementClaimed {
+  pub user: Pubkey,
+  pub amusd_burned: u64,
+  pub asol_burned: u64,
+  pub lst_received: u64,
+  pub remaining_lst: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceFunded {
+  pub funder: Pubkey,
+  pub lst_amount: u64,
+  pub value_lamports: u64,
+  pub insurance_fund_lamports: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoanTaken {
+  pub borrower: Pubkey,
+  pub receiver: Pubkey,
+  pub lst_amount: u64,
+  pub vault_balance: u64,
+  /// Peak utilization recorded for this slot after the loan
+  pub utilization_bps: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoanRepaid {
+  pub payer: Pubkey,
+  pub lst_amount: u64,
+  /// Fee in LST, kept in the vault as aSOL equity
+  pub fee: u64,
+  pub total_lst_amount: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollateralTypeAdded {
+  pub authority: Pubkey,
+  pub lst_mint: Pubkey,
+  pub collateral_vault: Pubkey,
+  pub vault: Pubkey,
+  pub vault_authority: Pubkey,
+  pub lst_to_sol_rate: u64,
+  pub collateral_weight_bps: u64,
+  /// The mint can freeze the new vault
+  pub lst_mint_has_freeze_authority: bool,
+  pub collateral_type_count: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollateralStatusUpdated {
+  pub authority: Pubkey,
+  pub lst_mint: Pubkey,
+  pub old_status: u8,
+  pub new_status: u8,
+  pub collateral_tvl_lamports: u64,
+  pub deprecated_collateral_tvl_lamports: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TvlRefreshed {
+  pub caller: Pubkey,
+  pub vault_count: u64,
+  pub old_collateral_tvl_lamports: u64,
+  pub new_collateral_tvl_lamports: u64,
+  pub weighted_collateral_tvl_lamports: u64,
+  pub deprecated_collateral_tvl_lamports: u64,
+  pub slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollateralMigrated {
+  pub authority: Pubkey,
+  pub from_lst_mint: Pubkey,
+  pub to_lst_mint: Pubkey,
+  pub from_lst_amount: u64,
+  pub to_lst_amount: u64,
+  pub value_lamports: u64,
+  pub old_tvl_lamports: u64,
+  pub new_tvl_lamports: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsmInitialized {
+  pub authority: Pubkey,
+  pub usdc_mint: Pubkey,
+  pub psm_vault: Pubkey,
+  pub psm_fee_bps: u64,
+  pub psm_debt_ceiling: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsmParametersUpdated {
+  pub authority: Pubkey,
+  pub old_psm_fee_bps: u64,
+  pub new_psm_fee_bps: u64,
+  pub old_psm_debt_ceiling: u64,
+  pub new_psm_debt_ceiling: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsmUsdcSwappedForAmusd {
+  pub user: Pubkey,
+  pub usdc_in: u64,
+  /// amUSD the deposit backs: `amusd_out + fee`
+  pub amusd_minted: u64,
+  pub amusd_out: u64,
+  /// amUSD minted to the treasury
+  pub fee: u64,
+  pub psm_amusd_outstanding: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsmAmusdSwappedForUsdc {
+  pub user: Pubkey,
+  pub amusd_in: u64,
+  /// `amusd_in - fee`, retired from `psm_amusd_outstanding`
+  pub amusd_burned: u64,
+  /// amUSD moved to the treasury
+  pub fee: u64,
+  pub usdc_out: u64,
+  pub psm_amusd_outstanding: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavingsPoolInitialized {
+  pub authority: Pubkey,
+  pub savings_pool: Pubkey,
+  pub amusd_vault: Pubkey,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavingsFeeSplitUpdated {
+  pub authority: Pubkey,
+  pub old_savings_fee_split_bps: u64,
+  pub new_savings_fee_split_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavingsDeposited {
+  pub user: Pubkey,
+  pub amusd_deposited: u64,
+  pub shares_issued: u64,
+  pub user_shares: u64,
+  pub total_pool_amusd: u64,
+  pub total_shares: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavingsWithdrawn {
+  pub user: Pubkey,
+  pub shares_burned: u64,
+  pub amusd_withdrawn: u64,
+  pub user_shares: u64,
+  pub total_pool_amusd: u64,
+  pub total_shares: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityFeeAccrued {
+  /// amUSD minted to the treasury
+  pub amusd_accrued: u64,
+  pub new_amusd_supply: u64,
+  pub stability_fee_bps_per_year: u64,
+  /// Seconds the accrual covered
+  pub elapsed_seconds: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityFeeUpdated {
+  pub authority: Pubkey,
+  pub old_stability_fee_bps_per_year: u64,
+  pub new_stability_fee_bps_per_year: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupplyCapsUpdated {
+  pub authority: Pubkey,
+  pub old_amusd_supply_cap: u64,
+  pub new_amusd_supply_cap: u64,
+  pub old_asol_supply_cap: u64,
+  pub new_asol_supply_cap: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TvlCapUpdated {
+  pub authority: Pubkey,
+  pub old_max_total_lst_amount: u64,
+  pub new_max_total_lst_amount: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionLimitsUpdated {
+  pub authority: Pubkey,
+  pub old_max_single_deposit_lst: u64,
+  pub new_max_single_deposit_lst: u64,
+  pub old_max_single_redeem_units: u64,
+  pub new_max_single_redeem_units: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerUserCapUpdated {
+  pub authority: Pubkey,
+  pub old_per_user_amusd_cap: u64,
+  pub new_per_user_amusd_cap: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintRoundUpdated {
+  pub authority: Pubkey,
+  pub old_max_asol_mint_per_round: u64,
+  pub new_max_asol_mint_per_round: u64,
+  pub old_mint_round_slots: u64,
+  pub new_mint_round_slots: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinActionGapUpdated {
+  pub authority: Pubkey,
+  pub old_min_action_gap_slots: u64,
+  pub new_min_action_gap_slots: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsolRedeemCooldownUpdated {
+  pub authority: Pubkey,
+  pub old_asol_redeem_cooldown_slots: u64,
+  pub new_asol_redeem_cooldown_slots: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairCrToleranceUpdated {
+  pub authority: Pubkey,
+  pub old_pair_cr_tolerance_bps: u64,
+  pub new_pair_cr_tolerance_bps: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedemptionRequested {
+  pub owner: Pubkey,
+  pub ticket: Pubkey,
+  pub sequence: u64,
+  /// amUSD or aSOL mint of the escrowed tokens
+  pub token_mint: Pubkey,
+  pub amount: u64,
+  pub min_lst_out: u64,
+  pub request_slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedemptionCancelled {
+  pub owner: Pubkey,
+  /// Owner, or anyone once the ticket has expired at the head of the queue
+  pub cancelled_by: Pubkey,
+  pub ticket: Pubkey,
+  pub sequence: u64,
+  pub amount_returned: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedemptionProcessed {
+  pub owner: Pubkey,
+  pub processed_by: Pubkey,
+  pub ticket: Pubkey,
+  pub sequence: u64,
+  /// Tokens redeemed; 0 when the ticket had been cancelled and is only cleared
+  pub amount: u64,
+  pub request_slot: u64,
+  pub processed_slot: u64,
+  pub operation_counter: u64,
+  pub timestamp: i64,
+}
+
+/// `emit_cpi!`, or plain `emit!` under the `emit-log` feature. Needs `ctx`
+/// in scope with the `#[event_cpi]` accounts.
+#[cfg(not(feature = "emit-log"))]
+pub(crate) use anchor_lang::prelude::emit_cpi as emit_event;
+#[cfg(feature = "emit-log")]
+pub(crate) use anchor_lang::prelude::emit as emit_event;
+
+/// Seed of the `event_authority` PDA `#[event_cpi]` adds
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// The `#[event_cpi]` accounts of an instruction, for emitting from shared
+/// code that has no `Context` in scope.
+#[derive(Clone)]
+pub struct EventCpi<'info> {
+  pub event_authority: AccountInfo<'info>,
+  pub bump: u8,
+}
+
+impl<'info> EventCpi<'info> {
+  pub fn new(event_authority: &AccountInfo<'info>, bump: u8) -> Self {
+    Self { event_authority: event_authority.clone(), bump }
+  }
+
+  /// Emit `event` the way `emit_event!` does.
+  #[cfg(not(feature = "emit-log"))]
+  pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+    let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+      .iter()
+      .copied()
+      .chain(event.data())
+      .collect();
+    let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+      crate::ID,
+      &data,
+      vec![AccountMeta::new_readonly(self.event_authority.key(), true)],
+    );
+    anchor_lang::solana_program::program::invoke_signed(
+      &ix,
+      std::slice::from_ref(&self.event_authority),
+      &[&[EVENT_AUTHORITY_SEED, &[self.bump]]],
+    )?;
+    Ok(())
+  }
+
+  /// Emit `event` the way `emit_event!` does.
+  #[cfg(feature = "emit-log")]
+  pub fn emit<E: Event>(&self, event: E) -> Result<()> {
+    emit!(event);
+    Ok(())
+  }
+}
+
+/// Emit the event for a recovery-mode crossing, if there was one.
+pub fn emit_recovery_transition(
+  event_cpi: &EventCpi,
+  transition: Option<RecoveryTransition>,
+  cr_bps: u64,
+  min_cr_bps: u64,
+  operation_counter: u64,
+  clock: &Clock,
+) -> Result<()> {
+  match transition {
+    Some(RecoveryTransition::Entered) => {
+      msg!("Recovery mode entered: CR {} < {} bps", cr_bps, min_cr_bps);
+      event_cpi.emit(RecoveryModeEntered {
+        cr_bps,
+        min_cr_bps,
+        slot: clock.slot,
+        operation_counter,
+        timestamp: clock.unix_timestamp,
+      })
+    }
+    Some(RecoveryTransition::Exited { entered_slot }) => {
+      msg!("Recovery mode exited: CR {} >= {} bps", cr_bps, min_cr_bps);
+      event_cpi.emit(RecoveryModeExited {
+        cr_bps,
+        min_cr_bps,
+        entered_slot,
+        slot: clock.slot,
+        operation_counter,
+        timestamp: clock.unix_timestamp,
+      })
+    }
+    None => Ok(()),
+  }
+}
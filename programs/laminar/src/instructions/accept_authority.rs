@@ -0,0 +1,48 @@
+//! accept_authority instruction - second step of the two-step admin transfer
+//! The pending authority must sign; it then replaces `authority` and the
+//! pending slot is cleared.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, AuthorityAccepted}, state::*};
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let old_authority = global_state.authority;
+  let new_authority = ctx.accounts.pending_authority.key();
+
+  global_state.authority = new_authority;
+  global_state.pending_authority = Pubkey::default();
+  global_state.increment_operation_counter()?;
+
+  emit_event!(AuthorityAccepted {
+    old_authority,
+    new_authority,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Authority transferred: {} -> {}", old_authority, new_authority);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+  #[account(mut)]
+  pub pending_authority: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.pending_authority != Pubkey::default() @ LaminarError::InvalidPendingAuthority,
+    constraint = global_state.pending_authority == pending_authority.key() @ LaminarError::InvalidPendingAuthority,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
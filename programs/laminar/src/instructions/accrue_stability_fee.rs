@@ -0,0 +1,107 @@
+//! accrue_stability_fee instruction - permissionless crank for the stability fee
+//! Mints the amUSD fee accrued on outstanding debt since the last accrual to
+//! the treasury and books it into `amusd_supply`, so liability and CR carry
+//! it from then on. mint_amusd and redeem_amusd run the same accrual at the
+//! top; this crank keeps the supply current for every other path.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::{error::LaminarError, events::{EventCpi, StabilityFeeAccrued}, state::*};
+
+/// Accrue the stability fee into `global_state` and mint it to the
+/// treasury. Returns the amUSD minted; zero mints nothing.
+pub fn accrue_stability_fee_to_treasury<'info>(
+  global_state: &mut Account<'info, GlobalState>,
+  amusd_mint: &InterfaceAccount<'info, Mint>,
+  treasury_amusd_account: &InterfaceAccount<'info, TokenAccount>,
+  token_program: &Interface<'info, TokenInterface>,
+  clock: &Clock,
+  event_cpi: &EventCpi<'info>,
+) -> Result<u64> {
+  let last_accrual_timestamp = global_state.last_accrual_timestamp;
+  let accrued = global_state.accrue_stability_fee(clock.unix_timestamp)?;
+  if accrued == 0 {
+    return Ok(0);
+  }
+
+  let seeds = &[GLOBAL_STATE_SEED, &[global_state.bump]];
+  let signer = &[&seeds[..]];
+  let mint_to_treasury = MintTo {
+    mint: amusd_mint.to_account_info(),
+    to: treasury_amusd_account.to_account_info(),
+    authority: global_state.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), mint_to_treasury, signer);
+  token_interface::mint_to(cpi_ctx, accrued)?;
+  msg!("Accrued {} amUSD stability fee to treasury", accrued);
+
+  event_cpi.emit(StabilityFeeAccrued {
+    amusd_accrued: accrued,
+    new_amusd_supply: global_state.amusd_supply,
+    stability_fee_bps_per_year: global_state.stability_fee_bps_per_year,
+    elapsed_seconds: clock.unix_timestamp.saturating_sub(last_accrual_timestamp) as u64,
+    operation_counter: global_state.operation_counter,
+    timestamp: clock.unix_timestamp,
+  })?;
+
+  Ok(accrued)
+}
+
+pub fn handler(ctx: Context<AccrueStabilityFee>) -> Result<()> {
+  ctx.accounts.global_state.validate_version()?;
+
+  let accrued = accrue_stability_fee_to_treasury(
+    &mut ctx.accounts.global_state,
+    &ctx.accounts.amusd_mint,
+    &ctx.accounts.treasury_amusd_account,
+    &ctx.accounts.token_program,
+    &ctx.accounts.clock,
+    &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+  )?;
+  ctx.accounts.global_state.increment_operation_counter()?;
+
+  ctx.accounts.amusd_mint.reload()?;
+  require!(
+    ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_mint_supply()?,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Stability fee accrued: {} amUSD", accrued);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccrueStabilityFee<'info> {
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(mut)]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// Treasury's amUSD token account (receives the fee)
+  #[account(
+    mut,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,136 @@
+//! add_collateral_type instruction - whitelist another LST as collateral (admin only)
+//! Creates the mint's `CollateralVault` PDA, its vault authority PDA
+//! [VAULT_AUTHORITY_SEED, lst_mint] and that authority's vault ATA. From then
+//! on mint/redeem accept the mint when its `CollateralVault` is passed. The
+//! primary LST stays in GlobalState and needs no registration.
+//! `collateral_weight_bps` is the share of the vault's value CR counts.
+//! wSOL (`spl_token::native_mint`) registers the same way, at a fixed rate of
+//! `SOL_PRECISION`. A mint with a freeze authority is accepted but flagged on
+//! its `CollateralVault`, like the primary LST is at initialize.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token::spl_token, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, CollateralTypeAdded};
+use crate::math::{BPS_PRECISION, SOL_PRECISION};
+use crate::state::*;
+
+pub fn handler(ctx: Context<AddCollateralType>, lst_to_sol_rate: u64, collateral_weight_bps: u64) -> Result<()> {
+  let lst_mint = ctx.accounts.lst_mint.key();
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  require!(
+    lst_mint != global_state.supported_lst_mint
+      && lst_mint != global_state.amusd_mint
+      && lst_mint != global_state.asol_mint,
+    LaminarError::CollateralAlreadyRegistered
+  );
+  require!(ctx.accounts.lst_mint.decimals == 9, LaminarError::InvalidDecimals);
+  if lst_mint == spl_token::native_mint::ID {
+    // wSOL is SOL; there is no other rate for it to have.
+    require!(lst_to_sol_rate == SOL_PRECISION, LaminarError::InvalidParameter);
+  } else {
+    // Same floor as the primary LST at initialize
+    require!(lst_to_sol_rate >= SOL_PRECISION / 2, LaminarError::InvalidParameter);
+    global_state.assert_lst_rate_within_bounds(lst_to_sol_rate)?;
+  }
+  require!(
+    collateral_weight_bps > 0 && collateral_weight_bps <= BPS_PRECISION,
+    LaminarError::InvalidParameter
+  );
+
+  let collateral_vault = &mut ctx.accounts.collateral_vault;
+  collateral_vault.lst_mint = lst_mint;
+  collateral_vault.vault = ctx.accounts.vault.key();
+  collateral_vault.vault_authority = ctx.accounts.vault_authority.key();
+  collateral_vault.bump = ctx.bumps.collateral_vault;
+  collateral_vault.vault_authority_bump = ctx.bumps.vault_authority;
+  collateral_vault.lst_amount = 0;
+  collateral_vault.lst_to_sol_rate = lst_to_sol_rate;
+  collateral_vault.status = CollateralStatus::Active as u8;
+  collateral_vault.collateral_weight_bps = collateral_weight_bps;
+  collateral_vault.lst_mint_has_freeze_authority = ctx.accounts.lst_mint.freeze_authority.is_some();
+  collateral_vault._reserved = [0; 8];
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.collateral_type_count = global_state.collateral_type_count
+    .checked_add(1)
+    .ok_or(LaminarError::MathOverflow)?;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(CollateralTypeAdded {
+    authority: ctx.accounts.authority.key(),
+    lst_mint,
+    collateral_vault: ctx.accounts.collateral_vault.key(),
+    vault: ctx.accounts.vault.key(),
+    vault_authority: ctx.accounts.vault_authority.key(),
+    lst_to_sol_rate,
+    collateral_weight_bps,
+    lst_mint_has_freeze_authority: ctx.accounts.collateral_vault.lst_mint_has_freeze_authority,
+    collateral_type_count: global_state.collateral_type_count,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Collateral type added: {} at rate {}, weight {}bps", lst_mint, lst_to_sol_rate, collateral_weight_bps);
+  if ctx.accounts.collateral_vault.lst_mint_has_freeze_authority {
+    msg!("WARNING: {} has a freeze authority; a frozen vault blocks its redemptions", lst_mint);
+  }
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddCollateralType<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// CollateralVault PDA for `lst_mint`; `init` rejects a second registration
+  #[account(
+    init,
+    payer = authority,
+    space = CollateralVault::LEN,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump
+  )]
+  pub collateral_vault: Box<Account<'info, CollateralVault>>,
+
+  /// Collateral vault - holds this LST
+  /// Deterministic ATA owned by the per-mint vault_authority PDA
+  #[account(
+    init,
+    payer = authority,
+    associated_token::mint = lst_mint,
+    associated_token::authority = vault_authority,
+    associated_token::token_program = token_program,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// The LST mint being whitelisted
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CHECK: PDA will be validated by the seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED, lst_mint.key().as_ref()],
+    bump
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
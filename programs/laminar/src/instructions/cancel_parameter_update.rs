@@ -0,0 +1,52 @@
+//! cancel_parameter_update instruction - admin aborts a queued change
+//! Rent goes back to the current authority.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, ParameterUpdateCancelled}, state::*};
+
+pub fn handler(ctx: Context<CancelParameterUpdate>, _kind: u8) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let pending = &ctx.accounts.pending_param_change;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(ParameterUpdateCancelled {
+    authority: ctx.accounts.authority.key(),
+    change: pending.change.clone(),
+    eta_slot: pending.eta_slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Parameter update cancelled: kind={}", pending.change.kind());
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: u8)]
+pub struct CancelParameterUpdate<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [PENDING_PARAM_CHANGE_SEED, kind.to_le_bytes().as_ref()],
+    bump = pending_param_change.bump,
+    close = authority
+  )]
+  pub pending_param_change: Box<Account<'info, PendingParamChange>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,109 @@
+//! cancel_redemption instruction - hand a queued redemption's tokens back
+//! The escrow is emptied into the owner's token account and the ticket stays
+//! in the queue with nothing left to redeem; process_redemption clears it
+//! and returns its rent when the queue reaches it.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, RedemptionCancelled};
+use crate::invariants::*;
+use crate::redemption::assert_can_cancel;
+use crate::state::*;
+
+pub fn handler(ctx: Context<CancelRedemption>) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  let ticket = &ctx.accounts.redemption_ticket;
+  require!(ticket.amount > 0, LaminarError::RedemptionTicketCancelled);
+  assert_can_cancel(&ctx.accounts.global_state, ticket, &ctx.accounts.caller.key(), ctx.accounts.clock.slot)?;
+
+  let amount = ticket.amount;
+  let sequence = ticket.sequence;
+  let sequence_bytes = sequence.to_le_bytes();
+  let seeds = &[REDEMPTION_TICKET_SEED, sequence_bytes.as_ref(), &[ticket.bump]];
+  let signer = &[&seeds[..]];
+
+  // State update
+  ctx.accounts.redemption_ticket.amount = 0;
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.escrow.to_account_info(),
+    mint: ctx.accounts.token_mint.to_account_info(),
+    to: ctx.accounts.owner_token_account.to_account_info(),
+    authority: ctx.accounts.redemption_ticket.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+  msg!("Cancelled redemption #{}: {} tokens returned", sequence, amount);
+
+  emit_event!(RedemptionCancelled {
+    owner: ctx.accounts.redemption_ticket.owner,
+    cancelled_by: ctx.accounts.caller.key(),
+    ticket: ctx.accounts.redemption_ticket.key(),
+    sequence,
+    amount_returned: amount,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRedemption<'info> {
+  /// Ticket owner, or anyone once the ticket has expired at the queue head
+  #[account(mut)]
+  pub caller: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [REDEMPTION_TICKET_SEED, redemption_ticket.sequence.to_le_bytes().as_ref()],
+    bump = redemption_ticket.bump,
+  )]
+  pub redemption_ticket: Box<Account<'info, RedemptionTicket>>,
+
+  /// CHECK: matched to the ticket's owner
+  #[account(address = redemption_ticket.owner @ LaminarError::InvalidAccountState)]
+  pub owner: UncheckedAccount<'info>,
+
+  #[account(address = redemption_ticket.token_mint @ LaminarError::InvalidMint)]
+  pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// Ticket escrow (source of the refund)
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = redemption_ticket,
+    associated_token::token_program = token_program,
+  )]
+  pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Owner's ATA for the escrowed token (receives the refund)
+  #[account(
+    init_if_needed,
+    payer = caller,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+    associated_token::token_program = token_program,
+  )]
+  pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,224 @@
+//! claim_settlement instruction - pro-rata exit from a settled protocol
+//! Burns any mix of amUSD and aSOL for the holder's share of the vault at the
+//! frozen settlement snapshot. amUSD shares the senior pool (par, capped at
+//! the vault) and aSOL the rest, so claims in any order drain the vault
+//! exactly. No CR, minimum-TVL or minimum-output rules apply; the balance
+//! sheet is still checked against the frozen values.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, SettlementClaimed};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<ClaimSettlement>, amusd_amount: u64, asol_amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+  require!(global_state.settled, LaminarError::ProtocolNotSettled);
+  require!(amusd_amount > 0 || asol_amount > 0, LaminarError::ZeroAmount);
+  require!(ctx.accounts.user_amusd_account.amount >= amusd_amount, LaminarError::InsufficientSupply);
+  require!(ctx.accounts.user_asol_account.amount >= asol_amount, LaminarError::InsufficientSupply);
+
+  let sol_price_usd = global_state.settlement_sol_price_usd;
+  let lst_to_sol_rate = global_state.settlement_lst_to_sol_rate;
+  let current_lst_amount = global_state.total_lst_amount;
+  let current_amusd_supply = global_state.amusd_supply;
+  let current_asol_supply = global_state.asol_supply;
+  let redemption_rate_bps = global_state.redemption_rate_bps;
+
+  // The senior pool is sized on amUSD at its redemption rate; shares stay nominal.
+  let amusd_value = mul_div_up(current_amusd_supply, redemption_rate_bps, BPS_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  let (amusd_pool_lst, asol_pool_lst) = compute_settlement_pools(
+    current_lst_amount,
+    lst_to_sol_rate,
+    amusd_value,
+    current_asol_supply,
+    sol_price_usd,
+  )
+  .ok_or(LaminarError::MathOverflow)?;
+
+  let amusd_lst = if amusd_amount > 0 {
+    mul_div_down(amusd_pool_lst, amusd_amount, current_amusd_supply).ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+  let asol_lst = if asol_amount > 0 {
+    mul_div_down(asol_pool_lst, asol_amount, current_asol_supply).ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+  let lst_out = amusd_lst.checked_add(asol_lst).ok_or(LaminarError::MathOverflow)?;
+
+  let new_lst_amount = current_lst_amount.checked_sub(lst_out).ok_or(LaminarError::InsufficientCollateral)?;
+  let new_amusd_supply = current_amusd_supply.checked_sub(amusd_amount).ok_or(LaminarError::InsufficientSupply)?;
+  let new_asol_supply = current_asol_supply.checked_sub(asol_amount).ok_or(LaminarError::InsufficientSupply)?;
+  // The reserve and insurance are paid out with the pools; they are gone once the vault is.
+  let (new_rounding_reserve, new_insurance_fund) = if new_lst_amount == 0 {
+    (0, 0)
+  } else {
+    (global_state.rounding_reserve_lamports, global_state.insurance_fund_lamports)
+  };
+  let new_non_claimable = compute_non_claimable_sol(new_rounding_reserve, new_insurance_fund).ok_or(LaminarError::MathOverflow)?;
+
+  let new_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let new_liability = compute_liability_at_rate_sol(new_amusd_supply, sol_price_usd, redemption_rate_bps).ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+  // (USD -> SOL, SOL -> LST) on the senior leg, (aSOL -> LST) on the junior
+  let rounding_bound_lamports = derive_rounding_bound_lamports(2, 1, sol_price_usd)?;
+  assert_balance_sheet_holds(new_tvl, new_liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_lst_amount = new_lst_amount;
+    global_state.amusd_supply = new_amusd_supply;
+    global_state.asol_supply = new_asol_supply;
+    global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    global_state.increment_operation_counter()?;
+  }
+
+  if amusd_amount > 0 {
+    let burn_accounts = Burn {
+      mint: ctx.accounts.amusd_mint.to_account_info(),
+      from: ctx.accounts.user_amusd_account.to_account_info(),
+      authority: ctx.accounts.user.to_account_info(),
+    };
+    token_interface::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts), amusd_amount)?;
+  }
+
+  if asol_amount > 0 {
+    let burn_accounts = Burn {
+      mint: ctx.accounts.asol_mint.to_account_info(),
+      from: ctx.accounts.user_asol_account.to_account_info(),
+      authority: ctx.accounts.user.to_account_info(),
+    };
+    token_interface::burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts), asol_amount)?;
+  }
+
+  if lst_out > 0 {
+    let seeds = &[VAULT_AUTHORITY_SEED, &[ctx.accounts.global_state.vault_authority_bump]];
+    let signer = &[&seeds[..]];
+    let transfer_accounts = TransferChecked {
+      from: ctx.accounts.vault.to_account_info(),
+      mint: ctx.accounts.lst_mint.to_account_info(),
+      to: ctx.accounts.user_lst_account.to_account_info(),
+      authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    token_interface::transfer_checked(
+      CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer),
+      lst_out,
+      ctx.accounts.lst_mint.decimals,
+    )?;
+  }
+
+  ctx.accounts.vault.reload()?;
+  ctx.accounts.amusd_mint.reload()?;
+  ctx.accounts.asol_mint.reload()?;
+  require!(ctx.accounts.vault.amount == new_lst_amount, LaminarError::BalanceSheetViolation);
+  require!(ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_mint_supply()?, LaminarError::BalanceSheetViolation);
+  require!(ctx.accounts.asol_mint.supply == new_asol_supply, LaminarError::BalanceSheetViolation);
+
+  emit_event!(SettlementClaimed {
+    user: ctx.accounts.user.key(),
+    amusd_burned: amusd_amount,
+    asol_burned: asol_amount,
+    lst_received: lst_out,
+    remaining_lst: new_lst_amount,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Settlement claim: {} amUSD + {} aSOL -> {} LST ({} LST left)",
+    amusd_amount,
+    asol_amount,
+    lst_out,
+    new_lst_amount
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimSettlement<'info> {
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = asol_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(mut)]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(mut)]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (burned from)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's aSOL token account (burned from)
+  #[account(
+    mut,
+    token::mint = asol_mint,
+    token::authority = user,
+  )]
+  pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's LST token account (receives the claim)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (source of LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
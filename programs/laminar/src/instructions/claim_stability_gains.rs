@@ -0,0 +1,125 @@
+//! claim_stability_gains instruction - pay out a Stability Pool position's LST gains
+//! Leaves the amUSD position in place. Settles the position first, against
+//! its epoch's record if a drawdown has since emptied that epoch.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, StabilityGainsClaimed};
+use crate::invariants::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<ClaimStabilityGains>) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  let settled_epoch = ctx.accounts.stability_deposit.epoch;
+
+  // State update
+  let lst_claimed = {
+    let deposit = &mut ctx.accounts.stability_deposit;
+    deposit.settle_gains(&ctx.accounts.stability_pool, ctx.accounts.epoch_record.as_deref())?;
+    let lst_claimed = deposit.pending_lst_gain;
+    require!(lst_claimed > 0, LaminarError::NoStabilityGains);
+    deposit.pending_lst_gain = 0;
+
+    let stability_pool = &mut ctx.accounts.stability_pool;
+    stability_pool.total_pool_lst = stability_pool.total_pool_lst
+      .checked_sub(lst_claimed)
+      .ok_or(LaminarError::InsufficientCollateral)?;
+    lst_claimed
+  };
+
+  let seeds = &[STABILITY_POOL_SEED, &[ctx.accounts.stability_pool.bump]];
+  let signer = &[&seeds[..]];
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.pool_lst_vault.to_account_info(),
+    mint: ctx.accounts.lst_mint.to_account_info(),
+    to: ctx.accounts.user_lst_account.to_account_info(),
+    authority: ctx.accounts.stability_pool.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer);
+  token_interface::transfer_checked(cpi_ctx, lst_claimed, ctx.accounts.lst_mint.decimals)?;
+
+  ctx.accounts.pool_lst_vault.reload()?;
+  // Tokens sent to the vault directly are a donation; only a shortfall breaks the books.
+  require!(
+    ctx.accounts.pool_lst_vault.amount >= ctx.accounts.stability_pool.total_pool_lst,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Claimed {} LST of Stability Pool gains (epoch {})", lst_claimed, settled_epoch);
+
+  emit_event!(StabilityGainsClaimed {
+    user: ctx.accounts.user.key(),
+    lst_claimed,
+    settled_epoch,
+    current_epoch: ctx.accounts.stability_pool.epoch,
+    lst_gain_snapshot: ctx.accounts.stability_deposit.lst_gain_snapshot,
+    user_shares: ctx.accounts.stability_deposit.shares,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimStabilityGains<'info> {
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_POOL_SEED],
+    bump = stability_pool.bump,
+  )]
+  pub stability_pool: Box<Account<'info, StabilityPool>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_DEPOSIT_SEED, user.key().as_ref()],
+    bump = stability_deposit.bump,
+  )]
+  pub stability_deposit: Box<Account<'info, StabilityDeposit>>,
+
+  /// Record of the epoch the position was opened in; required only once a
+  /// drawdown has emptied that epoch
+  pub epoch_record: Option<Account<'info, StabilityEpochRecord>>,
+
+  /// Pool LST vault (source of the gains)
+  #[account(
+    mut,
+    address = stability_pool.lst_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_lst_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's LST token account (receives the gains)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+    constraint = user_lst_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
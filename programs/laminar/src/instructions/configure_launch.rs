@@ -0,0 +1,320 @@
+//! configure_launch instruction - applies the full launch configuration atomically
+//! Only callable pre-launch (both pause flags set, no collateral or supply yet)
+//! so it cannot be used to change parameters on a live protocol. The treasury
+//! is classified and its fee ATAs created exactly as `set_treasury` does.
+
+use anchor_lang::prelude::*;
+use anchor_lang::prelude::program_option::COption;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+  constants::{
+    MAX_EMA_HALF_LIFE_SLOTS, MAX_FALLBACK_UNCERTAINTY_BPS, MAX_PARAM_TIMELOCK_SLOTS, MAX_PSM_FEE_BPS,
+    MAX_REBALANCE_DISCOUNT_BPS, MAX_RECOVERY_BONUS_BPS, MAX_STABILITY_FEE_BPS_PER_YEAR,
+  },
+  error::LaminarError,
+  events::{ConfigChanged, EventCpi, TreasuryUpdated},
+  instructions::set_treasury::classify_treasury,
+  invariants::*,
+  math::BPS_PRECISION,
+  state::*,
+};
+
+/// Complete set of tunables applied by `configure_launch`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LaunchConfig {
+  pub min_cr_bps: u64,
+  pub target_cr_bps: u64,
+
+  pub fee_amusd_mint_bps: u64,
+  pub fee_amusd_redeem_bps: u64,
+  pub fee_asol_mint_bps: u64,
+  pub fee_asol_redeem_bps: u64,
+  pub fee_min_multiplier_bps: u64,
+  pub fee_max_multiplier_bps: u64,
+
+  pub max_oracle_staleness_slots: u64,
+  pub max_conf_bps: u64,
+  pub uncertainty_max_bps: u64,
+  pub max_lst_stale_epochs: u64,
+  pub max_oracle_deviation_bps: u64,
+  pub max_price_move_bps: u64,
+  pub fallback_uncertainty_bps: u64,
+  pub ema_half_life_slots: u64,
+
+  pub max_rounding_reserve_lamports: u64,
+  pub nav_floor_lamports: u64,
+  pub max_asol_mint_per_round: u64,
+  pub mint_round_slots: u64,
+
+  pub amusd_supply_cap: u64,
+  pub asol_supply_cap: u64,
+  pub max_total_lst_amount: u64,
+  pub max_single_deposit_lst: u64,
+  pub max_single_redeem_units: u64,
+  pub per_user_amusd_cap: u64,
+
+  pub fee_split_insurance_bps: u64,
+  pub savings_fee_split_bps: u64,
+  pub stability_fee_bps_per_year: u64,
+
+  pub min_action_gap_slots: u64,
+  pub asol_redeem_cooldown_slots: u64,
+
+  pub param_timelock_slots: u64,
+  pub max_pause_duration_slots: u64,
+
+  pub rebalance_discount_bps: u64,
+  pub recovery_bonus_bps: u64,
+  pub max_flash_loan_bps: u64,
+
+  pub psm_fee_bps: u64,
+  pub psm_debt_ceiling: u64,
+  pub pair_cr_tolerance_bps: u64,
+
+  /// Must be the `treasury` account passed to the instruction
+  pub treasury: Pubkey,
+  pub treasury_is_pda: bool,
+  pub treasury_owner_program: Pubkey,
+}
+
+impl LaunchConfig {
+  /// Validate the whole config set with the central validators.
+  pub fn validate(&self) -> Result<()> {
+    validate_risk_config(self.min_cr_bps, self.target_cr_bps)?;
+    validate_fee_config(
+      self.fee_amusd_mint_bps,
+      self.fee_amusd_redeem_bps,
+      self.fee_asol_mint_bps,
+      self.fee_asol_redeem_bps,
+      self.fee_min_multiplier_bps,
+      self.fee_max_multiplier_bps,
+    )?;
+    validate_oracle_config(
+      self.max_oracle_staleness_slots,
+      self.max_conf_bps,
+      self.uncertainty_max_bps,
+      self.max_lst_stale_epochs,
+    )?;
+    // Same bounds the individual setters enforce
+    require!(self.mint_round_slots > 0, LaminarError::InvalidParameter);
+    require!(self.fee_split_insurance_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+    require!(self.savings_fee_split_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+    require!(self.stability_fee_bps_per_year <= MAX_STABILITY_FEE_BPS_PER_YEAR, LaminarError::InvalidParameter);
+    require!(
+      self.max_oracle_deviation_bps > 0 && self.max_oracle_deviation_bps <= BPS_PRECISION,
+      LaminarError::InvalidParameter
+    );
+    require!(self.max_price_move_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+    require!(self.fallback_uncertainty_bps <= MAX_FALLBACK_UNCERTAINTY_BPS, LaminarError::InvalidParameter);
+    require!(self.ema_half_life_slots <= MAX_EMA_HALF_LIFE_SLOTS, LaminarError::InvalidParameter);
+    require!(self.param_timelock_slots <= MAX_PARAM_TIMELOCK_SLOTS, LaminarError::InvalidParameter);
+    require!(self.max_pause_duration_slots > 0, LaminarError::InvalidParameter);
+    require!(self.rebalance_discount_bps <= MAX_REBALANCE_DISCOUNT_BPS, LaminarError::InvalidParameter);
+    require!(self.recovery_bonus_bps <= MAX_RECOVERY_BONUS_BPS, LaminarError::InvalidParameter);
+    require!(self.max_flash_loan_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+    require!(self.psm_fee_bps <= MAX_PSM_FEE_BPS, LaminarError::InvalidParameter);
+    require!(self.pair_cr_tolerance_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+    Ok(())
+  }
+}
+
+/// Pre-launch means: both pause flags set and nothing has ever been deposited or minted.
+pub fn is_pre_launch(global_state: &GlobalState) -> bool {
+  global_state.mint_paused
+    && global_state.redeem_paused
+    && global_state.total_lst_amount == 0
+    && global_state.collateral_tvl_lamports == 0
+    && global_state.amusd_supply == 0
+    && global_state.asol_supply == 0
+}
+
+/// Write a single config field and emit a `ConfigChanged` diff when the value moves.
+fn set_config_field(
+  field: &str,
+  slot: &mut u64,
+  new_value: u64,
+  authority: Pubkey,
+  operation_counter: u64,
+  timestamp: i64,
+  event_cpi: &EventCpi,
+) -> Result<()> {
+  let old_value = *slot;
+  if old_value == new_value {
+    return Ok(());
+  }
+
+  *slot = new_value;
+
+  event_cpi.emit(ConfigChanged {
+    authority,
+    field: field.to_string(),
+    old_value,
+    new_value,
+    operation_counter,
+    timestamp,
+  })
+}
+
+pub fn handler(ctx: Context<ConfigureLaunch>, config: LaunchConfig) -> Result<()> {
+  let authority = ctx.accounts.authority.key();
+  let timestamp = ctx.accounts.clock.unix_timestamp;
+  let recorded_treasury_owner = classify_treasury(
+    &ctx.accounts.treasury.to_account_info(),
+    config.treasury_is_pda,
+    config.treasury_owner_program,
+  )?;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  require!(is_pre_launch(global_state), LaminarError::ProtocolAlreadyLaunched);
+
+  // Validate everything before touching state
+  config.validate()?;
+  require!(
+    config.max_rounding_reserve_lamports >= global_state.rounding_reserve_lamports,
+    LaminarError::InvalidParameter
+  );
+  // `update_psm_parameters` needs the PSM to exist; before that only its zeros are accepted.
+  if config.psm_fee_bps != global_state.psm_fee_bps || config.psm_debt_ceiling != global_state.psm_debt_ceiling {
+    require!(global_state.psm_usdc_mint != Pubkey::default(), LaminarError::PsmNotInitialized);
+  }
+
+  // Count the launch before writing, so every `ConfigChanged` carries its counter
+  global_state.increment_operation_counter()?;
+  let operation_counter = global_state.operation_counter;
+
+  set_config_field("min_cr_bps", &mut global_state.min_cr_bps, config.min_cr_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("target_cr_bps", &mut global_state.target_cr_bps, config.target_cr_bps, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("fee_amusd_mint_bps", &mut global_state.fee_amusd_mint_bps, config.fee_amusd_mint_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fee_amusd_redeem_bps", &mut global_state.fee_amusd_redeem_bps, config.fee_amusd_redeem_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fee_asol_mint_bps", &mut global_state.fee_asol_mint_bps, config.fee_asol_mint_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fee_asol_redeem_bps", &mut global_state.fee_asol_redeem_bps, config.fee_asol_redeem_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fee_min_multiplier_bps", &mut global_state.fee_min_multiplier_bps, config.fee_min_multiplier_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fee_max_multiplier_bps", &mut global_state.fee_max_multiplier_bps, config.fee_max_multiplier_bps, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("max_oracle_staleness_slots", &mut global_state.max_oracle_staleness_slots, config.max_oracle_staleness_slots, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_conf_bps", &mut global_state.max_conf_bps, config.max_conf_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("uncertainty_max_bps", &mut global_state.uncertainty_max_bps, config.uncertainty_max_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_lst_stale_epochs", &mut global_state.max_lst_stale_epochs, config.max_lst_stale_epochs, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_oracle_deviation_bps", &mut global_state.max_oracle_deviation_bps, config.max_oracle_deviation_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_price_move_bps", &mut global_state.max_price_move_bps, config.max_price_move_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("fallback_uncertainty_bps", &mut global_state.fallback_uncertainty_bps, config.fallback_uncertainty_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("ema_half_life_slots", &mut global_state.ema_half_life_slots, config.ema_half_life_slots, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("max_rounding_reserve_lamports", &mut global_state.max_rounding_reserve_lamports, config.max_rounding_reserve_lamports, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("nav_floor_lamports", &mut global_state.nav_floor_lamports, config.nav_floor_lamports, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_asol_mint_per_round", &mut global_state.max_asol_mint_per_round, config.max_asol_mint_per_round, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("mint_round_slots", &mut global_state.mint_round_slots, config.mint_round_slots, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("amusd_supply_cap", &mut global_state.amusd_supply_cap, config.amusd_supply_cap, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("asol_supply_cap", &mut global_state.asol_supply_cap, config.asol_supply_cap, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_total_lst_amount", &mut global_state.max_total_lst_amount, config.max_total_lst_amount, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_single_deposit_lst", &mut global_state.max_single_deposit_lst, config.max_single_deposit_lst, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_single_redeem_units", &mut global_state.max_single_redeem_units, config.max_single_redeem_units, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("per_user_amusd_cap", &mut global_state.per_user_amusd_cap, config.per_user_amusd_cap, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("fee_split_insurance_bps", &mut global_state.fee_split_insurance_bps, config.fee_split_insurance_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("savings_fee_split_bps", &mut global_state.savings_fee_split_bps, config.savings_fee_split_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  // No amUSD exists yet, so there is nothing to accrue; the fee runs from now.
+  if config.stability_fee_bps_per_year != global_state.stability_fee_bps_per_year {
+    global_state.last_accrual_timestamp = timestamp;
+  }
+  set_config_field("stability_fee_bps_per_year", &mut global_state.stability_fee_bps_per_year, config.stability_fee_bps_per_year, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("min_action_gap_slots", &mut global_state.min_action_gap_slots, config.min_action_gap_slots, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("asol_redeem_cooldown_slots", &mut global_state.asol_redeem_cooldown_slots, config.asol_redeem_cooldown_slots, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("param_timelock_slots", &mut global_state.param_timelock_slots, config.param_timelock_slots, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_pause_duration_slots", &mut global_state.max_pause_duration_slots, config.max_pause_duration_slots, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("rebalance_discount_bps", &mut global_state.rebalance_discount_bps, config.rebalance_discount_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("recovery_bonus_bps", &mut global_state.recovery_bonus_bps, config.recovery_bonus_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("max_flash_loan_bps", &mut global_state.max_flash_loan_bps, config.max_flash_loan_bps, authority, operation_counter, timestamp, &event_cpi)?;
+
+  set_config_field("psm_fee_bps", &mut global_state.psm_fee_bps, config.psm_fee_bps, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("psm_debt_ceiling", &mut global_state.psm_debt_ceiling, config.psm_debt_ceiling, authority, operation_counter, timestamp, &event_cpi)?;
+  set_config_field("pair_cr_tolerance_bps", &mut global_state.pair_cr_tolerance_bps, config.pair_cr_tolerance_bps, authority, operation_counter, timestamp, &event_cpi)?;
+
+  let old_treasury = global_state.treasury;
+  if old_treasury != config.treasury || global_state.treasury_owner_program != recorded_treasury_owner {
+    global_state.treasury = config.treasury;
+    global_state.treasury_owner_program = recorded_treasury_owner;
+    event_cpi.emit(TreasuryUpdated {
+      authority,
+      old_treasury,
+      new_treasury: config.treasury,
+      treasury_is_pda: config.treasury_is_pda,
+      treasury_owner_program: recorded_treasury_owner,
+      operation_counter,
+      timestamp,
+    })?;
+  }
+
+  msg!("Launch configuration applied");
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(config: LaunchConfig)]
+pub struct ConfigureLaunch<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// CHECK: classified in `classify_treasury`; must co-sign unless acknowledged as a PDA
+  #[account(address = config.treasury @ LaminarError::InvalidTreasury)]
+  pub treasury: UncheckedAccount<'info>,
+
+  #[account(
+    constraint = amusd_mint.key() == global_state.amusd_mint @ LaminarError::InvalidMint,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(
+    constraint = asol_mint.key() == global_state.asol_mint @ LaminarError::InvalidMint,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// Treasury's amUSD fee account (created if missing)
+  #[account(
+    init_if_needed,
+    payer = authority,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+    constraint = treasury_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's aSOL fee account (created if missing)
+  #[account(
+    init_if_needed,
+    payer = authority,
+    associated_token::mint = asol_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+    constraint = treasury_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
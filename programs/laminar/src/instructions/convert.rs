@@ -0,0 +1,531 @@
+//! Tranche conversion instructions - swap amUSD and aSOL inside the book
+//! The input is burned and the output minted at the protocol's own prices,
+//! amUSD at $1 and aSOL at NAV, so no LST moves and TVL is unchanged. One
+//! fee is charged: the dynamic mint fee of the tranche received, taken out
+//! of the output the way the mint paths take it.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface},
+};
+use crate::{events::{emit_recovery_transition, AmusdConvertedToAsol, AsolConvertedToAmusd, EventCpi}, state::*};
+use crate::math::*;
+use crate::invariants::*;
+use crate::error::LaminarError;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::collateral::{needs_lst_snapshot, CollateralLeg};
+use crate::position::{assert_asol_redeem_cooldown, record_position_mint, PositionMint};
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::instructions::accrue_stability_fee::accrue_stability_fee_to_treasury;
+
+/// Retire `amusd_amount` of debt into aSOL. Debt becomes equity, so CR can
+/// only rise and the conversion stays open in recovery mode.
+pub fn handler_amusd_to_asol(ctx: Context<Convert>, amusd_amount: u64, min_asol_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(amusd_amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_amusd_account.amount >= amusd_amount,
+    LaminarError::InsufficientSupply
+  );
+
+  let user_position_bump = ctx.bumps.user_position;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+  // aSOL is minted, so price it as mint_asol does
+  let book = ConvertBook::read(accounts, &event_cpi, PriceBias::High)?;
+  let global_state = &accounts.global_state;
+  require!(book.asol_nav > 0, LaminarError::InsolventProtocol);
+
+  let asol_gross = compute_amusd_to_asol(amusd_amount, book.sol_price_usd, book.asol_nav)
+    .ok_or(LaminarError::MathOverflow)?;
+  let fee_bps = book.fee_bps(global_state, global_state.fee_asol_mint_bps, FeeAction::AsolMint)?;
+  let (asol_net, fee) = apply_fee(asol_gross, fee_bps)
+    .ok_or(LaminarError::MathOverflow)?;
+  // The insurance share is never minted; its value at NAV moves to insurance.
+  let (treasury_fee, insurance_fee) = split_fee(fee, global_state.fee_split_insurance_bps)
+    .ok_or(LaminarError::InvalidParameter)?;
+  let insurance_credit = mul_div_down(insurance_fee, book.asol_nav, SOL_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  msg!("amUSD in: {}, aSOL gross: {} at NAV {}", amusd_amount, asol_gross, book.asol_nav);
+  msg!("Fee: {} aSOL to treasury, {} aSOL to insurance", treasury_fee, insurance_fee);
+
+  require!(asol_net >= min_asol_out, LaminarError::SlippageExceeded);
+  require!(asol_net >= MIN_ASOL_MINT, LaminarError::AmountTooSmall);
+
+  let new_amusd_supply = global_state.amusd_supply
+    .checked_sub(amusd_amount)
+    .ok_or(LaminarError::InsufficientSupply)?;
+  let new_asol_supply = global_state.asol_supply
+    .checked_add(asol_gross)
+    .and_then(|supply| supply.checked_sub(insurance_fee))
+    .ok_or(LaminarError::MathOverflow)?;
+  compute_cap_headroom(new_asol_supply, global_state.asol_supply_cap)
+    .ok_or(LaminarError::AsolSupplyCapExceeded)?;
+  let new_asol_minted_this_round = global_state.asol_minted_this_round
+    .checked_add(asol_gross)
+    .ok_or(LaminarError::MathOverflow)?;
+  compute_cap_headroom(new_asol_minted_this_round, global_state.max_asol_mint_per_round)
+    .ok_or(LaminarError::MintRoundCapExceeded)?;
+  record_position_mint(
+    global_state,
+    accounts.user_position.as_deref_mut(),
+    accounts.user.key(),
+    user_position_bump,
+    accounts.clock.slot,
+    PositionMint { asol_minted: asol_net, ..Default::default() },
+  )?;
+
+  let new_liability = compute_liability_sol(new_amusd_supply, book.sol_price_usd)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_insurance_fund = global_state.insurance_fund_lamports
+    .checked_add(insurance_credit)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_cr = compute_cr_bps(book.weighted_tvl, new_liability);
+  msg!("CR: {}bps -> {}bps", book.cr_bps, new_cr);
+
+  // Retiring debt against unchanged collateral can only lift CR.
+  require!(new_cr >= book.cr_bps, LaminarError::BalanceSheetViolation);
+  book.assert_balance_sheet(global_state, new_liability, new_insurance_fund)?;
+
+  {
+    let global_state = &mut accounts.global_state;
+    global_state.amusd_supply = new_amusd_supply;
+    global_state.asol_supply = new_asol_supply;
+    global_state.asol_minted_this_round = new_asol_minted_this_round;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    global_state.increment_operation_counter()?;
+    let recovery = global_state.track_recovery_mode(new_cr, accounts.clock.slot);
+    emit_recovery_transition(&event_cpi, recovery, new_cr, global_state.min_cr_bps, global_state.operation_counter, &accounts.clock)?;
+  }
+
+  let seeds = &[GLOBAL_STATE_SEED, &[accounts.global_state.bump]];
+  let signer = &[&seeds[..]];
+
+  token_interface::burn(
+    CpiContext::new(
+      accounts.token_program.to_account_info(),
+      Burn {
+        mint: accounts.amusd_mint.to_account_info(),
+        from: accounts.user_amusd_account.to_account_info(),
+        authority: accounts.user.to_account_info(),
+      },
+    ),
+    amusd_amount,
+  )?;
+
+  token_interface::mint_to(
+    CpiContext::new_with_signer(
+      accounts.token_program.to_account_info(),
+      MintTo {
+        mint: accounts.asol_mint.to_account_info(),
+        to: accounts.user_asol_account.to_account_info(),
+        authority: accounts.global_state.to_account_info(),
+      },
+      signer,
+    ),
+    asol_net,
+  )?;
+
+  if treasury_fee > 0 {
+    token_interface::mint_to(
+      CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        MintTo {
+          mint: accounts.asol_mint.to_account_info(),
+          to: accounts.treasury_asol_account.to_account_info(),
+          authority: accounts.global_state.to_account_info(),
+        },
+        signer,
+      ),
+      treasury_fee,
+    )?;
+  }
+
+  assert_mints_synced(accounts)?;
+
+  event_cpi.emit(AmusdConvertedToAsol {
+    user: accounts.user.key(),
+    amusd_burned: amusd_amount,
+    asol_minted: asol_net,
+    fee: treasury_fee,
+    insurance_fee,
+    nav: book.asol_nav,
+    sol_price_used: book.sol_price_usd,
+    old_cr_bps: book.cr_bps,
+    new_cr_bps: new_cr,
+    operation_counter: accounts.global_state.operation_counter,
+    timestamp: accounts.clock.unix_timestamp,
+  })?;
+
+  Ok(())
+}
+
+/// Turn `asol_amount` of equity into amUSD. Equity becomes debt, so the
+/// conversion is closed in recovery mode and must leave CR at or above
+/// `min_cr_bps`.
+pub fn handler_asol_to_amusd(ctx: Context<Convert>, asol_amount: u64, min_amusd_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(asol_amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_asol_account.amount >= asol_amount,
+    LaminarError::InsufficientSupply
+  );
+  assert_asol_redeem_cooldown(&ctx.accounts.global_state, ctx.accounts.user_position.as_deref(), ctx.accounts.clock.slot)?;
+
+  let user_position_bump = ctx.bumps.user_position;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+  // amUSD is minted, so price it as mint_amusd does
+  let book = ConvertBook::read(accounts, &event_cpi, PriceBias::Low)?;
+  let global_state = &accounts.global_state;
+  let min_cr_bps = global_state.min_cr_bps;
+  assert_not_in_recovery_mode(book.cr_bps, min_cr_bps)?;
+  require!(book.asol_nav > 0, LaminarError::InsolventProtocol);
+
+  let amusd_gross = compute_asol_to_amusd(asol_amount, book.asol_nav, book.sol_price_usd)
+    .ok_or(LaminarError::MathOverflow)?;
+  let fee_bps = book.fee_bps(global_state, global_state.fee_amusd_mint_bps, FeeAction::AmusdMint)?;
+  let (amusd_net, fee) = apply_fee(amusd_gross, fee_bps)
+    .ok_or(LaminarError::MathOverflow)?;
+  // The insurance share is never minted; its value stays in the book as insurance.
+  let (treasury_fee, insurance_fee) = split_fee(fee, global_state.fee_split_insurance_bps)
+    .ok_or(LaminarError::InvalidParameter)?;
+  let insurance_credit = mul_div_down(insurance_fee, SOL_PRECISION, book.sol_price_usd)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  msg!("aSOL in: {} at NAV {}, amUSD gross: {}", asol_amount, book.asol_nav, amusd_gross);
+  msg!("Fee: {} amUSD to treasury, {} amUSD to insurance", treasury_fee, insurance_fee);
+
+  require!(amusd_net >= min_amusd_out, LaminarError::SlippageExceeded);
+  require!(amusd_net >= MIN_AMUSD_MINT, LaminarError::AmountTooSmall);
+
+  let new_asol_supply = global_state.asol_supply
+    .checked_sub(asol_amount)
+    .ok_or(LaminarError::InsufficientSupply)?;
+  let new_amusd_supply = global_state.amusd_supply
+    .checked_add(amusd_gross)
+    .and_then(|supply| supply.checked_sub(insurance_fee))
+    .ok_or(LaminarError::MathOverflow)?;
+  compute_cap_headroom(new_amusd_supply, global_state.amusd_supply_cap)
+    .ok_or(LaminarError::DebtCeilingExceeded)?;
+  record_position_mint(
+    global_state,
+    accounts.user_position.as_deref_mut(),
+    accounts.user.key(),
+    user_position_bump,
+    accounts.clock.slot,
+    PositionMint { amusd_minted: amusd_net, ..Default::default() },
+  )?;
+
+  let new_liability = compute_liability_sol(new_amusd_supply, book.sol_price_usd)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_insurance_fund = global_state.insurance_fund_lamports
+    .checked_add(insurance_credit)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_cr = compute_cr_bps(book.weighted_tvl, new_liability);
+  msg!("CR: {}bps -> {}bps", book.cr_bps, new_cr);
+
+  assert_cr_above_minimum(new_cr, min_cr_bps)?;
+  book.assert_balance_sheet(global_state, new_liability, new_insurance_fund)?;
+
+  {
+    let global_state = &mut accounts.global_state;
+    global_state.amusd_supply = new_amusd_supply;
+    global_state.asol_supply = new_asol_supply;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    global_state.increment_operation_counter()?;
+    let recovery = global_state.track_recovery_mode(new_cr, accounts.clock.slot);
+    emit_recovery_transition(&event_cpi, recovery, new_cr, min_cr_bps, global_state.operation_counter, &accounts.clock)?;
+  }
+
+  let seeds = &[GLOBAL_STATE_SEED, &[accounts.global_state.bump]];
+  let signer = &[&seeds[..]];
+
+  token_interface::burn(
+    CpiContext::new(
+      accounts.token_program.to_account_info(),
+      Burn {
+        mint: accounts.asol_mint.to_account_info(),
+        from: accounts.user_asol_account.to_account_info(),
+        authority: accounts.user.to_account_info(),
+      },
+    ),
+    asol_amount,
+  )?;
+
+  token_interface::mint_to(
+    CpiContext::new_with_signer(
+      accounts.token_program.to_account_info(),
+      MintTo {
+        mint: accounts.amusd_mint.to_account_info(),
+        to: accounts.user_amusd_account.to_account_info(),
+        authority: accounts.global_state.to_account_info(),
+      },
+      signer,
+    ),
+    amusd_net,
+  )?;
+
+  if treasury_fee > 0 {
+    token_interface::mint_to(
+      CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        MintTo {
+          mint: accounts.amusd_mint.to_account_info(),
+          to: accounts.treasury_amusd_account.to_account_info(),
+          authority: accounts.global_state.to_account_info(),
+        },
+        signer,
+      ),
+      treasury_fee,
+    )?;
+  }
+
+  assert_mints_synced(accounts)?;
+
+  event_cpi.emit(AsolConvertedToAmusd {
+    user: accounts.user.key(),
+    asol_burned: asol_amount,
+    amusd_minted: amusd_net,
+    fee: treasury_fee,
+    insurance_fee,
+    nav: book.asol_nav,
+    sol_price_used: book.sol_price_usd,
+    old_cr_bps: book.cr_bps,
+    new_cr_bps: new_cr,
+    operation_counter: accounts.global_state.operation_counter,
+    timestamp: accounts.clock.unix_timestamp,
+  })?;
+
+  Ok(())
+}
+
+/// The whole book at the conversion's price, after the sync and stability
+/// fee accrual every user path runs first
+struct ConvertBook {
+  sol_price_usd: u64,
+  tvl: u64,
+  weighted_tvl: u64,
+  cr_bps: u64,
+  /// CR the fee curve reads; deprecated collateral excluded
+  fee_cr_bps: u64,
+  uncertainty_index_bps: u64,
+  /// 0 while there is no aSOL supply or no claimable equity
+  asol_nav: u64,
+}
+
+impl ConvertBook {
+  fn read<'info>(accounts: &mut Convert<'info>, event_cpi: &EventCpi<'info>, bias: PriceBias) -> Result<Self> {
+    {
+      let global_state = &mut accounts.global_state;
+      global_state.validate_version()?;
+      assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+      assert_operation_counter_sane(global_state.operation_counter)?;
+      require!(!global_state.settled, LaminarError::ProtocolSettled);
+      if needs_lst_snapshot(global_state, None) {
+        assert_lst_snapshot_fresh(
+          accounts.clock.slot,
+          global_state.last_tvl_update_slot,
+          global_state.max_oracle_staleness_slots,
+        )?;
+      }
+      sync_exchange_rate_in_place(global_state, accounts.clock.slot)?;
+      global_state.roll_mint_round(accounts.clock.slot);
+      // Convert against the debt as of now, stability fee included.
+      accrue_stability_fee_to_treasury(
+        global_state,
+        &accounts.amusd_mint,
+        &accounts.treasury_amusd_account,
+        &accounts.token_program,
+        &accounts.clock,
+        event_cpi,
+      )?;
+    }
+
+    let global_state = &accounts.global_state;
+    // A conversion is a redeem of one tranche and a mint of the other.
+    require!(!global_state.mint_paused, LaminarError::MintPaused);
+    require!(!global_state.redeem_paused, LaminarError::RedeemPaused);
+    // amUSD written down below $1 cannot be swapped at par either way.
+    require!(global_state.redemption_rate_bps == BPS_PRECISION, LaminarError::AmusdWrittenDown);
+
+    let oracle_price = resolve_price(
+      &PriceAccounts {
+        price_update: accounts.price_update.as_deref(),
+        switchboard_feed: accounts.switchboard_feed.as_deref(),
+      },
+      global_state,
+      accounts.clock.slot,
+      accounts.clock.unix_timestamp,
+      bias,
+    )?;
+    // The primary leg carries every other vault in its book totals.
+    let leg = CollateralLeg::resolve(global_state, None, global_state.mock_lst_to_sol_rate)?;
+
+    let tvl = leg.tvl(leg.lst_amount)?;
+    let weighted_tvl = leg.weighted_tvl(leg.lst_amount)?;
+    let liability = compute_liability_sol(global_state.amusd_supply, oracle_price.sol_price_usd)
+      .ok_or(LaminarError::MathOverflow)?;
+    let non_claimable = compute_non_claimable_sol(global_state.rounding_reserve_lamports, global_state.insurance_fund_lamports)
+      .ok_or(LaminarError::MathOverflow)?;
+
+    Ok(Self {
+      sol_price_usd: oracle_price.sol_price_usd,
+      tvl,
+      weighted_tvl,
+      cr_bps: compute_cr_bps(weighted_tvl, liability),
+      fee_cr_bps: compute_fee_cr_bps(weighted_tvl, liability, global_state.deprecated_collateral_tvl_lamports),
+      uncertainty_index_bps: global_state.effective_uncertainty_index(global_state.uncertainty_index_bps, accounts.clock.slot)?,
+      asol_nav: nav_asol_with_reserve(tvl, liability, non_claimable, global_state.asol_supply).unwrap_or(0),
+    })
+  }
+
+  fn fee_bps(&self, global_state: &GlobalState, base_fee_bps: u64, action: FeeAction) -> Result<u64> {
+    compute_dynamic_fee_bps(
+      base_fee_bps,
+      action,
+      self.fee_cr_bps,
+      global_state.min_cr_bps,
+      global_state.target_cr_bps,
+      global_state.fee_min_multiplier_bps,
+      global_state.fee_max_multiplier_bps,
+      self.uncertainty_index_bps,
+      global_state.uncertainty_max_bps,
+    )
+    .ok_or(error!(LaminarError::InvalidParameter))
+  }
+
+  /// TVL is untouched, so only the debt and the insurance fund move.
+  fn assert_balance_sheet(&self, global_state: &GlobalState, new_liability: u64, new_insurance_fund: u64) -> Result<()> {
+    let new_non_claimable = compute_non_claimable_sol(global_state.rounding_reserve_lamports, new_insurance_fund)
+      .ok_or(LaminarError::MathOverflow)?;
+    let new_accounting_equity = compute_accounting_equity_sol(self.tvl, new_liability, new_non_claimable)
+      .ok_or(LaminarError::MathOverflow)?;
+    // (USD -> SOL, SOL -> tranche) => (k_lamports = 1, k_usd = 1)
+    let rounding_bound_lamports = derive_rounding_bound_lamports(1, 1, self.sol_price_usd)?;
+    assert_balance_sheet_holds(self.tvl, new_liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)
+  }
+}
+
+/// Both mints must match the book after the burn and mint CPIs.
+fn assert_mints_synced(accounts: &mut Convert) -> Result<()> {
+  accounts.amusd_mint.reload()?;
+  accounts.asol_mint.reload()?;
+  require!(
+    accounts.amusd_mint.supply == accounts.global_state.amusd_mint_supply()?,
+    LaminarError::BalanceSheetViolation
+  );
+  require!(
+    accounts.asol_mint.supply == accounts.global_state.asol_supply,
+    LaminarError::BalanceSheetViolation
+  );
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Convert<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = asol_mint,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// amUSD mint
+  #[account(
+    mut,
+    constraint = amusd_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// aSOL mint
+  #[account(
+    mut,
+    constraint = asol_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (burned from or minted to)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's aSOL token account (burned from or minted to)
+  #[account(
+    mut,
+    token::mint = asol_mint,
+    token::authority = user,
+    constraint = user_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's amUSD token account (receives the stability fee and the aSOL -> amUSD fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's aSOL token account (receives the amUSD -> aSOL fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = asol_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// Caller's running mint totals; required while `per_user_amusd_cap`,
+  /// `min_action_gap_slots` or `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+}
@@ -0,0 +1,138 @@
+//! deposit_savings instruction - park amUSD in the Savings Pool
+//! The depositor gets shares at the current share price; the pool's slice of
+//! every later amUSD fee raises that price for every share outstanding.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, SavingsDeposited};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<DepositSavings>, amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  require!(amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_amusd_account.amount >= amount,
+    LaminarError::InsufficientSupply
+  );
+
+  let savings_pool = &ctx.accounts.savings_pool;
+  let shares = compute_savings_shares(amount, savings_pool.total_pool_amusd, savings_pool.total_shares)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(shares > 0, LaminarError::AmountTooSmall);
+
+  let new_total_pool_amusd = savings_pool.total_pool_amusd
+    .checked_add(amount)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_total_shares = savings_pool.total_shares
+    .checked_add(shares)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // State update
+  {
+    let deposit = &mut ctx.accounts.savings_deposit;
+    if deposit.owner == Pubkey::default() {
+      deposit.owner = ctx.accounts.user.key();
+      deposit.bump = ctx.bumps.savings_deposit;
+    }
+    deposit.shares = deposit.shares
+      .checked_add(shares)
+      .ok_or(LaminarError::MathOverflow)?;
+
+    let savings_pool = &mut ctx.accounts.savings_pool;
+    savings_pool.total_pool_amusd = new_total_pool_amusd;
+    savings_pool.total_shares = new_total_shares;
+  }
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.user_amusd_account.to_account_info(),
+    mint: ctx.accounts.amusd_mint.to_account_info(),
+    to: ctx.accounts.pool_amusd_vault.to_account_info(),
+    authority: ctx.accounts.user.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.amusd_mint.decimals)?;
+
+  ctx.accounts.pool_amusd_vault.reload()?;
+  require!(
+    ctx.accounts.pool_amusd_vault.amount == ctx.accounts.savings_pool.total_pool_amusd,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Deposited {} amUSD for {} savings shares", amount, shares);
+
+  emit_event!(SavingsDeposited {
+    user: ctx.accounts.user.key(),
+    amusd_deposited: amount,
+    shares_issued: shares,
+    user_shares: ctx.accounts.savings_deposit.shares,
+    total_pool_amusd: new_total_pool_amusd,
+    total_shares: new_total_shares,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositSavings<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Box<Account<'info, SavingsPool>>,
+
+  /// Depositor position, created on first deposit
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = SavingsDeposit::LEN,
+    seeds = [SAVINGS_DEPOSIT_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub savings_deposit: Box<Account<'info, SavingsDeposit>>,
+
+  /// Pool amUSD vault (receives the deposit)
+  #[account(
+    mut,
+    address = savings_pool.amusd_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's amUSD token account (source of the deposit)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
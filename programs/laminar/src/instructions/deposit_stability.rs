@@ -0,0 +1,150 @@
+//! deposit_stability instruction - park amUSD in the Stability Pool
+//! The amUSD stays in supply (it is still protocol debt); the depositor gets
+//! shares in the pool, which later absorbs drawdowns ahead of redeem haircuts.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, StabilityDeposited};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<DepositStability>, amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+  require!(!ctx.accounts.global_state.settled, LaminarError::ProtocolSettled);
+
+  require!(amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_amusd_account.amount >= amount,
+    LaminarError::InsufficientSupply
+  );
+
+  let stability_pool = &ctx.accounts.stability_pool;
+  let shares = compute_stability_shares(amount, stability_pool.total_pool_amusd, stability_pool.total_shares)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(shares > 0, LaminarError::AmountTooSmall);
+
+  let new_total_pool_amusd = stability_pool.total_pool_amusd
+    .checked_add(amount)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_total_shares = stability_pool.total_shares
+    .checked_add(shares)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // State update
+  {
+    let deposit = &mut ctx.accounts.stability_deposit;
+    if deposit.owner == Pubkey::default() {
+      deposit.owner = ctx.accounts.user.key();
+      deposit.bump = ctx.bumps.stability_deposit;
+      deposit.epoch = ctx.accounts.stability_pool.epoch;
+      deposit.lst_gain_snapshot = ctx.accounts.stability_pool.lst_gain_per_share;
+    }
+    deposit.settle_gains(&ctx.accounts.stability_pool, ctx.accounts.epoch_record.as_deref())?;
+    deposit.shares = deposit.shares
+      .checked_add(shares)
+      .ok_or(LaminarError::MathOverflow)?;
+
+    let stability_pool = &mut ctx.accounts.stability_pool;
+    stability_pool.total_pool_amusd = new_total_pool_amusd;
+    stability_pool.total_shares = new_total_shares;
+
+    ctx.accounts.global_state.stability_pool_amusd = new_total_pool_amusd;
+  }
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.user_amusd_account.to_account_info(),
+    mint: ctx.accounts.amusd_mint.to_account_info(),
+    to: ctx.accounts.pool_amusd_vault.to_account_info(),
+    authority: ctx.accounts.user.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.amusd_mint.decimals)?;
+
+  ctx.accounts.pool_amusd_vault.reload()?;
+  // Tokens sent to the vault directly are a donation; only a shortfall breaks the books.
+  require!(
+    ctx.accounts.pool_amusd_vault.amount >= ctx.accounts.stability_pool.total_pool_amusd,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Deposited {} amUSD for {} shares", amount, shares);
+
+  emit_event!(StabilityDeposited {
+    user: ctx.accounts.user.key(),
+    amusd_deposited: amount,
+    shares_issued: shares,
+    user_shares: ctx.accounts.stability_deposit.shares,
+    total_pool_amusd: new_total_pool_amusd,
+    total_shares: new_total_shares,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositStability<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_POOL_SEED],
+    bump = stability_pool.bump,
+  )]
+  pub stability_pool: Box<Account<'info, StabilityPool>>,
+
+  /// Depositor position, created on first deposit
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = StabilityDeposit::LEN,
+    seeds = [STABILITY_DEPOSIT_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub stability_deposit: Box<Account<'info, StabilityDeposit>>,
+
+  /// Record of the epoch the position was opened in; required only once a
+  /// drawdown has emptied that epoch
+  pub epoch_record: Option<Account<'info, StabilityEpochRecord>>,
+
+  /// Pool amUSD vault (receives the deposit)
+  #[account(
+    mut,
+    address = stability_pool.amusd_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's amUSD token account (source of the deposit)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
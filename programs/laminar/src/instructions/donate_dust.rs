@@ -0,0 +1,230 @@
+//! donate_dust instruction - voluntary burn of sub-minimum amUSD/aSOL balances
+//! The burned value is credited to the rounding reserve (rounded down, capped);
+//! anything the reserve cannot absorb stays with claimable equity.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, DustDonated};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+/// Tranche being donated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DustToken {
+  AmUSD,
+  Asol,
+}
+
+pub fn handler(ctx: Context<DonateDust>, token: DustToken, amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  assert_oracle_freshness_and_confidence(
+    ctx.accounts.clock.slot,
+    global_state.last_oracle_update_slot,
+    global_state.max_oracle_staleness_slots,
+    ctx.accounts.clock.unix_timestamp,
+    global_state.last_oracle_update_timestamp,
+    global_state.max_oracle_age_seconds,
+    global_state.mock_sol_price_usd,
+    global_state.mock_oracle_confidence_usd,
+    global_state.max_conf_bps
+  )?;
+  global_state.assert_sol_price_within_bounds(global_state.mock_sol_price_usd)?;
+
+  require!(amount > 0, LaminarError::ZeroAmount);
+
+  let expected_mint = match token {
+    DustToken::AmUSD => global_state.amusd_mint,
+    DustToken::Asol => global_state.asol_mint,
+  };
+  require_keys_eq!(ctx.accounts.token_mint.key(), expected_mint, LaminarError::InvalidMint);
+
+  require!(
+    ctx.accounts.user_token_account.amount >= amount,
+    LaminarError::InsufficientSupply
+  );
+
+  // Capture values
+  let sol_price_usd = global_state.mock_sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let current_amusd_supply = global_state.amusd_supply;
+  let redemption_rate_bps = global_state.redemption_rate_bps;
+  let current_asol_supply = global_state.asol_supply;
+  let current_rounding_reserve = global_state.rounding_reserve_lamports;
+  let current_insurance_fund = global_state.insurance_fund_lamports;
+  let current_non_claimable = compute_non_claimable_sol(current_rounding_reserve, current_insurance_fund)
+    .ok_or(LaminarError::MathOverflow)?;
+  let max_rounding_reserve = global_state.max_rounding_reserve_lamports;
+
+  let tvl = compute_book_tvl_sol(global_state.total_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  let old_liability = if current_amusd_supply > 0 {
+    compute_liability_at_rate_sol(current_amusd_supply, sol_price_usd, redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+
+  let (new_amusd_supply, new_asol_supply, new_liability, donated_value_lamports) = match token {
+    DustToken::AmUSD => {
+      let new_amusd_supply = current_amusd_supply
+        .checked_sub(amount)
+        .ok_or(LaminarError::InsufficientSupply)?;
+
+      let new_liability = if new_amusd_supply > 0 {
+        compute_liability_at_rate_sol(new_amusd_supply, sol_price_usd, redemption_rate_bps)
+          .ok_or(LaminarError::MathOverflow)?
+      } else {
+        0
+      };
+
+      // Conservative: donated value rounds down, at the redemption rate
+      let amusd_value = mul_div_down(amount, redemption_rate_bps, BPS_PRECISION)
+        .ok_or(LaminarError::MathOverflow)?;
+      let value = mul_div_down(amusd_value, SOL_PRECISION, sol_price_usd)
+        .ok_or(LaminarError::MathOverflow)?;
+
+      (new_amusd_supply, current_asol_supply, new_liability, value)
+    }
+    DustToken::Asol => {
+      let new_asol_supply = current_asol_supply
+        .checked_sub(amount)
+        .ok_or(LaminarError::InsufficientSupply)?;
+
+      let nav = nav_asol_with_reserve(tvl, old_liability, current_non_claimable, current_asol_supply)
+        .ok_or(LaminarError::MathOverflow)?;
+
+      let value = mul_div_down(amount, nav, SOL_PRECISION)
+        .ok_or(LaminarError::MathOverflow)?;
+
+      (current_amusd_supply, new_asol_supply, old_liability, value)
+    }
+  };
+
+  let claimable_equity_after = compute_claimable_equity_sol(tvl, new_liability, current_non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  let reserve_credit = compute_dust_donation_credit(
+    donated_value_lamports,
+    claimable_equity_after,
+    current_rounding_reserve,
+    max_rounding_reserve,
+  );
+
+  let new_rounding_reserve = credit_rounding_reserve(current_rounding_reserve, reserve_credit, max_rounding_reserve)?;
+  let new_non_claimable = compute_non_claimable_sol(new_rounding_reserve, current_insurance_fund)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Burning the last aSOL must not leave equity without owners
+  if new_asol_supply == 0 {
+    let remaining_claimable = compute_claimable_equity_sol(tvl, new_liability, new_non_claimable)
+      .ok_or(LaminarError::MathOverflow)?;
+    require!(remaining_claimable == 0, LaminarError::EquityWithoutAsolSupply);
+  }
+
+  let new_accounting_equity = compute_accounting_equity_sol(tvl, new_liability, new_non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Single down-rounding on the value conversion: (k_lamports = 1, k_usd = 1)
+  let rounding_bound_lamports = derive_rounding_bound_lamports(1, 1, sol_price_usd)?;
+
+  assert_rounding_reserve_within_cap(new_rounding_reserve, max_rounding_reserve)?;
+  assert_balance_sheet_holds(tvl, new_liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.amusd_supply = new_amusd_supply;
+    global_state.asol_supply = new_asol_supply;
+    global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.increment_operation_counter()?;
+  }
+
+  // Burn donated tokens
+  let burn_accounts = Burn {
+    mint: ctx.accounts.token_mint.to_account_info(),
+    from: ctx.accounts.user_token_account.to_account_info(),
+    authority: ctx.accounts.user.to_account_info(),
+  };
+
+  let cpi_ctx_burn = CpiContext::new(
+    ctx.accounts.token_program.to_account_info(),
+    burn_accounts,
+  );
+
+  token_interface::burn(cpi_ctx_burn, amount)?;
+
+  ctx.accounts.token_mint.reload()?;
+
+  let expected_supply = match token {
+    DustToken::AmUSD => ctx.accounts.global_state.amusd_mint_supply()?,
+    DustToken::Asol => ctx.accounts.global_state.asol_supply,
+  };
+  require!(
+    ctx.accounts.token_mint.supply == expected_supply,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!(
+    "Dust donated: {} units of {}, value={} lamports, reserve credit={}",
+    amount,
+    ctx.accounts.token_mint.key(),
+    donated_value_lamports,
+    reserve_credit
+  );
+
+  emit_event!(DustDonated {
+    user: ctx.accounts.user.key(),
+    mint: ctx.accounts.token_mint.key(),
+    amount_burned: amount,
+    donated_value_lamports,
+    reserve_credit,
+    new_rounding_reserve,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DonateDust<'info> {
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// amUSD or aSOL mint, matched against `token` in the handler
+  #[account(
+    mut,
+    constraint = token_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's token account (source of donated tokens)
+  #[account(
+    mut,
+    token::mint = token_mint,
+    token::authority = user,
+  )]
+  pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
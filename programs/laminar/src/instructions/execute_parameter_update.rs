@@ -0,0 +1,64 @@
+//! execute_parameter_update instruction - permissionless application of a queued change
+//! Anyone may execute once `clock.slot >= eta_slot`; the pending account is
+//! closed back to its proposer. Kinds owned by a setter are refused here.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, ParameterUpdateExecuted}, state::*};
+
+pub fn handler(ctx: Context<ExecuteParameterUpdate>, _kind: u8) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let pending = &ctx.accounts.pending_param_change;
+  let slot = ctx.accounts.clock.slot;
+  require!(slot >= pending.eta_slot, LaminarError::TimelockNotElapsed);
+
+  // Bounds may have been tightened since queuing
+  pending.change.validate()?;
+  pending.change.apply(global_state)?;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(ParameterUpdateExecuted {
+    executor: ctx.accounts.executor.key(),
+    change: pending.change.clone(),
+    eta_slot: pending.eta_slot,
+    slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Parameter update executed: kind={}, slot={}", pending.change.kind(), slot);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: u8)]
+pub struct ExecuteParameterUpdate<'info> {
+  pub executor: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [PENDING_PARAM_CHANGE_SEED, kind.to_le_bytes().as_ref()],
+    bump = pending_param_change.bump,
+    has_one = proposer,
+    close = proposer
+  )]
+  pub pending_param_change: Box<Account<'info, PendingParamChange>>,
+
+  /// CHECK: rent recipient, pinned by `has_one = proposer`
+  #[account(mut)]
+  pub proposer: UncheckedAccount<'info>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
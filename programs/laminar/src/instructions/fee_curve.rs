@@ -0,0 +1,55 @@
+//! fee_curve instruction - read-only sample of the dynamic fee curve
+//! Returns `FEE_CURVE_POINTS` `(cr_bps, fee_bps)` pairs for one action via
+//! return data, computed by `quote::fee_curve_table` from the live
+//! parameters, so on-chain consumers see exactly what execution would charge.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  constants::FEE_CURVE_POINTS,
+  error::LaminarError,
+  math::FeeAction,
+  quote::{fee_curve_table, QuoteState},
+  state::*,
+};
+
+/// One `(cr_bps, fee_bps)` sample
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeCurvePoint {
+  pub cr_bps: u64,
+  pub fee_bps: u64,
+}
+
+/// Fixed-size fee curve, ascending in `cr_bps`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeCurveSample {
+  pub action: u8,
+  pub points: [FeeCurvePoint; FEE_CURVE_POINTS],
+}
+
+pub fn handler(ctx: Context<FeeCurve>, action: u8) -> Result<FeeCurveSample> {
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let fee_action = FeeAction::try_from(action).map_err(|_| LaminarError::InvalidParameter)?;
+  let table = fee_curve_table(fee_action, &QuoteState::from(&***global_state), FEE_CURVE_POINTS)
+    .ok_or(LaminarError::InvalidParameter)?;
+  require!(table.len() == FEE_CURVE_POINTS, LaminarError::InvalidParameter);
+
+  let mut points = [FeeCurvePoint::default(); FEE_CURVE_POINTS];
+  for (point, (cr_bps, fee_bps)) in points.iter_mut().zip(table) {
+    *point = FeeCurvePoint { cr_bps, fee_bps };
+  }
+
+  Ok(FeeCurveSample { action, points })
+}
+
+#[derive(Accounts)]
+pub struct FeeCurve<'info> {
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+}
@@ -0,0 +1,157 @@
+//! flash_loan_lst instruction - lend vault LST for the rest of the transaction
+//! The loan stays in `total_lst_amount` as a receivable tracked by
+//! `flash_outstanding_lamports`, so TVL, CR and NAV do not move. Instruction
+//! introspection requires a later top-level `flash_repay_lst` in the same
+//! transaction, and every instruction that touches the vault refuses to run
+//! until it has. The loan's share of the vault is recorded for the slot and
+//! raises the uncertainty fee on risk-increasing actions until it ends.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, FlashLoanTaken};
+use crate::instruction::FlashRepayLst as FlashRepayLstIx;
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<FlashLoanLst>, lst_amount: u64) -> Result<()> {
+  // Introspection reads the top-level instruction list; a CPI caller would
+  // see the outer transaction's index, not its own.
+  assert_not_cpi_context()?;
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  require!(!global_state.redeem_paused, LaminarError::RedeemPaused);
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+
+  require!(lst_amount > 0, LaminarError::ZeroAmount);
+  let max_loan = mul_div_down(global_state.total_lst_amount, global_state.max_flash_loan_bps, BPS_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(lst_amount <= max_loan, LaminarError::FlashLoanCapExceeded);
+
+  assert_repaid_later(&ctx.accounts.instructions_sysvar.to_account_info())?;
+
+  // Nothing is outstanding, so the whole book is in the vault.
+  let utilization_bps = mul_div_up(lst_amount, BPS_PRECISION, global_state.total_lst_amount)
+    .ok_or(LaminarError::MathOverflow)?;
+  let slot = ctx.accounts.clock.slot;
+  let slot_utilization_bps = global_state.flash_loan_utilization_at(slot).max(utilization_bps);
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.flash_outstanding_lamports = lst_amount;
+    global_state.flash_loan_utilization_bps = slot_utilization_bps;
+    global_state.flash_loan_utilization_slot = slot;
+    global_state.increment_operation_counter()?;
+  }
+
+  let seeds = &[VAULT_AUTHORITY_SEED, &[ctx.accounts.global_state.vault_authority_bump]];
+  let signer = &[&seeds[..]];
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.vault.to_account_info(),
+    mint: ctx.accounts.lst_mint.to_account_info(),
+    to: ctx.accounts.receiver_lst_account.to_account_info(),
+    authority: ctx.accounts.vault_authority.to_account_info(),
+  };
+  token_interface::transfer_checked(
+    CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, signer),
+    lst_amount,
+    ctx.accounts.lst_mint.decimals,
+  )?;
+
+  // The vault is short exactly the loan.
+  ctx.accounts.vault.reload()?;
+  let global_state = &ctx.accounts.global_state;
+  let reconciled = ctx.accounts.vault.amount
+    .checked_add(global_state.flash_outstanding_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(reconciled == global_state.total_lst_amount, LaminarError::BalanceSheetViolation);
+
+  emit_event!(FlashLoanTaken {
+    borrower: ctx.accounts.borrower.key(),
+    receiver: ctx.accounts.receiver_lst_account.key(),
+    lst_amount,
+    vault_balance: ctx.accounts.vault.amount,
+    utilization_bps: slot_utilization_bps,
+    slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Flash loan: {} LST out, vault={}", lst_amount, ctx.accounts.vault.amount);
+
+  Ok(())
+}
+
+/// Require a top-level `flash_repay_lst` somewhere after the current instruction.
+fn assert_repaid_later(instructions: &AccountInfo) -> Result<()> {
+  let current_index = load_current_index_checked(instructions)? as usize;
+  let mut index = current_index + 1;
+  while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+    if ix.program_id == crate::ID && ix.data.starts_with(FlashRepayLstIx::DISCRIMINATOR) {
+      return Ok(());
+    }
+    index += 1;
+  }
+  err!(LaminarError::FlashRepayMissing)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FlashLoanLst<'info> {
+  pub borrower: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Any LST account the borrower routes the loan to
+  #[account(
+    mut,
+    token::mint = lst_mint,
+  )]
+  pub receiver_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (source of the loan)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CHECK: address-constrained to the instructions sysvar
+  #[account(address = instructions_sysvar::ID)]
+  pub instructions_sysvar: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
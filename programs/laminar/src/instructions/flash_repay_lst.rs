@@ -0,0 +1,148 @@
+//! flash_repay_lst instruction - close the outstanding flash loan
+//! Pulls the loan plus FLASH_LOAN_FEE_BPS (rounded up) back into the vault.
+//! The fee joins `total_lst_amount` with nothing owed against it, so it
+//! lands in claimable equity and lifts aSOL NAV.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::FLASH_LOAN_FEE_BPS;
+use crate::error::LaminarError;
+use crate::events::{emit_event, FlashLoanRepaid};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<FlashRepayLst>) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+
+  let lst_amount = global_state.flash_outstanding_lamports;
+  require!(lst_amount > 0, LaminarError::NoFlashLoanOutstanding);
+
+  let fee = mul_div_up(lst_amount, FLASH_LOAN_FEE_BPS, BPS_PRECISION).ok_or(LaminarError::MathOverflow)?;
+  let repay_amount = lst_amount.checked_add(fee).ok_or(LaminarError::MathOverflow)?;
+  require!(
+    ctx.accounts.user_lst_account.amount >= repay_amount,
+    LaminarError::InsufficientCollateral
+  );
+
+  // Capture values
+  let sol_price_usd = global_state.mock_sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+
+  let new_lst_amount = global_state.total_lst_amount
+    .checked_add(fee)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let liability = if global_state.amusd_supply > 0 {
+    compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_usd, global_state.redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+  let non_claimable = compute_non_claimable_sol(global_state.rounding_reserve_lamports, global_state.insurance_fund_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, liability, non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Single down-rounding on the LST -> SOL conversion: (k_lamports = 1, k_usd = 0)
+  let rounding_bound_lamports = derive_rounding_bound_lamports(1, 0, sol_price_usd)?;
+  assert_balance_sheet_holds(new_tvl, liability, new_accounting_equity, non_claimable, rounding_bound_lamports)?;
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_lst_amount = new_lst_amount;
+    global_state.flash_outstanding_lamports = 0;
+    global_state.increment_operation_counter()?;
+  }
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.user_lst_account.to_account_info(),
+    mint: ctx.accounts.lst_mint.to_account_info(),
+    to: ctx.accounts.vault.to_account_info(),
+    authority: ctx.accounts.user.to_account_info(),
+  };
+  token_interface::transfer_checked(
+    CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts),
+    repay_amount,
+    ctx.accounts.lst_mint.decimals,
+  )?;
+
+  // Whole again: nothing is in flight.
+  ctx.accounts.vault.reload()?;
+  require!(
+    ctx.accounts.vault.amount == ctx.accounts.global_state.total_lst_amount,
+    LaminarError::BalanceSheetViolation
+  );
+
+  emit_event!(FlashLoanRepaid {
+    payer: ctx.accounts.user.key(),
+    lst_amount,
+    fee,
+    total_lst_amount: new_lst_amount,
+    slot: ctx.accounts.clock.slot,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Flash loan repaid: {} LST + {} fee", lst_amount, fee);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FlashRepayLst<'info> {
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Payer's LST token account (source of the repayment)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (receives the repayment)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,62 @@
+//! force_unpause_redeem instruction - permissionless escape hatch for a stuck pause
+//! Once a redeem pause outlives `max_pause_duration_slots`, anyone may clear
+//! `redeem_paused` so holders can exit even if the admin key is lost.
+//! Minting stays paused; only the admin can re-enable it.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, RedeemForceUnpaused}, invariants::pause_timeout_elapsed, state::*};
+
+pub fn handler(ctx: Context<ForceUnpauseRedeem>) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  require!(global_state.redeem_paused, LaminarError::RedeemNotPaused);
+
+  let slot = ctx.accounts.clock.slot;
+  let pause_started_slot = global_state.pause_started_slot;
+  let max_pause_duration_slots = global_state.max_pause_duration_slots;
+
+  require!(
+    pause_timeout_elapsed(slot, pause_started_slot, max_pause_duration_slots),
+    LaminarError::PauseTimeoutNotElapsed
+  );
+
+  global_state.redeem_paused = false;
+  global_state.pause_started_slot = 0;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(RedeemForceUnpaused {
+    caller: ctx.accounts.caller.key(),
+    pause_started_slot,
+    max_pause_duration_slots,
+    slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Redeem pause lifted after {} slots (max {}); mint_paused={}",
+    slot.saturating_sub(pause_started_slot),
+    max_pause_duration_slots,
+    global_state.mint_paused
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ForceUnpauseRedeem<'info> {
+  pub caller: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
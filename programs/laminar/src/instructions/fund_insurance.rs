@@ -0,0 +1,174 @@
+//! fund_insurance instruction - permissionless deposit into the insurance fund
+//! The LST joins the vault like any other collateral, but its value (rounded
+//! down at the synced LST rate) is booked to `insurance_fund_lamports` rather
+//! than to claimable equity. aSOL NAV is unchanged; the fund only pays out as
+//! a top-up on insolvent amUSD redemptions.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::collateral::deposit_lst;
+use crate::error::LaminarError;
+use crate::events::{emit_event, InsuranceFunded};
+use crate::instructions::sync_exchange_rate_in_place;
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<FundInsurance>, lst_amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+
+  // A frozen vault fails every transfer CPI; reject before touching state.
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  // sync first
+  {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  assert_lst_snapshot_fresh(
+    ctx.accounts.clock.slot,
+    global_state.last_tvl_update_slot,
+    global_state.max_oracle_staleness_slots,
+  )?;
+  sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  }
+
+  let global_state = &ctx.accounts.global_state;
+
+  require!(lst_amount > 0, LaminarError::ZeroAmount);
+  require!(lst_amount >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
+  require!(
+    ctx.accounts.user_lst_account.amount >= lst_amount,
+    LaminarError::InsufficientCollateral
+  );
+
+  // Only what the vault actually receives is booked; a Token-2022 transfer
+  // fee is withheld out of it.
+  let lst_received = deposit_lst(
+    &ctx.accounts.token_program,
+    &ctx.accounts.lst_mint,
+    &ctx.accounts.user_lst_account,
+    &mut ctx.accounts.vault,
+    ctx.accounts.user.to_account_info(),
+    lst_amount,
+  )?;
+
+  // Capture values
+  let sol_price_usd = global_state.mock_sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let current_rounding_reserve = global_state.rounding_reserve_lamports;
+
+  // Conservative: the fund is credited the rounded-down value; the sub-lamport
+  // remainder stays with equity.
+  let value_lamports = compute_tvl_sol(lst_received, lst_to_sol_rate)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(value_lamports > 0, LaminarError::AmountTooSmall);
+
+  let new_lst_amount = global_state.total_lst_amount
+    .checked_add(lst_received)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_insurance_fund = global_state.insurance_fund_lamports
+    .checked_add(value_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  let new_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let liability = if global_state.amusd_supply > 0 {
+    compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_usd, global_state.redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+  let new_non_claimable = compute_non_claimable_sol(current_rounding_reserve, new_insurance_fund)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, liability, new_non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Single down-rounding on the LST -> SOL conversion: (k_lamports = 1, k_usd = 0)
+  let rounding_bound_lamports = derive_rounding_bound_lamports(1, 0, sol_price_usd)?;
+  assert_balance_sheet_holds(new_tvl, liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_lst_amount = new_lst_amount;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    global_state.increment_operation_counter()?;
+  }
+
+  require!(
+    ctx.accounts.vault.amount == ctx.accounts.global_state.total_lst_amount,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!(
+    "Insurance funded: {} LST, value={} lamports, fund={} lamports",
+    lst_received,
+    value_lamports,
+    new_insurance_fund
+  );
+
+  emit_event!(InsuranceFunded {
+    funder: ctx.accounts.user.key(),
+    lst_amount: lst_received,
+    value_lamports,
+    insurance_fund_lamports: new_insurance_fund,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Funder's LST token account (source of the deposit)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (receives LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
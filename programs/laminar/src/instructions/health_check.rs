@@ -0,0 +1,117 @@
+//! health_check instruction - permissionless protocol health snapshot
+//! Detects a vault frozen by the LST freeze authority and pauses redemptions
+//! (which would otherwise fail in the transfer CPI) until the vault is thawed.
+//! Emits a `HealthSnapshot` on every call.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+  error::LaminarError,
+  events::{emit_event, HealthSnapshot, VaultFrozenDetected},
+  math::{compute_book_tvl_sol, compute_cr_bps, compute_liability_at_rate_sol},
+  state::*,
+};
+
+pub fn handler(ctx: Context<HealthCheck>) -> Result<()> {
+  let vault_frozen = ctx.accounts.vault.is_frozen();
+  let vault_balance = ctx.accounts.vault.amount;
+  let vault_key = ctx.accounts.vault.key();
+  let slot = ctx.accounts.clock.slot;
+  let timestamp = ctx.accounts.clock.unix_timestamp;
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  if vault_frozen {
+    let redeem_paused_now = !global_state.redeem_paused;
+    if redeem_paused_now {
+      global_state.redeem_paused = true;
+      global_state.pause_started_slot = slot;
+      global_state.redeem_paused_by_vault_freeze = true;
+      global_state.increment_operation_counter()?;
+    }
+
+    emit_event!(VaultFrozenDetected {
+      caller: ctx.accounts.caller.key(),
+      vault: vault_key,
+      lst_mint: global_state.supported_lst_mint,
+      redeem_paused_now,
+      slot,
+      operation_counter: global_state.operation_counter,
+      timestamp,
+    });
+    msg!("VAULT FROZEN: redemptions paused (newly paused: {})", redeem_paused_now);
+  } else if global_state.redeem_paused_by_vault_freeze {
+    // Only lift a pause this path set; admin pauses clear the flag.
+    global_state.redeem_paused = false;
+    global_state.pause_started_slot = 0;
+    global_state.redeem_paused_by_vault_freeze = false;
+    global_state.increment_operation_counter()?;
+    msg!("Vault thawed: automatic redeem pause lifted");
+  }
+
+  let tvl_sol = compute_book_tvl_sol(global_state.total_lst_amount, global_state.mock_lst_to_sol_rate, global_state.collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let weighted_tvl_sol = compute_book_tvl_sol(global_state.total_lst_amount, global_state.mock_lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let liability_sol = if global_state.amusd_supply > 0 {
+    compute_liability_at_rate_sol(global_state.amusd_supply, global_state.mock_sol_price_usd, global_state.redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+
+  emit_event!(HealthSnapshot {
+    tvl_sol,
+    liability_sol,
+    cr_bps: compute_cr_bps(weighted_tvl_sol, liability_sol),
+    total_lst_amount: global_state.total_lst_amount,
+    vault_balance,
+    rounding_reserve_lamports: global_state.rounding_reserve_lamports,
+    insurance_fund_lamports: global_state.insurance_fund_lamports,
+    vault_frozen,
+    lst_mint_has_freeze_authority: global_state.lst_mint_has_freeze_authority,
+    mint_paused: global_state.mint_paused,
+    redeem_paused: global_state.redeem_paused,
+    slot,
+    operation_counter: global_state.operation_counter,
+    timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct HealthCheck<'info> {
+  pub caller: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
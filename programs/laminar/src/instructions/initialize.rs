@@ -3,9 +3,12 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
-use crate::{constants::{AMUSD_MINT_FEE_BPS, AMUSD_REDEEM_FEE_BPS, ASOL_MINT_FEE_BPS, ASOL_REDEEM_FEE_BPS, DEFAULT_FEE_MAX_MULTIPLIER_BPS, DEFAULT_FEE_MIN_MULTIPLIER_BPS, DEFAULT_MAX_ASOL_MINT_PER_ROUND, DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_LST_STALE_EPOCHS, DEFAULT_MAX_ORACLE_STALENESS_SLOTS, DEFAULT_NAV_FLOOR_LAMPORTS, DEFAULT_UNCERTAINTY_MAX_BPS}, error::LaminarError, state::*};
-use crate::math::{SOL_PRECISION};
+use crate::{constants::{AMUSD_MINT_FEE_BPS, AMUSD_REDEEM_FEE_BPS, ASOL_MINT_FEE_BPS, ASOL_REDEEM_FEE_BPS, DEFAULT_FEE_MAX_MULTIPLIER_BPS, DEFAULT_FEE_MIN_MULTIPLIER_BPS, DEFAULT_FEE_SPLIT_INSURANCE_BPS, DEFAULT_MAX_FLASH_LOAN_BPS, DEFAULT_MAX_ASOL_MINT_PER_ROUND, DEFAULT_MINT_ROUND_SLOTS, DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_LST_RATE, DEFAULT_MAX_LST_STALE_EPOCHS, DEFAULT_MAX_ORACLE_AGE_SECONDS, DEFAULT_MAX_ORACLE_DEVIATION_BPS, DEFAULT_MAX_ORACLE_STALENESS_SLOTS, DEFAULT_MAX_PAUSE_DURATION_SLOTS, DEFAULT_MAX_SOL_PRICE_USD, DEFAULT_MIN_LST_RATE, DEFAULT_MIN_SOL_PRICE_USD, DEFAULT_NAV_FLOOR_LAMPORTS, DEFAULT_PAIR_CR_TOLERANCE_BPS, DEFAULT_REBALANCE_DISCOUNT_BPS, DEFAULT_RECOVERY_BONUS_BPS, DEFAULT_UNCERTAINTY_MAX_BPS}, error::LaminarError, oracle::PriceSource, state::*};
+use crate::math::{BPS_PRECISION, SOL_PRECISION};
+use crate::invariants::validate_risk_config;
+use crate::events::{emit_event, ProtocolInitialized};
 use crate::constants::DEFAULT_MAX_ROUNDING_RESERVE_LAMPORTS;
+use crate::constants::NO_FALLBACK_PRICE_SOURCE;
 
 pub fn handler(
   ctx: Context<Initialize>,
@@ -14,8 +17,7 @@ pub fn handler(
   mock_sol_price_usd: u64,
   mock_lst_to_sol_rate: u64,
 ) -> Result<()> {
-  require!(min_cr_bps >= 10_000, LaminarError::InvalidParameter);  // Min 100%
-  require!(target_cr_bps > min_cr_bps, LaminarError::InvalidParameter);
+  validate_risk_config(min_cr_bps, target_cr_bps)?;  // Min 100%, target > min
   require!(mock_sol_price_usd > 0, LaminarError::ZeroAmount);
   require!(mock_lst_to_sol_rate > 0, LaminarError::ZeroAmount);
   require!(mock_lst_to_sol_rate >= SOL_PRECISION / 2, LaminarError::InvalidParameter);  // LST can't be worth less than half SOL
@@ -29,17 +31,42 @@ pub fn handler(
   
   let global_state = &mut ctx.accounts.global_state;
 
-  global_state.version = 1;
+  global_state.version = CURRENT_VERSION;
   global_state.bump = ctx.bumps.global_state;
   global_state.vault_authority_bump = ctx.bumps.vault_authority;
   global_state.operation_counter = 0;
   global_state.authority = ctx.accounts.authority.key();
+  global_state.pending_authority = Pubkey::default();
   global_state.amusd_mint = ctx.accounts.amusd_mint.key();
   global_state.asol_mint = ctx.accounts.asol_mint.key();
 
   global_state.treasury = ctx.accounts.authority.key();
+  global_state.treasury_owner_program = Pubkey::default();
 
   global_state.supported_lst_mint = ctx.accounts.lst_mint.key();
+  global_state.lst_mint_has_freeze_authority = ctx.accounts.lst_mint.freeze_authority.is_some();
+  global_state.redeem_paused_by_vault_freeze = false;
+  global_state.pyth_price_feed = Pubkey::default();
+  global_state.switchboard_feed = Pubkey::default();
+  global_state.lst_stake_pool = Pubkey::default();
+  global_state.marinade_state = Pubkey::default();
+  global_state.lst_calculator_program = Pubkey::default();
+  global_state.lst_calculator_state = Pubkey::default();
+  global_state.cumulative_haircut_shortfall_lamports = 0;
+  global_state.episode_haircut_shortfall_lamports = 0;
+  global_state.max_episode_haircut_lamports = 0;
+  global_state.amusd_redeem_paused_by_haircut = false;
+  global_state.price_source = PriceSource::Mock as u8;
+  global_state.secondary_price_source = PriceSource::Mock as u8;
+  global_state.max_oracle_deviation_bps = DEFAULT_MAX_ORACLE_DEVIATION_BPS;
+  // Price breaker stays off until governance sets a bound
+  global_state.max_price_move_bps = 0;
+  global_state.min_sol_price_usd = DEFAULT_MIN_SOL_PRICE_USD;
+  global_state.max_sol_price_usd = DEFAULT_MAX_SOL_PRICE_USD;
+  global_state.min_lst_rate = DEFAULT_MIN_LST_RATE;
+  global_state.max_lst_rate = DEFAULT_MAX_LST_RATE;
+  global_state.assert_sol_price_within_bounds(mock_sol_price_usd)?;
+  global_state.assert_lst_rate_within_bounds(mock_lst_to_sol_rate)?;
 
   global_state.total_lst_amount = 0;
   global_state.amusd_supply = 0;
@@ -50,6 +77,10 @@ pub fn handler(
 
   global_state.mint_paused = false;
   global_state.redeem_paused = false;
+  global_state.pause_started_slot = 0;
+  global_state.max_pause_duration_slots = DEFAULT_MAX_PAUSE_DURATION_SLOTS;
+  // Instant setters stay usable until governance queues a non-zero timelock
+  global_state.param_timelock_slots = 0;
 
   // global_state.locked = false;
 
@@ -76,8 +107,52 @@ pub fn handler(
   global_state.max_asol_mint_per_round = DEFAULT_MAX_ASOL_MINT_PER_ROUND;
   global_state.last_tvl_update_slot = ctx.accounts.clock.slot;
   global_state.last_oracle_update_slot = ctx.accounts.clock.slot;
-  global_state.mock_sol_price_usd = mock_sol_price_usd;
-  global_state.mock_lst_to_sol_rate = mock_lst_to_sol_rate;
+  global_state.last_oracle_update_timestamp = ctx.accounts.clock.unix_timestamp;
+  global_state.max_oracle_age_seconds = DEFAULT_MAX_ORACLE_AGE_SECONDS;
+  global_state.fallback_price_source = NO_FALLBACK_PRICE_SOURCE;
+  global_state.fallback_uncertainty_bps = 0;
+  global_state.ema_sol_price_usd = mock_sol_price_usd;
+  global_state.ema_half_life_slots = 0;
+  global_state.last_ema_update_slot = ctx.accounts.clock.slot;
+  global_state.stability_pool_amusd = 0;
+  global_state.rebalance_discount_bps = DEFAULT_REBALANCE_DISCOUNT_BPS;
+  global_state.recovery_mode = false;
+  global_state.recovery_mode_entered_slot = 0;
+  global_state.recovery_bonus_bps = DEFAULT_RECOVERY_BONUS_BPS;
+  global_state.settled = false;
+  global_state.settlement_slot = 0;
+  global_state.settlement_sol_price_usd = 0;
+  global_state.settlement_lst_to_sol_rate = 0;
+  global_state.insurance_fund_lamports = 0;
+  global_state.fee_split_insurance_bps = DEFAULT_FEE_SPLIT_INSURANCE_BPS;
+  global_state.redemption_rate_bps = BPS_PRECISION;
+  global_state.max_flash_loan_bps = DEFAULT_MAX_FLASH_LOAN_BPS;
+  global_state.flash_loan_utilization_slot = 0;
+  global_state.collateral_tvl_lamports = 0;
+  global_state.collateral_type_count = 0;
+  global_state.deprecated_collateral_tvl_lamports = 0;
+  global_state.weighted_collateral_tvl_lamports = 0;
+  global_state.psm_usdc_mint = Pubkey::default();
+  global_state.psm_fee_bps = 0;
+  global_state.psm_debt_ceiling = 0;
+  global_state.psm_amusd_outstanding = 0;
+  global_state.savings_fee_split_bps = 0;
+  global_state.stability_fee_bps_per_year = 0;
+  global_state.last_accrual_timestamp = ctx.accounts.clock.unix_timestamp;
+  global_state.amusd_supply_cap = 0;
+  global_state.asol_supply_cap = 0;
+  global_state.max_total_lst_amount = 0;
+  global_state.max_single_deposit_lst = 0;
+  global_state.max_single_redeem_units = 0;
+  global_state.per_user_amusd_cap = 0;
+  global_state.mint_round_slots = DEFAULT_MINT_ROUND_SLOTS;
+  global_state.current_round_start_slot = ctx.accounts.clock.slot;
+  global_state.asol_minted_this_round = 0;
+  global_state.min_action_gap_slots = 0;
+  global_state.asol_redeem_cooldown_slots = 0;
+  global_state.next_redemption_ticket = 0;
+  global_state.redemption_queue_head = 0;
+  global_state.pair_cr_tolerance_bps = DEFAULT_PAIR_CR_TOLERANCE_BPS;
   global_state.mock_oracle_confidence_usd = 0;
 
   global_state._reserved = [0; 2];
@@ -88,20 +163,25 @@ pub fn handler(
   msg!("Supported LST: {}", global_state.supported_lst_mint);
   msg!("Min CR: {}bps", min_cr_bps);
   msg!("Target CR: {}bps", target_cr_bps);
+  if global_state.lst_mint_has_freeze_authority {
+    msg!("WARNING: LST mint has a freeze authority; vault freeze risk is tracked by health_check");
+  }
 
-  emit!(crate::events::ProtocolInitialized {
+  emit_event!(ProtocolInitialized {
     authority: ctx.accounts.authority.key(),
     amusd_mint: global_state.amusd_mint,
     asol_mint: global_state.asol_mint,
     supported_lst_mint: global_state.supported_lst_mint,
     min_cr_bps,
     target_cr_bps,
+    operation_counter: global_state.operation_counter,
     timestamp: ctx.accounts.clock.unix_timestamp,
   });
 
   Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
   #[account(mut)]
@@ -0,0 +1,86 @@
+//! initialize_psm instruction - one-time creation of the Peg Stability Module (admin only)
+//! Creates the PSM's USDC vault, a token account PDA [PSM_VAULT_SEED] owned by
+//! the primary vault authority, and records the stablecoin with its fee and
+//! debt ceiling. Swaps open immediately.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{constants::MAX_PSM_FEE_BPS, error::LaminarError, events::{emit_event, PsmInitialized}, state::*};
+
+pub fn handler(ctx: Context<InitializePsm>, psm_fee_bps: u64, psm_debt_ceiling: u64) -> Result<()> {
+  let usdc_mint = ctx.accounts.usdc_mint.key();
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  require!(
+    usdc_mint != global_state.supported_lst_mint
+      && usdc_mint != global_state.amusd_mint
+      && usdc_mint != global_state.asol_mint,
+    LaminarError::InvalidMint
+  );
+  require!(psm_fee_bps <= MAX_PSM_FEE_BPS, LaminarError::InvalidParameter);
+
+  global_state.psm_usdc_mint = usdc_mint;
+  global_state.psm_fee_bps = psm_fee_bps;
+  global_state.psm_debt_ceiling = psm_debt_ceiling;
+  global_state.psm_amusd_outstanding = 0;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PsmInitialized {
+    authority: ctx.accounts.authority.key(),
+    usdc_mint,
+    psm_vault: ctx.accounts.psm_vault.key(),
+    psm_fee_bps,
+    psm_debt_ceiling,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("PSM initialized: {} at {} bps, ceiling {} amUSD", usdc_mint, psm_fee_bps, psm_debt_ceiling);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePsm<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// PSM USDC vault; `init` rejects a second initialization
+  #[account(
+    init,
+    payer = authority,
+    seeds = [PSM_VAULT_SEED],
+    bump,
+    token::mint = usdc_mint,
+    token::authority = vault_authority,
+    token::token_program = token_program,
+  )]
+  pub psm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// The stablecoin amUSD is swapped against at par
+  pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump = global_state.vault_authority_bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,74 @@
+//! initialize_savings_pool instruction - one-time creation of the Savings Pool
+//! Creates the pool PDA and its amUSD token account. Deposits open at once;
+//! fees only flow in after `update_savings_fee_split` sets a split.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{events::{emit_event, SavingsPoolInitialized}, state::*};
+
+pub fn handler(ctx: Context<InitializeSavingsPool>) -> Result<()> {
+  ctx.accounts.global_state.validate_version()?;
+
+  let savings_pool = &mut ctx.accounts.savings_pool;
+  savings_pool.bump = ctx.bumps.savings_pool;
+  savings_pool.amusd_vault = ctx.accounts.pool_amusd_vault.key();
+  savings_pool.total_pool_amusd = 0;
+  savings_pool.total_shares = 0;
+  savings_pool._reserved = [0; 4];
+
+  emit_event!(SavingsPoolInitialized {
+    authority: ctx.accounts.authority.key(),
+    savings_pool: savings_pool.key(),
+    amusd_vault: savings_pool.amusd_vault,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Savings Pool initialized: amUSD vault {}", savings_pool.amusd_vault);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeSavingsPool<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    has_one = authority,
+    has_one = amusd_mint,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    init,
+    payer = authority,
+    space = SavingsPool::LEN,
+    seeds = [SAVINGS_POOL_SEED],
+    bump
+  )]
+  pub savings_pool: Box<Account<'info, SavingsPool>>,
+
+  /// Pool amUSD vault - deterministic ATA owned by the savings_pool PDA
+  #[account(
+    init,
+    payer = authority,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = savings_pool,
+    associated_token::token_program = token_program,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,90 @@
+//! initialize_stability_pool instruction - one-time creation of the Stability Pool
+//! Creates the pool PDA and its amUSD and LST token accounts; deposits open immediately.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{error::LaminarError, events::{emit_event, StabilityPoolInitialized}, state::*};
+
+pub fn handler(ctx: Context<InitializeStabilityPool>) -> Result<()> {
+  ctx.accounts.global_state.validate_version()?;
+
+  let stability_pool = &mut ctx.accounts.stability_pool;
+  stability_pool.bump = ctx.bumps.stability_pool;
+  stability_pool.amusd_vault = ctx.accounts.pool_amusd_vault.key();
+  stability_pool.total_pool_amusd = 0;
+  stability_pool.total_shares = 0;
+  stability_pool.lst_vault = ctx.accounts.pool_lst_vault.key();
+  stability_pool.total_pool_lst = 0;
+  stability_pool.lst_gain_per_share = 0;
+  stability_pool._reserved = [0; 4];
+
+  emit_event!(StabilityPoolInitialized {
+    authority: ctx.accounts.authority.key(),
+    stability_pool: stability_pool.key(),
+    amusd_vault: stability_pool.amusd_vault,
+    lst_vault: stability_pool.lst_vault,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Stability Pool initialized: amUSD vault {}", stability_pool.amusd_vault);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeStabilityPool<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    has_one = authority,
+    has_one = amusd_mint,
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    init,
+    payer = authority,
+    space = StabilityPool::LEN,
+    seeds = [STABILITY_POOL_SEED],
+    bump
+  )]
+  pub stability_pool: Box<Account<'info, StabilityPool>>,
+
+  /// Pool amUSD vault - deterministic ATA owned by the stability_pool PDA
+  #[account(
+    init,
+    payer = authority,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = stability_pool,
+    associated_token::token_program = token_program,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Pool LST vault - receives collateral from drawdowns, owned by the stability_pool PDA
+  #[account(
+    init,
+    payer = authority,
+    associated_token::mint = lst_mint,
+    associated_token::authority = stability_pool,
+    associated_token::token_program = token_program,
+  )]
+  pub pool_lst_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
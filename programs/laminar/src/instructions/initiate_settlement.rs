@@ -0,0 +1,107 @@
+//! initiate_settlement instruction - irreversible global settlement (admin only)
+//! Freezes the SOL price and LST rate at their current values, closes minting
+//! for good and sets `settled`. From then on `redeem_amusd` pays par at the
+//! frozen price with no fee and `redeem_asol` pays the frozen NAV, neither
+//! gated on CR; an insolvent book still haircuts amUSD pro rata. Whoever burns
+//! the last outstanding token takes the vault's residual dust.
+//!
+//! With a dead feed, point `price_source` at Mock and set the mock price
+//! before settling. Only the primary vault is snapshotted, so every
+//! `CollateralVault` must be redeemed out first.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, SettlementInitiated};
+use crate::math::*;
+use crate::oracle::{resolve_redeem_price, PriceAccounts, PriceBias};
+use crate::state::*;
+
+pub fn handler(ctx: Context<InitiateSettlement>) -> Result<()> {
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  require!(global_state.collateral_tvl_lamports == 0, LaminarError::CollateralVaultsNotEmpty);
+
+  // The low price values amUSD liability highest, so the senior tranche
+  // is never the one short-changed by where the snapshot lands.
+  let oracle_price = resolve_redeem_price(
+    &PriceAccounts {
+      price_update: ctx.accounts.price_update.as_deref(),
+      switchboard_feed: ctx.accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    ctx.accounts.clock.slot,
+    ctx.accounts.clock.unix_timestamp,
+    PriceBias::Low,
+  )?;
+  let sol_price_usd = oracle_price.sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+
+  let tvl = compute_book_tvl_sol(global_state.total_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let liability = compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_usd, global_state.redemption_rate_bps).ok_or(LaminarError::MathOverflow)?;
+  let cr_bps = compute_cr_bps(tvl, liability);
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.settled = true;
+  global_state.settlement_slot = ctx.accounts.clock.slot;
+  global_state.settlement_sol_price_usd = sol_price_usd;
+  global_state.settlement_lst_to_sol_rate = lst_to_sol_rate;
+  global_state.mint_paused = true;
+  global_state.redeem_paused = false;
+  global_state.pause_started_slot = 0;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(SettlementInitiated {
+    authority: ctx.accounts.authority.key(),
+    sol_price_usd,
+    lst_to_sol_rate,
+    total_lst_amount: global_state.total_lst_amount,
+    amusd_supply: global_state.amusd_supply,
+    asol_supply: global_state.asol_supply,
+    cr_bps,
+    slot: ctx.accounts.clock.slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Settlement initiated: price={}, lst_rate={}, CR={}bps",
+    sol_price_usd,
+    lst_to_sol_rate,
+    cr_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitiateSettlement<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+}
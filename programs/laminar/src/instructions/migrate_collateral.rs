@@ -0,0 +1,267 @@
+//! migrate_collateral instruction - swap one collateral LST for another (admin only)
+//! Moves `lst_amount` of `from_lst` out of its vault to the admin and takes
+//! the same SOL value of `to_lst` from the admin into that mint's vault, in
+//! one instruction. Either side may be the primary LST or a `CollateralVault`.
+//! Supplies and user claims are untouched; book TVL may not fall by more than
+//! the rounding bound. This is how the book leaves an LST whose stake pool is
+//! being wound down.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::collateral::{
+  assert_accepts_deposits, deposit_lst, expected_vault_balance, validate_leg_accounts, vault_authority_seeds, CollateralLeg,
+};
+use crate::constants::MIN_PROTOCOL_TVL;
+use crate::error::LaminarError;
+use crate::events::{emit_event, CollateralMigrated};
+use crate::instructions::sync_exchange_rate_in_place;
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<MigrateCollateral>, lst_amount: u64, from_lst: Pubkey, to_lst: Pubkey) -> Result<()> {
+  require!(from_lst != to_lst, LaminarError::InvalidParameter);
+  require!(lst_amount >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
+
+  // A frozen vault fails every transfer CPI; reject before touching state.
+  require!(!ctx.accounts.from_vault.is_frozen(), LaminarError::VaultFrozen);
+  require!(!ctx.accounts.to_vault.is_frozen(), LaminarError::VaultFrozen);
+
+  // sync first
+  {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  validate_leg_accounts(
+    global_state,
+    &global_state.key(),
+    ctx.accounts.from_collateral_vault.as_deref(),
+    &ctx.accounts.from_vault_authority.key(),
+    &ctx.accounts.from_vault.key(),
+  )?;
+  validate_leg_accounts(
+    global_state,
+    &global_state.key(),
+    ctx.accounts.to_collateral_vault.as_deref(),
+    &ctx.accounts.to_vault_authority.key(),
+    &ctx.accounts.to_vault.key(),
+  )?;
+  assert_lst_snapshot_fresh(
+    ctx.accounts.clock.slot,
+    global_state.last_tvl_update_slot,
+    global_state.max_oracle_staleness_slots,
+  )?;
+  sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  }
+
+  let global_state = &ctx.accounts.global_state;
+  assert_accepts_deposits(ctx.accounts.to_collateral_vault.as_deref())?;
+
+  let primary_lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let from_leg = CollateralLeg::resolve(global_state, ctx.accounts.from_collateral_vault.as_deref(), primary_lst_to_sol_rate)?;
+  let to_leg = CollateralLeg::resolve(global_state, ctx.accounts.to_collateral_vault.as_deref(), primary_lst_to_sol_rate)?;
+
+  let new_from_lst_amount = from_leg.lst_amount
+    .checked_sub(lst_amount)
+    .ok_or(LaminarError::InsufficientCollateral)?;
+  require!(
+    new_from_lst_amount >= MIN_PROTOCOL_TVL || new_from_lst_amount == 0,
+    LaminarError::BelowMinimumTVL
+  );
+
+  // Both conversions round up, so the LST coming in is worth at least what leaves.
+  let value_lamports = mul_div_up(lst_amount, from_leg.lst_to_sol_rate, SOL_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  let to_lst_amount = mul_div_up(value_lamports, SOL_PRECISION, to_leg.lst_to_sol_rate)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(
+    ctx.accounts.authority_to_account.amount >= to_lst_amount,
+    LaminarError::InsufficientCollateral
+  );
+
+  // Incoming LST first, so the book never holds less than it did. A transfer
+  // fee on `to_lst` comes out of what lands, and the TVL check below sees it.
+  let to_lst_received = deposit_lst(
+    &ctx.accounts.token_program,
+    &ctx.accounts.to_mint,
+    &ctx.accounts.authority_to_account,
+    &mut ctx.accounts.to_vault,
+    ctx.accounts.authority.to_account_info(),
+    to_lst_amount,
+  )?;
+  let new_to_lst_amount = to_leg.lst_amount
+    .checked_add(to_lst_received)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // The legs are different vaults: value the book with the outgoing one
+  // drained, then add what the incoming one gains.
+  let old_tvl = from_leg.tvl(from_leg.lst_amount)?;
+  let to_vault_gain = compute_tvl_sol(new_to_lst_amount, to_leg.lst_to_sol_rate)
+    .zip(compute_tvl_sol(to_leg.lst_amount, to_leg.lst_to_sol_rate))
+    .and_then(|(new_value, old_value)| new_value.checked_sub(old_value))
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_tvl = from_leg.tvl(new_from_lst_amount)?
+    .checked_add(to_vault_gain)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Each vault's value rounds down once: (k_lamports = 2, k_usd = 0)
+  let rounding_bound_lamports = derive_rounding_bound_lamports(2, 0, global_state.mock_sol_price_usd)?;
+  require!(
+    new_tvl.checked_add(rounding_bound_lamports).ok_or(LaminarError::MathOverflow)? >= old_tvl,
+    LaminarError::MigrationReducesTvl
+  );
+
+  // Update state BEFORE the withdrawal
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    CollateralLeg::commit(global_state, ctx.accounts.from_collateral_vault.as_deref_mut(), new_from_lst_amount)?;
+    CollateralLeg::commit(global_state, ctx.accounts.to_collateral_vault.as_deref_mut(), new_to_lst_amount)?;
+    global_state.increment_operation_counter()?;
+  }
+
+  let vault_authority_bump = [from_leg.vault_authority_bump];
+  let seeds = vault_authority_seeds(&from_lst, &vault_authority_bump, ctx.accounts.from_collateral_vault.is_some());
+  let signer = &[&seeds[..]];
+
+  let withdraw_accounts = TransferChecked {
+    from: ctx.accounts.from_vault.to_account_info(),
+    mint: ctx.accounts.from_mint.to_account_info(),
+    to: ctx.accounts.authority_from_account.to_account_info(),
+    authority: ctx.accounts.from_vault_authority.to_account_info(),
+  };
+  let cpi_ctx_withdraw = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    withdraw_accounts,
+    signer,
+  );
+  token_interface::transfer_checked(cpi_ctx_withdraw, lst_amount, ctx.accounts.from_mint.decimals)?;
+
+  ctx.accounts.from_vault.reload()?;
+  ctx.accounts.to_vault.reload()?;
+  require!(
+    ctx.accounts.from_vault.amount == expected_vault_balance(&ctx.accounts.global_state, ctx.accounts.from_collateral_vault.as_deref()),
+    LaminarError::BalanceSheetViolation
+  );
+  require!(
+    ctx.accounts.to_vault.amount == expected_vault_balance(&ctx.accounts.global_state, ctx.accounts.to_collateral_vault.as_deref()),
+    LaminarError::BalanceSheetViolation
+  );
+
+  emit_event!(CollateralMigrated {
+    authority: ctx.accounts.authority.key(),
+    from_lst_mint: from_lst,
+    to_lst_mint: to_lst,
+    from_lst_amount: lst_amount,
+    to_lst_amount: to_lst_received,
+    value_lamports,
+    old_tvl_lamports: old_tvl,
+    new_tvl_lamports: new_tvl,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Collateral migrated: {} {} -> {} {}, TVL {} -> {}",
+    lst_amount,
+    from_lst,
+    to_lst_received,
+    to_lst,
+    old_tvl,
+    new_tvl
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(lst_amount: u64, from_lst: Pubkey, to_lst: Pubkey)]
+pub struct MigrateCollateral<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Mint leaving the book
+  #[account(
+    address = from_lst,
+    constraint = from_lst == global_state.supported_lst_mint || from_collateral_vault.is_some() @ LaminarError::UnsupportedLST
+  )]
+  pub from_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CollateralVault of `from_lst`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, from_lst.as_ref()],
+    bump = from_collateral_vault.bump,
+  )]
+  pub from_collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Vault `lst_amount` is withdrawn from
+  #[account(
+    mut,
+    token::mint = from_mint,
+    token::authority = from_vault_authority,
+    constraint = from_vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub from_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: re-derived from stored bumps in `validate_leg_accounts`
+  pub from_vault_authority: UncheckedAccount<'info>,
+
+  /// Admin account receiving the withdrawn LST
+  #[account(
+    mut,
+    token::mint = from_mint,
+    token::authority = authority,
+  )]
+  pub authority_from_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Mint joining the book
+  #[account(
+    address = to_lst,
+    constraint = to_lst == global_state.supported_lst_mint || to_collateral_vault.is_some() @ LaminarError::UnsupportedLST
+  )]
+  pub to_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CollateralVault of `to_lst`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, to_lst.as_ref()],
+    bump = to_collateral_vault.bump,
+  )]
+  pub to_collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Vault the replacement LST is deposited into
+  #[account(
+    mut,
+    token::mint = to_mint,
+    token::authority = to_vault_authority,
+    constraint = to_vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub to_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: re-derived from stored bumps in `validate_leg_accounts`
+  pub to_vault_authority: UncheckedAccount<'info>,
+
+  /// Admin account supplying the replacement LST
+  #[account(
+    mut,
+    token::mint = to_mint,
+    token::authority = authority,
+  )]
+  pub authority_to_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
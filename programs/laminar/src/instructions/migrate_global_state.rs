@@ -0,0 +1,70 @@
+//! migrate_global_state instruction - admin repair of GlobalState after an upgrade
+//! Validates fields a migration may have corrupted. An `operation_counter` at or
+//! above `OPERATION_COUNTER_CEILING` is reset to zero with an explicit event so
+//! off-chain consumers can mark the discontinuity. State written before
+//! `price_source` existed gets the source its configured feed implied.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, OperationCounterReset, PriceSourceUpdated}, state::*};
+
+pub fn handler(ctx: Context<MigrateGlobalState>) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+
+  if let Some(old_price_source) = global_state.migrate_price_source() {
+    emit_event!(PriceSourceUpdated {
+      authority: ctx.accounts.authority.key(),
+      old_price_source,
+      new_price_source: global_state.price_source,
+      operation_counter: global_state.operation_counter,
+      timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    msg!("price_source migrated: {} -> {}", old_price_source, global_state.price_source);
+  }
+
+  match global_state.reset_corrupt_operation_counter() {
+    Some(old_operation_counter) => {
+      emit_event!(OperationCounterReset {
+        authority: ctx.accounts.authority.key(),
+        old_operation_counter,
+        new_operation_counter: global_state.operation_counter,
+        slot: ctx.accounts.clock.slot,
+        operation_counter: global_state.operation_counter,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+      });
+      msg!("operation_counter reset: {} -> {}", old_operation_counter, global_state.operation_counter);
+    }
+    None => {
+      global_state.increment_operation_counter()?;
+      msg!("GlobalState valid; operation_counter={}", global_state.operation_counter);
+    }
+  }
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
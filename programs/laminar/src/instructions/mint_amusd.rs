@@ -5,263 +5,463 @@
 use anchor_lang::prelude::program_option::COption;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked, MintTo};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, MintTo};
 // use crate::constants::AMUSD_MINT_FEE_BPS;
-use crate::events::AmUSDMinted;
+use crate::events::{emit_recovery_transition, AmUSDMinted, EventCpi};
 use crate::state::*;
 use crate::math::*;
 use crate::invariants::*;
+use crate::constants::{CURRENT_ARGS_VERSION, ENTRYPOINT_V1, ENTRYPOINT_V2, ENTRYPOINT_ZAP};
 use crate::error::LaminarError;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::savings::{assert_savings_vault_synced, route_savings_fee};
+use crate::position::{record_position_mint, PositionMint};
+use crate::collateral::{assert_accepts_deposits, deposit_lst, expected_vault_balance, needs_lst_snapshot, stake_sol_deposit, sync_native_deposit, validate_leg_accounts, wrap_sol_deposit, CollateralLeg};
+use crate::lst_rate::{StakePoolDeposit, SPL_STAKE_POOL_PROGRAM_ID};
+use crate::quote::{quote_mint_amusd, Quote};
 use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::instructions::accrue_stability_fee::accrue_stability_fee_to_treasury;
+
+
+/// Versioned arguments for `mint_amusd_v2`.
+///
+/// New optional fields are appended with defaults that reproduce v1
+/// behavior, so `mint_amusd` and `mint_amusd_v2` always share one code path.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAmusdArgs {
+  /// Layout version of this struct (see `CURRENT_ARGS_VERSION`)
+  pub args_version: u8,
+  /// Deposit amount in LST base units
+  pub lst_amount: u64,
+  /// Minimum amUSD the user accepts after fees
+  pub min_amusd_out: u64,
+  /// Last slot the order may execute in; 0 for no deadline (args version 3)
+  pub deadline_slot: u64,
+  /// Highest dynamic fee the user accepts, in bps; 0 for no bound (args version 4)
+  pub max_fee_bps: u64,
+  /// Caller's order reference, echoed in the event and otherwise unused; 0 for none (args version 5)
+  pub client_order_id: u64,
+}
 
+impl MintAmusdArgs {
+  /// Args equivalent to a v1 `mint_amusd` call.
+  pub fn v1(lst_amount: u64, min_amusd_out: u64) -> Self {
+    Self {
+      args_version: CURRENT_ARGS_VERSION,
+      lst_amount,
+      min_amusd_out,
+      deadline_slot: 0,
+      max_fee_bps: 0,
+      client_order_id: 0,
+    }
+  }
+}
 
+/// v1 entrypoint - thin wrapper over the v2 args path.
 pub fn handler(
   ctx: Context<MintAmUSD>,
-  lst_amount: u64, 
+  lst_amount: u64,
   min_amusd_out: u64,
 ) -> Result<()> {
+  process(ctx, MintAmusdArgs::v1(lst_amount, min_amusd_out), ENTRYPOINT_V1)
+}
+
+/// v2 entrypoint - single versioned args struct.
+pub fn handler_v2(ctx: Context<MintAmUSD>, args: MintAmusdArgs) -> Result<()> {
+  process(ctx, args, ENTRYPOINT_V2)
+}
+
+/// SOL zap entrypoint - turns `lamports` of native SOL into collateral, then
+/// mints against it: the primary LST is staked for through its stake pool,
+/// the wSOL leg's SOL is wrapped into the user's wSOL account. Both are
+/// outgoing CPIs, so the caller is still checked against CPI invocation.
+pub fn handler_zap(ctx: Context<MintAmUSD>, lamports: u64, min_amusd_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  if ctx.accounts.collateral_vault.is_none() {
+    let stake_pool = ctx.accounts.stake_pool_deposit()?;
+    let user = ctx.accounts.user.to_account_info();
+    let lst_amount = stake_sol_deposit(&stake_pool, user, &mut ctx.accounts.user_lst_account, lamports)?;
+    return process(ctx, MintAmusdArgs::v1(lst_amount, min_amusd_out), ENTRYPOINT_ZAP);
+  }
+
+  wrap_sol_deposit(
+    ctx.accounts.collateral_vault.as_deref(),
+    &ctx.accounts.user,
+    &ctx.accounts.user_lst_account,
+    &ctx.accounts.system_program,
+    lamports,
+  )?;
+  process(ctx, MintAmusdArgs::v1(lamports, min_amusd_out), ENTRYPOINT_ZAP)
+}
+
+fn process(
+  ctx: Context<MintAmUSD>,
+  args: MintAmusdArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  validate_args_version(args.args_version)?;
+
+  let user_position_bump = ctx.bumps.user_position;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+  execute_mint_amusd(
+    MintAmusdAccounts {
+      user: accounts.user.to_account_info(),
+      global_state: &mut accounts.global_state,
+      amusd_mint: &mut accounts.amusd_mint,
+      treasury_amusd_account: &accounts.treasury_amusd_account,
+      user_lst_account: &mut accounts.user_lst_account,
+      recipient_amusd_account: accounts.recipient_amusd_account.as_deref().unwrap_or(&accounts.user_amusd_account),
+      vault: &mut accounts.vault,
+      vault_authority: &accounts.vault_authority,
+      lst_mint: &accounts.lst_mint,
+      token_program: &accounts.token_program,
+      clock: &accounts.clock,
+      price_update: accounts.price_update.as_deref(),
+      switchboard_feed: accounts.switchboard_feed.as_deref(),
+      collateral_vault: accounts.collateral_vault.as_deref_mut(),
+      savings_pool: accounts.savings_pool.as_deref_mut(),
+      savings_amusd_vault: accounts.savings_amusd_vault.as_deref_mut(),
+      user_position: accounts.user_position.as_deref_mut(),
+      user_position_bump,
+      event_cpi,
+    },
+    args,
+    entrypoint_version,
+  )
+}
+
+/// Everything an amUSD mint reads and writes; filled from `MintAmUSD` or
+/// from a paired mint's accounts
+pub struct MintAmusdAccounts<'a, 'info> {
+  /// Signing wallet; funds the deposit and owns the position
+  pub user: AccountInfo<'info>,
+  pub global_state: &'a mut Account<'info, GlobalState>,
+  pub amusd_mint: &'a mut InterfaceAccount<'info, Mint>,
+  pub treasury_amusd_account: &'a InterfaceAccount<'info, TokenAccount>,
+  pub user_lst_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+  /// Receives the minted amUSD
+  pub recipient_amusd_account: &'a InterfaceAccount<'info, TokenAccount>,
+  pub vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+  pub vault_authority: &'a AccountInfo<'info>,
+  pub lst_mint: &'a InterfaceAccount<'info, Mint>,
+  pub token_program: &'a Interface<'info, TokenInterface>,
+  pub clock: &'a Clock,
+  pub price_update: Option<&'a AccountInfo<'info>>,
+  pub switchboard_feed: Option<&'a AccountInfo<'info>>,
+  pub collateral_vault: Option<&'a mut Account<'info, CollateralVault>>,
+  pub savings_pool: Option<&'a mut Account<'info, SavingsPool>>,
+  pub savings_amusd_vault: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
+  pub user_position: Option<&'a mut Account<'info, UserPosition>>,
+  pub user_position_bump: Option<u8>,
+  pub event_cpi: EventCpi<'info>,
+}
+
+/// Deposit `args.lst_amount` and mint amUSD against it at current prices.
+/// Args-version checks are the caller's job.
+pub fn execute_mint_amusd<'info>(
+  mut accounts: MintAmusdAccounts<'_, 'info>,
+  args: MintAmusdArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  let MintAmusdArgs { lst_amount, min_amusd_out, deadline_slot, max_fee_bps, client_order_id, .. } = args;
+  assert_before_deadline(deadline_slot, accounts.clock.slot)?;
+
   // All validations before any state changes
   
   assert_not_cpi_context()?;
 
+  // A frozen vault fails every transfer CPI; reject before touching state.
+  require!(!accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+  sync_native_deposit(
+    accounts.collateral_vault.as_deref(),
+    accounts.user_lst_account,
+    accounts.token_program,
+  )?;
+
   // sync first
   {
-  let global_state = &mut ctx.accounts.global_state;
+  let global_state = &mut accounts.global_state;
   global_state.validate_version()?;
-  assert_lst_snapshot_fresh(ctx.accounts.clock.slot, global_state.last_tvl_update_slot, global_state.max_oracle_staleness_slots,)?;
-  sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  validate_leg_accounts(
+    global_state,
+    &global_state.key(),
+    accounts.collateral_vault.as_deref(),
+    &accounts.vault_authority.key(),
+    &accounts.vault.key(),
+  )?;
+  if needs_lst_snapshot(global_state, accounts.collateral_vault.as_deref()) {
+    assert_lst_snapshot_fresh(accounts.clock.slot, global_state.last_tvl_update_slot, global_state.max_oracle_staleness_slots,)?;
+  }
+  sync_exchange_rate_in_place(global_state, accounts.clock.slot)?;
+  // Price against the debt as of now, stability fee included.
+  accrue_stability_fee_to_treasury(
+    global_state,
+    accounts.amusd_mint,
+    accounts.treasury_amusd_account,
+    accounts.token_program,
+    accounts.clock,
+    &accounts.event_cpi,
+  )?;
   }
 
   // read only borrow
-  let global_state = &ctx.accounts.global_state;
-
-  assert_oracle_freshness_and_confidence(
-    ctx.accounts.clock.slot, 
-    global_state.last_oracle_update_slot, 
-    global_state.max_oracle_staleness_slots, 
-    global_state.mock_sol_price_usd, 
-    global_state.mock_oracle_confidence_usd, 
-    global_state.max_conf_bps
+  let global_state = &accounts.global_state;
+
+  let oracle_price = resolve_price(
+    &PriceAccounts {
+      price_update: accounts.price_update,
+      switchboard_feed: accounts.switchboard_feed,
+    },
+    global_state,
+    accounts.clock.slot,
+    accounts.clock.unix_timestamp,
+    PriceBias::Low,
   )?;
   
+  // The vault this deposit lands in, valued against the whole book
+  let leg = CollateralLeg::resolve(global_state, accounts.collateral_vault.as_deref(), global_state.mock_lst_to_sol_rate)?;
+
   // Capture current state values for calculations
-  let sol_price_usd = global_state.mock_sol_price_usd;
-  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
-  let current_lst_amount = global_state.total_lst_amount;
-  let current_amusd_supply = global_state.amusd_supply;
+  let sol_price_usd = oracle_price.sol_price_usd;
   let min_cr_bps = global_state.min_cr_bps;
-  let target_cr_bps = global_state.target_cr_bps;
-  let fee_amusd_mint_bps = global_state.fee_amusd_mint_bps;
-  let fee_min_multiplier_bps = global_state.fee_min_multiplier_bps;
-  let fee_max_multiplier_bps = global_state.fee_max_multiplier_bps;
-  let uncertainty_index_bps = global_state.uncertainty_index_bps;
-  let uncertainty_max_bps = global_state.uncertainty_max_bps;
-
-
-  let current_rounding_reserve = global_state.rounding_reserve_lamports;
+  // Oracle uncertainty plus any flash liquidity drawn this slot
+  let uncertainty_index_bps = global_state.effective_uncertainty_index(global_state.uncertainty_index_bps, accounts.clock.slot)?;
 
   // Configured hard cap for reserve growth.
   let max_rounding_reserve = global_state.max_rounding_reserve_lamports;
   
   // Input validations
   require!(!global_state.mint_paused, LaminarError::MintPaused);
+  assert_accepts_deposits(accounts.collateral_vault.as_deref())?;
+  // New amUSD would be minted at par against holders written down below it.
+  require!(global_state.redemption_rate_bps == BPS_PRECISION, LaminarError::AmusdWrittenDown);
   require!(lst_amount > 0, LaminarError::ZeroAmount);
   require!(lst_amount >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
+  require!(
+    compute_cap_headroom(lst_amount, global_state.max_single_deposit_lst).is_some(),
+    LaminarError::DepositTooLarge
+  );
 
   require!(
-    ctx.accounts.user_lst_account.amount >= lst_amount,
+    accounts.user_lst_account.amount >= lst_amount,
     LaminarError::InsufficientCollateral
   );
 
-  // MATH LOGICS
-  let old_tvl = compute_tvl_sol(current_lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
-  
-  let old_liability = if current_amusd_supply > 0 {
-    compute_liability_sol(current_amusd_supply, sol_price_usd)
-      .ok_or(LaminarError::MathOverflow)?
-  } else {
-    0
-  };
-    
-  let old_cr_bps = compute_cr_bps(old_tvl, old_liability);
-
-  // Convert full LST deposit to SOL value
-  let sol_value = compute_tvl_sol(lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  // used to measure deterministic dust
-  let sol_value_up = mul_div_up(lst_amount, lst_to_sol_rate, SOL_PRECISION)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  msg!("LST deposited: {}", lst_amount);
-  msg!("SOL value: {}", sol_value);
-
-  // Calculate gross amUSD based on full deposit
-  let amusd_gross = mul_div_down(sol_value, sol_price_usd, SOL_PRECISION)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  // for reserve counting
-  let amusd_gross_up = mul_div_up(sol_value_up, sol_price_usd, SOL_PRECISION)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let mint_rounding_delta_usd = compute_rounding_delta_units(amusd_gross, amusd_gross_up)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let reserve_credit_from_mint = usd_dust_to_lamports_up(mint_rounding_delta_usd, sol_price_usd)
-    .ok_or(LaminarError::MathOverflow)?;
+  // Take the deposit before pricing it: a Token-2022 transfer fee can leave
+  // the vault with less than `lst_amount`, and only what lands backs the mint.
+  let lst_received = deposit_lst(
+    accounts.token_program,
+    accounts.lst_mint,
+    accounts.user_lst_account,
+    accounts.vault,
+    accounts.user.clone(),
+    lst_amount,
+  )?;
+  require!(lst_received >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
 
+  // MATH LOGICS
+  let book = leg.quote_state(global_state, sol_price_usd, uncertainty_index_bps);
+  let old_tvl = book.tvl().ok_or(LaminarError::MathOverflow)?;
+  let old_cr_bps = book.cr_bps().ok_or(LaminarError::MathOverflow)?;
+  // New debt is refused outright while CR is under min, not just when this
+  // mint would be the one to push it there.
+  assert_not_in_recovery_mode(old_cr_bps, min_cr_bps)?;
 
   // Fee is taken in amUSD terms (per whitepaper: amUSD_net = amUSD_minted − fee)
-  let fee_bps = compute_dynamic_fee_bps(fee_amusd_mint_bps, FeeAction::AmusdMint, old_cr_bps, min_cr_bps, target_cr_bps, fee_min_multiplier_bps, fee_max_multiplier_bps, uncertainty_index_bps, uncertainty_max_bps).ok_or(LaminarError::InvalidParameter)?;
-  
-  let (amusd_to_user, amusd_fee) = apply_fee(amusd_gross, fee_bps)
-    .ok_or(LaminarError::MathOverflow)?;
+  let quote = quote_mint_amusd(&book, lst_received).ok_or(LaminarError::MathOverflow)?;
+  assert_fee_within_max(quote.fee_bps, max_fee_bps)?;
+  let Quote { gross: amusd_gross, to_user: amusd_to_user, fee: amusd_treasury_fee, fee_bps, cr_multiplier_bps, uncertainty_multiplier_bps, insurance_fee: amusd_insurance_fee, .. } = quote;
+
+  msg!("LST deposited: {} (received {})", lst_amount, lst_received);
+
+  // Part of the treasury fee goes to Savings Pool depositors instead.
+  let amusd_savings_fee = route_savings_fee(
+    global_state,
+    accounts.savings_pool.as_deref(),
+    accounts.savings_amusd_vault.as_deref(),
+    amusd_treasury_fee,
+  )?;
 
   msg!("amUSD gross: {}", amusd_gross);
-  msg!("amUSD fee (to treasury): {}", amusd_fee);
+  msg!("amUSD fee (to treasury): {}", amusd_treasury_fee - amusd_savings_fee);
+  msg!("amUSD fee (to savings): {}", amusd_savings_fee);
+  msg!("amUSD fee (to insurance): {}", amusd_insurance_fee);
   msg!("amUSD to user: {}", amusd_to_user);
 
   // Slippage protection (on user's portion)
   require!(amusd_to_user >= min_amusd_out, LaminarError::SlippageExceeded);
   require!(amusd_to_user >= MIN_AMUSD_MINT, LaminarError::AmountTooSmall);
 
-  // Calculate new state values
-  // Full LST goes to vault
-  let new_lst_amount = current_lst_amount
-    .checked_add(lst_amount)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let new_tvl = compute_tvl_sol(new_lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
+  // Everything received stays in the vault
+  let new_lst_amount = quote.new_total_lst_amount;
+  require!(
+    compute_cap_headroom(new_lst_amount, global_state.max_total_lst_amount).is_some(),
+    LaminarError::TvlCapExceeded
+  );
 
-  // Total amUSD supply increases by gross amount (user + fee)
+  // Total amUSD supply increases by user + treasury fee
   // This is the total liability
-  let new_amusd_supply = current_amusd_supply
-    .checked_add(amusd_gross)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let new_liability = compute_liability_sol(new_amusd_supply, sol_price_usd)
-    .ok_or(LaminarError::MathOverflow)?;
+  let new_amusd_supply = quote.new_supply;
+
+  // Debt ceiling on the LST book; checked before any amUSD is minted
+  let amusd_supply_headroom = compute_cap_headroom(new_amusd_supply, global_state.amusd_supply_cap)
+    .ok_or(LaminarError::DebtCeilingExceeded)?;
+  record_position_mint(
+    global_state,
+    accounts.user_position.as_deref_mut(),
+    accounts.user.key(),
+    accounts.user_position_bump,
+    accounts.clock.slot,
+    PositionMint { lst_deposited: lst_received, amusd_minted: amusd_to_user, ..Default::default() },
+  )?;
 
-  let new_rounding_reserve = credit_rounding_reserve(current_rounding_reserve, reserve_credit_from_mint, max_rounding_reserve)?;
+  let new_rounding_reserve = quote.new_rounding_reserve;
+  let new_insurance_fund = quote.new_insurance_fund;
+  let after = book.after(FeeAction::AmusdMint, &quote);
+  let new_tvl = after.tvl().ok_or(LaminarError::MathOverflow)?;
+  let new_liability = after.liability().ok_or(LaminarError::MathOverflow)?;
+  let new_non_claimable = after.non_claimable().ok_or(LaminarError::MathOverflow)?;
 
   // Signed accounting equity (can be negative during insolvency)
-  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
-  let new_cr = compute_cr_bps(new_tvl, new_liability);
+  let new_cr = after.cr_bps().ok_or(LaminarError::MathOverflow)?;
 
   msg!("Post-min CR: {}bps ({}%)", new_cr, new_cr/100);
 
-  // Deterministic rounding bound for minr_amusd parh:
+  // Deterministic rounding bound for mint_amusd path:
   // (LST-> SOL, SOL-> USD) => (k_lamports = 2, k_usd = 1)
   let rounding_bound_lamports= derive_rounding_bound_lamports(2, 1, sol_price_usd)?;
 
   assert_rounding_reserve_within_cap(new_rounding_reserve, max_rounding_reserve)?;
   assert_cr_above_minimum(new_cr, min_cr_bps)?;
-  assert_balance_sheet_holds(new_tvl, new_liability, new_accounting_equity, new_rounding_reserve, rounding_bound_lamports)?;
-
+  assert_balance_sheet_holds(new_tvl, new_liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
 
   // State update
   {
-    let global_state = &mut ctx.accounts.global_state;
-    global_state.total_lst_amount = new_lst_amount;
+    let global_state = &mut accounts.global_state;
+    CollateralLeg::commit(global_state, accounts.collateral_vault.as_deref_mut(), new_lst_amount)?;
     global_state.amusd_supply = new_amusd_supply;
-    global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+    global_state.increment_operation_counter()?;
     global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    if let Some(savings_pool) = accounts.savings_pool.as_deref_mut() {
+      savings_pool.credit_fee(amusd_savings_fee)?;
+    }
+    let recovery = global_state.track_recovery_mode(new_cr, accounts.clock.slot);
+    emit_recovery_transition(&accounts.event_cpi, recovery, new_cr, min_cr_bps, global_state.operation_counter, accounts.clock)?;
     msg!("State updated: LST={}, amUSD={}", new_lst_amount, new_amusd_supply);
   }
 
   // CPI calls
 
-  // Transfer full LST from user to vault
-  let transfer_accounts = TransferChecked {
-    from: ctx.accounts.user_lst_account.to_account_info(),
-    mint: ctx.accounts.lst_mint.to_account_info(),
-    to: ctx.accounts.vault.to_account_info(),
-    authority: ctx.accounts.user.to_account_info(),
-  };
-
-  let cpi_ctx = CpiContext::new(
-    ctx.accounts.token_program.to_account_info(),
-    transfer_accounts,
-  );
-
-  token_interface::transfer_checked(cpi_ctx, lst_amount, ctx.accounts.lst_mint.decimals)?;
-  msg!("Transferred {} LST to vault", lst_amount);
-
   // Mint amUSD to user
-  let seeds = &[GLOBAL_STATE_SEED, &[ctx.bumps.global_state]];
+  let seeds = &[GLOBAL_STATE_SEED, &[accounts.global_state.bump]];
   let signer = &[&seeds[..]];
 
+  let recipient_amusd_account = accounts.recipient_amusd_account.to_account_info();
   let mint_to_user = MintTo {
-    mint: ctx.accounts.amusd_mint.to_account_info(),
-    to: ctx.accounts.user_amusd_account.to_account_info(),
-    authority: ctx.accounts.global_state.to_account_info(),
+    mint: accounts.amusd_mint.to_account_info(),
+    to: recipient_amusd_account.clone(),
+    authority: accounts.global_state.to_account_info(),
   };
 
   let cpi_ctx_user = CpiContext::new_with_signer(
-    ctx.accounts.token_program.to_account_info(),
+    accounts.token_program.to_account_info(),
     mint_to_user,
     signer,
   );
 
   token_interface::mint_to(cpi_ctx_user, amusd_to_user)?;
-  msg!("Minted {} amUSD to user", amusd_to_user);
+  msg!("Minted {} amUSD to {}", amusd_to_user, recipient_amusd_account.key);
 
   // Mint amUSD fee to treasury (per whitepaper Section 16.5)
-  if amusd_fee > 0 {
+  let amusd_fee_to_treasury = amusd_treasury_fee - amusd_savings_fee;
+  if amusd_fee_to_treasury > 0 {
     let mint_to_treasury = MintTo {
-      mint: ctx.accounts.amusd_mint.to_account_info(),
-      to: ctx.accounts.treasury_amusd_account.to_account_info(),
-      authority: ctx.accounts.global_state.to_account_info(),
+      mint: accounts.amusd_mint.to_account_info(),
+      to: accounts.treasury_amusd_account.to_account_info(),
+      authority: accounts.global_state.to_account_info(),
     };
 
     let cpi_ctx_treasury = CpiContext::new_with_signer(
-      ctx.accounts.token_program.to_account_info(),
+      accounts.token_program.to_account_info(),
       mint_to_treasury,
       signer,
     );
 
-    token_interface::mint_to(cpi_ctx_treasury, amusd_fee)?;
-    msg!("Minted {} amUSD fee to treasury", amusd_fee);
+    token_interface::mint_to(cpi_ctx_treasury, amusd_fee_to_treasury)?;
+    msg!("Minted {} amUSD fee to treasury", amusd_fee_to_treasury);
   }
 
-  ctx.accounts.vault.reload()?;
-  ctx.accounts.amusd_mint.reload()?;
+  if let Some(savings_amusd_vault) = accounts.savings_amusd_vault.as_ref().filter(|_| amusd_savings_fee > 0) {
+    let mint_to_savings = MintTo {
+      mint: accounts.amusd_mint.to_account_info(),
+      to: savings_amusd_vault.to_account_info(),
+      authority: accounts.global_state.to_account_info(),
+    };
+
+    let cpi_ctx_savings = CpiContext::new_with_signer(
+      accounts.token_program.to_account_info(),
+      mint_to_savings,
+      signer,
+    );
+
+    token_interface::mint_to(cpi_ctx_savings, amusd_savings_fee)?;
+    msg!("Minted {} amUSD fee to the Savings Pool", amusd_savings_fee);
+  }
+
+  accounts.vault.reload()?;
+  accounts.amusd_mint.reload()?;
 
-  let expected_vault_balance = ctx.accounts.global_state.total_lst_amount;
   require!(
-    ctx.accounts.vault.amount == expected_vault_balance,
+    accounts.vault.amount == expected_vault_balance(accounts.global_state, accounts.collateral_vault.as_deref()),
     LaminarError::BalanceSheetViolation
   );
 
   require!(
-    ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_supply,
+    accounts.amusd_mint.supply == accounts.global_state.amusd_mint_supply()?,
     LaminarError::BalanceSheetViolation
   );
 
+  assert_savings_vault_synced(accounts.savings_pool.as_deref(), accounts.savings_amusd_vault.as_deref_mut())?;
+
   msg!("Mint complete!");
   msg!("New TVL: {} lamports", new_tvl);
-  msg!("New amUSD supply: {} (user {} + treasury {})", new_amusd_supply, amusd_to_user, amusd_fee);
+  msg!("New amUSD supply: {} (user {} + treasury {})", new_amusd_supply, amusd_to_user, amusd_treasury_fee);
 
-  emit!(AmUSDMinted {
-    user: ctx.accounts.user.key(),
-    lst_deposited: lst_amount,
+  accounts.event_cpi.emit(AmUSDMinted {
+    user: accounts.user.key(),
+    recipient: recipient_amusd_account.key(),
+    lst_deposited: lst_received,
     amusd_minted: amusd_to_user,
-    fee: amusd_fee,
+    fee: amusd_treasury_fee,
+    fee_bps,
+    cr_multiplier_bps,
+    uncertainty_multiplier_bps,
+    insurance_fee: amusd_insurance_fee,
+    savings_fee: amusd_savings_fee,
+    amusd_supply_headroom,
     old_tvl,
     new_tvl,
     old_cr_bps,
     new_cr_bps: new_cr,
     sol_price_used: sol_price_usd,
-    timestamp: ctx.accounts.clock.unix_timestamp,
-  });
+    deadline_slot,
+    client_order_id,
+    entrypoint_version,
+    operation_counter: accounts.global_state.operation_counter,
+    timestamp: accounts.clock.unix_timestamp,
+  })?;
 
 
   Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MintAmUSD<'info> {
   #[account(mut)]
@@ -327,16 +527,13 @@ pub struct MintAmUSD<'info> {
   )]
   pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-  /// CHECK: PDA validated by seeds
-  #[account(
-    seeds = [VAULT_AUTHORITY_SEED],
-    bump,
-  )]
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
   pub vault_authority: UncheckedAccount<'info>,
 
-  /// LST mint
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
   #[account(
-    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
   )]
   pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
 
@@ -345,4 +542,109 @@ pub struct MintAmUSD<'info> {
   pub system_program: Program<'info, System>,
 
   pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Savings Pool; required only while `global_state.savings_fee_split_bps` is set
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Option<Box<Account<'info, SavingsPool>>>,
+
+  /// Savings Pool amUSD vault (receives its fee share); matched to the pool in `route_savings_fee`
+  #[account(mut)]
+  pub savings_amusd_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// Caller's running mint totals; required while `per_user_amusd_cap` or `min_action_gap_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+
+  /// Receives the minted amUSD instead of `user_amusd_account`; any token
+  /// account of `amusd_mint`, whoever owns it
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+  )]
+  pub recipient_amusd_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// SPL stake pool of the primary LST; required only by the SOL zap into the primary LST
+  /// CHECK: address matched to the configured pool; owner and layout checked in `lst_rate::StakePoolDeposit`
+  #[account(
+    mut,
+    constraint = stake_pool.key() == global_state.lst_stake_pool @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: the pool's withdraw authority PDA, checked by the stake pool program
+  pub stake_pool_withdraw_authority: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `reserve_stake` in `lst_rate::StakePoolDeposit`
+  #[account(mut)]
+  pub reserve_stake: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `manager_fee_account` in `lst_rate::StakePoolDeposit`
+  #[account(mut)]
+  pub manager_fee_account: Option<UncheckedAccount<'info>>,
+
+  /// The primary LST mint again, writable for the pool to mint into
+  /// CHECK: matched to `lst_mint`; the stake pool checks it against its header
+  #[account(
+    mut,
+    constraint = stake_pool_mint.key() == lst_mint.key() @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool_mint: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: SPL stake pool program, matched by address
+  #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+  pub stake_pool_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> MintAmUSD<'info> {
+  /// The stake pool's `DepositSol` accounts; all are required.
+  fn stake_pool_deposit(&self) -> Result<StakePoolDeposit<'info>> {
+    let required = |account: &Option<UncheckedAccount<'info>>| {
+      account
+        .as_ref()
+        .map(|account| account.to_account_info())
+        .ok_or(error!(LaminarError::InvalidStakePool))
+    };
+    Ok(StakePoolDeposit {
+      stake_pool: required(&self.stake_pool)?,
+      withdraw_authority: required(&self.stake_pool_withdraw_authority)?,
+      reserve_stake: required(&self.reserve_stake)?,
+      manager_fee_account: required(&self.manager_fee_account)?,
+      pool_mint: required(&self.stake_pool_mint)?,
+      system_program: self.system_program.to_account_info(),
+      token_program: self.token_program.to_account_info(),
+      stake_pool_program: required(&self.stake_pool_program)?,
+    })
+  }
 }
@@ -3,60 +3,219 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
   associated_token::AssociatedToken,
-  token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked, MintTo}
+  token_interface::{self, Mint, TokenAccount, TokenInterface, MintTo}
 };
-use crate::{ events::AsolMinted, instructions::sync_exchange_rate_in_place, state::*};
+use crate::{ events::{emit_recovery_transition, AsolMinted, EventCpi}, instructions::sync_exchange_rate_in_place, state::*};
 use crate::math::*;
 use crate::invariants::*;
+use crate::constants::{CURRENT_ARGS_VERSION, ENTRYPOINT_V1, ENTRYPOINT_V2, ENTRYPOINT_ZAP};
 use crate::error::LaminarError;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::position::{record_position_mint, PositionMint};
+use crate::collateral::{assert_accepts_deposits, deposit_lst, expected_vault_balance, needs_lst_snapshot, stake_sol_deposit, sync_native_deposit, validate_leg_accounts, wrap_sol_deposit, CollateralLeg};
+use crate::lst_rate::{StakePoolDeposit, SPL_STAKE_POOL_PROGRAM_ID};
+use crate::quote::{quote_mint_asol, Quote};
+
+
+/// Versioned arguments for `mint_asol_v2`.
+///
+/// New optional fields are appended with defaults that reproduce v1
+/// behavior, so `mint_asol` and `mint_asol_v2` always share one code path.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAsolArgs {
+  /// Layout version of this struct (see `CURRENT_ARGS_VERSION`)
+  pub args_version: u8,
+  /// Deposit amount in LST base units
+  pub lst_amount: u64,
+  /// Minimum aSOL the user accepts after fees
+  pub min_asol_out: u64,
+  /// Last slot the order may execute in; 0 for no deadline (args version 3)
+  pub deadline_slot: u64,
+  /// Highest dynamic fee the user accepts, in bps; 0 for no bound (args version 4)
+  pub max_fee_bps: u64,
+  /// Caller's order reference, echoed in the event and otherwise unused; 0 for none (args version 5)
+  pub client_order_id: u64,
+}
 
+impl MintAsolArgs {
+  /// Args equivalent to a v1 `mint_asol` call.
+  pub fn v1(lst_amount: u64, min_asol_out: u64) -> Self {
+    Self {
+      args_version: CURRENT_ARGS_VERSION,
+      lst_amount,
+      min_asol_out,
+      deadline_slot: 0,
+      max_fee_bps: 0,
+      client_order_id: 0,
+    }
+  }
+}
 
+/// v1 entrypoint - thin wrapper over the v2 args path.
 pub fn handler(
   ctx: Context<MintAsol>,
   lst_amount: u64,
   min_asol_out: u64,
 ) -> Result<()> {
+  process(ctx, MintAsolArgs::v1(lst_amount, min_asol_out), ENTRYPOINT_V1)
+}
+
+/// v2 entrypoint - single versioned args struct.
+pub fn handler_v2(ctx: Context<MintAsol>, args: MintAsolArgs) -> Result<()> {
+  process(ctx, args, ENTRYPOINT_V2)
+}
+
+/// SOL zap entrypoint - turns `lamports` of native SOL into collateral, then
+/// mints against it: the primary LST is staked for through its stake pool,
+/// the wSOL leg's SOL is wrapped into the user's wSOL account. Both are
+/// outgoing CPIs, so the caller is still checked against CPI invocation.
+pub fn handler_zap(ctx: Context<MintAsol>, lamports: u64, min_asol_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  if ctx.accounts.collateral_vault.is_none() {
+    let stake_pool = ctx.accounts.stake_pool_deposit()?;
+    let user = ctx.accounts.user.to_account_info();
+    let lst_amount = stake_sol_deposit(&stake_pool, user, &mut ctx.accounts.user_lst_account, lamports)?;
+    return process(ctx, MintAsolArgs::v1(lst_amount, min_asol_out), ENTRYPOINT_ZAP);
+  }
+
+  wrap_sol_deposit(
+    ctx.accounts.collateral_vault.as_deref(),
+    &ctx.accounts.user,
+    &ctx.accounts.user_lst_account,
+    &ctx.accounts.system_program,
+    lamports,
+  )?;
+  process(ctx, MintAsolArgs::v1(lamports, min_asol_out), ENTRYPOINT_ZAP)
+}
+
+fn process(
+  ctx: Context<MintAsol>,
+  args: MintAsolArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  validate_args_version(args.args_version)?;
+
+  let user_position_bump = ctx.bumps.user_position;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+  execute_mint_asol(
+    MintAsolAccounts {
+      user: accounts.user.to_account_info(),
+      global_state: &mut accounts.global_state,
+      asol_mint: &mut accounts.asol_mint,
+      treasury_asol_account: &accounts.treasury_asol_account,
+      user_lst_account: &mut accounts.user_lst_account,
+      recipient_asol_account: accounts.recipient_asol_account.as_deref().unwrap_or(&accounts.user_asol_account),
+      vault: &mut accounts.vault,
+      vault_authority: &accounts.vault_authority,
+      lst_mint: &accounts.lst_mint,
+      token_program: &accounts.token_program,
+      clock: &accounts.clock,
+      price_update: accounts.price_update.as_deref(),
+      switchboard_feed: accounts.switchboard_feed.as_deref(),
+      collateral_vault: accounts.collateral_vault.as_deref_mut(),
+      user_position: accounts.user_position.as_deref_mut(),
+      user_position_bump,
+      event_cpi,
+    },
+    args,
+    entrypoint_version,
+  )
+}
+
+/// Everything an aSOL mint reads and writes; filled from `MintAsol` or
+/// from a paired mint's accounts
+pub struct MintAsolAccounts<'a, 'info> {
+  /// Signing wallet; funds the deposit and owns the position
+  pub user: AccountInfo<'info>,
+  pub global_state: &'a mut Account<'info, GlobalState>,
+  pub asol_mint: &'a mut InterfaceAccount<'info, Mint>,
+  pub treasury_asol_account: &'a InterfaceAccount<'info, TokenAccount>,
+  pub user_lst_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+  /// Receives the minted aSOL
+  pub recipient_asol_account: &'a InterfaceAccount<'info, TokenAccount>,
+  pub vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+  pub vault_authority: &'a AccountInfo<'info>,
+  pub lst_mint: &'a InterfaceAccount<'info, Mint>,
+  pub token_program: &'a Interface<'info, TokenInterface>,
+  pub clock: &'a Clock,
+  pub price_update: Option<&'a AccountInfo<'info>>,
+  pub switchboard_feed: Option<&'a AccountInfo<'info>>,
+  pub collateral_vault: Option<&'a mut Account<'info, CollateralVault>>,
+  pub user_position: Option<&'a mut Account<'info, UserPosition>>,
+  pub user_position_bump: Option<u8>,
+  pub event_cpi: EventCpi<'info>,
+}
+
+/// Deposit `args.lst_amount` and mint aSOL against it at current prices.
+/// Args-version checks are the caller's job.
+pub fn execute_mint_asol<'info>(
+  mut accounts: MintAsolAccounts<'_, 'info>,
+  args: MintAsolArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  let MintAsolArgs { lst_amount, min_asol_out, deadline_slot, max_fee_bps, client_order_id, .. } = args;
+  assert_before_deadline(deadline_slot, accounts.clock.slot)?;
+
   // All validations before any state changes
 
   assert_not_cpi_context()?;
 
+  // A frozen vault fails every transfer CPI; reject before touching state.
+  require!(!accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+  sync_native_deposit(
+    accounts.collateral_vault.as_deref(),
+    accounts.user_lst_account,
+    accounts.token_program,
+  )?;
+
   // sync first
   {
-  let global_state = &mut ctx.accounts.global_state;
+  let global_state = &mut accounts.global_state;
   global_state.validate_version()?;
-  assert_lst_snapshot_fresh(
-    ctx.accounts.clock.slot,
-    global_state.last_tvl_update_slot,
-    global_state.max_oracle_staleness_slots,
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  validate_leg_accounts(
+    global_state,
+    &global_state.key(),
+    accounts.collateral_vault.as_deref(),
+    &accounts.vault_authority.key(),
+    &accounts.vault.key(),
   )?;
-  sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  if needs_lst_snapshot(global_state, accounts.collateral_vault.as_deref()) {
+    assert_lst_snapshot_fresh(
+      accounts.clock.slot,
+      global_state.last_tvl_update_slot,
+      global_state.max_oracle_staleness_slots,
+    )?;
+  }
+  sync_exchange_rate_in_place(global_state, accounts.clock.slot)?;
+  global_state.roll_mint_round(accounts.clock.slot);
   }
 
   // read only borrow
-  let global_state = &ctx.accounts.global_state;
-
-  assert_oracle_freshness_and_confidence(
-    ctx.accounts.clock.slot, 
-    global_state.last_oracle_update_slot, 
-    global_state.max_oracle_staleness_slots, 
-    global_state.mock_sol_price_usd, 
-    global_state.mock_oracle_confidence_usd, 
-    global_state.max_conf_bps
+  let global_state = &accounts.global_state;
+
+  let oracle_price = resolve_price(
+    &PriceAccounts {
+      price_update: accounts.price_update,
+      switchboard_feed: accounts.switchboard_feed,
+    },
+    global_state,
+    accounts.clock.slot,
+    accounts.clock.unix_timestamp,
+    PriceBias::High,
   )?;
   
+  // The vault this deposit lands in, valued against the whole book
+  let leg = CollateralLeg::resolve(global_state, accounts.collateral_vault.as_deref(), global_state.mock_lst_to_sol_rate)?;
+
   // Capture values
-  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
-  let sol_price_used = global_state.mock_sol_price_usd;
-  let current_lst_amount = global_state.total_lst_amount;
-  let current_amusd_supply = global_state.amusd_supply;
+  let sol_price_used = oracle_price.sol_price_usd;
   let current_asol_supply = global_state.asol_supply;
-  let target_cr_bps = global_state.target_cr_bps;
   let min_cr_bps = global_state.min_cr_bps;
-  let fee_asol_mint_bps = global_state.fee_asol_mint_bps;
-  let fee_min_multiplier_bps = global_state.fee_min_multiplier_bps;
-  let fee_max_multiplier_bps = global_state.fee_max_multiplier_bps;
-  let uncertainty_index_bps = global_state.uncertainty_index_bps;
-  let uncertainty_max_bps = global_state.uncertainty_max_bps;
+  // Oracle uncertainty plus any flash liquidity drawn this slot
+  let uncertainty_index_bps = global_state.effective_uncertainty_index(global_state.uncertainty_index_bps, accounts.clock.slot)?;
 
   let current_rounding_reserve = global_state.rounding_reserve_lamports;
 
@@ -65,60 +224,64 @@ pub fn handler(
 
   // Input validations
   require!(!global_state.mint_paused, LaminarError::MintPaused);
+  assert_accepts_deposits(accounts.collateral_vault.as_deref())?;
   require!(lst_amount > 0, LaminarError::ZeroAmount);
   require!(lst_amount >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
+  require!(
+    compute_cap_headroom(lst_amount, global_state.max_single_deposit_lst).is_some(),
+    LaminarError::DepositTooLarge
+  );
 
   require!(
-    ctx.accounts.user_lst_account.amount >= lst_amount,
+    accounts.user_lst_account.amount >= lst_amount,
     LaminarError::InsufficientCollateral
   );
 
-  // All math logic
-
-  let old_tvl = compute_tvl_sol(current_lst_amount, lst_to_sol_rate).ok_or(LaminarError::MathOverflow)?;
+  // Take the deposit before pricing it: a Token-2022 transfer fee can leave
+  // the vault with less than `lst_amount`, and only what lands backs the mint.
+  let lst_received = deposit_lst(
+    accounts.token_program,
+    accounts.lst_mint,
+    accounts.user_lst_account,
+    accounts.vault,
+    accounts.user.clone(),
+    lst_amount,
+  )?;
+  require!(lst_received >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
 
-  let current_liability = if current_amusd_supply > 0 {
-    compute_liability_sol(current_amusd_supply, sol_price_used)
-      .ok_or(LaminarError::MathOverflow)?
-  } else {
-    0
-  };
+  // All math logic
 
-  let old_claimable_equity = compute_claimable_equity_sol(old_tvl, current_liability, current_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
-  let old_cr_bps = compute_cr_bps(old_tvl, current_liability);
+  let book = leg.quote_state(global_state, sol_price_used, uncertainty_index_bps);
+  let old_tvl = book.tvl().ok_or(LaminarError::MathOverflow)?;
+  let current_liability = book.liability().ok_or(LaminarError::MathOverflow)?;
+  let current_non_claimable = book.non_claimable().ok_or(LaminarError::MathOverflow)?;
+  let old_claimable_equity = compute_claimable_equity_sol(old_tvl, current_liability, current_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
   // Determinstic rounding bound for mint_asol path:
   // (LST-> SOL, SOL-> aSOL) => (k_lamports=2, k_usd=0)
   let rounding_bound_lamports = derive_rounding_bound_lamports(2, 0, sol_price_used)?;
 
-  // May be increased bt orphan-equity dust sweep in bootstrap mode.
-  let mut effective_rounding_reserve = current_rounding_reserve;
-
-  if current_asol_supply == 0 {
+  let current_nav = if current_asol_supply == 0 {
     // Bootstrap must be solvent
     require!(old_tvl >= current_liability, LaminarError::InsolventProtocol);
 
-    // Bootstrap requires TVL -= L + R (within deterministic rounding bound).
+    // Bootstrap requires TVL -= L + R + I (within deterministic rounding bound).
     let lhs = old_tvl as i128;
     let rhs = (current_liability as i128)
-      .checked_add(effective_rounding_reserve as i128)
+      .checked_add(current_non_claimable as i128)
       .ok_or(LaminarError::MathOverflow)?;
 
-    let bootstrap_diff: u128 = if lhs > rhs {
-      (lhs - rhs) as u128
-    } else {
-      (rhs - lhs) as u128
-    };
+    let bootstrap_diff: u128 = lhs.abs_diff(rhs);
 
     require!(
       bootstrap_diff <= rounding_bound_lamports as u128,
       LaminarError::EquityWithoutAsolSupply
     );
 
-    // Orphan-equity dust sweep:
-    // if claimable equity is dust-only, reclassify it into rounding reserve.
+    // Orphan-equity dust sweep: `quote_mint_asol` reclassifies dust-only
+    // claimable equity into the rounding reserve.
     if old_claimable_equity > 0 {
-      effective_rounding_reserve = effective_rounding_reserve
+      let effective_rounding_reserve = current_rounding_reserve
         .checked_add(old_claimable_equity)
         .ok_or(LaminarError::MathOverflow)?;
 
@@ -133,87 +296,77 @@ pub fn handler(
       );
     }
 
-  }
-
-  let sol_value = compute_tvl_sol(lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let sol_value_up = mul_div_up(lst_amount, lst_to_sol_rate, SOL_PRECISION)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  msg!("LST deposited: {}", lst_amount);
-  msg!("SOL value: {}", sol_value);
-
-  let current_nav = if current_asol_supply == 0 {
     // First mint bootstrap price
     SOL_PRECISION  // 1 aSOL = 1 SOL
   } else {
-    nav_asol_with_reserve(old_tvl, current_liability, effective_rounding_reserve, current_asol_supply)
-      .ok_or(LaminarError::MathOverflow)?
-  };
-
-  // Calculate aSOL to mint
-  let asol_gross = if current_asol_supply == 0 {
-    sol_value
-  } else {
-    if current_nav == 0 {
-      return Err(LaminarError::InsolventProtocol.into());
-    }
-    mul_div_down(sol_value, SOL_PRECISION, current_nav)
-      .ok_or(LaminarError::MathOverflow)?
-  };
-
-  let asol_reference_up = if current_asol_supply == 0 {
-    sol_value_up
-  } else {
-    mul_div_up(sol_value_up, SOL_PRECISION, current_nav)
-      .ok_or(LaminarError::MathOverflow)?
+    let nav = book.nav().ok_or(LaminarError::MathOverflow)?;
+    require!(nav > 0, LaminarError::InsolventProtocol);
+    nav
   };
 
-  let mint_rounding_delta_asol = compute_rounding_delta_units(asol_gross, asol_reference_up)
+  msg!("LST deposited: {} (received {})", lst_amount, lst_received);
+
+  // Recovery mode: fresh equity mints below NAV. The bonus is new supply
+  // on top of the gross, capped so existing holders lose at most
+  // MAX_RECOVERY_DILUTION_BPS of NAV per mint.
+  let quote = quote_mint_asol(&book, lst_received).ok_or(LaminarError::MathOverflow)?;
+  assert_fee_within_max(quote.fee_bps, max_fee_bps)?;
+  let Quote { gross: asol_gross, to_user: asol_net, fee: treasury_fee, fee_bps, cr_multiplier_bps, uncertainty_multiplier_bps, insurance_fee, .. } = quote;
+  let recovery_bonus = asol_gross
+    .checked_sub(treasury_fee)
+    .and_then(|net| net.checked_sub(insurance_fee))
+    .and_then(|net| asol_net.checked_sub(net))
     .ok_or(LaminarError::MathOverflow)?;
 
-  let reserve_credit_from_mint = if current_asol_supply == 0 {
-    mint_rounding_delta_asol
-  } else {
-    asol_dust_to_lamports_up(mint_rounding_delta_asol, current_nav)
-      .ok_or(LaminarError::MathOverflow)?
-  };
   msg!("aSOL gross (before fee): {}", asol_gross);
-
-  // Apply fee
-  let fee_bps = compute_dynamic_fee_bps(fee_asol_mint_bps, FeeAction::AsolMint, old_cr_bps, min_cr_bps, target_cr_bps, fee_min_multiplier_bps, fee_max_multiplier_bps, uncertainty_index_bps, uncertainty_max_bps).ok_or(LaminarError::InvalidParameter)?;
-
-  let (asol_net, fee) = apply_fee(asol_gross, fee_bps)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  msg!("Fee: {} aSOL", fee);
+  msg!("Fee: {} aSOL to treasury, {} aSOL to insurance", treasury_fee, insurance_fee);
+  if recovery_bonus > 0 {
+    msg!("Recovery bonus: {} aSOL", recovery_bonus);
+  }
   msg!("aSOL net (to user): {}", asol_net);
 
   require!(asol_net >= min_asol_out, LaminarError::SlippageExceeded);
   require!(asol_net >= MIN_ASOL_MINT, LaminarError::AmountTooSmall);
 
   // Calculate new state values
-  let new_lst_amount = current_lst_amount
-    .checked_add(lst_amount)
-    .ok_or(LaminarError::MathOverflow)?;
+  let new_lst_amount = quote.new_total_lst_amount;
+  require!(
+    compute_cap_headroom(new_lst_amount, global_state.max_total_lst_amount).is_some(),
+    LaminarError::TvlCapExceeded
+  );
 
-  let new_tvl = compute_tvl_sol(new_lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
+  let new_asol_supply = quote.new_supply;
 
-  let new_asol_supply = current_asol_supply
+  // Equity ceiling; the treasury fee counts toward it like the user's share
+  let asol_supply_headroom = compute_cap_headroom(new_asol_supply, global_state.asol_supply_cap)
+    .ok_or(LaminarError::AsolSupplyCapExceeded)?;
+  let new_asol_minted_this_round = global_state.asol_minted_this_round
     .checked_add(asol_gross)
     .ok_or(LaminarError::MathOverflow)?;
+  let round_capacity_remaining = compute_cap_headroom(new_asol_minted_this_round, global_state.max_asol_mint_per_round)
+    .ok_or(LaminarError::MintRoundCapExceeded)?;
+  record_position_mint(
+    global_state,
+    accounts.user_position.as_deref_mut(),
+    accounts.user.key(),
+    accounts.user_position_bump,
+    accounts.clock.slot,
+    PositionMint { lst_deposited: lst_received, asol_minted: asol_net, ..Default::default() },
+  )?;
 
+  let new_rounding_reserve = quote.new_rounding_reserve;
+  let new_insurance_fund = quote.new_insurance_fund;
+  let after = book.after(FeeAction::AsolMint, &quote);
+  let new_tvl = after.tvl().ok_or(LaminarError::MathOverflow)?;
   let new_liability = current_liability;  // aSOL mint doesn't change liability
-  
-  let new_rounding_reserve = credit_rounding_reserve(effective_rounding_reserve, reserve_credit_from_mint, max_rounding_reserve)?;
+  let new_cr_bps = after.cr_bps().ok_or(LaminarError::MathOverflow)?;
+  let new_non_claimable = after.non_claimable().ok_or(LaminarError::MathOverflow)?;
 
   // Signed accounting equity for invariant checking
-  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
   // Claimable equity for user-facing events
-  let new_claimable_equity = compute_claimable_equity_sol(new_tvl, new_liability, new_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
+  let new_claimable_equity = compute_claimable_equity_sol(new_tvl, new_liability, new_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
   let leverage_multiple = if new_claimable_equity > 0 {
     mul_div_down(new_tvl, 100, new_claimable_equity).unwrap_or(0)
@@ -227,112 +380,114 @@ pub fn handler(
     new_tvl,
     new_liability,
     new_accounting_equity,
-    new_rounding_reserve,
+    new_non_claimable,
     rounding_bound_lamports,
   )?;
+
   // Update state BEFORE external calls
 
   {
-    let global_state = &mut ctx.accounts.global_state;
-    global_state.total_lst_amount = new_lst_amount;
+    let global_state = &mut accounts.global_state;
+    CollateralLeg::commit(global_state, accounts.collateral_vault.as_deref_mut(), new_lst_amount)?;
     global_state.asol_supply = new_asol_supply;
-    global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+    global_state.asol_minted_this_round = new_asol_minted_this_round;
+    global_state.increment_operation_counter()?;
     global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    let recovery = global_state.track_recovery_mode(new_cr_bps, accounts.clock.slot);
+    emit_recovery_transition(&accounts.event_cpi, recovery, new_cr_bps, min_cr_bps, global_state.operation_counter, accounts.clock)?;
     msg!("State updated: LST={}, aSOL={}", new_lst_amount, new_asol_supply);
   }
 
   // External calls (CPIs)
 
-  // Transfer LST from user to vault
-  let transfer_accounts = TransferChecked {
-    from: ctx.accounts.user_lst_account.to_account_info(),
-    mint: ctx.accounts.lst_mint.to_account_info(),
-    to: ctx.accounts.vault.to_account_info(),
-    authority: ctx.accounts.user.to_account_info(),
-  };
-
-  let cpi_ctx = CpiContext::new(
-    ctx.accounts.token_program.to_account_info(),
-    transfer_accounts,
-  );
-
-  token_interface::transfer_checked(cpi_ctx, lst_amount, ctx.accounts.lst_mint.decimals)?;
-  msg!("Transferred {} LST to vault", lst_amount);
-
   // Mint aSOL to user
-  let seeds = &[GLOBAL_STATE_SEED, &[ctx.bumps.global_state]];
+  let seeds = &[GLOBAL_STATE_SEED, &[accounts.global_state.bump]];
   let signer = &[&seeds[..]];
 
+  let recipient_asol_account = accounts.recipient_asol_account.to_account_info();
   let mint_to_user = MintTo {
-    mint: ctx.accounts.asol_mint.to_account_info(),
-    to: ctx.accounts.user_asol_account.to_account_info(),
-    authority: ctx.accounts.global_state.to_account_info(),
+    mint: accounts.asol_mint.to_account_info(),
+    to: recipient_asol_account.clone(),
+    authority: accounts.global_state.to_account_info(),
   };
 
   let cpi_ctx_user = CpiContext::new_with_signer(
-    ctx.accounts.token_program.to_account_info(),
+    accounts.token_program.to_account_info(),
     mint_to_user,
     signer,
   );
 
   token_interface::mint_to(cpi_ctx_user, asol_net)?;
-  msg!("Minted {} aSOL to user", asol_net);
+  msg!("Minted {} aSOL to {}", asol_net, recipient_asol_account.key);
 
   // Mint fee to treasury
-  if fee > 0 {
+  if treasury_fee > 0 {
     let mint_to_treasury = MintTo {
-      mint: ctx.accounts.asol_mint.to_account_info(),
-      to: ctx.accounts.treasury_asol_account.to_account_info(),
-      authority: ctx.accounts.global_state.to_account_info(),
+      mint: accounts.asol_mint.to_account_info(),
+      to: accounts.treasury_asol_account.to_account_info(),
+      authority: accounts.global_state.to_account_info(),
     };
 
     let cpi_ctx_treasury = CpiContext::new_with_signer(
-      ctx.accounts.token_program.to_account_info(),
+      accounts.token_program.to_account_info(),
       mint_to_treasury,
       signer,
     );
 
-    token_interface::mint_to(cpi_ctx_treasury, fee)?;
-    msg!("Minted {} aSOL fee to treasury", fee);
+    token_interface::mint_to(cpi_ctx_treasury, treasury_fee)?;
+    msg!("Minted {} aSOL fee to treasury", treasury_fee);
   }
 
-  ctx.accounts.vault.reload()?;
-  ctx.accounts.asol_mint.reload()?;
+  accounts.vault.reload()?;
+  accounts.asol_mint.reload()?;
 
-  let expected_vault_balance = ctx.accounts.global_state.total_lst_amount;
   require!(
-    ctx.accounts.vault.amount == expected_vault_balance,
+    accounts.vault.amount == expected_vault_balance(accounts.global_state, accounts.collateral_vault.as_deref()),
     LaminarError::BalanceSheetViolation
   );
 
   require!(
-    ctx.accounts.asol_mint.supply == ctx.accounts.global_state.asol_supply,
+    accounts.asol_mint.supply == accounts.global_state.asol_supply,
     LaminarError::BalanceSheetViolation
   );
 
   msg!("Mint complete!");
   msg!("New TVL: {} lamports", new_tvl);
-  msg!("New aSOL supply: {} (user {} + treasury {})", new_asol_supply, asol_net, fee);
+  msg!("New aSOL supply: {} (user {} + treasury {})", new_asol_supply, asol_net, treasury_fee);
   
 
-  emit!(AsolMinted {
-    user: ctx.accounts.user.key(),
-    lst_deposited: lst_amount,
+  accounts.event_cpi.emit(AsolMinted {
+    user: accounts.user.key(),
+    recipient: recipient_asol_account.key(),
+    lst_deposited: lst_received,
     asol_minted: asol_net,
-    fee,
+    fee: treasury_fee,
+    fee_bps,
+    cr_multiplier_bps,
+    uncertainty_multiplier_bps,
+    insurance_fee,
+    recovery_bonus,
+    asol_supply_headroom,
+    round_capacity_remaining,
     nav: current_nav,
     old_tvl,
     new_tvl,
     old_equity: old_claimable_equity,
     new_equity: new_claimable_equity,
     leverage_multiple,
-    timestamp: ctx.accounts.clock.unix_timestamp,
-  });
+    deadline_slot,
+    client_order_id,
+    entrypoint_version,
+    operation_counter: accounts.global_state.operation_counter,
+    timestamp: accounts.clock.unix_timestamp,
+  })?;
 
 
   Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MintAsol<'info> {
   #[account(mut)]
@@ -397,16 +552,13 @@ pub struct MintAsol<'info> {
   )]
   pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-  /// CHECK: PDA validated by seeds
-  #[account(
-    seeds = [VAULT_AUTHORITY_SEED],
-    bump,
-  )]
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
   pub vault_authority: UncheckedAccount<'info>,
 
-  /// LST mint
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
   #[account(
-    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
   )]
   pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
 
@@ -415,4 +567,97 @@ pub struct MintAsol<'info> {
   pub system_program: Program<'info, System>,
 
   pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Caller's running mint totals; required while `min_action_gap_slots` or `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+
+  /// Receives the minted aSOL instead of `user_asol_account`; any token
+  /// account of `asol_mint`, whoever owns it
+  #[account(
+    mut,
+    token::mint = asol_mint,
+  )]
+  pub recipient_asol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// SPL stake pool of the primary LST; required only by the SOL zap into the primary LST
+  /// CHECK: address matched to the configured pool; owner and layout checked in `lst_rate::StakePoolDeposit`
+  #[account(
+    mut,
+    constraint = stake_pool.key() == global_state.lst_stake_pool @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: the pool's withdraw authority PDA, checked by the stake pool program
+  pub stake_pool_withdraw_authority: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `reserve_stake` in `lst_rate::StakePoolDeposit`
+  #[account(mut)]
+  pub reserve_stake: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `manager_fee_account` in `lst_rate::StakePoolDeposit`
+  #[account(mut)]
+  pub manager_fee_account: Option<UncheckedAccount<'info>>,
+
+  /// The primary LST mint again, writable for the pool to mint into
+  /// CHECK: matched to `lst_mint`; the stake pool checks it against its header
+  #[account(
+    mut,
+    constraint = stake_pool_mint.key() == lst_mint.key() @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool_mint: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: SPL stake pool program, matched by address
+  #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+  pub stake_pool_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> MintAsol<'info> {
+  /// The stake pool's `DepositSol` accounts; all are required.
+  fn stake_pool_deposit(&self) -> Result<StakePoolDeposit<'info>> {
+    let required = |account: &Option<UncheckedAccount<'info>>| {
+      account
+        .as_ref()
+        .map(|account| account.to_account_info())
+        .ok_or(error!(LaminarError::InvalidStakePool))
+    };
+    Ok(StakePoolDeposit {
+      stake_pool: required(&self.stake_pool)?,
+      withdraw_authority: required(&self.stake_pool_withdraw_authority)?,
+      reserve_stake: required(&self.reserve_stake)?,
+      manager_fee_account: required(&self.manager_fee_account)?,
+      pool_mint: required(&self.stake_pool_mint)?,
+      system_program: self.system_program.to_account_info(),
+      token_program: self.token_program.to_account_info(),
+      stake_pool_program: required(&self.stake_pool_program)?,
+    })
+  }
 }
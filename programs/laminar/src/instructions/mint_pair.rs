@@ -0,0 +1,298 @@
+//! Paired mint instruction - adds debt and equity in one step
+//! User deposits LST once and receives both amUSD and aSOL, split so that
+//! CR is the same before and after
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::math::*;
+use crate::invariants::*;
+use crate::constants::{ENTRYPOINT_PAIR, MAX_PAIR_CR_DRIFT_BPS};
+use crate::error::LaminarError;
+use crate::events::EventCpi;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::collateral::CollateralLeg;
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::instructions::accrue_stability_fee::accrue_stability_fee_to_treasury;
+use crate::instructions::mint_amusd::{execute_mint_amusd, MintAmusdAccounts, MintAmusdArgs};
+use crate::instructions::mint_asol::{execute_mint_asol, MintAsolAccounts, MintAsolArgs};
+
+/// Split `lst_amount` with `compute_cr_neutral_split` and run both mint
+/// paths on their share, each with its own fee and checks. A leg whose
+/// share rounds to zero is skipped and must have a zero minimum.
+pub fn handler(
+  ctx: Context<MintPair>,
+  lst_amount: u64,
+  min_amusd_out: u64,
+  min_asol_out: u64,
+) -> Result<()> {
+  assert_not_cpi_context()?;
+
+  let user_position_bump = ctx.bumps.user_position;
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+
+  // Bring the debt up to date before measuring CR, so the split and the
+  // drift check see the liability both legs will.
+  accounts.global_state.validate_version()?;
+  sync_exchange_rate_in_place(&mut accounts.global_state, accounts.clock.slot)?;
+  accrue_stability_fee_to_treasury(
+    &mut accounts.global_state,
+    &accounts.amusd_mint,
+    &accounts.treasury_amusd_account,
+    &accounts.token_program,
+    &accounts.clock,
+    &event_cpi,
+  )?;
+
+  let (cr_before_bps, collateral_weight_bps, weighted_tvl, liability) = book_cr(accounts)?;
+  let (lst_to_amusd, lst_to_asol) = compute_cr_neutral_split(lst_amount, collateral_weight_bps, weighted_tvl, liability)
+    .ok_or(LaminarError::MathOverflow)?;
+  msg!("Pair split: {} LST to aSOL, {} LST to amUSD", lst_to_asol, lst_to_amusd);
+
+  // Equity first: the aSOL leg lifts CR, so the amUSD leg never runs with
+  // CR below where the pair started.
+  if lst_to_asol > 0 {
+    execute_mint_asol(
+      MintAsolAccounts {
+        user: accounts.user.to_account_info(),
+        global_state: &mut accounts.global_state,
+        asol_mint: &mut accounts.asol_mint,
+        treasury_asol_account: &accounts.treasury_asol_account,
+        user_lst_account: &mut accounts.user_lst_account,
+        recipient_asol_account: &accounts.user_asol_account,
+        vault: &mut accounts.vault,
+        vault_authority: &accounts.vault_authority,
+        lst_mint: &accounts.lst_mint,
+        token_program: &accounts.token_program,
+        clock: &accounts.clock,
+        price_update: accounts.price_update.as_deref(),
+        switchboard_feed: accounts.switchboard_feed.as_deref(),
+        collateral_vault: accounts.collateral_vault.as_deref_mut(),
+        user_position: accounts.user_position.as_deref_mut(),
+        user_position_bump,
+        event_cpi: event_cpi.clone(),
+      },
+      MintAsolArgs::v1(lst_to_asol, min_asol_out),
+      ENTRYPOINT_PAIR,
+    )?;
+    accounts.user_lst_account.reload()?;
+  } else {
+    require!(min_asol_out == 0, LaminarError::SlippageExceeded);
+  }
+
+  if lst_to_amusd > 0 {
+    execute_mint_amusd(
+      MintAmusdAccounts {
+        user: accounts.user.to_account_info(),
+        global_state: &mut accounts.global_state,
+        amusd_mint: &mut accounts.amusd_mint,
+        treasury_amusd_account: &accounts.treasury_amusd_account,
+        user_lst_account: &mut accounts.user_lst_account,
+        recipient_amusd_account: &accounts.user_amusd_account,
+        vault: &mut accounts.vault,
+        vault_authority: &accounts.vault_authority,
+        lst_mint: &accounts.lst_mint,
+        token_program: &accounts.token_program,
+        clock: &accounts.clock,
+        price_update: accounts.price_update.as_deref(),
+        switchboard_feed: accounts.switchboard_feed.as_deref(),
+        collateral_vault: accounts.collateral_vault.as_deref_mut(),
+        savings_pool: accounts.savings_pool.as_deref_mut(),
+        savings_amusd_vault: accounts.savings_amusd_vault.as_deref_mut(),
+        user_position: accounts.user_position.as_deref_mut(),
+        user_position_bump,
+        event_cpi: event_cpi.clone(),
+      },
+      MintAmusdArgs::v1(lst_to_amusd, min_amusd_out),
+      ENTRYPOINT_PAIR,
+    )?;
+  } else {
+    require!(min_amusd_out == 0, LaminarError::SlippageExceeded);
+  }
+
+  let (cr_after_bps, ..) = book_cr(accounts)?;
+  msg!("Pair CR: {}bps -> {}bps", cr_before_bps, cr_after_bps);
+  assert_cr_drift_within(cr_before_bps, cr_after_bps, MAX_PAIR_CR_DRIFT_BPS)?;
+
+  Ok(())
+}
+
+/// Weighted CR of the whole book at the amUSD leg's price, with the inputs
+/// the split needs: `(cr_bps, collateral_weight_bps, weighted_tvl, liability)`
+fn book_cr(accounts: &MintPair) -> Result<(u64, u64, u64, u64)> {
+  let global_state = &accounts.global_state;
+  let oracle_price = resolve_price(
+    &PriceAccounts {
+      price_update: accounts.price_update.as_deref(),
+      switchboard_feed: accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    accounts.clock.slot,
+    accounts.clock.unix_timestamp,
+    PriceBias::Low,
+  )?;
+  let leg = CollateralLeg::resolve(global_state, accounts.collateral_vault.as_deref(), global_state.mock_lst_to_sol_rate)?;
+
+  let weighted_tvl = leg.weighted_tvl(leg.lst_amount)?;
+  let liability = compute_liability_sol(global_state.amusd_supply, oracle_price.sol_price_usd)
+    .ok_or(LaminarError::MathOverflow)?;
+  Ok((compute_cr_bps(weighted_tvl, liability), leg.collateral_weight_bps, weighted_tvl, liability))
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MintPair<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = asol_mint,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// amUSD mint
+  #[account(
+    mut,
+    constraint = amusd_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+    constraint = amusd_mint.key() == global_state.amusd_mint @ LaminarError::InvalidMint,
+    constraint = amusd_mint.freeze_authority == COption::Some(global_state.key()) @ LaminarError::InvalidFreezeAuthority,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// aSOL mint
+  #[account(
+    mut,
+    constraint = asol_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (receives minted amUSD)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's aSOL token account (receives minted aSOL)
+  #[account(
+    mut,
+    token::mint = asol_mint,
+    token::authority = user,
+    constraint = user_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's amUSD token account (receives protocol fees)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's aSOL token account (receives protocol fees)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = asol_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// User's LST token account (source of collateral for both legs)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+    constraint = user_lst_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (receives LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Savings Pool; required only while `global_state.savings_fee_split_bps` is set
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Option<Box<Account<'info, SavingsPool>>>,
+
+  /// Savings Pool amUSD vault (receives its fee share); matched to the pool in `route_savings_fee`
+  #[account(mut)]
+  pub savings_amusd_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// Caller's running mint totals; required while `per_user_amusd_cap`,
+  /// `min_action_gap_slots` or `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+}
@@ -1,12 +1,118 @@
 //! Core protocol instructions 
 //! Each instruction enforces invariants and updates the balance
+//!
+//! Write-set budget (accounts shared across users that user-path instructions
+//! may write-lock). Everything else writable must be owned by the caller.
+//!
+//! | instruction    | shared writable accounts                                    |
+//! |----------------|-------------------------------------------------------------|
+//! | mint_amusd     | global_state, amusd_mint, vault, treasury_amusd_account     |
+//! | redeem_amusd   | global_state, amusd_mint, vault, treasury_amusd_account     |
+//! | mint_asol      | global_state, asol_mint, vault, treasury_asol_account       |
+//! | redeem_asol    | global_state, asol_mint, vault, treasury_asol_account       |
+//! | mint_pair      | global_state, amusd_mint, asol_mint, vault, treasury_amusd_account, treasury_asol_account |
+//! | redeem_pair    | global_state, amusd_mint, asol_mint, vault, treasury_amusd_account, treasury_asol_account |
+//! | convert_amusd_to_asol | global_state, amusd_mint, asol_mint, treasury_amusd_account, treasury_asol_account |
+//! | convert_asol_to_amusd | global_state, amusd_mint, asol_mint, treasury_amusd_account, treasury_asol_account |
+//! | sync_exchange_rate | global_state                                            |
+//! | donate_dust    | global_state, amusd_mint or asol_mint                       |
+//! | force_unpause_redeem | global_state                                          |
+//! | health_check   | global_state                                                |
+//! | accrue_stability_fee | global_state, amusd_mint, treasury_amusd_account      |
+//! | psm_swap_usdc_for_amusd | global_state, amusd_mint, psm_vault, treasury_amusd_account |
+//! | psm_swap_amusd_for_usdc | global_state, amusd_mint, psm_vault, treasury_amusd_account |
+//!
+//! mint_amusd, mint_pair, redeem_amusd and redeem_pair also write-lock savings_pool and its amUSD
+//! vault while `savings_fee_split_bps` is set, to pay the pool its fee share.
+//!
+//! GlobalState already serializes every user-path instruction, so the other
+//! singletons add no extra contention. New feature accounts (stats, snapshots,
+//! price history) must not join a user-path write set; defer their writes to a
+//! keeper/admin instruction instead. Per-user accounts must be derived from the
+//! caller so two users never share one. The "Write-Set Budget" integration
+//! test enforces this table.
 
 pub mod initialize;
 pub mod mint_amusd;
 pub mod redeem_amusd;
 pub mod mint_asol;
+pub mod mint_pair;
+pub mod redeem_pair;
+pub mod convert;
 pub mod redeem_asol;
 pub mod sync_exchange_rate;
+pub mod configure_launch;
+pub mod update_fee_parameters;
+pub mod set_treasury;
+pub mod update_oracle_parameters;
+pub mod update_rounding_reserve_cap;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod donate_dust;
+pub mod force_unpause_redeem;
+pub mod update_max_pause_duration;
+pub mod queue_parameter_update;
+pub mod execute_parameter_update;
+pub mod cancel_parameter_update;
+pub mod health_check;
+pub mod set_pyth_price_feed;
+pub mod migrate_global_state;
+pub mod set_switchboard_feed;
+pub mod set_lst_stake_pool;
+pub mod fee_curve;
+pub mod set_marinade_state;
+pub mod set_haircut_breaker;
+pub mod set_lst_calculator;
+pub mod set_price_source;
+pub mod set_secondary_price_source;
+pub mod update_max_price_move;
+pub mod update_oracle_bounds;
+pub mod update_mock_oracle_confidence;
+pub mod update_max_oracle_age;
+pub mod set_fallback_price_source;
+pub mod update_ema_half_life;
+pub mod initialize_stability_pool;
+pub mod deposit_stability;
+pub mod withdraw_stability;
+pub mod trigger_drawdown;
+pub mod claim_stability_gains;
+pub mod rebalance;
+pub mod update_rebalance_discount;
+pub mod update_recovery_bonus;
+pub mod initiate_settlement;
+pub mod claim_settlement;
+pub mod fund_insurance;
+pub mod update_fee_split;
+pub mod socialize_bad_debt;
+pub mod flash_loan_lst;
+pub mod flash_repay_lst;
+pub mod add_collateral_type;
+pub mod set_collateral_status;
+pub mod refresh_tvl;
+pub mod migrate_collateral;
+pub mod initialize_psm;
+pub mod update_psm_parameters;
+pub mod psm_swap_usdc_for_amusd;
+pub mod psm_swap_amusd_for_usdc;
+pub mod initialize_savings_pool;
+pub mod update_savings_fee_split;
+pub mod deposit_savings;
+pub mod withdraw_savings;
+pub mod accrue_stability_fee;
+pub mod update_stability_fee;
+pub mod update_supply_caps;
+pub mod update_tvl_cap;
+pub mod update_transaction_limits;
+pub mod update_per_user_cap;
+pub mod update_mint_round;
+pub mod update_min_action_gap;
+pub mod update_asol_redeem_cooldown;
+pub mod update_pair_cr_tolerance;
+pub mod request_redemption;
+pub mod cancel_redemption;
+pub mod process_redemption;
+pub mod quote_trade;
+pub mod view_protocol_health;
 
 #[allow(ambiguous_glob_reexports)]
 pub use initialize::*;
@@ -17,6 +123,156 @@ pub use redeem_amusd::*;
 #[allow(ambiguous_glob_reexports)]
 pub use mint_asol::*;
 #[allow(ambiguous_glob_reexports)]
+pub use mint_pair::*;
+#[allow(ambiguous_glob_reexports)]
+pub use redeem_pair::*;
+#[allow(ambiguous_glob_reexports)]
+pub use convert::*;
+#[allow(ambiguous_glob_reexports)]
 pub use redeem_asol::*;
 #[allow(ambiguous_glob_reexports)]
-pub use sync_exchange_rate::*;
\ No newline at end of file
+pub use sync_exchange_rate::*;
+#[allow(ambiguous_glob_reexports)]
+pub use configure_launch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_fee_parameters::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_treasury::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_oracle_parameters::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_rounding_reserve_cap::*;
+#[allow(ambiguous_glob_reexports)]
+pub use propose_authority::*;
+#[allow(ambiguous_glob_reexports)]
+pub use accept_authority::*;
+#[allow(ambiguous_glob_reexports)]
+pub use donate_dust::*;
+#[allow(ambiguous_glob_reexports)]
+pub use force_unpause_redeem::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_max_pause_duration::*;
+#[allow(ambiguous_glob_reexports)]
+pub use queue_parameter_update::*;
+#[allow(ambiguous_glob_reexports)]
+pub use execute_parameter_update::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_parameter_update::*;
+#[allow(ambiguous_glob_reexports)]
+pub use health_check::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_pyth_price_feed::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_global_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_switchboard_feed::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_lst_stake_pool::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fee_curve::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_marinade_state::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_haircut_breaker::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_lst_calculator::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_price_source::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_secondary_price_source::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_max_price_move::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_oracle_bounds::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_mock_oracle_confidence::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_max_oracle_age::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_fallback_price_source::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_ema_half_life::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_stability_pool::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deposit_stability::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_stability::*;
+#[allow(ambiguous_glob_reexports)]
+pub use trigger_drawdown::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_stability_gains::*;
+#[allow(ambiguous_glob_reexports)]
+pub use rebalance::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_rebalance_discount::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_recovery_bonus::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initiate_settlement::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_settlement::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fund_insurance::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_fee_split::*;
+#[allow(ambiguous_glob_reexports)]
+pub use socialize_bad_debt::*;
+#[allow(ambiguous_glob_reexports)]
+pub use flash_loan_lst::*;
+#[allow(ambiguous_glob_reexports)]
+pub use flash_repay_lst::*;
+#[allow(ambiguous_glob_reexports)]
+pub use add_collateral_type::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_collateral_status::*;
+#[allow(ambiguous_glob_reexports)]
+pub use refresh_tvl::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_collateral::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_psm::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_psm_parameters::*;
+#[allow(ambiguous_glob_reexports)]
+pub use psm_swap_usdc_for_amusd::*;
+#[allow(ambiguous_glob_reexports)]
+pub use psm_swap_amusd_for_usdc::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize_savings_pool::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_savings_fee_split::*;
+#[allow(ambiguous_glob_reexports)]
+pub use deposit_savings::*;
+#[allow(ambiguous_glob_reexports)]
+pub use withdraw_savings::*;
+#[allow(ambiguous_glob_reexports)]
+pub use accrue_stability_fee::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_stability_fee::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_supply_caps::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_tvl_cap::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_transaction_limits::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_per_user_cap::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_mint_round::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_min_action_gap::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_asol_redeem_cooldown::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_pair_cr_tolerance::*;
+#[allow(ambiguous_glob_reexports)]
+pub use request_redemption::*;
+#[allow(ambiguous_glob_reexports)]
+pub use cancel_redemption::*;
+#[allow(ambiguous_glob_reexports)]
+pub use process_redemption::*;
+#[allow(ambiguous_glob_reexports)]
+pub use quote_trade::*;
+#[allow(ambiguous_glob_reexports)]
+pub use view_protocol_health::*;
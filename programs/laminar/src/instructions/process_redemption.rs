@@ -0,0 +1,272 @@
+//! process_redemption instruction - permissionless crank for the redemption queue
+//! Redeems the ticket at the head of the queue out of its escrow, through the
+//! same code path as redeem_amusd / redeem_asol and at this slot's prices,
+//! then closes the escrow and the ticket with their rent going to the owner.
+//! A cancelled ticket is only cleared.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::constants::ENTRYPOINT_QUEUE;
+use crate::error::LaminarError;
+use crate::events::{emit_event, EventCpi, RedemptionProcessed};
+use crate::instructions::{
+  execute_redeem_amusd, execute_redeem_asol, RedeemAmusdAccounts, RedeemAmusdArgs, RedeemAsolAccounts, RedeemAsolArgs,
+};
+use crate::invariants::*;
+use crate::redemption::RedeemSource;
+use crate::state::*;
+
+pub fn handler(ctx: Context<ProcessRedemption>) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  let ticket = &ctx.accounts.redemption_ticket;
+  require!(
+    ticket.sequence == ctx.accounts.global_state.redemption_queue_head,
+    LaminarError::RedemptionOutOfOrder
+  );
+
+  let owner = ticket.owner;
+  let token_mint = ticket.token_mint;
+  let sequence = ticket.sequence;
+  let amount = ticket.amount;
+  let min_lst_out = ticket.min_lst_out;
+  let request_slot = ticket.request_slot;
+  let sequence_bytes = sequence.to_le_bytes();
+  let seeds = &[REDEMPTION_TICKET_SEED, sequence_bytes.as_ref(), &[ticket.bump]];
+  let signer = &[&seeds[..]];
+
+  if amount > 0 {
+    let accounts = &mut *ctx.accounts;
+    let source = RedeemSource {
+      owner,
+      token_account: &accounts.escrow,
+      authority: accounts.redemption_ticket.to_account_info(),
+      signer_seeds: signer,
+    };
+
+    if token_mint == accounts.global_state.amusd_mint {
+      execute_redeem_amusd(
+        RedeemAmusdAccounts {
+          global_state: &mut accounts.global_state,
+          amusd_mint: &mut accounts.token_mint,
+          treasury_amusd_account: &accounts.treasury_token_account,
+          recipient_lst_account: &accounts.owner_lst_account,
+          vault: &mut accounts.vault,
+          vault_authority: &accounts.vault_authority,
+          lst_mint: &accounts.lst_mint,
+          token_program: &accounts.token_program,
+          clock: &accounts.clock,
+          price_update: accounts.price_update.as_deref(),
+          switchboard_feed: accounts.switchboard_feed.as_deref(),
+          collateral_vault: accounts.collateral_vault.as_deref_mut(),
+          savings_pool: accounts.savings_pool.as_deref_mut(),
+          savings_amusd_vault: accounts.savings_amusd_vault.as_deref_mut(),
+          event_cpi: EventCpi::new(&accounts.event_authority, ctx.bumps.event_authority),
+        },
+        source,
+        RedeemAmusdArgs::v1(amount, min_lst_out),
+        ENTRYPOINT_QUEUE,
+      )?;
+    } else {
+      execute_redeem_asol(
+        RedeemAsolAccounts {
+          global_state: &mut accounts.global_state,
+          asol_mint: &mut accounts.token_mint,
+          treasury_asol_account: &accounts.treasury_token_account,
+          recipient_lst_account: &accounts.owner_lst_account,
+          vault: &mut accounts.vault,
+          vault_authority: &accounts.vault_authority,
+          lst_mint: &accounts.lst_mint,
+          token_program: &accounts.token_program,
+          clock: &accounts.clock,
+          price_update: accounts.price_update.as_deref(),
+          switchboard_feed: accounts.switchboard_feed.as_deref(),
+          collateral_vault: accounts.collateral_vault.as_deref_mut(),
+          event_cpi: EventCpi::new(&accounts.event_authority, ctx.bumps.event_authority),
+        },
+        source,
+        RedeemAsolArgs::v1(amount, min_lst_out),
+        ENTRYPOINT_QUEUE,
+      )?;
+    }
+  }
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.redemption_queue_head = sequence
+      .checked_add(1)
+      .ok_or(LaminarError::MathOverflow)?;
+    // A redemption already counted itself; clearing a cancelled ticket has not.
+    if amount == 0 {
+      global_state.increment_operation_counter()?;
+    }
+  }
+
+  // Tokens sent to the escrow from outside the queue stay with it rather
+  // than let a transfer block every ticket behind this one.
+  ctx.accounts.escrow.reload()?;
+  if ctx.accounts.escrow.amount == 0 {
+    let close_accounts = CloseAccount {
+      account: ctx.accounts.escrow.to_account_info(),
+      destination: ctx.accounts.owner.to_account_info(),
+      authority: ctx.accounts.redemption_ticket.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), close_accounts, signer);
+    token_interface::close_account(cpi_ctx)?;
+  }
+
+  msg!("Processed redemption #{}: {} tokens", sequence, amount);
+
+  emit_event!(RedemptionProcessed {
+    owner,
+    processed_by: ctx.accounts.cranker.key(),
+    ticket: ctx.accounts.redemption_ticket.key(),
+    sequence,
+    amount,
+    request_slot,
+    processed_slot: ctx.accounts.clock.slot,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProcessRedemption<'info> {
+  /// Anyone; pays for any ATA the payout needs
+  #[account(mut)]
+  pub cranker: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Ticket at the head of the queue; closed to its owner
+  #[account(
+    mut,
+    seeds = [REDEMPTION_TICKET_SEED, redemption_ticket.sequence.to_le_bytes().as_ref()],
+    bump = redemption_ticket.bump,
+    close = owner,
+  )]
+  pub redemption_ticket: Box<Account<'info, RedemptionTicket>>,
+
+  /// CHECK: matched to the ticket's owner; receives the rent
+  #[account(
+    mut,
+    address = redemption_ticket.owner @ LaminarError::InvalidAccountState,
+  )]
+  pub owner: UncheckedAccount<'info>,
+
+  /// amUSD or aSOL mint of the ticket
+  #[account(
+    mut,
+    address = redemption_ticket.token_mint @ LaminarError::InvalidMint,
+    constraint = token_mint.mint_authority == anchor_lang::solana_program::program_option::COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// Ticket escrow (source of burned tokens)
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = redemption_ticket,
+    associated_token::token_program = token_program,
+  )]
+  pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// Treasury's ATA for the ticket's token (receives redemption fee)
+  #[account(
+    init_if_needed,
+    payer = cranker,
+    associated_token::mint = token_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Owner's LST ATA (receives redeemed LST)
+  #[account(
+    init_if_needed,
+    payer = cranker,
+    associated_token::mint = lst_mint,
+    associated_token::authority = owner,
+    associated_token::token_program = token_program,
+  )]
+  pub owner_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (source of LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Savings Pool; required for an amUSD ticket while `global_state.savings_fee_split_bps` is set
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Option<Box<Account<'info, SavingsPool>>>,
+
+  /// Savings Pool amUSD vault (receives its fee share); matched to the pool in `route_savings_fee`
+  #[account(mut)]
+  pub savings_amusd_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
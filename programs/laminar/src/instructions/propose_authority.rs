@@ -0,0 +1,46 @@
+//! propose_authority instruction - first step of the two-step admin transfer
+//! Stores `pending_authority`; nothing changes until that key signs
+//! `accept_authority`. Proposing again overwrites, proposing the default key cancels.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, AuthorityProposed}, state::*};
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let previous_pending_authority = global_state.pending_authority;
+
+  global_state.pending_authority = new_authority;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(AuthorityProposed {
+    authority: ctx.accounts.authority.key(),
+    previous_pending_authority,
+    pending_authority: new_authority,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Authority transfer proposed: {}", new_authority);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,193 @@
+//! psm_swap_amusd_for_usdc instruction - redeem amUSD 1:1 for the PSM's USDC
+//! The `psm_fee_bps` fee moves to the treasury as amUSD; the rest is burned,
+//! retired from `psm_amusd_outstanding` and paid out of the PSM vault rescaled
+//! to USDC decimals. Any amUSD can be swapped, but never more than the PSM
+//! has outstanding, so the vault only ever pays for debt it backs.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, PsmAmusdSwappedForUsdc};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<PsmSwapAmusdForUsdc>, amusd_amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+  require!(!global_state.redeem_paused, LaminarError::RedeemPaused);
+  require!(amusd_amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_amusd_account.amount >= amusd_amount,
+    LaminarError::InsufficientSupply
+  );
+
+  let (amusd_burned, usdc_out, fee) =
+    compute_psm_swap_out(amusd_amount, ctx.accounts.usdc_mint.decimals, global_state.psm_fee_bps)
+      .ok_or(LaminarError::MathOverflow)?;
+  require!(usdc_out > 0, LaminarError::AmountTooSmall);
+
+  let new_psm_amusd_outstanding = global_state.psm_amusd_outstanding
+    .checked_sub(amusd_burned)
+    .ok_or(LaminarError::PsmLiquidityExhausted)?;
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.psm_amusd_outstanding = new_psm_amusd_outstanding;
+    global_state.increment_operation_counter()?;
+  }
+
+  if fee > 0 {
+    token_interface::transfer_checked(
+      CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+          from: ctx.accounts.user_amusd_account.to_account_info(),
+          mint: ctx.accounts.amusd_mint.to_account_info(),
+          to: ctx.accounts.treasury_amusd_account.to_account_info(),
+          authority: ctx.accounts.user.to_account_info(),
+        },
+      ),
+      fee,
+      ctx.accounts.amusd_mint.decimals,
+    )?;
+  }
+
+  token_interface::burn(
+    CpiContext::new(
+      ctx.accounts.token_program.to_account_info(),
+      Burn {
+        mint: ctx.accounts.amusd_mint.to_account_info(),
+        from: ctx.accounts.user_amusd_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+      },
+    ),
+    amusd_burned,
+  )?;
+
+  let vault_authority_bump = [ctx.accounts.global_state.vault_authority_bump];
+  let seeds = &[VAULT_AUTHORITY_SEED, &vault_authority_bump[..]];
+  let signer = &[&seeds[..]];
+
+  token_interface::transfer_checked(
+    CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      TransferChecked {
+        from: ctx.accounts.psm_vault.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.user_usdc_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+      },
+      signer,
+    ),
+    usdc_out,
+    ctx.accounts.usdc_mint.decimals,
+  )?;
+
+  ctx.accounts.psm_vault.reload()?;
+  ctx.accounts.amusd_mint.reload()?;
+  assert_psm_fully_backed(
+    ctx.accounts.psm_vault.amount,
+    ctx.accounts.usdc_mint.decimals,
+    new_psm_amusd_outstanding,
+  )?;
+  require!(
+    ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_mint_supply()?,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("PSM: {} amUSD in ({} burned, fee {}), {} USDC out", amusd_amount, amusd_burned, fee, usdc_out);
+
+  emit_event!(PsmAmusdSwappedForUsdc {
+    user: ctx.accounts.user.key(),
+    amusd_in: amusd_amount,
+    amusd_burned,
+    fee,
+    usdc_out,
+    psm_amusd_outstanding: new_psm_amusd_outstanding,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PsmSwapAmusdForUsdc<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = treasury,
+    constraint = usdc_mint.key() == global_state.psm_usdc_mint @ LaminarError::PsmNotInitialized,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(mut)]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (source of the swap)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's amUSD token account (receives the PSM fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// User's USDC token account (receives the payout)
+  #[account(
+    mut,
+    token::mint = usdc_mint,
+    token::authority = user,
+  )]
+  pub user_usdc_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// PSM USDC vault
+  #[account(
+    mut,
+    seeds = [PSM_VAULT_SEED],
+    bump,
+    token::mint = usdc_mint,
+    token::authority = vault_authority,
+    constraint = psm_vault.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub psm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA; re-derived from the stored bump
+  pub vault_authority: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
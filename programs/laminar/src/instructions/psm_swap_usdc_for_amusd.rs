@@ -0,0 +1,192 @@
+//! psm_swap_usdc_for_amusd instruction - mint amUSD 1:1 against USDC
+//! The USDC joins the PSM vault, and its value rescaled to amUSD decimals is
+//! minted: the user's share plus the `psm_fee_bps` fee, minted to the
+//! treasury. All of it is booked to `psm_amusd_outstanding`, not
+//! `amusd_supply`, so the LST book's CR, NAV and liabilities never see it.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
+};
+
+use crate::collateral::deposit_lst;
+use crate::error::LaminarError;
+use crate::events::{emit_event, PsmUsdcSwappedForAmusd};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<PsmSwapUsdcForAmusd>, usdc_amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  require!(!global_state.mint_paused, LaminarError::MintPaused);
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  require!(usdc_amount > 0, LaminarError::ZeroAmount);
+  require!(
+    ctx.accounts.user_usdc_account.amount >= usdc_amount,
+    LaminarError::InsufficientCollateral
+  );
+
+  // Only what the vault actually receives is minted against.
+  let usdc_received = deposit_lst(
+    &ctx.accounts.token_program,
+    &ctx.accounts.usdc_mint,
+    &ctx.accounts.user_usdc_account,
+    &mut ctx.accounts.psm_vault,
+    ctx.accounts.user.to_account_info(),
+    usdc_amount,
+  )?;
+
+  let global_state = &ctx.accounts.global_state;
+  let (amusd_minted, amusd_out, fee) =
+    compute_psm_swap_in(usdc_received, ctx.accounts.usdc_mint.decimals, global_state.psm_fee_bps)
+      .ok_or(LaminarError::MathOverflow)?;
+  require!(amusd_out >= MIN_AMUSD_MINT, LaminarError::AmountTooSmall);
+
+  let new_psm_amusd_outstanding = global_state.psm_amusd_outstanding
+    .checked_add(amusd_minted)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(
+    new_psm_amusd_outstanding <= global_state.psm_debt_ceiling,
+    LaminarError::PsmDebtCeilingExceeded
+  );
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.psm_amusd_outstanding = new_psm_amusd_outstanding;
+    global_state.increment_operation_counter()?;
+  }
+
+  let seeds = &[GLOBAL_STATE_SEED, &[ctx.accounts.global_state.bump]];
+  let signer = &[&seeds[..]];
+
+  token_interface::mint_to(
+    CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      MintTo {
+        mint: ctx.accounts.amusd_mint.to_account_info(),
+        to: ctx.accounts.user_amusd_account.to_account_info(),
+        authority: ctx.accounts.global_state.to_account_info(),
+      },
+      signer,
+    ),
+    amusd_out,
+  )?;
+
+  if fee > 0 {
+    token_interface::mint_to(
+      CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+          mint: ctx.accounts.amusd_mint.to_account_info(),
+          to: ctx.accounts.treasury_amusd_account.to_account_info(),
+          authority: ctx.accounts.global_state.to_account_info(),
+        },
+        signer,
+      ),
+      fee,
+    )?;
+  }
+
+  ctx.accounts.psm_vault.reload()?;
+  ctx.accounts.amusd_mint.reload()?;
+  assert_psm_fully_backed(
+    ctx.accounts.psm_vault.amount,
+    ctx.accounts.usdc_mint.decimals,
+    new_psm_amusd_outstanding,
+  )?;
+  require!(
+    ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_mint_supply()?,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("PSM: {} USDC in, {} amUSD out (fee {})", usdc_received, amusd_out, fee);
+
+  emit_event!(PsmUsdcSwappedForAmusd {
+    user: ctx.accounts.user.key(),
+    usdc_in: usdc_received,
+    amusd_minted,
+    amusd_out,
+    fee,
+    psm_amusd_outstanding: new_psm_amusd_outstanding,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PsmSwapUsdcForAmusd<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = treasury,
+    constraint = usdc_mint.key() == global_state.psm_usdc_mint @ LaminarError::PsmNotInitialized,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    constraint = amusd_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (receives minted amUSD)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's amUSD token account (receives the PSM fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// User's USDC token account (source of the swap)
+  #[account(
+    mut,
+    token::mint = usdc_mint,
+    token::authority = user,
+  )]
+  pub user_usdc_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// PSM USDC vault
+  #[account(
+    mut,
+    seeds = [PSM_VAULT_SEED],
+    bump,
+    token::mint = usdc_mint,
+  )]
+  pub psm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,70 @@
+//! queue_parameter_update instruction - admin queues a timelocked parameter change
+//! The change is validated now and again at execution; it cannot apply before
+//! `eta_slot = clock.slot + param_timelock_slots`. Fee/CR/oracle/timelock/feed
+//! changes are applied by `execute_parameter_update`; every other kind by its
+//! own setter, passed the pending account.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, ParameterUpdateQueued}, state::*};
+
+pub fn handler(ctx: Context<QueueParameterUpdate>, change: ParamChange) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  change.validate()?;
+
+  let queued_slot = ctx.accounts.clock.slot;
+  let eta_slot = queued_slot.saturating_add(global_state.param_timelock_slots);
+
+  let pending = &mut ctx.accounts.pending_param_change;
+  pending.proposer = ctx.accounts.authority.key();
+  pending.change = change.clone();
+  pending.queued_slot = queued_slot;
+  pending.eta_slot = eta_slot;
+  pending.bump = ctx.bumps.pending_param_change;
+
+  global_state.increment_operation_counter()?;
+
+  emit_event!(ParameterUpdateQueued {
+    authority: ctx.accounts.authority.key(),
+    change,
+    queued_slot,
+    eta_slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Parameter update queued: kind={}, eta_slot={}", pending.change.kind(), eta_slot);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(change: ParamChange)]
+pub struct QueueParameterUpdate<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// One pending change per kind; cancel or execute before queuing another
+  #[account(
+    init,
+    payer = authority,
+    space = PendingParamChange::LEN,
+    seeds = [PENDING_PARAM_CHANGE_SEED, change.kind().to_le_bytes().as_ref()],
+    bump
+  )]
+  pub pending_param_change: Box<Account<'info, PendingParamChange>>,
+
+  pub system_program: Program<'info, System>,
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,151 @@
+//! Quote instructions - read-only pricing of the four user instructions
+//! Each replays its handler's pre-pricing steps (rate sync, stability fee
+//! accrual, oracle read, collateral leg) on a copy of GlobalState and prices
+//! the order with the same `quote` function the handler executes. The
+//! result is returned as a `TradeQuote` via return data, so
+//! `simulateTransaction` shows what execution would do without anyone
+//! re-implementing the math. Nothing is written.
+//!
+//! Redeems also report whether they pay below par, so a wallet can show the
+//! haircut before the user signs instead of after it executes.
+//!
+//! Like `quote`, these do not enforce slippage, pauses, caps or invariants;
+//! an order that quotes can still be refused by its handler.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  collateral::CollateralLeg,
+  error::LaminarError,
+  instructions::sync_exchange_rate_in_place,
+  math::FeeAction,
+  oracle::{resolve_price, resolve_redeem_price, PriceAccounts, PriceBias},
+  quote::QuoteState,
+  state::*,
+};
+
+/// One order priced the way its instruction would execute it now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TradeQuote {
+  /// Tokens priced before the fee: minted on a mint, burned on a redeem
+  pub gross: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// Whole fee, treasury and insurance shares, in the fee-bearing token
+  pub fee: u64,
+  /// Delivered to the user: amUSD/aSOL on a mint, LST on a redeem
+  pub net: u64,
+  /// Reserve-aware aSOL NAV after the order; 0 with no aSOL outstanding
+  pub nav: u64,
+  /// CR after the order, at collateral weights
+  pub cr_bps: u64,
+  /// The redeem pays below par: amUSD takes the haircut, aSOL is paid the
+  /// NAV of an insolvent book. Always false on a mint.
+  pub insolvency_mode: bool,
+  /// Share of par an amUSD redeem pays before any insurance top-up;
+  /// `BPS_PRECISION` everywhere else
+  pub haircut_bps: u64,
+  /// What `net` would be at par; equal to `net` off the amUSD haircut path
+  pub par_net: u64,
+}
+
+/// Quote `mint_amusd` for `lst_amount` LST landing in the vault
+pub fn handler_mint_amusd(ctx: Context<QuoteTrade>, lst_amount: u64) -> Result<TradeQuote> {
+  quote_trade(ctx.accounts, FeeAction::AmusdMint, lst_amount)
+}
+
+/// Quote `redeem_amusd` for `amusd_amount` amUSD
+pub fn handler_redeem_amusd(ctx: Context<QuoteTrade>, amusd_amount: u64) -> Result<TradeQuote> {
+  quote_trade(ctx.accounts, FeeAction::AmUSDRedeem, amusd_amount)
+}
+
+/// Quote `mint_asol` for `lst_amount` LST landing in the vault
+pub fn handler_mint_asol(ctx: Context<QuoteTrade>, lst_amount: u64) -> Result<TradeQuote> {
+  quote_trade(ctx.accounts, FeeAction::AsolMint, lst_amount)
+}
+
+/// Quote `redeem_asol` for `asol_amount` aSOL
+pub fn handler_redeem_asol(ctx: Context<QuoteTrade>, asol_amount: u64) -> Result<TradeQuote> {
+  quote_trade(ctx.accounts, FeeAction::AsolRedeem, asol_amount)
+}
+
+fn quote_trade(accounts: &QuoteTrade, action: FeeAction, amount: u64) -> Result<TradeQuote> {
+  require!(amount > 0, LaminarError::ZeroAmount);
+
+  let state = pricing_state(accounts, action)?;
+  let quote = state.trade_quote(action, amount).ok_or(LaminarError::MathOverflow)?;
+  Ok(quote)
+}
+
+/// The book `action`'s handler would price against in this slot, built on a
+/// copy of GlobalState so the syncs it runs first are never written back
+fn pricing_state(accounts: &QuoteTrade, action: FeeAction) -> Result<QuoteState> {
+  let clock = &accounts.clock;
+  let mut book = (**accounts.global_state).clone();
+  book.validate_version()?;
+
+  let redeem = matches!(action, FeeAction::AmUSDRedeem | FeeAction::AsolRedeem);
+  // A settled book redeems on its frozen snapshot.
+  let settled = redeem && book.settled;
+  if !settled {
+    sync_exchange_rate_in_place(&mut book, clock.slot)?;
+    // Only the amUSD paths accrue the stability fee before pricing.
+    if matches!(action, FeeAction::AmusdMint | FeeAction::AmUSDRedeem) {
+      book.accrue_stability_fee(clock.unix_timestamp)?;
+    }
+  }
+
+  let price_accounts = PriceAccounts {
+    price_update: accounts.price_update.as_deref(),
+    switchboard_feed: accounts.switchboard_feed.as_deref(),
+  };
+  let (sol_price_usd, primary_lst_to_sol_rate, uncertainty_index_bps) = if settled {
+    (book.settlement_sol_price_usd, book.settlement_lst_to_sol_rate, book.uncertainty_index_bps)
+  } else if redeem {
+    let bias = if action == FeeAction::AmUSDRedeem { PriceBias::High } else { PriceBias::Low };
+    let oracle_price = resolve_redeem_price(&price_accounts, &book, clock.slot, clock.unix_timestamp, bias)?;
+    let uncertainty_index_bps = book.effective_uncertainty_index(book.uncertainty_index_for(&oracle_price), clock.slot)?;
+    (oracle_price.sol_price_usd, book.mock_lst_to_sol_rate, uncertainty_index_bps)
+  } else {
+    let bias = if action == FeeAction::AmusdMint { PriceBias::Low } else { PriceBias::High };
+    let oracle_price = resolve_price(&price_accounts, &book, clock.slot, clock.unix_timestamp, bias)?;
+    let uncertainty_index_bps = book.effective_uncertainty_index(book.uncertainty_index_bps, clock.slot)?;
+    (oracle_price.sol_price_usd, book.mock_lst_to_sol_rate, uncertainty_index_bps)
+  };
+
+  let leg = CollateralLeg::resolve(&book, accounts.collateral_vault.as_deref(), primary_lst_to_sol_rate)?;
+  Ok(leg.quote_state(&book, sol_price_usd, uncertainty_index_bps))
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault the order would move; omitted for the primary LST
+  #[account(
+    seeds = [VAULT_SEED, collateral_vault.lst_mint.as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+}
@@ -0,0 +1,307 @@
+//! rebalance instruction - recapitalize a sub-min CR at a discount to NAV (permissionless)
+//! The caller deposits LST and receives aSOL minted at NAV plus a bonus, up to
+//! `rebalance_discount_bps`, paid out of the treasury's aSOL. The treasury opts
+//! in by approving the GlobalState PDA as delegate on its aSOL account; without
+//! an allowance the rebalance fills at NAV. Existing aSOL holders are not
+//! diluted and amUSD holders only gain collateral. The deposit is clipped to
+//! what lifts CR to exactly `min_cr_bps`.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::collateral::deposit_lst;
+use crate::error::LaminarError;
+use crate::events::{emit_event, emit_recovery_transition, EventCpi, Rebalanced};
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::invariants::*;
+use crate::math::*;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::state::*;
+
+pub fn handler(ctx: Context<Rebalance>, max_lst_amount: u64, min_asol_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.validate_version()?;
+    assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+    assert_operation_counter_sane(global_state.operation_counter)?;
+    global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+    assert_lst_snapshot_fresh(ctx.accounts.clock.slot, global_state.last_tvl_update_slot, global_state.max_oracle_staleness_slots)?;
+    sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  }
+
+  let global_state = &ctx.accounts.global_state;
+
+  // Priced like mint_asol: the high price understates NAV and CR alike.
+  let oracle_price = resolve_price(
+    &PriceAccounts {
+      price_update: ctx.accounts.price_update.as_deref(),
+      switchboard_feed: ctx.accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    ctx.accounts.clock.slot,
+    ctx.accounts.clock.unix_timestamp,
+    PriceBias::High,
+  )?;
+  let sol_price_used = oracle_price.sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let min_cr_bps = global_state.min_cr_bps;
+  let current_lst_amount = global_state.total_lst_amount;
+  let current_asol_supply = global_state.asol_supply;
+  let current_rounding_reserve = global_state.rounding_reserve_lamports;
+  let current_insurance_fund = global_state.insurance_fund_lamports;
+  let max_rounding_reserve = global_state.max_rounding_reserve_lamports;
+
+  require!(!global_state.mint_paused, LaminarError::MintPaused);
+  require!(max_lst_amount > 0, LaminarError::ZeroAmount);
+  assert_supply_nonzero(current_asol_supply, "rebalance")?;
+
+  let old_tvl = compute_book_tvl_sol(current_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let old_weighted_tvl = compute_book_tvl_sol(current_lst_amount, lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let liability = compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_used, global_state.redemption_rate_bps).ok_or(LaminarError::MathOverflow)?;
+  let old_cr_bps = compute_cr_bps(old_weighted_tvl, liability);
+  require!(old_cr_bps < min_cr_bps, LaminarError::RebalanceNotNeeded);
+
+  // The Stability Pool absorbs first; rebalancers fill what it cannot.
+  assert_no_pending_drawdown(old_cr_bps, min_cr_bps, global_state.stability_pool_amusd)?;
+
+  // amUSD enters at its redemption-rate value, matching `liability` above.
+  let amusd_value = mul_div_up(global_state.amusd_supply, global_state.redemption_rate_bps, BPS_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  let lst_needed = compute_rebalance_lst_needed(
+    current_lst_amount,
+    lst_to_sol_rate,
+    global_state.weighted_collateral_tvl_lamports,
+    amusd_value,
+    sol_price_used,
+    min_cr_bps,
+  )
+  .ok_or(LaminarError::MathOverflow)?;
+  let lst_amount = max_lst_amount.min(lst_needed);
+  // The final fill may be whatever sliver is left below the deposit minimum.
+  require!(lst_amount >= MIN_LST_DEPOSIT || lst_amount == lst_needed, LaminarError::AmountTooSmall);
+  require!(ctx.accounts.user_lst_account.amount >= lst_amount, LaminarError::InsufficientCollateral);
+  // Priced on what lands in the vault, net of any Token-2022 transfer fee
+  let lst_received = deposit_lst(
+    &ctx.accounts.token_program,
+    &ctx.accounts.lst_mint,
+    &ctx.accounts.user_lst_account,
+    &mut ctx.accounts.vault,
+    ctx.accounts.user.to_account_info(),
+    lst_amount,
+  )?;
+
+  let current_non_claimable = compute_non_claimable_sol(current_rounding_reserve, current_insurance_fund)
+    .ok_or(LaminarError::MathOverflow)?;
+  let nav = nav_asol_with_reserve(old_tvl, liability, current_non_claimable, current_asol_supply)
+    .ok_or(LaminarError::MathOverflow)?;
+  // No equity left to price aSOL against
+  require!(nav > 0, LaminarError::InsolventProtocol);
+
+  let sol_value = compute_tvl_sol(lst_received, lst_to_sol_rate).ok_or(LaminarError::MathOverflow)?;
+  let sol_value_up = mul_div_up(lst_received, lst_to_sol_rate, SOL_PRECISION).ok_or(LaminarError::MathOverflow)?;
+
+  // Same rounding as mint_asol: (k_lamports=2, k_usd=0)
+  let asol_minted = mul_div_down(sol_value, SOL_PRECISION, nav).ok_or(LaminarError::MathOverflow)?;
+  let asol_reference_up = mul_div_up(sol_value_up, SOL_PRECISION, nav).ok_or(LaminarError::MathOverflow)?;
+  let rounding_delta_asol = compute_rounding_delta_units(asol_minted, asol_reference_up).ok_or(LaminarError::MathOverflow)?;
+  let reserve_credit = asol_dust_to_lamports_up(rounding_delta_asol, nav).ok_or(LaminarError::MathOverflow)?;
+  let rounding_bound_lamports = derive_rounding_bound_lamports(2, 0, sol_price_used)?;
+
+  // Discount comes out of the treasury's delegated aSOL, never out of the book.
+  let treasury_asol = &ctx.accounts.treasury_asol_account;
+  let treasury_allowance = if treasury_asol.delegate == COption::Some(ctx.accounts.global_state.key()) {
+    treasury_asol.delegated_amount.min(treasury_asol.amount)
+  } else {
+    0
+  };
+  let bonus_target = compute_rebalance_bonus(asol_minted, global_state.rebalance_discount_bps)
+    .ok_or(LaminarError::MathOverflow)?;
+  let treasury_bonus = bonus_target.min(treasury_allowance);
+
+  let asol_out = asol_minted.checked_add(treasury_bonus).ok_or(LaminarError::MathOverflow)?;
+  require!(asol_out >= min_asol_out, LaminarError::SlippageExceeded);
+  require!(asol_minted > 0, LaminarError::AmountTooSmall);
+
+  let new_lst_amount = current_lst_amount.checked_add(lst_received).ok_or(LaminarError::MathOverflow)?;
+  let new_asol_supply = current_asol_supply.checked_add(asol_minted).ok_or(LaminarError::MathOverflow)?;
+  let new_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let new_weighted_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let new_cr_bps = compute_cr_bps(new_weighted_tvl, liability);
+  let new_rounding_reserve = credit_rounding_reserve(current_rounding_reserve, reserve_credit, max_rounding_reserve)?;
+  let new_non_claimable = compute_non_claimable_sol(new_rounding_reserve, current_insurance_fund)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, liability, new_non_claimable)
+    .ok_or(LaminarError::MathOverflow)?;
+
+  // Effective discount the caller got, for the event
+  let discount_bps = mul_div_down(treasury_bonus, BPS_PRECISION, asol_out).ok_or(LaminarError::MathOverflow)?;
+
+  assert_rounding_reserve_within_cap(new_rounding_reserve, max_rounding_reserve)?;
+  assert_balance_sheet_holds(new_tvl, liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
+
+  // State update
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_lst_amount = new_lst_amount;
+    global_state.asol_supply = new_asol_supply;
+    global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.increment_operation_counter()?;
+    let recovery = global_state.track_recovery_mode(new_cr_bps, ctx.accounts.clock.slot);
+    let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    emit_recovery_transition(&event_cpi, recovery, new_cr_bps, min_cr_bps, global_state.operation_counter, &ctx.accounts.clock)?;
+  }
+
+  // CPI calls
+  let seeds = &[GLOBAL_STATE_SEED, &[ctx.accounts.global_state.bump]];
+  let signer = &[&seeds[..]];
+
+  let mint_to_user = MintTo {
+    mint: ctx.accounts.asol_mint.to_account_info(),
+    to: ctx.accounts.user_asol_account.to_account_info(),
+    authority: ctx.accounts.global_state.to_account_info(),
+  };
+  let cpi_ctx_mint = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), mint_to_user, signer);
+  token_interface::mint_to(cpi_ctx_mint, asol_minted)?;
+
+  if treasury_bonus > 0 {
+    let bonus_accounts = TransferChecked {
+      from: ctx.accounts.treasury_asol_account.to_account_info(),
+      mint: ctx.accounts.asol_mint.to_account_info(),
+      to: ctx.accounts.user_asol_account.to_account_info(),
+      authority: ctx.accounts.global_state.to_account_info(),
+    };
+    let cpi_ctx_bonus = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), bonus_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx_bonus, treasury_bonus, ctx.accounts.asol_mint.decimals)?;
+  }
+
+  ctx.accounts.vault.reload()?;
+  ctx.accounts.asol_mint.reload()?;
+  require!(ctx.accounts.vault.amount == ctx.accounts.global_state.total_lst_amount, LaminarError::BalanceSheetViolation);
+  require!(ctx.accounts.asol_mint.supply == ctx.accounts.global_state.asol_supply, LaminarError::BalanceSheetViolation);
+
+  msg!(
+    "Rebalanced: {} LST for {} aSOL (+{} from treasury), CR {} -> {} bps",
+    lst_received,
+    asol_minted,
+    treasury_bonus,
+    old_cr_bps,
+    new_cr_bps
+  );
+
+  emit_event!(Rebalanced {
+    caller: ctx.accounts.user.key(),
+    lst_deposited: lst_received,
+    asol_minted,
+    treasury_bonus,
+    nav,
+    discount_bps,
+    old_cr_bps,
+    new_cr_bps,
+    sol_price_used,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = asol_mint,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    constraint = asol_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's aSOL token account (receives minted aSOL and the bonus)
+  #[account(
+    mut,
+    token::mint = asol_mint,
+    token::authority = user,
+    constraint = user_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's aSOL token account (funds the discount through its delegation)
+  #[account(
+    mut,
+    associated_token::mint = asol_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// User's LST token account (source of collateral)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+    constraint = user_lst_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (receives LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+}
@@ -7,59 +7,250 @@ use anchor_spl::{
   associated_token::AssociatedToken,
   token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked, Burn}
 };
-use crate::{constants:: MIN_PROTOCOL_TVL, events::AsolRedeemed, instructions::sync_exchange_rate_in_place, state::*};
+use crate::{constants::{MIN_PROTOCOL_TVL, REDEEM_ALL, CURRENT_ARGS_VERSION, ENTRYPOINT_V1, ENTRYPOINT_V2, ENTRYPOINT_TO_SOL}, events::{emit_recovery_transition, EventCpi, AsolRedeemed, FallbackOracleUsed, RoundingFallback}, instructions::sync_exchange_rate_in_place, state::*};
 use crate::math::*;
 use crate::invariants::*;
 use crate::error::LaminarError;
+use crate::oracle::{resolve_redeem_price, PriceAccounts, PriceBias};
+use crate::collateral::{assert_native_leg, expected_vault_balance, needs_lst_snapshot, validate_leg_accounts, unstake_lst_payout, unwrap_sol_payout, vault_authority_seeds, CollateralLeg, SolUnwrap};
+use crate::lst_rate::{StakePoolWithdraw, SPL_STAKE_POOL_PROGRAM_ID, STAKE_PROGRAM_ID};
+use crate::position::{assert_asol_redeem_cooldown, record_position_redeem};
+use crate::redemption::RedeemSource;
+use crate::quote::{quote_redeem_asol, Quote};
+
+
+/// Versioned arguments for `redeem_asol_v2`.
+///
+/// New optional fields are appended with defaults that reproduce v1
+/// behavior, so `redeem_asol` and `redeem_asol_v2` always share one code path.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RedeemAsolArgs {
+  /// Layout version of this struct (see `CURRENT_ARGS_VERSION`)
+  pub args_version: u8,
+  /// aSOL to burn; `REDEEM_ALL` for the whole balance
+  pub asol_amount: u64,
+  /// Minimum LST the user accepts after fees
+  pub min_lst_out: u64,
+  /// Redeem only what the vault can pay out instead of failing (args version 2)
+  pub allow_partial: bool,
+  /// Last slot the order may execute in; 0 for no deadline (args version 3)
+  pub deadline_slot: u64,
+  /// Highest dynamic fee the user accepts, in bps; 0 for no bound (args version 4)
+  pub max_fee_bps: u64,
+  /// Caller's order reference, echoed in the event and otherwise unused; 0 for none (args version 5)
+  pub client_order_id: u64,
+}
 
+impl RedeemAsolArgs {
+  /// Args equivalent to a v1 `redeem_asol` call.
+  pub fn v1(asol_amount: u64, min_lst_out: u64) -> Self {
+    Self {
+      args_version: CURRENT_ARGS_VERSION,
+      asol_amount,
+      min_lst_out,
+      allow_partial: false,
+      deadline_slot: 0,
+      max_fee_bps: 0,
+      client_order_id: 0,
+    }
+  }
+}
 
+/// v1 entrypoint - thin wrapper over the v2 args path.
 pub fn handler(
   ctx: Context<RedeemAsol>,
   asol_amount: u64,
   min_lst_out: u64,
 ) -> Result<()> {
+  process(ctx, RedeemAsolArgs::v1(asol_amount, min_lst_out), ENTRYPOINT_V1)
+}
+
+/// v2 entrypoint - single versioned args struct.
+pub fn handler_v2(ctx: Context<RedeemAsol>, args: RedeemAsolArgs) -> Result<()> {
+  process(ctx, args, ENTRYPOINT_V2)
+}
+
+/// Redeem-to-SOL entrypoint - pays out as usual, then turns the payout into
+/// native SOL: the primary LST is unstaked through its stake pool, the wSOL
+/// leg's payout is unwrapped. `min_sol_out` is in lamports and checked against
+/// what actually reached the user, any unstake fee taken.
+pub fn handler_to_sol(ctx: Context<RedeemAsol>, asol_amount: u64, min_sol_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(ctx.accounts.recipient_lst_account.is_none(), LaminarError::RecipientNotSupported);
+  let user = ctx.accounts.user.to_account_info();
+  let mut user_lst_account = ctx.accounts.user_lst_account.clone();
+  let balance_before = user_lst_account.amount;
+
+  if ctx.accounts.collateral_vault.is_none() {
+    let stake_pool = ctx.accounts.stake_pool_withdraw()?;
+    // The pool sets the LST -> SOL price, so only the SOL floor applies.
+    process(ctx, RedeemAsolArgs::v1(asol_amount, 0), ENTRYPOINT_TO_SOL)?;
+    unstake_lst_payout(&stake_pool, user, &mut user_lst_account, balance_before, min_sol_out)?;
+    return Ok(());
+  }
+
+  assert_native_leg(ctx.accounts.collateral_vault.as_deref())?;
+  let unwrap = ctx.accounts.sol_unwrap(ctx.bumps.sol_unwrap_account)?;
+  // wSOL is 1:1 with lamports, so the LST floor is the SOL floor.
+  process(ctx, RedeemAsolArgs::v1(asol_amount, min_sol_out), ENTRYPOINT_TO_SOL)?;
+  unwrap_sol_payout(&unwrap, user, &mut user_lst_account, balance_before, min_sol_out)?;
+  Ok(())
+}
+
+fn process(
+  ctx: Context<RedeemAsol>,
+  args: RedeemAsolArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  validate_args_version(args.args_version)?;
+
+  assert_asol_redeem_cooldown(&ctx.accounts.global_state, ctx.accounts.user_position.as_deref(), ctx.accounts.clock.slot)?;
+  record_position_redeem(
+    &ctx.accounts.global_state,
+    ctx.accounts.user_position.as_deref_mut(),
+    ctx.accounts.user_asol_account.owner,
+    ctx.bumps.user_position,
+    ctx.accounts.clock.slot,
+  )?;
+
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+  execute_redeem_asol(
+    RedeemAsolAccounts {
+      global_state: &mut accounts.global_state,
+      asol_mint: &mut accounts.asol_mint,
+      treasury_asol_account: &accounts.treasury_asol_account,
+      recipient_lst_account: accounts.recipient_lst_account.as_deref().unwrap_or(&accounts.user_lst_account),
+      vault: &mut accounts.vault,
+      vault_authority: &accounts.vault_authority,
+      lst_mint: &accounts.lst_mint,
+      token_program: &accounts.token_program,
+      clock: &accounts.clock,
+      price_update: accounts.price_update.as_deref(),
+      switchboard_feed: accounts.switchboard_feed.as_deref(),
+      collateral_vault: accounts.collateral_vault.as_deref_mut(),
+      event_cpi,
+    },
+    RedeemSource {
+      owner: accounts.user_asol_account.owner,
+      token_account: &accounts.user_asol_account,
+      authority: accounts.user.to_account_info(),
+      signer_seeds: &[],
+    },
+    args,
+    entrypoint_version,
+  )
+}
+
+/// Everything an aSOL redemption reads and writes besides the aSOL source;
+/// filled from `RedeemAsol` or from a queued ticket's accounts
+pub struct RedeemAsolAccounts<'a, 'info> {
+  pub global_state: &'a mut Account<'info, GlobalState>,
+  pub asol_mint: &'a mut InterfaceAccount<'info, Mint>,
+  pub treasury_asol_account: &'a InterfaceAccount<'info, TokenAccount>,
+  /// Receives the redeemed LST: the caller's account or an explicit recipient
+  pub recipient_lst_account: &'a InterfaceAccount<'info, TokenAccount>,
+  pub vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+  pub vault_authority: &'a AccountInfo<'info>,
+  pub lst_mint: &'a InterfaceAccount<'info, Mint>,
+  pub token_program: &'a Interface<'info, TokenInterface>,
+  pub clock: &'a Clock,
+  pub price_update: Option<&'a AccountInfo<'info>>,
+  pub switchboard_feed: Option<&'a AccountInfo<'info>>,
+  pub collateral_vault: Option<&'a mut Account<'info, CollateralVault>>,
+  pub event_cpi: EventCpi<'info>,
+}
+
+/// Burn `args.asol_amount` from `source` and pay the LST out at current
+/// NAV. With `allow_partial`, only as much as the vault can pay out is burned.
+/// Per-wallet position checks are the caller's job.
+pub fn execute_redeem_asol<'info>(
+  mut accounts: RedeemAsolAccounts<'_, 'info>,
+  source: RedeemSource<'_, 'info>,
+  args: RedeemAsolArgs,
+  entrypoint_version: u8,
+) -> Result<()> {
+  let RedeemAsolArgs { asol_amount, min_lst_out, allow_partial, deadline_slot, max_fee_bps, client_order_id, .. } = args;
+
+  // REDEEM_ALL takes whatever the source holds now, fees included.
+  let asol_amount = if asol_amount == REDEEM_ALL { source.spendable() } else { asol_amount };
+
   // All validations before any state changes
   
   assert_not_cpi_context()?;
+  assert_before_deadline(deadline_slot, accounts.clock.slot)?;
 
-  // sync first
+  // A frozen vault fails every transfer CPI; reject before touching state.
+  require!(!accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  // sync first; a settled book runs on its frozen snapshot instead
   {
-  let global_state = &mut ctx.accounts.global_state;
+  let global_state = &mut accounts.global_state;
   global_state.validate_version()?;
-  assert_lst_snapshot_fresh(
-    ctx.accounts.clock.slot,
-    global_state.last_tvl_update_slot,
-    global_state.max_oracle_staleness_slots,
+  assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+  assert_operation_counter_sane(global_state.operation_counter)?;
+  validate_leg_accounts(
+    global_state,
+    &global_state.key(),
+    accounts.collateral_vault.as_deref(),
+    &accounts.vault_authority.key(),
+    &accounts.vault.key(),
   )?;
-  sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  if !global_state.settled {
+    if needs_lst_snapshot(global_state, accounts.collateral_vault.as_deref()) {
+      assert_lst_snapshot_fresh(
+        accounts.clock.slot,
+        global_state.last_tvl_update_slot,
+        global_state.max_oracle_staleness_slots,
+      )?;
+    }
+    sync_exchange_rate_in_place(global_state, accounts.clock.slot)?;
+  }
   }
 
   // read only borrow
-  let global_state = &ctx.accounts.global_state;
-
-  assert_oracle_freshness_and_confidence(
-    ctx.accounts.clock.slot, 
-    global_state.last_oracle_update_slot, 
-    global_state.max_oracle_staleness_slots, 
-    global_state.mock_sol_price_usd, 
-    global_state.mock_oracle_confidence_usd, 
-    global_state.max_conf_bps
-  )?;
-  
+  let global_state = &accounts.global_state;
+  let settled = global_state.settled;
+
+  let (sol_price_used, primary_lst_to_sol_rate, uncertainty_index_bps) = if settled {
+    (global_state.settlement_sol_price_usd, global_state.settlement_lst_to_sol_rate, global_state.uncertainty_index_bps)
+  } else {
+    let oracle_price = resolve_redeem_price(
+      &PriceAccounts {
+        price_update: accounts.price_update,
+        switchboard_feed: accounts.switchboard_feed,
+      },
+      global_state,
+      accounts.clock.slot,
+      accounts.clock.unix_timestamp,
+      PriceBias::Low,
+    )?;
+
+    if oracle_price.is_fallback {
+      accounts.event_cpi.emit(FallbackOracleUsed {
+        user: source.owner,
+        primary_price_source: global_state.price_source,
+        fallback_price_source: oracle_price.source as u8,
+        sol_price_usd: oracle_price.sol_price_usd,
+        uncertainty_index_bps: global_state.uncertainty_index_for(&oracle_price),
+        slot: accounts.clock.slot,
+        operation_counter: global_state.operation_counter,
+        timestamp: accounts.clock.unix_timestamp,
+      })?;
+    }
+
+    let uncertainty_index_bps = global_state.effective_uncertainty_index(global_state.uncertainty_index_for(&oracle_price), accounts.clock.slot)?;
+    (oracle_price.sol_price_usd, global_state.mock_lst_to_sol_rate, uncertainty_index_bps)
+  };
+
+  // The vault this redemption pays from, valued against the whole book
+  let leg = CollateralLeg::resolve(global_state, accounts.collateral_vault.as_deref(), primary_lst_to_sol_rate)?;
+
   // Capture values
-  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
-  let sol_price_used = global_state.mock_sol_price_usd;
-  let current_lst_amount = global_state.total_lst_amount;
+  let current_lst_amount = leg.lst_amount;
   let current_amusd_supply = global_state.amusd_supply;
   let current_asol_supply = global_state.asol_supply;
-  let target_cr_bps = global_state.target_cr_bps;
   let min_cr_bps = global_state.min_cr_bps;
-  let current_rounding_reserve = global_state.rounding_reserve_lamports;
-  let fee_asol_redeem_bps = global_state.fee_asol_redeem_bps;
-  let fee_min_multiplier_bps = global_state.fee_min_multiplier_bps;
-  let fee_max_multiplier_bps = global_state.fee_max_multiplier_bps;
-  let uncertainty_index_bps = global_state.uncertainty_index_bps;
-  let uncertainty_max_bps = global_state.uncertainty_max_bps;
 
 
   // Configured hard cap for reserve growth
@@ -68,123 +259,132 @@ pub fn handler(
   // Validations
   require!(!global_state.redeem_paused, LaminarError::RedeemPaused);
   require!(asol_amount > 0, LaminarError::ZeroAmount);
+  require!(
+    compute_cap_headroom(asol_amount, global_state.max_single_redeem_units).is_some(),
+    LaminarError::RedeemTooLarge
+  );
   // require!(min_lst_out > 0, LaminarError::ZeroAmount);
   // require!(min_lst_out >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
 
-  msg!("aSOL to redeem: {}", asol_amount);
-
-  // All math logic
-
-  let old_tvl = compute_tvl_sol(current_lst_amount, lst_to_sol_rate).ok_or(LaminarError::MathOverflow)?;
-
-  // let current_tvl = compute_tvl_sol(current_lst_amount, lst_to_sol_rate)
-  //   .ok_or(LaminarError::MathOverflow)?;
-
-  let current_liability = if current_amusd_supply > 0 {
-    compute_liability_sol(current_amusd_supply, sol_price_used)
-      .ok_or(LaminarError::MathOverflow)?
+  let quote_state = leg.quote_state(global_state, sol_price_used, uncertainty_index_bps);
+
+  // Partial fill: shrink the order to the most the vault can pay while the
+  // leg stays above MIN_PROTOCOL_TVL; the rest stays with the user.
+  let requested_amount = asol_amount;
+  let asol_amount = if allow_partial {
+    let payable_lst = current_lst_amount.min(accounts.vault.amount);
+    compute_partial_fill(requested_amount, |units| {
+      quote_redeem_asol(&quote_state, units).is_some_and(|quote| {
+        quote.to_user <= payable_lst
+          && (settled || quote.new_total_lst_amount >= MIN_PROTOCOL_TVL || quote.new_total_lst_amount == 0)
+      })
+    })
   } else {
-    0
+    requested_amount
   };
+  require!(asol_amount > 0, LaminarError::InsufficientCollateral);
+  let fill_bps = mul_div_down(asol_amount, BPS_PRECISION, requested_amount)
+    .ok_or(LaminarError::MathOverflow)?;
 
-  let old_claimable_equity = compute_claimable_equity_sol(old_tvl, current_liability, current_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
-
-  let old_cr_bps = compute_cr_bps(old_tvl, current_liability);
+  msg!("aSOL to redeem: {} of {} requested", asol_amount, requested_amount);
 
-  let fee_bps = compute_dynamic_fee_bps(fee_asol_redeem_bps, FeeAction::AsolRedeem, old_cr_bps, min_cr_bps, target_cr_bps, fee_min_multiplier_bps, fee_max_multiplier_bps, uncertainty_index_bps, uncertainty_max_bps).ok_or(LaminarError::InvalidParameter)?;
+  // All math logic
 
-  let (asol_net_in, asol_fee_in) = apply_fee(asol_amount, fee_bps)
-    .ok_or(LaminarError::MathOverflow)?;
-  require!(asol_net_in > 0, LaminarError::AmountTooSmall);
+  let old_tvl = quote_state.tvl().ok_or(LaminarError::MathOverflow)?;
+  let current_liability = quote_state.liability().ok_or(LaminarError::MathOverflow)?;
+  let current_non_claimable = quote_state.non_claimable().ok_or(LaminarError::MathOverflow)?;
+  let old_claimable_equity = compute_claimable_equity_sol(old_tvl, current_liability, current_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
-  msg!("aSOL input: {}", asol_amount);
-  msg!("aSOL fee (to treasury): {}", asol_fee_in);
-  msg!("aSOL net burn basis: {}", asol_net_in);
+  // Equity exits are closed while CR is under min, unless the book is
+  // settling and they pay out the frozen NAV.
+  if !settled {
+    let old_cr_bps = quote_state.cr_bps().ok_or(LaminarError::MathOverflow)?;
+    assert_not_in_recovery_mode(old_cr_bps, min_cr_bps)?;
+  }
 
-  let solvent_mode = old_cr_bps >= BPS_PRECISION;
+  // Settlement: burning the last outstanding token takes the vault's dust,
+  // even when that dust is too small to show up in NAV. Settled redemptions
+  // are fee-free, so the whole order is the net burn.
+  let final_settlement_exit = settled
+    && asol_amount == current_asol_supply
+    && current_amusd_supply == 0;
 
-  let current_nav = nav_asol_with_reserve(old_tvl, current_liability, current_rounding_reserve, current_asol_supply)
+  let current_nav = nav_asol_with_reserve(old_tvl, current_liability, current_non_claimable, current_asol_supply)
     .ok_or(LaminarError::InsolventProtocol)?;
-  require!(current_nav > 0, LaminarError::InsolventProtocol);
+  require!(current_nav > 0 || final_settlement_exit, LaminarError::InsolventProtocol);
 
   msg!("Current aSOL NAV: {} lamports per aSOL", current_nav);
 
-  require!(min_lst_out > 0, LaminarError::ZeroAmount);
-  require!(min_lst_out >= MIN_LST_DEPOSIT, LaminarError::AmountTooSmall);
+  require!(
+    source.spendable() >= asol_amount,
+    LaminarError::InsufficientSupply
+  );
 
-  let sol_value_down = mul_div_down(asol_net_in, current_nav, SOL_PRECISION)
+  // - Solvent (CR >= 100%): user-favoring rounding (up, up), reserve debited
+  // - Insolvent (CR < 100%): conservative rounding (down, down), no reserve debit
+  // With NAV and supply checked above, the quote only fails when this leg
+  // cannot cover the payout.
+  let quote = quote_redeem_asol(&quote_state, asol_amount).ok_or(LaminarError::InsufficientCollateral)?;
+  assert_fee_within_max(quote.fee_bps, max_fee_bps)?;
+  let Quote { to_user: lst_out, fee: asol_treasury_fee, fee_bps, cr_multiplier_bps, uncertainty_multiplier_bps, insurance_fee: asol_insurance_fee, .. } = quote;
+
+  let asol_net_in = asol_amount
+    .checked_sub(asol_treasury_fee)
+    .and_then(|net| net.checked_sub(asol_insurance_fee))
     .ok_or(LaminarError::MathOverflow)?;
-  let lst_gross_down = mul_div_down(sol_value_down, SOL_PRECISION, lst_to_sol_rate)
+  require!(asol_net_in > 0, LaminarError::AmountTooSmall);
+  let asol_burned = asol_net_in
+    .checked_add(asol_insurance_fee)
     .ok_or(LaminarError::MathOverflow)?;
 
-  // - Solvent (CR >= 100%): user-favoring rounding (up, up), reserve debited
-  // - Insolvent (CR < 100%): conservative rounding (down, down), no reserve debit
-    let (sol_value_gross, lst_gross, reserve_debit_from_redeem) = if solvent_mode {
-    let sol_value_up = mul_div_up(asol_net_in, current_nav, SOL_PRECISION)
-      .ok_or(LaminarError::MathOverflow)?;
-    let lst_gross_up = mul_div_up(sol_value_up, SOL_PRECISION, lst_to_sol_rate)
-      .ok_or(LaminarError::MathOverflow)?;
-
-    let redeem_rounding_delta_lst = compute_rounding_delta_units(lst_gross_down, lst_gross_up)
-      .ok_or(LaminarError::MathOverflow)?;
-    let lamport_debit = lst_dust_to_lamports_up(redeem_rounding_delta_lst, lst_to_sol_rate)
-      .ok_or(LaminarError::MathOverflow)?;
-
-    if lamport_debit <= current_rounding_reserve {
-      (sol_value_up, lst_gross_up, lamport_debit)
-    } else {
-      msg!(
-        "Rounding reserve insufficient for user-favoring redeem rounding; fallback to conservative path"
-      );
-      (sol_value_down, lst_gross_down, 0u64)
-    }
-  } else {
-    (sol_value_down, lst_gross_down, 0u64)
-  };
+  msg!("aSOL input: {}", asol_amount);
+  msg!("aSOL fee (to treasury): {}", asol_treasury_fee);
+  msg!("aSOL fee (to insurance): {}", asol_insurance_fee);
+  msg!("aSOL net burn basis: {}", asol_net_in);
 
+  if final_settlement_exit {
+    msg!("Final settlement exit: sweeping {} LST", current_lst_amount);
+  }
+  msg!("LST gross to user: {}", lst_out);
 
-  msg!("SOL value (before fee): {}", sol_value_gross);
-  msg!("LST gross to user: {}", lst_gross);
+  // Closing out a position is exempt from the MIN_LST_DEPOSIT output
+  // floor; otherwise dust balances could never be redeemed.
+  let closes_position = asol_amount == source.token_account.amount;
 
-  let lst_out = lst_gross;
+  // The protocol minimum and the caller's slippage bound fail separately;
+  // min_lst_out = 0 sets no bound.
+  require!(closes_position || lst_out >= MIN_LST_DEPOSIT, LaminarError::RedeemOutputTooSmall);
   require!(lst_out >= min_lst_out, LaminarError::SlippageExceeded);
 
-  let total_lst_out = lst_out;
-
   // Calculate new state values
-  let new_lst_amount = current_lst_amount
-    .checked_sub(total_lst_out)
-    .ok_or(LaminarError::InsufficientCollateral)?;
+  let new_lst_amount = quote.new_total_lst_amount;
 
   require!(
-    new_lst_amount >= MIN_PROTOCOL_TVL || new_lst_amount == 0,
+    settled || new_lst_amount >= MIN_PROTOCOL_TVL || new_lst_amount == 0,
     LaminarError::BelowMinimumTVL
   );
 
-  let new_tvl = compute_tvl_sol(new_lst_amount, lst_to_sol_rate)
-    .ok_or(LaminarError::MathOverflow)?;
-
-  let new_asol_supply = current_asol_supply
-    .checked_sub(asol_net_in)
-    .ok_or(LaminarError::InsufficientSupply)?;
-
+  let new_asol_supply = quote.new_supply;
+  let new_rounding_reserve = quote.new_rounding_reserve;
+  let new_insurance_fund = quote.new_insurance_fund;
+  let after = quote_state.after(FeeAction::AsolRedeem, &quote);
+  let new_tvl = after.tvl().ok_or(LaminarError::MathOverflow)?;
   let new_liability = current_liability;  // aSOL redeem doesn't change liability
-  let new_rounding_reserve = debit_rounding_reserve(
-    current_rounding_reserve,
-    reserve_debit_from_redeem,
-  )?;
+  let new_non_claimable = after.non_claimable().ok_or(LaminarError::MathOverflow)?;
 
-  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
+  let new_accounting_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
-  let new_claimable_equity = compute_claimable_equity_sol(new_tvl, new_liability, new_rounding_reserve).ok_or(LaminarError::MathOverflow)?;
+  let new_claimable_equity = compute_claimable_equity_sol(new_tvl, new_liability, new_non_claimable).ok_or(LaminarError::MathOverflow)?;
 
   let new_cr_bps = if new_liability > 0 {
-    compute_cr_bps(new_tvl, new_liability)
+    after.cr_bps().ok_or(LaminarError::MathOverflow)?
   } else {
       u64::MAX
   };
 
-  assert_cr_above_minimum(new_cr_bps, min_cr_bps)?;
+  if !settled {
+    assert_cr_above_minimum(new_cr_bps, min_cr_bps)?;
+  }
 
   if new_cr_bps == u64::MAX {
     msg!("Post-redeem CR: inf (no amUSD liability)");
@@ -197,99 +397,111 @@ pub fn handler(
   let rounding_bound_lamports =
     derive_rounding_bound_lamports(2, 0, sol_price_used)?;
 
-  require!(
-    ctx.accounts.user_asol_account.amount >= asol_amount,
-    LaminarError::InsufficientSupply
-  );
-
   // Verify vault has enough funds
   require!(
-    ctx.accounts.vault.amount >= total_lst_out,
+    accounts.vault.amount >= lst_out,
     LaminarError::InsufficientCollateral
   );
 
   // Invariant checks
   assert_rounding_reserve_within_cap(new_rounding_reserve, max_rounding_reserve)?;
-  assert_balance_sheet_holds(new_tvl, new_liability, new_accounting_equity, new_rounding_reserve, rounding_bound_lamports)?;
+  assert_balance_sheet_holds(new_tvl, new_liability, new_accounting_equity, new_non_claimable, rounding_bound_lamports)?;
 
   // Update state BEFORE external calls
 
   {
-    let global_state = &mut ctx.accounts.global_state;
-    global_state.total_lst_amount = new_lst_amount;
+    let global_state = &mut accounts.global_state;
+    CollateralLeg::commit(global_state, accounts.collateral_vault.as_deref_mut(), new_lst_amount)?;
     global_state.asol_supply = new_asol_supply;
-    global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+    global_state.increment_operation_counter()?;
     global_state.rounding_reserve_lamports = new_rounding_reserve;
+    global_state.insurance_fund_lamports = new_insurance_fund;
+    let recovery = global_state.track_recovery_mode(new_cr_bps, accounts.clock.slot);
+    emit_recovery_transition(&accounts.event_cpi, recovery, new_cr_bps, min_cr_bps, global_state.operation_counter, accounts.clock)?;
     msg!("State updated: LST={}, aSOL={}", new_lst_amount, new_asol_supply);
+
+    // The reserve could not fund the user-favoring rounding; the payout rounded down.
+    if let Some(requested_debit) = quote.rounding_fallback_debit {
+      accounts.event_cpi.emit(RoundingFallback {
+        instruction: FeeAction::AsolRedeem as u8,
+        requested_debit,
+        reserve_available: new_rounding_reserve,
+        operation_counter: global_state.operation_counter,
+        timestamp: accounts.clock.unix_timestamp,
+      })?;
+    }
   }
 
   // External calls (CPIs)
 
   // Transfer fee to treasury
-  if asol_fee_in > 0 {
+  if asol_treasury_fee > 0 {
     let transfer_treasury_accounts = TransferChecked {
-      from: ctx.accounts.user_asol_account.to_account_info(),
-      mint: ctx.accounts.asol_mint.to_account_info(),
-      to: ctx.accounts.treasury_asol_account.to_account_info(),
-      authority: ctx.accounts.user.to_account_info(),
+      from: source.token_account.to_account_info(),
+      mint: accounts.asol_mint.to_account_info(),
+      to: accounts.treasury_asol_account.to_account_info(),
+      authority: source.authority.clone(),
     };
 
-    let cpi_ctx_fee = CpiContext::new(
-      ctx.accounts.token_program.to_account_info(),
+    let cpi_ctx_fee = CpiContext::new_with_signer(
+      accounts.token_program.to_account_info(),
       transfer_treasury_accounts,
+      source.signer_seeds,
     );
 
-    token_interface::transfer_checked(cpi_ctx_fee, asol_fee_in, ctx.accounts.asol_mint.decimals)?;
-    msg!("Transferred {} aSOL fee to treasury", asol_fee_in);
+    token_interface::transfer_checked(cpi_ctx_fee, asol_treasury_fee, accounts.asol_mint.decimals)?;
+    msg!("Transferred {} aSOL fee to treasury", asol_treasury_fee);
   }
 
   // Burn aSOL from user
   let burn_accounts = Burn {
-    mint: ctx.accounts.asol_mint.to_account_info(),
-    from: ctx.accounts.user_asol_account.to_account_info(),
-    authority: ctx.accounts.user.to_account_info(),
+    mint: accounts.asol_mint.to_account_info(),
+    from: source.token_account.to_account_info(),
+    authority: source.authority.clone(),
   };
 
-  let cpi_ctx_burn = CpiContext::new(
-    ctx.accounts.token_program.to_account_info(),
-    burn_accounts
+  let cpi_ctx_burn = CpiContext::new_with_signer(
+    accounts.token_program.to_account_info(),
+    burn_accounts,
+    source.signer_seeds,
   );
 
-  token_interface::burn(cpi_ctx_burn, asol_net_in)?;
-  msg!("Burned {} aSOL from user", asol_net_in);
+  token_interface::burn(cpi_ctx_burn, asol_burned)?;
+  msg!("Burned {} aSOL from user", asol_burned);
 
   // Transfer LST from vault to user
-  let seeds = &[VAULT_AUTHORITY_SEED, &[ctx.accounts.global_state.vault_authority_bump]];
+  let lst_mint_key = accounts.lst_mint.key();
+  let vault_authority_bump = [leg.vault_authority_bump];
+  let seeds = vault_authority_seeds(&lst_mint_key, &vault_authority_bump, accounts.collateral_vault.is_some());
   let signer = &[&seeds[..]];
 
   let transfer_user_accounts = TransferChecked {
-    from: ctx.accounts.vault.to_account_info(),
-    mint: ctx.accounts.lst_mint.to_account_info(),
-    to: ctx.accounts.user_lst_account.to_account_info(),
-    authority: ctx.accounts.vault_authority.to_account_info(),
+    from: accounts.vault.to_account_info(),
+    mint: accounts.lst_mint.to_account_info(),
+    to: accounts.recipient_lst_account.to_account_info(),
+    authority: accounts.vault_authority.to_account_info(),
   };
 
   let cpi_ctx_user = CpiContext::new_with_signer(
-    ctx.accounts.token_program.to_account_info(),
+    accounts.token_program.to_account_info(),
     transfer_user_accounts,
     signer
   );
 
-  token_interface::transfer_checked(cpi_ctx_user, lst_out, ctx.accounts.lst_mint.decimals)?;
-  msg!("Transferred {} LST to user", lst_out);
+  token_interface::transfer_checked(cpi_ctx_user, lst_out, accounts.lst_mint.decimals)?;
+  msg!("Transferred {} LST to {}", lst_out, accounts.recipient_lst_account.key());
 
 
-  ctx.accounts.asol_mint.reload()?;
-  ctx.accounts.vault.reload()?;
+  accounts.asol_mint.reload()?;
+  accounts.vault.reload()?;
 
-  let expected_vault_balance = ctx.accounts.global_state.total_lst_amount;
   require!(
-    ctx.accounts.vault.amount == expected_vault_balance,
+    accounts.vault.amount == expected_vault_balance(accounts.global_state, accounts.collateral_vault.as_deref()),
     LaminarError::BalanceSheetViolation
   );
 
   require!(
-    ctx.accounts.asol_mint.supply == ctx.accounts.global_state.asol_supply,
+    accounts.asol_mint.supply == accounts.global_state.asol_supply,
     LaminarError::BalanceSheetViolation
   );
 
@@ -297,22 +509,33 @@ pub fn handler(
   msg!("New TVL: {} lamports", new_tvl);
   msg!("New aSOL supply: {}", new_asol_supply);
 
-  emit!(AsolRedeemed {
-    user: ctx.accounts.user.key(),
-    asol_burned: asol_net_in,
+  accounts.event_cpi.emit(AsolRedeemed {
+    user: source.owner,
+    recipient: accounts.recipient_lst_account.key(),
+    asol_burned,
     lst_received: lst_out,
-    fee: asol_fee_in,
+    fee: asol_treasury_fee,
+    fee_bps,
+    cr_multiplier_bps,
+    uncertainty_multiplier_bps,
+    insurance_fee: asol_insurance_fee,
     nav: current_nav,
     old_tvl,
     new_tvl,
     old_equity: old_claimable_equity,
     new_equity: new_claimable_equity,
-    timestamp: ctx.accounts.clock.unix_timestamp,
-  });
+    fill_bps,
+    deadline_slot,
+    client_order_id,
+    entrypoint_version,
+    operation_counter: accounts.global_state.operation_counter,
+    timestamp: accounts.clock.unix_timestamp,
+  })?;
 
   Ok(())
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RedeemAsol<'info> {
   #[account(mut)]
@@ -336,11 +559,11 @@ pub struct RedeemAsol<'info> {
   )]
   pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
 
-  /// User's aSOL token account (source of burned aSOL)
+  /// Source of the burned aSOL; `user` signs as its owner or as an approved delegate
   #[account(
     mut,
     token::mint = asol_mint,
-    token::authority = user,
+    constraint = user_asol_account.owner == user.key() || user_asol_account.delegate == anchor_lang::solana_program::program_option::COption::Some(user.key()) @ LaminarError::InvalidSourceAuthority,
     constraint = user_asol_account.close_authority == anchor_lang::solana_program::program_option::COption::None @ LaminarError::InvalidAccountState,
   )]
   pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
@@ -377,17 +600,13 @@ pub struct RedeemAsol<'info> {
   )]
   pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-  /// Vault authority PDA - signs transfers from vault
-  /// CHECK: PDA validated by seeds
-  #[account(
-    seeds = [VAULT_AUTHORITY_SEED],
-    bump,
-  )]
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
   pub vault_authority: UncheckedAccount<'info>,
 
-  /// LST mint
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
   #[account(
-    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
   )]
   pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
 
@@ -396,4 +615,128 @@ pub struct RedeemAsol<'info> {
   pub system_program: Program<'info, System>,
 
   pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Source owner's last action slots; required while `min_action_gap_slots` or
+  /// `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user_asol_account.owner.as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+
+  /// Receives the redeemed LST instead of `user_lst_account`; any token
+  /// account of `lst_mint`, whoever owns it
+  #[account(
+    mut,
+    token::mint = lst_mint,
+  )]
+  pub recipient_lst_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// SPL stake pool of the primary LST; required only by redeem-to-SOL out of the primary LST
+  /// CHECK: address matched to the configured pool; owner and layout checked in `lst_rate::StakePoolWithdraw`
+  #[account(
+    mut,
+    constraint = stake_pool.key() == global_state.lst_stake_pool @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: the pool's withdraw authority PDA, checked by the stake pool program
+  pub stake_pool_withdraw_authority: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `reserve_stake` in `lst_rate::StakePoolWithdraw`
+  #[account(mut)]
+  pub reserve_stake: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: matched to the pool's `manager_fee_account` in `lst_rate::StakePoolWithdraw`
+  #[account(mut)]
+  pub manager_fee_account: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: StakeHistory sysvar, matched by address
+  #[account(address = anchor_lang::solana_program::sysvar::stake_history::ID)]
+  pub stake_history: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: native stake program, matched by address
+  #[account(address = STAKE_PROGRAM_ID)]
+  pub stake_program: Option<UncheckedAccount<'info>>,
+
+  /// CHECK: SPL stake pool program, matched by address
+  #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+  pub stake_pool_program: Option<UncheckedAccount<'info>>,
+
+  /// The primary LST mint again, writable for the pool to burn the payout
+  /// CHECK: matched to `lst_mint`; the stake pool checks it against its header
+  #[account(
+    mut,
+    constraint = stake_pool_mint.key() == lst_mint.key() @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool_mint: Option<UncheckedAccount<'info>>,
+
+  /// Temporary wSOL account redeem-to-SOL out of the wSOL leg unwraps through
+  /// CHECK: PDA; created, funded and closed within `collateral::unwrap_sol_payout`
+  #[account(mut, seeds = [SOL_UNWRAP_SEED, user.key().as_ref()], bump)]
+  pub sol_unwrap_account: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> RedeemAsol<'info> {
+  /// The stake pool's `WithdrawSol` accounts; all are required.
+  fn stake_pool_withdraw(&self) -> Result<StakePoolWithdraw<'info>> {
+    let required = |account: &Option<UncheckedAccount<'info>>| {
+      account
+        .as_ref()
+        .map(|account| account.to_account_info())
+        .ok_or(error!(LaminarError::InvalidStakePool))
+    };
+    Ok(StakePoolWithdraw {
+      stake_pool: required(&self.stake_pool)?,
+      withdraw_authority: required(&self.stake_pool_withdraw_authority)?,
+      reserve_stake: required(&self.reserve_stake)?,
+      manager_fee_account: required(&self.manager_fee_account)?,
+      pool_mint: required(&self.stake_pool_mint)?,
+      clock: self.clock.to_account_info(),
+      stake_history: required(&self.stake_history)?,
+      stake_program: required(&self.stake_program)?,
+      token_program: self.token_program.to_account_info(),
+      stake_pool_program: required(&self.stake_pool_program)?,
+    })
+  }
+  /// The accounts the wSOL leg's payout is unwrapped through.
+  fn sol_unwrap(&self, bump: Option<u8>) -> Result<SolUnwrap<'info>> {
+    let (Some(unwrap_account), Some(unwrap_bump)) = (&self.sol_unwrap_account, bump) else {
+      return err!(LaminarError::SolUnwrapAccountRequired);
+    };
+    Ok(SolUnwrap {
+      wsol_mint: self.lst_mint.to_account_info(),
+      wsol_decimals: self.lst_mint.decimals,
+      unwrap_account: unwrap_account.to_account_info(),
+      unwrap_bump,
+      token_program: self.token_program.to_account_info(),
+      system_program: self.system_program.to_account_info(),
+    })
+  }
+
 }
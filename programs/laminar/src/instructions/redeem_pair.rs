@@ -0,0 +1,346 @@
+//! Paired redemption instruction - removes debt and equity in one step
+//! User burns amUSD and aSOL together and receives the combined LST, with
+//! CR held within `pair_cr_tolerance_bps` of where it started
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::math::*;
+use crate::invariants::*;
+use crate::constants::ENTRYPOINT_PAIR;
+use crate::error::LaminarError;
+use crate::events::EventCpi;
+use crate::oracle::{resolve_redeem_price, PriceAccounts, PriceBias};
+use crate::collateral::CollateralLeg;
+use crate::position::{assert_asol_redeem_cooldown, record_position_redeem};
+use crate::redemption::RedeemSource;
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::instructions::accrue_stability_fee::accrue_stability_fee_to_treasury;
+use crate::instructions::redeem_amusd::{execute_redeem_amusd, RedeemAmusdAccounts, RedeemAmusdArgs};
+use crate::instructions::redeem_asol::{execute_redeem_asol, RedeemAsolAccounts, RedeemAsolArgs};
+
+/// Run the amUSD redeem path on `amusd_amount`, then the aSOL path on
+/// `asol_amount`, each with its own fee and checks. An `asol_amount` of 0
+/// sizes the aSOL leg with `compute_cr_neutral_redeem` from the book the
+/// amUSD leg left. `min_lst_out` bounds the two payouts together.
+pub fn handler(
+  ctx: Context<RedeemPair>,
+  amusd_amount: u64,
+  asol_amount: u64,
+  min_lst_out: u64,
+) -> Result<()> {
+  assert_not_cpi_context()?;
+  // A settled book pays both tranches at frozen prices; there is no CR to hold.
+  require!(!ctx.accounts.global_state.settled, LaminarError::ProtocolSettled);
+
+  assert_asol_redeem_cooldown(&ctx.accounts.global_state, ctx.accounts.user_position.as_deref(), ctx.accounts.clock.slot)?;
+  record_position_redeem(
+    &ctx.accounts.global_state,
+    ctx.accounts.user_position.as_deref_mut(),
+    ctx.accounts.user.key(),
+    ctx.bumps.user_position,
+    ctx.accounts.clock.slot,
+  )?;
+
+  let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+  let accounts = ctx.accounts;
+
+  // Bring the debt up to date before measuring CR, so the sizing and the
+  // drift check see the liability both legs will.
+  accounts.global_state.validate_version()?;
+  sync_exchange_rate_in_place(&mut accounts.global_state, accounts.clock.slot)?;
+  accrue_stability_fee_to_treasury(
+    &mut accounts.global_state,
+    &accounts.amusd_mint,
+    &accounts.treasury_amusd_account,
+    &accounts.token_program,
+    &accounts.clock,
+    &event_cpi,
+  )?;
+
+  let before = PairBook::read(accounts)?;
+  let lst_before = accounts.user_lst_account.amount;
+
+  // Debt first: the amUSD leg lifts CR, so the aSOL leg never runs with CR
+  // below where the pair started.
+  execute_redeem_amusd(
+    RedeemAmusdAccounts {
+      global_state: &mut accounts.global_state,
+      amusd_mint: &mut accounts.amusd_mint,
+      treasury_amusd_account: &accounts.treasury_amusd_account,
+      recipient_lst_account: &accounts.user_lst_account,
+      vault: &mut accounts.vault,
+      vault_authority: &accounts.vault_authority,
+      lst_mint: &accounts.lst_mint,
+      token_program: &accounts.token_program,
+      clock: &accounts.clock,
+      price_update: accounts.price_update.as_deref(),
+      switchboard_feed: accounts.switchboard_feed.as_deref(),
+      collateral_vault: accounts.collateral_vault.as_deref_mut(),
+      savings_pool: accounts.savings_pool.as_deref_mut(),
+      savings_amusd_vault: accounts.savings_amusd_vault.as_deref_mut(),
+      event_cpi: event_cpi.clone(),
+    },
+    RedeemSource {
+      owner: accounts.user.key(),
+      token_account: &accounts.user_amusd_account,
+      authority: accounts.user.to_account_info(),
+      signer_seeds: &[],
+    },
+    RedeemAmusdArgs::v1(amusd_amount, 0),
+    ENTRYPOINT_PAIR,
+  )?;
+
+  let asol_amount = if asol_amount == 0 {
+    let mid = PairBook::read(accounts)?;
+    let sized = compute_cr_neutral_redeem(
+      before.weighted_tvl,
+      before.liability,
+      mid.weighted_tvl,
+      mid.liability,
+      mid.collateral_weight_bps,
+      mid.asol_nav,
+    )
+    .ok_or(LaminarError::MathOverflow)?;
+    msg!("Pair aSOL leg sized to {}", sized);
+    sized
+  } else {
+    asol_amount
+  };
+
+  if asol_amount > 0 {
+    execute_redeem_asol(
+      RedeemAsolAccounts {
+        global_state: &mut accounts.global_state,
+        asol_mint: &mut accounts.asol_mint,
+        treasury_asol_account: &accounts.treasury_asol_account,
+        recipient_lst_account: &accounts.user_lst_account,
+        vault: &mut accounts.vault,
+        vault_authority: &accounts.vault_authority,
+        lst_mint: &accounts.lst_mint,
+        token_program: &accounts.token_program,
+        clock: &accounts.clock,
+        price_update: accounts.price_update.as_deref(),
+        switchboard_feed: accounts.switchboard_feed.as_deref(),
+        collateral_vault: accounts.collateral_vault.as_deref_mut(),
+        event_cpi,
+      },
+      RedeemSource {
+        owner: accounts.user.key(),
+        token_account: &accounts.user_asol_account,
+        authority: accounts.user.to_account_info(),
+        signer_seeds: &[],
+      },
+      RedeemAsolArgs::v1(asol_amount, 0),
+      ENTRYPOINT_PAIR,
+    )?;
+  }
+
+  let after = PairBook::read(accounts)?;
+  msg!("Pair CR: {}bps -> {}bps", before.cr_bps, after.cr_bps);
+  assert_cr_drift_within(before.cr_bps, after.cr_bps, accounts.global_state.pair_cr_tolerance_bps)?;
+
+  accounts.user_lst_account.reload()?;
+  let lst_out = accounts.user_lst_account.amount.saturating_sub(lst_before);
+  require!(lst_out >= min_lst_out, LaminarError::SlippageExceeded);
+
+  Ok(())
+}
+
+/// The whole book as the aSOL leg prices it
+struct PairBook {
+  cr_bps: u64,
+  weighted_tvl: u64,
+  liability: u64,
+  collateral_weight_bps: u64,
+  /// 0 while there is no aSOL supply
+  asol_nav: u64,
+}
+
+impl PairBook {
+  fn read(accounts: &RedeemPair) -> Result<Self> {
+    let global_state = &accounts.global_state;
+    let oracle_price = resolve_redeem_price(
+      &PriceAccounts {
+        price_update: accounts.price_update.as_deref(),
+        switchboard_feed: accounts.switchboard_feed.as_deref(),
+      },
+      global_state,
+      accounts.clock.slot,
+      accounts.clock.unix_timestamp,
+      PriceBias::Low,
+    )?;
+    let leg = CollateralLeg::resolve(global_state, accounts.collateral_vault.as_deref(), global_state.mock_lst_to_sol_rate)?;
+
+    let tvl = leg.tvl(leg.lst_amount)?;
+    let weighted_tvl = leg.weighted_tvl(leg.lst_amount)?;
+    let liability = compute_liability_at_rate_sol(global_state.amusd_supply, oracle_price.sol_price_usd, global_state.redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?;
+    let non_claimable = compute_non_claimable_sol(global_state.rounding_reserve_lamports, global_state.insurance_fund_lamports)
+      .ok_or(LaminarError::MathOverflow)?;
+    let asol_nav = nav_asol_with_reserve(tvl, liability, non_claimable, global_state.asol_supply).unwrap_or(0);
+
+    Ok(Self {
+      cr_bps: compute_cr_bps(weighted_tvl, liability),
+      weighted_tvl,
+      liability,
+      collateral_weight_bps: leg.collateral_weight_bps,
+      asol_nav,
+    })
+  }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemPair<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    has_one = asol_mint,
+    has_one = treasury,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// amUSD mint
+  #[account(
+    mut,
+    constraint = amusd_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// aSOL mint
+  #[account(
+    mut,
+    constraint = asol_mint.mint_authority == COption::Some(global_state.key()) @ LaminarError::InvalidMintAuthority,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's amUSD token account (source of the burned amUSD)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's aSOL token account (source of the burned aSOL)
+  #[account(
+    mut,
+    token::mint = asol_mint,
+    token::authority = user,
+    constraint = user_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's amUSD token account (receives the amUSD leg's fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Treasury's aSOL token account (receives the aSOL leg's fee)
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = asol_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  /// User's LST token account (receives both payouts)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+    constraint = user_lst_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (source of LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: [VAULT_AUTHORITY_SEED] PDA, or [VAULT_AUTHORITY_SEED, lst_mint] with a
+  /// collateral_vault; re-derived from stored bumps in `validate_leg_accounts`
+  pub vault_authority: UncheckedAccount<'info>,
+
+  /// LST mint: the primary LST, or any mint whose CollateralVault is passed
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint || collateral_vault.is_some() @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+  /// CollateralVault of `lst_mint`; omitted for the primary LST
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.key().as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Option<Box<Account<'info, CollateralVault>>>,
+
+  /// Savings Pool; required only while `global_state.savings_fee_split_bps` is set
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Option<Box<Account<'info, SavingsPool>>>,
+
+  /// Savings Pool amUSD vault (receives its fee share); matched to the pool in `route_savings_fee`
+  #[account(mut)]
+  pub savings_amusd_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+  /// Caller's last action slots; required while `min_action_gap_slots` or
+  /// `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+}
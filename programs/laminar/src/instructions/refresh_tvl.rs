@@ -0,0 +1,76 @@
+//! refresh_tvl instruction - rebuild the cached collateral totals (permissionless)
+//! Handlers value the other vaults from `collateral_tvl_lamports` and its
+//! weighted and deprecated companions rather than loading every vault. This
+//! crank re-sums them from the `CollateralVault` accounts, passed as remaining
+//! accounts: all `collateral_type_count` of them, in ascending key order so
+//! none can be counted twice.
+
+use anchor_lang::prelude::*;
+
+use crate::collateral::CollateralTotals;
+use crate::error::LaminarError;
+use crate::events::{emit_event, TvlRefreshed};
+use crate::state::*;
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshTvl<'info>>) -> Result<()> {
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(
+    ctx.remaining_accounts.len() as u64 == global_state.collateral_type_count,
+    LaminarError::CollateralVaultsIncomplete
+  );
+
+  let mut totals = CollateralTotals::default();
+  let mut previous_key: Option<Pubkey> = None;
+  for account_info in ctx.remaining_accounts {
+    require!(
+      previous_key.is_none_or(|previous_key| previous_key < *account_info.key),
+      LaminarError::CollateralVaultsIncomplete
+    );
+    // Checks the owner and discriminator; only add_collateral_type creates these.
+    let collateral_vault = Account::<CollateralVault>::try_from(account_info)?;
+    totals.add_vault(&collateral_vault)?;
+    previous_key = Some(*account_info.key);
+  }
+
+  let global_state = &mut ctx.accounts.global_state;
+  let old_totals = CollateralTotals::cached(global_state);
+  totals.store(global_state);
+  global_state.increment_operation_counter()?;
+
+  emit_event!(TvlRefreshed {
+    caller: ctx.accounts.caller.key(),
+    vault_count: global_state.collateral_type_count,
+    old_collateral_tvl_lamports: old_totals.tvl_lamports,
+    new_collateral_tvl_lamports: totals.tvl_lamports,
+    weighted_collateral_tvl_lamports: totals.weighted_tvl_lamports,
+    deprecated_collateral_tvl_lamports: totals.deprecated_tvl_lamports,
+    slot: ctx.accounts.clock.slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "TVL refreshed: {} vaults, collateral {} -> {} lamports",
+    global_state.collateral_type_count,
+    old_totals.tvl_lamports,
+    totals.tvl_lamports
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefreshTvl<'info> {
+  pub caller: Signer<'info>,
+
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,162 @@
+//! request_redemption instruction - queue an amUSD or aSOL redemption
+//! The tokens move into an escrow ATA owned by a new RedemptionTicket, and
+//! process_redemption later redeems them at the prices of the slot it runs
+//! in. Requests are accepted while redemptions are paused.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, RedemptionRequested};
+use crate::invariants::*;
+use crate::math::*;
+use crate::position::{assert_asol_redeem_cooldown, record_position_redeem};
+use crate::state::*;
+
+pub fn handler(ctx: Context<RequestRedemption>, amount: u64, min_lst_out: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  require!(amount > 0, LaminarError::ZeroAmount);
+  require!(
+    compute_cap_headroom(amount, ctx.accounts.global_state.max_single_redeem_units).is_some(),
+    LaminarError::RedeemTooLarge
+  );
+  require!(
+    ctx.accounts.user_token_account.amount >= amount,
+    LaminarError::InsufficientSupply
+  );
+
+  // The wallet's limits bind when it asks; processing is permissionless.
+  if ctx.accounts.token_mint.key() == ctx.accounts.global_state.asol_mint {
+    assert_asol_redeem_cooldown(&ctx.accounts.global_state, ctx.accounts.user_position.as_deref(), ctx.accounts.clock.slot)?;
+  }
+  record_position_redeem(
+    &ctx.accounts.global_state,
+    ctx.accounts.user_position.as_deref_mut(),
+    ctx.accounts.user.key(),
+    ctx.bumps.user_position,
+    ctx.accounts.clock.slot,
+  )?;
+
+  // State update
+  let sequence = ctx.accounts.global_state.next_redemption_ticket;
+  {
+    let ticket = &mut ctx.accounts.redemption_ticket;
+    ticket.owner = ctx.accounts.user.key();
+    ticket.token_mint = ctx.accounts.token_mint.key();
+    ticket.sequence = sequence;
+    ticket.amount = amount;
+    ticket.min_lst_out = min_lst_out;
+    ticket.request_slot = ctx.accounts.clock.slot;
+    ticket.bump = ctx.bumps.redemption_ticket;
+    ticket._reserved = [0; 2];
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.next_redemption_ticket = sequence
+      .checked_add(1)
+      .ok_or(LaminarError::MathOverflow)?;
+    global_state.increment_operation_counter()?;
+  }
+
+  let transfer_accounts = TransferChecked {
+    from: ctx.accounts.user_token_account.to_account_info(),
+    mint: ctx.accounts.token_mint.to_account_info(),
+    to: ctx.accounts.escrow.to_account_info(),
+    authority: ctx.accounts.user.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_accounts);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+  ctx.accounts.escrow.reload()?;
+  require!(
+    ctx.accounts.escrow.amount == amount,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Queued redemption #{}: {} tokens of {}", sequence, amount, ctx.accounts.token_mint.key());
+
+  emit_event!(RedemptionRequested {
+    owner: ctx.accounts.user.key(),
+    ticket: ctx.accounts.redemption_ticket.key(),
+    sequence,
+    token_mint: ctx.accounts.token_mint.key(),
+    amount,
+    min_lst_out,
+    request_slot: ctx.accounts.clock.slot,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// amUSD or aSOL mint
+  #[account(
+    constraint = token_mint.key() == global_state.amusd_mint || token_mint.key() == global_state.asol_mint @ LaminarError::InvalidMint,
+  )]
+  pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// User's token account (source of the escrowed tokens)
+  #[account(
+    mut,
+    token::mint = token_mint,
+    token::authority = user,
+    constraint = user_token_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// The new ticket, issued under the next sequence number
+  #[account(
+    init,
+    payer = user,
+    space = RedemptionTicket::LEN,
+    seeds = [REDEMPTION_TICKET_SEED, global_state.next_redemption_ticket.to_le_bytes().as_ref()],
+    bump
+  )]
+  pub redemption_ticket: Box<Account<'info, RedemptionTicket>>,
+
+  /// Escrow - deterministic ATA owned by the ticket PDA
+  #[account(
+    init,
+    payer = user,
+    associated_token::mint = token_mint,
+    associated_token::authority = redemption_ticket,
+    associated_token::token_program = token_program,
+  )]
+  pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Caller's last action slots; required while `min_action_gap_slots` or
+  /// `asol_redeem_cooldown_slots` is set, created on first use
+  #[account(
+    init_if_needed,
+    payer = user,
+    space = UserPosition::LEN,
+    seeds = [USER_POSITION_SEED, user.key().as_ref()],
+    bump
+  )]
+  pub user_position: Option<Box<Account<'info, UserPosition>>>,
+}
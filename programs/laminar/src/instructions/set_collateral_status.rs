@@ -0,0 +1,94 @@
+//! set_collateral_status instruction - wind a collateral type down (admin only)
+//! `DepositsDisabled` refuses new mints against the LST while its vault keeps
+//! paying redemptions. `Deprecated` also drops the vault from the CR the fee
+//! curve reads; it still counts toward TVL until drained. Either step can be
+//! reversed back to `Active`.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, CollateralStatusUpdated};
+use crate::invariants::assert_param_change_unlocked;
+use crate::state::*;
+
+pub fn handler(ctx: Context<SetCollateralStatus>, lst_mint: Pubkey, new_status: u8) -> Result<()> {
+  let status = CollateralStatus::try_from(new_status)?;
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Winding a collateral further down only reduces risk, so it skips the timelock.
+  if new_status <= ctx.accounts.collateral_vault.status {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::VaultStatus { lst_mint, status: new_status },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let collateral_vault = &mut ctx.accounts.collateral_vault;
+  let old_status = collateral_vault.status;
+  let was_deprecated = collateral_vault.is_deprecated();
+  let vault_tvl = collateral_vault.weighted_tvl_sol()?;
+  collateral_vault.status = status as u8;
+
+  // Deprecated vaults are tracked separately so the fee CR can leave them out.
+  match (was_deprecated, collateral_vault.is_deprecated()) {
+    (false, true) => {
+      global_state.deprecated_collateral_tvl_lamports = global_state.deprecated_collateral_tvl_lamports
+        .checked_add(vault_tvl)
+        .ok_or(LaminarError::MathOverflow)?;
+    }
+    (true, false) => {
+      global_state.deprecated_collateral_tvl_lamports = global_state.deprecated_collateral_tvl_lamports
+        .checked_sub(vault_tvl)
+        .ok_or(LaminarError::MathOverflow)?;
+    }
+    _ => {}
+  }
+  global_state.increment_operation_counter()?;
+
+  emit_event!(CollateralStatusUpdated {
+    authority: ctx.accounts.authority.key(),
+    lst_mint,
+    old_status,
+    new_status,
+    collateral_tvl_lamports: global_state.collateral_tvl_lamports,
+    deprecated_collateral_tvl_lamports: global_state.deprecated_collateral_tvl_lamports,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Collateral status updated: {} {} -> {}", lst_mint, old_status, new_status);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(lst_mint: Pubkey)]
+pub struct SetCollateralStatus<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  #[account(
+    mut,
+    seeds = [VAULT_SEED, lst_mint.as_ref()],
+    bump = collateral_vault.bump,
+  )]
+  pub collateral_vault: Box<Account<'info, CollateralVault>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,93 @@
+//! set_fallback_price_source instruction - admin configuration of the stale-primary fallback
+//! While the primary (or its cross-check) is stale, redemptions price from the
+//! fallback alone instead of failing, paying `fallback_uncertainty_bps` on top
+//! of the uncertainty index. Mints still fail on stale data. `None` disables it.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  constants::{MAX_FALLBACK_UNCERTAINTY_BPS, NO_FALLBACK_PRICE_SOURCE},
+  error::LaminarError,
+  events::{emit_event, FallbackPriceSourceUpdated},
+  invariants::assert_param_change_unlocked,
+  oracle::PriceSource,
+  state::*,
+};
+
+pub fn handler(
+  ctx: Context<SetFallbackPriceSource>,
+  new_fallback_price_source: Option<u8>,
+  new_fallback_uncertainty_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::FallbackPriceSource {
+      fallback_price_source: new_fallback_price_source,
+      fallback_uncertainty_bps: new_fallback_uncertainty_bps,
+    },
+    ctx.accounts.clock.slot,
+  )?;
+
+  if let Some(fallback) = new_fallback_price_source {
+    let source = PriceSource::try_from(fallback)?;
+    // StakePool reads the same admin-set price as Mock.
+    require!(source != PriceSource::StakePool, LaminarError::InvalidParameter);
+    require!(source.is_configured(global_state), LaminarError::InvalidPriceFeed);
+    require!(fallback != global_state.price_source, LaminarError::InvalidParameter);
+  }
+  require!(
+    new_fallback_uncertainty_bps <= MAX_FALLBACK_UNCERTAINTY_BPS,
+    LaminarError::InvalidParameter
+  );
+
+  let old_fallback_price_source = global_state.fallback_source();
+  let old_fallback_uncertainty_bps = global_state.fallback_uncertainty_bps;
+
+  global_state.fallback_price_source = new_fallback_price_source.unwrap_or(NO_FALLBACK_PRICE_SOURCE);
+  global_state.fallback_uncertainty_bps = new_fallback_uncertainty_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(FallbackPriceSourceUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_fallback_price_source,
+    new_fallback_price_source,
+    old_fallback_uncertainty_bps,
+    new_fallback_uncertainty_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Fallback price source updated: {:?} -> {:?} (surcharge {} -> {} bps)",
+    old_fallback_price_source,
+    new_fallback_price_source,
+    old_fallback_uncertainty_bps,
+    new_fallback_uncertainty_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFallbackPriceSource<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,70 @@
+//! set_haircut_breaker instruction - admin bound on per-episode haircut losses
+//! Also the governance acknowledgement that resumes amUSD redemption after the
+//! breaker tripped. Zero disables the breaker.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, HaircutBreakerUpdated}, invariants::{assert_param_change_unlocked, is_cap_reduction}, state::*};
+
+pub fn handler(ctx: Context<SetHaircutBreaker>, new_max_episode_haircut_lamports: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Arming or tightening an untripped breaker skips the timelock; resuming a
+  // tripped redeem does not.
+  if !is_cap_reduction(global_state.max_episode_haircut_lamports, new_max_episode_haircut_lamports)
+    || global_state.amusd_redeem_paused_by_haircut
+  {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::HaircutBreaker { max_episode_haircut_lamports: new_max_episode_haircut_lamports },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let old_max_episode_haircut_lamports = global_state.max_episode_haircut_lamports;
+  let redeem_resumed = global_state.amusd_redeem_paused_by_haircut;
+
+  global_state.max_episode_haircut_lamports = new_max_episode_haircut_lamports;
+  global_state.amusd_redeem_paused_by_haircut = false;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(HaircutBreakerUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_episode_haircut_lamports,
+    new_max_episode_haircut_lamports,
+    redeem_resumed,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Haircut breaker updated: {} -> {} lamports (redeem resumed: {})",
+    old_max_episode_haircut_lamports,
+    new_max_episode_haircut_lamports,
+    redeem_resumed
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetHaircutBreaker<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,84 @@
+//! set_lst_calculator instruction - admin selection of a Sanctum calculator rate source
+//! Only programs in `lst_rate::SANCTUM_CALCULATOR_PROGRAMS` are accepted.
+//! Exclusive with the other LST rate sources; the default program id switches
+//! back to the admin-set mock rate.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, LstCalculatorUpdated}, invariants::assert_param_change_unlocked, lst_rate::is_known_calculator, state::*};
+
+pub fn handler(
+  ctx: Context<SetLstCalculator>,
+  new_lst_calculator_program: Pubkey,
+  new_lst_calculator_state: Pubkey,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::LstCalculator {
+      lst_calculator_program: new_lst_calculator_program,
+      lst_calculator_state: new_lst_calculator_state,
+    },
+    ctx.accounts.clock.slot,
+  )?;
+
+  if new_lst_calculator_program == Pubkey::default() {
+    require!(new_lst_calculator_state == Pubkey::default(), LaminarError::InvalidParameter);
+  } else {
+    require!(is_known_calculator(&new_lst_calculator_program), LaminarError::UnknownLstCalculator);
+    require!(new_lst_calculator_state != Pubkey::default(), LaminarError::InvalidLstCalculatorState);
+    require!(
+      global_state.lst_stake_pool == Pubkey::default() && global_state.marinade_state == Pubkey::default(),
+      LaminarError::InvalidParameter
+    );
+  }
+
+  let old_lst_calculator_program = global_state.lst_calculator_program;
+  let old_lst_calculator_state = global_state.lst_calculator_state;
+
+  global_state.lst_calculator_program = new_lst_calculator_program;
+  global_state.lst_calculator_state = new_lst_calculator_state;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(LstCalculatorUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_lst_calculator_program,
+    new_lst_calculator_program,
+    old_lst_calculator_state,
+    new_lst_calculator_state,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "LST calculator updated: {} -> {} (state {})",
+    old_lst_calculator_program,
+    new_lst_calculator_program,
+    new_lst_calculator_state
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLstCalculator<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,66 @@
+//! set_lst_stake_pool instruction - admin selection of the LST exchange-rate source
+//! Exclusive with the other LST rate sources; the default pubkey switches back to the
+//! admin-set mock rate.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, LstStakePoolUpdated}, invariants::assert_param_change_unlocked, oracle::PriceSource, state::*};
+
+pub fn handler(ctx: Context<SetLstStakePool>, new_lst_stake_pool: Pubkey) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::LstStakePool { lst_stake_pool: new_lst_stake_pool },
+    ctx.accounts.clock.slot,
+  )?;
+  require!(
+    new_lst_stake_pool == Pubkey::default()
+      || (global_state.marinade_state == Pubkey::default() && global_state.lst_calculator_program == Pubkey::default()),
+    LaminarError::InvalidParameter
+  );
+  // The `StakePool` price source is only valid while a pool is configured.
+  require!(
+    new_lst_stake_pool != Pubkey::default() || global_state.price_source != PriceSource::StakePool as u8,
+    LaminarError::InvalidParameter
+  );
+
+  let old_lst_stake_pool = global_state.lst_stake_pool;
+
+  global_state.lst_stake_pool = new_lst_stake_pool;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(LstStakePoolUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_lst_stake_pool,
+    new_lst_stake_pool,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("LST stake pool updated: {} -> {}", old_lst_stake_pool, new_lst_stake_pool);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLstStakePool<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,61 @@
+//! set_marinade_state instruction - admin selection of the Marinade mSOL rate source
+//! Exclusive with the other LST rate sources; the default pubkey switches back to the
+//! admin-set mock rate.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, MarinadeStateUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(ctx: Context<SetMarinadeState>, new_marinade_state: Pubkey) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MarinadeState { marinade_state: new_marinade_state },
+    ctx.accounts.clock.slot,
+  )?;
+  require!(
+    new_marinade_state == Pubkey::default()
+      || (global_state.lst_stake_pool == Pubkey::default() && global_state.lst_calculator_program == Pubkey::default()),
+    LaminarError::InvalidParameter
+  );
+
+  let old_marinade_state = global_state.marinade_state;
+
+  global_state.marinade_state = new_marinade_state;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MarinadeStateUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_marinade_state,
+    new_marinade_state,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Marinade state updated: {} -> {}", old_marinade_state, new_marinade_state);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMarinadeState<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,72 @@
+//! set_price_source instruction - admin selection of the SOL/USD price source
+//! The selected source's feed must already be configured. `Mock` is only
+//! selectable once the Pyth and Switchboard feeds are cleared, so a configured
+//! feed is never silently ignored. The cross-checked secondary feed cannot
+//! also become the primary.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, PriceSourceUpdated}, invariants::assert_param_change_unlocked, oracle::PriceSource, state::*};
+
+pub fn handler(ctx: Context<SetPriceSource>, new_price_source: u8) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::PrimaryPriceSource { price_source: new_price_source },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let source = PriceSource::try_from(new_price_source)?;
+  require!(source.is_configured(global_state), LaminarError::InvalidPriceFeed);
+  require!(
+    source != PriceSource::Mock
+      || (global_state.pyth_price_feed == Pubkey::default() && global_state.switchboard_feed == Pubkey::default()),
+    LaminarError::InvalidParameter
+  );
+  require!(
+    global_state.secondary_price_source == PriceSource::Mock as u8
+      || new_price_source != global_state.secondary_price_source,
+    LaminarError::InvalidParameter
+  );
+  require!(global_state.fallback_price_source != new_price_source, LaminarError::InvalidParameter);
+
+  let old_price_source = global_state.price_source;
+
+  global_state.price_source = new_price_source;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PriceSourceUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_price_source,
+    new_price_source,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Price source updated: {} -> {}", old_price_source, new_price_source);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPriceSource<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,68 @@
+//! set_pyth_price_feed instruction - admin configuration of the SOL/USD Pyth feed
+//! Configuring a feed while pricing from the mock selects it; the default
+//! pubkey switches pricing back to the mock when Pyth is the live source.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, PythPriceFeedUpdated}, oracle::PriceSource, state::*};
+
+pub fn handler(ctx: Context<SetPythPriceFeed>, new_pyth_price_feed: Pubkey) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(global_state.param_timelock_slots == 0, LaminarError::TimelockActive);
+
+  let old_pyth_price_feed = global_state.pyth_price_feed;
+
+  global_state.pyth_price_feed = new_pyth_price_feed;
+  if new_pyth_price_feed != Pubkey::default() {
+    if global_state.price_source == PriceSource::Mock as u8 {
+      global_state.price_source = PriceSource::Pyth as u8;
+    }
+  } else {
+    // The cross-check and fallback must be switched off before their feed is cleared.
+    require!(
+      global_state.secondary_price_source != PriceSource::Pyth as u8,
+      LaminarError::InvalidParameter
+    );
+    require!(
+      global_state.fallback_price_source != PriceSource::Pyth as u8,
+      LaminarError::InvalidParameter
+    );
+    if global_state.price_source == PriceSource::Pyth as u8 {
+      // Clearing the live feed only falls back to the mock; with the other
+      // feed configured the admin must select it first.
+      require!(global_state.switchboard_feed == Pubkey::default(), LaminarError::InvalidParameter);
+      global_state.price_source = PriceSource::Mock as u8;
+    }
+  }
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PythPriceFeedUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_pyth_price_feed,
+    new_pyth_price_feed,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Pyth price feed updated: {} -> {}", old_pyth_price_feed, new_pyth_price_feed);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPythPriceFeed<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
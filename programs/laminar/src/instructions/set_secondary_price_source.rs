@@ -0,0 +1,89 @@
+//! set_secondary_price_source instruction - admin configuration of the oracle cross-check
+//! The secondary must be a configured Pyth or Switchboard feed other than the
+//! primary; `Mock` turns the cross-check off. `max_oracle_deviation_bps` bounds
+//! how far the two prices may drift apart before pricing actions fail.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, SecondaryPriceSourceUpdated}, invariants::assert_param_change_unlocked, math::BPS_PRECISION, oracle::PriceSource, state::*};
+
+pub fn handler(
+  ctx: Context<SetSecondaryPriceSource>,
+  new_secondary_price_source: u8,
+  new_max_oracle_deviation_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::SecondaryPriceSource {
+      secondary_price_source: new_secondary_price_source,
+      max_oracle_deviation_bps: new_max_oracle_deviation_bps,
+    },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let source = PriceSource::try_from(new_secondary_price_source)?;
+  if source != PriceSource::Mock {
+    // Only the independent feeds; StakePool reads the same admin-set price as Mock.
+    require!(
+      source == PriceSource::Pyth || source == PriceSource::Switchboard,
+      LaminarError::InvalidParameter
+    );
+    require!(source.is_configured(global_state), LaminarError::InvalidPriceFeed);
+    require!(new_secondary_price_source != global_state.price_source, LaminarError::InvalidParameter);
+  }
+  require!(
+    new_max_oracle_deviation_bps > 0 && new_max_oracle_deviation_bps <= BPS_PRECISION,
+    LaminarError::InvalidParameter
+  );
+
+  let old_secondary_price_source = global_state.secondary_price_source;
+  let old_max_oracle_deviation_bps = global_state.max_oracle_deviation_bps;
+
+  global_state.secondary_price_source = new_secondary_price_source;
+  global_state.max_oracle_deviation_bps = new_max_oracle_deviation_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(SecondaryPriceSourceUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_secondary_price_source,
+    new_secondary_price_source,
+    old_max_oracle_deviation_bps,
+    new_max_oracle_deviation_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Secondary price source updated: {} -> {} (max deviation {} -> {} bps)",
+    old_secondary_price_source,
+    new_secondary_price_source,
+    old_max_oracle_deviation_bps,
+    new_max_oracle_deviation_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSecondaryPriceSource<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,77 @@
+//! set_switchboard_feed instruction - admin configuration of the SOL/USD Switchboard feed
+//! Configuring a feed while pricing from the mock selects it; the default
+//! pubkey switches pricing back to the mock when Switchboard is the live source.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, SwitchboardFeedUpdated}, invariants::assert_param_change_unlocked, oracle::PriceSource, state::*};
+
+pub fn handler(ctx: Context<SetSwitchboardFeed>, new_switchboard_feed: Pubkey) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::SwitchboardFeed { switchboard_feed: new_switchboard_feed },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let old_switchboard_feed = global_state.switchboard_feed;
+
+  global_state.switchboard_feed = new_switchboard_feed;
+  if new_switchboard_feed != Pubkey::default() {
+    if global_state.price_source == PriceSource::Mock as u8 {
+      global_state.price_source = PriceSource::Switchboard as u8;
+    }
+  } else {
+    // The cross-check and fallback must be switched off before their feed is cleared.
+    require!(
+      global_state.secondary_price_source != PriceSource::Switchboard as u8,
+      LaminarError::InvalidParameter
+    );
+    require!(
+      global_state.fallback_price_source != PriceSource::Switchboard as u8,
+      LaminarError::InvalidParameter
+    );
+    if global_state.price_source == PriceSource::Switchboard as u8 {
+      // Clearing the live feed only falls back to the mock; with the other
+      // feed configured the admin must select it first.
+      require!(global_state.pyth_price_feed == Pubkey::default(), LaminarError::InvalidParameter);
+      global_state.price_source = PriceSource::Mock as u8;
+    }
+  }
+  global_state.increment_operation_counter()?;
+
+  emit_event!(SwitchboardFeedUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_switchboard_feed,
+    new_switchboard_feed,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Switchboard feed updated: {} -> {}", old_switchboard_feed, new_switchboard_feed);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSwitchboardFeed<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,172 @@
+//! set_treasury instruction - rotates the fee treasury (admin only)
+//! Fee ATAs are derived with `associated_token::authority = treasury`, so the
+//! treasury key is classified before it is accepted: either a system wallet
+//! that co-signs, or a program-owned address the admin explicitly acknowledges.
+//! The new treasury's amUSD and aSOL ATAs are created here (if missing) so the
+//! next mint/redeem does not depend on a user paying for them.
+
+use anchor_lang::prelude::*;
+use anchor_lang::prelude::program_option::COption;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token,
+  token_2022,
+  token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{error::LaminarError, events::{emit_event, TreasuryUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+/// Classify a proposed treasury account.
+///
+/// # Arguments
+/// * `treasury` - Proposed treasury account
+/// * `treasury_is_pda` - Admin acknowledgment that the address is program-controlled
+/// * `treasury_owner_program` - Program that controls the address (PDA case only)
+///
+/// # Returns
+/// Owner program recorded in GlobalState (default pubkey for wallets)
+pub fn classify_treasury(
+  treasury: &AccountInfo,
+  treasury_is_pda: bool,
+  treasury_owner_program: Pubkey,
+) -> Result<Pubkey> {
+  // Token accounts can never own fee ATAs meaningfully.
+  require!(
+    treasury.owner != &token::ID && treasury.owner != &token_2022::ID,
+    LaminarError::InvalidTreasury
+  );
+
+  if !treasury_is_pda {
+    // Co-signature proves the key has a private key (on-curve wallet).
+    require!(treasury.is_signer, LaminarError::InvalidTreasury);
+    require!(
+      treasury.owner == &System::id() && treasury.data_is_empty(),
+      LaminarError::InvalidTreasury
+    );
+    return Ok(Pubkey::default());
+  }
+
+  require!(
+    treasury_owner_program != Pubkey::default() && treasury_owner_program != System::id(),
+    LaminarError::InvalidTreasury
+  );
+
+  // An existing program-owned account must match the acknowledged owner.
+  if treasury.owner != &System::id() {
+    require!(treasury.owner == &treasury_owner_program, LaminarError::InvalidTreasury);
+  }
+
+  Ok(treasury_owner_program)
+}
+
+pub fn handler(
+  ctx: Context<SetTreasury>,
+  treasury_is_pda: bool,
+  treasury_owner_program: Pubkey,
+) -> Result<()> {
+  let recorded_owner = classify_treasury(
+    &ctx.accounts.new_treasury.to_account_info(),
+    treasury_is_pda,
+    treasury_owner_program,
+  )?;
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::Treasury {
+      treasury: ctx.accounts.new_treasury.key(),
+      treasury_is_pda,
+      treasury_owner_program,
+    },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let old_treasury = global_state.treasury;
+  let new_treasury = ctx.accounts.new_treasury.key();
+
+  global_state.treasury = new_treasury;
+  global_state.treasury_owner_program = recorded_owner;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(TreasuryUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_treasury,
+    new_treasury,
+    treasury_is_pda,
+    treasury_owner_program: recorded_owner,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Treasury updated: {} (pda={}), fee ATAs amUSD={} aSOL={}",
+    new_treasury,
+    treasury_is_pda,
+    ctx.accounts.new_treasury_amusd_account.key(),
+    ctx.accounts.new_treasury_asol_account.key()
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  /// CHECK: classified in `classify_treasury`; must co-sign unless acknowledged as a PDA
+  pub new_treasury: UncheckedAccount<'info>,
+
+  #[account(
+    constraint = amusd_mint.key() == global_state.amusd_mint @ LaminarError::InvalidMint,
+  )]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(
+    constraint = asol_mint.key() == global_state.asol_mint @ LaminarError::InvalidMint,
+  )]
+  pub asol_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// New treasury's amUSD fee account (created if missing)
+  #[account(
+    init_if_needed,
+    payer = authority,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = new_treasury,
+    associated_token::token_program = token_program,
+    constraint = new_treasury_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub new_treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// New treasury's aSOL fee account (created if missing)
+  #[account(
+    init_if_needed,
+    payer = authority,
+    associated_token::mint = asol_mint,
+    associated_token::authority = new_treasury,
+    associated_token::token_program = token_program,
+    constraint = new_treasury_asol_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub new_treasury_asol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,110 @@
+//! socialize_bad_debt instruction - write amUSD down to what the book can pay (admin only)
+//! Once the Stability Pool and the insurance fund are spent and CR is still
+//! below 100%, the shortfall is spread over every holder at once: the global
+//! `redemption_rate_bps` is scaled by CR so liabilities match TVL again.
+//! Balances are untouched; each amUSD simply redeems for the new rate. The
+//! write-down is permanent and closes amUSD minting.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, BadDebtSocialized};
+use crate::invariants::*;
+use crate::math::*;
+use crate::oracle::{resolve_redeem_price, PriceAccounts, PriceBias};
+use crate::state::*;
+
+pub fn handler(ctx: Context<SocializeBadDebt>) -> Result<()> {
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+
+  // Redemptions price high, so this is the CR they will see; the high price
+  // also keeps the write-down the smallest the feed supports.
+  let oracle_price = resolve_redeem_price(
+    &PriceAccounts {
+      price_update: ctx.accounts.price_update.as_deref(),
+      switchboard_feed: ctx.accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    ctx.accounts.clock.slot,
+    ctx.accounts.clock.unix_timestamp,
+    PriceBias::High,
+  )?;
+  let sol_price_usd = oracle_price.sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let old_redemption_rate_bps = global_state.redemption_rate_bps;
+
+  let tvl = compute_book_tvl_sol(global_state.total_lst_amount, lst_to_sol_rate, global_state.collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let old_liability = compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_usd, old_redemption_rate_bps)
+    .ok_or(LaminarError::MathOverflow)?;
+  let cr_bps_before = compute_cr_bps(tvl, old_liability);
+
+  // Losses fall on the pool and the fund before they fall on every holder.
+  require!(cr_bps_before < BPS_PRECISION, LaminarError::NoBadDebt);
+  assert_no_pending_drawdown(cr_bps_before, global_state.min_cr_bps, global_state.stability_pool_amusd)?;
+  require!(global_state.insurance_fund_lamports == 0, LaminarError::InsuranceNotExhausted);
+
+  let new_redemption_rate_bps = compute_socialized_rate_bps(old_redemption_rate_bps, cr_bps_before)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_liability = compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_usd, new_redemption_rate_bps)
+    .ok_or(LaminarError::MathOverflow)?;
+  let cr_bps_after = compute_cr_bps(tvl, new_liability);
+
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.redemption_rate_bps = new_redemption_rate_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(BadDebtSocialized {
+    authority: ctx.accounts.authority.key(),
+    old_redemption_rate_bps,
+    new_redemption_rate_bps,
+    cr_bps_before,
+    cr_bps_after,
+    amusd_supply: global_state.amusd_supply,
+    slot: ctx.accounts.clock.slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Bad debt socialized: rate {} -> {}bps, CR {} -> {}bps",
+    old_redemption_rate_bps,
+    new_redemption_rate_bps,
+    cr_bps_before,
+    cr_bps_after
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SocializeBadDebt<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+}
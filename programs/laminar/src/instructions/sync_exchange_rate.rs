@@ -1,10 +1,19 @@
 //! sync_exchange_rate instruction - referesh cached LST pricing snapshot metadata
-//! In current MVP, rate source is mocked in GloabalState.
+//! With `lst_stake_pool` or `marinade_state` configured the rate is read from
+//! that account; with `lst_calculator_program` it comes from a Sanctum
+//! calculator CPI over the remaining accounts. Otherwise it stays the
+//! admin-set mock rate in GlobalState.
 //! This ensures deterministic ordering: sync first, then pricing.
 
 use anchor_lang::prelude::*;
 
-use crate::{error::LaminarError, state::*};
+use crate::{
+  constants::MAX_LST_RATE_DELTA_BPS,
+  error::LaminarError,
+  events::{emit_event, ExchangeRateSynced},
+  lst_rate::{assert_lst_rate_within_band, read_calculator_rate, read_marinade_rate, read_stake_pool_rate},
+  state::*,
+};
 
 
 /// Refresh cached exchange-rate freshness metadata in-place.
@@ -21,27 +30,77 @@ pub fn sync_exchange_rate_in_place(
     LaminarError::InvalidParameter
   );
 
-  global_state.last_tvl_update_slot = current_slot;
-  Ok(())
+  // A live rate source is only refreshed by `sync_exchange_rate` reading it,
+  // so user paths must not extend its freshness window.
+  if !global_state.has_lst_rate_source() {
+    global_state.last_tvl_update_slot = current_slot;
+  }
+
+  global_state.refresh_ema_price(current_slot)?;
+  global_state.refresh_uncertainty_index()
+
 }
 
-pub fn handler(ctx: Context<SyncExchangeRate>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SyncExchangeRate<'info>>) -> Result<()> {
   let global_state = &mut ctx.accounts.global_state;
   global_state.validate_version()?;
+  // Settlement froze the rate redemptions use.
+  require!(!global_state.settled, LaminarError::ProtocolSettled);
+  let previous_update_slot = global_state.last_tvl_update_slot;
+
+  let source_rate = if global_state.lst_stake_pool != Pubkey::default() {
+    let stake_pool = ctx.accounts.stake_pool.as_deref().ok_or(LaminarError::InvalidStakePool)?;
+    Some(read_stake_pool_rate(
+      stake_pool,
+      &global_state.supported_lst_mint,
+      ctx.accounts.clock.epoch,
+      global_state.max_lst_stale_epochs,
+    )?)
+  } else if global_state.marinade_state != Pubkey::default() {
+    let marinade_state = ctx.accounts.marinade_state.as_deref().ok_or(LaminarError::InvalidMarinadeState)?;
+    Some(read_marinade_rate(marinade_state, &global_state.supported_lst_mint)?)
+  } else if global_state.lst_calculator_program != Pubkey::default() {
+    let calculator = ctx.accounts.lst_calculator.as_deref().ok_or(LaminarError::UnknownLstCalculator)?;
+    let rate = read_calculator_rate(
+      calculator,
+      &global_state.supported_lst_mint,
+      &global_state.lst_calculator_state,
+      ctx.remaining_accounts,
+    )?;
+    assert_lst_rate_within_band(global_state.mock_lst_to_sol_rate, rate, MAX_LST_RATE_DELTA_BPS)?;
+    Some(rate)
+  } else {
+    None
+  };
+
+  if let Some(rate) = source_rate {
+    require!(ctx.accounts.clock.slot >= global_state.last_tvl_update_slot, LaminarError::InvalidParameter);
+    global_state.mock_lst_to_sol_rate = rate;
+    global_state.last_tvl_update_slot = ctx.accounts.clock.slot;
+  }
 
   sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
-  global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+  global_state.increment_operation_counter()?;
   
   msg!(
-    "Exchange rate synced at slot {} (mock lst_to_sol_rate={})",
+    "Exchange rate synced at slot {} (lst_to_sol_rate={})",
     ctx.accounts.clock.slot,
     global_state.mock_lst_to_sol_rate
   );
 
+  emit_event!(ExchangeRateSynced {
+    slot: ctx.accounts.clock.slot,
+    lst_to_sol_rate: global_state.mock_lst_to_sol_rate,
+    previous_update_slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
   Ok(())
 }
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SyncExchangeRate<'info> {
   #[account(
@@ -53,4 +112,26 @@ pub struct SyncExchangeRate<'info> {
   pub global_state: Box<Account<'info, GlobalState>>,
 
   pub clock: Sysvar<'info, Clock>,
+
+  /// SPL stake pool backing the LST; required only while `global_state.lst_stake_pool` is set
+  /// CHECK: address matched to the configured pool; owner and layout checked in `lst_rate::read_stake_pool_rate`
+  #[account(
+    constraint = stake_pool.key() == global_state.lst_stake_pool @ LaminarError::InvalidStakePool
+  )]
+  pub stake_pool: Option<UncheckedAccount<'info>>,
+
+  /// Marinade state account; required only while `global_state.marinade_state` is set
+  /// CHECK: address matched to the configured state; owner and layout checked in `lst_rate::read_marinade_rate`
+  #[account(
+    constraint = marinade_state.key() == global_state.marinade_state @ LaminarError::InvalidMarinadeState
+  )]
+  pub marinade_state: Option<UncheckedAccount<'info>>,
+
+  /// Sanctum calculator program; required only while `global_state.lst_calculator_program` is set.
+  /// Its `LstToSol` accounts (LST mint first) follow as remaining accounts.
+  /// CHECK: address matched to the configured calculator; allowlist and executable checked in `lst_rate::read_calculator_rate`
+  #[account(
+    constraint = lst_calculator.key() == global_state.lst_calculator_program @ LaminarError::UnknownLstCalculator
+  )]
+  pub lst_calculator: Option<UncheckedAccount<'info>>,
 }
\ No newline at end of file
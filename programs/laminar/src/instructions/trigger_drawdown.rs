@@ -0,0 +1,297 @@
+//! trigger_drawdown instruction - Stability Pool absorbs a sub-min CR (permissionless)
+//! Pool amUSD is burned against protocol debt and the matching LST moves from
+//! the vault into the pool, before any holder redeems through the shortfall.
+//! Redemptions refuse a sub-min CR while the pool is non-empty, so clients
+//! prepend this instruction. A drawdown that empties the pool closes its epoch
+//! and writes the epoch record, paid for by the keeper.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, emit_recovery_transition, EventCpi, StabilityDrawdown};
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::invariants::*;
+use crate::math::*;
+use crate::oracle::{resolve_redeem_price, PriceAccounts, PriceBias};
+use crate::state::*;
+
+pub fn handler(ctx: Context<TriggerDrawdown>) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(!ctx.accounts.vault.is_frozen(), LaminarError::VaultFrozen);
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.validate_version()?;
+    require!(!global_state.settled, LaminarError::ProtocolSettled);
+    assert_no_flash_loan_outstanding(global_state.flash_outstanding_lamports)?;
+    assert_operation_counter_sane(global_state.operation_counter)?;
+    global_state.validate_bumps(&global_state.key(), &ctx.accounts.vault_authority.key())?;
+    assert_lst_snapshot_fresh(ctx.accounts.clock.slot, global_state.last_tvl_update_slot, global_state.max_oracle_staleness_slots)?;
+    sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  }
+
+  let global_state = &ctx.accounts.global_state;
+
+  // Same price a redemption in this slot sees, so the two agree on whether
+  // CR is below min.
+  let oracle_price = resolve_redeem_price(
+    &PriceAccounts {
+      price_update: ctx.accounts.price_update.as_deref(),
+      switchboard_feed: ctx.accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    ctx.accounts.clock.slot,
+    ctx.accounts.clock.unix_timestamp,
+    PriceBias::High,
+  )?;
+  let sol_price_used = oracle_price.sol_price_usd;
+  let lst_to_sol_rate = global_state.mock_lst_to_sol_rate;
+  let min_cr_bps = global_state.min_cr_bps;
+  let redemption_rate_bps = global_state.redemption_rate_bps;
+
+  let old_tvl = compute_book_tvl_sol(global_state.total_lst_amount, lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let old_liability = compute_liability_at_rate_sol(global_state.amusd_supply, sol_price_used, redemption_rate_bps).ok_or(LaminarError::MathOverflow)?;
+  let old_cr_bps = compute_cr_bps(old_tvl, old_liability);
+  require!(old_cr_bps < min_cr_bps, LaminarError::StabilityDrawdownNotNeeded);
+
+  // The drawdown is sized on amUSD at its redemption-rate value; the burn
+  // converts back to nominal amUSD rounded up, never past the pool.
+  let pool_amusd = ctx.accounts.stability_pool.total_pool_amusd;
+  let amusd_value = mul_div_up(global_state.amusd_supply, redemption_rate_bps, BPS_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  let pool_value = mul_div_down(pool_amusd, redemption_rate_bps, BPS_PRECISION)
+    .ok_or(LaminarError::MathOverflow)?;
+  let (value_burned, lst_to_pool) = compute_stability_drawdown(
+    global_state.total_lst_amount,
+    lst_to_sol_rate,
+    global_state.weighted_collateral_tvl_lamports,
+    amusd_value,
+    sol_price_used,
+    pool_value,
+    min_cr_bps,
+  )
+  .ok_or(LaminarError::MathOverflow)?;
+  let amusd_burned = mul_div_up(value_burned, BPS_PRECISION, redemption_rate_bps)
+    .ok_or(LaminarError::MathOverflow)?
+    .min(pool_amusd);
+  require!(amusd_burned > 0, LaminarError::ZeroAmount);
+
+  let new_lst_amount = global_state.total_lst_amount
+    .checked_sub(lst_to_pool)
+    .ok_or(LaminarError::InsufficientCollateral)?;
+  let new_amusd_supply = global_state.amusd_supply
+    .checked_sub(amusd_burned)
+    .ok_or(LaminarError::MathOverflow)?;
+  let new_tvl = compute_book_tvl_sol(new_lst_amount, lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports).ok_or(LaminarError::MathOverflow)?;
+  let new_liability = compute_liability_at_rate_sol(new_amusd_supply, sol_price_used, redemption_rate_bps).ok_or(LaminarError::MathOverflow)?;
+  let new_cr_bps = compute_cr_bps(new_tvl, new_liability);
+
+  // State update
+  let closed_epoch = ctx.accounts.stability_pool.absorb_drawdown(amusd_burned, lst_to_pool)?;
+  let epoch_closed = closed_epoch.is_some();
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_lst_amount = new_lst_amount;
+    global_state.amusd_supply = new_amusd_supply;
+    global_state.stability_pool_amusd = ctx.accounts.stability_pool.total_pool_amusd;
+    global_state.increment_operation_counter()?;
+    assert_no_pending_drawdown(new_cr_bps, min_cr_bps, global_state.stability_pool_amusd)?;
+    let recovery = global_state.track_recovery_mode(new_cr_bps, ctx.accounts.clock.slot);
+    let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    emit_recovery_transition(&event_cpi, recovery, new_cr_bps, min_cr_bps, global_state.operation_counter, &ctx.accounts.clock)?;
+  }
+
+  if let Some(record) = closed_epoch {
+    // `epoch_record` was seeded with the epoch this drawdown closed.
+    let bump = ctx.bumps.epoch_record;
+    create_epoch_record(&ctx, bump, record.epoch)?;
+    let record = StabilityEpochRecord { bump, ..record };
+    let mut data = ctx.accounts.epoch_record.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+    msg!("Stability Pool emptied: epoch {} closed", record.epoch);
+  }
+
+  // CPI calls
+  let pool_seeds = &[STABILITY_POOL_SEED, &[ctx.accounts.stability_pool.bump]];
+  let pool_signer = &[&pool_seeds[..]];
+
+  let burn_accounts = Burn {
+    mint: ctx.accounts.amusd_mint.to_account_info(),
+    from: ctx.accounts.pool_amusd_vault.to_account_info(),
+    authority: ctx.accounts.stability_pool.to_account_info(),
+  };
+  let cpi_ctx_burn = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), burn_accounts, pool_signer);
+  token_interface::burn(cpi_ctx_burn, amusd_burned)?;
+
+  if lst_to_pool > 0 {
+    let vault_seeds = &[VAULT_AUTHORITY_SEED, &[ctx.accounts.global_state.vault_authority_bump]];
+    let vault_signer = &[&vault_seeds[..]];
+    let transfer_accounts = TransferChecked {
+      from: ctx.accounts.vault.to_account_info(),
+      mint: ctx.accounts.lst_mint.to_account_info(),
+      to: ctx.accounts.pool_lst_vault.to_account_info(),
+      authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_accounts, vault_signer);
+    token_interface::transfer_checked(cpi_ctx, lst_to_pool, ctx.accounts.lst_mint.decimals)?;
+  }
+
+  ctx.accounts.vault.reload()?;
+  ctx.accounts.amusd_mint.reload()?;
+  ctx.accounts.pool_amusd_vault.reload()?;
+  ctx.accounts.pool_lst_vault.reload()?;
+  require!(ctx.accounts.vault.amount == ctx.accounts.global_state.total_lst_amount, LaminarError::BalanceSheetViolation);
+  require!(ctx.accounts.amusd_mint.supply == ctx.accounts.global_state.amusd_mint_supply()?, LaminarError::BalanceSheetViolation);
+  // Pool vaults may hold donations on top of what the pool tracks.
+  require!(ctx.accounts.pool_amusd_vault.amount >= ctx.accounts.stability_pool.total_pool_amusd, LaminarError::BalanceSheetViolation);
+  require!(ctx.accounts.pool_lst_vault.amount >= ctx.accounts.stability_pool.total_pool_lst, LaminarError::BalanceSheetViolation);
+
+  msg!("Drawdown: burned {} pool amUSD for {} LST, CR {} -> {} bps", amusd_burned, lst_to_pool, old_cr_bps, new_cr_bps);
+
+  emit_event!(StabilityDrawdown {
+    keeper: ctx.accounts.keeper.key(),
+    amusd_burned,
+    lst_to_pool,
+    old_cr_bps,
+    new_cr_bps,
+    sol_price_used,
+    total_pool_amusd: ctx.accounts.stability_pool.total_pool_amusd,
+    epoch: ctx.accounts.stability_pool.epoch,
+    epoch_closed,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+/// Allocate the epoch record PDA, tolerating lamports sent to its address
+/// ahead of time (a bare `create_account` would fail and block the drawdown).
+fn create_epoch_record(ctx: &Context<TriggerDrawdown>, bump: u8, epoch: u64) -> Result<()> {
+  let epoch_bytes = epoch.to_le_bytes();
+  let record_seeds = &[STABILITY_EPOCH_SEED, &epoch_bytes[..], &[bump]];
+  let signer = &[&record_seeds[..]];
+  let system_program = ctx.accounts.system_program.to_account_info();
+  let keeper = ctx.accounts.keeper.to_account_info();
+  let epoch_record = ctx.accounts.epoch_record.to_account_info();
+  let rent = Rent::get()?.minimum_balance(StabilityEpochRecord::LEN);
+
+  let current_lamports = epoch_record.lamports();
+  if current_lamports == 0 {
+    return system_program::create_account(
+      CpiContext::new_with_signer(system_program, CreateAccount { from: keeper, to: epoch_record }, signer),
+      rent,
+      StabilityEpochRecord::LEN as u64,
+      &crate::ID,
+    );
+  }
+
+  let top_up = rent.saturating_sub(current_lamports);
+  if top_up > 0 {
+    system_program::transfer(
+      CpiContext::new(system_program.clone(), Transfer { from: keeper, to: epoch_record.clone() }),
+      top_up,
+    )?;
+  }
+  system_program::allocate(
+    CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: epoch_record.clone() }, signer),
+    StabilityEpochRecord::LEN as u64,
+  )?;
+  system_program::assign(
+    CpiContext::new_with_signer(system_program, Assign { account_to_assign: epoch_record }, signer),
+    &crate::ID,
+  )
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TriggerDrawdown<'info> {
+  /// Anyone; pays for the epoch record if the pool is emptied
+  #[account(mut)]
+  pub keeper: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_POOL_SEED],
+    bump = stability_pool.bump,
+  )]
+  pub stability_pool: Box<Account<'info, StabilityPool>>,
+
+  /// CHECK: record PDA for the pool's current epoch; created only if this drawdown empties the pool
+  #[account(
+    mut,
+    seeds = [STABILITY_EPOCH_SEED, &stability_pool.epoch.to_le_bytes()],
+    bump,
+  )]
+  pub epoch_record: UncheckedAccount<'info>,
+
+  /// Pool amUSD vault (burned from)
+  #[account(
+    mut,
+    address = stability_pool.amusd_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Pool LST vault (receives the drawdown collateral)
+  #[account(
+    mut,
+    address = stability_pool.lst_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_lst_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Protocol vault (source of LST)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = vault_authority,
+    constraint = vault.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: PDA validated by seeds
+  #[account(
+    seeds = [VAULT_AUTHORITY_SEED],
+    bump,
+  )]
+  pub vault_authority: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+}
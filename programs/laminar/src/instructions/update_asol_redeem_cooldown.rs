@@ -0,0 +1,60 @@
+//! update_asol_redeem_cooldown instruction - admin knob for the aSOL redeem cooldown
+//! Discourages hit-and-run equity that mints around an oracle update and
+//! redeems straight after; amUSD paths and other wallets are unaffected.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, AsolRedeemCooldownUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(ctx: Context<UpdateAsolRedeemCooldown>, new_asol_redeem_cooldown_slots: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::AsolRedeemCooldown { asol_redeem_cooldown_slots: new_asol_redeem_cooldown_slots },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let old_asol_redeem_cooldown_slots = global_state.asol_redeem_cooldown_slots;
+
+  global_state.asol_redeem_cooldown_slots = new_asol_redeem_cooldown_slots;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(AsolRedeemCooldownUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_asol_redeem_cooldown_slots,
+    new_asol_redeem_cooldown_slots,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "aSOL redeem cooldown updated: {} -> {} slots",
+    old_asol_redeem_cooldown_slots,
+    new_asol_redeem_cooldown_slots
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAsolRedeemCooldown<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,69 @@
+//! update_ema_half_life instruction - admin update of the SOL price EMA half-life
+//! The EMA is folded under the old half-life first, so the change only shapes
+//! how later prices are averaged. Zero turns smoothing off.
+
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_EMA_HALF_LIFE_SLOTS, error::LaminarError, events::{emit_event, EmaHalfLifeUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateEmaHalfLife>,
+  new_ema_half_life_slots: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::EmaHalfLife { ema_half_life_slots: new_ema_half_life_slots },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_ema_half_life_slots <= MAX_EMA_HALF_LIFE_SLOTS, LaminarError::InvalidParameter);
+
+  global_state.refresh_ema_price(ctx.accounts.clock.slot)?;
+
+  let old_ema_half_life_slots = global_state.ema_half_life_slots;
+
+  global_state.ema_half_life_slots = new_ema_half_life_slots;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(EmaHalfLifeUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_ema_half_life_slots,
+    new_ema_half_life_slots,
+    ema_sol_price_usd: global_state.ema_sol_price_usd,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "EMA half-life updated: {} -> {} slots (ema={})",
+    old_ema_half_life_slots,
+    new_ema_half_life_slots,
+    global_state.ema_sol_price_usd
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateEmaHalfLife<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
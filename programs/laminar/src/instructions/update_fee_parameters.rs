@@ -0,0 +1,91 @@
+//! update_fee_parameters instruction - admin update of base fees and fee multiplier bounds
+//! Bounds are enforced by the central `validate_fee_config` validator.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, FeeParametersUpdated}, invariants::*, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateFeeParameters>,
+  fee_amusd_mint_bps: u64,
+  fee_amusd_redeem_bps: u64,
+  fee_asol_mint_bps: u64,
+  fee_asol_redeem_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(global_state.param_timelock_slots == 0, LaminarError::TimelockActive);
+
+  validate_fee_config(
+    fee_amusd_mint_bps,
+    fee_amusd_redeem_bps,
+    fee_asol_mint_bps,
+    fee_asol_redeem_bps,
+    fee_min_multiplier_bps,
+    fee_max_multiplier_bps,
+  )?;
+
+  let old_fee_amusd_mint_bps = global_state.fee_amusd_mint_bps;
+  let old_fee_amusd_redeem_bps = global_state.fee_amusd_redeem_bps;
+  let old_fee_asol_mint_bps = global_state.fee_asol_mint_bps;
+  let old_fee_asol_redeem_bps = global_state.fee_asol_redeem_bps;
+  let old_fee_min_multiplier_bps = global_state.fee_min_multiplier_bps;
+  let old_fee_max_multiplier_bps = global_state.fee_max_multiplier_bps;
+
+  global_state.fee_amusd_mint_bps = fee_amusd_mint_bps;
+  global_state.fee_amusd_redeem_bps = fee_amusd_redeem_bps;
+  global_state.fee_asol_mint_bps = fee_asol_mint_bps;
+  global_state.fee_asol_redeem_bps = fee_asol_redeem_bps;
+  global_state.fee_min_multiplier_bps = fee_min_multiplier_bps;
+  global_state.fee_max_multiplier_bps = fee_max_multiplier_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(FeeParametersUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_fee_amusd_mint_bps,
+    new_fee_amusd_mint_bps: fee_amusd_mint_bps,
+    old_fee_amusd_redeem_bps,
+    new_fee_amusd_redeem_bps: fee_amusd_redeem_bps,
+    old_fee_asol_mint_bps,
+    new_fee_asol_mint_bps: fee_asol_mint_bps,
+    old_fee_asol_redeem_bps,
+    new_fee_asol_redeem_bps: fee_asol_redeem_bps,
+    old_fee_min_multiplier_bps,
+    new_fee_min_multiplier_bps: fee_min_multiplier_bps,
+    old_fee_max_multiplier_bps,
+    new_fee_max_multiplier_bps: fee_max_multiplier_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Fee parameters updated: mint_amusd={}, redeem_amusd={}, mint_asol={}, redeem_asol={}, mult=[{}, {}]",
+    fee_amusd_mint_bps,
+    fee_amusd_redeem_bps,
+    fee_asol_mint_bps,
+    fee_asol_redeem_bps,
+    fee_min_multiplier_bps,
+    fee_max_multiplier_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeeParameters<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,65 @@
+//! update_fee_split instruction - admin knob for the treasury/insurance fee split
+//! Applies to every mint and redeem fee from the next instruction on; fees
+//! already paid to the treasury are not moved.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, FeeSplitUpdated}, invariants::assert_param_change_unlocked, math::BPS_PRECISION, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateFeeSplit>,
+  new_fee_split_insurance_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::FeeSplit { fee_split_insurance_bps: new_fee_split_insurance_bps },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_fee_split_insurance_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+
+  let old_fee_split_insurance_bps = global_state.fee_split_insurance_bps;
+
+  global_state.fee_split_insurance_bps = new_fee_split_insurance_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(FeeSplitUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_fee_split_insurance_bps,
+    new_fee_split_insurance_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Fee split updated: {} -> {} bps to insurance",
+    old_fee_split_insurance_bps,
+    new_fee_split_insurance_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateFeeSplit<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
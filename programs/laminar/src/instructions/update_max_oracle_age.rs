@@ -0,0 +1,64 @@
+//! update_max_oracle_age instruction - admin update of the wall-clock oracle age bound
+//! Enforced next to `max_oracle_staleness_slots`; a price fails when either is exceeded.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, MaxOracleAgeUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateMaxOracleAge>,
+  new_max_oracle_age_seconds: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MaxOracleAge { max_oracle_age_seconds: new_max_oracle_age_seconds },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_max_oracle_age_seconds > 0, LaminarError::InvalidParameter);
+
+  let old_max_oracle_age_seconds = global_state.max_oracle_age_seconds;
+
+  global_state.max_oracle_age_seconds = new_max_oracle_age_seconds;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MaxOracleAgeUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_oracle_age_seconds,
+    new_max_oracle_age_seconds,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Max oracle age updated: {} -> {} seconds",
+    old_max_oracle_age_seconds,
+    new_max_oracle_age_seconds
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMaxOracleAge<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
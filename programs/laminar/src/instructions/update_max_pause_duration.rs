@@ -0,0 +1,64 @@
+//! update_max_pause_duration instruction - admin update of the redeem pause timeout
+//! Applies to the pause in progress as well, measured from its original start slot.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, MaxPauseDurationUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateMaxPauseDuration>,
+  new_max_pause_duration_slots: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MaxPauseDuration { max_pause_duration_slots: new_max_pause_duration_slots },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_max_pause_duration_slots > 0, LaminarError::InvalidParameter);
+
+  let old_max_pause_duration_slots = global_state.max_pause_duration_slots;
+
+  global_state.max_pause_duration_slots = new_max_pause_duration_slots;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MaxPauseDurationUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_pause_duration_slots,
+    new_max_pause_duration_slots,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Max pause duration updated: {} -> {} slots",
+    old_max_pause_duration_slots,
+    new_max_pause_duration_slots
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMaxPauseDuration<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
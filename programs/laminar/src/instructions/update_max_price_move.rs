@@ -0,0 +1,66 @@
+//! update_max_price_move instruction - admin bound on mock price updates
+//! `update_mock_prices` rejects a SOL price or LST rate further than this from
+//! the previous value unless it is forced while mint and redeem are paused.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, MaxPriceMoveUpdated}, invariants::assert_param_change_unlocked, math::BPS_PRECISION, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateMaxPriceMove>,
+  new_max_price_move_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MaxPriceMove { max_price_move_bps: new_max_price_move_bps },
+    ctx.accounts.clock.slot,
+  )?;
+
+  // A bound above 100% would never stop the 10x moves the breaker is for.
+  require!(new_max_price_move_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+
+  let old_max_price_move_bps = global_state.max_price_move_bps;
+
+  global_state.max_price_move_bps = new_max_price_move_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MaxPriceMoveUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_price_move_bps,
+    new_max_price_move_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Max price move updated: {} -> {} bps",
+    old_max_price_move_bps,
+    new_max_price_move_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMaxPriceMove<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
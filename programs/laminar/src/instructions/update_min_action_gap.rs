@@ -0,0 +1,57 @@
+//! update_min_action_gap instruction - admin knob for the mint/redeem gap per wallet
+//! With a gap set, one signer cannot mint and redeem in the same slot (or
+//! transaction) to arbitrage rounding and the reserve; every user path then
+//! needs the caller's UserPosition to check it.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, MinActionGapUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(ctx: Context<UpdateMinActionGap>, new_min_action_gap_slots: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MinActionGap { min_action_gap_slots: new_min_action_gap_slots },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let old_min_action_gap_slots = global_state.min_action_gap_slots;
+
+  global_state.min_action_gap_slots = new_min_action_gap_slots;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MinActionGapUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_min_action_gap_slots,
+    new_min_action_gap_slots,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Min action gap updated: {} -> {} slots", old_min_action_gap_slots, new_min_action_gap_slots);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMinActionGap<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
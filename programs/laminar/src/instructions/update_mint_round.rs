@@ -0,0 +1,72 @@
+//! update_mint_round instruction - admin knob for the per-round aSOL mint cap
+//! The round in progress keeps its start slot and running total; a new
+//! length or cap applies to it from the next mint on.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, MintRoundUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(ctx: Context<UpdateMintRound>, new_max_asol_mint_per_round: u64, new_mint_round_slots: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MintRound {
+      max_asol_mint_per_round: new_max_asol_mint_per_round,
+      mint_round_slots: new_mint_round_slots,
+    },
+    ctx.accounts.clock.slot,
+  )?;
+
+  // A zero-slot round would roll on every mint and never bind.
+  require!(new_mint_round_slots > 0, LaminarError::InvalidParameter);
+
+  let old_max_asol_mint_per_round = global_state.max_asol_mint_per_round;
+  let old_mint_round_slots = global_state.mint_round_slots;
+
+  global_state.max_asol_mint_per_round = new_max_asol_mint_per_round;
+  global_state.mint_round_slots = new_mint_round_slots;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(MintRoundUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_asol_mint_per_round,
+    new_max_asol_mint_per_round,
+    old_mint_round_slots,
+    new_mint_round_slots,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Mint round updated: cap {} -> {} aSOL, length {} -> {} slots",
+    old_max_asol_mint_per_round,
+    new_max_asol_mint_per_round,
+    old_mint_round_slots,
+    new_mint_round_slots
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMintRound<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,67 @@
+//! update_mock_oracle_confidence instruction - admin update of the mock confidence width
+//! Pricing actions reject a confidence wider than `max_conf_bps` of price, so this
+//! refreshes `last_oracle_update_slot` and its timestamp like any other mock oracle snapshot.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, OracleConfidenceUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateMockOracleConfidence>,
+  new_confidence_usd: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::MockOracleConfidence { confidence_usd: new_confidence_usd },
+    ctx.accounts.clock.slot,
+  )?;
+
+  let old_confidence_usd = global_state.mock_oracle_confidence_usd;
+
+  global_state.mock_oracle_confidence_usd = new_confidence_usd;
+  global_state.last_oracle_update_slot = ctx.accounts.clock.slot;
+  global_state.last_oracle_update_timestamp = ctx.accounts.clock.unix_timestamp;
+  global_state.refresh_uncertainty_index()?;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(OracleConfidenceUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_confidence_usd,
+    new_confidence_usd,
+    slot: ctx.accounts.clock.slot,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Mock oracle confidence updated: {} -> {} micro-USD",
+    old_confidence_usd,
+    new_confidence_usd
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMockOracleConfidence<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,82 @@
+//! update_oracle_bounds instruction - admin update of absolute oracle sanity bounds
+//! Prices and LST rates outside these bounds are data errors; every pricing
+//! instruction and `update_mock_prices` fails with `OraclePriceOutOfBounds`.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, OracleBoundsUpdated}, invariants::{assert_param_change_unlocked, validate_oracle_bounds}, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateOracleBounds>,
+  min_sol_price_usd: u64,
+  max_sol_price_usd: u64,
+  min_lst_rate: u64,
+  max_lst_rate: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::OracleBounds { min_sol_price_usd, max_sol_price_usd, min_lst_rate, max_lst_rate },
+    ctx.accounts.clock.slot,
+  )?;
+
+  validate_oracle_bounds(min_sol_price_usd, max_sol_price_usd, min_lst_rate, max_lst_rate)?;
+
+  let old_min_sol_price_usd = global_state.min_sol_price_usd;
+  let old_max_sol_price_usd = global_state.max_sol_price_usd;
+  let old_min_lst_rate = global_state.min_lst_rate;
+  let old_max_lst_rate = global_state.max_lst_rate;
+
+  global_state.min_sol_price_usd = min_sol_price_usd;
+  global_state.max_sol_price_usd = max_sol_price_usd;
+  global_state.min_lst_rate = min_lst_rate;
+  global_state.max_lst_rate = max_lst_rate;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(OracleBoundsUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_min_sol_price_usd,
+    new_min_sol_price_usd: min_sol_price_usd,
+    old_max_sol_price_usd,
+    new_max_sol_price_usd: max_sol_price_usd,
+    old_min_lst_rate,
+    new_min_lst_rate: min_lst_rate,
+    old_max_lst_rate,
+    new_max_lst_rate: max_lst_rate,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Oracle bounds updated: sol_price=[{}, {}], lst_rate=[{}, {}]",
+    min_sol_price_usd,
+    max_sol_price_usd,
+    min_lst_rate,
+    max_lst_rate
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOracleBounds<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
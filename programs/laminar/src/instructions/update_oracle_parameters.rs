@@ -0,0 +1,77 @@
+//! update_oracle_parameters instruction - admin update of oracle risk bounds
+//! Bounds are enforced by the central `validate_oracle_config` validator.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, OracleParametersUpdated}, invariants::*, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateOracleParameters>,
+  max_oracle_staleness_slots: u64,
+  max_conf_bps: u64,
+  uncertainty_max_bps: u64,
+  max_lst_stale_epochs: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  require!(global_state.param_timelock_slots == 0, LaminarError::TimelockActive);
+
+  validate_oracle_config(
+    max_oracle_staleness_slots,
+    max_conf_bps,
+    uncertainty_max_bps,
+    max_lst_stale_epochs,
+  )?;
+
+  let old_max_oracle_staleness_slots = global_state.max_oracle_staleness_slots;
+  let old_max_conf_bps = global_state.max_conf_bps;
+  let old_uncertainty_max_bps = global_state.uncertainty_max_bps;
+  let old_max_lst_stale_epochs = global_state.max_lst_stale_epochs;
+
+  global_state.max_oracle_staleness_slots = max_oracle_staleness_slots;
+  global_state.max_conf_bps = max_conf_bps;
+  global_state.uncertainty_max_bps = uncertainty_max_bps;
+  global_state.max_lst_stale_epochs = max_lst_stale_epochs;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(OracleParametersUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_oracle_staleness_slots,
+    new_max_oracle_staleness_slots: max_oracle_staleness_slots,
+    old_max_conf_bps,
+    new_max_conf_bps: max_conf_bps,
+    old_uncertainty_max_bps,
+    new_uncertainty_max_bps: uncertainty_max_bps,
+    old_max_lst_stale_epochs,
+    new_max_lst_stale_epochs: max_lst_stale_epochs,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Oracle parameters updated: staleness_slots={}, max_conf={}bps, uncertainty_max={}bps, lst_stale_epochs={}",
+    max_oracle_staleness_slots,
+    max_conf_bps,
+    uncertainty_max_bps,
+    max_lst_stale_epochs
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateOracleParameters<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,61 @@
+//! update_pair_cr_tolerance instruction - admin bound on redeem_pair's CR move
+//! A paired redemption whose legs leave CR further than this from where it
+//! started is rejected; 0 demands the exact pre-op CR.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, PairCrToleranceUpdated}, invariants::assert_param_change_unlocked, math::BPS_PRECISION, state::*};
+
+pub fn handler(ctx: Context<UpdatePairCrTolerance>, new_pair_cr_tolerance_bps: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::PairCrTolerance { pair_cr_tolerance_bps: new_pair_cr_tolerance_bps },
+    ctx.accounts.clock.slot,
+  )?;
+  require!(new_pair_cr_tolerance_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+
+  let old_pair_cr_tolerance_bps = global_state.pair_cr_tolerance_bps;
+
+  global_state.pair_cr_tolerance_bps = new_pair_cr_tolerance_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PairCrToleranceUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_pair_cr_tolerance_bps,
+    new_pair_cr_tolerance_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Pair CR tolerance updated: {} -> {} bps",
+    old_pair_cr_tolerance_bps,
+    new_pair_cr_tolerance_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePairCrTolerance<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
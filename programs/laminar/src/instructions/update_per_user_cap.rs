@@ -0,0 +1,59 @@
+//! update_per_user_cap instruction - admin knob for the per-wallet amUSD cap
+//! The cap counts everything a wallet minted since its UserPosition was
+//! opened; lowering it never touches amUSD already minted.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, PerUserCapUpdated}, invariants::{assert_param_change_unlocked, is_cap_reduction}, state::*};
+
+pub fn handler(ctx: Context<UpdatePerUserCap>, new_per_user_amusd_cap: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Lowering the cap only reduces risk, so it skips the timelock.
+  if !is_cap_reduction(global_state.per_user_amusd_cap, new_per_user_amusd_cap) {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::PerUserCap { per_user_amusd_cap: new_per_user_amusd_cap },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let old_per_user_amusd_cap = global_state.per_user_amusd_cap;
+
+  global_state.per_user_amusd_cap = new_per_user_amusd_cap;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PerUserCapUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_per_user_amusd_cap,
+    new_per_user_amusd_cap,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Per-user amUSD cap updated: {} -> {}", old_per_user_amusd_cap, new_per_user_amusd_cap);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePerUserCap<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,77 @@
+//! update_psm_parameters instruction - admin knob for the PSM fee and debt ceiling
+//! A ceiling below `psm_amusd_outstanding` only stops new USDC swaps in;
+//! amUSD already outstanding can still be swapped back out.
+
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_PSM_FEE_BPS, error::LaminarError, events::{emit_event, PsmParametersUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdatePsmParameters>,
+  new_psm_fee_bps: u64,
+  new_psm_debt_ceiling: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Lowering the debt ceiling at an unchanged fee only reduces risk, so it
+  // skips the timelock.
+  if new_psm_debt_ceiling > global_state.psm_debt_ceiling || new_psm_fee_bps != global_state.psm_fee_bps {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::PsmParameters { psm_fee_bps: new_psm_fee_bps, psm_debt_ceiling: new_psm_debt_ceiling },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+  require!(global_state.psm_usdc_mint != Pubkey::default(), LaminarError::PsmNotInitialized);
+
+  require!(new_psm_fee_bps <= MAX_PSM_FEE_BPS, LaminarError::InvalidParameter);
+
+  let old_psm_fee_bps = global_state.psm_fee_bps;
+  let old_psm_debt_ceiling = global_state.psm_debt_ceiling;
+
+  global_state.psm_fee_bps = new_psm_fee_bps;
+  global_state.psm_debt_ceiling = new_psm_debt_ceiling;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(PsmParametersUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_psm_fee_bps,
+    new_psm_fee_bps,
+    old_psm_debt_ceiling,
+    new_psm_debt_ceiling,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "PSM updated: fee {} -> {} bps, ceiling {} -> {}",
+    old_psm_fee_bps,
+    new_psm_fee_bps,
+    old_psm_debt_ceiling,
+    new_psm_debt_ceiling
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePsmParameters<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,65 @@
+//! update_rebalance_discount instruction - admin bound on the rebalance discount
+//! The discount is paid from the treasury's delegated aSOL, so this caps how
+//! fast the treasury can be spent recapitalizing a sub-min CR.
+
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_REBALANCE_DISCOUNT_BPS, error::LaminarError, events::{emit_event, RebalanceDiscountUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateRebalanceDiscount>,
+  new_rebalance_discount_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::RebalanceDiscount { rebalance_discount_bps: new_rebalance_discount_bps },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_rebalance_discount_bps <= MAX_REBALANCE_DISCOUNT_BPS, LaminarError::InvalidParameter);
+
+  let old_rebalance_discount_bps = global_state.rebalance_discount_bps;
+
+  global_state.rebalance_discount_bps = new_rebalance_discount_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(RebalanceDiscountUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_rebalance_discount_bps,
+    new_rebalance_discount_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Rebalance discount updated: {} -> {} bps",
+    old_rebalance_discount_bps,
+    new_rebalance_discount_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRebalanceDiscount<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
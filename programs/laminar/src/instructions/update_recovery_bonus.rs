@@ -0,0 +1,65 @@
+//! update_recovery_bonus instruction - admin knob for the recovery-mode mint discount
+//! The bonus is paid in dilution by existing aSOL holders; per-mint dilution
+//! is separately capped by MAX_RECOVERY_DILUTION_BPS whatever this is set to.
+
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_RECOVERY_BONUS_BPS, error::LaminarError, events::{emit_event, RecoveryBonusUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateRecoveryBonus>,
+  new_recovery_bonus_bps: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::RecoveryBonus { recovery_bonus_bps: new_recovery_bonus_bps },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_recovery_bonus_bps <= MAX_RECOVERY_BONUS_BPS, LaminarError::InvalidParameter);
+
+  let old_recovery_bonus_bps = global_state.recovery_bonus_bps;
+
+  global_state.recovery_bonus_bps = new_recovery_bonus_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(RecoveryBonusUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_recovery_bonus_bps,
+    new_recovery_bonus_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Recovery bonus updated: {} -> {} bps",
+    old_recovery_bonus_bps,
+    new_recovery_bonus_bps
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRecoveryBonus<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
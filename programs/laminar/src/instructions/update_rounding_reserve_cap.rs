@@ -0,0 +1,69 @@
+//! update_rounding_reserve_cap instruction - admin update of the rounding reserve cap
+//! The cap may be raised or lowered, but never below the reserve already accumulated.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, RoundingReserveCapUpdated}, invariants::assert_param_change_unlocked, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateRoundingReserveCap>,
+  new_max_rounding_reserve_lamports: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::RoundingReserveCap { max_rounding_reserve_lamports: new_max_rounding_reserve_lamports },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(
+    new_max_rounding_reserve_lamports >= global_state.rounding_reserve_lamports,
+    LaminarError::InvalidParameter
+  );
+
+  let old_max_rounding_reserve_lamports = global_state.max_rounding_reserve_lamports;
+
+  global_state.max_rounding_reserve_lamports = new_max_rounding_reserve_lamports;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(RoundingReserveCapUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_rounding_reserve_lamports,
+    new_max_rounding_reserve_lamports,
+    rounding_reserve_lamports: global_state.rounding_reserve_lamports,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Rounding reserve cap updated: {} -> {} (reserve={})",
+    old_max_rounding_reserve_lamports,
+    new_max_rounding_reserve_lamports,
+    global_state.rounding_reserve_lamports
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRoundingReserveCap<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
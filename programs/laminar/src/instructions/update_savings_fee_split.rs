@@ -0,0 +1,64 @@
+//! update_savings_fee_split instruction - admin knob for the Savings Pool's fee share
+//! Sets the bps of every amUSD treasury fee that mint_amusd and redeem_amusd
+//! pay into the Savings Pool instead. Takes the pool so a split can only be
+//! set once there is somewhere for it to go.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, events::{emit_event, SavingsFeeSplitUpdated}, invariants::assert_param_change_unlocked, math::BPS_PRECISION, state::*};
+
+pub fn handler(ctx: Context<UpdateSavingsFeeSplit>, new_savings_fee_split_bps: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  assert_param_change_unlocked(
+    global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::SavingsFeeSplit { savings_fee_split_bps: new_savings_fee_split_bps },
+    ctx.accounts.clock.slot,
+  )?;
+
+  require!(new_savings_fee_split_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+
+  let old_savings_fee_split_bps = global_state.savings_fee_split_bps;
+  global_state.savings_fee_split_bps = new_savings_fee_split_bps;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(SavingsFeeSplitUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_savings_fee_split_bps,
+    new_savings_fee_split_bps,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("Savings fee split: {} -> {} bps", old_savings_fee_split_bps, new_savings_fee_split_bps);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSavingsFeeSplit<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  #[account(
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Box<Account<'info, SavingsPool>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
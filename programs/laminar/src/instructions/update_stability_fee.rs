@@ -0,0 +1,97 @@
+//! update_stability_fee instruction - admin knob for the annual stability fee
+//! Accrues everything owed at the old rate first, so a new rate only ever
+//! applies from the slot it is set.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+  constants::MAX_STABILITY_FEE_BPS_PER_YEAR,
+  error::LaminarError,
+  events::{emit_event, EventCpi, StabilityFeeUpdated},
+  instructions::accrue_stability_fee::accrue_stability_fee_to_treasury,
+  invariants::assert_param_change_unlocked,
+  state::*,
+};
+
+pub fn handler(ctx: Context<UpdateStabilityFee>, new_stability_fee_bps_per_year: u64) -> Result<()> {
+  ctx.accounts.global_state.validate_version()?;
+  assert_param_change_unlocked(
+    ctx.accounts.global_state.param_timelock_slots,
+    ctx.accounts.pending_param_change.as_deref(),
+    &ParamChange::StabilityFee { stability_fee_bps_per_year: new_stability_fee_bps_per_year },
+    ctx.accounts.clock.slot,
+  )?;
+  require!(new_stability_fee_bps_per_year <= MAX_STABILITY_FEE_BPS_PER_YEAR, LaminarError::InvalidParameter);
+
+  accrue_stability_fee_to_treasury(
+    &mut ctx.accounts.global_state,
+    &ctx.accounts.amusd_mint,
+    &ctx.accounts.treasury_amusd_account,
+    &ctx.accounts.token_program,
+    &ctx.accounts.clock,
+    &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+  )?;
+
+  let global_state = &mut ctx.accounts.global_state;
+  let old_stability_fee_bps_per_year = global_state.stability_fee_bps_per_year;
+  global_state.stability_fee_bps_per_year = new_stability_fee_bps_per_year;
+  global_state.last_accrual_timestamp = ctx.accounts.clock.unix_timestamp;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(StabilityFeeUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_stability_fee_bps_per_year,
+    new_stability_fee_bps_per_year,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Stability fee: {} -> {} bps/year",
+    old_stability_fee_bps_per_year,
+    new_stability_fee_bps_per_year
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateStabilityFee<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    has_one = amusd_mint,
+    has_one = treasury,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  #[account(mut)]
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  /// Treasury's amUSD token account (receives the fee accrued at the old rate)
+  #[account(
+    mut,
+    associated_token::mint = amusd_mint,
+    associated_token::authority = treasury,
+    associated_token::token_program = token_program,
+  )]
+  pub treasury_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// CHECK: Verified by has_one constraint on global_state
+  pub treasury: UncheckedAccount<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
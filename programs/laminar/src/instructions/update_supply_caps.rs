@@ -0,0 +1,71 @@
+//! update_supply_caps instruction - admin knob for the amUSD and aSOL supply caps
+//! A cap below the current supply only stops new mints;
+//! redemptions and stability fee accrual keep working regardless.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, SupplyCapsUpdated}, invariants::{assert_param_change_unlocked, is_cap_reduction}, state::*};
+
+pub fn handler(ctx: Context<UpdateSupplyCaps>, new_amusd_supply_cap: u64, new_asol_supply_cap: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Lowering the caps only reduces risk, so it skips the timelock.
+  if !is_cap_reduction(global_state.amusd_supply_cap, new_amusd_supply_cap)
+    || !is_cap_reduction(global_state.asol_supply_cap, new_asol_supply_cap)
+  {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::SupplyCaps { amusd_supply_cap: new_amusd_supply_cap, asol_supply_cap: new_asol_supply_cap },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let old_amusd_supply_cap = global_state.amusd_supply_cap;
+  let old_asol_supply_cap = global_state.asol_supply_cap;
+
+  global_state.amusd_supply_cap = new_amusd_supply_cap;
+  global_state.asol_supply_cap = new_asol_supply_cap;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(SupplyCapsUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_amusd_supply_cap,
+    new_amusd_supply_cap,
+    old_asol_supply_cap,
+    new_asol_supply_cap,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Supply caps updated: amUSD {} -> {}, aSOL {} -> {}",
+    old_amusd_supply_cap,
+    new_amusd_supply_cap,
+    old_asol_supply_cap,
+    new_asol_supply_cap
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateSupplyCaps<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
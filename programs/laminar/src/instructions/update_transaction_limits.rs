@@ -0,0 +1,78 @@
+//! update_transaction_limits instruction - admin knob for per-transaction size limits
+//! Bounds how far a single mint or redemption can move CR and NAV; a larger
+//! position has to be built or unwound over several transactions.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, TransactionLimitsUpdated}, invariants::{assert_param_change_unlocked, is_cap_reduction}, state::*};
+
+pub fn handler(
+  ctx: Context<UpdateTransactionLimits>,
+  new_max_single_deposit_lst: u64,
+  new_max_single_redeem_units: u64,
+) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Lowering the limits only reduces risk, so it skips the timelock.
+  if !is_cap_reduction(global_state.max_single_deposit_lst, new_max_single_deposit_lst)
+    || !is_cap_reduction(global_state.max_single_redeem_units, new_max_single_redeem_units)
+  {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::TransactionLimits {
+        max_single_deposit_lst: new_max_single_deposit_lst,
+        max_single_redeem_units: new_max_single_redeem_units,
+      },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let old_max_single_deposit_lst = global_state.max_single_deposit_lst;
+  let old_max_single_redeem_units = global_state.max_single_redeem_units;
+
+  global_state.max_single_deposit_lst = new_max_single_deposit_lst;
+  global_state.max_single_redeem_units = new_max_single_redeem_units;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(TransactionLimitsUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_single_deposit_lst,
+    new_max_single_deposit_lst,
+    old_max_single_redeem_units,
+    new_max_single_redeem_units,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!(
+    "Transaction limits updated: deposit {} -> {} LST, redeem {} -> {} units",
+    old_max_single_deposit_lst,
+    new_max_single_deposit_lst,
+    old_max_single_redeem_units,
+    new_max_single_redeem_units
+  );
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTransactionLimits<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,59 @@
+//! update_tvl_cap instruction - admin knob for the guarded-launch collateral ceiling
+//! Only mint_amusd and mint_asol are held to it; redemptions drain the vault
+//! regardless, and a cap below the current balance just stops new deposits.
+
+use anchor_lang::prelude::*;
+
+use crate::{events::{emit_event, TvlCapUpdated}, invariants::{assert_param_change_unlocked, is_cap_reduction}, state::*};
+
+pub fn handler(ctx: Context<UpdateTvlCap>, new_max_total_lst_amount: u64) -> Result<()> {
+  let global_state = &mut ctx.accounts.global_state;
+  global_state.validate_version()?;
+  // Lowering the cap only reduces risk, so it skips the timelock.
+  if !is_cap_reduction(global_state.max_total_lst_amount, new_max_total_lst_amount) {
+    assert_param_change_unlocked(
+      global_state.param_timelock_slots,
+      ctx.accounts.pending_param_change.as_deref(),
+      &ParamChange::TvlCap { max_total_lst_amount: new_max_total_lst_amount },
+      ctx.accounts.clock.slot,
+    )?;
+  }
+
+  let old_max_total_lst_amount = global_state.max_total_lst_amount;
+
+  global_state.max_total_lst_amount = new_max_total_lst_amount;
+  global_state.increment_operation_counter()?;
+
+  emit_event!(TvlCapUpdated {
+    authority: ctx.accounts.authority.key(),
+    old_max_total_lst_amount,
+    new_max_total_lst_amount,
+    operation_counter: global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  msg!("TVL cap updated: {} -> {} LST", old_max_total_lst_amount, new_max_total_lst_amount);
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateTvlCap<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+
+  #[account(
+    mut,
+    has_one = authority,
+    seeds = [GLOBAL_STATE_SEED],
+    bump
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  /// Matured queued change for this update; required while the parameter timelock is set
+  #[account(mut, close = authority)]
+  pub pending_param_change: Option<Box<Account<'info, PendingParamChange>>>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
@@ -0,0 +1,54 @@
+//! view_protocol_health instruction - read-only balance-sheet snapshot
+//! Returns a `ProtocolHealth` via return data so indexers and keepers get
+//! CR and NAV from one simulated call instead of decoding GlobalState. The
+//! book is valued by `GlobalState::metrics`: cached prices, or the frozen
+//! snapshot once settled. No signer, nothing written.
+
+use anchor_lang::prelude::*;
+
+use crate::{error::LaminarError, state::*};
+
+/// Protocol balance sheet at cached prices
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProtocolHealth {
+  /// Book TVL in lamports, every vault at full value
+  pub tvl: u64,
+  /// amUSD liability in lamports at the redemption rate
+  pub liability: u64,
+  /// CR on the collateral-weighted book
+  pub cr_bps: u64,
+  /// Reserve-aware lamports per aSOL; 0 with no aSOL outstanding
+  pub nav_asol: u64,
+  /// Lamports per amUSD at the redemption rate
+  pub nav_amusd: u64,
+  /// Equity left to aSOL holders after liability, reserve and insurance
+  pub claimable_equity: u64,
+  pub rounding_reserve: u64,
+}
+
+pub fn handler(ctx: Context<ViewProtocolHealth>) -> Result<ProtocolHealth> {
+  let global_state = &ctx.accounts.global_state;
+  global_state.validate_version()?;
+
+  let metrics = global_state.metrics().ok_or(LaminarError::MathOverflow)?;
+
+  Ok(ProtocolHealth {
+    tvl: metrics.tvl,
+    liability: metrics.liability,
+    cr_bps: metrics.cr_bps,
+    nav_asol: metrics.nav_asol.unwrap_or(0),
+    nav_amusd: metrics.nav_amusd,
+    claimable_equity: metrics.claimable_equity,
+    rounding_reserve: global_state.rounding_reserve_lamports,
+  })
+}
+
+#[derive(Accounts)]
+pub struct ViewProtocolHealth<'info> {
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+}
@@ -0,0 +1,125 @@
+//! withdraw_savings instruction - burn Savings Pool shares for amUSD
+//! Pays the shares' value at the current share price, rounded down so the
+//! depositors who stay never fund a withdrawal.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, SavingsWithdrawn};
+use crate::invariants::*;
+use crate::math::*;
+use crate::state::*;
+
+pub fn handler(ctx: Context<WithdrawSavings>, shares: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  ctx.accounts.global_state.validate_version()?;
+
+  require!(shares > 0, LaminarError::ZeroAmount);
+  require!(shares <= ctx.accounts.savings_deposit.shares, LaminarError::InsufficientSavingsDeposit);
+
+  let savings_pool = &ctx.accounts.savings_pool;
+  let amount = compute_savings_withdrawal(shares, savings_pool.total_pool_amusd, savings_pool.total_shares)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(amount > 0, LaminarError::AmountTooSmall);
+
+  // State update
+  {
+    let deposit = &mut ctx.accounts.savings_deposit;
+    deposit.shares -= shares;
+
+    let savings_pool = &mut ctx.accounts.savings_pool;
+    savings_pool.total_pool_amusd = savings_pool.total_pool_amusd
+      .checked_sub(amount)
+      .ok_or(LaminarError::MathOverflow)?;
+    savings_pool.total_shares = savings_pool.total_shares
+      .checked_sub(shares)
+      .ok_or(LaminarError::MathOverflow)?;
+  }
+
+  let seeds = &[SAVINGS_POOL_SEED, &[ctx.accounts.savings_pool.bump]];
+  let signer = &[&seeds[..]];
+
+  let transfer_amusd = TransferChecked {
+    from: ctx.accounts.pool_amusd_vault.to_account_info(),
+    mint: ctx.accounts.amusd_mint.to_account_info(),
+    to: ctx.accounts.user_amusd_account.to_account_info(),
+    authority: ctx.accounts.savings_pool.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_amusd, signer);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.amusd_mint.decimals)?;
+
+  ctx.accounts.pool_amusd_vault.reload()?;
+  require!(
+    ctx.accounts.pool_amusd_vault.amount == ctx.accounts.savings_pool.total_pool_amusd,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Withdrew {} amUSD for {} savings shares", amount, shares);
+
+  emit_event!(SavingsWithdrawn {
+    user: ctx.accounts.user.key(),
+    shares_burned: shares,
+    amusd_withdrawn: amount,
+    user_shares: ctx.accounts.savings_deposit.shares,
+    total_pool_amusd: ctx.accounts.savings_pool.total_pool_amusd,
+    total_shares: ctx.accounts.savings_pool.total_shares,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawSavings<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [SAVINGS_POOL_SEED],
+    bump = savings_pool.bump,
+  )]
+  pub savings_pool: Box<Account<'info, SavingsPool>>,
+
+  #[account(
+    mut,
+    seeds = [SAVINGS_DEPOSIT_SEED, user.key().as_ref()],
+    bump = savings_deposit.bump,
+  )]
+  pub savings_deposit: Box<Account<'info, SavingsDeposit>>,
+
+  /// Pool amUSD vault (source of the withdrawal)
+  #[account(
+    mut,
+    address = savings_pool.amusd_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's amUSD token account (receives the withdrawal)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+}
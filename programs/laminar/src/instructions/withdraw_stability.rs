@@ -0,0 +1,228 @@
+//! withdraw_stability instruction - take amUSD back out of the Stability Pool
+//! Burns the shares backing `amount` (rounded up) and pays out every LST gain
+//! the position has accrued. Closed while CR < min_cr_bps, so depositors
+//! cannot step out just ahead of a drawdown.
+
+use anchor_lang::prelude::program_option::COption;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::error::LaminarError;
+use crate::events::{emit_event, StabilityWithdrawn};
+use crate::instructions::sync_exchange_rate::sync_exchange_rate_in_place;
+use crate::invariants::*;
+use crate::math::*;
+use crate::oracle::{resolve_price, PriceAccounts, PriceBias};
+use crate::state::*;
+
+pub fn handler(ctx: Context<WithdrawStability>, amount: u64) -> Result<()> {
+  assert_not_cpi_context()?;
+  require!(amount > 0, LaminarError::ZeroAmount);
+
+  {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.validate_version()?;
+    assert_lst_snapshot_fresh(ctx.accounts.clock.slot, global_state.last_tvl_update_slot, global_state.max_oracle_staleness_slots)?;
+    sync_exchange_rate_in_place(global_state, ctx.accounts.clock.slot)?;
+  }
+
+  let global_state = &ctx.accounts.global_state;
+
+  // Low price = high liability, so the gate closes no later than a drawdown would open.
+  let oracle_price = resolve_price(
+    &PriceAccounts {
+      price_update: ctx.accounts.price_update.as_deref(),
+      switchboard_feed: ctx.accounts.switchboard_feed.as_deref(),
+    },
+    global_state,
+    ctx.accounts.clock.slot,
+    ctx.accounts.clock.unix_timestamp,
+    PriceBias::Low,
+  )?;
+
+  let tvl = compute_book_tvl_sol(global_state.total_lst_amount, global_state.mock_lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports)
+    .ok_or(LaminarError::MathOverflow)?;
+  let liability = if global_state.amusd_supply > 0 {
+    compute_liability_at_rate_sol(global_state.amusd_supply, oracle_price.sol_price_usd, global_state.redemption_rate_bps)
+      .ok_or(LaminarError::MathOverflow)?
+  } else {
+    0
+  };
+  let cr_bps = compute_cr_bps(tvl, liability);
+  require!(cr_bps >= global_state.min_cr_bps, LaminarError::StabilityWithdrawalBelowMinCr);
+
+  let stability_pool = &ctx.accounts.stability_pool;
+  require!(amount <= stability_pool.total_pool_amusd, LaminarError::InsufficientStabilityDeposit);
+  let shares_burned = compute_stability_shares_to_burn(amount, stability_pool.total_pool_amusd, stability_pool.total_shares)
+    .ok_or(LaminarError::MathOverflow)?;
+  // Shares from an emptied epoch are worth nothing.
+  let deposit = &ctx.accounts.stability_deposit;
+  let live_shares = if deposit.epoch == stability_pool.epoch { deposit.shares } else { 0 };
+  require!(
+    shares_burned <= live_shares,
+    LaminarError::InsufficientStabilityDeposit
+  );
+
+  // State update
+  let lst_gain_paid = {
+    let deposit = &mut ctx.accounts.stability_deposit;
+    deposit.settle_gains(&ctx.accounts.stability_pool, ctx.accounts.epoch_record.as_deref())?;
+    deposit.shares -= shares_burned;
+    let lst_gain_paid = deposit.pending_lst_gain;
+    deposit.pending_lst_gain = 0;
+
+    let stability_pool = &mut ctx.accounts.stability_pool;
+    stability_pool.total_pool_amusd -= amount;
+    stability_pool.total_shares -= shares_burned;
+    stability_pool.total_pool_lst = stability_pool.total_pool_lst
+      .checked_sub(lst_gain_paid)
+      .ok_or(LaminarError::InsufficientCollateral)?;
+
+    ctx.accounts.global_state.stability_pool_amusd = stability_pool.total_pool_amusd;
+    lst_gain_paid
+  };
+
+  let seeds = &[STABILITY_POOL_SEED, &[ctx.accounts.stability_pool.bump]];
+  let signer = &[&seeds[..]];
+
+  let transfer_amusd = TransferChecked {
+    from: ctx.accounts.pool_amusd_vault.to_account_info(),
+    mint: ctx.accounts.amusd_mint.to_account_info(),
+    to: ctx.accounts.user_amusd_account.to_account_info(),
+    authority: ctx.accounts.stability_pool.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_amusd, signer);
+  token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.amusd_mint.decimals)?;
+
+  if lst_gain_paid > 0 {
+    let transfer_lst = TransferChecked {
+      from: ctx.accounts.pool_lst_vault.to_account_info(),
+      mint: ctx.accounts.lst_mint.to_account_info(),
+      to: ctx.accounts.user_lst_account.to_account_info(),
+      authority: ctx.accounts.stability_pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), transfer_lst, signer);
+    token_interface::transfer_checked(cpi_ctx, lst_gain_paid, ctx.accounts.lst_mint.decimals)?;
+  }
+
+  ctx.accounts.pool_amusd_vault.reload()?;
+  ctx.accounts.pool_lst_vault.reload()?;
+  // Tokens sent to the vault directly are a donation; only a shortfall breaks the books.
+  require!(
+    ctx.accounts.pool_amusd_vault.amount >= ctx.accounts.stability_pool.total_pool_amusd,
+    LaminarError::BalanceSheetViolation
+  );
+  require!(
+    ctx.accounts.pool_lst_vault.amount >= ctx.accounts.stability_pool.total_pool_lst,
+    LaminarError::BalanceSheetViolation
+  );
+
+  msg!("Withdrew {} amUSD ({} shares) and {} LST gains", amount, shares_burned, lst_gain_paid);
+
+  emit_event!(StabilityWithdrawn {
+    user: ctx.accounts.user.key(),
+    amusd_withdrawn: amount,
+    shares_burned,
+    lst_gain_paid,
+    user_shares: ctx.accounts.stability_deposit.shares,
+    total_pool_amusd: ctx.accounts.stability_pool.total_pool_amusd,
+    total_shares: ctx.accounts.stability_pool.total_shares,
+    cr_bps,
+    operation_counter: ctx.accounts.global_state.operation_counter,
+    timestamp: ctx.accounts.clock.unix_timestamp,
+  });
+
+  Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawStability<'info> {
+  #[account(mut)]
+  pub user: Signer<'info>,
+
+  /// GlobalState PDA
+  #[account(
+    mut,
+    seeds = [GLOBAL_STATE_SEED],
+    bump,
+    has_one = amusd_mint,
+    constraint = global_state.to_account_info().owner == &crate::ID @ LaminarError::InvalidAccountOwner,
+  )]
+  pub global_state: Box<Account<'info, GlobalState>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_POOL_SEED],
+    bump = stability_pool.bump,
+  )]
+  pub stability_pool: Box<Account<'info, StabilityPool>>,
+
+  #[account(
+    mut,
+    seeds = [STABILITY_DEPOSIT_SEED, user.key().as_ref()],
+    bump = stability_deposit.bump,
+  )]
+  pub stability_deposit: Box<Account<'info, StabilityDeposit>>,
+
+  /// Record of the epoch the position was opened in; required only once a
+  /// drawdown has emptied that epoch
+  pub epoch_record: Option<Account<'info, StabilityEpochRecord>>,
+
+  /// Pool amUSD vault (source of the withdrawal)
+  #[account(
+    mut,
+    address = stability_pool.amusd_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_amusd_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// Pool LST vault (source of accrued gains)
+  #[account(
+    mut,
+    address = stability_pool.lst_vault @ LaminarError::InvalidAccountState,
+  )]
+  pub pool_lst_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's amUSD token account (receives the withdrawal)
+  #[account(
+    mut,
+    token::mint = amusd_mint,
+    token::authority = user,
+    constraint = user_amusd_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_amusd_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  /// User's LST token account (receives accrued gains)
+  #[account(
+    mut,
+    token::mint = lst_mint,
+    token::authority = user,
+    constraint = user_lst_account.close_authority == COption::None @ LaminarError::InvalidAccountState,
+  )]
+  pub user_lst_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  pub amusd_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(
+    constraint = lst_mint.key() == global_state.supported_lst_mint @ LaminarError::UnsupportedLST
+  )]
+  pub lst_mint: Box<InterfaceAccount<'info, Mint>>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub clock: Sysvar<'info, Clock>,
+
+  /// Pyth SOL/USD price update; required only while `global_state.price_source` is Pyth
+  /// CHECK: address matched to the configured feed; owner and layout checked in `pyth::read_pyth_price`
+  #[account(
+    constraint = price_update.key() == global_state.pyth_price_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub price_update: Option<UncheckedAccount<'info>>,
+
+  /// Switchboard SOL/USD pull feed; required only while `global_state.price_source` is Switchboard
+  /// CHECK: address matched to the configured feed; owner and layout checked in `switchboard::read_switchboard_price`
+  #[account(
+    constraint = switchboard_feed.key() == global_state.switchboard_feed @ LaminarError::InvalidPriceFeed
+  )]
+  pub switchboard_feed: Option<UncheckedAccount<'info>>,
+}
@@ -4,7 +4,7 @@
 
 use anchor_lang::prelude::*;
 
-use crate::{error::LaminarError, math::{BPS_PRECISION, SOL_PRECISION, mul_div_up}};
+use crate::{constants::{AMUSD_DECIMALS, CURRENT_ARGS_VERSION, MAX_BASE_FEE_BPS, OPERATION_COUNTER_CEILING}, error::LaminarError, math::{BPS_PRECISION, SOL_PRECISION, mul_div_up, rescale_decimals_down}, state::{ParamChange, PendingParamChange}};
 
 
 /// Derive deterministic rounding bound in lamports for a given instruction path.
@@ -58,7 +58,8 @@ pub fn assert_rounding_reserve_within_cap(current: u64, max: u64) -> Result<()>
 /// This is the foundational invariant of the entire protocol
 /// 
 /// # Arguments 
-/// * `tvl` - Total value locked in lamports
+/// * `tvl` - Total value locked in lamports, unweighted: collateral weights
+///   discount CR, never the value the balance sheet accounts for
 /// * `liability` - Total liabilities in lamports 
 /// * `equity` - Total equity in lamports
 pub fn assert_balance_sheet_holds(tvl: u64, liability: u64, accounting_equity: i128, rounding_reserve: u64, rounding_bound_lamports: u64) -> Result<()> { 
@@ -69,11 +70,7 @@ pub fn assert_balance_sheet_holds(tvl: u64, liability: u64, accounting_equity: i
     .and_then(|v| v.checked_add(rounding_reserve as i128))
     .ok_or(LaminarError::ArithmeticOverflow)?;
 
-  let diff: u128 = if lhs >= rhs {
-    (lhs - rhs) as u128
-  } else {
-      (rhs - lhs) as u128
-  };
+  let diff: u128 = lhs.abs_diff(rhs);
 
   require!(
     diff <= rounding_bound_lamports as u128,
@@ -101,6 +98,17 @@ pub fn assert_cr_above_minimum(cr_bps: u64, min_cr_bps: u64) -> Result<()> {
 }
 
 
+/// Assert the protocol is out of recovery mode (CR at or above minimum)
+/// Gates the actions that lower CR: minting amUSD and redeeming aSOL.
+/// 
+/// # Arguments
+/// * `cr_bps` - CR before the action
+/// * `min_cr_bps` - Minimum allowed CR in basis points
+pub fn assert_not_in_recovery_mode(cr_bps: u64, min_cr_bps: u64) -> Result<()> {
+  require!(cr_bps >= min_cr_bps, LaminarError::RecoveryMode);
+  Ok(())
+}
+
 /// Assert that TVL is always >= liablilty (no negative equity)
 /// Prevents bad debt propagtion
 /// 
@@ -171,6 +179,24 @@ pub fn debit_rounding_reserve(
   Ok(next)
 }
 
+/// Drawdown-first: CR may sit below `min_cr_bps` only once the Stability
+/// Pool has nothing left to burn. Checked by redemptions before they price
+/// through a sub-min CR, and by the drawdown itself afterwards.
+pub fn assert_no_pending_drawdown(cr_bps: u64, min_cr_bps: u64, stability_pool_amusd: u64) -> Result<()> {
+  require!(
+    cr_bps >= min_cr_bps || stability_pool_amusd == 0,
+    LaminarError::StabilityDrawdownPending
+  );
+  Ok(())
+}
+
+/// Assert no flash loan is open. Every instruction that moves vault LST or
+/// reconciles against it runs only while the vault is whole.
+pub fn assert_no_flash_loan_outstanding(flash_outstanding_lamports: u64) -> Result<()> {
+  require!(flash_outstanding_lamports == 0, LaminarError::FlashLoanOutstanding);
+  Ok(())
+}
+
 /// Uses stack height instead of instruction index. so normal setup 
 /// instructions in the same tnx is allowed
 pub fn assert_not_cpi_context()-> Result<()> {
@@ -189,13 +215,20 @@ pub fn assert_not_cpi_context()-> Result<()> {
 /// * `current_slot` - Current slot from Clock sysvar
 /// * `last_oracle_update_slot` - Slot of last oracle snapshot update
 /// * `max_oracle_staleness_slots` - Max allowed oracle age in slots
+/// * `current_timestamp` - Current unix timestamp from Clock sysvar
+/// * `last_oracle_update_timestamp` - Unix timestamp of last oracle snapshot update
+/// * `max_oracle_age_seconds` - Max allowed oracle age in seconds
 /// * `oracle_price_usd` - Oracle price in micro-USD
 /// * `oracle_confidence_usd` - Oracle confidence width in micro-USD
 /// * `max_conf_bps` - Max allowed confidence ratio in bps
+#[allow(clippy::too_many_arguments)]
 pub fn assert_oracle_freshness_and_confidence(
   current_slot: u64,
   last_oracle_update_slot: u64,
   max_oracle_staleness_slots: u64,
+  current_timestamp: i64,
+  last_oracle_update_timestamp: i64,
+  max_oracle_age_seconds: u64,
   oracle_price_usd: u64,
   oracle_confidence_usd: u64,
   max_conf_bps: u64,
@@ -212,6 +245,15 @@ pub fn assert_oracle_freshness_and_confidence(
     LaminarError::OraclePriceStale
   );
 
+  // Wall-clock age catches skipped or slow slots. Publishers' clocks can run
+  // slightly ahead of the cluster's, so a future timestamp counts as age zero.
+  let oracle_age_seconds = current_timestamp.saturating_sub(last_oracle_update_timestamp).max(0) as u64;
+
+  require!(
+    oracle_age_seconds <= max_oracle_age_seconds,
+    LaminarError::OraclePriceStale
+  );
+
   let conf_bps = mul_div_up(oracle_confidence_usd, BPS_PRECISION, oracle_price_usd)
     .ok_or(LaminarError::ArithmeticOverflow)?;
 
@@ -256,9 +298,200 @@ pub fn assert_lst_snapshot_fresh(
   Ok(())
 }
 
+/// Assert an oracle value lies within its absolute sanity bounds, inclusive.
+///
+/// # Arguments
+/// * `value` - SOL/USD price or LST/SOL rate being used
+/// * `min` - Lowest accepted value
+/// * `max` - Highest accepted value; zero leaves the upper side unbounded
+pub fn assert_oracle_value_within_bounds(value: u64, min: u64, max: u64) -> Result<()> {
+  require!(
+    value >= min && (max == 0 || value <= max),
+    LaminarError::OraclePriceOutOfBounds
+  );
+  Ok(())
+}
+
+/// Validate absolute oracle sanity bounds: a non-zero max must not sit below
+/// its min.
+pub fn validate_oracle_bounds(
+  min_sol_price_usd: u64,
+  max_sol_price_usd: u64,
+  min_lst_rate: u64,
+  max_lst_rate: u64,
+) -> Result<()> {
+  require!(
+    max_sol_price_usd == 0 || min_sol_price_usd <= max_sol_price_usd,
+    LaminarError::InvalidParameter
+  );
+  require!(max_lst_rate == 0 || min_lst_rate <= max_lst_rate, LaminarError::InvalidParameter);
+  Ok(())
+}
+
+/// Circuit breaker on admin price updates.
+///
+/// # Arguments
+/// * `move_bps` - Relative move of the updated value, from `compute_price_deviation_bps`
+/// * `max_price_move_bps` - Configured bound; zero disables the breaker
+pub fn assert_price_move_within_bound(move_bps: u64, max_price_move_bps: u64) -> Result<()> {
+  require!(
+    max_price_move_bps == 0 || move_bps <= max_price_move_bps,
+    LaminarError::PriceMoveTooLarge
+  );
+  Ok(())
+}
+
+/// Validate collateral ratio risk parameters.
+///
+/// # Arguments
+/// * `min_cr_bps` - Minimum collateral ratio in bps (must be >= 100%)
+/// * `target_cr_bps` - Target collateral ratio in bps (must be > min)
+pub fn validate_risk_config(min_cr_bps: u64, target_cr_bps: u64) -> Result<()> {
+  require!(min_cr_bps >= BPS_PRECISION, LaminarError::InvalidParameter);
+  require!(target_cr_bps > min_cr_bps, LaminarError::InvalidParameter);
+  Ok(())
+}
+
+/// Validate base fees and dynamic fee multiplier bounds.
+///
+/// Enforces `fee_min_multiplier_bps <= BPS_PRECISION <= fee_max_multiplier_bps`
+/// and caps every base fee at `MAX_BASE_FEE_BPS`.
+pub fn validate_fee_config(
+  fee_amusd_mint_bps: u64,
+  fee_amusd_redeem_bps: u64,
+  fee_asol_mint_bps: u64,
+  fee_asol_redeem_bps: u64,
+  fee_min_multiplier_bps: u64,
+  fee_max_multiplier_bps: u64,
+) -> Result<()> {
+  require!(fee_amusd_mint_bps <= MAX_BASE_FEE_BPS, LaminarError::InvalidParameter);
+  require!(fee_amusd_redeem_bps <= MAX_BASE_FEE_BPS, LaminarError::InvalidParameter);
+  require!(fee_asol_mint_bps <= MAX_BASE_FEE_BPS, LaminarError::InvalidParameter);
+  require!(fee_asol_redeem_bps <= MAX_BASE_FEE_BPS, LaminarError::InvalidParameter);
+
+  require!(fee_min_multiplier_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+  require!(fee_max_multiplier_bps >= BPS_PRECISION, LaminarError::InvalidParameter);
+  Ok(())
+}
+
+/// Validate oracle risk parameters.
+///
+/// # Arguments
+/// * `max_oracle_staleness_slots` - Max oracle/LST snapshot age in slots (must be > 0)
+/// * `max_conf_bps` - Max oracle confidence ratio in bps (must be > 0)
+/// * `uncertainty_max_bps` - Cap on uncertainty multiplier (must be >= 1.0x)
+/// * `max_lst_stale_epochs` - LST staleness bound in epochs (must be > 0)
+pub fn validate_oracle_config(
+  max_oracle_staleness_slots: u64,
+  max_conf_bps: u64,
+  uncertainty_max_bps: u64,
+  max_lst_stale_epochs: u64,
+) -> Result<()> {
+  require!(max_oracle_staleness_slots > 0, LaminarError::InvalidParameter);
+  require!(max_conf_bps > 0 && max_conf_bps <= BPS_PRECISION, LaminarError::InvalidParameter);
+  require!(uncertainty_max_bps >= BPS_PRECISION, LaminarError::InvalidParameter);
+  require!(max_lst_stale_epochs > 0, LaminarError::InvalidParameter);
+  Ok(())
+}
+
+/// Validate a `*Args` struct version against the versions this program understands.
+pub fn validate_args_version(args_version: u8) -> Result<()> {
+  require!(
+    (1..=CURRENT_ARGS_VERSION).contains(&args_version),
+    LaminarError::UnsupportedArgsVersion
+  );
+  Ok(())
+}
+
+/// A user order carrying `deadline_slot` may still execute at `current_slot`;
+/// 0 means no deadline.
+pub fn assert_before_deadline(deadline_slot: u64, current_slot: u64) -> Result<()> {
+  require!(
+    deadline_slot == 0 || current_slot <= deadline_slot,
+    LaminarError::DeadlineExceeded
+  );
+  Ok(())
+}
+
+/// The dynamic fee charged on an order stays within the user's `max_fee_bps`;
+/// 0 means no bound.
+pub fn assert_fee_within_max(fee_bps: u64, max_fee_bps: u64) -> Result<()> {
+  require!(
+    max_fee_bps == 0 || fee_bps <= max_fee_bps,
+    LaminarError::FeeExceedsMax
+  );
+  Ok(())
+}
+
+/// A paired mint leaves CR within `max_drift_bps` of where it started.
+pub fn assert_cr_drift_within(cr_before_bps: u64, cr_after_bps: u64, max_drift_bps: u64) -> Result<()> {
+  require!(
+    cr_before_bps.abs_diff(cr_after_bps) <= max_drift_bps,
+    LaminarError::PairCrDrift
+  );
+  Ok(())
+}
+
+/// True once a redeem pause has lasted strictly longer than the allowed window.
+pub fn pause_timeout_elapsed(
+  current_slot: u64,
+  pause_started_slot: u64,
+  max_pause_duration_slots: u64,
+) -> bool {
+  current_slot.saturating_sub(pause_started_slot) > max_pause_duration_slots
+}
+
+/// Preflight sanity check on `operation_counter`. Organic traffic cannot get
+/// near the ceiling, so a counter at or above it means a corrupt write (e.g. a
+/// bad migration); fail before anything keys off it. `migrate_global_state`
+/// repairs it.
+pub fn assert_operation_counter_sane(operation_counter: u64) -> Result<()> {
+  require!(
+    operation_counter < OPERATION_COUNTER_CEILING,
+    LaminarError::OperationCounterCorrupt
+  );
+  Ok(())
+}
+
+/// The PSM vault must back every amUSD it has minted at par. Its USDC is
+/// rescaled to amUSD decimals rounding down, so dust never counts as backing.
+pub fn assert_psm_fully_backed(psm_vault_usdc: u64, usdc_decimals: u8, psm_amusd_outstanding: u64) -> Result<()> {
+  let backing = rescale_decimals_down(psm_vault_usdc, usdc_decimals, AMUSD_DECIMALS)
+    .ok_or(LaminarError::MathOverflow)?;
+  require!(backing >= psm_amusd_outstanding, LaminarError::BalanceSheetViolation);
+  Ok(())
+}
+
+/// Timelock gate for the instant admin setters. With no timelock they apply
+/// directly; with one they need `pending`, the matured queued change carrying
+/// exactly the setter's arguments, which the setter then closes.
+pub fn assert_param_change_unlocked(
+  param_timelock_slots: u64,
+  pending: Option<&impl std::ops::Deref<Target = PendingParamChange>>,
+  change: &ParamChange,
+  slot: u64,
+) -> Result<()> {
+  let Some(pending) = pending else {
+    require!(param_timelock_slots == 0, LaminarError::TimelockActive);
+    return Ok(());
+  };
+  require!(pending.change == *change, LaminarError::ParamChangeMismatch);
+  require!(slot >= pending.eta_slot, LaminarError::TimelockNotElapsed);
+  Ok(())
+}
+
+/// Whether moving a cap where 0 means unlimited from `old_cap` to `new_cap`
+/// admits nothing the old cap refused. Such a change skips the timelock.
+pub fn is_cap_reduction(old_cap: u64, new_cap: u64) -> bool {
+  old_cap == 0 || (new_cap != 0 && new_cap <= old_cap)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{
+        DEFAULT_MAX_LST_RATE, DEFAULT_MAX_SOL_PRICE_USD, DEFAULT_MIN_LST_RATE, DEFAULT_MIN_SOL_PRICE_USD,
+    };
 
     #[test]
     fn test_balance_sheet_holds_exact() {
@@ -356,6 +589,16 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_recovery_mode_gate() {
+        assert!(assert_not_in_recovery_mode(13_000, 13_000).is_ok());
+        assert!(assert_not_in_recovery_mode(u64::MAX, 13_000).is_ok());
+        assert_eq!(
+            assert_not_in_recovery_mode(12_999, 13_000).unwrap_err(),
+            error!(LaminarError::RecoveryMode)
+        );
+    }
+
     #[test]
     fn test_no_negative_equity_valid() {
         let result = assert_no_negative_equity(200, 100);
@@ -416,6 +659,9 @@ mod tests {
             1_000, // current_slot
             900,   // last_update
             150,   // max staleness
+            10_000, // current_timestamp
+            9_970,  // last_update_timestamp
+            60,     // max age
             100_000_000, // price
             1_000_000,   // conf = 1%
             150,          // max 1.5%
@@ -429,6 +675,9 @@ mod tests {
             1_000,
             800,
             150,
+            10_000,
+            9_970,
+            60,
             100_000_000,
             1_000_000,
             150,
@@ -442,6 +691,9 @@ mod tests {
             1_000,
             900,
             150,
+            10_000,
+            9_970,
+            60,
             100_000_000,
             2_000_000, // 2%
             150,       // 1.5% max
@@ -449,6 +701,34 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_oracle_freshness_fails_on_ancient_timestamp_within_slot_bound() {
+        // 10 slots old but ten minutes of wall clock: the cluster skipped slots
+        let result = assert_oracle_freshness_and_confidence(
+            1_000, 990, 150, 10_600, 10_000, 60, 100_000_000, 1_000_000, 150,
+        );
+        assert_eq!(result.unwrap_err(), error!(LaminarError::OraclePriceStale));
+
+        // Exactly at the age bound is still fresh
+        assert!(assert_oracle_freshness_and_confidence(
+            1_000, 990, 150, 10_060, 10_000, 60, 100_000_000, 1_000_000, 150,
+        ).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_freshness_fails_on_stale_slot_within_age_bound() {
+        // Timestamp is current but the slot bound has passed
+        let result = assert_oracle_freshness_and_confidence(
+            1_000, 849, 150, 10_000, 10_000, 60, 100_000_000, 1_000_000, 150,
+        );
+        assert_eq!(result.unwrap_err(), error!(LaminarError::OraclePriceStale));
+
+        // A publish time slightly ahead of the cluster clock is not stale
+        assert!(assert_oracle_freshness_and_confidence(
+            1_000, 990, 150, 10_000, 10_002, 60, 100_000_000, 1_000_000, 150,
+        ).is_ok());
+    }
+
     #[test]
     fn test_lst_snapshot_fresh_valid() {
         // age = 50, max = 150 -> valid
@@ -476,5 +756,174 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_oracle_value_within_bounds_at_the_edges() {
+        let (min, max) = (DEFAULT_MIN_SOL_PRICE_USD, DEFAULT_MAX_SOL_PRICE_USD);
+        assert!(assert_oracle_value_within_bounds(min, min, max).is_ok());
+        assert!(assert_oracle_value_within_bounds(max, min, max).is_ok());
+        assert_eq!(
+            assert_oracle_value_within_bounds(min - 1, min, max).unwrap_err(),
+            error!(LaminarError::OraclePriceOutOfBounds)
+        );
+        assert_eq!(
+            assert_oracle_value_within_bounds(max + 1, min, max).unwrap_err(),
+            error!(LaminarError::OraclePriceOutOfBounds)
+        );
+
+        let (min, max) = (DEFAULT_MIN_LST_RATE, DEFAULT_MAX_LST_RATE);
+        assert!(assert_oracle_value_within_bounds(min, min, max).is_ok());
+        assert!(assert_oracle_value_within_bounds(max, min, max).is_ok());
+        assert!(assert_oracle_value_within_bounds(min - 1, min, max).is_err());
+        assert!(assert_oracle_value_within_bounds(max + 1, min, max).is_err());
+        // 1 micro-USD SOL and a 100x LST are rejected under the defaults
+        assert!(assert_oracle_value_within_bounds(1, DEFAULT_MIN_SOL_PRICE_USD, DEFAULT_MAX_SOL_PRICE_USD).is_err());
+        assert!(assert_oracle_value_within_bounds(100 * SOL_PRECISION, min, max).is_err());
+
+        // A zero max leaves the upper side open
+        assert!(assert_oracle_value_within_bounds(u64::MAX, min, 0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_oracle_bounds() {
+        assert!(validate_oracle_bounds(10_000, 10_000, 1, 1).is_ok());
+        assert!(validate_oracle_bounds(10_000, 0, 1, 0).is_ok());
+        assert!(validate_oracle_bounds(10_001, 10_000, 1, 2).is_err());
+        assert!(validate_oracle_bounds(1, 2, 3, 2).is_err());
+    }
+
+    #[test]
+    fn test_price_move_within_bound() {
+        assert!(assert_price_move_within_bound(2_000, 2_000).is_ok());
+        assert_eq!(
+            assert_price_move_within_bound(2_001, 2_000).unwrap_err(),
+            error!(LaminarError::PriceMoveTooLarge)
+        );
+        // A 10x move is accepted only with the breaker disabled
+        assert!(assert_price_move_within_bound(90_000, 0).is_ok());
+        assert!(assert_price_move_within_bound(90_000, 2_000).is_err());
+    }
+
+    #[test]
+    fn test_validate_risk_config() {
+        assert!(validate_risk_config(13_000, 15_000).is_ok());
+        assert!(validate_risk_config(10_000, 10_001).is_ok());
+        assert!(validate_risk_config(9_999, 15_000).is_err());
+        assert!(validate_risk_config(15_000, 15_000).is_err());
+    }
+
+    #[test]
+    fn test_validate_fee_config() {
+        assert!(validate_fee_config(50, 25, 30, 15, 10_000, 40_000).is_ok());
+        assert!(validate_fee_config(1_000, 1_000, 1_000, 1_000, 5_000, 10_000).is_ok());
+
+        // Base fee above cap
+        assert!(validate_fee_config(1_001, 25, 30, 15, 10_000, 40_000).is_err());
+        // Inverted multiplier bounds
+        assert!(validate_fee_config(50, 25, 30, 15, 12_000, 40_000).is_err());
+        assert!(validate_fee_config(50, 25, 30, 15, 10_000, 9_000).is_err());
+    }
+
+    #[test]
+    fn test_validate_oracle_config() {
+        assert!(validate_oracle_config(150, 150, 20_000, 1).is_ok());
+        assert!(validate_oracle_config(0, 150, 20_000, 1).is_err());
+        assert!(validate_oracle_config(150, 0, 20_000, 1).is_err());
+        assert!(validate_oracle_config(150, 150, 9_999, 1).is_err());
+        assert!(validate_oracle_config(150, 150, 20_000, 0).is_err());
+    }
+
+    #[test]
+    fn test_validate_args_version() {
+        assert!(validate_args_version(CURRENT_ARGS_VERSION).is_ok());
+        assert!(validate_args_version(0).is_err());
+        assert!(validate_args_version(CURRENT_ARGS_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn test_assert_before_deadline() {
+        assert!(assert_before_deadline(0, u64::MAX).is_ok());
+        assert!(assert_before_deadline(100, 99).is_ok());
+        assert!(assert_before_deadline(100, 100).is_ok());
+        assert!(assert_before_deadline(100, 101).is_err());
+    }
+
+    #[test]
+    fn test_assert_fee_within_max() {
+        assert!(assert_fee_within_max(u64::MAX, 0).is_ok());
+        assert!(assert_fee_within_max(30, 31).is_ok());
+        assert!(assert_fee_within_max(30, 30).is_ok());
+        assert!(assert_fee_within_max(31, 30).is_err());
+    }
+
+    #[test]
+    fn test_assert_cr_drift_within() {
+        assert!(assert_cr_drift_within(15_000, 15_000, 0).is_ok());
+        assert!(assert_cr_drift_within(15_000, 15_010, 10).is_ok());
+        assert!(assert_cr_drift_within(15_000, 14_990, 10).is_ok());
+        assert!(assert_cr_drift_within(15_000, 15_011, 10).is_err());
+        assert!(assert_cr_drift_within(15_000, 14_989, 10).is_err());
+        // No debt on either side reads u64::MAX
+        assert!(assert_cr_drift_within(u64::MAX, u64::MAX, 10).is_ok());
+    }
+
+    #[test]
+    fn test_assert_param_change_unlocked() {
+        let change = ParamChange::TvlCap { max_total_lst_amount: 1_000 };
+        let pending = Box::new(PendingParamChange {
+            proposer: Pubkey::new_unique(),
+            change: change.clone(),
+            queued_slot: 100,
+            eta_slot: 110,
+            bump: 255,
+        });
+        let none = None::<&Box<PendingParamChange>>;
+
+        // No timelock: the setter applies directly
+        assert!(assert_param_change_unlocked(0, none, &change, 100).is_ok());
+        // Timelock on: only the matured, identical queued change unlocks it
+        assert!(assert_param_change_unlocked(10, none, &change, 200).is_err());
+        assert!(assert_param_change_unlocked(10, Some(&pending), &change, 109).is_err());
+        assert!(assert_param_change_unlocked(10, Some(&pending), &change, 110).is_ok());
+        let other = ParamChange::TvlCap { max_total_lst_amount: 2_000 };
+        assert!(assert_param_change_unlocked(10, Some(&pending), &other, 110).is_err());
+    }
+
+    #[test]
+    fn test_is_cap_reduction() {
+        assert!(is_cap_reduction(1_000, 999));
+        assert!(is_cap_reduction(1_000, 1_000));
+        assert!(is_cap_reduction(0, 1_000));
+        assert!(is_cap_reduction(0, 0));
+        assert!(!is_cap_reduction(1_000, 1_001));
+        // 0 lifts the cap
+        assert!(!is_cap_reduction(1_000, 0));
+    }
+
+    #[test]
+    fn test_assert_no_pending_drawdown() {
+        assert!(assert_no_pending_drawdown(13_000, 13_000, 1).is_ok());
+        assert!(assert_no_pending_drawdown(12_999, 13_000, 0).is_ok());
+        assert!(assert_no_pending_drawdown(12_999, 13_000, 1).is_err());
+    }
+
+    #[test]
+    fn test_pause_timeout_boundary() {
+        // Exactly max slots elapsed is still within the window
+        assert!(!pause_timeout_elapsed(1_100, 1_000, 100));
+        assert!(pause_timeout_elapsed(1_101, 1_000, 100));
+        // Clock behind the recorded slot never unlocks
+        assert!(!pause_timeout_elapsed(900, 1_000, 0));
+    }
+
+    #[test]
+    fn test_operation_counter_ceiling() {
+        assert!(assert_operation_counter_sane(0).is_ok());
+        assert!(assert_operation_counter_sane(OPERATION_COUNTER_CEILING - 1).is_ok());
+        assert_eq!(
+            assert_operation_counter_sane(OPERATION_COUNTER_CEILING).unwrap_err(),
+            error!(LaminarError::OperationCounterCorrupt)
+        );
+        assert!(assert_operation_counter_sane(u64::MAX).is_err());
+    }
 
 }
@@ -7,9 +7,21 @@ pub mod instructions;
 pub mod error;
 pub mod events;
 pub mod constants;
+pub mod quote;
+pub mod lst_rate;
+pub mod oracle;
+pub mod pyth;
+pub mod switchboard;
+pub mod collateral;
+pub mod savings;
+pub mod position;
+pub mod redemption;
+#[cfg(feature = "serde")]
+pub mod serde_pubkey;
 // pub mod reentrancy;
 
 use instructions::*;
+pub use instructions::view_protocol_health::ProtocolHealth;
 
 use crate::state::GLOBAL_STATE_SEED;
 
@@ -20,6 +32,9 @@ pub mod laminar {
     // use crate::reentrancy::ReentrancyGuard;
 
     use crate::error::LaminarError;
+    use crate::events::{emit_event, emit_recovery_transition, EventCpi};
+    use crate::invariants::assert_price_move_within_bound;
+    use crate::math::{compute_book_tvl_sol, compute_cr_bps, compute_liability_at_rate_sol, compute_price_deviation_bps};
 
     use super::*;
 
@@ -48,7 +63,25 @@ pub mod laminar {
         instructions::mint_amusd::handler(ctx, lst_amount, min_amusd_out)
     }
 
+    /// Mint amUSD (versioned args)
+    pub fn mint_amusd_v2(
+        ctx: Context<MintAmUSD>,
+        args: MintAmusdArgs,
+    ) -> Result<()> {
+        instructions::mint_amusd::handler_v2(ctx, args)
+    }
+
+    /// Mint amUSD from native SOL, staked into the primary LST or wrapped into the wSOL leg
+    pub fn zap_mint_amusd(
+        ctx: Context<MintAmUSD>,
+        lamports: u64,
+        min_amusd_out: u64,
+    ) -> Result<()> {
+        instructions::mint_amusd::handler_zap(ctx, lamports, min_amusd_out)
+    }
+
     /// Redeem amUSD by burning debt and receiving LST
+    /// (`u64::MAX` redeems the whole token balance)
     pub fn redeem_amusd(
         ctx: Context<RedeemAmUSD>,
         amusd_amount: u64,
@@ -57,6 +90,23 @@ pub mod laminar {
         instructions::redeem_amusd::handler(ctx, amusd_amount, min_lst_out)
     }
 
+    /// Redeem amUSD (versioned args)
+    pub fn redeem_amusd_v2(
+        ctx: Context<RedeemAmUSD>,
+        args: RedeemAmusdArgs,
+    ) -> Result<()> {
+        instructions::redeem_amusd::handler_v2(ctx, args)
+    }
+
+    /// Redeem amUSD out of the wSOL collateral leg, unwrapped to native SOL
+    pub fn redeem_amusd_to_sol(
+        ctx: Context<RedeemAmUSD>,
+        amusd_amount: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        instructions::redeem_amusd::handler_to_sol(ctx, amusd_amount, min_sol_out)
+    }
+
     /// Mint aSOL by depositing LST collateral at NAV
     pub fn mint_asol(
         ctx: Context<MintAsol>,
@@ -66,7 +116,64 @@ pub mod laminar {
         instructions::mint_asol::handler(ctx, lst_amount, min_asol_out)
     }
 
+    /// Mint aSOL (versioned args)
+    pub fn mint_asol_v2(
+        ctx: Context<MintAsol>,
+        args: MintAsolArgs,
+    ) -> Result<()> {
+        instructions::mint_asol::handler_v2(ctx, args)
+    }
+
+    /// Mint aSOL from native SOL, staked into the primary LST or wrapped into the wSOL leg
+    pub fn zap_mint_asol(
+        ctx: Context<MintAsol>,
+        lamports: u64,
+        min_asol_out: u64,
+    ) -> Result<()> {
+        instructions::mint_asol::handler_zap(ctx, lamports, min_asol_out)
+    }
+
+    /// Mint amUSD and aSOL from one LST deposit, split so CR is unchanged
+    pub fn mint_pair(
+        ctx: Context<MintPair>,
+        lst_amount: u64,
+        min_amusd_out: u64,
+        min_asol_out: u64,
+    ) -> Result<()> {
+        instructions::mint_pair::handler(ctx, lst_amount, min_amusd_out, min_asol_out)
+    }
+
+    /// Redeem amUSD and aSOL together, keeping CR within `pair_cr_tolerance_bps`;
+    /// `asol_amount` 0 sizes the aSOL leg to hold CR
+    pub fn redeem_pair(
+        ctx: Context<RedeemPair>,
+        amusd_amount: u64,
+        asol_amount: u64,
+        min_lst_out: u64,
+    ) -> Result<()> {
+        instructions::redeem_pair::handler(ctx, amusd_amount, asol_amount, min_lst_out)
+    }
+
+    /// Convert amUSD into aSOL at protocol prices; no LST moves
+    pub fn convert_amusd_to_asol(
+        ctx: Context<Convert>,
+        amusd_amount: u64,
+        min_asol_out: u64,
+    ) -> Result<()> {
+        instructions::convert::handler_amusd_to_asol(ctx, amusd_amount, min_asol_out)
+    }
+
+    /// Convert aSOL into amUSD at protocol prices; CR must stay above `min_cr_bps`
+    pub fn convert_asol_to_amusd(
+        ctx: Context<Convert>,
+        asol_amount: u64,
+        min_amusd_out: u64,
+    ) -> Result<()> {
+        instructions::convert::handler_asol_to_amusd(ctx, asol_amount, min_amusd_out)
+    }
+
     /// Redeem aSOL by burning equity and receiving LST at NAV
+    /// (`u64::MAX` redeems the whole token balance)
     pub fn redeem_asol(
         ctx: Context<RedeemAsol>,
         asol_amount: u64,
@@ -75,6 +182,42 @@ pub mod laminar {
         instructions::redeem_asol::handler(ctx, asol_amount, min_lst_out)
     }
 
+    /// Redeem aSOL (versioned args)
+    pub fn redeem_asol_v2(
+        ctx: Context<RedeemAsol>,
+        args: RedeemAsolArgs,
+    ) -> Result<()> {
+        instructions::redeem_asol::handler_v2(ctx, args)
+    }
+
+    /// Redeem aSOL out of the wSOL collateral leg, unwrapped to native SOL
+    pub fn redeem_asol_to_sol(
+        ctx: Context<RedeemAsol>,
+        asol_amount: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        instructions::redeem_asol::handler_to_sol(ctx, asol_amount, min_sol_out)
+    }
+
+    /// Escrow amUSD or aSOL in a RedemptionTicket, redeemed later by process_redemption
+    pub fn request_redemption(
+        ctx: Context<RequestRedemption>,
+        amount: u64,
+        min_lst_out: u64,
+    ) -> Result<()> {
+        instructions::request_redemption::handler(ctx, amount, min_lst_out)
+    }
+
+    /// Return a queued ticket's tokens to its owner (owner, or anyone once expired)
+    pub fn cancel_redemption(ctx: Context<CancelRedemption>) -> Result<()> {
+        instructions::cancel_redemption::handler(ctx)
+    }
+
+    /// Redeem the ticket at the head of the redemption queue (permissionless)
+    pub fn process_redemption(ctx: Context<ProcessRedemption>) -> Result<()> {
+        instructions::process_redemption::handler(ctx)
+    }
+
     /// Emergency pause control (admin only)
     pub fn emergency_pause(
         ctx: Context<EmergencyPause>,
@@ -82,38 +225,92 @@ pub mod laminar {
         redeem_paused: bool,
     ) -> Result<()> {
         let global_state = &mut ctx.accounts.global_state;
+        // Settlement closes minting for good.
+        require!(mint_paused || !global_state.settled, LaminarError::ProtocolSettled);
+        // Re-pausing an already paused redeem path keeps the original start slot
+        if !redeem_paused {
+            global_state.pause_started_slot = 0;
+        } else if !global_state.redeem_paused {
+            global_state.pause_started_slot = ctx.accounts.clock.slot;
+        }
         global_state.mint_paused = mint_paused;
         global_state.redeem_paused = redeem_paused;
-        global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+        // An explicit admin decision supersedes any automatic freeze pause
+        global_state.redeem_paused_by_vault_freeze = false;
+        global_state.increment_operation_counter()?;
 
-        emit!(crate::events::EmergencyPause {
+        emit_event!(crate::events::EmergencyPause {
             authority: ctx.accounts.authority.key(),
             mint_paused,
             redeem_paused,
+            operation_counter: global_state.operation_counter,
             timestamp: ctx.accounts.clock.unix_timestamp,
         });
         Ok(())
     }
 
+    /// Set the mock SOL price and LST rate; moves past max_price_move_bps need `force` while fully paused (admin only)
     pub fn update_mock_prices(
         ctx: Context<UpdateMockPrices>,
         new_sol_price_usd: u64,
         new_lst_to_sol_rate: u64,
         new_oracle_confidence_usd: u64,
+        force: bool,
     ) -> Result<()> {
         let global_state = &mut ctx.accounts.global_state;
+        // Settled books price off the frozen snapshot; nothing may move it.
+        require!(!global_state.settled, LaminarError::ProtocolSettled);
         
         require!(new_sol_price_usd > 0, LaminarError::ZeroAmount);
         require!(new_lst_to_sol_rate > 0, LaminarError::ZeroAmount);
+        // A live rate source owns the rate; only sync_exchange_rate may move it.
+        require!(
+            !global_state.has_lst_rate_source()
+                || new_lst_to_sol_rate == global_state.mock_lst_to_sol_rate,
+            LaminarError::InvalidParameter
+        );
         
+        global_state.assert_sol_price_within_bounds(new_sol_price_usd)?;
+        global_state.assert_lst_rate_within_bounds(new_lst_to_sol_rate)?;
+
         let old_sol_price = global_state.mock_sol_price_usd;
         let old_lst_rate = global_state.mock_lst_to_sol_rate;
+
+        let sol_price_move_bps = compute_price_deviation_bps(old_sol_price, new_sol_price_usd)
+            .ok_or(LaminarError::MathOverflow)?;
+        let lst_rate_move_bps = compute_price_deviation_bps(old_lst_rate, new_lst_to_sol_rate)
+            .ok_or(LaminarError::MathOverflow)?;
+
+        if force {
+            // Re-basing past the breaker is only safe while nothing can trade on it.
+            require!(
+                global_state.mint_paused && global_state.redeem_paused,
+                LaminarError::ForcedPriceUpdateNotPaused
+            );
+        } else {
+            assert_price_move_within_bound(sol_price_move_bps, global_state.max_price_move_bps)?;
+            assert_price_move_within_bound(lst_rate_move_bps, global_state.max_price_move_bps)?;
+        }
         
+        // The old price held until now; fold it in before replacing it.
+        global_state.refresh_ema_price(ctx.accounts.clock.slot)?;
         global_state.mock_sol_price_usd = new_sol_price_usd;
         global_state.mock_lst_to_sol_rate = new_lst_to_sol_rate;
-        global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+        global_state.increment_operation_counter()?;
         global_state.mock_oracle_confidence_usd = new_oracle_confidence_usd;
         global_state.last_oracle_update_slot = ctx.accounts.clock.slot;
+        global_state.last_oracle_update_timestamp = ctx.accounts.clock.unix_timestamp;
+        global_state.refresh_uncertainty_index()?;
+
+        // A price move is what usually carries CR across min.
+        let tvl = compute_book_tvl_sol(global_state.total_lst_amount, new_lst_to_sol_rate, global_state.weighted_collateral_tvl_lamports)
+            .ok_or(LaminarError::MathOverflow)?;
+        let liability = compute_liability_at_rate_sol(global_state.amusd_supply, new_sol_price_usd, global_state.redemption_rate_bps)
+            .ok_or(LaminarError::MathOverflow)?;
+        let cr_bps = compute_cr_bps(tvl, liability);
+        let recovery = global_state.track_recovery_mode(cr_bps, ctx.accounts.clock.slot);
+        let event_cpi = EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        emit_recovery_transition(&event_cpi, recovery, cr_bps, global_state.min_cr_bps, global_state.operation_counter, &ctx.accounts.clock)?;
 
         msg!(
             "Oracle snapshot updated: slot={}, price={}, conf={}, lst_rate={}",
@@ -124,17 +321,234 @@ pub mod laminar {
         );
 
         
-        emit!(crate::events::OraclePriceUpdated {
+        emit_event!(crate::events::OraclePriceUpdated {
             authority: ctx.accounts.authority.key(),
             old_sol_price,
             new_sol_price: new_sol_price_usd,
             old_lst_rate,
             new_lst_rate: new_lst_to_sol_rate,
+            sol_price_move_bps,
+            lst_rate_move_bps,
+            forced: force,
+            operation_counter: global_state.operation_counter,
             timestamp: ctx.accounts.clock.unix_timestamp,
         });
         
         Ok(())
     }
+
+    /// Set the mock oracle confidence width and refresh the oracle slot (admin only)
+    pub fn update_mock_oracle_confidence(
+        ctx: Context<UpdateMockOracleConfidence>,
+        new_confidence_usd: u64,
+    ) -> Result<()> {
+        instructions::update_mock_oracle_confidence::handler(ctx, new_confidence_usd)
+    }
+
+    /// Select the source redemptions price from while the primary is stale (admin only)
+    pub fn set_fallback_price_source(
+        ctx: Context<SetFallbackPriceSource>,
+        new_fallback_price_source: Option<u8>,
+        new_fallback_uncertainty_bps: u64,
+    ) -> Result<()> {
+        instructions::set_fallback_price_source::handler(ctx, new_fallback_price_source, new_fallback_uncertainty_bps)
+    }
+
+    /// Set the SOL price EMA half-life in slots; zero disables smoothing (admin only)
+    pub fn update_ema_half_life(
+        ctx: Context<UpdateEmaHalfLife>,
+        new_ema_half_life_slots: u64,
+    ) -> Result<()> {
+        instructions::update_ema_half_life::handler(ctx, new_ema_half_life_slots)
+    }
+
+    /// Set the largest discount to NAV a rebalance pays from the treasury (admin only)
+    pub fn update_rebalance_discount(
+        ctx: Context<UpdateRebalanceDiscount>,
+        new_rebalance_discount_bps: u64,
+    ) -> Result<()> {
+        instructions::update_rebalance_discount::handler(ctx, new_rebalance_discount_bps)
+    }
+
+    /// Set the recovery-mode aSOL mint discount to NAV (admin only)
+    pub fn update_recovery_bonus(
+        ctx: Context<UpdateRecoveryBonus>,
+        new_recovery_bonus_bps: u64,
+    ) -> Result<()> {
+        instructions::update_recovery_bonus::handler(ctx, new_recovery_bonus_bps)
+    }
+
+    /// Freeze price and LST rate, close minting for good and enter global settlement (admin only)
+    pub fn initiate_settlement(ctx: Context<InitiateSettlement>) -> Result<()> {
+        instructions::initiate_settlement::handler(ctx)
+    }
+
+    /// Burn amUSD/aSOL for a pro-rata share of the vault after settlement
+    pub fn claim_settlement(
+        ctx: Context<ClaimSettlement>,
+        amusd_amount: u64,
+        asol_amount: u64,
+    ) -> Result<()> {
+        instructions::claim_settlement::handler(ctx, amusd_amount, asol_amount)
+    }
+
+    /// Deposit LST into the insurance fund (permissionless)
+    pub fn fund_insurance(ctx: Context<FundInsurance>, lst_amount: u64) -> Result<()> {
+        instructions::fund_insurance::handler(ctx, lst_amount)
+    }
+
+    /// Set the share of mint/redeem fees booked to the insurance fund (admin only)
+    pub fn update_fee_split(
+        ctx: Context<UpdateFeeSplit>,
+        new_fee_split_insurance_bps: u64,
+    ) -> Result<()> {
+        instructions::update_fee_split::handler(ctx, new_fee_split_insurance_bps)
+    }
+
+    /// Write amUSD down to a uniform redemption rate once CR is below 100% (admin only)
+    pub fn socialize_bad_debt(ctx: Context<SocializeBadDebt>) -> Result<()> {
+        instructions::socialize_bad_debt::handler(ctx)
+    }
+
+    /// Lend vault LST until a later flash_repay_lst in the same transaction
+    pub fn flash_loan_lst(ctx: Context<FlashLoanLst>, lst_amount: u64) -> Result<()> {
+        instructions::flash_loan_lst::handler(ctx, lst_amount)
+    }
+
+    /// Repay the outstanding flash loan plus its fee
+    pub fn flash_repay_lst(ctx: Context<FlashRepayLst>) -> Result<()> {
+        instructions::flash_repay_lst::handler(ctx)
+    }
+
+    /// Whitelist another LST as collateral with its own vault (admin only)
+    pub fn add_collateral_type(ctx: Context<AddCollateralType>, lst_to_sol_rate: u64, collateral_weight_bps: u64) -> Result<()> {
+        instructions::add_collateral_type::handler(ctx, lst_to_sol_rate, collateral_weight_bps)
+    }
+
+    /// Disable deposits into, or deprecate, a collateral type (admin only)
+    pub fn set_collateral_status(ctx: Context<SetCollateralStatus>, lst_mint: Pubkey, new_status: u8) -> Result<()> {
+        instructions::set_collateral_status::handler(ctx, lst_mint, new_status)
+    }
+
+    /// Re-sum the cached collateral totals from every CollateralVault, passed as remaining accounts (permissionless)
+    pub fn refresh_tvl<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshTvl<'info>>) -> Result<()> {
+        instructions::refresh_tvl::handler(ctx)
+    }
+
+    /// Swap collateral out of one LST vault for the same SOL value of another LST (admin only)
+    pub fn migrate_collateral(ctx: Context<MigrateCollateral>, lst_amount: u64, from_lst: Pubkey, to_lst: Pubkey) -> Result<()> {
+        instructions::migrate_collateral::handler(ctx, lst_amount, from_lst, to_lst)
+    }
+
+    /// Create the Peg Stability Module's USDC vault and set its fee and debt ceiling (admin only)
+    pub fn initialize_psm(ctx: Context<InitializePsm>, psm_fee_bps: u64, psm_debt_ceiling: u64) -> Result<()> {
+        instructions::initialize_psm::handler(ctx, psm_fee_bps, psm_debt_ceiling)
+    }
+
+    /// Update the PSM fee and debt ceiling (admin only)
+    pub fn update_psm_parameters(ctx: Context<UpdatePsmParameters>, new_psm_fee_bps: u64, new_psm_debt_ceiling: u64) -> Result<()> {
+        instructions::update_psm_parameters::handler(ctx, new_psm_fee_bps, new_psm_debt_ceiling)
+    }
+
+    /// Swap USDC for amUSD at par through the PSM
+    pub fn psm_swap_usdc_for_amusd(ctx: Context<PsmSwapUsdcForAmusd>, usdc_amount: u64) -> Result<()> {
+        instructions::psm_swap_usdc_for_amusd::handler(ctx, usdc_amount)
+    }
+
+    /// Swap amUSD for the PSM's USDC at par
+    pub fn psm_swap_amusd_for_usdc(ctx: Context<PsmSwapAmusdForUsdc>, amusd_amount: u64) -> Result<()> {
+        instructions::psm_swap_amusd_for_usdc::handler(ctx, amusd_amount)
+    }
+
+    /// Create the Savings Pool and its amUSD vault (admin only)
+    pub fn initialize_savings_pool(ctx: Context<InitializeSavingsPool>) -> Result<()> {
+        instructions::initialize_savings_pool::handler(ctx)
+    }
+
+    /// Set the share of amUSD treasury fees paid into the Savings Pool (admin only)
+    pub fn update_savings_fee_split(ctx: Context<UpdateSavingsFeeSplit>, new_savings_fee_split_bps: u64) -> Result<()> {
+        instructions::update_savings_fee_split::handler(ctx, new_savings_fee_split_bps)
+    }
+
+    /// Deposit amUSD into the Savings Pool for shares
+    pub fn deposit_savings(ctx: Context<DepositSavings>, amount: u64) -> Result<()> {
+        instructions::deposit_savings::handler(ctx, amount)
+    }
+
+    /// Burn Savings Pool shares for amUSD at the current share price
+    pub fn withdraw_savings(ctx: Context<WithdrawSavings>, shares: u64) -> Result<()> {
+        instructions::withdraw_savings::handler(ctx, shares)
+    }
+
+    /// Mint the stability fee accrued on amUSD debt to the treasury (permissionless)
+    pub fn accrue_stability_fee(ctx: Context<AccrueStabilityFee>) -> Result<()> {
+        instructions::accrue_stability_fee::handler(ctx)
+    }
+
+    /// Set the annual stability fee on amUSD debt (admin only)
+    pub fn update_stability_fee(ctx: Context<UpdateStabilityFee>, new_stability_fee_bps_per_year: u64) -> Result<()> {
+        instructions::update_stability_fee::handler(ctx, new_stability_fee_bps_per_year)
+    }
+
+    /// Set the amUSD and aSOL supply caps; 0 = unlimited (admin only)
+    pub fn update_supply_caps(ctx: Context<UpdateSupplyCaps>, new_amusd_supply_cap: u64, new_asol_supply_cap: u64) -> Result<()> {
+        instructions::update_supply_caps::handler(ctx, new_amusd_supply_cap, new_asol_supply_cap)
+    }
+
+    /// Set the LST ceiling a vault may reach through mints; 0 = unlimited (admin only)
+    pub fn update_tvl_cap(ctx: Context<UpdateTvlCap>, new_max_total_lst_amount: u64) -> Result<()> {
+        instructions::update_tvl_cap::handler(ctx, new_max_total_lst_amount)
+    }
+
+    /// Set the per-transaction deposit and redemption limits; 0 = unlimited (admin only)
+    pub fn update_transaction_limits(
+        ctx: Context<UpdateTransactionLimits>,
+        new_max_single_deposit_lst: u64,
+        new_max_single_redeem_units: u64,
+    ) -> Result<()> {
+        instructions::update_transaction_limits::handler(ctx, new_max_single_deposit_lst, new_max_single_redeem_units)
+    }
+
+    /// Set the cumulative amUSD each wallet may mint; 0 = unlimited (admin only)
+    pub fn update_per_user_cap(ctx: Context<UpdatePerUserCap>, new_per_user_amusd_cap: u64) -> Result<()> {
+        instructions::update_per_user_cap::handler(ctx, new_per_user_amusd_cap)
+    }
+
+    /// Set the per-round aSOL mint cap (0 = unlimited) and the round length in slots (admin only)
+    pub fn update_mint_round(ctx: Context<UpdateMintRound>, new_max_asol_mint_per_round: u64, new_mint_round_slots: u64) -> Result<()> {
+        instructions::update_mint_round::handler(ctx, new_max_asol_mint_per_round, new_mint_round_slots)
+    }
+
+    /// Set the slots one wallet must leave between a mint and a redeem; 0 disables (admin only)
+    pub fn update_min_action_gap(ctx: Context<UpdateMinActionGap>, new_min_action_gap_slots: u64) -> Result<()> {
+        instructions::update_min_action_gap::handler(ctx, new_min_action_gap_slots)
+    }
+
+    /// Set the slots a wallet waits after minting aSOL before redeeming it; 0 disables (admin only)
+    pub fn update_asol_redeem_cooldown(ctx: Context<UpdateAsolRedeemCooldown>, new_asol_redeem_cooldown_slots: u64) -> Result<()> {
+        instructions::update_asol_redeem_cooldown::handler(ctx, new_asol_redeem_cooldown_slots)
+    }
+
+    /// Set the largest CR move a redeem_pair may cause, in bps (admin only)
+    pub fn update_pair_cr_tolerance(ctx: Context<UpdatePairCrTolerance>, new_pair_cr_tolerance_bps: u64) -> Result<()> {
+        instructions::update_pair_cr_tolerance::handler(ctx, new_pair_cr_tolerance_bps)
+    }
+
+    /// Set the max wall-clock oracle age in seconds (admin only)
+    pub fn update_max_oracle_age(
+        ctx: Context<UpdateMaxOracleAge>,
+        new_max_oracle_age_seconds: u64,
+    ) -> Result<()> {
+        instructions::update_max_oracle_age::handler(ctx, new_max_oracle_age_seconds)
+    }
+
+    /// Bound the per-update move of the mock price and rate; zero disables the breaker (admin only)
+    pub fn update_max_price_move(
+        ctx: Context<UpdateMaxPriceMove>,
+        new_max_price_move_bps: u64,
+    ) -> Result<()> {
+        instructions::update_max_price_move::handler(ctx, new_max_price_move_bps)
+    }
     
     /// Update risk parameters (admin only)
     pub fn update_parameters(
@@ -142,35 +556,292 @@ pub mod laminar {
         new_min_cr_bps: u64,
         new_target_cr_bps: u64,
     ) -> Result<()> {
-        require!(new_min_cr_bps >= 10_000, LaminarError::InvalidParameter);
-        require!(new_target_cr_bps > new_min_cr_bps, LaminarError::InvalidParameter);
+        crate::invariants::validate_risk_config(new_min_cr_bps, new_target_cr_bps)?;
         
         let global_state = &mut ctx.accounts.global_state;
+        require!(global_state.param_timelock_slots == 0, LaminarError::TimelockActive);
         
         let old_min = global_state.min_cr_bps;
         let old_target = global_state.target_cr_bps;
         
         global_state.min_cr_bps = new_min_cr_bps;
         global_state.target_cr_bps = new_target_cr_bps;
-        global_state.operation_counter = global_state.operation_counter.saturating_add(1);
+        global_state.increment_operation_counter()?;
         
-        emit!(crate::events::ParametersUpdated {
+        emit_event!(crate::events::ParametersUpdated {
             authority: ctx.accounts.authority.key(),
             old_min_cr_bps: old_min,
             new_min_cr_bps,
             old_target_cr_bps: old_target,
             new_target_cr_bps,
+            operation_counter: global_state.operation_counter,
             timestamp: ctx.accounts.clock.unix_timestamp,
         });
         
         Ok(())
     }
 
-    pub fn sync_exchange_rate(ctx: Context<SyncExchangeRate>) -> Result<()> {
+    pub fn sync_exchange_rate<'info>(ctx: Context<'_, '_, 'info, 'info, SyncExchangeRate<'info>>) -> Result<()> {
         instructions::sync_exchange_rate::handler(ctx)
     }
+
+    /// Apply the complete launch configuration atomically (admin only, pre-launch)
+    pub fn configure_launch(
+        ctx: Context<ConfigureLaunch>,
+        config: LaunchConfig,
+    ) -> Result<()> {
+        instructions::configure_launch::handler(ctx, config)
+    }
+
+    /// Update base fees and fee multiplier bounds (admin only)
+    pub fn update_fee_parameters(
+        ctx: Context<UpdateFeeParameters>,
+        fee_amusd_mint_bps: u64,
+        fee_amusd_redeem_bps: u64,
+        fee_asol_mint_bps: u64,
+        fee_asol_redeem_bps: u64,
+        fee_min_multiplier_bps: u64,
+        fee_max_multiplier_bps: u64,
+    ) -> Result<()> {
+        instructions::update_fee_parameters::handler(
+            ctx,
+            fee_amusd_mint_bps,
+            fee_amusd_redeem_bps,
+            fee_asol_mint_bps,
+            fee_asol_redeem_bps,
+            fee_min_multiplier_bps,
+            fee_max_multiplier_bps,
+        )
+    }
+
+    /// Rotate the fee treasury (admin only)
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        treasury_is_pda: bool,
+        treasury_owner_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_treasury::handler(ctx, treasury_is_pda, treasury_owner_program)
+    }
+
+    /// Update oracle staleness/confidence/uncertainty bounds (admin only)
+    pub fn update_oracle_parameters(
+        ctx: Context<UpdateOracleParameters>,
+        max_oracle_staleness_slots: u64,
+        max_conf_bps: u64,
+        uncertainty_max_bps: u64,
+        max_lst_stale_epochs: u64,
+    ) -> Result<()> {
+        instructions::update_oracle_parameters::handler(
+            ctx,
+            max_oracle_staleness_slots,
+            max_conf_bps,
+            uncertainty_max_bps,
+            max_lst_stale_epochs,
+        )
+    }
+
+    /// Update absolute SOL price / LST rate sanity bounds (admin only)
+    pub fn update_oracle_bounds(
+        ctx: Context<UpdateOracleBounds>,
+        min_sol_price_usd: u64,
+        max_sol_price_usd: u64,
+        min_lst_rate: u64,
+        max_lst_rate: u64,
+    ) -> Result<()> {
+        instructions::update_oracle_bounds::handler(
+            ctx,
+            min_sol_price_usd,
+            max_sol_price_usd,
+            min_lst_rate,
+            max_lst_rate,
+        )
+    }
+
+    /// Raise or lower the rounding reserve cap (admin only)
+    pub fn update_rounding_reserve_cap(
+        ctx: Context<UpdateRoundingReserveCap>,
+        new_max_rounding_reserve_lamports: u64,
+    ) -> Result<()> {
+        instructions::update_rounding_reserve_cap::handler(ctx, new_max_rounding_reserve_lamports)
+    }
+
+    /// Propose a new admin authority (step 1 of 2, current admin only)
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept a proposed admin authority (step 2 of 2, pending authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Burn sub-minimum amUSD/aSOL balances into the rounding reserve
+    pub fn donate_dust(
+        ctx: Context<DonateDust>,
+        token: DustToken,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::donate_dust::handler(ctx, token, amount)
+    }
+
+    /// Create the Stability Pool and its amUSD vault (admin only, once)
+    pub fn initialize_stability_pool(ctx: Context<InitializeStabilityPool>) -> Result<()> {
+        instructions::initialize_stability_pool::handler(ctx)
+    }
+
+    /// Deposit amUSD into the Stability Pool for pool shares
+    pub fn deposit_stability(ctx: Context<DepositStability>, amount: u64) -> Result<()> {
+        instructions::deposit_stability::handler(ctx, amount)
+    }
+
+    /// Withdraw amUSD and accrued LST gains from the Stability Pool
+    pub fn withdraw_stability(ctx: Context<WithdrawStability>, amount: u64) -> Result<()> {
+        instructions::withdraw_stability::handler(ctx, amount)
+    }
+
+    /// Burn Stability Pool amUSD against a sub-min CR before holders take haircuts (permissionless)
+    pub fn trigger_drawdown(ctx: Context<TriggerDrawdown>) -> Result<()> {
+        instructions::trigger_drawdown::handler(ctx)
+    }
+
+    /// Claim a Stability Pool position's accrued LST gains without withdrawing
+    pub fn claim_stability_gains(ctx: Context<ClaimStabilityGains>) -> Result<()> {
+        instructions::claim_stability_gains::handler(ctx)
+    }
+
+    /// Deposit LST for discounted aSOL while CR is below min, up to min_cr_bps (permissionless)
+    pub fn rebalance(
+        ctx: Context<Rebalance>,
+        max_lst_amount: u64,
+        min_asol_out: u64,
+    ) -> Result<()> {
+        instructions::rebalance::handler(ctx, max_lst_amount, min_asol_out)
+    }
+
+    /// Lift a redeem pause that outlived max_pause_duration_slots (permissionless)
+    pub fn force_unpause_redeem(ctx: Context<ForceUnpauseRedeem>) -> Result<()> {
+        instructions::force_unpause_redeem::handler(ctx)
+    }
+
+    /// Set how long a redeem pause may last before anyone can lift it (admin only)
+    pub fn update_max_pause_duration(
+        ctx: Context<UpdateMaxPauseDuration>,
+        new_max_pause_duration_slots: u64,
+    ) -> Result<()> {
+        instructions::update_max_pause_duration::handler(ctx, new_max_pause_duration_slots)
+    }
+
+    /// Queue a timelocked admin parameter change (admin only)
+    pub fn queue_parameter_update(
+        ctx: Context<QueueParameterUpdate>,
+        change: state::ParamChange,
+    ) -> Result<()> {
+        instructions::queue_parameter_update::handler(ctx, change)
+    }
+
+    /// Apply a queued fee/CR/oracle/timelock/price-feed change once its ETA has passed (permissionless)
+    pub fn execute_parameter_update(ctx: Context<ExecuteParameterUpdate>, kind: u8) -> Result<()> {
+        instructions::execute_parameter_update::handler(ctx, kind)
+    }
+
+    /// Abort a queued change (admin only)
+    pub fn cancel_parameter_update(ctx: Context<CancelParameterUpdate>, kind: u8) -> Result<()> {
+        instructions::cancel_parameter_update::handler(ctx, kind)
+    }
+
+    /// Emit a HealthSnapshot; pauses redemptions while the vault is frozen (permissionless)
+    pub fn health_check(ctx: Context<HealthCheck>) -> Result<()> {
+        instructions::health_check::handler(ctx)
+    }
+
+    /// Configure the SOL/USD Pyth feed, selecting it over the mock price; the default pubkey clears it (admin only)
+    pub fn set_pyth_price_feed(ctx: Context<SetPythPriceFeed>, new_pyth_price_feed: Pubkey) -> Result<()> {
+        instructions::set_pyth_price_feed::handler(ctx, new_pyth_price_feed)
+    }
+
+    /// Validate GlobalState after an upgrade and reset a corrupt operation_counter (admin only)
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        instructions::migrate_global_state::handler(ctx)
+    }
+
+    /// Configure the SOL/USD Switchboard pull feed, selecting it over the mock price; the default pubkey clears it (admin only)
+    pub fn set_switchboard_feed(ctx: Context<SetSwitchboardFeed>, new_switchboard_feed: Pubkey) -> Result<()> {
+        instructions::set_switchboard_feed::handler(ctx, new_switchboard_feed)
+    }
+
+    /// Derive the LST rate from an SPL stake pool, or the mock rate with the default pubkey (admin only)
+    pub fn set_lst_stake_pool(ctx: Context<SetLstStakePool>, new_lst_stake_pool: Pubkey) -> Result<()> {
+        instructions::set_lst_stake_pool::handler(ctx, new_lst_stake_pool)
+    }
+
+    /// Derive the LST rate from a Marinade state account, or the mock rate with the default pubkey (admin only)
+    pub fn set_marinade_state(ctx: Context<SetMarinadeState>, new_marinade_state: Pubkey) -> Result<()> {
+        instructions::set_marinade_state::handler(ctx, new_marinade_state)
+    }
+
+    /// Bound the haircut shortfall per insolvency episode and resume a tripped amUSD redeem (admin only)
+    pub fn set_haircut_breaker(ctx: Context<SetHaircutBreaker>, new_max_episode_haircut_lamports: u64) -> Result<()> {
+        instructions::set_haircut_breaker::handler(ctx, new_max_episode_haircut_lamports)
+    }
+
+    /// Derive the LST rate from a Sanctum calculator CPI, or the mock rate with the default program id (admin only)
+    pub fn set_lst_calculator(
+        ctx: Context<SetLstCalculator>,
+        new_lst_calculator_program: Pubkey,
+        new_lst_calculator_state: Pubkey,
+    ) -> Result<()> {
+        instructions::set_lst_calculator::handler(ctx, new_lst_calculator_program, new_lst_calculator_state)
+    }
+
+    /// Sample the dynamic fee of an action across CR via return data (read-only, permissionless)
+    pub fn fee_curve(ctx: Context<FeeCurve>, action: u8) -> Result<FeeCurveSample> {
+        instructions::fee_curve::handler(ctx, action)
+    }
+
+    /// Price a `mint_amusd` of `lst_amount` via return data without executing it (read-only, permissionless)
+    pub fn quote_mint_amusd(ctx: Context<QuoteTrade>, lst_amount: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::handler_mint_amusd(ctx, lst_amount)
+    }
+
+    /// Price a `redeem_amusd` of `amusd_amount` via return data without executing it (read-only, permissionless)
+    pub fn quote_redeem_amusd(ctx: Context<QuoteTrade>, amusd_amount: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::handler_redeem_amusd(ctx, amusd_amount)
+    }
+
+    /// Price a `mint_asol` of `lst_amount` via return data without executing it (read-only, permissionless)
+    pub fn quote_mint_asol(ctx: Context<QuoteTrade>, lst_amount: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::handler_mint_asol(ctx, lst_amount)
+    }
+
+    /// Price a `redeem_asol` of `asol_amount` via return data without executing it (read-only, permissionless)
+    pub fn quote_redeem_asol(ctx: Context<QuoteTrade>, asol_amount: u64) -> Result<TradeQuote> {
+        instructions::quote_trade::handler_redeem_asol(ctx, asol_amount)
+    }
+
+    /// Return TVL, liability, CR, NAVs and claimable equity via return data (read-only, permissionless)
+    pub fn view_protocol_health(ctx: Context<ViewProtocolHealth>) -> Result<ProtocolHealth> {
+        instructions::view_protocol_health::handler(ctx)
+    }
+
+    /// Select the SOL/USD source (0 = mock, 1 = Pyth, 2 = Switchboard, 3 = stake pool) among configured feeds (admin only)
+    pub fn set_price_source(ctx: Context<SetPriceSource>, new_price_source: u8) -> Result<()> {
+        instructions::set_price_source::handler(ctx, new_price_source)
+    }
+
+    /// Cross-check SOL/USD against a second configured feed (0 = off, 1 = Pyth, 2 = Switchboard) (admin only)
+    pub fn set_secondary_price_source(
+        ctx: Context<SetSecondaryPriceSource>,
+        new_secondary_price_source: u8,
+        new_max_oracle_deviation_bps: u64,
+    ) -> Result<()> {
+        instructions::set_secondary_price_source::handler(ctx, new_secondary_price_source, new_max_oracle_deviation_bps)
+    }
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct EmergencyPause<'info> {
     #[account(mut)]
@@ -187,6 +858,7 @@ pub struct EmergencyPause<'info> {
 }
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateMockPrices<'info> {
     #[account(mut)]
@@ -203,6 +875,7 @@ pub struct UpdateMockPrices<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateParameters<'info> {
     #[account(mut)]
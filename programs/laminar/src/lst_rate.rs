@@ -0,0 +1,511 @@
+//! LST -> SOL exchange-rate sources
+//! `sync_exchange_rate` reads the configured source and writes the rate into
+//! `GlobalState::mock_lst_to_sol_rate` at `SOL_PRECISION` scale. Supported
+//! sources are an SPL stake pool, the Marinade state account (mSOL), and a
+//! Sanctum SOL value calculator program for any other LST. While no source
+//! is configured the admin-set mock rate is used unchanged.
+//!
+//! The stake pool is also the primary LST's entry and exit: the SOL zap
+//! stakes through its `DepositSol` (`StakePoolDeposit`), and redeem-to-SOL
+//! unstakes the payout instantly through its `WithdrawSol` (`StakePoolWithdraw`).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+  instruction::{AccountMeta, Instruction},
+  program::{get_return_data, invoke},
+};
+
+use crate::error::LaminarError;
+use crate::math::{mul_div_down, BPS_PRECISION, SOL_PRECISION};
+
+/// SPL stake pool program
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// `AccountType::StakePool` tag of the SPL stake pool layout
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Leading fields of the SPL stake pool `StakePool` account, up to
+/// `last_update_epoch`. Borsh reads the prefix and ignores the rest.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StakePoolHeader {
+  pub account_type: u8,
+  pub manager: Pubkey,
+  pub staker: Pubkey,
+  pub stake_deposit_authority: Pubkey,
+  pub stake_withdraw_bump_seed: u8,
+  pub validator_list: Pubkey,
+  pub reserve_stake: Pubkey,
+  pub pool_mint: Pubkey,
+  pub manager_fee_account: Pubkey,
+  pub token_program_id: Pubkey,
+  pub total_lamports: u64,
+  pub pool_token_supply: u64,
+  pub last_update_epoch: u64,
+}
+
+/// LST -> SOL rate of a stake pool at `SOL_PRECISION` scale, rounded down so
+/// collateral is never overvalued. `None` for an empty pool.
+pub fn stake_pool_rate(total_lamports: u64, pool_token_supply: u64) -> Option<u64> {
+  if pool_token_supply == 0 || total_lamports == 0 {
+    return None;
+  }
+  mul_div_down(total_lamports, SOL_PRECISION, pool_token_supply)
+}
+
+/// Decode an SPL stake pool account, checking its owner, type and mint.
+fn read_stake_pool_header(stake_pool: &AccountInfo, lst_mint: &Pubkey) -> Result<StakePoolHeader> {
+  require_keys_eq!(*stake_pool.owner, SPL_STAKE_POOL_PROGRAM_ID, LaminarError::InvalidStakePool);
+
+  let data = stake_pool.try_borrow_data()?;
+  let header = StakePoolHeader::deserialize(&mut &data[..])
+    .map_err(|_| error!(LaminarError::InvalidStakePool))?;
+
+  require!(header.account_type == STAKE_POOL_ACCOUNT_TYPE, LaminarError::InvalidStakePool);
+  require_keys_eq!(header.pool_mint, *lst_mint, LaminarError::InvalidStakePool);
+  Ok(header)
+}
+
+/// Read the exchange rate from an SPL stake pool account.
+///
+/// # Arguments
+/// * `stake_pool` - Account supplied as `stake_pool`; address already matched
+///   against `GlobalState::lst_stake_pool` by the account constraints
+/// * `lst_mint` - Supported LST mint; must be the pool's mint
+/// * `current_epoch` - Current epoch from the Clock sysvar
+/// * `max_lst_stale_epochs` - Epochs the pool's balances may lag behind
+pub fn read_stake_pool_rate(
+  stake_pool: &AccountInfo,
+  lst_mint: &Pubkey,
+  current_epoch: u64,
+  max_lst_stale_epochs: u64,
+) -> Result<u64> {
+  let header = read_stake_pool_header(stake_pool, lst_mint)?;
+
+  // `total_lamports` only moves when the pool is updated for the epoch.
+  require!(header.last_update_epoch <= current_epoch, LaminarError::InvalidStakePool);
+  require!(
+    current_epoch - header.last_update_epoch <= max_lst_stale_epochs,
+    LaminarError::LstRateStale
+  );
+
+  stake_pool_rate(header.total_lamports, header.pool_token_supply)
+    .ok_or(error!(LaminarError::InvalidStakePool))
+}
+
+/// Native stake program, passed through to `WithdrawSol`
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+
+/// `StakePoolInstruction::WithdrawSol` tag
+pub const STAKE_POOL_WITHDRAW_SOL_IX: u8 = 16;
+
+/// Accounts of the configured pool's `WithdrawSol`, which redeem-to-SOL uses
+/// to unstake the primary LST instantly out of the pool's reserve
+#[derive(Clone)]
+pub struct StakePoolWithdraw<'info> {
+  /// Configured `GlobalState::lst_stake_pool`
+  pub stake_pool: AccountInfo<'info>,
+  pub withdraw_authority: AccountInfo<'info>,
+  pub reserve_stake: AccountInfo<'info>,
+  /// Takes the pool's SOL withdrawal fee, in pool tokens
+  pub manager_fee_account: AccountInfo<'info>,
+  /// Supported LST mint
+  pub pool_mint: AccountInfo<'info>,
+  pub clock: AccountInfo<'info>,
+  pub stake_history: AccountInfo<'info>,
+  pub stake_program: AccountInfo<'info>,
+  pub token_program: AccountInfo<'info>,
+  pub stake_pool_program: AccountInfo<'info>,
+}
+
+impl<'info> StakePoolWithdraw<'info> {
+  /// Burn `pool_tokens` of `user`'s `source` for SOL paid to `user`, less
+  /// whatever the pool charges. The accounts the header names are checked
+  /// here; the pool program checks the rest.
+  pub fn withdraw_sol(&self, user: &AccountInfo<'info>, source: &AccountInfo<'info>, pool_tokens: u64) -> Result<()> {
+    let header = read_stake_pool_header(&self.stake_pool, self.pool_mint.key)?;
+    require_keys_eq!(header.reserve_stake, *self.reserve_stake.key, LaminarError::InvalidStakePool);
+    require_keys_eq!(header.manager_fee_account, *self.manager_fee_account.key, LaminarError::InvalidStakePool);
+    require_keys_eq!(header.token_program_id, *self.token_program.key, LaminarError::InvalidStakePool);
+
+    let mut data = Vec::with_capacity(9);
+    data.push(STAKE_POOL_WITHDRAW_SOL_IX);
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    let ix = Instruction {
+      program_id: SPL_STAKE_POOL_PROGRAM_ID,
+      accounts: vec![
+        AccountMeta::new(*self.stake_pool.key, false),
+        AccountMeta::new_readonly(*self.withdraw_authority.key, false),
+        AccountMeta::new_readonly(*user.key, true),
+        AccountMeta::new(*source.key, false),
+        AccountMeta::new(*self.reserve_stake.key, false),
+        AccountMeta::new(*user.key, false),
+        AccountMeta::new(*self.manager_fee_account.key, false),
+        AccountMeta::new(*self.pool_mint.key, false),
+        AccountMeta::new_readonly(*self.clock.key, false),
+        AccountMeta::new_readonly(*self.stake_history.key, false),
+        AccountMeta::new_readonly(*self.stake_program.key, false),
+        AccountMeta::new_readonly(*self.token_program.key, false),
+      ],
+      data,
+    };
+
+    invoke(
+      &ix,
+      &[
+        self.stake_pool.clone(),
+        self.withdraw_authority.clone(),
+        user.clone(),
+        source.clone(),
+        self.reserve_stake.clone(),
+        self.manager_fee_account.clone(),
+        self.pool_mint.clone(),
+        self.clock.clone(),
+        self.stake_history.clone(),
+        self.stake_program.clone(),
+        self.token_program.clone(),
+        self.stake_pool_program.clone(),
+      ],
+    )?;
+    Ok(())
+  }
+}
+
+/// `StakePoolInstruction::DepositSol` tag
+pub const STAKE_POOL_DEPOSIT_SOL_IX: u8 = 14;
+
+/// Accounts of the configured pool's `DepositSol`, which the SOL zap uses to
+/// stake native SOL into the primary LST
+#[derive(Clone)]
+pub struct StakePoolDeposit<'info> {
+  /// Configured `GlobalState::lst_stake_pool`
+  pub stake_pool: AccountInfo<'info>,
+  pub withdraw_authority: AccountInfo<'info>,
+  pub reserve_stake: AccountInfo<'info>,
+  /// Takes the pool's SOL deposit fee, in pool tokens
+  pub manager_fee_account: AccountInfo<'info>,
+  /// Supported LST mint
+  pub pool_mint: AccountInfo<'info>,
+  pub system_program: AccountInfo<'info>,
+  pub token_program: AccountInfo<'info>,
+  pub stake_pool_program: AccountInfo<'info>,
+}
+
+impl<'info> StakePoolDeposit<'info> {
+  /// Stake `lamports` of `user`'s SOL for pool tokens minted to
+  /// `destination`, less whatever the pool charges. `destination` is also
+  /// the referrer, so any referral fee comes back to the depositor.
+  pub fn deposit_sol(&self, user: &AccountInfo<'info>, destination: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+    let header = read_stake_pool_header(&self.stake_pool, self.pool_mint.key)?;
+    require_keys_eq!(header.reserve_stake, *self.reserve_stake.key, LaminarError::InvalidStakePool);
+    require_keys_eq!(header.manager_fee_account, *self.manager_fee_account.key, LaminarError::InvalidStakePool);
+    require_keys_eq!(header.token_program_id, *self.token_program.key, LaminarError::InvalidStakePool);
+
+    let mut data = Vec::with_capacity(9);
+    data.push(STAKE_POOL_DEPOSIT_SOL_IX);
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    let ix = Instruction {
+      program_id: SPL_STAKE_POOL_PROGRAM_ID,
+      accounts: vec![
+        AccountMeta::new(*self.stake_pool.key, false),
+        AccountMeta::new_readonly(*self.withdraw_authority.key, false),
+        AccountMeta::new(*self.reserve_stake.key, false),
+        AccountMeta::new(*user.key, true),
+        AccountMeta::new(*destination.key, false),
+        AccountMeta::new(*self.manager_fee_account.key, false),
+        AccountMeta::new(*destination.key, false),
+        AccountMeta::new(*self.pool_mint.key, false),
+        AccountMeta::new_readonly(*self.system_program.key, false),
+        AccountMeta::new_readonly(*self.token_program.key, false),
+      ],
+      data,
+    };
+
+    invoke(
+      &ix,
+      &[
+        self.stake_pool.clone(),
+        self.withdraw_authority.clone(),
+        self.reserve_stake.clone(),
+        user.clone(),
+        destination.clone(),
+        self.manager_fee_account.clone(),
+        self.pool_mint.clone(),
+        self.system_program.clone(),
+        self.token_program.clone(),
+        self.stake_pool_program.clone(),
+      ],
+    )?;
+    Ok(())
+  }
+}
+
+/// Marinade liquid staking program
+pub const MARINADE_PROGRAM_ID: Pubkey = pubkey!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+
+/// Anchor discriminator of Marinade's `State` account
+pub const MARINADE_STATE_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
+
+/// Offset of `State::msol_mint`, right after the discriminator
+pub const MARINADE_MSOL_MINT_OFFSET: usize = 8;
+
+/// Offset of `State::msol_price` in Marinade's fixed `State` layout
+pub const MARINADE_MSOL_PRICE_OFFSET: usize = 512;
+
+/// `msol_price` is SOL per mSOL as a 32.32 fixed-point number
+pub const MARINADE_PRICE_DENOMINATOR: u64 = 1 << 32;
+
+/// Convert Marinade's 32.32 `msol_price` to `SOL_PRECISION` scale, rounded
+/// down. `None` for a zero price or overflow.
+pub fn marinade_rate(msol_price: u64) -> Option<u64> {
+  if msol_price == 0 {
+    return None;
+  }
+  mul_div_down(msol_price, SOL_PRECISION, MARINADE_PRICE_DENOMINATOR)
+}
+
+/// Read the mSOL exchange rate from Marinade's `State` account.
+///
+/// Marinade refreshes `msol_price` in its per-epoch stake update, so the
+/// value never runs ahead of the stake it prices.
+///
+/// # Arguments
+/// * `marinade_state` - Account supplied as `marinade_state`; address already
+///   matched against `GlobalState::marinade_state` by the account constraints
+/// * `lst_mint` - Supported LST mint; must be Marinade's mSOL mint
+pub fn read_marinade_rate(marinade_state: &AccountInfo, lst_mint: &Pubkey) -> Result<u64> {
+  require_keys_eq!(*marinade_state.owner, MARINADE_PROGRAM_ID, LaminarError::InvalidMarinadeState);
+
+  let data = marinade_state.try_borrow_data()?;
+  require!(data.len() >= MARINADE_MSOL_PRICE_OFFSET + 8, LaminarError::InvalidMarinadeState);
+  require!(data[..8] == MARINADE_STATE_DISCRIMINATOR, LaminarError::InvalidMarinadeState);
+
+  let msol_mint = Pubkey::try_from(&data[MARINADE_MSOL_MINT_OFFSET..MARINADE_MSOL_MINT_OFFSET + 32])
+    .map_err(|_| error!(LaminarError::InvalidMarinadeState))?;
+  require_keys_eq!(msol_mint, *lst_mint, LaminarError::InvalidMarinadeState);
+
+  let mut price_bytes = [0u8; 8];
+  price_bytes.copy_from_slice(&data[MARINADE_MSOL_PRICE_OFFSET..MARINADE_MSOL_PRICE_OFFSET + 8]);
+
+  marinade_rate(u64::from_le_bytes(price_bytes)).ok_or(error!(LaminarError::InvalidMarinadeState))
+}
+
+/// Sanctum SOL value calculator programs `sync_exchange_rate` may CPI into.
+/// Each implements the `LstToSol` instruction of the calculator interface.
+pub const SANCTUM_CALCULATOR_PROGRAMS: [Pubkey; 6] = [
+  // SPL stake pool calculator
+  pubkey!("sp1V4h2gWorkGhVcazBc22Hfo2f5sd7jcjT4EDPrWFF"),
+  // Sanctum SPL stake pool calculator
+  pubkey!("sspUE1vrh7xRoXxGsg7vR1zde2WdGtJRbyK9uRumBDy"),
+  // Sanctum SPL multi-validator stake pool calculator
+  pubkey!("ssmbu3KZxgonUtjEMCKspZzxvUQCxAFnyh1rcHUeEDo"),
+  // Marinade calculator
+  pubkey!("mare3SCyfZkAndpBRBeonETmkCCB3TJTTrz8ZN2dnhP"),
+  // Lido calculator
+  pubkey!("1idUSy4MGGKyKhvjSnGZ6Zc7Q4eKQcibym4BkEEw9KR"),
+  // wSOL calculator
+  pubkey!("wsoGmxQLSvwWpuaidCApxN5kEowLe2HLQLJhCQnj4bE"),
+];
+
+/// `LstToSol` instruction discriminator of the calculator interface
+pub const LST_TO_SOL_IX_DISCM: u8 = 0;
+
+/// Return data of `LstToSol`: SOL value of the LST amount, in lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U64ValueRange {
+  pub min: u64,
+  pub max: u64,
+}
+
+pub fn is_known_calculator(program_id: &Pubkey) -> bool {
+  SANCTUM_CALCULATOR_PROGRAMS.contains(program_id)
+}
+
+/// Reject a rate that moved more than `max_delta_bps` from `previous_rate`.
+pub fn assert_lst_rate_within_band(previous_rate: u64, new_rate: u64, max_delta_bps: u64) -> Result<()> {
+  let band = mul_div_down(previous_rate, max_delta_bps, BPS_PRECISION).ok_or(LaminarError::MathOverflow)?;
+  require!(previous_rate.abs_diff(new_rate) <= band, LaminarError::LstRateOutOfBand);
+  Ok(())
+}
+
+/// Ask a Sanctum calculator for the SOL value of one whole LST.
+///
+/// `calculator_accounts` are forwarded read-only as the calculator's
+/// `LstToSol` accounts: the LST mint first, then whatever that calculator
+/// needs. The configured LST state account must be among them, so the rate
+/// is always derived from the account governance selected. The lower bound
+/// of the returned range is used so collateral is never overvalued.
+///
+/// # Arguments
+/// * `calculator` - Calculator program; address already matched against
+///   `GlobalState::lst_calculator_program` by the account constraints
+/// * `lst_mint` - Supported LST mint
+/// * `lst_state` - Configured `GlobalState::lst_calculator_state`
+/// * `calculator_accounts` - Remaining accounts of `sync_exchange_rate`
+pub fn read_calculator_rate<'info>(
+  calculator: &AccountInfo<'info>,
+  lst_mint: &Pubkey,
+  lst_state: &Pubkey,
+  calculator_accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+  require!(is_known_calculator(calculator.key), LaminarError::UnknownLstCalculator);
+  require!(calculator.executable, LaminarError::UnknownLstCalculator);
+  require!(
+    calculator_accounts.first().map(|account| account.key) == Some(lst_mint),
+    LaminarError::UnsupportedLST
+  );
+  require!(
+    calculator_accounts.iter().any(|account| account.key == lst_state),
+    LaminarError::InvalidLstCalculatorState
+  );
+
+  let mut data = Vec::with_capacity(9);
+  data.push(LST_TO_SOL_IX_DISCM);
+  data.extend_from_slice(&SOL_PRECISION.to_le_bytes());
+
+  let ix = Instruction {
+    program_id: *calculator.key,
+    accounts: calculator_accounts
+      .iter()
+      .map(|account| AccountMeta::new_readonly(*account.key, false))
+      .collect(),
+    data,
+  };
+
+  let mut account_infos = calculator_accounts.to_vec();
+  account_infos.push(calculator.clone());
+  invoke(&ix, &account_infos)?;
+
+  let (program_id, return_data) = get_return_data().ok_or(LaminarError::InvalidLstCalculatorState)?;
+  require_keys_eq!(program_id, *calculator.key, LaminarError::InvalidLstCalculatorState);
+  let range = U64ValueRange::try_from_slice(&return_data)
+    .map_err(|_| error!(LaminarError::InvalidLstCalculatorState))?;
+
+  require!(range.min > 0 && range.min <= range.max, LaminarError::InvalidLstCalculatorState);
+  Ok(range.min)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use anchor_lang::prelude::borsh;
+
+  #[test]
+  fn test_stake_pool_rate_rounds_down() {
+    assert_eq!(stake_pool_rate(1_050, 1_000), Some(1_050_000_000));
+    // 10 SOL backing 3 pool tokens: 3.333333333... SOL each
+    assert_eq!(stake_pool_rate(10 * SOL_PRECISION, 3 * SOL_PRECISION), Some(3_333_333_333));
+    assert_eq!(stake_pool_rate(0, 1_000), None);
+    assert_eq!(stake_pool_rate(1_000, 0), None);
+  }
+
+  #[test]
+  fn test_stake_pool_header_decodes_prefix_of_full_account() {
+    let header = StakePoolHeader {
+      account_type: STAKE_POOL_ACCOUNT_TYPE,
+      manager: Pubkey::new_unique(),
+      staker: Pubkey::new_unique(),
+      stake_deposit_authority: Pubkey::new_unique(),
+      stake_withdraw_bump_seed: 255,
+      validator_list: Pubkey::new_unique(),
+      reserve_stake: Pubkey::new_unique(),
+      pool_mint: Pubkey::new_unique(),
+      manager_fee_account: Pubkey::new_unique(),
+      token_program_id: Pubkey::new_unique(),
+      total_lamports: 1_234 * SOL_PRECISION,
+      pool_token_supply: 1_000 * SOL_PRECISION,
+      last_update_epoch: 700,
+    };
+    let mut data = borsh::to_vec(&header).unwrap();
+    // total_lamports sits at the documented SPL offset
+    assert_eq!(&data[258..266], &(1_234 * SOL_PRECISION).to_le_bytes());
+    // Trailing fee/fund fields of the real account are ignored
+    data.resize(611, 0xab);
+
+    let decoded = StakePoolHeader::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(decoded, header);
+  }
+
+  /// mSOL mint on mainnet
+  const MSOL_MINT: Pubkey = pubkey!("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So");
+
+  /// Representative mainnet `msol_price`: about 1.2925 SOL per mSOL as
+  /// 32.32 fixed point.
+  const MAINNET_MSOL_PRICE: u64 = 5_551_421_112;
+
+  /// Marinade `State` bytes: discriminator, mSOL mint and `msol_price` at
+  /// their layout offsets, every other field filled with noise.
+  fn marinade_state_data(msol_mint: &Pubkey, msol_price: u64) -> Vec<u8> {
+    let mut data = vec![0xcd; 1_024];
+    data[..8].copy_from_slice(&MARINADE_STATE_DISCRIMINATOR);
+    data[MARINADE_MSOL_MINT_OFFSET..MARINADE_MSOL_MINT_OFFSET + 32].copy_from_slice(msol_mint.as_ref());
+    data[MARINADE_MSOL_PRICE_OFFSET..MARINADE_MSOL_PRICE_OFFSET + 8].copy_from_slice(&msol_price.to_le_bytes());
+    data
+  }
+
+  fn with_account<T>(owner: &Pubkey, data: &mut [u8], f: impl FnOnce(&AccountInfo) -> T) -> T {
+    let key = pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC");
+    let mut lamports = 1;
+    let info = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+    f(&info)
+  }
+
+  const MAX_BAND_BPS: u64 = crate::constants::MAX_LST_RATE_DELTA_BPS;
+
+  #[test]
+  fn test_lst_rate_band_is_symmetric_and_inclusive() {
+    let rate = 1_100_000_000;
+    // 2% of 1.1 = 0.022
+    assert!(assert_lst_rate_within_band(rate, rate, MAX_BAND_BPS).is_ok());
+    assert!(assert_lst_rate_within_band(rate, rate + 22_000_000, MAX_BAND_BPS).is_ok());
+    assert!(assert_lst_rate_within_band(rate, rate - 22_000_000, MAX_BAND_BPS).is_ok());
+    assert!(assert_lst_rate_within_band(rate, rate + 22_000_001, MAX_BAND_BPS).is_err());
+    assert!(assert_lst_rate_within_band(rate, rate - 22_000_001, MAX_BAND_BPS).is_err());
+  }
+
+  #[test]
+  fn test_known_calculators_exclude_arbitrary_programs() {
+    for program_id in SANCTUM_CALCULATOR_PROGRAMS {
+      assert!(is_known_calculator(&program_id));
+    }
+    assert!(!is_known_calculator(&Pubkey::default()));
+    assert!(!is_known_calculator(&crate::ID));
+    assert!(!is_known_calculator(&SPL_STAKE_POOL_PROGRAM_ID));
+  }
+
+  #[test]
+  fn test_marinade_rate_converts_from_32_32_fixed_point() {
+    assert_eq!(marinade_rate(MARINADE_PRICE_DENOMINATOR), Some(SOL_PRECISION));
+    assert_eq!(marinade_rate(MAINNET_MSOL_PRICE), Some(1_292_540_950));
+    // 1/3 SOL rounds down
+    assert_eq!(marinade_rate(MARINADE_PRICE_DENOMINATOR / 3), Some(333_333_333));
+    assert_eq!(marinade_rate(0), None);
+  }
+
+  #[test]
+  fn test_read_marinade_rate_from_mainnet_layout() {
+    let mut data = marinade_state_data(&MSOL_MINT, MAINNET_MSOL_PRICE);
+    let rate = with_account(&MARINADE_PROGRAM_ID, &mut data, |info| read_marinade_rate(info, &MSOL_MINT)).unwrap();
+    assert_eq!(rate, 1_292_540_950);
+  }
+
+  #[test]
+  fn test_read_marinade_rate_rejects_spoofed_accounts() {
+    // Right bytes, wrong owner
+    let mut data = marinade_state_data(&MSOL_MINT, MAINNET_MSOL_PRICE);
+    assert!(with_account(&Pubkey::new_unique(), &mut data, |info| read_marinade_rate(info, &MSOL_MINT)).is_err());
+
+    // Different LST
+    assert!(with_account(&MARINADE_PROGRAM_ID, &mut data, |info| read_marinade_rate(info, &Pubkey::new_unique())).is_err());
+
+    // Some other Marinade account
+    data[..8].copy_from_slice(&[0; 8]);
+    assert!(with_account(&MARINADE_PROGRAM_ID, &mut data, |info| read_marinade_rate(info, &MSOL_MINT)).is_err());
+
+    // Truncated
+    let mut short = marinade_state_data(&MSOL_MINT, MAINNET_MSOL_PRICE);
+    short.truncate(MARINADE_MSOL_PRICE_OFFSET + 4);
+    assert!(with_account(&MARINADE_PROGRAM_ID, &mut short, |info| read_marinade_rate(info, &MSOL_MINT)).is_err());
+  }
+}
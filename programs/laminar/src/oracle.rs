@@ -0,0 +1,472 @@
+//! SOL/USD price resolution
+//! `GlobalState::price_source` selects where the price comes from; each source
+//! has its own feed pubkey in GlobalState. `resolve_price` is the single entry
+//! point for pricing instructions: it reads the selected source and applies
+//! the freshness/confidence guard, so handlers never see an unchecked price.
+//! With `secondary_price_source` set, the secondary feed is read and guarded
+//! too; the two must agree within `max_oracle_deviation_bps`, and the action's
+//! `PriceBias` picks which of them is used. Every price read, and the cached
+//! LST rate, must also sit inside GlobalState's absolute sanity bounds.
+//! With `ema_half_life_slots` set, a mock-priced action also takes the side of
+//! spot and `ema_sol_price_usd` its bias asks for.
+//! Redemptions go through `resolve_redeem_price`, which swaps a stale primary
+//! for `fallback_price_source` instead of failing; mints never fall back.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::invariants::assert_oracle_freshness_and_confidence;
+use crate::math::compute_price_deviation_bps;
+use crate::pyth::read_pyth_price;
+use crate::state::GlobalState;
+use crate::switchboard::read_switchboard_price;
+
+/// Where the SOL/USD price comes from (`GlobalState::price_source`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PriceSource {
+  /// Admin-set `mock_sol_price_usd`
+  Mock = 0,
+  /// Pyth `PriceUpdateV2` at `pyth_price_feed`
+  Pyth = 1,
+  /// Switchboard on-demand feed at `switchboard_feed`
+  Switchboard = 2,
+  /// Admin-set SOL/USD, only while the LST leg is live from `lst_stake_pool`
+  StakePool = 3,
+}
+
+impl TryFrom<u8> for PriceSource {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self> {
+    match value {
+      0 => Ok(PriceSource::Mock),
+      1 => Ok(PriceSource::Pyth),
+      2 => Ok(PriceSource::Switchboard),
+      3 => Ok(PriceSource::StakePool),
+      _ => err!(LaminarError::InvalidPriceFeed),
+    }
+  }
+}
+
+impl PriceSource {
+  /// True when the feed this source reads is configured in `global_state`.
+  pub fn is_configured(self, global_state: &GlobalState) -> bool {
+    match self {
+      PriceSource::Mock => true,
+      PriceSource::Pyth => global_state.pyth_price_feed != Pubkey::default(),
+      PriceSource::Switchboard => global_state.switchboard_feed != Pubkey::default(),
+      PriceSource::StakePool => global_state.lst_stake_pool != Pubkey::default(),
+    }
+  }
+}
+
+/// Which of two cross-checked prices an action uses. Each action takes the
+/// price that is worse for the user and safer for the balance sheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceBias {
+  /// Lower price: deposited collateral buys less amUSD and liability in SOL
+  /// is larger (amUSD mint, aSOL redeem)
+  Low,
+  /// Higher price: each amUSD claims less SOL and aSOL NAV is higher (amUSD
+  /// redeem, aSOL mint)
+  High,
+}
+
+impl PriceBias {
+  /// The conservative one of two prices for this bias.
+  pub fn pick(self, a: u64, b: u64) -> u64 {
+    match self {
+      PriceBias::Low => a.min(b),
+      PriceBias::High => a.max(b),
+    }
+  }
+}
+
+/// Raw adapter output: SOL/USD in micro-USD with its confidence width and
+/// source slot and time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+  pub price_usd: u64,
+  pub confidence_usd: u64,
+  /// Slot the price was posted (Pyth), last updated (Switchboard) or last set
+  /// by the admin (mock)
+  pub publish_slot: u64,
+  /// Unix timestamp matching `publish_slot`
+  pub publish_time: i64,
+}
+
+/// Oracle accounts a pricing instruction may supply. Addresses are already
+/// matched against GlobalState by each instruction's account constraints.
+#[derive(Clone, Copy, Default)]
+pub struct PriceAccounts<'a, 'info> {
+  /// Pyth `PriceUpdateV2`, required while the source is `Pyth`
+  pub price_update: Option<&'a AccountInfo<'info>>,
+  /// Switchboard pull feed, required while the source is `Switchboard`
+  pub switchboard_feed: Option<&'a AccountInfo<'info>>,
+}
+
+/// SOL/USD price that passed the freshness and confidence guard
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceSnapshot {
+  pub sol_price_usd: u64,
+  pub confidence_usd: u64,
+  pub publish_slot: u64,
+  pub publish_time: i64,
+  pub source: PriceSource,
+  /// Read from `fallback_price_source` because the primary was stale
+  pub is_fallback: bool,
+}
+
+/// Read `source` without applying the freshness/confidence guard.
+pub fn read_price_source(
+  source: PriceSource,
+  accounts: &PriceAccounts,
+  global_state: &GlobalState,
+  current_slot: u64,
+) -> Result<OraclePrice> {
+  require!(source.is_configured(global_state), LaminarError::InvalidPriceFeed);
+
+  match source {
+    PriceSource::Mock | PriceSource::StakePool => Ok(OraclePrice {
+      price_usd: global_state.mock_sol_price_usd,
+      confidence_usd: global_state.mock_oracle_confidence_usd,
+      publish_slot: global_state.last_oracle_update_slot,
+      publish_time: global_state.last_oracle_update_timestamp,
+    }),
+    PriceSource::Pyth => {
+      let price_update = accounts.price_update.ok_or(LaminarError::InvalidPriceFeed)?;
+      read_pyth_price(price_update, current_slot, global_state.max_oracle_staleness_slots)
+    }
+    PriceSource::Switchboard => {
+      let switchboard_feed = accounts.switchboard_feed.ok_or(LaminarError::InvalidPriceFeed)?;
+      read_switchboard_price(switchboard_feed)
+    }
+  }
+}
+
+/// Read `source` and apply the freshness/confidence and sanity-bound guards.
+fn read_guarded_price(
+  source: PriceSource,
+  accounts: &PriceAccounts,
+  global_state: &GlobalState,
+  current_slot: u64,
+  current_timestamp: i64,
+) -> Result<PriceSnapshot> {
+  let price = read_price_source(source, accounts, global_state, current_slot)?;
+
+  assert_oracle_freshness_and_confidence(
+    current_slot,
+    price.publish_slot,
+    global_state.max_oracle_staleness_slots,
+    current_timestamp,
+    price.publish_time,
+    global_state.max_oracle_age_seconds,
+    price.price_usd,
+    price.confidence_usd,
+    global_state.max_conf_bps,
+  )?;
+  global_state.assert_sol_price_within_bounds(price.price_usd)?;
+
+  Ok(PriceSnapshot {
+    sol_price_usd: price.price_usd,
+    confidence_usd: price.confidence_usd,
+    publish_slot: price.publish_slot,
+    publish_time: price.publish_time,
+    source,
+    is_fallback: false,
+  })
+}
+
+/// Reject two prices further apart than `max_oracle_deviation_bps`, otherwise
+/// return the one `bias` asks for. Ties keep the primary.
+pub fn cross_check_prices(
+  primary: PriceSnapshot,
+  secondary: PriceSnapshot,
+  max_oracle_deviation_bps: u64,
+  bias: PriceBias,
+) -> Result<PriceSnapshot> {
+  let deviation_bps = compute_price_deviation_bps(primary.sol_price_usd, secondary.sol_price_usd)
+    .ok_or(LaminarError::InvalidParameter)?;
+  require!(deviation_bps <= max_oracle_deviation_bps, LaminarError::OracleDeviation);
+
+  let use_secondary = match bias {
+    PriceBias::Low => secondary.sol_price_usd < primary.sol_price_usd,
+    PriceBias::High => secondary.sol_price_usd > primary.sol_price_usd,
+  };
+  Ok(if use_secondary { secondary } else { primary })
+}
+
+/// Smooth a mock-priced snapshot against the EMA. Live feeds are left alone:
+/// the EMA follows `mock_sol_price_usd`, not them.
+fn apply_ema(snapshot: PriceSnapshot, global_state: &GlobalState, bias: PriceBias) -> PriceSnapshot {
+  let mock_priced = matches!(snapshot.source, PriceSource::Mock | PriceSource::StakePool);
+  if global_state.ema_half_life_slots == 0 || !mock_priced {
+    return snapshot;
+  }
+  PriceSnapshot {
+    sol_price_usd: bias.pick(snapshot.sol_price_usd, global_state.ema_sol_price_usd),
+    ..snapshot
+  }
+}
+
+/// SOL/USD price for pricing actions from the configured source, cross-checked
+/// against `secondary_price_source` when one is configured.
+///
+/// # Arguments
+/// * `accounts` - Oracle accounts supplied to the instruction
+/// * `global_state` - Source selection, feed addresses and guard bounds
+/// * `current_slot` - Current slot from the Clock sysvar
+/// * `current_timestamp` - Current unix timestamp from the Clock sysvar
+/// * `bias` - Which price the action takes when both feeds are read
+pub fn resolve_price(
+  accounts: &PriceAccounts,
+  global_state: &GlobalState,
+  current_slot: u64,
+  current_timestamp: i64,
+  bias: PriceBias,
+) -> Result<PriceSnapshot> {
+  // Every pricing action also values collateral at the cached LST rate.
+  global_state.assert_lst_rate_within_bounds(global_state.mock_lst_to_sol_rate)?;
+
+  let source = PriceSource::try_from(global_state.price_source)?;
+  let primary = read_guarded_price(source, accounts, global_state, current_slot, current_timestamp)?;
+
+  // `Mock` as the secondary means no cross-check is configured.
+  let secondary_source = PriceSource::try_from(global_state.secondary_price_source)?;
+  if secondary_source == PriceSource::Mock {
+    return Ok(apply_ema(primary, global_state, bias));
+  }
+
+  let secondary = read_guarded_price(secondary_source, accounts, global_state, current_slot, current_timestamp)?;
+  let price = cross_check_prices(primary, secondary, global_state.max_oracle_deviation_bps, bias)?;
+  Ok(apply_ema(price, global_state, bias))
+}
+
+/// True for the errors a stale (rather than malformed) price fails with.
+fn is_stale_price_error(err: &Error) -> bool {
+  *err == error!(LaminarError::OraclePriceStale) || *err == error!(LaminarError::PythPriceStale)
+}
+
+/// SOL/USD price for redemptions. Same as `resolve_price`, except that a stale
+/// feed is replaced by `fallback_price_source` (itself guarded) when one is
+/// configured. The snapshot is flagged `is_fallback` so the caller can charge
+/// the fallback uncertainty surcharge.
+pub fn resolve_redeem_price(
+  accounts: &PriceAccounts,
+  global_state: &GlobalState,
+  current_slot: u64,
+  current_timestamp: i64,
+  bias: PriceBias,
+) -> Result<PriceSnapshot> {
+  match resolve_price(accounts, global_state, current_slot, current_timestamp, bias) {
+    Err(err) if is_stale_price_error(&err) => {
+      let Some(fallback) = global_state.fallback_source() else {
+        return Err(err);
+      };
+      let source = PriceSource::try_from(fallback)?;
+      let snapshot = read_guarded_price(source, accounts, global_state, current_slot, current_timestamp)?;
+      Ok(PriceSnapshot { is_fallback: true, ..apply_ema(snapshot, global_state, bias) })
+    }
+    result => result,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn state() -> GlobalState {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.mock_sol_price_usd = 150_000_000;
+    state.mock_oracle_confidence_usd = 10_000;
+    state.last_oracle_update_slot = 100;
+    state.max_oracle_staleness_slots = 150;
+    state.last_oracle_update_timestamp = 1_000;
+    state.max_oracle_age_seconds = 60;
+    state.max_conf_bps = 150;
+    state
+  }
+
+  #[test]
+  fn test_price_source_round_trips_and_rejects_unknown() {
+    for source in [PriceSource::Mock, PriceSource::Pyth, PriceSource::Switchboard, PriceSource::StakePool] {
+      assert_eq!(PriceSource::try_from(source as u8).unwrap(), source);
+    }
+    assert!(PriceSource::try_from(4).is_err());
+    assert!(PriceSource::try_from(u8::MAX).is_err());
+  }
+
+  #[test]
+  fn test_resolve_price_reads_mock_and_applies_guard() {
+    let mut state = state();
+    let snapshot = resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap();
+    assert_eq!(snapshot.sol_price_usd, 150_000_000);
+    assert_eq!(snapshot.source, PriceSource::Mock);
+
+    // The guard runs inside resolve_price, not in the caller.
+    assert!(resolve_price(&PriceAccounts::default(), &state, 251, 1_000, PriceBias::Low).is_err());
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_061, PriceBias::Low).is_err());
+    state.mock_oracle_confidence_usd = 3_000_000;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+  }
+
+  #[test]
+  fn test_resolve_price_requires_the_selected_feed() {
+    let mut state = state();
+
+    // Selected but unconfigured sources never fall back to the mock.
+    for source in [PriceSource::Pyth, PriceSource::Switchboard, PriceSource::StakePool] {
+      state.price_source = source as u8;
+      assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+    }
+
+    // Configured but missing the account
+    state.pyth_price_feed = Pubkey::new_unique();
+    state.price_source = PriceSource::Pyth as u8;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+
+    // A configured feed that is not selected is not consulted.
+    state.price_source = PriceSource::Mock as u8;
+    assert_eq!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap().source, PriceSource::Mock);
+
+    state.lst_stake_pool = Pubkey::new_unique();
+    state.price_source = PriceSource::StakePool as u8;
+    assert_eq!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap().sol_price_usd, 150_000_000);
+
+    state.price_source = 9;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+  }
+
+  fn snapshot(sol_price_usd: u64, source: PriceSource) -> PriceSnapshot {
+    PriceSnapshot { sol_price_usd, confidence_usd: 0, publish_slot: 100, publish_time: 1_000, source, is_fallback: false }
+  }
+
+  #[test]
+  fn test_cross_check_takes_the_conservative_price_when_feeds_agree() {
+    let pyth = snapshot(100_000_000, PriceSource::Pyth);
+    let switchboard = snapshot(100_500_000, PriceSource::Switchboard);
+
+    assert_eq!(cross_check_prices(pyth, switchboard, 100, PriceBias::Low).unwrap(), pyth);
+    assert_eq!(cross_check_prices(pyth, switchboard, 100, PriceBias::High).unwrap(), switchboard);
+    assert_eq!(cross_check_prices(switchboard, pyth, 100, PriceBias::Low).unwrap(), pyth);
+    assert_eq!(cross_check_prices(switchboard, pyth, 100, PriceBias::High).unwrap(), switchboard);
+
+    // Exactly at the bound still agrees; an equal price keeps the primary.
+    let edge = snapshot(101_000_000, PriceSource::Switchboard);
+    assert_eq!(cross_check_prices(pyth, edge, 100, PriceBias::High).unwrap(), edge);
+    let equal = snapshot(100_000_000, PriceSource::Switchboard);
+    assert_eq!(cross_check_prices(pyth, equal, 100, PriceBias::Low).unwrap().source, PriceSource::Pyth);
+  }
+
+  #[test]
+  fn test_cross_check_rejects_diverging_feeds() {
+    let pyth = snapshot(100_000_000, PriceSource::Pyth);
+    let switchboard = snapshot(101_000_001, PriceSource::Switchboard);
+
+    for bias in [PriceBias::Low, PriceBias::High] {
+      assert_eq!(
+        cross_check_prices(pyth, switchboard, 100, bias).unwrap_err(),
+        error!(LaminarError::OracleDeviation)
+      );
+    }
+  }
+
+  #[test]
+  fn test_resolve_price_without_secondary_uses_the_primary_alone() {
+    let mut state = state();
+    state.max_oracle_deviation_bps = 1;
+
+    // The mock secondary is the disabled cross-check, whatever the bias.
+    for bias in [PriceBias::Low, PriceBias::High] {
+      let snapshot = resolve_price(&PriceAccounts::default(), &state, 200, 1_000, bias).unwrap();
+      assert_eq!(snapshot.sol_price_usd, 150_000_000);
+      assert_eq!(snapshot.source, PriceSource::Mock);
+    }
+
+    // A selected secondary whose account is missing never falls back.
+    state.switchboard_feed = Pubkey::new_unique();
+    state.secondary_price_source = PriceSource::Switchboard as u8;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+
+    state.secondary_price_source = 9;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_err());
+  }
+
+  #[test]
+  fn test_resolve_price_enforces_sanity_bounds_inclusively() {
+    let mut state = state();
+    state.mock_lst_to_sol_rate = 1_050_000_000;
+    state.min_sol_price_usd = 150_000_000;
+    state.max_sol_price_usd = 150_000_000;
+    state.min_lst_rate = 1_050_000_000;
+    state.max_lst_rate = 1_050_000_000;
+    assert!(resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).is_ok());
+
+    state.max_sol_price_usd = 149_999_999;
+    state.min_sol_price_usd = 0;
+    assert_eq!(
+      resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap_err(),
+      error!(LaminarError::OraclePriceOutOfBounds)
+    );
+
+    state.max_sol_price_usd = 0;
+    state.min_lst_rate = 1_050_000_001;
+    assert_eq!(
+      resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap_err(),
+      error!(LaminarError::OraclePriceOutOfBounds)
+    );
+  }
+
+  #[test]
+  fn test_resolve_redeem_price_only_falls_back_on_staleness() {
+    let mut state = state();
+    state.fallback_price_source = PriceSource::Switchboard as u8;
+
+    // Fresh primary: used as-is.
+    let snapshot = resolve_redeem_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::High).unwrap();
+    assert_eq!(snapshot.source, PriceSource::Mock);
+    assert!(!snapshot.is_fallback);
+
+    // Stale primary: the fallback is read, and guarded like any other feed
+    // (here its account is missing).
+    assert_eq!(
+      resolve_redeem_price(&PriceAccounts::default(), &state, 251, 1_000, PriceBias::High).unwrap_err(),
+      error!(LaminarError::InvalidPriceFeed)
+    );
+
+    // Without a fallback the staleness error surfaces unchanged.
+    state.fallback_price_source = crate::constants::NO_FALLBACK_PRICE_SOURCE;
+    assert_eq!(
+      resolve_redeem_price(&PriceAccounts::default(), &state, 251, 1_000, PriceBias::High).unwrap_err(),
+      error!(LaminarError::OraclePriceStale)
+    );
+
+    // A malformed primary is never papered over by the fallback.
+    state.fallback_price_source = PriceSource::Mock as u8;
+    state.pyth_price_feed = Pubkey::new_unique();
+    state.price_source = PriceSource::Pyth as u8;
+    assert_eq!(
+      resolve_redeem_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::High).unwrap_err(),
+      error!(LaminarError::InvalidPriceFeed)
+    );
+  }
+
+  #[test]
+  fn test_resolve_price_takes_the_conservative_side_of_spot_and_ema() {
+    let mut state = state();
+    state.ema_sol_price_usd = 140_000_000;
+
+    // Smoothing disabled: spot only.
+    for bias in [PriceBias::Low, PriceBias::High] {
+      let snapshot = resolve_price(&PriceAccounts::default(), &state, 200, 1_000, bias).unwrap();
+      assert_eq!(snapshot.sol_price_usd, 150_000_000);
+    }
+
+    state.ema_half_life_slots = 150;
+    let low = resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::Low).unwrap();
+    let high = resolve_price(&PriceAccounts::default(), &state, 200, 1_000, PriceBias::High).unwrap();
+    assert_eq!(low.sol_price_usd, 140_000_000);
+    assert_eq!(high.sol_price_usd, 150_000_000);
+  }
+}
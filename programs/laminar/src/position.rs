@@ -0,0 +1,127 @@
+//! Per-wallet position tracking
+//! The four user paths take an optional UserPosition, created on the
+//! caller's first use at the caller's expense. Mints fold into its running
+//! totals and every action stamps its slot. While `per_user_amusd_cap`,
+//! `min_action_gap_slots` or `asol_redeem_cooldown_slots` is set the position
+//! is how those limits are enforced, so the paths they bind cannot skip it.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::math::compute_cap_headroom;
+use crate::state::{GlobalState, UserPosition};
+
+/// Amounts one mint adds to a position
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PositionMint {
+  pub lst_deposited: u64,
+  pub amusd_minted: u64,
+  pub asol_minted: u64,
+}
+
+/// Add a mint to the caller's position, hold its amUSD total to
+/// `per_user_amusd_cap` and keep it `min_action_gap_slots` clear of the
+/// wallet's last redeem.
+///
+/// `bump` is only read the first time, when the position was just created.
+pub fn record_position_mint(
+  global_state: &GlobalState,
+  user_position: Option<&mut Account<UserPosition>>,
+  owner: Pubkey,
+  bump: Option<u8>,
+  current_slot: u64,
+  mint: PositionMint,
+) -> Result<()> {
+  let PositionMint { lst_deposited, amusd_minted, asol_minted } = mint;
+  let capped = global_state.per_user_amusd_cap > 0 && amusd_minted > 0;
+  let cooled = global_state.asol_redeem_cooldown_slots > 0 && asol_minted > 0;
+  let Some(position) = user_position else {
+    require!(!capped && !cooled && global_state.min_action_gap_slots == 0, LaminarError::UserPositionRequired);
+    return Ok(());
+  };
+  open_position(position, owner, bump)?;
+  assert_action_gap(position.last_redeem_slot, current_slot, global_state.min_action_gap_slots)?;
+
+  position.lst_deposited = position
+    .lst_deposited
+    .checked_add(lst_deposited)
+    .ok_or(LaminarError::MathOverflow)?;
+  position.amusd_minted = position
+    .amusd_minted
+    .checked_add(amusd_minted)
+    .ok_or(LaminarError::MathOverflow)?;
+  position.asol_minted = position
+    .asol_minted
+    .checked_add(asol_minted)
+    .ok_or(LaminarError::MathOverflow)?;
+  position.last_mint_slot = current_slot;
+  if asol_minted > 0 {
+    position.last_asol_mint_slot = current_slot;
+  }
+  require!(
+    compute_cap_headroom(position.amusd_minted, global_state.per_user_amusd_cap).is_some(),
+    LaminarError::PerUserCapExceeded
+  );
+  Ok(())
+}
+
+/// Keep a redeem `min_action_gap_slots` clear of the wallet's last mint.
+pub fn record_position_redeem(
+  global_state: &GlobalState,
+  user_position: Option<&mut Account<UserPosition>>,
+  owner: Pubkey,
+  bump: Option<u8>,
+  current_slot: u64,
+) -> Result<()> {
+  let Some(position) = user_position else {
+    require!(global_state.min_action_gap_slots == 0, LaminarError::UserPositionRequired);
+    return Ok(());
+  };
+  open_position(position, owner, bump)?;
+  assert_action_gap(position.last_mint_slot, current_slot, global_state.min_action_gap_slots)?;
+  position.last_redeem_slot = current_slot;
+  Ok(())
+}
+
+/// Hold redeem_asol until `asol_redeem_cooldown_slots` have passed since
+/// the wallet last minted aSOL.
+pub fn assert_asol_redeem_cooldown(
+  global_state: &GlobalState,
+  user_position: Option<&Account<UserPosition>>,
+  current_slot: u64,
+) -> Result<()> {
+  if global_state.asol_redeem_cooldown_slots == 0 {
+    return Ok(());
+  }
+  let Some(position) = user_position else {
+    return err!(LaminarError::UserPositionRequired);
+  };
+  if position.last_asol_mint_slot == 0 {
+    return Ok(());
+  }
+  require!(
+    current_slot >= position.last_asol_mint_slot.saturating_add(global_state.asol_redeem_cooldown_slots),
+    LaminarError::AsolRedeemCooldownActive
+  );
+  Ok(())
+}
+
+fn open_position(position: &mut UserPosition, owner: Pubkey, bump: Option<u8>) -> Result<()> {
+  if position.owner == Pubkey::default() {
+    position.owner = owner;
+    position.bump = bump.ok_or(LaminarError::InvalidAccountState)?;
+  }
+  Ok(())
+}
+
+/// Slot 0 marks an action the wallet has never taken.
+fn assert_action_gap(last_opposite_slot: u64, current_slot: u64, min_action_gap_slots: u64) -> Result<()> {
+  if last_opposite_slot == 0 {
+    return Ok(());
+  }
+  require!(
+    current_slot >= last_opposite_slot.saturating_add(min_action_gap_slots),
+    LaminarError::ActionTooSoon
+  );
+  Ok(())
+}
@@ -0,0 +1,105 @@
+//! Pyth pull-oracle adapter for the SOL/USD price
+//! Reads a `PriceUpdateV2` account posted by the Pyth receiver program and
+//! maps it onto the `(price, confidence, slot)` triple the pricing guards
+//! already consume. Feed selection lives in `oracle`.
+
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, VerificationLevel};
+
+use crate::error::LaminarError;
+use crate::oracle::OraclePrice;
+
+/// USD_PRECISION = 10^6
+const USD_DECIMALS: i32 = 6;
+
+/// Scale a Pyth `price * 10^expo` pair into micro-USD.
+/// Price rounds down and confidence rounds up, so the confidence ratio never
+/// looks tighter than the feed reported. Returns `None` on a non-positive
+/// price, an exponent too large to represent, or overflow.
+pub fn pyth_to_micro_usd(price: i64, conf: u64, expo: i32) -> Option<(u64, u64)> {
+  if price <= 0 {
+    return None;
+  }
+  let price = price as u64;
+  let shift = expo.checked_add(USD_DECIMALS)?;
+
+  if shift >= 0 {
+    let scale = 10u64.checked_pow(shift as u32)?;
+    Some((price.checked_mul(scale)?, conf.checked_mul(scale)?))
+  } else {
+    let scale = 10u64.checked_pow(shift.unsigned_abs())?;
+    Some((price / scale, conf.div_ceil(scale)))
+  }
+}
+
+/// Decode and sanity-check a Pyth price update.
+///
+/// # Arguments
+/// * `price_update` - Account supplied as `price_update`; address already
+///   matched against `GlobalState::pyth_price_feed` by the account constraints
+/// * `current_slot` - Current slot from the Clock sysvar
+/// * `max_oracle_staleness_slots` - Max allowed age of the posted update
+pub fn read_pyth_price(
+  price_update: &AccountInfo,
+  current_slot: u64,
+  max_oracle_staleness_slots: u64,
+) -> Result<OraclePrice> {
+  require_keys_eq!(
+    *price_update.owner,
+    pyth_solana_receiver_sdk::ID,
+    LaminarError::InvalidPriceFeed
+  );
+
+  let data = price_update.try_borrow_data()?;
+  let update = PriceUpdateV2::try_deserialize(&mut &data[..])
+    .map_err(|_| error!(LaminarError::InvalidPriceFeed))?;
+
+  // Partially verified updates carry fewer guardian signatures than required.
+  require!(
+    update.verification_level == VerificationLevel::Full,
+    LaminarError::InvalidPriceFeed
+  );
+  require!(update.posted_slot <= current_slot, LaminarError::InvalidPriceFeed);
+  require!(
+    current_slot - update.posted_slot <= max_oracle_staleness_slots,
+    LaminarError::PythPriceStale
+  );
+
+  let message = &update.price_message;
+  let (price_usd, confidence_usd) = pyth_to_micro_usd(message.price, message.conf, message.exponent)
+    .ok_or(LaminarError::InvalidPriceFeed)?;
+  require!(price_usd > 0, LaminarError::InvalidPriceFeed);
+
+  Ok(OraclePrice {
+    price_usd,
+    confidence_usd,
+    publish_slot: update.posted_slot,
+    publish_time: message.publish_time,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::math::USD_PRECISION;
+
+  #[test]
+  fn test_pyth_to_micro_usd_scales_by_exponent() {
+    // $142.50123456 at expo -8
+    assert_eq!(pyth_to_micro_usd(14_250_123_456, 7_000_001, -8), Some((142_501_234, 70_001)));
+    // Already micro-USD
+    assert_eq!(pyth_to_micro_usd(142_000_000, 50_000, -6), Some((142_000_000, 50_000)));
+    // Whole dollars
+    assert_eq!(pyth_to_micro_usd(142, 1, 0), Some((142 * USD_PRECISION, USD_PRECISION)));
+  }
+
+  #[test]
+  fn test_pyth_to_micro_usd_rejects_bad_inputs() {
+    assert_eq!(pyth_to_micro_usd(0, 0, -8), None);
+    assert_eq!(pyth_to_micro_usd(-1, 0, -8), None);
+    assert_eq!(pyth_to_micro_usd(i64::MAX, 0, 12), None);
+    assert_eq!(pyth_to_micro_usd(1, 0, i32::MAX), None);
+    // Sub-micro-USD prices round to zero and are rejected by the caller.
+    assert_eq!(pyth_to_micro_usd(1, 1, -12), Some((0, 1)));
+  }
+}
@@ -0,0 +1,913 @@
+//! Pure quote functions for the four user instructions
+//! The one implementation of their pricing: each handler builds a
+//! `QuoteState` from its accounts and commits what the quote returns. With
+//! no account access, off-chain callers and tests get the same outputs from a
+//! state snapshot alone.
+//!
+//! Quotes do not enforce slippage, minimums, pauses or invariants; the
+//! handlers own those checks. `None` means the math itself failed.
+
+use crate::constants::MAX_RECOVERY_DILUTION_BPS;
+use crate::instructions::TradeQuote;
+use crate::math::*;
+use crate::state::GlobalState;
+
+/// Snapshot of the `GlobalState` fields the quote math reads
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QuoteState {
+  pub total_lst_amount: u64,
+  /// SOL value held outside the vault being quoted (see `through_vault`)
+  pub other_tvl_lamports: u64,
+  /// Share of the quoted vault's value CR counts (BPS_PRECISION for the primary LST)
+  pub collateral_weight_bps: u64,
+  /// `other_tvl_lamports` at each vault's collateral weight
+  pub other_weighted_tvl_lamports: u64,
+  /// Part of the weighted book held in deprecated collateral, left out of the fee CR
+  pub deprecated_tvl_lamports: u64,
+  pub amusd_supply: u64,
+  pub asol_supply: u64,
+  pub rounding_reserve_lamports: u64,
+  pub insurance_fund_lamports: u64,
+  pub sol_price_usd: u64,
+  pub lst_to_sol_rate: u64,
+  pub min_cr_bps: u64,
+  pub target_cr_bps: u64,
+  pub fee_amusd_mint_bps: u64,
+  pub fee_amusd_redeem_bps: u64,
+  pub fee_asol_mint_bps: u64,
+  pub fee_asol_redeem_bps: u64,
+  pub fee_min_multiplier_bps: u64,
+  pub fee_max_multiplier_bps: u64,
+  /// Oracle uncertainty index; a flash loan earlier in the same slot adds
+  /// to it on chain (see `GlobalState::effective_uncertainty_index`)
+  pub uncertainty_index_bps: u64,
+  pub uncertainty_max_bps: u64,
+  pub recovery_bonus_bps: u64,
+  pub fee_split_insurance_bps: u64,
+  /// USD each amUSD redeems for, in bps of par
+  pub redemption_rate_bps: u64,
+  /// Global settlement: redemptions are fee-free and the last token out sweeps the vault
+  pub settled: bool,
+}
+
+impl From<&GlobalState> for QuoteState {
+  fn from(global_state: &GlobalState) -> Self {
+    // A settled book is valued at its frozen snapshot.
+    let (sol_price_usd, lst_to_sol_rate) = if global_state.settled {
+      (global_state.settlement_sol_price_usd, global_state.settlement_lst_to_sol_rate)
+    } else {
+      (global_state.mock_sol_price_usd, global_state.mock_lst_to_sol_rate)
+    };
+    Self {
+      total_lst_amount: global_state.total_lst_amount,
+      other_tvl_lamports: global_state.collateral_tvl_lamports,
+      collateral_weight_bps: BPS_PRECISION,
+      other_weighted_tvl_lamports: global_state.weighted_collateral_tvl_lamports,
+      deprecated_tvl_lamports: global_state.deprecated_collateral_tvl_lamports,
+      amusd_supply: global_state.amusd_supply,
+      asol_supply: global_state.asol_supply,
+      rounding_reserve_lamports: global_state.rounding_reserve_lamports,
+      insurance_fund_lamports: global_state.insurance_fund_lamports,
+      sol_price_usd,
+      lst_to_sol_rate,
+      min_cr_bps: global_state.min_cr_bps,
+      target_cr_bps: global_state.target_cr_bps,
+      fee_amusd_mint_bps: global_state.fee_amusd_mint_bps,
+      fee_amusd_redeem_bps: global_state.fee_amusd_redeem_bps,
+      fee_asol_mint_bps: global_state.fee_asol_mint_bps,
+      fee_asol_redeem_bps: global_state.fee_asol_redeem_bps,
+      fee_min_multiplier_bps: global_state.fee_min_multiplier_bps,
+      fee_max_multiplier_bps: global_state.fee_max_multiplier_bps,
+      uncertainty_index_bps: global_state.uncertainty_index_bps,
+      uncertainty_max_bps: global_state.uncertainty_max_bps,
+      recovery_bonus_bps: global_state.recovery_bonus_bps,
+      fee_split_insurance_bps: global_state.fee_split_insurance_bps,
+      redemption_rate_bps: global_state.redemption_rate_bps,
+      settled: global_state.settled,
+    }
+  }
+}
+
+impl QuoteState {
+  /// The same book seen through another collateral vault: quotes then move
+  /// that vault's LST at its rate, and `new_total_lst_amount` is its balance.
+  pub fn through_vault(&self, lst_amount: u64, lst_to_sol_rate: u64, collateral_weight_bps: u64) -> Option<Self> {
+    let other_tvl_lamports = self.tvl()?.checked_sub(compute_tvl_sol(lst_amount, lst_to_sol_rate)?)?;
+    let other_weighted_tvl_lamports = self
+      .weighted_tvl()?
+      .checked_sub(compute_weighted_tvl_sol(lst_amount, lst_to_sol_rate, collateral_weight_bps)?)?;
+    Some(Self {
+      total_lst_amount: lst_amount,
+      lst_to_sol_rate,
+      other_tvl_lamports,
+      collateral_weight_bps,
+      other_weighted_tvl_lamports,
+      ..*self
+    })
+  }
+
+  /// Book TVL at full value, the one payouts and NAV use
+  pub fn tvl(&self) -> Option<u64> {
+    compute_book_tvl_sol(self.total_lst_amount, self.lst_to_sol_rate, self.other_tvl_lamports)
+  }
+
+  /// Book TVL at collateral weights, the one CR is taken on
+  pub fn weighted_tvl(&self) -> Option<u64> {
+    compute_weighted_tvl_sol(self.total_lst_amount, self.lst_to_sol_rate, self.collateral_weight_bps)?
+      .checked_add(self.other_weighted_tvl_lamports)
+  }
+
+  /// amUSD liability in lamports at the redemption rate
+  pub fn liability(&self) -> Option<u64> {
+    if self.amusd_supply > 0 {
+      compute_liability_at_rate_sol(self.amusd_supply, self.sol_price_usd, self.redemption_rate_bps)
+    } else {
+      Some(0)
+    }
+  }
+
+  /// Rounding reserve plus insurance fund, held back from aSOL holders
+  pub fn non_claimable(&self) -> Option<u64> {
+    compute_non_claimable_sol(self.rounding_reserve_lamports, self.insurance_fund_lamports)
+  }
+
+  /// CR on the unweighted book with the insurance fund left out; sizes the
+  /// amUSD redemption haircut
+  pub fn uninsured_cr_bps(&self) -> Option<u64> {
+    Some(compute_uninsured_cr_bps(self.tvl()?, self.liability()?, self.insurance_fund_lamports))
+  }
+
+  /// (insolvency_mode, haircut_bps) an amUSD redeem takes on this book
+  pub fn redeem_haircut(&self) -> Option<(bool, u64)> {
+    Some(derive_redeem_haircut_bps(self.uninsured_cr_bps()?))
+  }
+
+  /// Lamports `amusd_amount` redeems for at par, rounded down
+  pub fn par_sol_value(&self, amusd_amount: u64) -> Option<u64> {
+    let amusd_value_down = mul_div_down(amusd_amount, self.redemption_rate_bps, BPS_PRECISION)?;
+    mul_div_down(amusd_value_down, SOL_PRECISION, self.sol_price_usd)
+  }
+
+  /// LST `amusd_amount` redeems for at par, rounded down
+  pub fn par_lst_value(&self, amusd_amount: u64) -> Option<u64> {
+    mul_div_down(self.par_sol_value(amusd_amount)?, SOL_PRECISION, self.lst_to_sol_rate)
+  }
+}
+
+/// Outputs of one instruction as committed by its handler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+  /// Tokens priced before the fee: minted on a mint, burned on a redeem
+  pub gross: u64,
+  /// Dynamic fee rate charged; 0 on the fee-free settlement and haircut paths
+  pub fee_bps: u64,
+  /// CR multiplier behind `fee_bps`; neutral (10_000) on the fee-free paths
+  pub cr_multiplier_bps: u64,
+  /// Uncertainty multiplier behind `fee_bps`; neutral on the fee-free paths
+  pub uncertainty_multiplier_bps: u64,
+  /// Tokens delivered to the user (amUSD/aSOL on mint, LST on redeem)
+  pub to_user: u64,
+  /// Fee routed to the treasury, in the fee-bearing token
+  pub fee: u64,
+  /// Fee share booked to the insurance fund instead, in the fee-bearing token
+  pub insurance_fee: u64,
+  pub new_total_lst_amount: u64,
+  /// New supply of the token this instruction mints or burns
+  pub new_supply: u64,
+  pub new_rounding_reserve: u64,
+  pub new_insurance_fund: u64,
+  /// Reserve debit the user-favoring rounding needed when the reserve could
+  /// not cover it and the payout was rounded down instead; redeems only
+  pub rounding_fallback_debit: Option<u64>,
+}
+
+/// Quote `mint_amusd` for `lst_amount` LST received into the vault
+pub fn quote_mint_amusd(state: &QuoteState, lst_amount: u64) -> Option<Quote> {
+  let old_weighted_tvl = state.weighted_tvl()?;
+  let old_liability = state.liability()?;
+  let fee_cr_bps = compute_fee_cr_bps(old_weighted_tvl, old_liability, state.deprecated_tvl_lamports);
+
+  let sol_value = compute_tvl_sol(lst_amount, state.lst_to_sol_rate)?;
+  let sol_value_up = mul_div_up(lst_amount, state.lst_to_sol_rate, SOL_PRECISION)?;
+
+  let amusd_gross = mul_div_down(sol_value, state.sol_price_usd, SOL_PRECISION)?;
+  let amusd_gross_up = mul_div_up(sol_value_up, state.sol_price_usd, SOL_PRECISION)?;
+
+  let mint_rounding_delta_usd = compute_rounding_delta_units(amusd_gross, amusd_gross_up)?;
+  let reserve_credit = usd_dust_to_lamports_up(mint_rounding_delta_usd, state.sol_price_usd)?;
+
+  let dynamic_fee = compute_dynamic_fee(state.fee_amusd_mint_bps, FeeAction::AmusdMint, fee_cr_bps, state.min_cr_bps, state.target_cr_bps, state.fee_min_multiplier_bps, state.fee_max_multiplier_bps, state.uncertainty_index_bps, state.uncertainty_max_bps)?;
+  let (amusd_to_user, amusd_fee) = apply_fee(amusd_gross, dynamic_fee.fee_bps)?;
+  let (treasury_fee, insurance_fee) = split_fee(amusd_fee, state.fee_split_insurance_bps)?;
+  let insurance_credit = mul_div_down(insurance_fee, SOL_PRECISION, state.sol_price_usd)?;
+
+  Some(Quote {
+    gross: amusd_gross,
+    fee_bps: dynamic_fee.fee_bps,
+    cr_multiplier_bps: dynamic_fee.cr_multiplier_bps,
+    uncertainty_multiplier_bps: dynamic_fee.uncertainty_multiplier_bps,
+    to_user: amusd_to_user,
+    fee: treasury_fee,
+    insurance_fee,
+    new_total_lst_amount: state.total_lst_amount.checked_add(lst_amount)?,
+    new_supply: state.amusd_supply.checked_add(amusd_gross)?.checked_sub(insurance_fee)?,
+    new_rounding_reserve: state.rounding_reserve_lamports.checked_add(reserve_credit)?,
+    new_insurance_fund: state.insurance_fund_lamports.checked_add(insurance_credit)?,
+    rounding_fallback_debit: None,
+  })
+}
+
+/// Quote `redeem_amusd` for `amusd_amount` amUSD
+pub fn quote_redeem_amusd(state: &QuoteState, amusd_amount: u64) -> Option<Quote> {
+  let old_weighted_tvl = state.weighted_tvl()?;
+  let old_liability = state.liability()?;
+  let fee_cr_bps = compute_fee_cr_bps(old_weighted_tvl, old_liability, state.deprecated_tvl_lamports);
+
+  // `state` is post-drawdown: the handler refuses a sub-min CR while the
+  // Stability Pool can still absorb it, so CR here is the post-drawdown CR.
+  let (insolvency_mode, haircut_bps) = state.redeem_haircut()?;
+
+  let dynamic_fee = if insolvency_mode || state.settled {
+    DynamicFee::NONE
+  } else {
+    compute_dynamic_fee(state.fee_amusd_redeem_bps, FeeAction::AmUSDRedeem, fee_cr_bps, state.min_cr_bps, state.target_cr_bps, state.fee_min_multiplier_bps, state.fee_max_multiplier_bps, state.uncertainty_index_bps, state.uncertainty_max_bps)?
+  };
+  let (amusd_net_in, amusd_fee_in) = apply_fee(amusd_amount, dynamic_fee.fee_bps)?;
+  let (treasury_fee, insurance_fee) = split_fee(amusd_fee_in, state.fee_split_insurance_bps)?;
+  let insurance_value = mul_div_down(insurance_fee, state.redemption_rate_bps, BPS_PRECISION)?;
+  let insurance_credit = mul_div_down(insurance_value, SOL_PRECISION, state.sol_price_usd)?;
+
+  let sol_value_par_down = state.par_sol_value(amusd_net_in)?;
+  let lst_par_down = state.par_lst_value(amusd_net_in)?;
+
+  let mut rounding_fallback_debit = None;
+  let (lst_out, reserve_debit, insurance_debit) = if insolvency_mode {
+    let sol_value_haircut = mul_div_down(sol_value_par_down, haircut_bps, BPS_PRECISION)?;
+    let insurance_topup = compute_insurance_topup(sol_value_par_down, sol_value_haircut, state.insurance_fund_lamports)?;
+    let sol_value_paid = sol_value_haircut.checked_add(insurance_topup)?;
+    (mul_div_down(sol_value_paid, SOL_PRECISION, state.lst_to_sol_rate)?, 0u64, insurance_topup)
+  } else {
+    let amusd_value_up = mul_div_up(amusd_net_in, state.redemption_rate_bps, BPS_PRECISION)?;
+    let sol_value_up = mul_div_up(amusd_value_up, SOL_PRECISION, state.sol_price_usd)?;
+    let lst_gross_up = mul_div_up(sol_value_up, SOL_PRECISION, state.lst_to_sol_rate)?;
+    let delta_lst = compute_rounding_delta_units(lst_par_down, lst_gross_up)?;
+    let lamport_debit = lst_dust_to_lamports_up(delta_lst, state.lst_to_sol_rate)?;
+
+    if lamport_debit <= state.rounding_reserve_lamports {
+      (lst_gross_up, lamport_debit, 0u64)
+    } else {
+      rounding_fallback_debit = Some(lamport_debit);
+      (lst_par_down, 0u64, 0u64)
+    }
+  };
+
+  let (lst_out, reserve_debit, insurance_debit) = if state.settled && amusd_net_in == state.amusd_supply && (state.asol_supply == 0 || insolvency_mode) {
+    // The sweep takes the whole reserve; nothing fell back.
+    rounding_fallback_debit = None;
+    (state.total_lst_amount, state.rounding_reserve_lamports, state.insurance_fund_lamports)
+  } else {
+    (lst_out, reserve_debit, insurance_debit)
+  };
+
+  Some(Quote {
+    gross: amusd_amount,
+    fee_bps: dynamic_fee.fee_bps,
+    cr_multiplier_bps: dynamic_fee.cr_multiplier_bps,
+    uncertainty_multiplier_bps: dynamic_fee.uncertainty_multiplier_bps,
+    to_user: lst_out,
+    fee: treasury_fee,
+    insurance_fee,
+    new_total_lst_amount: state.total_lst_amount.checked_sub(lst_out)?,
+    new_supply: state.amusd_supply.checked_sub(amusd_net_in)?.checked_sub(insurance_fee)?,
+    new_rounding_reserve: state.rounding_reserve_lamports.checked_sub(reserve_debit)?,
+    new_insurance_fund: state.insurance_fund_lamports.checked_add(insurance_credit)?.checked_sub(insurance_debit)?,
+    rounding_fallback_debit,
+  })
+}
+
+/// Quote `mint_asol` for `lst_amount` LST received into the vault
+pub fn quote_mint_asol(state: &QuoteState, lst_amount: u64) -> Option<Quote> {
+  let old_tvl = state.tvl()?;
+  let old_weighted_tvl = state.weighted_tvl()?;
+  let old_liability = state.liability()?;
+  let old_claimable_equity = compute_claimable_equity_sol(old_tvl, old_liability, state.non_claimable()?)?;
+  let old_cr_bps = compute_cr_bps(old_weighted_tvl, old_liability);
+  let fee_cr_bps = compute_fee_cr_bps(old_weighted_tvl, old_liability, state.deprecated_tvl_lamports);
+
+  // Bootstrap sweeps dust-only claimable equity into the reserve.
+  let mut effective_rounding_reserve = state.rounding_reserve_lamports;
+  if state.asol_supply == 0 && old_claimable_equity > 0 {
+    effective_rounding_reserve = effective_rounding_reserve.checked_add(old_claimable_equity)?;
+  }
+
+  let sol_value = compute_tvl_sol(lst_amount, state.lst_to_sol_rate)?;
+  let sol_value_up = mul_div_up(lst_amount, state.lst_to_sol_rate, SOL_PRECISION)?;
+
+  let (asol_gross, asol_reference_up, current_nav) = if state.asol_supply == 0 {
+    (sol_value, sol_value_up, SOL_PRECISION)
+  } else {
+    let effective_non_claimable = compute_non_claimable_sol(effective_rounding_reserve, state.insurance_fund_lamports)?;
+    let nav = nav_asol_with_reserve(old_tvl, old_liability, effective_non_claimable, state.asol_supply)?;
+    if nav == 0 {
+      return None;
+    }
+    (
+      mul_div_down(sol_value, SOL_PRECISION, nav)?,
+      mul_div_up(sol_value_up, SOL_PRECISION, nav)?,
+      nav,
+    )
+  };
+
+  let delta_asol = compute_rounding_delta_units(asol_gross, asol_reference_up)?;
+  let reserve_credit = if state.asol_supply == 0 {
+    delta_asol
+  } else {
+    asol_dust_to_lamports_up(delta_asol, current_nav)?
+  };
+
+  let dynamic_fee = compute_dynamic_fee(state.fee_asol_mint_bps, FeeAction::AsolMint, fee_cr_bps, state.min_cr_bps, state.target_cr_bps, state.fee_min_multiplier_bps, state.fee_max_multiplier_bps, state.uncertainty_index_bps, state.uncertainty_max_bps)?;
+  let (asol_net, fee) = apply_fee(asol_gross, dynamic_fee.fee_bps)?;
+  let (treasury_fee, insurance_fee) = split_fee(fee, state.fee_split_insurance_bps)?;
+  let insurance_credit = mul_div_down(insurance_fee, current_nav, SOL_PRECISION)?;
+
+  let recovery_bonus = if state.asol_supply > 0 && old_cr_bps < state.min_cr_bps {
+    compute_recovery_bonus_asol(asol_gross, state.asol_supply, state.recovery_bonus_bps, MAX_RECOVERY_DILUTION_BPS)?
+  } else {
+    0
+  };
+
+  Some(Quote {
+    gross: asol_gross,
+    fee_bps: dynamic_fee.fee_bps,
+    cr_multiplier_bps: dynamic_fee.cr_multiplier_bps,
+    uncertainty_multiplier_bps: dynamic_fee.uncertainty_multiplier_bps,
+    to_user: asol_net.checked_add(recovery_bonus)?,
+    fee: treasury_fee,
+    insurance_fee,
+    new_total_lst_amount: state.total_lst_amount.checked_add(lst_amount)?,
+    new_supply: state.asol_supply.checked_add(asol_gross)?.checked_add(recovery_bonus)?.checked_sub(insurance_fee)?,
+    new_rounding_reserve: effective_rounding_reserve.checked_add(reserve_credit)?,
+    new_insurance_fund: state.insurance_fund_lamports.checked_add(insurance_credit)?,
+    rounding_fallback_debit: None,
+  })
+}
+
+/// Quote `redeem_asol` for `asol_amount` aSOL
+pub fn quote_redeem_asol(state: &QuoteState, asol_amount: u64) -> Option<Quote> {
+  let old_tvl = state.tvl()?;
+  let old_weighted_tvl = state.weighted_tvl()?;
+  let old_liability = state.liability()?;
+  let fee_cr_bps = compute_fee_cr_bps(old_weighted_tvl, old_liability, state.deprecated_tvl_lamports);
+
+  let dynamic_fee = if state.settled {
+    DynamicFee::NONE
+  } else {
+    compute_dynamic_fee(state.fee_asol_redeem_bps, FeeAction::AsolRedeem, fee_cr_bps, state.min_cr_bps, state.target_cr_bps, state.fee_min_multiplier_bps, state.fee_max_multiplier_bps, state.uncertainty_index_bps, state.uncertainty_max_bps)?
+  };
+  let (asol_net_in, asol_fee_in) = apply_fee(asol_amount, dynamic_fee.fee_bps)?;
+  let final_settlement_exit = state.settled && asol_net_in == state.asol_supply && state.amusd_supply == 0;
+
+  let current_nav = nav_asol_with_reserve(old_tvl, old_liability, state.non_claimable()?, state.asol_supply)?;
+  if current_nav == 0 && !final_settlement_exit {
+    return None;
+  }
+
+  let (treasury_fee, insurance_fee) = split_fee(asol_fee_in, state.fee_split_insurance_bps)?;
+  let insurance_credit = mul_div_down(insurance_fee, current_nav, SOL_PRECISION)?;
+
+  let sol_value_down = mul_div_down(asol_net_in, current_nav, SOL_PRECISION)?;
+  let lst_gross_down = mul_div_down(sol_value_down, SOL_PRECISION, state.lst_to_sol_rate)?;
+
+  let mut rounding_fallback_debit = None;
+  let (lst_out, reserve_debit) = if compute_cr_bps(old_tvl, old_liability) >= BPS_PRECISION {
+    let sol_value_up = mul_div_up(asol_net_in, current_nav, SOL_PRECISION)?;
+    let lst_gross_up = mul_div_up(sol_value_up, SOL_PRECISION, state.lst_to_sol_rate)?;
+    let delta_lst = compute_rounding_delta_units(lst_gross_down, lst_gross_up)?;
+    let lamport_debit = lst_dust_to_lamports_up(delta_lst, state.lst_to_sol_rate)?;
+
+    if lamport_debit <= state.rounding_reserve_lamports {
+      (lst_gross_up, lamport_debit)
+    } else {
+      rounding_fallback_debit = Some(lamport_debit);
+      (lst_gross_down, 0u64)
+    }
+  } else {
+    (lst_gross_down, 0u64)
+  };
+
+  let (lst_out, reserve_debit, insurance_debit) = if final_settlement_exit {
+    rounding_fallback_debit = None;
+    (state.total_lst_amount, state.rounding_reserve_lamports, state.insurance_fund_lamports)
+  } else {
+    (lst_out, reserve_debit, 0u64)
+  };
+
+  Some(Quote {
+    gross: asol_amount,
+    fee_bps: dynamic_fee.fee_bps,
+    cr_multiplier_bps: dynamic_fee.cr_multiplier_bps,
+    uncertainty_multiplier_bps: dynamic_fee.uncertainty_multiplier_bps,
+    to_user: lst_out,
+    fee: treasury_fee,
+    insurance_fee,
+    new_total_lst_amount: state.total_lst_amount.checked_sub(lst_out)?,
+    new_supply: state.asol_supply.checked_sub(asol_net_in)?.checked_sub(insurance_fee)?,
+    new_rounding_reserve: state.rounding_reserve_lamports.checked_sub(reserve_debit)?,
+    new_insurance_fund: state.insurance_fund_lamports.checked_add(insurance_credit)?.checked_sub(insurance_debit)?,
+    rounding_fallback_debit,
+  })
+}
+
+impl QuoteState {
+  /// CR of this book at collateral weights
+  pub fn cr_bps(&self) -> Option<u64> {
+    Some(compute_cr_bps(self.weighted_tvl()?, self.liability()?))
+  }
+
+  /// Reserve-aware aSOL NAV of this book; `None` while there is no aSOL supply
+  pub fn nav(&self) -> Option<u64> {
+    nav_asol_with_reserve(self.tvl()?, self.liability()?, self.non_claimable()?, self.asol_supply)
+  }
+
+  /// The book `quote` leaves behind; `action` names the supply it moved
+  pub fn after(&self, action: FeeAction, quote: &Quote) -> Self {
+    let mut next = Self {
+      total_lst_amount: quote.new_total_lst_amount,
+      rounding_reserve_lamports: quote.new_rounding_reserve,
+      insurance_fund_lamports: quote.new_insurance_fund,
+      ..*self
+    };
+    match action {
+      FeeAction::AmusdMint | FeeAction::AmUSDRedeem => next.amusd_supply = quote.new_supply,
+      FeeAction::AsolMint | FeeAction::AsolRedeem => next.asol_supply = quote.new_supply,
+    }
+    next
+  }
+
+  /// `action` for `amount` as the quote instructions report it, from the
+  /// same `quote` function its handler executes
+  pub fn trade_quote(&self, action: FeeAction, amount: u64) -> Option<TradeQuote> {
+    let quote = match action {
+      FeeAction::AmusdMint => quote_mint_amusd(self, amount),
+      FeeAction::AmUSDRedeem => quote_redeem_amusd(self, amount),
+      FeeAction::AsolMint => quote_mint_asol(self, amount),
+      FeeAction::AsolRedeem => quote_redeem_asol(self, amount),
+    }?;
+
+    let (insolvency_mode, haircut_bps, par_net) = match action {
+      FeeAction::AmUSDRedeem => {
+        let (insolvency_mode, haircut_bps) = self.redeem_haircut()?;
+        let par_net = if insolvency_mode {
+          self.par_lst_value(amount.checked_sub(quote.fee)?.checked_sub(quote.insurance_fee)?)?
+        } else {
+          quote.to_user
+        };
+        (insolvency_mode, haircut_bps, par_net)
+      }
+      FeeAction::AsolRedeem => (compute_cr_bps(self.tvl()?, self.liability()?) < BPS_PRECISION, BPS_PRECISION, quote.to_user),
+      FeeAction::AmusdMint | FeeAction::AsolMint => (false, BPS_PRECISION, quote.to_user),
+    };
+
+    let after = self.after(action, &quote);
+    let nav = if after.asol_supply == 0 { 0 } else { after.nav()? };
+
+    Some(TradeQuote {
+      gross: quote.gross,
+      fee_bps: quote.fee_bps,
+      fee: quote.fee.checked_add(quote.insurance_fee)?,
+      net: quote.to_user,
+      nav,
+      cr_bps: after.cr_bps()?,
+      insolvency_mode,
+      haircut_bps,
+      par_net,
+    })
+  }
+
+  /// Configured base fee of `action`
+  pub fn base_fee_bps(&self, action: FeeAction) -> u64 {
+    match action {
+      FeeAction::AmusdMint => self.fee_amusd_mint_bps,
+      FeeAction::AmUSDRedeem => self.fee_amusd_redeem_bps,
+      FeeAction::AsolMint => self.fee_asol_mint_bps,
+      FeeAction::AsolRedeem => self.fee_asol_redeem_bps,
+    }
+  }
+
+  /// Fee `action` is charged on this book now: the dynamic fee at the fee
+  /// CR, or 0 on the settlement and amUSD haircut paths
+  pub fn effective_fee_bps(&self, action: FeeAction) -> Option<u64> {
+    let haircut = action == FeeAction::AmUSDRedeem && self.redeem_haircut()?.0;
+    if self.settled || haircut {
+      return Some(0);
+    }
+    let fee_cr_bps = compute_fee_cr_bps(self.weighted_tvl()?, self.liability()?, self.deprecated_tvl_lamports);
+    compute_dynamic_fee_bps(self.base_fee_bps(action), action, fee_cr_bps, self.min_cr_bps, self.target_cr_bps, self.fee_min_multiplier_bps, self.fee_max_multiplier_bps, self.uncertainty_index_bps, self.uncertainty_max_bps)
+  }
+
+  /// Balance sheet and fee schedule of this book
+  pub fn metrics(&self) -> Option<ProtocolMetrics> {
+    let tvl = self.tvl()?;
+    let liability = self.liability()?;
+    let non_claimable = self.non_claimable()?;
+    let nav_amusd = mul_div_down(nav_amusd(self.sol_price_usd)?, self.redemption_rate_bps, BPS_PRECISION)?;
+    let nav_asol = if self.asol_supply == 0 { None } else { Some(self.nav()?) };
+
+    let mut fee_bps = [0; 4];
+    for (wire, fee) in fee_bps.iter_mut().enumerate() {
+      *fee = self.effective_fee_bps(FeeAction::try_from(wire as u8).ok()?)?;
+    }
+
+    Some(ProtocolMetrics {
+      tvl,
+      liability,
+      accounting_equity: compute_accounting_equity_sol(tvl, liability, non_claimable)?,
+      claimable_equity: compute_claimable_equity_sol(tvl, liability, non_claimable)?,
+      cr_bps: self.cr_bps()?,
+      nav_amusd,
+      nav_asol,
+      fee_bps,
+    })
+  }
+}
+
+/// Derived values of one book, computed the way the handlers compute them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProtocolMetrics {
+  /// Book TVL in lamports, every vault at full value
+  pub tvl: u64,
+  /// amUSD liability in lamports at the redemption rate
+  pub liability: u64,
+  /// TVL less liability, rounding reserve and insurance fund; negative while insolvent
+  pub accounting_equity: i128,
+  /// `accounting_equity` clamped at zero, what aSOL holders can claim
+  pub claimable_equity: u64,
+  /// CR on the collateral-weighted book
+  pub cr_bps: u64,
+  /// Lamports per amUSD at the redemption rate
+  pub nav_amusd: u64,
+  /// Reserve-aware lamports per aSOL; `None` with no aSOL outstanding
+  pub nav_asol: Option<u64>,
+  /// Effective fee of each `FeeAction`, indexed by its wire encoding
+  pub fee_bps: [u64; 4],
+}
+
+impl ProtocolMetrics {
+  /// Effective fee of `action`
+  pub fn fee_bps_for(&self, action: FeeAction) -> u64 {
+    self.fee_bps[action as usize]
+  }
+}
+
+impl GlobalState {
+  /// `QuoteState::metrics` of this account: cached prices, or the frozen
+  /// snapshot once settled. Flash-loan utilization, which only prices fees
+  /// in the slot it was recorded, is left out.
+  pub fn metrics(&self) -> Option<ProtocolMetrics> {
+    QuoteState::from(self).metrics()
+  }
+}
+
+/// Sample the dynamic fee of `action` as `(cr_bps, fee_bps)` pairs for charting.
+///
+/// Points are spaced evenly over `[min_cr, target_cr + (target_cr - min_cr)]`,
+/// so the ramp and the flat plateau above target get equal width. Both
+/// endpoints and `target_cr` itself are always sampled: an odd `n_points`
+/// lands target on the grid, otherwise it is inserted, giving `n_points + 1`
+/// entries. Every fee comes from `compute_dynamic_fee_bps` with the same
+/// inputs the handlers pass, including the current uncertainty index.
+/// `None` when `n_points < 2`, `target_cr <= min_cr`, or the math fails.
+pub fn fee_curve_table(action: FeeAction, params: &QuoteState, n_points: usize) -> Option<Vec<(u64, u64)>> {
+  if n_points < 2 || params.target_cr_bps <= params.min_cr_bps {
+    return None;
+  }
+  let lo = params.min_cr_bps;
+  let hi = params.target_cr_bps.checked_add(params.target_cr_bps - lo)?;
+  let segments = (n_points - 1) as u64;
+
+  let mut crs = Vec::with_capacity(n_points + 1);
+  for i in 0..=segments {
+    crs.push(lo.checked_add(mul_div_down(hi - lo, i, segments)?)?);
+  }
+  if let Err(at) = crs.binary_search(&params.target_cr_bps) {
+    crs.insert(at, params.target_cr_bps);
+  }
+
+  crs
+    .into_iter()
+    .map(|cr_bps| {
+      let fee_bps = compute_dynamic_fee_bps(params.base_fee_bps(action), action, cr_bps, params.min_cr_bps, params.target_cr_bps, params.fee_min_multiplier_bps, params.fee_max_multiplier_bps, params.uncertainty_index_bps, params.uncertainty_max_bps)?;
+      Some((cr_bps, fee_bps))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::constants::{
+    AMUSD_MINT_FEE_BPS, AMUSD_REDEEM_FEE_BPS, ASOL_MINT_FEE_BPS, ASOL_REDEEM_FEE_BPS, DEFAULT_FEE_MAX_MULTIPLIER_BPS,
+    DEFAULT_MIN_CR_BPS, DEFAULT_TARGET_CR_BPS,
+  };
+  use anchor_lang::{AccountSerialize, AnchorDeserialize};
+
+  const ACTIONS: [FeeAction; 4] = [FeeAction::AmusdMint, FeeAction::AmUSDRedeem, FeeAction::AsolMint, FeeAction::AsolRedeem];
+
+  fn params() -> QuoteState {
+    QuoteState {
+      min_cr_bps: DEFAULT_MIN_CR_BPS,
+      target_cr_bps: DEFAULT_TARGET_CR_BPS,
+      collateral_weight_bps: BPS_PRECISION,
+      fee_amusd_mint_bps: AMUSD_MINT_FEE_BPS,
+      fee_amusd_redeem_bps: AMUSD_REDEEM_FEE_BPS,
+      fee_asol_mint_bps: ASOL_MINT_FEE_BPS,
+      fee_asol_redeem_bps: ASOL_REDEEM_FEE_BPS,
+      fee_min_multiplier_bps: 5_000,
+      fee_max_multiplier_bps: DEFAULT_FEE_MAX_MULTIPLIER_BPS,
+      uncertainty_index_bps: 0,
+      uncertainty_max_bps: 20_000,
+      ..QuoteState::default()
+    }
+  }
+
+  fn direct(action: FeeAction, p: &QuoteState, cr_bps: u64) -> u64 {
+    compute_dynamic_fee_bps(p.base_fee_bps(action), action, cr_bps, p.min_cr_bps, p.target_cr_bps, p.fee_min_multiplier_bps, p.fee_max_multiplier_bps, p.uncertainty_index_bps, p.uncertainty_max_bps).unwrap()
+  }
+
+  #[test]
+  fn test_fee_curve_table_endpoints_match_direct_computation() {
+    let p = params();
+    let hi = 2 * p.target_cr_bps - p.min_cr_bps;
+    for action in ACTIONS {
+      for n_points in [2, 16, 17] {
+        let table = fee_curve_table(action, &p, n_points).unwrap();
+        assert_eq!(table.first(), Some(&(p.min_cr_bps, direct(action, &p, p.min_cr_bps))));
+        assert_eq!(table.last(), Some(&(hi, direct(action, &p, hi))));
+        assert!(table.contains(&(p.target_cr_bps, direct(action, &p, p.target_cr_bps))));
+        assert!(table.windows(2).all(|w| w[0].0 < w[1].0));
+        for &(cr_bps, fee_bps) in &table {
+          assert_eq!(fee_bps, direct(action, &p, cr_bps));
+        }
+      }
+      // Odd point counts put target on the grid without an extra entry.
+      assert_eq!(fee_curve_table(action, &p, 17).unwrap().len(), 17);
+      assert_eq!(fee_curve_table(action, &p, 16).unwrap().len(), 17);
+    }
+  }
+
+  #[test]
+  fn test_fee_curve_table_plateaus_are_flat() {
+    let p = params();
+    for action in ACTIONS {
+      let table = fee_curve_table(action, &p, 33).unwrap();
+      let base = p.base_fee_bps(action);
+
+      // At and above target the multiplier is neutral.
+      assert!(table.iter().filter(|(cr, _)| *cr >= p.target_cr_bps).all(|(_, fee)| *fee == base));
+
+      // The ramp moves monotonically toward the clamped value at min_cr.
+      let ramp: Vec<u64> = table.iter().filter(|(cr, _)| *cr <= p.target_cr_bps).map(|(_, fee)| *fee).collect();
+      if action.is_risk_increasing() {
+        assert_eq!(ramp[0], base * p.fee_max_multiplier_bps / BPS_PRECISION);
+        assert!(ramp.windows(2).all(|w| w[0] >= w[1]));
+      } else {
+        assert_eq!(ramp[0], base * p.fee_min_multiplier_bps / BPS_PRECISION);
+        assert!(ramp.windows(2).all(|w| w[0] <= w[1]));
+      }
+    }
+  }
+
+  #[test]
+  fn test_fee_curve_table_rejects_degenerate_inputs() {
+    let p = params();
+    assert_eq!(fee_curve_table(FeeAction::AmusdMint, &p, 1), None);
+    let flat = QuoteState { target_cr_bps: p.min_cr_bps, ..p };
+    assert_eq!(fee_curve_table(FeeAction::AmusdMint, &flat, 17), None);
+  }
+
+  #[test]
+  fn test_through_vault_keeps_the_book_and_moves_the_vault() {
+    let book = QuoteState {
+      total_lst_amount: 100 * SOL_PRECISION,
+      other_tvl_lamports: 50 * SOL_PRECISION,
+      other_weighted_tvl_lamports: 50 * SOL_PRECISION,
+      lst_to_sol_rate: 1_050_000_000,
+      sol_price_usd: 100 * USD_PRECISION,
+      amusd_supply: 5_000 * USD_PRECISION,
+      asol_supply: 100 * SOL_PRECISION,
+      redemption_rate_bps: BPS_PRECISION,
+      ..params()
+    };
+    let vault = book.through_vault(50 * SOL_PRECISION, SOL_PRECISION, BPS_PRECISION).unwrap();
+    assert_eq!(vault.tvl(), book.tvl());
+    assert_eq!(vault.weighted_tvl(), book.weighted_tvl());
+    assert_eq!(vault.other_tvl_lamports, 105 * SOL_PRECISION);
+    assert_eq!(vault.other_weighted_tvl_lamports, 105 * SOL_PRECISION);
+
+    // Same book, same CR: the fee rate matches and only the vault grows.
+    let via_book = quote_mint_amusd(&book, 10 * SOL_PRECISION).unwrap();
+    let via_vault = quote_mint_amusd(&vault, 10 * SOL_PRECISION).unwrap();
+    assert_eq!(via_vault.new_total_lst_amount, 60 * SOL_PRECISION);
+    assert!(via_vault.to_user < via_book.to_user);
+
+    // A vault worth more than the book is not part of it.
+    assert_eq!(book.through_vault(u64::MAX / 2, SOL_PRECISION, BPS_PRECISION), None);
+  }
+
+  #[test]
+  fn test_haircut_redeem_pays_the_haircut_share_of_par() {
+    // 100 SOL of LST against 12,000 amUSD at $100: 83.33% CR, no insurance
+    let book = QuoteState {
+      total_lst_amount: 100 * SOL_PRECISION,
+      lst_to_sol_rate: SOL_PRECISION,
+      sol_price_usd: 100 * USD_PRECISION,
+      amusd_supply: 12_000 * USD_PRECISION,
+      asol_supply: 100 * SOL_PRECISION,
+      redemption_rate_bps: BPS_PRECISION,
+      ..params()
+    };
+    assert_eq!(book.redeem_haircut(), Some((true, 8_333)));
+
+    let quote = quote_redeem_amusd(&book, 1_000 * USD_PRECISION).unwrap();
+    let par = book.par_lst_value(1_000 * USD_PRECISION).unwrap();
+    assert_eq!(quote.fee_bps, 0);
+    assert_eq!(par, 10 * SOL_PRECISION);
+    assert_eq!(quote.to_user, mul_div_down(par, 8_333, BPS_PRECISION).unwrap());
+
+    let solvent = QuoteState { amusd_supply: 5_000 * USD_PRECISION, ..book };
+    assert_eq!(solvent.redeem_haircut(), Some((false, BPS_PRECISION)));
+  }
+
+  #[test]
+  fn test_haircut_trade_quote_matches_what_redeem_executes() {
+    // 83.33% CR with a small insurance fund that tops up part of the haircut
+    let book = QuoteState {
+      total_lst_amount: 100 * SOL_PRECISION,
+      lst_to_sol_rate: 1_050_000_000,
+      sol_price_usd: 105 * USD_PRECISION,
+      amusd_supply: 12_600 * USD_PRECISION,
+      asol_supply: 100 * SOL_PRECISION,
+      insurance_fund_lamports: SOL_PRECISION / 10,
+      redemption_rate_bps: BPS_PRECISION,
+      ..params()
+    };
+    let amusd_amount = 1_000 * USD_PRECISION;
+    let reported = book.trade_quote(FeeAction::AmUSDRedeem, amusd_amount).unwrap();
+    let executed = quote_redeem_amusd(&book, amusd_amount).unwrap();
+
+    // What `redeem_amusd` pays and commits
+    assert!(reported.insolvency_mode);
+    assert_eq!(reported.net, executed.to_user);
+    assert_eq!(reported.fee, 0);
+    assert_eq!(reported.cr_bps, book.after(FeeAction::AmUSDRedeem, &executed).cr_bps().unwrap());
+
+    // ...and the haircut it records in `HaircutApplied`
+    let (_, haircut_bps) = derive_redeem_haircut_bps(book.uninsured_cr_bps().unwrap());
+    let sol_value_par_down = book.par_sol_value(amusd_amount).unwrap();
+    let sol_value_haircut = mul_div_down(sol_value_par_down, haircut_bps, BPS_PRECISION).unwrap();
+    let insurance_topup = compute_insurance_topup(sol_value_par_down, sol_value_haircut, book.insurance_fund_lamports).unwrap();
+    assert!(insurance_topup > 0 && insurance_topup == book.insurance_fund_lamports);
+    assert_eq!(reported.haircut_bps, haircut_bps);
+    assert_eq!(reported.par_net, book.par_lst_value(amusd_amount).unwrap());
+    assert_eq!(
+      reported.net,
+      mul_div_down(sol_value_haircut + insurance_topup, SOL_PRECISION, book.lst_to_sol_rate).unwrap()
+    );
+    assert_eq!(executed.new_insurance_fund, 0);
+    assert!(reported.net < reported.par_net);
+  }
+
+  #[test]
+  fn test_collateral_weight_lowers_cr_but_not_payouts() {
+    // 50 of the book's 155 SOL sits in a vault counted at 60%
+    let book = QuoteState {
+      total_lst_amount: 50 * SOL_PRECISION,
+      other_tvl_lamports: 105 * SOL_PRECISION,
+      other_weighted_tvl_lamports: 105 * SOL_PRECISION,
+      lst_to_sol_rate: SOL_PRECISION,
+      sol_price_usd: 100 * USD_PRECISION,
+      amusd_supply: 10_000 * USD_PRECISION,
+      asol_supply: 100 * SOL_PRECISION,
+      redemption_rate_bps: BPS_PRECISION,
+      ..params()
+    };
+    let weighted = QuoteState { collateral_weight_bps: 6_000, other_weighted_tvl_lamports: 105 * SOL_PRECISION, ..book };
+    assert_eq!(weighted.tvl(), book.tvl());
+    assert_eq!(weighted.weighted_tvl(), Some(135 * SOL_PRECISION));
+
+    // CR drops from 155% to 135%, so the amUSD mint fee rises...
+    let full = quote_mint_amusd(&book, SOL_PRECISION).unwrap();
+    let haircut = quote_mint_amusd(&weighted, SOL_PRECISION).unwrap();
+    assert!(haircut.to_user < full.to_user);
+    // ...while a fee-free aSOL exit still gets the unweighted NAV.
+    let free = |state: QuoteState| QuoteState { fee_asol_redeem_bps: 0, ..state };
+    assert_eq!(
+      quote_redeem_asol(&free(weighted), SOL_PRECISION).map(|quote| quote.to_user),
+      quote_redeem_asol(&free(book), SOL_PRECISION).map(|quote| quote.to_user),
+    );
+  }
+
+  #[test]
+  fn test_redeems_report_the_rounding_fallback() {
+    let book = QuoteState {
+      total_lst_amount: 100 * SOL_PRECISION,
+      lst_to_sol_rate: 1_050_000_000,
+      sol_price_usd: 100 * USD_PRECISION,
+      amusd_supply: 5_000 * USD_PRECISION,
+      asol_supply: 100 * SOL_PRECISION,
+      redemption_rate_bps: BPS_PRECISION,
+      fee_amusd_redeem_bps: 0,
+      fee_asol_redeem_bps: 0,
+      ..params()
+    };
+    let funded = QuoteState { rounding_reserve_lamports: SOL_PRECISION, ..book };
+
+    // 1 amUSD is 9_523_809.5 LST units; rounding up costs the reserve 2 lamports.
+    let short = quote_redeem_amusd(&book, USD_PRECISION).unwrap();
+    let covered = quote_redeem_amusd(&funded, USD_PRECISION).unwrap();
+    assert_eq!(short.rounding_fallback_debit, Some(2));
+    assert_eq!(short.new_rounding_reserve, 0);
+    assert_eq!(covered.rounding_fallback_debit, None);
+    assert_eq!(covered.new_rounding_reserve, SOL_PRECISION - 2);
+    assert_eq!(short.to_user + 1, covered.to_user);
+
+    assert_eq!(quote_redeem_asol(&book, SOL_PRECISION).unwrap().rounding_fallback_debit, Some(2));
+    assert_eq!(quote_redeem_asol(&funded, SOL_PRECISION).unwrap().rounding_fallback_debit, None);
+    assert_eq!(quote_mint_amusd(&book, SOL_PRECISION).unwrap().rounding_fallback_debit, None);
+  }
+
+  /// Account bytes as RPC returns them for a book with 105 SOL of TVL and
+  /// 50 SOL of liability
+  fn global_state_fixture() -> Vec<u8> {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.total_lst_amount = 100 * SOL_PRECISION;
+    state.mock_lst_to_sol_rate = 1_050_000_000;
+    state.mock_sol_price_usd = 100 * USD_PRECISION;
+    state.amusd_supply = 5_000 * USD_PRECISION;
+    state.asol_supply = 53 * SOL_PRECISION;
+    state.rounding_reserve_lamports = 1_000;
+    state.insurance_fund_lamports = 2 * SOL_PRECISION;
+    state.redemption_rate_bps = BPS_PRECISION;
+    state.min_cr_bps = DEFAULT_MIN_CR_BPS;
+    state.target_cr_bps = DEFAULT_TARGET_CR_BPS;
+    state.fee_amusd_mint_bps = AMUSD_MINT_FEE_BPS;
+    state.fee_amusd_redeem_bps = AMUSD_REDEEM_FEE_BPS;
+    state.fee_asol_mint_bps = ASOL_MINT_FEE_BPS;
+    state.fee_asol_redeem_bps = ASOL_REDEEM_FEE_BPS;
+    state.fee_min_multiplier_bps = 5_000;
+    state.fee_max_multiplier_bps = DEFAULT_FEE_MAX_MULTIPLIER_BPS;
+    state.uncertainty_max_bps = 20_000;
+
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data
+  }
+
+  #[test]
+  fn test_metrics_of_serialized_global_state() {
+    let state = GlobalState::try_from_bytes(&global_state_fixture()).unwrap();
+    let metrics = state.metrics().unwrap();
+
+    assert_eq!(metrics.tvl, 105 * SOL_PRECISION);
+    assert_eq!(metrics.liability, 50 * SOL_PRECISION);
+    assert_eq!(metrics.cr_bps, 21_000);
+    // Reserve and insurance fund are held back from aSOL holders
+    assert_eq!(metrics.accounting_equity, (53 * SOL_PRECISION - 1_000) as i128);
+    assert_eq!(metrics.claimable_equity, 53 * SOL_PRECISION - 1_000);
+    assert_eq!(metrics.nav_amusd, 10_000_000);
+    assert_eq!(metrics.nav_asol, Some(999_999_981));
+
+    // Above target CR every action pays its base fee
+    let book = QuoteState::from(&state);
+    for action in ACTIONS {
+      assert_eq!(metrics.fee_bps_for(action), book.base_fee_bps(action));
+    }
+    // ...which is what a trade is charged
+    assert_eq!(
+      metrics.fee_bps_for(FeeAction::AmusdMint),
+      quote_mint_amusd(&book, SOL_PRECISION).unwrap().fee_bps
+    );
+  }
+
+  #[test]
+  fn test_metrics_follow_settlement_and_insolvency() {
+    let mut state = GlobalState::try_from_bytes(&global_state_fixture()).unwrap();
+    state.asol_supply = 0;
+    assert_eq!(state.metrics().unwrap().nav_asol, None);
+
+    // Below 100% uninsured CR amUSD redeems at a haircut, fee-free
+    state.mock_sol_price_usd = 20 * USD_PRECISION;
+    let metrics = state.metrics().unwrap();
+    assert!(metrics.accounting_equity < 0);
+    assert_eq!(metrics.claimable_equity, 0);
+    assert_eq!(metrics.fee_bps_for(FeeAction::AmUSDRedeem), 0);
+    assert!(metrics.fee_bps_for(FeeAction::AsolRedeem) > 0);
+
+    state.settled = true;
+    state.settlement_sol_price_usd = 100 * USD_PRECISION;
+    state.settlement_lst_to_sol_rate = 1_050_000_000;
+    let metrics = state.metrics().unwrap();
+    assert_eq!(metrics.cr_bps, 21_000);
+    assert_eq!(metrics.fee_bps, [0; 4]);
+  }
+}
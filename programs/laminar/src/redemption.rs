@@ -0,0 +1,57 @@
+//! Redemption sources and the redemption queue
+//! redeem_amusd and redeem_asol burn from a token account the signing wallet
+//! owns or holds an SPL delegate approval over.
+//! process_redemption runs the same redeem math for a queued
+//! RedemptionTicket, burning from the ticket's escrow ATA under the ticket's
+//! PDA seeds. Tickets are processed strictly in sequence order.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::REDEMPTION_TICKET_EXPIRY_SLOTS;
+use crate::error::LaminarError;
+use crate::state::{GlobalState, RedemptionTicket};
+
+/// The token account a redemption burns from, and who signs for it
+pub struct RedeemSource<'a, 'info> {
+  /// Wallet the redemption is credited to in events
+  pub owner: Pubkey,
+  /// Source of the fee transfers and the burn
+  pub token_account: &'a InterfaceAccount<'info, TokenAccount>,
+  /// Authority over `token_account`: the signing owner or delegate, or a ticket PDA
+  pub authority: AccountInfo<'info>,
+  /// Seeds `authority` signs with; empty for a signing wallet
+  pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl RedeemSource<'_, '_> {
+  /// Tokens `authority` may burn: the whole balance for the owner, at most
+  /// the approved amount for a delegate
+  pub fn spendable(&self) -> u64 {
+    if self.token_account.owner == self.authority.key() {
+      self.token_account.amount
+    } else {
+      self.token_account.amount.min(self.token_account.delegated_amount)
+    }
+  }
+}
+
+/// Only the owner may cancel a ticket, except that once it is at the head of
+/// the queue, redemptions are open and it was requested at least
+/// `REDEMPTION_TICKET_EXPIRY_SLOTS` ago, anyone may hand it back. A ticket
+/// that cannot be processed then cannot hold up the tickets behind it.
+pub fn assert_can_cancel(
+  global_state: &GlobalState,
+  ticket: &RedemptionTicket,
+  caller: &Pubkey,
+  current_slot: u64,
+) -> Result<()> {
+  if *caller == ticket.owner {
+    return Ok(());
+  }
+  let expired = ticket.sequence == global_state.redemption_queue_head
+    && !global_state.redeem_paused
+    && current_slot >= ticket.request_slot.saturating_add(REDEMPTION_TICKET_EXPIRY_SLOTS);
+  require!(expired, LaminarError::RedemptionCancelNotAllowed);
+  Ok(())
+}
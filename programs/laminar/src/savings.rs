@@ -0,0 +1,55 @@
+//! Savings Pool fee routing
+//! mint_amusd and redeem_amusd split their amUSD treasury fee with
+//! `route_savings_fee`: `savings_fee_split_bps` of it is paid into the
+//! Savings Pool's vault instead of the treasury. The pool books it without
+//! issuing shares, so every depositor's shares are worth more amUSD. The
+//! fee is the same amUSD liability either way; only its holder changes.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::error::LaminarError;
+use crate::math::compute_savings_fee;
+use crate::state::{GlobalState, SavingsPool};
+
+/// Part of `amusd_treasury_fee` owed to the Savings Pool.
+///
+/// The pool and its vault are only required once a split is configured and
+/// there is a fee to split. A pool with no shares outstanding takes nothing:
+/// there is no depositor to accrue it, and the next depositor would
+/// otherwise capture it all.
+pub fn route_savings_fee(
+  global_state: &GlobalState,
+  savings_pool: Option<&Account<SavingsPool>>,
+  savings_amusd_vault: Option<&InterfaceAccount<TokenAccount>>,
+  amusd_treasury_fee: u64,
+) -> Result<u64> {
+  if global_state.savings_fee_split_bps == 0 || amusd_treasury_fee == 0 {
+    return Ok(0);
+  }
+  let (Some(savings_pool), Some(savings_amusd_vault)) = (savings_pool, savings_amusd_vault) else {
+    return err!(LaminarError::SavingsPoolRequired);
+  };
+  require_keys_eq!(savings_amusd_vault.key(), savings_pool.amusd_vault, LaminarError::InvalidAccountState);
+  if savings_pool.total_shares == 0 {
+    return Ok(0);
+  }
+  let savings_fee = compute_savings_fee(amusd_treasury_fee, global_state.savings_fee_split_bps)
+    .ok_or(LaminarError::InvalidParameter)?;
+  Ok(savings_fee)
+}
+
+/// Check the Savings Pool vault still holds exactly what the pool has booked.
+pub fn assert_savings_vault_synced(
+  savings_pool: Option<&Account<SavingsPool>>,
+  savings_amusd_vault: Option<&mut InterfaceAccount<TokenAccount>>,
+) -> Result<()> {
+  if let (Some(savings_pool), Some(savings_amusd_vault)) = (savings_pool, savings_amusd_vault) {
+    savings_amusd_vault.reload()?;
+    require!(
+      savings_amusd_vault.amount == savings_pool.total_pool_amusd,
+      LaminarError::BalanceSheetViolation
+    );
+  }
+  Ok(())
+}
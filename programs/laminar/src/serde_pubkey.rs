@@ -0,0 +1,18 @@
+//! Serde adapter writing a `Pubkey` as its base58 string
+//! Applied to every `Pubkey` field of the serde-enabled state and event
+//! types, so JSON carries addresses the way explorers and RPC print them
+//! rather than as 32-number arrays.
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_str(pubkey)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+  let encoded = String::deserialize(deserializer)?;
+  Pubkey::from_str(&encoded).map_err(D::Error::custom)
+}
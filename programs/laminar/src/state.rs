@@ -2,13 +2,22 @@
 //! These accounts hold the global balance sheet and vault configuration
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
 
 use crate::error::LaminarError;
+use crate::invariants::assert_oracle_value_within_bounds;
+use crate::math::{
+  combine_uncertainty_sources, compute_ema_price, compute_stability_fee_accrual, compute_stability_gain,
+  compute_stability_gain_per_share, compute_tvl_sol, compute_weighted_tvl_sol, derive_uncertainty_index_bps,
+};
+use crate::oracle::{PriceSnapshot, PriceSource};
 
 /// Global protocol state - the single source of truth for the balance sheet and vault configuration
 /// This account is a singleton (only one exists per protocol deployment)
 
 #[account]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlobalState {
   /// Protocol version for upgrades
   pub version: u8,
@@ -16,6 +25,7 @@ pub struct GlobalState {
   /// Bump seed for this PDA
   pub bump: u8,
 
+  /// Bump seed for the vault authority PDA
   pub vault_authority_bump: u8,
 
   /// Operation counter - increments on every state change (for debugging/tracing)
@@ -66,20 +76,28 @@ pub struct GlobalState {
   /// Reentrancy lock (solana CPI safety)
   // pub locked: bool,
 
+  /// Admin-set SOL price in micro-USD (USD_PRECISION scale)
   pub mock_sol_price_usd: u64,
 
+  /// Admin-set LST->SOL exchange rate (SOL_PRECISION scale)
   pub mock_lst_to_sol_rate: u64,
 
+  /// Base fee for amUSD mint in bps (before dynamic multipliers)
   pub fee_amusd_mint_bps: u64,
 
+  /// Base fee for amUSD redeem in bps (before dynamic multipliers)
   pub fee_amusd_redeem_bps: u64,
 
+  /// Base fee for aSOL mint in bps (before dynamic multipliers)
   pub fee_asol_mint_bps: u64,
 
+  /// Base fee for aSOL redeem in bps (before dynamic multipliers)
   pub fee_asol_redeem_bps: u64,
 
+  /// Lower clamp for the dynamic fee multiplier (<= 1.0x)
   pub fee_min_multiplier_bps: u64,
 
+  /// Upper clamp for the dynamic fee multiplier (>= 1.0x)
   pub fee_max_multiplier_bps: u64,
 
   /// Non-claimable reserve used to absorb determinsitic fixed-point dust.
@@ -92,10 +110,12 @@ pub struct GlobalState {
   /// Uncertainty signal derived from oracle confidence.
   pub uncertainty_index_bps: u64,
 
-  /// Current flash-loan utilization signal.
+  /// Peak flash-loan utilization (loan over vault, bps) in
+  /// `flash_loan_utilization_slot`; mixed into the fee uncertainty index.
   pub flash_loan_utilization_bps: u64,
 
-  /// Flash liquidity currently out during router operations.
+  /// LST base units lent by `flash_loan_lst` and not yet repaid. Still
+  /// counted in `total_lst_amount`, so the vault holds that minus this.
   pub flash_outstanding_lamports: u64,
 
   /// Oracle freshness bound in slots.
@@ -113,7 +133,7 @@ pub struct GlobalState {
   /// NAV floor for conversion safety.
   pub nav_floor_lamports: u64,
 
-  /// Per-round cap on aSOL mint during conversion paths.
+  /// Per-round cap on gross aSOL minted through mint_asol; 0 = unlimited.
   pub max_asol_mint_per_round: u64,
 
   /// Last slot when cached TVL/LST rate was refreshed.
@@ -122,16 +142,286 @@ pub struct GlobalState {
   /// Last slot when oracle inputs were refreshed.
   pub last_oracle_update_slot: u64,
 
+  /// Admin-set oracle confidence width in micro-USD
   pub mock_oracle_confidence_usd: u64,
 
+  /// Program owning the treasury address when it was acknowledged as a PDA
+  /// (e.g. a multisig vault). Default pubkey for wallet treasuries.
+  pub treasury_owner_program: Pubkey,
+
+  /// Authority proposed via `propose_authority`; becomes `authority` once it
+  /// signs `accept_authority`. Default pubkey when no transfer is pending.
+  pub pending_authority: Pubkey,
+
+  /// Slot at which redemptions were last paused by `emergency_pause`.
+  /// Zero while redemptions are live.
+  pub pause_started_slot: u64,
+
+  /// Redeem pause length after which `force_unpause_redeem` becomes
+  /// callable by anyone.
+  pub max_pause_duration_slots: u64,
+
+  /// Delay between queuing and executing a fee/CR/oracle change. While
+  /// non-zero the instant admin setters are disabled.
+  pub param_timelock_slots: u64,
+
+  /// Recorded at initialize: the LST mint can freeze the vault. Standing risk
+  /// flag surfaced in every `HealthSnapshot`.
+  pub lst_mint_has_freeze_authority: bool,
+
+  /// Redemptions were paused by `health_check` after seeing a frozen vault,
+  /// so a later check may lift the pause once the vault is thawed.
+  pub redeem_paused_by_vault_freeze: bool,
+
+  /// Pyth `PriceUpdateV2` account for SOL/USD, read while `price_source` is
+  /// `Pyth`. Default pubkey when unconfigured.
+  pub pyth_price_feed: Pubkey,
+
+  /// Switchboard on-demand pull feed for SOL/USD, read while `price_source`
+  /// is `Switchboard`. Default pubkey when unconfigured.
+  pub switchboard_feed: Pubkey,
+
+  /// SPL stake pool whose balances define `mock_lst_to_sol_rate`. Default
+  /// pubkey keeps the admin-set mock rate.
+  pub lst_stake_pool: Pubkey,
+
+  /// Marinade `State` account whose `msol_price` defines `mock_lst_to_sol_rate`.
+  /// Default pubkey when unused; never set together with `lst_stake_pool`.
+  pub marinade_state: Pubkey,
+
+  /// Sanctum SOL value calculator CPI'd by `sync_exchange_rate` for the LST
+  /// rate. Default pubkey when unused; exclusive with the other rate sources.
+  pub lst_calculator_program: Pubkey,
+
+  /// LST state account (e.g. the stake pool) the calculator must be given.
+  pub lst_calculator_state: Pubkey,
+
+  /// Lifetime par shortfall (SOL lamports) imposed on amUSD redeemers by
+  /// haircut redemptions. Input to any reimbursement decision.
+  pub cumulative_haircut_shortfall_lamports: u64,
+
+  /// Haircut shortfall within the current insolvency episode. Reset once CR
+  /// is observed back at or above 100%.
+  pub episode_haircut_shortfall_lamports: u64,
+
+  /// Episode shortfall above which amUSD redemption pauses. Zero disables
+  /// the breaker.
+  pub max_episode_haircut_lamports: u64,
+
+  /// amUSD redemption was paused by the haircut breaker; cleared only by
+  /// `set_haircut_breaker`.
+  pub amusd_redeem_paused_by_haircut: bool,
+
+  /// `oracle::PriceSource` discriminant selecting the SOL/USD source
+  pub price_source: u8,
+
+  /// `oracle::PriceSource` cross-checked against `price_source` on every
+  /// pricing action. `Mock` (zero) disables the cross-check.
+  pub secondary_price_source: u8,
+
+  /// Max relative disagreement between the primary and secondary prices
+  /// before pricing actions fail with `OracleDeviation`.
+  pub max_oracle_deviation_bps: u64,
+
+  /// Largest move of `mock_sol_price_usd` or `mock_lst_to_sol_rate` one
+  /// `update_mock_prices` call may make without `force`. Zero disables the
+  /// breaker.
+  pub max_price_move_bps: u64,
+
+  /// Absolute bounds on the SOL/USD price (micro-USD) any instruction will
+  /// accept, inclusive. A zero max leaves that side unbounded.
+  pub min_sol_price_usd: u64,
+  pub max_sol_price_usd: u64,
+
+  /// Absolute bounds on the LST/SOL rate (lamports per LST), inclusive. A
+  /// zero max leaves that side unbounded.
+  pub min_lst_rate: u64,
+  pub max_lst_rate: u64,
+
+  /// Unix timestamp of the mock oracle snapshot, set with
+  /// `last_oracle_update_slot`
+  pub last_oracle_update_timestamp: i64,
+
+  /// Max wall-clock age of any price used, alongside
+  /// `max_oracle_staleness_slots`; whichever trips first fails
+  pub max_oracle_age_seconds: u64,
+
+  /// `oracle::PriceSource` redemptions price from while the primary is
+  /// stale. `NO_FALLBACK_PRICE_SOURCE` disables the fallback; mints never
+  /// use it.
+  pub fallback_price_source: u8,
+
+  /// Added to `uncertainty_index_bps` for the fee of a redemption priced
+  /// from the fallback
+  pub fallback_uncertainty_bps: u64,
+
+  /// EMA of `mock_sol_price_usd`, folded in at every oracle refresh.
+  /// Mock-priced actions take the conservative side of spot and EMA.
+  pub ema_sol_price_usd: u64,
+
+  /// EMA half-life in slots. Zero disables smoothing (EMA tracks spot).
+  pub ema_half_life_slots: u64,
+
+  /// Slot `ema_sol_price_usd` was last folded at
+  pub last_ema_update_slot: u64,
+
+  /// amUSD held by the Stability Pool (part of `amusd_supply`). While it is
+  /// non-zero a sub-min CR must be drawn down before anyone redeems.
+  pub stability_pool_amusd: u64,
+
+  /// Largest discount to NAV a `rebalance` pays, funded from the treasury's
+  /// delegated aSOL. Zero makes rebalances fill at NAV.
+  pub rebalance_discount_bps: u64,
+
+  /// CR was last observed below `min_cr_bps`: amUSD mints and aSOL
+  /// redemptions are refused until an instruction observes it back at min.
+  pub recovery_mode: bool,
+
+  /// Slot recovery mode was last entered. Kept after exit for observability.
+  pub recovery_mode_entered_slot: u64,
+
+  /// Discount to NAV on `mint_asol` while CR < min, paid by dilution and
+  /// capped per mint at MAX_RECOVERY_DILUTION_BPS. Zero turns it off.
+  pub recovery_bonus_bps: u64,
+
+  /// Global settlement has begun. Irreversible: minting stays closed and
+  /// redemptions price off the snapshot below instead of the oracle.
+  pub settled: bool,
+
+  /// Slot `initiate_settlement` ran in
+  pub settlement_slot: u64,
+
+  /// SOL/USD frozen at settlement
+  pub settlement_sol_price_usd: u64,
+
+  /// LST->SOL rate frozen at settlement
+  pub settlement_lst_to_sol_rate: u64,
+
+  /// First-loss buffer for amUSD, valued in lamports like the rounding reserve.
+  /// Deposited through `fund_insurance`; never part of claimable equity.
+  pub insurance_fund_lamports: u64,
+
+  /// Share of every mint/redeem fee, in bps, booked to the insurance fund
+  /// instead of the treasury. That share is never minted (or is burned) and
+  /// its value is credited to `insurance_fund_lamports`.
+  pub fee_split_insurance_bps: u64,
+
+  /// USD each amUSD redeems for, in bps of par. Starts at BPS_PRECISION and
+  /// only ever falls, through `socialize_bad_debt`; every liability is
+  /// `amusd_supply` valued at this rate.
+  pub redemption_rate_bps: u64,
+
+  /// Largest flash loan, in bps of `total_lst_amount`.
+  pub max_flash_loan_bps: u64,
+
+  /// Slot `flash_loan_utilization_bps` was recorded in; the signal only
+  /// prices actions in that same slot.
+  pub flash_loan_utilization_slot: u64,
+
+  /// SOL value of the LST held across every `CollateralVault`, each valued
+  /// at its own rate. Book TVL is the primary vault's TVL plus this.
+  pub collateral_tvl_lamports: u64,
+
+  /// Number of collateral types registered through `add_collateral_type`
+  pub collateral_type_count: u64,
+
+  /// Part of `weighted_collateral_tvl_lamports` held in `Deprecated`
+  /// vaults. Still backs the book, but left out of the CR the fee curve reads.
+  pub deprecated_collateral_tvl_lamports: u64,
+
+  /// `collateral_tvl_lamports` with each vault scaled by its
+  /// `collateral_weight_bps`. CR reads this; NAV, redemptions and the
+  /// balance sheet read the unweighted total.
+  pub weighted_collateral_tvl_lamports: u64,
+
+  /// Stablecoin the Peg Stability Module swaps amUSD against at par;
+  /// `Pubkey::default()` until `initialize_psm`.
+  pub psm_usdc_mint: Pubkey,
+
+  /// PSM fee, in bps, on either swap direction. Taken in amUSD and paid to
+  /// the treasury, so it stays fully backed by the PSM vault.
+  pub psm_fee_bps: u64,
+
+  /// Cap on `psm_amusd_outstanding`
+  pub psm_debt_ceiling: u64,
+
+  /// amUSD minted against the PSM's USDC and not yet swapped back. Kept out
+  /// of `amusd_supply`, so LST CR, NAV and every liability ignore debt the
+  /// PSM vault already backs in full.
+  pub psm_amusd_outstanding: u64,
+
+  /// Share, in bps, of every amUSD treasury fee deposited into the
+  /// SavingsPool instead, raising its share price. Zero until set.
+  pub savings_fee_split_bps: u64,
+
+  /// Annual stability fee, in bps, charged on `amusd_supply`. Accrues
+  /// linearly and is minted to the treasury as new amUSD; 0 disables it.
+  pub stability_fee_bps_per_year: u64,
+
+  /// Unix timestamp the stability fee was last accrued to
+  pub last_accrual_timestamp: i64,
+
+  /// Ceiling on `amusd_supply` that mint_amusd may reach; 0 = unlimited.
+  /// Redemptions and stability fee accrual are never held to it.
+  pub amusd_supply_cap: u64,
+
+  /// Ceiling on `asol_supply` that mint_asol may reach, treasury fee
+  /// included; 0 = unlimited. Lets equity exposure ramp during a launch.
+  pub asol_supply_cap: u64,
+
+  /// Ceiling on the LST a vault may hold after a mint; 0 = unlimited.
+  /// A guarded-launch limit on collateral, separate from the supply caps.
+  pub max_total_lst_amount: u64,
+
+  /// Largest LST amount one mint_amusd or mint_asol may deposit; 0 = unlimited
+  pub max_single_deposit_lst: u64,
+
+  /// Largest amount one redemption may burn, in amUSD for redeem_amusd and
+  /// in aSOL for redeem_asol; 0 = unlimited
+  pub max_single_redeem_units: u64,
+
+  /// Cumulative amUSD one wallet may mint, tracked on its UserPosition;
+  /// 0 = unlimited, and positions stay optional.
+  pub per_user_amusd_cap: u64,
+
+  /// Length, in slots, of a `max_asol_mint_per_round` round
+  pub mint_round_slots: u64,
+
+  /// Slot the current mint round opened at
+  pub current_round_start_slot: u64,
+
+  /// Gross aSOL minted since `current_round_start_slot`
+  pub asol_minted_this_round: u64,
+
+  /// Slots a wallet must wait between a mint and a redeem, either way
+  /// round; 0 disables the check and leaves UserPosition optional.
+  pub min_action_gap_slots: u64,
+
+  /// Slots a wallet must wait after mint_asol before it may redeem_asol;
+  /// 0 disables the cooldown.
+  pub asol_redeem_cooldown_slots: u64,
+
+  /// Sequence number the next RedemptionTicket is issued under
+  pub next_redemption_ticket: u64,
+
+  /// Sequence number of the oldest ticket still in the redemption queue;
+  /// only this ticket can be processed
+  pub redemption_queue_head: u64,
+
+  /// Largest CR move a redeem_pair may cause, in bps
+  pub pair_cr_tolerance_bps: u64,
+
+  /// Reserved for future use. Always zero; new fields are appended above
+  /// this array and LEN is bumped rather than carving words out of it.
+  #[cfg_attr(feature = "serde", serde(skip))]
   pub _reserved: [u64; 2],
 }
 
 impl GlobalState {
-  pub const LEN: usize = 8 + // discrimanator
+  pub const LEN: usize = 8 + // discriminator
     1 + // version
     1 + // bump
-    1 + // vault_authoruty_bump
+    1 + // vault_authority_bump
     8 + // operation_counter
     32 + // authority
     32 + // amusd_mint
@@ -153,65 +443,1112 @@ impl GlobalState {
     8 + // fee_asol_mint_bps
     8 + // fee_asol_redeem_bps
     8 + // fee_min_multiplier_bps
-    8 + // fee_max_mutliplier_bps
+    8 + // fee_max_multiplier_bps
     8 + // rounding_reserve_lamports
-    8 + //max_rounding_reserve_lamports
-    8 + // uncertainity_index_bps
+    8 + // max_rounding_reserve_lamports
+    8 + // uncertainty_index_bps
     8 + // flash_loan_utilization_bps
     8 + // flash_outstanding_lamports
     8 + // max_oracle_staleness_slots
     8 + // max_conf_bps
-    8 + // uncertainity_max_bos
+    8 + // uncertainty_max_bps
     8 + // max_lst_stale_epochs
     8 + // nav_floor_lamports
     8 + // max_asol_mint_per_round
     8 + // last_tvl_update_slot
     8 + // last_oracle_update_slot
     8 + // mock_oracle_confidence_usd
+    32 + // treasury_owner_program
+    32 + // pending_authority
+    8 + // pause_started_slot
+    8 + // max_pause_duration_slots
+    8 + // param_timelock_slots
+    1 + // lst_mint_has_freeze_authority
+    1 + // redeem_paused_by_vault_freeze
+    32 + // pyth_price_feed
+    32 + // switchboard_feed
+    32 + // lst_stake_pool
+    32 + // marinade_state
+    32 + // lst_calculator_program
+    32 + // lst_calculator_state
+    8 + // cumulative_haircut_shortfall_lamports
+    8 + // episode_haircut_shortfall_lamports
+    8 + // max_episode_haircut_lamports
+    1 + // amusd_redeem_paused_by_haircut
+    1 + // price_source
+    1 + // secondary_price_source
+    8 + // max_oracle_deviation_bps
+    8 + // max_price_move_bps
+    8 + // min_sol_price_usd
+    8 + // max_sol_price_usd
+    8 + // min_lst_rate
+    8 + // max_lst_rate
+    8 + // last_oracle_update_timestamp
+    8 + // max_oracle_age_seconds
+    1 + // fallback_price_source
+    8 + // fallback_uncertainty_bps
+    8 + // ema_sol_price_usd
+    8 + // ema_half_life_slots
+    8 + // last_ema_update_slot
+    8 + // stability_pool_amusd
+    8 + // rebalance_discount_bps
+    1 + // recovery_mode
+    8 + // recovery_mode_entered_slot
+    8 + // recovery_bonus_bps
+    1 + // settled
+    8 + // settlement_slot
+    8 + // settlement_sol_price_usd
+    8 + // settlement_lst_to_sol_rate
+    8 + // insurance_fund_lamports
+    8 + // fee_split_insurance_bps
+    8 + // redemption_rate_bps
+    8 + // max_flash_loan_bps
+    8 + // flash_loan_utilization_slot
+    8 + // collateral_tvl_lamports
+    8 + // collateral_type_count
+    8 + // deprecated_collateral_tvl_lamports
+    8 + // weighted_collateral_tvl_lamports
+    32 + // psm_usdc_mint
+    8 + // psm_fee_bps
+    8 + // psm_debt_ceiling
+    8 + // psm_amusd_outstanding
+    8 + // savings_fee_split_bps
+    8 + // stability_fee_bps_per_year
+    8 + // last_accrual_timestamp
+    8 + // amusd_supply_cap
+    8 + // asol_supply_cap
+    8 + // max_total_lst_amount
+    8 + // max_single_deposit_lst
+    8 + // max_single_redeem_units
+    8 + // per_user_amusd_cap
+    8 + // mint_round_slots
+    8 + // current_round_start_slot
+    8 + // asol_minted_this_round
+    8 + // min_action_gap_slots
+    8 + // asol_redeem_cooldown_slots
+    8 + // next_redemption_ticket
+    8 + // redemption_queue_head
+    8 + // pair_cr_tolerance_bps
     16; // _reserved (2 * 8 = 16)
 }
 
-/// Collateral vault metadata - holds LST vault configuration
-/// 
-/// TODO: FUTURE IMPLEMENTATION
-/// Currently unused in MVP-0 (single vault design).
-/// This struct will be activated when multi-LST support is added.
-/// For now, vault metadata is stored directly in GlobalState.
-///
-/// One vault account will exist per whitelisted LST type.
+/// Recovery-mode boundary crossing reported by `GlobalState::track_recovery_mode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecoveryTransition {
+  Entered,
+  Exited { entered_slot: u64 },
+}
 
+/// Collateral vault metadata - one per LST whitelisted by `add_collateral_type`
+///
+/// The original LST keeps living in GlobalState (`supported_lst_mint`,
+/// `total_lst_amount`, the `[VAULT_AUTHORITY_SEED]` vault) for backward
+/// compatibility. Every further LST gets one of these, its own vault ATA and
+/// its own authority PDA, and is passed to mint/redeem in place of the
+/// single-mint constraint. Its SOL value is cached in
+/// `GlobalState::collateral_tvl_lamports`.
+///
+/// PDA: [VAULT_SEED, lst_mint]
 #[account]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollateralVault {
   /// LST mint that this vault holds
   pub lst_mint: Pubkey,
 
-  /// Vault authority (PDA) - signs transfers from vault
+  /// Vault token account (ATA of `vault_authority`)
+  pub vault: Pubkey,
+
+  /// Vault authority PDA [VAULT_AUTHORITY_SEED, lst_mint] - signs transfers from vault
   pub vault_authority: Pubkey,
 
-  /// Bump seed for vault_authority PDA
+  /// Bump seed for this account's PDA
   pub bump: u8,
 
+  /// Bump seed for vault_authority PDA
+  pub vault_authority_bump: u8,
+
+  /// LST held in `vault`
+  pub lst_amount: u64,
+
+  /// LST->SOL rate for this mint (mock, set at registration)
+  pub lst_to_sol_rate: u64,
+
+  /// `CollateralStatus` as u8
+  pub status: u8,
+
+  /// Share of this vault's SOL value counted toward CR (BPS_PRECISION = 100%)
+  pub collateral_weight_bps: u64,
+
+  /// Recorded at add_collateral_type: the mint can freeze `vault`. Redeems
+  /// through a frozen vault fail with `VaultFrozen` before touching state.
+  pub lst_mint_has_freeze_authority: bool,
+
   /// Reserved space for future upgrades
+  #[cfg_attr(feature = "serde", serde(skip))]
   pub _reserved: [u64; 8],
 }
 
+/// Lifecycle of a registered collateral type (`CollateralVault::status`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CollateralStatus {
+  /// Accepts deposits and pays redemptions
+  Active = 0,
+  /// Pays redemptions only
+  DepositsDisabled = 1,
+  /// Pays redemptions only and is left out of the fee curve's CR until drained
+  Deprecated = 2,
+}
+
+impl TryFrom<u8> for CollateralStatus {
+  type Error = Error;
+
+  fn try_from(value: u8) -> Result<Self> {
+    match value {
+      0 => Ok(CollateralStatus::Active),
+      1 => Ok(CollateralStatus::DepositsDisabled),
+      2 => Ok(CollateralStatus::Deprecated),
+      _ => err!(LaminarError::InvalidParameter),
+    }
+  }
+}
+
 impl CollateralVault {
   pub const LEN: usize = 8 + // discriminator
     32 + // lst_mint
+    32 + // vault
     32 + // vault_authority
     1 + // bump
+    1 + // vault_authority_bump
+    8 + // lst_amount
+    8 + // lst_to_sol_rate
+    1 + // status
+    8 + // collateral_weight_bps
+    1 + // lst_mint_has_freeze_authority
     64; // _reserved
+
+  pub fn is_deprecated(&self) -> bool {
+    self.status == CollateralStatus::Deprecated as u8
+  }
+
+  /// wSOL vault: valued at par, with no stake-pool rate behind it
+  pub fn is_native(&self) -> bool {
+    self.lst_mint == spl_token::native_mint::ID
+  }
+
+  /// SOL value of the LST in this vault, rounded down
+  pub fn tvl_sol(&self) -> Result<u64> {
+    compute_tvl_sol(self.lst_amount, self.lst_to_sol_rate).ok_or(error!(LaminarError::MathOverflow))
+  }
+
+  /// `tvl_sol` at this vault's collateral weight
+  pub fn weighted_tvl_sol(&self) -> Result<u64> {
+    compute_weighted_tvl_sol(self.lst_amount, self.lst_to_sol_rate, self.collateral_weight_bps)
+      .ok_or(error!(LaminarError::MathOverflow))
+  }
+
+  /// Same contract as `GlobalState::validate_bumps`, for this vault's PDAs.
+  pub fn validate_bumps(&self, collateral_vault_key: &Pubkey, vault_authority_key: &Pubkey) -> Result<()> {
+    let derived_collateral_vault = Pubkey::create_program_address(
+      &[VAULT_SEED, self.lst_mint.as_ref(), &[self.bump]],
+      &crate::ID,
+    )
+    .map_err(|_| error!(LaminarError::CorruptedBumpSeed))?;
+    require_keys_eq!(derived_collateral_vault, *collateral_vault_key, LaminarError::CorruptedBumpSeed);
+
+    let derived_vault_authority = Pubkey::create_program_address(
+      &[VAULT_AUTHORITY_SEED, self.lst_mint.as_ref(), &[self.vault_authority_bump]],
+      &crate::ID,
+    )
+    .map_err(|_| error!(LaminarError::CorruptedBumpSeed))?;
+    require_keys_eq!(derived_vault_authority, *vault_authority_key, LaminarError::CorruptedBumpSeed);
+    require_keys_eq!(self.vault_authority, *vault_authority_key, LaminarError::CorruptedBumpSeed);
+
+    Ok(())
+  }
+}
+
+/// Admin parameter change that must sit out the timelock before it applies
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", serde_with::apply(Pubkey => #[serde(with = "crate::serde_pubkey")]))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParamChange {
+  Risk {
+    min_cr_bps: u64,
+    target_cr_bps: u64,
+  },
+  Fees {
+    fee_amusd_mint_bps: u64,
+    fee_amusd_redeem_bps: u64,
+    fee_asol_mint_bps: u64,
+    fee_asol_redeem_bps: u64,
+    fee_min_multiplier_bps: u64,
+    fee_max_multiplier_bps: u64,
+  },
+  Oracle {
+    max_oracle_staleness_slots: u64,
+    max_conf_bps: u64,
+    uncertainty_max_bps: u64,
+    max_lst_stale_epochs: u64,
+  },
+  Timelock {
+    param_timelock_slots: u64,
+  },
+  PriceFeed {
+    pyth_price_feed: Pubkey,
+  },
+  // The kinds below are applied by their own setter once the ETA passes, so
+  // it keeps its account checks and event; see `assert_param_change_unlocked`.
+  VaultStatus {
+    lst_mint: Pubkey,
+    status: u8,
+  },
+  FallbackPriceSource {
+    fallback_price_source: Option<u8>,
+    fallback_uncertainty_bps: u64,
+  },
+  HaircutBreaker {
+    max_episode_haircut_lamports: u64,
+  },
+  LstCalculator {
+    lst_calculator_program: Pubkey,
+    lst_calculator_state: Pubkey,
+  },
+  LstStakePool {
+    lst_stake_pool: Pubkey,
+  },
+  MarinadeState {
+    marinade_state: Pubkey,
+  },
+  PrimaryPriceSource {
+    price_source: u8,
+  },
+  SecondaryPriceSource {
+    secondary_price_source: u8,
+    max_oracle_deviation_bps: u64,
+  },
+  SwitchboardFeed {
+    switchboard_feed: Pubkey,
+  },
+  Treasury {
+    treasury: Pubkey,
+    treasury_is_pda: bool,
+    treasury_owner_program: Pubkey,
+  },
+  AsolRedeemCooldown {
+    asol_redeem_cooldown_slots: u64,
+  },
+  EmaHalfLife {
+    ema_half_life_slots: u64,
+  },
+  FeeSplit {
+    fee_split_insurance_bps: u64,
+  },
+  MaxOracleAge {
+    max_oracle_age_seconds: u64,
+  },
+  MaxPauseDuration {
+    max_pause_duration_slots: u64,
+  },
+  MaxPriceMove {
+    max_price_move_bps: u64,
+  },
+  MinActionGap {
+    min_action_gap_slots: u64,
+  },
+  MintRound {
+    max_asol_mint_per_round: u64,
+    mint_round_slots: u64,
+  },
+  MockOracleConfidence {
+    confidence_usd: u64,
+  },
+  OracleBounds {
+    min_sol_price_usd: u64,
+    max_sol_price_usd: u64,
+    min_lst_rate: u64,
+    max_lst_rate: u64,
+  },
+  PairCrTolerance {
+    pair_cr_tolerance_bps: u64,
+  },
+  PerUserCap {
+    per_user_amusd_cap: u64,
+  },
+  PsmParameters {
+    psm_fee_bps: u64,
+    psm_debt_ceiling: u64,
+  },
+  RebalanceDiscount {
+    rebalance_discount_bps: u64,
+  },
+  RecoveryBonus {
+    recovery_bonus_bps: u64,
+  },
+  RoundingReserveCap {
+    max_rounding_reserve_lamports: u64,
+  },
+  SavingsFeeSplit {
+    savings_fee_split_bps: u64,
+  },
+  StabilityFee {
+    stability_fee_bps_per_year: u64,
+  },
+  SupplyCaps {
+    amusd_supply_cap: u64,
+    asol_supply_cap: u64,
+  },
+  TransactionLimits {
+    max_single_deposit_lst: u64,
+    max_single_redeem_units: u64,
+  },
+  TvlCap {
+    max_total_lst_amount: u64,
+  },
+}
+
+impl ParamChange {
+  /// Borsh size of the largest variant (tag + Treasury)
+  pub const MAX_LEN: usize = 1 + 32 + 1 + 32;
+
+  /// PDA seed discriminant - one pending change per kind at a time
+  pub fn kind(&self) -> u8 {
+    match self {
+      ParamChange::Risk { .. } => 0,
+      ParamChange::Fees { .. } => 1,
+      ParamChange::Oracle { .. } => 2,
+      ParamChange::Timelock { .. } => 3,
+      ParamChange::PriceFeed { .. } => 4,
+      ParamChange::VaultStatus { .. } => 5,
+      ParamChange::FallbackPriceSource { .. } => 6,
+      ParamChange::HaircutBreaker { .. } => 7,
+      ParamChange::LstCalculator { .. } => 8,
+      ParamChange::LstStakePool { .. } => 9,
+      ParamChange::MarinadeState { .. } => 10,
+      ParamChange::PrimaryPriceSource { .. } => 11,
+      ParamChange::SecondaryPriceSource { .. } => 12,
+      ParamChange::SwitchboardFeed { .. } => 13,
+      ParamChange::Treasury { .. } => 14,
+      ParamChange::AsolRedeemCooldown { .. } => 15,
+      ParamChange::EmaHalfLife { .. } => 16,
+      ParamChange::FeeSplit { .. } => 17,
+      ParamChange::MaxOracleAge { .. } => 18,
+      ParamChange::MaxPauseDuration { .. } => 19,
+      ParamChange::MaxPriceMove { .. } => 20,
+      ParamChange::MinActionGap { .. } => 21,
+      ParamChange::MintRound { .. } => 22,
+      ParamChange::MockOracleConfidence { .. } => 23,
+      ParamChange::OracleBounds { .. } => 24,
+      ParamChange::PairCrTolerance { .. } => 25,
+      ParamChange::PerUserCap { .. } => 26,
+      ParamChange::PsmParameters { .. } => 27,
+      ParamChange::RebalanceDiscount { .. } => 28,
+      ParamChange::RecoveryBonus { .. } => 29,
+      ParamChange::RoundingReserveCap { .. } => 30,
+      ParamChange::SavingsFeeSplit { .. } => 31,
+      ParamChange::StabilityFee { .. } => 32,
+      ParamChange::SupplyCaps { .. } => 33,
+      ParamChange::TransactionLimits { .. } => 34,
+      ParamChange::TvlCap { .. } => 35,
+    }
+  }
+
+  /// Same bounds the instant setters enforce
+  pub fn validate(&self) -> Result<()> {
+    match *self {
+      ParamChange::Risk { min_cr_bps, target_cr_bps } => {
+        crate::invariants::validate_risk_config(min_cr_bps, target_cr_bps)
+      }
+      ParamChange::Fees {
+        fee_amusd_mint_bps,
+        fee_amusd_redeem_bps,
+        fee_asol_mint_bps,
+        fee_asol_redeem_bps,
+        fee_min_multiplier_bps,
+        fee_max_multiplier_bps,
+      } => crate::invariants::validate_fee_config(
+        fee_amusd_mint_bps,
+        fee_amusd_redeem_bps,
+        fee_asol_mint_bps,
+        fee_asol_redeem_bps,
+        fee_min_multiplier_bps,
+        fee_max_multiplier_bps,
+      ),
+      ParamChange::Oracle {
+        max_oracle_staleness_slots,
+        max_conf_bps,
+        uncertainty_max_bps,
+        max_lst_stale_epochs,
+      } => crate::invariants::validate_oracle_config(
+        max_oracle_staleness_slots,
+        max_conf_bps,
+        uncertainty_max_bps,
+        max_lst_stale_epochs,
+      ),
+      ParamChange::Timelock { param_timelock_slots } => {
+        require!(
+          param_timelock_slots <= crate::constants::MAX_PARAM_TIMELOCK_SLOTS,
+          LaminarError::InvalidParameter
+        );
+        Ok(())
+      }
+      ParamChange::PriceFeed { .. } => Ok(()),
+      // Checked by the setter against live state when it applies the change
+      _ => Ok(()),
+    }
+  }
+
+  /// Write a queued change into `global_state`. Setter-applied kinds are
+  /// refused; they go through their own instruction.
+  pub fn apply(&self, global_state: &mut GlobalState) -> Result<()> {
+    match *self {
+      ParamChange::Risk { min_cr_bps, target_cr_bps } => {
+        global_state.min_cr_bps = min_cr_bps;
+        global_state.target_cr_bps = target_cr_bps;
+      }
+      ParamChange::Fees {
+        fee_amusd_mint_bps,
+        fee_amusd_redeem_bps,
+        fee_asol_mint_bps,
+        fee_asol_redeem_bps,
+        fee_min_multiplier_bps,
+        fee_max_multiplier_bps,
+      } => {
+        global_state.fee_amusd_mint_bps = fee_amusd_mint_bps;
+        global_state.fee_amusd_redeem_bps = fee_amusd_redeem_bps;
+        global_state.fee_asol_mint_bps = fee_asol_mint_bps;
+        global_state.fee_asol_redeem_bps = fee_asol_redeem_bps;
+        global_state.fee_min_multiplier_bps = fee_min_multiplier_bps;
+        global_state.fee_max_multiplier_bps = fee_max_multiplier_bps;
+      }
+      ParamChange::Oracle {
+        max_oracle_staleness_slots,
+        max_conf_bps,
+        uncertainty_max_bps,
+        max_lst_stale_epochs,
+      } => {
+        global_state.max_oracle_staleness_slots = max_oracle_staleness_slots;
+        global_state.max_conf_bps = max_conf_bps;
+        global_state.uncertainty_max_bps = uncertainty_max_bps;
+        global_state.max_lst_stale_epochs = max_lst_stale_epochs;
+      }
+      ParamChange::Timelock { param_timelock_slots } => {
+        global_state.param_timelock_slots = param_timelock_slots;
+      }
+      ParamChange::PriceFeed { pyth_price_feed } => {
+        // Same source rules as `set_pyth_price_feed`, except that clearing
+        // the live feed falls over to Switchboard instead of failing.
+        global_state.pyth_price_feed = pyth_price_feed;
+        if pyth_price_feed != Pubkey::default() {
+          if global_state.price_source == PriceSource::Mock as u8 {
+            global_state.price_source = PriceSource::Pyth as u8;
+          }
+        } else if global_state.price_source == PriceSource::Pyth as u8 {
+          global_state.price_source = if global_state.switchboard_feed != Pubkey::default() {
+            PriceSource::Switchboard as u8
+          } else {
+            PriceSource::Mock as u8
+          };
+        }
+        // A cross-check against the cleared feed, or against the new primary,
+        // has nothing left to compare.
+        if (global_state.secondary_price_source == PriceSource::Pyth as u8 && pyth_price_feed == Pubkey::default())
+          || global_state.secondary_price_source == global_state.price_source
+        {
+          global_state.secondary_price_source = PriceSource::Mock as u8;
+        }
+        // Likewise for a fallback that was the cleared feed or is now primary.
+        if (global_state.fallback_price_source == PriceSource::Pyth as u8 && pyth_price_feed == Pubkey::default())
+          || global_state.fallback_price_source == global_state.price_source
+        {
+          global_state.fallback_price_source = crate::constants::NO_FALLBACK_PRICE_SOURCE;
+        }
+      }
+      _ => return err!(LaminarError::ParamChangeAppliedBySetter),
+    }
+    Ok(())
+  }
+}
+
+/// A queued `ParamChange` awaiting its ETA
+/// PDA: [PENDING_PARAM_CHANGE_SEED, kind]
+#[account]
+pub struct PendingParamChange {
+  /// Authority that queued the change; receives the rent back on execute/cancel.
+  /// A setter-applied change refunds the authority that applies it instead.
+  pub proposer: Pubkey,
+
+  pub change: ParamChange,
+
+  pub queued_slot: u64,
+
+  /// First slot at which the change can be applied
+  pub eta_slot: u64,
+
+  pub bump: u8,
+}
+
+impl PendingParamChange {
+  pub const LEN: usize = 8 + // discriminator
+    32 + // proposer
+    ParamChange::MAX_LEN + // change
+    8 + // queued_slot
+    8 + // eta_slot
+    1; // bump
+}
+
+/// Stability Pool - amUSD parked to absorb drawdowns before holders take haircuts
+/// PDA: [STABILITY_POOL_SEED]
+#[account]
+pub struct StabilityPool {
+  pub bump: u8,
+
+  /// Pool-owned amUSD token account (ATA of this PDA)
+  pub amusd_vault: Pubkey,
+
+  /// amUSD held for depositors; mirrors the `amusd_vault` balance
+  pub total_pool_amusd: u64,
+
+  /// Depositor shares outstanding; each is a pro-rata claim on `total_pool_amusd`
+  pub total_shares: u64,
+
+  /// Pool-owned LST token account (ATA of this PDA) holding drawdown gains
+  pub lst_vault: Pubkey,
+
+  /// LST gains not yet paid out; mirrors the `lst_vault` balance
+  pub total_pool_lst: u64,
+
+  /// Cumulative LST gained per share this epoch, scaled by `STABILITY_GAIN_SCALE`
+  pub lst_gain_per_share: u128,
+
+  /// Bumped whenever a drawdown empties the pool; shares from earlier
+  /// epochs are worth no amUSD and stop accruing gains
+  pub epoch: u64,
+
+  pub _reserved: [u64; 4],
+}
+
+impl StabilityPool {
+  pub const LEN: usize = 8 + // discriminator
+    1 + // bump
+    32 + // amusd_vault
+    8 + // total_pool_amusd
+    8 + // total_shares
+    32 + // lst_vault
+    8 + // total_pool_lst
+    16 + // lst_gain_per_share
+    8 + // epoch
+    32; // _reserved
+
+  /// Book a drawdown: `amusd_burned` leaves the pool and `lst_gained` is
+  /// shared over the current shares. Returns the record of the epoch this
+  /// closed when the pool is emptied.
+  pub fn absorb_drawdown(&mut self, amusd_burned: u64, lst_gained: u64) -> Result<Option<StabilityEpochRecord>> {
+    if self.total_shares == 0 {
+      return Ok(None);
+    }
+    self.total_pool_amusd = self.total_pool_amusd
+      .checked_sub(amusd_burned)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.total_pool_lst = self.total_pool_lst
+      .checked_add(lst_gained)
+      .ok_or(LaminarError::MathOverflow)?;
+    let increment = compute_stability_gain_per_share(lst_gained, self.total_shares)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.lst_gain_per_share = self.lst_gain_per_share
+      .checked_add(increment)
+      .ok_or(LaminarError::MathOverflow)?;
+
+    if self.total_pool_amusd > 0 {
+      return Ok(None);
+    }
+    let closed = StabilityEpochRecord {
+      epoch: self.epoch,
+      final_lst_gain_per_share: self.lst_gain_per_share,
+      bump: 0,
+    };
+    self.epoch = self.epoch.checked_add(1).ok_or(LaminarError::MathOverflow)?;
+    self.total_shares = 0;
+    self.lst_gain_per_share = 0;
+    Ok(Some(closed))
+  }
+}
+
+/// One depositor's position in the Stability Pool
+/// PDA: [STABILITY_DEPOSIT_SEED, owner]
+#[account]
+pub struct StabilityDeposit {
+  pub owner: Pubkey,
+
+  pub shares: u64,
+
+  /// Pool epoch `shares` were issued in
+  pub epoch: u64,
+
+  /// Pool `lst_gain_per_share` when `pending_lst_gain` was last settled
+  pub lst_gain_snapshot: u128,
+
+  /// LST gains settled into this position but not yet paid out
+  pub pending_lst_gain: u64,
+
+  pub bump: u8,
+
+  pub _reserved: [u64; 4],
+}
+
+impl StabilityDeposit {
+  pub const LEN: usize = 8 + // discriminator
+    32 + // owner
+    8 + // shares
+    8 + // epoch
+    16 + // lst_gain_snapshot
+    8 + // pending_lst_gain
+    1 + // bump
+    32; // _reserved
+
+  /// Fold gains accrued since the snapshot into `pending_lst_gain`. Must run
+  /// before `shares` changes. A position from an emptied epoch settles
+  /// against that epoch's record and is moved, with no shares, into the
+  /// current one.
+  pub fn settle_gains(&mut self, pool: &StabilityPool, epoch_record: Option<&StabilityEpochRecord>) -> Result<()> {
+    let gain_per_share = if self.epoch == pool.epoch {
+      pool.lst_gain_per_share
+    } else {
+      epoch_record
+        .filter(|record| record.epoch == self.epoch)
+        .ok_or(LaminarError::StabilityEpochRecordRequired)?
+        .final_lst_gain_per_share
+    };
+    let accrued = compute_stability_gain(self.shares, gain_per_share, self.lst_gain_snapshot)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.pending_lst_gain = self.pending_lst_gain
+      .checked_add(accrued)
+      .ok_or(LaminarError::MathOverflow)?;
+    if self.epoch != pool.epoch {
+      self.shares = 0;
+      self.epoch = pool.epoch;
+    }
+    self.lst_gain_snapshot = pool.lst_gain_per_share;
+    Ok(())
+  }
+}
+
+/// Final accumulator of a Stability Pool epoch, written by the drawdown that
+/// emptied the pool so positions from that epoch can still settle their gains
+/// PDA: [STABILITY_EPOCH_SEED, epoch (le bytes)]
+#[account]
+pub struct StabilityEpochRecord {
+  pub epoch: u64,
+
+  pub final_lst_gain_per_share: u128,
+
+  pub bump: u8,
+}
+
+impl StabilityEpochRecord {
+  pub const LEN: usize = 8 + // discriminator
+    8 + // epoch
+    16 + // final_lst_gain_per_share
+    1; // bump
+}
+
+/// Savings Pool - amUSD deposited to earn a slice of the amUSD fees
+/// PDA: [SAVINGS_POOL_SEED]
+#[account]
+pub struct SavingsPool {
+  pub bump: u8,
+
+  /// Pool-owned amUSD token account (ATA of this PDA)
+  pub amusd_vault: Pubkey,
+
+  /// amUSD held for depositors, fees included; mirrors the `amusd_vault` balance
+  pub total_pool_amusd: u64,
+
+  /// Depositor shares outstanding; each is a pro-rata claim on `total_pool_amusd`
+  pub total_shares: u64,
+
+  pub _reserved: [u64; 4],
+}
+
+impl SavingsPool {
+  pub const LEN: usize = 8 + // discriminator
+    1 + // bump
+    32 + // amusd_vault
+    8 + // total_pool_amusd
+    8 + // total_shares
+    32; // _reserved
+
+  /// Book a fee deposit. No shares are issued, so it raises the share price.
+  pub fn credit_fee(&mut self, amusd_fee: u64) -> Result<()> {
+    self.total_pool_amusd = self.total_pool_amusd
+      .checked_add(amusd_fee)
+      .ok_or(LaminarError::MathOverflow)?;
+    Ok(())
+  }
+}
+
+/// One depositor's position in the Savings Pool
+/// PDA: [SAVINGS_DEPOSIT_SEED, owner]
+#[account]
+pub struct SavingsDeposit {
+  pub owner: Pubkey,
+
+  pub shares: u64,
+
+  pub bump: u8,
+
+  pub _reserved: [u64; 4],
+}
+
+impl SavingsDeposit {
+  pub const LEN: usize = 8 + // discriminator
+    32 + // owner
+    8 + // shares
+    1 + // bump
+    32; // _reserved
 }
 
+/// One wallet's cumulative mint activity and last action slots, for points
+/// and per-wallet limits
+/// PDA: [USER_POSITION_SEED, owner]
+#[account]
+pub struct UserPosition {
+  pub owner: Pubkey,
+
+  /// LST deposited through mint_amusd and mint_asol, net of transfer fees
+  pub lst_deposited: u64,
+
+  /// amUSD minted to the wallet, fees excluded
+  pub amusd_minted: u64,
+
+  /// aSOL minted to the wallet, fees excluded
+  pub asol_minted: u64,
+
+  /// Slot of the wallet's last mint_amusd or mint_asol; 0 = none yet
+  pub last_mint_slot: u64,
+
+  /// Slot of the wallet's last redeem_amusd or redeem_asol; 0 = none yet
+  pub last_redeem_slot: u64,
+
+  /// Slot of the wallet's last mint_asol; 0 = none yet
+  pub last_asol_mint_slot: u64,
+
+  pub bump: u8,
+
+  pub _reserved: [u64; 4],
+}
+
+impl UserPosition {
+  pub const LEN: usize = 8 + // discriminator
+    32 + // owner
+    8 + // lst_deposited
+    8 + // amusd_minted
+    8 + // asol_minted
+    8 + // last_mint_slot
+    8 + // last_redeem_slot
+    8 + // last_asol_mint_slot
+    1 + // bump
+    32; // _reserved
+}
+
+/// A queued redemption: amUSD or aSOL escrowed by request_redemption and
+/// redeemed at processing-time prices by process_redemption
+/// PDA: [REDEMPTION_TICKET_SEED, sequence (le bytes)]
+#[account]
+pub struct RedemptionTicket {
+  pub owner: Pubkey,
+
+  /// amUSD or aSOL mint of the escrowed tokens
+  pub token_mint: Pubkey,
+
+  /// Queue position; tickets are processed in sequence order
+  pub sequence: u64,
+
+  /// Tokens held in the ticket's escrow ATA; 0 once cancelled
+  pub amount: u64,
+
+  /// Minimum LST the owner accepts when the ticket is processed
+  pub min_lst_out: u64,
+
+  pub request_slot: u64,
+
+  pub bump: u8,
+
+  pub _reserved: [u64; 2],
+}
+
+impl RedemptionTicket {
+  pub const LEN: usize = 8 + // discriminator
+    32 + // owner
+    32 + // token_mint
+    8 + // sequence
+    8 + // amount
+    8 + // min_lst_out
+    8 + // request_slot
+    1 + // bump
+    16; // _reserved
+}
+
+#[constant]
 pub const GLOBAL_STATE_SEED: &[u8] = b"global_state";
 
+#[constant]
+pub const PENDING_PARAM_CHANGE_SEED: &[u8] = b"pending_param_change";
+
+#[constant]
 pub const VAULT_SEED: &[u8] = b"vault";
 
+#[constant]
 pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
 
+#[constant]
+pub const STABILITY_POOL_SEED: &[u8] = b"stability_pool";
+
+#[constant]
+pub const STABILITY_DEPOSIT_SEED: &[u8] = b"stability_deposit";
+
+#[constant]
+pub const STABILITY_EPOCH_SEED: &[u8] = b"stability_epoch";
+
+#[constant]
+pub const PSM_VAULT_SEED: &[u8] = b"psm_vault";
+
+#[constant]
+pub const SAVINGS_POOL_SEED: &[u8] = b"savings_pool";
+
+#[constant]
+pub const SAVINGS_DEPOSIT_SEED: &[u8] = b"savings_deposit";
+
+#[constant]
+pub const USER_POSITION_SEED: &[u8] = b"user_position";
+
+#[constant]
+pub const REDEMPTION_TICKET_SEED: &[u8] = b"redemption_ticket";
+
+#[constant]
+pub const SOL_UNWRAP_SEED: &[u8] = b"sol_unwrap";
+
 pub const CURRENT_VERSION: u8 = 1;
 
 impl GlobalState {
+  /// Decode GlobalState account data as fetched over RPC, discriminator
+  /// included. Rejects data that is not a GlobalState.
+  pub fn try_from_bytes(data: &[u8]) -> Result<Self> {
+    Self::try_deserialize(&mut &data[..])
+  }
+
+  /// True while an external source (stake pool, Marinade state or Sanctum
+  /// calculator) owns `mock_lst_to_sol_rate`; only `sync_exchange_rate` may
+  /// then move it.
+  pub fn has_lst_rate_source(&self) -> bool {
+    self.lst_stake_pool != Pubkey::default()
+      || self.marinade_state != Pubkey::default()
+      || self.lst_calculator_program != Pubkey::default()
+  }
+
+  /// `fallback_price_source`, or `None` when no fallback is configured
+  pub fn fallback_source(&self) -> Option<u8> {
+    (self.fallback_price_source != crate::constants::NO_FALLBACK_PRICE_SOURCE).then_some(self.fallback_price_source)
+  }
+
+  /// Re-derive `uncertainty_index_bps` from the cached oracle snapshot
+  /// (`mock_oracle_confidence_usd` over `mock_sol_price_usd`).
+  pub fn refresh_uncertainty_index(&mut self) -> Result<()> {
+    self.uncertainty_index_bps =
+      derive_uncertainty_index_bps(self.mock_oracle_confidence_usd, self.mock_sol_price_usd)
+        .ok_or(LaminarError::InvalidParameter)?;
+    Ok(())
+  }
+
+  /// Fold the stability fee accrued since `last_accrual_timestamp` into
+  /// `amusd_supply`. Returns the amUSD the caller must mint to the treasury.
+  /// Time that accrues less than one base unit is carried to the next call
+  /// rather than dropped, so frequent calls cannot dodge the fee.
+  pub fn accrue_stability_fee(&mut self, now: i64) -> Result<u64> {
+    let elapsed_seconds = u64::try_from(now.saturating_sub(self.last_accrual_timestamp)).unwrap_or(0);
+    if self.stability_fee_bps_per_year == 0 || self.amusd_supply == 0 || self.settled {
+      self.last_accrual_timestamp = now.max(self.last_accrual_timestamp);
+      return Ok(0);
+    }
+    let accrued = compute_stability_fee_accrual(self.amusd_supply, self.stability_fee_bps_per_year, elapsed_seconds)
+      .ok_or(LaminarError::MathOverflow)?;
+    if accrued == 0 {
+      return Ok(0);
+    }
+    self.amusd_supply = self.amusd_supply
+      .checked_add(accrued)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.last_accrual_timestamp = now;
+    Ok(accrued)
+  }
+
+  /// Open a fresh mint round once `mint_round_slots` have passed since the
+  /// current one opened. A round opens at the first mint after the last
+  /// one elapsed, so idle stretches never bank capacity.
+  pub fn roll_mint_round(&mut self, current_slot: u64) {
+    if current_slot.saturating_sub(self.current_round_start_slot) >= self.mint_round_slots {
+      self.current_round_start_slot = current_slot;
+      self.asol_minted_this_round = 0;
+    }
+  }
+
+  /// Fold the price that held since `last_ema_update_slot` into
+  /// `ema_sol_price_usd`. Call before `mock_sol_price_usd` changes.
+  pub fn refresh_ema_price(&mut self, current_slot: u64) -> Result<()> {
+    let elapsed_slots = current_slot.saturating_sub(self.last_ema_update_slot);
+    self.ema_sol_price_usd = compute_ema_price(
+      self.ema_sol_price_usd,
+      self.mock_sol_price_usd,
+      elapsed_slots,
+      self.ema_half_life_slots,
+    )
+    .ok_or(LaminarError::MathOverflow)?;
+    self.last_ema_update_slot = current_slot;
+    Ok(())
+  }
+
+  /// Uncertainty index for the fee of an action priced from `price`: the
+  /// stored index, plus the surcharge when the fallback was used.
+  pub fn uncertainty_index_for(&self, price: &PriceSnapshot) -> u64 {
+    if price.is_fallback {
+      self.uncertainty_index_bps.saturating_add(self.fallback_uncertainty_bps)
+    } else {
+      self.uncertainty_index_bps
+    }
+  }
+
+  /// Flash-loan utilization recorded in `slot`, or zero if none was.
+  pub fn flash_loan_utilization_at(&self, slot: u64) -> u64 {
+    if self.flash_loan_utilization_slot == slot {
+      self.flash_loan_utilization_bps
+    } else {
+      0
+    }
+  }
+
+  /// `index_bps` with this slot's flash-loan utilization mixed in; what a
+  /// fee computed at `slot` is priced with.
+  pub fn effective_uncertainty_index(&self, index_bps: u64, slot: u64) -> Result<u64> {
+    let index = combine_uncertainty_sources(index_bps, self.flash_loan_utilization_at(slot))
+      .ok_or(LaminarError::MathOverflow)?;
+    Ok(index)
+  }
+
+  /// Reject a SOL/USD price outside `[min_sol_price_usd, max_sol_price_usd]`.
+  pub fn assert_sol_price_within_bounds(&self, sol_price_usd: u64) -> Result<()> {
+    assert_oracle_value_within_bounds(sol_price_usd, self.min_sol_price_usd, self.max_sol_price_usd)
+  }
+
+  /// Reject an LST/SOL rate outside `[min_lst_rate, max_lst_rate]`.
+  pub fn assert_lst_rate_within_bounds(&self, lst_to_sol_rate: u64) -> Result<()> {
+    assert_oracle_value_within_bounds(lst_to_sol_rate, self.min_lst_rate, self.max_lst_rate)
+  }
+
+  /// Advance `operation_counter`. Fails instead of saturating so the counter
+  /// never silently stops being strictly monotonic.
+  pub fn increment_operation_counter(&mut self) -> Result<()> {
+    self.operation_counter = self
+      .operation_counter
+      .checked_add(1)
+      .ok_or(LaminarError::CounterSaturated)?;
+    Ok(())
+  }
+
+  /// Add one haircut redemption's par shortfall to the lifetime and episode
+  /// totals. Returns true when this trips the episode breaker.
+  pub fn record_haircut_shortfall(&mut self, shortfall_lamports: u64) -> Result<bool> {
+    self.cumulative_haircut_shortfall_lamports = self
+      .cumulative_haircut_shortfall_lamports
+      .checked_add(shortfall_lamports)
+      .ok_or(LaminarError::MathOverflow)?;
+    self.episode_haircut_shortfall_lamports = self
+      .episode_haircut_shortfall_lamports
+      .checked_add(shortfall_lamports)
+      .ok_or(LaminarError::MathOverflow)?;
+
+    let tripped = self.max_episode_haircut_lamports > 0
+      && self.episode_haircut_shortfall_lamports > self.max_episode_haircut_lamports
+      && !self.amusd_redeem_paused_by_haircut;
+    if tripped {
+      self.amusd_redeem_paused_by_haircut = true;
+    }
+    Ok(tripped)
+  }
+
+  /// End the current insolvency episode once CR is back at or above 100%.
+  /// Returns the closed episode's shortfall, if there was one. A tripped
+  /// breaker stays tripped.
+  pub fn close_haircut_episode(&mut self, cr_bps: u64) -> Option<u64> {
+    if cr_bps < crate::math::BPS_PRECISION || self.episode_haircut_shortfall_lamports == 0 {
+      return None;
+    }
+    let closed = self.episode_haircut_shortfall_lamports;
+    self.episode_haircut_shortfall_lamports = 0;
+    Some(closed)
+  }
+
+  /// Follow CR across `min_cr_bps`, as observed after a state change.
+  /// Returns the boundary crossing, if this observation made one.
+  pub fn track_recovery_mode(&mut self, cr_bps: u64, slot: u64) -> Option<RecoveryTransition> {
+    let below_min = cr_bps < self.min_cr_bps;
+    if below_min == self.recovery_mode {
+      return None;
+    }
+    self.recovery_mode = below_min;
+    if below_min {
+      self.recovery_mode_entered_slot = slot;
+      Some(RecoveryTransition::Entered)
+    } else {
+      Some(RecoveryTransition::Exited { entered_slot: self.recovery_mode_entered_slot })
+    }
+  }
+
+  /// Zero a counter at or above `OPERATION_COUNTER_CEILING`. Returns the
+  /// corrupt value when a reset happened.
+  pub fn reset_corrupt_operation_counter(&mut self) -> Option<u64> {
+    if self.operation_counter < crate::constants::OPERATION_COUNTER_CEILING {
+      return None;
+    }
+    let corrupt = self.operation_counter;
+    self.operation_counter = 0;
+    Some(corrupt)
+  }
+
+  /// Derive `price_source` for state written before the discriminant existed.
+  /// `Mock` is never selected while a Pyth or Switchboard feed is configured,
+  /// so that combination (or an unknown discriminant) means the feed was live
+  /// under the old implicit selection. Returns the old value when it changed.
+  pub fn migrate_price_source(&mut self) -> Option<u8> {
+    let source = PriceSource::try_from(self.price_source).ok();
+    if source.is_some_and(|source| source != PriceSource::Mock && source.is_configured(self)) {
+      return None;
+    }
+
+    let inferred = if self.pyth_price_feed != Pubkey::default() {
+      PriceSource::Pyth
+    } else if self.switchboard_feed != Pubkey::default() {
+      PriceSource::Switchboard
+    } else {
+      PriceSource::Mock
+    };
+    if inferred as u8 == self.price_source {
+      return None;
+    }
+
+    let old = self.price_source;
+    self.price_source = inferred as u8;
+    Some(old)
+  }
+
+  /// Supply of the amUSD mint: LST-backed debt plus what the PSM backs.
+  pub fn amusd_mint_supply(&self) -> Result<u64> {
+    self.amusd_supply
+      .checked_add(self.psm_amusd_outstanding)
+      .ok_or(LaminarError::MathOverflow.into())
+  }
+
   pub fn validate_version(&self) -> Result<()> {
     require!(
       self.version == CURRENT_VERSION,
@@ -219,6 +1556,35 @@ impl GlobalState {
     );
     Ok(())
   }
+
+  /// Stored bumps are what every signer seed uses, so they must re-derive to
+  /// the accounts actually passed in. Fails early with `CorruptedBumpSeed`
+  /// instead of an opaque signature failure deep inside a token CPI.
+  pub fn validate_bumps(&self, global_state_key: &Pubkey, vault_authority_key: &Pubkey) -> Result<()> {
+    self.validate_global_state_bump(global_state_key)?;
+
+    let derived_vault_authority = Pubkey::create_program_address(
+      &[VAULT_AUTHORITY_SEED, &[self.vault_authority_bump]],
+      &crate::ID,
+    )
+    .map_err(|_| error!(LaminarError::CorruptedBumpSeed))?;
+    require_keys_eq!(derived_vault_authority, *vault_authority_key, LaminarError::CorruptedBumpSeed);
+
+    Ok(())
+  }
+
+  /// The GlobalState half of `validate_bumps`, for instructions signing with
+  /// a `CollateralVault` authority instead of the primary one.
+  pub fn validate_global_state_bump(&self, global_state_key: &Pubkey) -> Result<()> {
+    let derived_global_state = Pubkey::create_program_address(
+      &[GLOBAL_STATE_SEED, &[self.bump]],
+      &crate::ID,
+    )
+    .map_err(|_| error!(LaminarError::CorruptedBumpSeed))?;
+    require_keys_eq!(derived_global_state, *global_state_key, LaminarError::CorruptedBumpSeed);
+
+    Ok(())
+  }
 }
 
 
@@ -269,6 +1635,77 @@ mod tests {
     last_tvl_update_slot: 0,
     last_oracle_update_slot: 0,
     mock_oracle_confidence_usd: 0,
+    treasury_owner_program: Pubkey::default(),
+    pending_authority: Pubkey::default(),
+    pause_started_slot: 0,
+    max_pause_duration_slots: 0,
+    param_timelock_slots: 0,
+    lst_mint_has_freeze_authority: false,
+    redeem_paused_by_vault_freeze: false,
+    pyth_price_feed: Pubkey::default(),
+    switchboard_feed: Pubkey::default(),
+    lst_stake_pool: Pubkey::default(),
+    marinade_state: Pubkey::default(),
+    lst_calculator_program: Pubkey::default(),
+    lst_calculator_state: Pubkey::default(),
+    cumulative_haircut_shortfall_lamports: 0,
+    episode_haircut_shortfall_lamports: 0,
+    max_episode_haircut_lamports: 0,
+    amusd_redeem_paused_by_haircut: false,
+    price_source: 0,
+    secondary_price_source: 0,
+    max_oracle_deviation_bps: 0,
+    max_price_move_bps: 0,
+    min_sol_price_usd: 0,
+    max_sol_price_usd: 0,
+    min_lst_rate: 0,
+    max_lst_rate: 0,
+    last_oracle_update_timestamp: 0,
+    max_oracle_age_seconds: 0,
+    fallback_price_source: crate::constants::NO_FALLBACK_PRICE_SOURCE,
+    fallback_uncertainty_bps: 0,
+    ema_sol_price_usd: 0,
+    ema_half_life_slots: 0,
+    last_ema_update_slot: 0,
+    stability_pool_amusd: 0,
+    rebalance_discount_bps: 0,
+    recovery_mode: false,
+    recovery_mode_entered_slot: 0,
+    recovery_bonus_bps: 0,
+    settled: false,
+    settlement_slot: 0,
+    settlement_sol_price_usd: 0,
+    settlement_lst_to_sol_rate: 0,
+    insurance_fund_lamports: 0,
+    fee_split_insurance_bps: 0,
+    redemption_rate_bps: 0,
+    max_flash_loan_bps: 0,
+    flash_loan_utilization_slot: 0,
+    collateral_tvl_lamports: 0,
+    collateral_type_count: 0,
+    deprecated_collateral_tvl_lamports: 0,
+    weighted_collateral_tvl_lamports: 0,
+    psm_usdc_mint: Pubkey::default(),
+    psm_fee_bps: 0,
+    psm_debt_ceiling: 0,
+    psm_amusd_outstanding: 0,
+    savings_fee_split_bps: 0,
+    stability_fee_bps_per_year: 0,
+    last_accrual_timestamp: 0,
+    amusd_supply_cap: 0,
+    asol_supply_cap: 0,
+    max_total_lst_amount: 0,
+    max_single_deposit_lst: 0,
+    max_single_redeem_units: 0,
+    per_user_amusd_cap: 0,
+    mint_round_slots: 0,
+    current_round_start_slot: 0,
+    asol_minted_this_round: 0,
+    min_action_gap_slots: 0,
+    asol_redeem_cooldown_slots: 0,
+    next_redemption_ticket: 0,
+    redemption_queue_head: 0,
+    pair_cr_tolerance_bps: 0,
     _reserved: [0; 2],
   };
 
@@ -284,4 +1721,446 @@ mod tests {
       serialized.len()
     );
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn test_stability_account_sizes() {
+    let pool = StabilityPool {
+      bump: 0,
+      amusd_vault: Pubkey::default(),
+      total_pool_amusd: 0,
+      total_shares: 0,
+      lst_vault: Pubkey::default(),
+      total_pool_lst: 0,
+      lst_gain_per_share: 0,
+      epoch: 0,
+      _reserved: [0; 4],
+    };
+    assert_eq!(StabilityPool::LEN, 8 + borsh::to_vec(&pool).unwrap().len());
+
+    let deposit = StabilityDeposit {
+      owner: Pubkey::default(),
+      shares: 0,
+      epoch: 0,
+      lst_gain_snapshot: 0,
+      pending_lst_gain: 0,
+      bump: 0,
+      _reserved: [0; 4],
+    };
+    assert_eq!(StabilityDeposit::LEN, 8 + borsh::to_vec(&deposit).unwrap().len());
+
+    let record = StabilityEpochRecord { epoch: 0, final_lst_gain_per_share: 0, bump: 0 };
+    assert_eq!(StabilityEpochRecord::LEN, 8 + borsh::to_vec(&record).unwrap().len());
+  }
+
+  #[test]
+  fn test_savings_account_sizes() {
+    let pool = SavingsPool {
+      bump: 0,
+      amusd_vault: Pubkey::default(),
+      total_pool_amusd: 0,
+      total_shares: 0,
+      _reserved: [0; 4],
+    };
+    assert_eq!(SavingsPool::LEN, 8 + borsh::to_vec(&pool).unwrap().len());
+
+    let deposit = SavingsDeposit {
+      owner: Pubkey::default(),
+      shares: 0,
+      bump: 0,
+      _reserved: [0; 4],
+    };
+    assert_eq!(SavingsDeposit::LEN, 8 + borsh::to_vec(&deposit).unwrap().len());
+  }
+
+  #[test]
+  fn test_user_position_size() {
+    let position = UserPosition {
+      owner: Pubkey::default(),
+      lst_deposited: 0,
+      amusd_minted: 0,
+      asol_minted: 0,
+      last_mint_slot: 0,
+      last_redeem_slot: 0,
+      last_asol_mint_slot: 0,
+      bump: 0,
+      _reserved: [0; 4],
+    };
+    assert_eq!(UserPosition::LEN, 8 + borsh::to_vec(&position).unwrap().len());
+  }
+
+  #[test]
+  fn test_redemption_ticket_size() {
+    let ticket = RedemptionTicket {
+      owner: Pubkey::default(),
+      token_mint: Pubkey::default(),
+      sequence: 0,
+      amount: 0,
+      min_lst_out: 0,
+      request_slot: 0,
+      bump: 0,
+      _reserved: [0; 2],
+    };
+    assert_eq!(RedemptionTicket::LEN, 8 + borsh::to_vec(&ticket).unwrap().len());
+  }
+
+  #[test]
+  fn test_collateral_vault_size() {
+    let vault = CollateralVault {
+      lst_mint: Pubkey::default(),
+      vault: Pubkey::default(),
+      vault_authority: Pubkey::default(),
+      bump: 0,
+      vault_authority_bump: 0,
+      lst_amount: 0,
+      lst_to_sol_rate: 0,
+      status: CollateralStatus::Active as u8,
+      collateral_weight_bps: 0,
+      lst_mint_has_freeze_authority: false,
+      _reserved: [0; 8],
+    };
+    assert_eq!(CollateralVault::LEN, 8 + borsh::to_vec(&vault).unwrap().len());
+  }
+
+  #[test]
+  fn test_collateral_status_round_trips() {
+    for status in [CollateralStatus::Active, CollateralStatus::DepositsDisabled, CollateralStatus::Deprecated] {
+      assert_eq!(CollateralStatus::try_from(status as u8).unwrap(), status);
+    }
+    assert!(CollateralStatus::try_from(3).is_err());
+  }
+
+  fn pool_with(total_pool_amusd: u64, total_shares: u64) -> StabilityPool {
+    StabilityPool {
+      bump: 0,
+      amusd_vault: Pubkey::default(),
+      total_pool_amusd,
+      total_shares,
+      lst_vault: Pubkey::default(),
+      total_pool_lst: 0,
+      lst_gain_per_share: 0,
+      epoch: 0,
+      _reserved: [0; 4],
+    }
+  }
+
+  fn deposit_with(shares: u64) -> StabilityDeposit {
+    StabilityDeposit {
+      owner: Pubkey::default(),
+      shares,
+      epoch: 0,
+      lst_gain_snapshot: 0,
+      pending_lst_gain: 0,
+      bump: 0,
+      _reserved: [0; 4],
+    }
+  }
+
+  #[test]
+  fn test_partial_drawdown_shares_gains_and_keeps_the_epoch() {
+    let mut pool = pool_with(1_000, 1_000);
+    let mut deposit = deposit_with(250);
+
+    assert!(pool.absorb_drawdown(400, 80).unwrap().is_none());
+    assert_eq!((pool.total_pool_amusd, pool.total_pool_lst, pool.epoch), (600, 80, 0));
+
+    deposit.settle_gains(&pool, None).unwrap();
+    assert_eq!((deposit.shares, deposit.pending_lst_gain), (250, 20));
+  }
+
+  #[test]
+  fn test_emptying_drawdown_closes_the_epoch() {
+    let mut pool = pool_with(1_000, 1_000);
+    let mut deposit = deposit_with(250);
+
+    let record = pool.absorb_drawdown(1_000, 80).unwrap().unwrap();
+    assert_eq!(record.epoch, 0);
+    assert_eq!((pool.epoch, pool.total_shares, pool.lst_gain_per_share), (1, 0, 0));
+
+    // A stale position needs its epoch's record to settle
+    assert!(deposit.settle_gains(&pool, None).is_err());
+    let wrong = StabilityEpochRecord { epoch: 7, ..record };
+    assert!(deposit.settle_gains(&pool, Some(&wrong)).is_err());
+
+    deposit.settle_gains(&pool, Some(&record)).unwrap();
+    assert_eq!((deposit.shares, deposit.epoch, deposit.pending_lst_gain), (0, 1, 20));
+
+    // Later gains in the new epoch are not this position's
+    pool.total_pool_amusd = 500;
+    pool.total_shares = 500;
+    pool.absorb_drawdown(100, 50).unwrap();
+    deposit.settle_gains(&pool, None).unwrap();
+    assert_eq!(deposit.pending_lst_gain, 20);
+  }
+
+  #[test]
+  fn test_validate_bumps_rejects_non_canonical_bump() {
+    let (global_state_key, bump) = Pubkey::find_program_address(&[GLOBAL_STATE_SEED], &crate::ID);
+    let (vault_authority_key, vault_authority_bump) =
+      Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED], &crate::ID);
+
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.bump = bump;
+    state.vault_authority_bump = vault_authority_bump;
+    assert!(state.validate_bumps(&global_state_key, &vault_authority_key).is_ok());
+
+    // Stored bump from a stale derivation (e.g. pre-migration seeds)
+    state.bump = bump.wrapping_sub(1);
+    let err = state.validate_bumps(&global_state_key, &vault_authority_key).unwrap_err();
+    assert_eq!(err, error!(LaminarError::CorruptedBumpSeed));
+
+    state.bump = bump;
+    state.vault_authority_bump = vault_authority_bump.wrapping_sub(1);
+    let err = state.validate_bumps(&global_state_key, &vault_authority_key).unwrap_err();
+    assert_eq!(err, error!(LaminarError::CorruptedBumpSeed));
+  }
+
+  #[test]
+  fn test_operation_counter_checked_increment_and_reset() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+
+    state.increment_operation_counter().unwrap();
+    assert_eq!(state.operation_counter, 1);
+    assert_eq!(state.reset_corrupt_operation_counter(), None);
+    assert_eq!(state.operation_counter, 1);
+
+    state.operation_counter = u64::MAX;
+    assert_eq!(
+      state.increment_operation_counter().unwrap_err(),
+      error!(LaminarError::CounterSaturated)
+    );
+    assert_eq!(state.operation_counter, u64::MAX);
+
+    // A corrupt migration value is reset, after which counting resumes.
+    assert_eq!(state.reset_corrupt_operation_counter(), Some(u64::MAX));
+    assert_eq!(state.operation_counter, 0);
+
+    state.operation_counter = crate::constants::OPERATION_COUNTER_CEILING - 1;
+    assert_eq!(state.reset_corrupt_operation_counter(), None);
+  }
+
+  #[test]
+  fn test_global_state_try_from_bytes_checks_discriminator() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.amusd_supply = 42;
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), GlobalState::LEN);
+    assert_eq!(GlobalState::try_from_bytes(&data).unwrap().amusd_supply, 42);
+
+    // Another account type, or too few bytes to hold a discriminator
+    let mut vault = data.clone();
+    vault[..8].copy_from_slice(CollateralVault::DISCRIMINATOR);
+    assert!(GlobalState::try_from_bytes(&vault).is_err());
+    assert!(GlobalState::try_from_bytes(&data[..4]).is_err());
+    assert!(GlobalState::try_from_bytes(&data[..GlobalState::LEN - 1]).is_err());
+  }
+
+  #[test]
+  fn test_roll_mint_round() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.mint_round_slots = 100;
+    state.current_round_start_slot = 1_000;
+    state.asol_minted_this_round = 7;
+
+    state.roll_mint_round(1_099);
+    assert_eq!((state.current_round_start_slot, state.asol_minted_this_round), (1_000, 7));
+
+    // The next round opens at the mint that finds the last one over, not on a fixed grid.
+    state.roll_mint_round(1_250);
+    assert_eq!((state.current_round_start_slot, state.asol_minted_this_round), (1_250, 0));
+  }
+
+  #[test]
+  fn test_recovery_mode_tracks_min_cr_crossings() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    state.min_cr_bps = 13_000;
+
+    assert_eq!(state.track_recovery_mode(15_000, 10), None);
+    assert_eq!(state.track_recovery_mode(12_999, 20), Some(RecoveryTransition::Entered));
+    assert!(state.recovery_mode);
+    assert_eq!(state.recovery_mode_entered_slot, 20);
+
+    // Only the crossing is reported
+    assert_eq!(state.track_recovery_mode(9_000, 30), None);
+    assert_eq!(state.recovery_mode_entered_slot, 20);
+
+    // Exactly min is out of recovery; no debt (infinite CR) too.
+    assert_eq!(state.track_recovery_mode(13_000, 40), Some(RecoveryTransition::Exited { entered_slot: 20 }));
+    assert!(!state.recovery_mode);
+    assert_eq!(state.track_recovery_mode(u64::MAX, 50), None);
+    assert_eq!(state.recovery_mode_entered_slot, 20);
+  }
+
+  #[test]
+  fn test_haircut_shortfall_accumulates_per_episode_and_trips_breaker() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+
+    // Breaker disabled: totals still accumulate.
+    assert!(!state.record_haircut_shortfall(400).unwrap());
+    assert_eq!(state.close_haircut_episode(9_999), None);
+    assert_eq!(state.close_haircut_episode(10_000), Some(400));
+    assert_eq!(state.episode_haircut_shortfall_lamports, 0);
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, 400);
+    assert_eq!(state.close_haircut_episode(u64::MAX), None);
+
+    state.max_episode_haircut_lamports = 1_000;
+    assert!(!state.record_haircut_shortfall(600).unwrap());
+    assert!(!state.record_haircut_shortfall(400).unwrap());
+    // Exceeding, not reaching, the bound trips it, and only once.
+    assert!(state.record_haircut_shortfall(1).unwrap());
+    assert!(state.amusd_redeem_paused_by_haircut);
+    assert!(!state.record_haircut_shortfall(1).unwrap());
+    assert_eq!(state.cumulative_haircut_shortfall_lamports, 1_402);
+
+    // Closing the episode does not lift the pause.
+    assert_eq!(state.close_haircut_episode(10_000), Some(1_002));
+    assert!(state.amusd_redeem_paused_by_haircut);
+
+    state.cumulative_haircut_shortfall_lamports = u64::MAX;
+    assert_eq!(
+      state.record_haircut_shortfall(1).unwrap_err(),
+      error!(LaminarError::MathOverflow)
+    );
+  }
+
+  #[test]
+  fn test_migrate_price_source_infers_legacy_feed_selection() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    assert_eq!(state.migrate_price_source(), None);
+
+    // Pre-discriminant state with a live Switchboard feed
+    state.switchboard_feed = Pubkey::new_unique();
+    assert_eq!(state.migrate_price_source(), Some(PriceSource::Mock as u8));
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+    assert_eq!(state.migrate_price_source(), None);
+
+    // An explicit selection of a configured feed is left alone.
+    state.pyth_price_feed = Pubkey::new_unique();
+    assert_eq!(state.migrate_price_source(), None);
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+
+    // Unknown or unconfigured discriminants are repaired.
+    state.price_source = 7;
+    assert_eq!(state.migrate_price_source(), Some(7));
+    assert_eq!(state.price_source, PriceSource::Pyth as u8);
+    state.price_source = PriceSource::StakePool as u8;
+    assert_eq!(state.migrate_price_source(), Some(PriceSource::StakePool as u8));
+    assert_eq!(state.price_source, PriceSource::Pyth as u8);
+  }
+
+  #[test]
+  fn test_price_feed_param_change_tracks_price_source() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+    let pyth_price_feed = Pubkey::new_unique();
+
+    ParamChange::PriceFeed { pyth_price_feed }.apply(&mut state).unwrap();
+    assert_eq!(state.price_source, PriceSource::Pyth as u8);
+
+    state.switchboard_feed = Pubkey::new_unique();
+    ParamChange::PriceFeed { pyth_price_feed: Pubkey::default() }.apply(&mut state).unwrap();
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+
+    // Configuring Pyth does not steal an explicit Switchboard selection.
+    ParamChange::PriceFeed { pyth_price_feed }.apply(&mut state).unwrap();
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+
+    // Clearing the cross-checked feed disables the cross-check.
+    state.secondary_price_source = PriceSource::Pyth as u8;
+    ParamChange::PriceFeed { pyth_price_feed: Pubkey::default() }.apply(&mut state).unwrap();
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+    assert_eq!(state.secondary_price_source, PriceSource::Mock as u8);
+
+    // Falling over to the cross-checked Switchboard feed disables it too.
+    ParamChange::PriceFeed { pyth_price_feed }.apply(&mut state).unwrap();
+    state.price_source = PriceSource::Pyth as u8;
+    state.secondary_price_source = PriceSource::Switchboard as u8;
+    ParamChange::PriceFeed { pyth_price_feed: Pubkey::default() }.apply(&mut state).unwrap();
+    assert_eq!(state.price_source, PriceSource::Switchboard as u8);
+    assert_eq!(state.secondary_price_source, PriceSource::Mock as u8);
+
+    // A fallback on the cleared feed is dropped as well.
+    ParamChange::PriceFeed { pyth_price_feed }.apply(&mut state).unwrap();
+    state.fallback_price_source = PriceSource::Pyth as u8;
+    ParamChange::PriceFeed { pyth_price_feed: Pubkey::default() }.apply(&mut state).unwrap();
+    assert_eq!(state.fallback_price_source, crate::constants::NO_FALLBACK_PRICE_SOURCE);
+  }
+
+  #[test]
+  fn test_param_change_max_len_covers_every_variant() {
+    let changes = [
+      ParamChange::Risk { min_cr_bps: u64::MAX, target_cr_bps: u64::MAX },
+      ParamChange::Fees {
+        fee_amusd_mint_bps: u64::MAX,
+        fee_amusd_redeem_bps: u64::MAX,
+        fee_asol_mint_bps: u64::MAX,
+        fee_asol_redeem_bps: u64::MAX,
+        fee_min_multiplier_bps: u64::MAX,
+        fee_max_multiplier_bps: u64::MAX,
+      },
+      ParamChange::Oracle {
+        max_oracle_staleness_slots: u64::MAX,
+        max_conf_bps: u64::MAX,
+        uncertainty_max_bps: u64::MAX,
+        max_lst_stale_epochs: u64::MAX,
+      },
+      ParamChange::Timelock { param_timelock_slots: u64::MAX },
+      ParamChange::PriceFeed { pyth_price_feed: Pubkey::new_unique() },
+      ParamChange::VaultStatus { lst_mint: Pubkey::new_unique(), status: u8::MAX },
+      ParamChange::FallbackPriceSource { fallback_price_source: Some(u8::MAX), fallback_uncertainty_bps: u64::MAX },
+      ParamChange::HaircutBreaker { max_episode_haircut_lamports: u64::MAX },
+      ParamChange::LstCalculator { lst_calculator_program: Pubkey::new_unique(), lst_calculator_state: Pubkey::new_unique() },
+      ParamChange::LstStakePool { lst_stake_pool: Pubkey::new_unique() },
+      ParamChange::MarinadeState { marinade_state: Pubkey::new_unique() },
+      ParamChange::PrimaryPriceSource { price_source: u8::MAX },
+      ParamChange::SecondaryPriceSource { secondary_price_source: u8::MAX, max_oracle_deviation_bps: u64::MAX },
+      ParamChange::SwitchboardFeed { switchboard_feed: Pubkey::new_unique() },
+      ParamChange::Treasury { treasury: Pubkey::new_unique(), treasury_is_pda: true, treasury_owner_program: Pubkey::new_unique() },
+      ParamChange::AsolRedeemCooldown { asol_redeem_cooldown_slots: u64::MAX },
+      ParamChange::EmaHalfLife { ema_half_life_slots: u64::MAX },
+      ParamChange::FeeSplit { fee_split_insurance_bps: u64::MAX },
+      ParamChange::MaxOracleAge { max_oracle_age_seconds: u64::MAX },
+      ParamChange::MaxPauseDuration { max_pause_duration_slots: u64::MAX },
+      ParamChange::MaxPriceMove { max_price_move_bps: u64::MAX },
+      ParamChange::MinActionGap { min_action_gap_slots: u64::MAX },
+      ParamChange::MintRound { max_asol_mint_per_round: u64::MAX, mint_round_slots: u64::MAX },
+      ParamChange::MockOracleConfidence { confidence_usd: u64::MAX },
+      ParamChange::OracleBounds {
+        min_sol_price_usd: u64::MAX,
+        max_sol_price_usd: u64::MAX,
+        min_lst_rate: u64::MAX,
+        max_lst_rate: u64::MAX,
+      },
+      ParamChange::PairCrTolerance { pair_cr_tolerance_bps: u64::MAX },
+      ParamChange::PerUserCap { per_user_amusd_cap: u64::MAX },
+      ParamChange::PsmParameters { psm_fee_bps: u64::MAX, psm_debt_ceiling: u64::MAX },
+      ParamChange::RebalanceDiscount { rebalance_discount_bps: u64::MAX },
+      ParamChange::RecoveryBonus { recovery_bonus_bps: u64::MAX },
+      ParamChange::RoundingReserveCap { max_rounding_reserve_lamports: u64::MAX },
+      ParamChange::SavingsFeeSplit { savings_fee_split_bps: u64::MAX },
+      ParamChange::StabilityFee { stability_fee_bps_per_year: u64::MAX },
+      ParamChange::SupplyCaps { amusd_supply_cap: u64::MAX, asol_supply_cap: u64::MAX },
+      ParamChange::TransactionLimits { max_single_deposit_lst: u64::MAX, max_single_redeem_units: u64::MAX },
+      ParamChange::TvlCap { max_total_lst_amount: u64::MAX },
+    ];
+
+    let longest = changes
+      .iter()
+      .map(|change| borsh::to_vec(change).expect("Failed to serialize").len())
+      .max()
+      .unwrap();
+    assert_eq!(ParamChange::MAX_LEN, longest);
+
+    let kinds: Vec<u8> = changes.iter().map(ParamChange::kind).collect();
+    assert_eq!(kinds, (0..=35).collect::<Vec<u8>>());
+  }
+
+  #[test]
+  fn test_setter_param_change_is_not_applied_on_execute() {
+    let mut state = GlobalState::try_from_slice(&vec![0u8; GlobalState::LEN - 8]).unwrap();
+
+    assert!(ParamChange::TvlCap { max_total_lst_amount: 1_000 }.apply(&mut state).is_err());
+    assert_eq!(state.max_total_lst_amount, 0);
+    assert!(ParamChange::Timelock { param_timelock_slots: 10 }.apply(&mut state).is_ok());
+    assert_eq!(state.param_timelock_slots, 10);
+  }
+}
@@ -0,0 +1,153 @@
+//! Switchboard on-demand adapter for the SOL/USD price
+//! Reads the aggregated result of a `PullFeedAccountData` account and maps its
+//! value and standard deviation onto the same `(price, confidence, slot)`
+//! triple as the Pyth adapter. Staleness and confidence are left to
+//! `assert_oracle_freshness_and_confidence`.
+//!
+//! The feed is decoded here from its fixed zero-copy layout rather than
+//! through `switchboard-on-demand`, whose releases resolve against a newer
+//! `solana-program` than Anchor's.
+
+use anchor_lang::prelude::*;
+
+use crate::error::LaminarError;
+use crate::oracle::OraclePrice;
+
+/// Switchboard on-demand program (mainnet)
+pub const ON_DEMAND_MAINNET_PID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Switchboard on-demand program (devnet)
+pub const ON_DEMAND_DEVNET_PID: Pubkey = pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+/// Anchor discriminator of `PullFeedAccountData`
+pub const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+/// Discriminator plus the `repr(C)` `PullFeedAccountData` body
+pub const PULL_FEED_ACCOUNT_LEN: usize = 8 + 3_200;
+
+/// Offsets into the account, discriminator included
+pub const PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 2_208;
+/// `result: CurrentResult` starts here
+const PULL_FEED_RESULT_OFFSET: usize = 8 + 2_256;
+pub const PULL_FEED_RESULT_VALUE_OFFSET: usize = PULL_FEED_RESULT_OFFSET;
+pub const PULL_FEED_RESULT_STD_DEV_OFFSET: usize = PULL_FEED_RESULT_OFFSET + 16;
+pub const PULL_FEED_RESULT_NUM_SAMPLES_OFFSET: usize = PULL_FEED_RESULT_OFFSET + 96;
+pub const PULL_FEED_RESULT_SLOT_OFFSET: usize = PULL_FEED_RESULT_OFFSET + 104;
+
+/// The fields of a pull feed the adapter reads
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PullFeedResult {
+  /// 18-decimal fixed point
+  pub value: i128,
+  pub std_dev: i128,
+  pub num_samples: u8,
+  pub slot: u64,
+  pub last_update_timestamp: i64,
+}
+
+impl PullFeedResult {
+  /// `None` unless `data` is a whole `PullFeedAccountData` account.
+  pub fn decode(data: &[u8]) -> Option<Self> {
+    if data.len() < PULL_FEED_ACCOUNT_LEN || data[..8] != PULL_FEED_DISCRIMINATOR {
+      return None;
+    }
+    let i128_at = |offset: usize| data[offset..offset + 16].try_into().ok().map(i128::from_le_bytes);
+    let u64_at = |offset: usize| data[offset..offset + 8].try_into().ok().map(u64::from_le_bytes);
+    Some(Self {
+      value: i128_at(PULL_FEED_RESULT_VALUE_OFFSET)?,
+      std_dev: i128_at(PULL_FEED_RESULT_STD_DEV_OFFSET)?,
+      num_samples: data[PULL_FEED_RESULT_NUM_SAMPLES_OFFSET],
+      slot: u64_at(PULL_FEED_RESULT_SLOT_OFFSET)?,
+      last_update_timestamp: u64_at(PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET)? as i64,
+    })
+  }
+}
+
+/// Switchboard results are fixed-point with 18 decimals; micro-USD has 6.
+const SWITCHBOARD_TO_MICRO_USD: i128 = 1_000_000_000_000;
+
+/// Scale a Switchboard `(value, std_dev)` pair into micro-USD.
+/// Value rounds down and the deviation rounds up, matching the Pyth adapter.
+/// Returns `None` on a non-positive value, a negative deviation, or overflow.
+pub fn switchboard_to_micro_usd(value: i128, std_dev: i128) -> Option<(u64, u64)> {
+  if value <= 0 || std_dev < 0 {
+    return None;
+  }
+  let price = u64::try_from(value / SWITCHBOARD_TO_MICRO_USD).ok()?;
+  let confidence = u64::try_from((std_dev as u128).div_ceil(SWITCHBOARD_TO_MICRO_USD as u128)).ok()?;
+  Some((price, confidence))
+}
+
+/// Decode a Switchboard pull feed.
+///
+/// # Arguments
+/// * `switchboard_feed` - Account supplied as `switchboard_feed`; address
+///   already matched against `GlobalState::switchboard_feed` by the account constraints
+pub fn read_switchboard_price(switchboard_feed: &AccountInfo) -> Result<OraclePrice> {
+  require!(
+    *switchboard_feed.owner == ON_DEMAND_MAINNET_PID || *switchboard_feed.owner == ON_DEMAND_DEVNET_PID,
+    LaminarError::InvalidPriceFeed
+  );
+
+  let data = switchboard_feed.try_borrow_data()?;
+  let feed = PullFeedResult::decode(&data).ok_or(LaminarError::InvalidPriceFeed)?;
+
+  // A feed that never reached quorum reports an all-zero result.
+  require!(feed.num_samples > 0, LaminarError::InvalidPriceFeed);
+
+  let (price_usd, confidence_usd) = switchboard_to_micro_usd(feed.value, feed.std_dev)
+    .ok_or(LaminarError::InvalidPriceFeed)?;
+  require!(price_usd > 0, LaminarError::InvalidPriceFeed);
+
+  Ok(OraclePrice {
+    price_usd,
+    confidence_usd,
+    publish_slot: feed.slot,
+    publish_time: feed.last_update_timestamp,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const ONE: i128 = 1_000_000_000_000_000_000;
+
+  #[test]
+  fn test_switchboard_to_micro_usd_scales_from_18_decimals() {
+    // $142.5 +/- $0.07
+    assert_eq!(switchboard_to_micro_usd(142 * ONE + ONE / 2, 7 * ONE / 100), Some((142_500_000, 70_000)));
+    // Sub-micro-USD deviation still counts as one unit of confidence
+    assert_eq!(switchboard_to_micro_usd(100 * ONE, 1), Some((100_000_000, 1)));
+    assert_eq!(switchboard_to_micro_usd(100 * ONE, 0), Some((100_000_000, 0)));
+  }
+
+  #[test]
+  fn test_pull_feed_result_decodes_the_zero_copy_layout() {
+    let mut data = vec![0u8; PULL_FEED_ACCOUNT_LEN];
+    data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+    data[PULL_FEED_RESULT_VALUE_OFFSET..][..16].copy_from_slice(&(142 * ONE).to_le_bytes());
+    data[PULL_FEED_RESULT_STD_DEV_OFFSET..][..16].copy_from_slice(&(ONE / 10).to_le_bytes());
+    data[PULL_FEED_RESULT_NUM_SAMPLES_OFFSET] = 3;
+    data[PULL_FEED_RESULT_SLOT_OFFSET..][..8].copy_from_slice(&77u64.to_le_bytes());
+    data[PULL_FEED_LAST_UPDATE_TIMESTAMP_OFFSET..][..8].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+
+    assert_eq!(
+      PullFeedResult::decode(&data),
+      Some(PullFeedResult { value: 142 * ONE, std_dev: ONE / 10, num_samples: 3, slot: 77, last_update_timestamp: 1_700_000_000 })
+    );
+
+    // Truncated or foreign accounts
+    assert_eq!(PullFeedResult::decode(&data[..PULL_FEED_ACCOUNT_LEN - 1]), None);
+    data[0] ^= 1;
+    assert_eq!(PullFeedResult::decode(&data), None);
+  }
+
+  #[test]
+  fn test_switchboard_to_micro_usd_rejects_bad_inputs() {
+    assert_eq!(switchboard_to_micro_usd(0, 0), None);
+    assert_eq!(switchboard_to_micro_usd(-ONE, 0), None);
+    assert_eq!(switchboard_to_micro_usd(ONE, -1), None);
+    assert_eq!(switchboard_to_micro_usd(i128::MAX, 0), None);
+  }
+}
@@ -1,16 +1,31 @@
-use laminar::constants::MIN_PROTOCOL_TVL;
+use laminar::constants::{DEFAULT_PAIR_CR_TOLERANCE_BPS, MAX_RECOVERY_DILUTION_BPS, MIN_PROTOCOL_TVL};
 use laminar::invariants::{
-    assert_balance_sheet_holds, assert_cr_above_minimum, assert_rounding_reserve_within_cap,
+    assert_balance_sheet_holds, assert_cr_above_minimum, assert_no_pending_drawdown, assert_rounding_reserve_within_cap,
     credit_rounding_reserve, debit_rounding_reserve, derive_rounding_bound_lamports,
 };
+use laminar::quote::{
+    quote_mint_amusd, quote_mint_asol, quote_redeem_amusd, quote_redeem_asol, Quote, QuoteState,
+};
 use laminar::math::{
-    apply_fee, asol_dust_to_lamports_up, compute_accounting_equity_sol, compute_claimable_equity_sol,
-    compute_cr_bps, compute_dynamic_fee_bps, compute_liability_sol, compute_rounding_delta_units,
-    compute_tvl_sol, lst_dust_to_lamports_up, mul_div_down, mul_div_up, nav_asol_with_reserve,
+    apply_fee, asol_dust_to_lamports_up, split_fee, compute_accounting_equity_sol, compute_amusd_to_asol, compute_asol_to_amusd, compute_claimable_equity_sol,
+    compute_cr_bps, compute_cr_neutral_redeem, compute_dynamic_fee_bps, compute_ema_price, compute_insurance_topup, compute_liability_sol,
+    compute_liability_at_rate_sol, compute_non_claimable_sol, compute_socialized_rate_bps, compute_uninsured_cr_bps,
+    compute_stability_drawdown, compute_stability_gain, compute_stability_gain_per_share, compute_stability_share_value,
+    compute_stability_shares, compute_stability_shares_to_burn, compute_rounding_delta_units,
+    compute_tvl_sol, compute_weighted_tvl_sol, derive_redeem_haircut_bps, lst_dust_to_lamports_up, mul_div_down, mul_div_up, nav_asol_with_reserve,
     usd_dust_to_lamports_up, FeeAction, BPS_PRECISION, MIN_AMUSD_MINT, MIN_ASOL_MINT,
     MIN_LST_DEPOSIT, SOL_PRECISION, USD_PRECISION,
 };
 
+/// Spec spot and EMA: the EMA sat at $98 and has had one half-life to close
+/// half the gap to a $100 spot.
+fn spec_spot_and_ema() -> (u64, u64) {
+    let spot = 100_000_000u64;
+    let ema = compute_ema_price(98_000_000, spot, 150, 150).unwrap();
+    assert_eq!(ema, 99_000_000);
+    (spot, ema)
+}
+
 #[test]
 fn vector_63_5_1_mint_amusd_matches_spec_numbers() {
     let total_lst_amount = 1_000 * SOL_PRECISION;
@@ -18,7 +33,9 @@ fn vector_63_5_1_mint_amusd_matches_spec_numbers() {
     let amusd_supply = 50_000 * USD_PRECISION;
     let rounding_reserve = 0u64;
 
-    let p_safe = 99_000_000u64;
+    let (spot, ema) = spec_spot_and_ema();
+    let p_safe = spot.min(ema);
+    assert_eq!(p_safe, 99_000_000);
     let q_lst = 10 * SOL_PRECISION;
     let fee_base_bps = 50u64;
 
@@ -51,7 +68,9 @@ fn vector_63_5_1_mint_amusd_matches_spec_numbers() {
 
 #[test]
 fn vector_63_5_2_redeem_amusd_matches_spec_numbers() {
-    let p_redeem = 100_000_000u64;
+    let (spot, ema) = spec_spot_and_ema();
+    let p_redeem = spot.max(ema);
+    assert_eq!(p_redeem, 100_000_000);
     let lst_to_sol_rate = 1_050_000_000u64;
     let amusd_in = 1_000 * USD_PRECISION;
 
@@ -62,6 +81,162 @@ fn vector_63_5_2_redeem_amusd_matches_spec_numbers() {
     assert_eq!(lst_out, 9_523_809_524);
 }
 
+#[test]
+fn vector_63_5_2_drawdown_lifts_cr_back_to_min_before_redeem() {
+    let (spot, ema) = spec_spot_and_ema();
+    let p_redeem = spot.max(ema);
+    let lst_to_sol_rate = 1_050_000_000u64;
+    let total_lst_amount = 1_000 * SOL_PRECISION;
+    let amusd_supply = 87_500 * USD_PRECISION;
+    let pool_amusd = 50_000 * USD_PRECISION;
+    let min_cr_bps = 13_000u64;
+
+    let tvl = compute_tvl_sol(total_lst_amount, lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(amusd_supply, p_redeem).unwrap();
+    assert_eq!(compute_cr_bps(tvl, liability), 12_000);
+
+    let (amusd_burned, lst_to_pool) =
+        compute_stability_drawdown(total_lst_amount, lst_to_sol_rate, 0, amusd_supply, p_redeem, pool_amusd, min_cr_bps).unwrap();
+    assert_eq!(amusd_burned, 29_166_666_669);
+    assert_eq!(lst_to_pool, 277_777_777_800);
+
+    let new_tvl = compute_tvl_sol(total_lst_amount - lst_to_pool, lst_to_sol_rate).unwrap();
+    let new_liability = compute_liability_sol(amusd_supply - amusd_burned, p_redeem).unwrap();
+    assert_eq!(compute_cr_bps(new_tvl, new_liability), 13_000);
+    assert_no_pending_drawdown(compute_cr_bps(new_tvl, new_liability), min_cr_bps, pool_amusd - amusd_burned).unwrap();
+
+    // The remainder redeems at par exactly as in vector_63_5_2.
+    let sol_out = mul_div_up(1_000 * USD_PRECISION, SOL_PRECISION, p_redeem).unwrap();
+    assert_eq!(sol_out, 10_000_000_000);
+}
+
+#[test]
+fn vector_63_5_2_drawdown_below_par_takes_the_holders_haircut() {
+    let p_redeem = 100_000_000u64;
+    let lst_to_sol_rate = 1_050_000_000u64;
+    let total_lst_amount = 1_000 * SOL_PRECISION;
+    let amusd_supply = 131_250 * USD_PRECISION;
+    let pool_amusd = 10_000 * USD_PRECISION;
+
+    let tvl = compute_tvl_sol(total_lst_amount, lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(amusd_supply, p_redeem).unwrap();
+    assert_eq!(compute_cr_bps(tvl, liability), 8_000);
+
+    // Whole pool, paid at the 80% haircut: 100 SOL of par becomes 80 SOL.
+    let (amusd_burned, lst_to_pool) =
+        compute_stability_drawdown(total_lst_amount, lst_to_sol_rate, 0, amusd_supply, p_redeem, pool_amusd, 13_000).unwrap();
+    assert_eq!(amusd_burned, pool_amusd);
+    assert_eq!(lst_to_pool, 76_190_476_190);
+
+    // CR is unchanged and the pool is empty, so the haircut path may proceed.
+    let new_tvl = compute_tvl_sol(total_lst_amount - lst_to_pool, lst_to_sol_rate).unwrap();
+    let new_liability = compute_liability_sol(amusd_supply - amusd_burned, p_redeem).unwrap();
+    assert_eq!(compute_cr_bps(new_tvl, new_liability), 8_000);
+    assert!(assert_no_pending_drawdown(compute_cr_bps(new_tvl, new_liability), 13_000, 0).is_ok());
+    assert!(assert_no_pending_drawdown(compute_cr_bps(tvl, liability), 13_000, pool_amusd).is_err());
+}
+
+/// 900 SOL of uninsured collateral against 1_000 SOL of amUSD (uninsured
+/// CR 90%) plus `insurance` lamports of insurance, LST at 1:1.
+fn insured_insolvent_book(insurance: u64) -> QuoteState {
+    QuoteState {
+        total_lst_amount: 900 * SOL_PRECISION + insurance,
+        amusd_supply: 100_000 * USD_PRECISION,
+        asol_supply: 500 * SOL_PRECISION,
+        rounding_reserve_lamports: 0,
+        insurance_fund_lamports: insurance,
+        lst_to_sol_rate: SOL_PRECISION,
+        ..ModelState::seeded().quote_state()
+    }
+}
+
+#[test]
+fn vector_insurance_fully_absorbs_the_haircut() {
+    let state = insured_insolvent_book(20 * SOL_PRECISION);
+    let tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap();
+    assert_eq!(compute_uninsured_cr_bps(tvl, liability, state.insurance_fund_lamports), 9_000);
+
+    // 1_000 amUSD is 10 SOL at par; the 90% haircut leaves 1 SOL short,
+    // which the 20 SOL fund covers outright.
+    let quote = quote_redeem_amusd(&state, 1_000 * USD_PRECISION).unwrap();
+    assert_eq!(quote.fee, 0);
+    assert_eq!(quote.to_user, 10 * SOL_PRECISION);
+    assert_eq!(quote.new_insurance_fund, 19 * SOL_PRECISION);
+
+    // The uninsured book is untouched: CR stays at 90%.
+    let new_tvl = compute_tvl_sol(quote.new_total_lst_amount, state.lst_to_sol_rate).unwrap();
+    let new_liability = compute_liability_sol(quote.new_supply, state.sol_price_usd).unwrap();
+    assert_eq!(compute_uninsured_cr_bps(new_tvl, new_liability, quote.new_insurance_fund), 9_000);
+
+    let non_claimable = compute_non_claimable_sol(quote.new_rounding_reserve, quote.new_insurance_fund).unwrap();
+    let equity = compute_accounting_equity_sol(new_tvl, new_liability, non_claimable).unwrap();
+    let bound = derive_rounding_bound_lamports(3, 1, state.sol_price_usd).unwrap();
+    assert_balance_sheet_holds(new_tvl, new_liability, equity, non_claimable, bound).unwrap();
+}
+
+#[test]
+fn vector_insurance_partially_absorbs_the_haircut() {
+    let state = insured_insolvent_book(SOL_PRECISION / 2);
+
+    // Same 1 SOL shortfall, but the fund only has 0.5 SOL: it is spent in
+    // full and the holder eats the rest.
+    let quote = quote_redeem_amusd(&state, 1_000 * USD_PRECISION).unwrap();
+    assert_eq!(quote.to_user, 9_500_000_000);
+    assert_eq!(quote.new_insurance_fund, 0);
+
+    let uninsured = quote_redeem_amusd(&insured_insolvent_book(0), 1_000 * USD_PRECISION).unwrap();
+    assert_eq!(uninsured.to_user, 9 * SOL_PRECISION);
+    assert_eq!(compute_insurance_topup(10 * SOL_PRECISION, 9 * SOL_PRECISION, SOL_PRECISION / 2), Some(SOL_PRECISION / 2));
+}
+
+#[test]
+fn vector_socialized_bad_debt_redeems_every_holder_at_the_same_rate() {
+    let book = insured_insolvent_book(0);
+    let tvl = compute_tvl_sol(book.total_lst_amount, book.lst_to_sol_rate).unwrap();
+    let liability = compute_liability_sol(book.amusd_supply, book.sol_price_usd).unwrap();
+    let rate = compute_socialized_rate_bps(BPS_PRECISION, compute_cr_bps(tvl, liability)).unwrap();
+    assert_eq!(rate, 8_999);
+
+    // 100_000 amUSD written down to 89_990 USD is 899.9 SOL against 900 SOL
+    // of collateral: CR is back just above 100%.
+    let mut state = QuoteState { redemption_rate_bps: rate, fee_amusd_redeem_bps: 0, ..book };
+    assert_eq!(compute_liability_at_rate_sol(state.amusd_supply, state.sol_price_usd, rate).unwrap(), 899_900_000_000);
+
+    // 1_000 amUSD is 899.9 USD, 8.999 SOL, for the first holder out and the next.
+    for _ in 0..2 {
+        let quote = quote_redeem_amusd(&state, 1_000 * USD_PRECISION).unwrap();
+        assert_eq!(quote.to_user, 8_999_000_000);
+        assert_eq!(quote.new_insurance_fund, 0);
+        state.total_lst_amount = quote.new_total_lst_amount;
+        state.amusd_supply = quote.new_supply;
+
+        let new_tvl = compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap();
+        let new_liability = compute_liability_at_rate_sol(state.amusd_supply, state.sol_price_usd, rate).unwrap();
+        assert_eq!(compute_cr_bps(new_tvl, new_liability), BPS_PRECISION + 1);
+    }
+}
+
+#[test]
+fn vector_fee_split_routes_a_quarter_of_the_mint_fee_to_insurance() {
+    let mut state = ModelState { fee_split_insurance_bps: 2_500, ..ModelState::seeded() };
+    let quote = quote_mint_amusd(&state.quote_state(), 10 * SOL_PRECISION).unwrap();
+
+    // 10 LST at 1.05 and $100 is 1_050 amUSD gross; CR is above target, so
+    // the 0.5% base fee of 5.25 amUSD splits 3.9375 / 1.3125.
+    assert_eq!(quote.to_user, 1_044_750_000);
+    assert_eq!(quote.fee, 3_937_500);
+    assert_eq!(quote.insurance_fee, 1_312_500);
+    assert_eq!(quote.new_supply, 80_000 * USD_PRECISION + 1_050 * USD_PRECISION - 1_312_500);
+    // 1.3125 amUSD at $100 is 0.013125 SOL of insurance.
+    assert_eq!(quote.new_insurance_fund, 13_125_000);
+
+    let receipt = model_mint_amusd(&mut state, Vault::Primary, 10 * SOL_PRECISION).unwrap();
+    assert_eq!((receipt.to_treasury, receipt.to_insurance), (quote.fee, quote.insurance_fee));
+    assert_eq!(state.insurance_fund_lamports, quote.new_insurance_fund);
+    assert_model_invariants(&state, receipt.bound);
+}
+
 #[test]
 fn vector_63_5_3_mint_asol_matches_conservative_rounding() {
     let total_lst_amount = 1_000 * SOL_PRECISION;
@@ -92,6 +267,59 @@ fn vector_63_5_3_mint_asol_matches_conservative_rounding() {
     assert_eq!(asol_minted, 10_500_000_000);
 }
 
+#[test]
+fn vector_recovery_mint_asol_buys_more_asol_per_lst_than_at_target() {
+    // Same book at target CR (15000) and after SOL falls to $70 (CR 10500).
+    let at_target = QuoteState {
+        amusd_supply: 105_000 * USD_PRECISION,
+        asol_supply: 525 * SOL_PRECISION,
+        ..ModelState::seeded().quote_state()
+    };
+    let in_recovery = QuoteState { sol_price_usd: 70 * USD_PRECISION, ..at_target };
+    let tvl_and_liability = |state: &QuoteState| {
+        (
+            compute_tvl_sol(state.total_lst_amount, state.lst_to_sol_rate).unwrap(),
+            compute_liability_sol(state.amusd_supply, state.sol_price_usd).unwrap(),
+        )
+    };
+    let cr = |state: &QuoteState| {
+        let (tvl, liability) = tvl_and_liability(state);
+        compute_cr_bps(tvl, liability)
+    };
+    let nav = |state: &QuoteState| {
+        let (tvl, liability) = tvl_and_liability(state);
+        nav_asol_with_reserve(tvl, liability, state.rounding_reserve_lamports, state.asol_supply).unwrap()
+    };
+    assert_eq!(cr(&at_target), 15_000);
+    assert_eq!(cr(&in_recovery), 10_500);
+    let lst_amount = 10 * SOL_PRECISION;
+    let sol_value = compute_tvl_sol(lst_amount, at_target.lst_to_sol_rate).unwrap();
+
+    let target_quote = quote_mint_asol(&at_target, lst_amount).unwrap();
+    let recovery_quote = quote_mint_asol(&in_recovery, lst_amount).unwrap();
+    let undiscounted = quote_mint_asol(&QuoteState { recovery_bonus_bps: 0, ..in_recovery }, lst_amount).unwrap();
+
+    assert!(recovery_quote.to_user > target_quote.to_user);
+
+    // Valued at pre-mint NAV: fees make a target-CR mint a small loss, while
+    // the recovery bonus leaves the depositor ahead. The lower NAV alone
+    // does not do that.
+    assert!(mul_div_down(target_quote.to_user, nav(&at_target), SOL_PRECISION).unwrap() < sol_value);
+    assert!(mul_div_down(undiscounted.to_user, nav(&in_recovery), SOL_PRECISION).unwrap() < sol_value);
+    assert!(mul_div_down(recovery_quote.to_user, nav(&in_recovery), SOL_PRECISION).unwrap() > sol_value);
+
+    // Existing holders give up at most MAX_RECOVERY_DILUTION_BPS of NAV.
+    let after = QuoteState {
+        total_lst_amount: recovery_quote.new_total_lst_amount,
+        asol_supply: recovery_quote.new_supply,
+        rounding_reserve_lamports: recovery_quote.new_rounding_reserve,
+        ..in_recovery
+    };
+    let nav_floor = mul_div_down(nav(&in_recovery), BPS_PRECISION - MAX_RECOVERY_DILUTION_BPS, BPS_PRECISION).unwrap();
+    assert!(nav(&after) >= nav_floor);
+    assert!(nav(&after) < nav(&in_recovery));
+}
+
 #[test]
 fn vector_63_5_4_redeem_asol_matches_spec_numbers() {
     let asol_in = SOL_PRECISION;
@@ -212,6 +440,7 @@ struct ModelState {
     asol_supply: u64,
     rounding_reserve_lamports: u64,
     max_rounding_reserve_lamports: u64,
+    insurance_fund_lamports: u64,
     sol_price_usd: u64,
     lst_to_sol_rate: u64,
     min_cr_bps: u64,
@@ -224,6 +453,19 @@ struct ModelState {
     fee_max_multiplier_bps: u64,
     uncertainty_index_bps: u64,
     uncertainty_max_bps: u64,
+    recovery_bonus_bps: u64,
+    fee_split_insurance_bps: u64,
+    collateral_lst_amount: u64,
+    collateral_lst_to_sol_rate: u64,
+    collateral_weight_bps: u64,
+}
+
+/// Which vault a model action moves LST through: the primary LST's, or the
+/// one `CollateralVault` the model carries beside it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Vault {
+    Primary,
+    Collateral,
 }
 
 impl ModelState {
@@ -243,6 +485,7 @@ impl ModelState {
             asol_supply,
             rounding_reserve_lamports: 0,
             max_rounding_reserve_lamports: 1_000_000_000,
+            insurance_fund_lamports: 0,
             sol_price_usd,
             lst_to_sol_rate,
             min_cr_bps: 13_000,
@@ -255,11 +498,62 @@ impl ModelState {
             fee_max_multiplier_bps: 40_000,
             uncertainty_index_bps: 0,
             uncertainty_max_bps: 20_000,
+            recovery_bonus_bps: 200,
+            fee_split_insurance_bps: 0,
+            collateral_lst_amount: 0,
+            collateral_lst_to_sol_rate: SOL_PRECISION,
+            collateral_weight_bps: BPS_PRECISION,
+        }
+    }
+
+    /// `seeded` with a second collateral type: 400 LST at 1.08 SOL, counted
+    /// toward CR at 85%. aSOL supply is re-seeded so NAV starts at par.
+    fn seeded_with_collateral() -> Self {
+        let mut state = Self {
+            collateral_lst_amount: 400 * SOL_PRECISION,
+            collateral_lst_to_sol_rate: 1_080_000_000,
+            collateral_weight_bps: 8_500,
+            ..Self::seeded()
+        };
+        state.asol_supply = compute_claimable_equity_sol(state.tvl(), state.liability(), 0).unwrap();
+        state
+    }
+
+    fn set_lst(&mut self, vault: Vault, lst_amount: u64) {
+        match vault {
+            Vault::Primary => self.total_lst_amount = lst_amount,
+            Vault::Collateral => self.collateral_lst_amount = lst_amount,
         }
     }
 
+    /// Book TVL with `vault` holding `lst_amount`, each vault at its own rate.
+    fn tvl_with(self, vault: Vault, lst_amount: u64) -> Option<u64> {
+        self.book_with(vault, lst_amount, BPS_PRECISION)
+    }
+
+    /// `tvl_with` with the collateral vault at its weight, as CR sees it.
+    fn weighted_tvl_with(self, vault: Vault, lst_amount: u64) -> Option<u64> {
+        self.book_with(vault, lst_amount, self.collateral_weight_bps)
+    }
+
+    fn book_with(self, vault: Vault, lst_amount: u64, collateral_weight_bps: u64) -> Option<u64> {
+        let (primary, collateral) = match vault {
+            Vault::Primary => (lst_amount, self.collateral_lst_amount),
+            Vault::Collateral => (self.total_lst_amount, lst_amount),
+        };
+        compute_tvl_sol(primary, self.lst_to_sol_rate)?.checked_add(compute_weighted_tvl_sol(
+            collateral,
+            self.collateral_lst_to_sol_rate,
+            collateral_weight_bps,
+        )?)
+    }
+
     fn tvl(self) -> u64 {
-        compute_tvl_sol(self.total_lst_amount, self.lst_to_sol_rate).unwrap()
+        self.tvl_with(Vault::Primary, self.total_lst_amount).unwrap()
+    }
+
+    fn weighted_tvl(self) -> u64 {
+        self.weighted_tvl_with(Vault::Primary, self.total_lst_amount).unwrap()
     }
 
     fn liability(self) -> u64 {
@@ -269,6 +563,23 @@ impl ModelState {
             compute_liability_sol(self.amusd_supply, self.sol_price_usd).unwrap()
         }
     }
+
+    fn non_claimable(self) -> u64 {
+        compute_non_claimable_sol(self.rounding_reserve_lamports, self.insurance_fund_lamports).unwrap()
+    }
+}
+
+/// Outcome of a successful model action.
+///
+/// `to_user` is what the caller receives (tranche units for mints, LST for
+/// redeems); `to_treasury` is the fee left in tranche supply for the treasury
+/// and `to_insurance` the fee share taken out of supply into the insurance fund.
+#[derive(Clone, Copy)]
+struct ModelReceipt {
+    bound: u64,
+    to_user: u64,
+    to_treasury: u64,
+    to_insurance: u64,
 }
 
 fn xorshift64(seed: &mut u64) -> u64 {
@@ -287,11 +598,28 @@ fn rand_range(seed: &mut u64, lo: u64, hi: u64) -> u64 {
     lo + (xorshift64(seed) % (hi - lo + 1))
 }
 
+/// Property runs alternate between the single-vault book and one with a
+/// second collateral type; actions on the latter go through either vault.
+fn seeded_for(seed: u64) -> (ModelState, bool) {
+    if seed % 2 == 0 {
+        (ModelState::seeded_with_collateral(), true)
+    } else {
+        (ModelState::seeded(), false)
+    }
+}
+
+fn rand_vault(seed: &mut u64, two_vaults: bool) -> Vault {
+    if two_vaults && xorshift64(seed) % 2 == 0 {
+        Vault::Collateral
+    } else {
+        Vault::Primary
+    }
+}
+
 fn assert_model_invariants(state: &ModelState, rounding_bound_lamports: u64) {
     let tvl = state.tvl();
     let liability = state.liability();
-    let accounting_equity =
-        compute_accounting_equity_sol(tvl, liability, state.rounding_reserve_lamports).unwrap();
+    let accounting_equity = compute_accounting_equity_sol(tvl, liability, state.non_claimable()).unwrap();
 
     assert_rounding_reserve_within_cap(
         state.rounding_reserve_lamports,
@@ -303,179 +631,94 @@ fn assert_model_invariants(state: &ModelState, rounding_bound_lamports: u64) {
         tvl,
         liability,
         accounting_equity,
-        state.rounding_reserve_lamports,
+        state.non_claimable(),
         rounding_bound_lamports,
     )
     .unwrap();
 }
 
-fn model_mint_amusd(state: &mut ModelState, lst_amount: u64) -> Option<u64> {
-    if lst_amount < MIN_LST_DEPOSIT {
-        return None;
-    }
-
-    let old_tvl = state.tvl();
-    let old_liability = state.liability();
-    let old_cr = compute_cr_bps(old_tvl, old_liability);
-
-    let sol_value = compute_tvl_sol(lst_amount, state.lst_to_sol_rate)?;
-    let sol_value_up = mul_div_up(lst_amount, state.lst_to_sol_rate, SOL_PRECISION)?;
-
-    let amusd_gross = mul_div_down(sol_value, state.sol_price_usd, SOL_PRECISION)?;
-    if amusd_gross < MIN_AMUSD_MINT {
-        return None;
+/// Commit `quote` for `action` through `vault` if the handler's post-trade
+/// checks pass on the book it leaves: reserve within cap and the balance
+/// sheet within `bound`. `None` where the handler would refuse.
+fn model_commit(state: &mut ModelState, vault: Vault, action: FeeAction, quote: &Quote, bound: u64) -> Option<ModelReceipt> {
+    let mut next = *state;
+    next.set_lst(vault, quote.new_total_lst_amount);
+    match action {
+        FeeAction::AmusdMint | FeeAction::AmUSDRedeem => next.amusd_supply = quote.new_supply,
+        FeeAction::AsolMint | FeeAction::AsolRedeem => next.asol_supply = quote.new_supply,
     }
+    next.rounding_reserve_lamports = quote.new_rounding_reserve;
+    next.insurance_fund_lamports = quote.new_insurance_fund;
+
+    assert_rounding_reserve_within_cap(next.rounding_reserve_lamports, next.max_rounding_reserve_lamports).ok()?;
+    let equity = compute_accounting_equity_sol(next.tvl(), next.liability(), next.non_claimable())?;
+    assert_balance_sheet_holds(next.tvl(), next.liability(), equity, next.non_claimable(), bound).ok()?;
+
+    *state = next;
+    Some(ModelReceipt {
+        bound,
+        to_user: quote.to_user,
+        to_treasury: quote.fee,
+        to_insurance: quote.insurance_fee,
+    })
+}
 
-    let amusd_gross_up = mul_div_up(sol_value_up, state.sol_price_usd, SOL_PRECISION)?;
-    let delta_usd = compute_rounding_delta_units(amusd_gross, amusd_gross_up)?;
-    let reserve_credit = usd_dust_to_lamports_up(delta_usd, state.sol_price_usd)?;
-
-    let fee_bps = compute_dynamic_fee_bps(
-        state.fee_amusd_mint_bps,
-        FeeAction::AmusdMint,
-        old_cr,
-        state.min_cr_bps,
-        state.target_cr_bps,
-        state.fee_min_multiplier_bps,
-        state.fee_max_multiplier_bps,
-        state.uncertainty_index_bps,
-        state.uncertainty_max_bps,
-    )?;
-
-    let (amusd_to_user, _) = apply_fee(amusd_gross, fee_bps)?;
-    if amusd_to_user < MIN_AMUSD_MINT {
-        return None;
+/// CR once `vault` holds `lst_amount` against `liability`; `u64::MAX` with no debt.
+fn model_cr_with(state: &ModelState, vault: Vault, lst_amount: u64, liability: u64) -> Option<u64> {
+    if liability == 0 {
+        return Some(u64::MAX);
     }
+    Some(compute_cr_bps(state.weighted_tvl_with(vault, lst_amount)?, liability))
+}
 
-    let new_lst = state.total_lst_amount.checked_add(lst_amount)?;
-    let new_amusd_supply = state.amusd_supply.checked_add(amusd_gross)?;
-    let new_tvl = compute_tvl_sol(new_lst, state.lst_to_sol_rate)?;
-    let new_liability = compute_liability_sol(new_amusd_supply, state.sol_price_usd)?;
-    let new_reserve = credit_rounding_reserve(
-        state.rounding_reserve_lamports,
-        reserve_credit,
-        state.max_rounding_reserve_lamports,
-    )
-    .ok()?;
-
-    let new_cr = compute_cr_bps(new_tvl, new_liability);
-    if assert_cr_above_minimum(new_cr, state.min_cr_bps).is_err() {
+fn model_mint_amusd(state: &mut ModelState, vault: Vault, lst_amount: u64) -> Option<ModelReceipt> {
+    if lst_amount < MIN_LST_DEPOSIT {
         return None;
     }
 
-    let equity = compute_accounting_equity_sol(new_tvl, new_liability, new_reserve)?;
-    let bound = derive_rounding_bound_lamports(2, 1, state.sol_price_usd).ok()?;
-    if assert_balance_sheet_holds(new_tvl, new_liability, equity, new_reserve, bound).is_err() {
+    let quote = quote_mint_amusd(&state.quote_state_through(vault), lst_amount)?;
+    if quote.to_user < MIN_AMUSD_MINT {
         return None;
     }
 
-    state.total_lst_amount = new_lst;
-    state.amusd_supply = new_amusd_supply;
-    state.rounding_reserve_lamports = new_reserve;
+    let new_liability = compute_liability_sol(quote.new_supply, state.sol_price_usd)?;
+    let new_cr = model_cr_with(state, vault, quote.new_total_lst_amount, new_liability)?;
+    assert_cr_above_minimum(new_cr, state.min_cr_bps).ok()?;
 
-    Some(bound)
+    let bound = derive_rounding_bound_lamports(2, 1, state.sol_price_usd).ok()?;
+    model_commit(state, vault, FeeAction::AmusdMint, &quote, bound)
 }
 
-fn model_redeem_amusd(state: &mut ModelState, amusd_amount: u64) -> Option<u64> {
+fn model_redeem_amusd(state: &mut ModelState, vault: Vault, amusd_amount: u64) -> Option<ModelReceipt> {
     if amusd_amount == 0 || state.amusd_supply == 0 {
         return None;
     }
 
     let amount = amusd_amount.min(state.amusd_supply);
+    let book = state.quote_state_through(vault);
+    let (insolvency_mode, _) = derive_redeem_haircut_bps(book.uninsured_cr_bps()?);
 
-    let old_tvl = state.tvl();
-    let old_liability = state.liability();
-    let old_cr = compute_cr_bps(old_tvl, old_liability);
-    let insolvency_mode = old_cr < BPS_PRECISION;
-
-    let (amusd_net_in, _) = if insolvency_mode {
-        (amount, 0u64)
-    } else {
-        let fee_bps = compute_dynamic_fee_bps(
-            state.fee_amusd_redeem_bps,
-            FeeAction::AmUSDRedeem,
-            old_cr,
-            state.min_cr_bps,
-            state.target_cr_bps,
-            state.fee_min_multiplier_bps,
-            state.fee_max_multiplier_bps,
-            state.uncertainty_index_bps,
-            state.uncertainty_max_bps,
-        )?;
-        let (net, fee) = apply_fee(amount, fee_bps)?;
-        if net == 0 {
-            return None;
-        }
-        (net, fee)
-    };
-
-    let sol_par_down = mul_div_down(amusd_net_in, SOL_PRECISION, state.sol_price_usd)?;
-    let lst_par_down = mul_div_down(sol_par_down, SOL_PRECISION, state.lst_to_sol_rate)?;
-
-    let (lst_out, reserve_debit, rounding_k_lamports) = if insolvency_mode {
-        let haircut_bps = old_cr.min(BPS_PRECISION);
-        let sol_haircut = mul_div_down(sol_par_down, haircut_bps, BPS_PRECISION)?;
-        let lst_haircut = mul_div_down(sol_haircut, SOL_PRECISION, state.lst_to_sol_rate)?;
-        (lst_haircut, 0u64, 3u64)
-    } else {
-        let sol_up = mul_div_up(amusd_net_in, SOL_PRECISION, state.sol_price_usd)?;
-        let lst_up = mul_div_up(sol_up, SOL_PRECISION, state.lst_to_sol_rate)?;
-        let delta_lst = compute_rounding_delta_units(lst_par_down, lst_up)?;
-        let lamport_debit = lst_dust_to_lamports_up(delta_lst, state.lst_to_sol_rate)?;
-
-        if lamport_debit <= state.rounding_reserve_lamports {
-            (lst_up, lamport_debit, 2u64)
-        } else {
-            (lst_par_down, 0u64, 2u64)
-        }
-    };
-
-    if lst_out < MIN_LST_DEPOSIT {
+    let quote = quote_redeem_amusd(&book, amount)?;
+    if quote.gross == quote.fee + quote.insurance_fee || quote.to_user < MIN_LST_DEPOSIT {
         return None;
     }
-
-    let new_lst = state.total_lst_amount.checked_sub(lst_out)?;
-    if !(new_lst >= MIN_PROTOCOL_TVL || new_lst == 0) {
+    if !(quote.new_total_lst_amount >= MIN_PROTOCOL_TVL || quote.new_total_lst_amount == 0) {
         return None;
     }
 
-    let new_amusd_supply = state.amusd_supply.checked_sub(amusd_net_in)?;
-    let new_liability = if new_amusd_supply == 0 {
-        0
-    } else {
-        compute_liability_sol(new_amusd_supply, state.sol_price_usd)?
-    };
-
-    let new_tvl = compute_tvl_sol(new_lst, state.lst_to_sol_rate)?;
-    let new_reserve = debit_rounding_reserve(state.rounding_reserve_lamports, reserve_debit).ok()?;
-    let new_equity = compute_accounting_equity_sol(new_tvl, new_liability, new_reserve)?;
+    let rounding_k_lamports = if insolvency_mode { 3 } else { 2 };
     let bound = derive_rounding_bound_lamports(rounding_k_lamports, 1, state.sol_price_usd).ok()?;
-
-    if assert_balance_sheet_holds(new_tvl, new_liability, new_equity, new_reserve, bound).is_err()
-    {
-        return None;
-    }
-
-    state.total_lst_amount = new_lst;
-    state.amusd_supply = new_amusd_supply;
-    state.rounding_reserve_lamports = new_reserve;
-
-    Some(bound)
+    model_commit(state, vault, FeeAction::AmUSDRedeem, &quote, bound)
 }
 
-fn model_mint_asol(state: &mut ModelState, lst_amount: u64) -> Option<u64> {
+fn model_mint_asol(state: &mut ModelState, vault: Vault, lst_amount: u64) -> Option<ModelReceipt> {
     if lst_amount < MIN_LST_DEPOSIT {
         return None;
     }
 
     let old_tvl = state.tvl();
     let old_liability = state.liability();
-    let old_cr = compute_cr_bps(old_tvl, old_liability);
-    let old_claimable =
-        compute_claimable_equity_sol(old_tvl, old_liability, state.rounding_reserve_lamports)?;
-
     let bound = derive_rounding_bound_lamports(2, 0, state.sol_price_usd).ok()?;
-    let mut effective_reserve = state.rounding_reserve_lamports;
 
     if state.asol_supply == 0 {
         if old_tvl < old_liability {
@@ -483,185 +726,52 @@ fn model_mint_asol(state: &mut ModelState, lst_amount: u64) -> Option<u64> {
         }
 
         let lhs = old_tvl as i128;
-        let rhs = (old_liability as i128).checked_add(effective_reserve as i128)?;
-        let diff = if lhs >= rhs {
-            (lhs - rhs) as u128
-        } else {
-            (rhs - lhs) as u128
-        };
+        let rhs = (old_liability as i128).checked_add(state.non_claimable() as i128)?;
+        let diff = lhs.abs_diff(rhs);
 
         if diff > bound as u128 {
             return None;
         }
 
-        if old_claimable > 0 {
-            effective_reserve = effective_reserve.checked_add(old_claimable)?;
-            if effective_reserve > state.max_rounding_reserve_lamports {
-                return None;
-            }
-        }
-    }
-
-    let sol_value = compute_tvl_sol(lst_amount, state.lst_to_sol_rate)?;
-    let sol_value_up = mul_div_up(lst_amount, state.lst_to_sol_rate, SOL_PRECISION)?;
-
-    let current_nav = if state.asol_supply == 0 {
-        SOL_PRECISION
-    } else {
-        let nav = nav_asol_with_reserve(old_tvl, old_liability, effective_reserve, state.asol_supply)?;
-        if nav == 0 {
+        let old_claimable = compute_claimable_equity_sol(old_tvl, old_liability, state.non_claimable())?;
+        if state.rounding_reserve_lamports.checked_add(old_claimable)? > state.max_rounding_reserve_lamports {
             return None;
         }
-        nav
-    };
-
-    let asol_gross = if state.asol_supply == 0 {
-        sol_value
-    } else {
-        mul_div_down(sol_value, SOL_PRECISION, current_nav)?
-    };
-
-    let asol_ref_up = if state.asol_supply == 0 {
-        sol_value_up
-    } else {
-        mul_div_up(sol_value_up, SOL_PRECISION, current_nav)?
-    };
-
-    let delta_asol = compute_rounding_delta_units(asol_gross, asol_ref_up)?;
-    let reserve_credit = if state.asol_supply == 0 {
-        delta_asol
-    } else {
-        asol_dust_to_lamports_up(delta_asol, current_nav)?
-    };
-
-    let fee_bps = compute_dynamic_fee_bps(
-        state.fee_asol_mint_bps,
-        FeeAction::AsolMint,
-        old_cr,
-        state.min_cr_bps,
-        state.target_cr_bps,
-        state.fee_min_multiplier_bps,
-        state.fee_max_multiplier_bps,
-        state.uncertainty_index_bps,
-        state.uncertainty_max_bps,
-    )?;
-
-    let (asol_net, _) = apply_fee(asol_gross, fee_bps)?;
-    if asol_net < MIN_ASOL_MINT {
-        return None;
     }
 
-    let new_lst = state.total_lst_amount.checked_add(lst_amount)?;
-    let new_asol_supply = state.asol_supply.checked_add(asol_gross)?;
-    let new_tvl = compute_tvl_sol(new_lst, state.lst_to_sol_rate)?;
-    let new_reserve = credit_rounding_reserve(
-        effective_reserve,
-        reserve_credit,
-        state.max_rounding_reserve_lamports,
-    )
-    .ok()?;
-    let new_equity = compute_accounting_equity_sol(new_tvl, old_liability, new_reserve)?;
-
-    if assert_balance_sheet_holds(new_tvl, old_liability, new_equity, new_reserve, bound).is_err() {
+    let quote = quote_mint_asol(&state.quote_state_through(vault), lst_amount)?;
+    if quote.to_user < MIN_ASOL_MINT {
         return None;
     }
 
-    state.total_lst_amount = new_lst;
-    state.asol_supply = new_asol_supply;
-    state.rounding_reserve_lamports = new_reserve;
-
-    Some(bound)
+    model_commit(state, vault, FeeAction::AsolMint, &quote, bound)
 }
 
-fn model_redeem_asol(state: &mut ModelState, asol_amount: u64) -> Option<u64> {
+fn model_redeem_asol(state: &mut ModelState, vault: Vault, asol_amount: u64) -> Option<ModelReceipt> {
     if asol_amount == 0 || state.asol_supply == 0 {
         return None;
     }
 
     let amount = asol_amount.min(state.asol_supply);
 
-    let old_tvl = state.tvl();
-    let old_liability = state.liability();
-    let old_cr = compute_cr_bps(old_tvl, old_liability);
-    let solvent_mode = old_cr >= BPS_PRECISION;
-
-    let fee_bps = compute_dynamic_fee_bps(
-        state.fee_asol_redeem_bps,
-        FeeAction::AsolRedeem,
-        old_cr,
-        state.min_cr_bps,
-        state.target_cr_bps,
-        state.fee_min_multiplier_bps,
-        state.fee_max_multiplier_bps,
-        state.uncertainty_index_bps,
-        state.uncertainty_max_bps,
-    )?;
-
-    let (asol_net_in, _) = apply_fee(amount, fee_bps)?;
-    if asol_net_in == 0 {
-        return None;
-    }
-
-    let nav = nav_asol_with_reserve(
-        old_tvl,
-        old_liability,
-        state.rounding_reserve_lamports,
-        state.asol_supply,
-    )?;
-    if nav == 0 {
-        return None;
-    }
-
-    let sol_down = mul_div_down(asol_net_in, nav, SOL_PRECISION)?;
-    let lst_down = mul_div_down(sol_down, SOL_PRECISION, state.lst_to_sol_rate)?;
-
-    let (lst_out, reserve_debit) = if solvent_mode {
-        let sol_up = mul_div_up(asol_net_in, nav, SOL_PRECISION)?;
-        let lst_up = mul_div_up(sol_up, SOL_PRECISION, state.lst_to_sol_rate)?;
-        let delta_lst = compute_rounding_delta_units(lst_down, lst_up)?;
-        let debit = lst_dust_to_lamports_up(delta_lst, state.lst_to_sol_rate)?;
-        if debit <= state.rounding_reserve_lamports {
-            (lst_up, debit)
-        } else {
-            (lst_down, 0u64)
-        }
-    } else {
-        (lst_down, 0u64)
-    };
-
-    if lst_out < MIN_LST_DEPOSIT {
+    let quote = quote_redeem_asol(&state.quote_state_through(vault), amount)?;
+    if quote.gross == quote.fee + quote.insurance_fee || quote.to_user < MIN_LST_DEPOSIT {
         return None;
     }
-
-    let new_lst = state.total_lst_amount.checked_sub(lst_out)?;
-    if !(new_lst >= MIN_PROTOCOL_TVL || new_lst == 0) {
+    if !(quote.new_total_lst_amount >= MIN_PROTOCOL_TVL || quote.new_total_lst_amount == 0) {
         return None;
     }
 
-    let new_asol_supply = state.asol_supply.checked_sub(asol_net_in)?;
-    let new_tvl = compute_tvl_sol(new_lst, state.lst_to_sol_rate)?;
-    let new_reserve = debit_rounding_reserve(state.rounding_reserve_lamports, reserve_debit).ok()?;
-    let new_equity = compute_accounting_equity_sol(new_tvl, old_liability, new_reserve)?;
-    let new_cr = if old_liability == 0 {
-        u64::MAX
-    } else {
-        compute_cr_bps(new_tvl, old_liability)
-    };
-
-    if assert_cr_above_minimum(new_cr, state.min_cr_bps).is_err() {
-        return None;
-    }
+    let new_cr = model_cr_with(state, vault, quote.new_total_lst_amount, state.liability())?;
+    assert_cr_above_minimum(new_cr, state.min_cr_bps).ok()?;
 
     let bound = derive_rounding_bound_lamports(2, 0, state.sol_price_usd).ok()?;
-    if assert_balance_sheet_holds(new_tvl, old_liability, new_equity, new_reserve, bound).is_err() {
-        return None;
-    }
-
-    state.total_lst_amount = new_lst;
-    state.asol_supply = new_asol_supply;
-    state.rounding_reserve_lamports = new_reserve;
+    model_commit(state, vault, FeeAction::AsolRedeem, &quote, bound)
+}
 
-    Some(bound)
+fn model_fund_insurance(state: &mut ModelState, lst_amount: u64) {
+    state.total_lst_amount += lst_amount;
+    state.insurance_fund_lamports += compute_tvl_sol(lst_amount, state.lst_to_sol_rate).unwrap();
 }
 
 #[test]
@@ -671,7 +781,7 @@ fn property_random_action_sequences_preserve_invariants() {
 
     for seed in 1..=SEEDS {
         let mut rng = seed;
-        let mut state = ModelState::seeded();
+        let (mut state, two_vaults) = seeded_for(seed);
 
         for _ in 0..STEPS_PER_SEED {
             if xorshift64(&mut rng) % 97 == 0 {
@@ -681,32 +791,771 @@ fn property_random_action_sequences_preserve_invariants() {
             if xorshift64(&mut rng) % 131 == 0 {
                 state.lst_to_sol_rate = rand_range(&mut rng, 900_000_000, 1_150_000_000);
             }
+            if xorshift64(&mut rng) % 151 == 0 {
+                let amt = rand_range(&mut rng, MIN_LST_DEPOSIT, 50 * SOL_PRECISION);
+                model_fund_insurance(&mut state, amt);
+            }
 
+            let vault = rand_vault(&mut rng, two_vaults);
             let maybe_bound = match xorshift64(&mut rng) % 4 {
                 0 => {
                     let amt = rand_range(&mut rng, MIN_LST_DEPOSIT, 20 * SOL_PRECISION);
-                    model_mint_amusd(&mut state, amt)
+                    model_mint_amusd(&mut state, vault, amt)
                 }
                 1 => {
                     let cap = state.amusd_supply.min(2_000 * USD_PRECISION);
                     let amt = if cap == 0 { 0 } else { rand_range(&mut rng, 1, cap) };
-                    model_redeem_amusd(&mut state, amt)
+                    model_redeem_amusd(&mut state, vault, amt)
                 }
                 2 => {
                     let amt = rand_range(&mut rng, MIN_LST_DEPOSIT, 20 * SOL_PRECISION);
-                    model_mint_asol(&mut state, amt)
+                    model_mint_asol(&mut state, vault, amt)
                 }
                 _ => {
                     let cap = state.asol_supply.min(20 * SOL_PRECISION);
                     let amt = if cap == 0 { 0 } else { rand_range(&mut rng, 1, cap) };
-                    model_redeem_asol(&mut state, amt)
+                    model_redeem_asol(&mut state, vault, amt)
                 }
             };
 
             let bound = maybe_bound
+                .map(|receipt| receipt.bound)
                 .unwrap_or_else(|| derive_rounding_bound_lamports(3, 1, state.sol_price_usd).unwrap());
 
             assert_model_invariants(&state, bound);
         }
     }
 }
+
+impl ModelState {
+    fn quote_state(self) -> QuoteState {
+        QuoteState {
+            total_lst_amount: self.total_lst_amount,
+            other_tvl_lamports: compute_tvl_sol(self.collateral_lst_amount, self.collateral_lst_to_sol_rate).unwrap(),
+            collateral_weight_bps: BPS_PRECISION,
+            other_weighted_tvl_lamports: compute_weighted_tvl_sol(
+                self.collateral_lst_amount,
+                self.collateral_lst_to_sol_rate,
+                self.collateral_weight_bps,
+            )
+            .unwrap(),
+            deprecated_tvl_lamports: 0,
+            amusd_supply: self.amusd_supply,
+            asol_supply: self.asol_supply,
+            rounding_reserve_lamports: self.rounding_reserve_lamports,
+            insurance_fund_lamports: self.insurance_fund_lamports,
+            sol_price_usd: self.sol_price_usd,
+            lst_to_sol_rate: self.lst_to_sol_rate,
+            min_cr_bps: self.min_cr_bps,
+            target_cr_bps: self.target_cr_bps,
+            fee_amusd_mint_bps: self.fee_amusd_mint_bps,
+            fee_amusd_redeem_bps: self.fee_amusd_redeem_bps,
+            fee_asol_mint_bps: self.fee_asol_mint_bps,
+            fee_asol_redeem_bps: self.fee_asol_redeem_bps,
+            fee_min_multiplier_bps: self.fee_min_multiplier_bps,
+            fee_max_multiplier_bps: self.fee_max_multiplier_bps,
+            uncertainty_index_bps: self.uncertainty_index_bps,
+            uncertainty_max_bps: self.uncertainty_max_bps,
+            recovery_bonus_bps: self.recovery_bonus_bps,
+            fee_split_insurance_bps: self.fee_split_insurance_bps,
+            redemption_rate_bps: BPS_PRECISION,
+            settled: false,
+        }
+    }
+
+    /// The quote state a handler builds for an action through `vault`.
+    fn quote_state_through(self, vault: Vault) -> QuoteState {
+        let book = self.quote_state();
+        match vault {
+            Vault::Primary => book,
+            Vault::Collateral => book
+                .through_vault(self.collateral_lst_amount, self.collateral_lst_to_sol_rate, self.collateral_weight_bps)
+                .unwrap(),
+        }
+    }
+}
+
+/// Largest residual position (in lamports of value) a holder may be left
+/// with after wind-down. Exits are bounded below by the MIN_PROTOCOL_TVL floor
+/// (in LST units) and the MIN_LST_DEPOSIT minimum output; with halving retries
+/// a pinned holder keeps less than two floors of LST, i.e. < 3 floors of value
+/// at the highest modelled LST rate.
+const WIND_DOWN_DUST_LAMPORTS: u64 = 3 * MIN_PROTOCOL_TVL + 2 * MIN_LST_DEPOSIT;
+
+const WIND_DOWN_HOLDERS: usize = 6;
+const TREASURY_HOLDER: usize = 0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tranche {
+    AmUSD,
+    Asol,
+}
+
+/// Per-holder tranche balances. Index `TREASURY_HOLDER` collects fees.
+struct Holders {
+    amusd: [u64; WIND_DOWN_HOLDERS],
+    asol: [u64; WIND_DOWN_HOLDERS],
+}
+
+impl Holders {
+    /// Split the seeded supplies across the non-treasury holders.
+    fn from_seeded(state: &ModelState, rng: &mut u64) -> Self {
+        let mut holders = Self {
+            amusd: [0; WIND_DOWN_HOLDERS],
+            asol: [0; WIND_DOWN_HOLDERS],
+        };
+        let mut amusd_left = state.amusd_supply;
+        let mut asol_left = state.asol_supply;
+        for i in 1..WIND_DOWN_HOLDERS - 1 {
+            let a = rand_range(rng, 0, amusd_left);
+            let b = rand_range(rng, 0, asol_left);
+            holders.amusd[i] = a;
+            holders.asol[i] = b;
+            amusd_left -= a;
+            asol_left -= b;
+        }
+        holders.amusd[WIND_DOWN_HOLDERS - 1] = amusd_left;
+        holders.asol[WIND_DOWN_HOLDERS - 1] = asol_left;
+        holders
+    }
+
+    fn balance_mut(&mut self, tranche: Tranche, holder: usize) -> &mut u64 {
+        match tranche {
+            Tranche::AmUSD => &mut self.amusd[holder],
+            Tranche::Asol => &mut self.asol[holder],
+        }
+    }
+
+    /// Try to redeem `amount` for `holder`; fees are credited to the treasury.
+    fn redeem(&mut self, state: &mut ModelState, tranche: Tranche, holder: usize, amount: u64) -> bool {
+        if amount == 0 || amount > *self.balance_mut(tranche, holder) {
+            return false;
+        }
+        let receipt = match tranche {
+            Tranche::AmUSD => model_redeem_amusd(state, Vault::Primary, amount),
+            Tranche::Asol => model_redeem_asol(state, Vault::Primary, amount),
+        };
+        match receipt {
+            Some(receipt) => {
+                *self.balance_mut(tranche, holder) -= amount;
+                *self.balance_mut(tranche, TREASURY_HOLDER) += receipt.to_treasury;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Exit as much as possible: full balance first, then halving, so the
+    /// floor/min-output cliffs only ever pin a residual, never the position.
+    fn exit_step(&mut self, state: &mut ModelState, tranche: Tranche, holder: usize) -> bool {
+        let balance = *self.balance_mut(tranche, holder);
+        for shift in 0..16 {
+            let amount = balance >> shift;
+            if amount == 0 {
+                break;
+            }
+            if self.redeem(state, tranche, holder, amount) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Value of a residual position in lamports at the frozen wind-down prices.
+fn residual_value_lamports(state: &ModelState, tranche: Tranche, balance: u64) -> u64 {
+    if balance == 0 {
+        return 0;
+    }
+    match tranche {
+        Tranche::AmUSD => {
+            // Par value, scaled down to the pro-rata haircut when insolvent.
+            let par = compute_liability_sol(balance, state.sol_price_usd).unwrap();
+            let (tvl, liability) = (state.tvl(), state.liability());
+            if tvl < liability {
+                mul_div_down(par, tvl, liability).unwrap()
+            } else {
+                par
+            }
+        }
+        Tranche::Asol => {
+            let nav = nav_asol_with_reserve(
+                state.tvl(),
+                state.liability(),
+                state.non_claimable(),
+                state.asol_supply,
+            )
+            .unwrap_or(0);
+            mul_div_down(balance, nav, SOL_PRECISION).unwrap()
+        }
+    }
+}
+
+#[test]
+fn property_wind_down_lets_every_solvent_holder_exit() {
+    const SEEDS: u64 = 40;
+    const WARMUP_STEPS: usize = 400;
+    const MAX_PASSES: usize = 20_000;
+
+    for seed in 1..=SEEDS {
+        let mut rng = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        let mut state = ModelState::seeded();
+        let mut holders = Holders::from_seeded(&state, &mut rng);
+
+        // Reach a random state with live holder balances.
+        for _ in 0..WARMUP_STEPS {
+            if xorshift64(&mut rng) % 23 == 0 {
+                state.sol_price_usd = rand_range(&mut rng, 40 * USD_PRECISION, 160 * USD_PRECISION);
+            }
+            if xorshift64(&mut rng) % 31 == 0 {
+                state.lst_to_sol_rate = rand_range(&mut rng, 900_000_000, 1_150_000_000);
+            }
+
+            let holder = rand_range(&mut rng, 1, (WIND_DOWN_HOLDERS - 1) as u64) as usize;
+            match xorshift64(&mut rng) % 4 {
+                0 => {
+                    let amt = rand_range(&mut rng, MIN_LST_DEPOSIT, 20 * SOL_PRECISION);
+                    if let Some(receipt) = model_mint_amusd(&mut state, Vault::Primary, amt) {
+                        holders.amusd[holder] += receipt.to_user;
+                        holders.amusd[TREASURY_HOLDER] += receipt.to_treasury;
+                    }
+                }
+                1 => {
+                    let amt = rand_range(&mut rng, 1, holders.amusd[holder].max(1));
+                    holders.redeem(&mut state, Tranche::AmUSD, holder, amt);
+                }
+                2 => {
+                    let amt = rand_range(&mut rng, MIN_LST_DEPOSIT, 20 * SOL_PRECISION);
+                    if let Some(receipt) = model_mint_asol(&mut state, Vault::Primary, amt) {
+                        holders.asol[holder] += receipt.to_user;
+                        holders.asol[TREASURY_HOLDER] += receipt.to_treasury;
+                    }
+                }
+                _ => {
+                    let amt = rand_range(&mut rng, 1, holders.asol[holder].max(1));
+                    holders.redeem(&mut state, Tranche::Asol, holder, amt);
+                }
+            }
+        }
+
+        // Wind-down: mints disabled, prices frozen, everyone exits in random order.
+        let started_insolvent = compute_cr_bps(state.tvl(), state.liability()) < BPS_PRECISION;
+
+        let mut order: Vec<(Tranche, usize)> = (0..WIND_DOWN_HOLDERS)
+            .flat_map(|h| [(Tranche::AmUSD, h), (Tranche::Asol, h)])
+            .collect();
+
+        let mut passes = 0;
+        loop {
+            assert!(passes < MAX_PASSES, "seed {seed}: wind-down did not terminate");
+            passes += 1;
+
+            for i in (1..order.len()).rev() {
+                let j = rand_range(&mut rng, 0, i as u64) as usize;
+                order.swap(i, j);
+            }
+
+            let mut progressed = false;
+            for &(tranche, holder) in &order {
+                if holders.exit_step(&mut state, tranche, holder) {
+                    progressed = true;
+                    let bound = derive_rounding_bound_lamports(3, 1, state.sol_price_usd).unwrap();
+                    assert_model_invariants(&state, bound);
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+
+        // Ledger conservation: holder balances always sum to supply.
+        assert_eq!(holders.amusd.iter().sum::<u64>(), state.amusd_supply, "seed {seed}");
+        assert_eq!(holders.asol.iter().sum::<u64>(), state.asol_supply, "seed {seed}");
+
+        // amUSD holders always exit (at par, or haircut-limited when insolvent).
+        for h in 0..WIND_DOWN_HOLDERS {
+            let residual = residual_value_lamports(&state, Tranche::AmUSD, holders.amusd[h]);
+            assert!(
+                residual <= WIND_DOWN_DUST_LAMPORTS,
+                "seed {seed}: amUSD holder {h} stranded with {residual} lamports of value"
+            );
+        }
+
+        if started_insolvent {
+            // aSOL is wiped out under insolvency; only amUSD exit is guaranteed.
+            continue;
+        }
+
+        let mut residual_total = 0u64;
+        for h in 0..WIND_DOWN_HOLDERS {
+            let residual = residual_value_lamports(&state, Tranche::Asol, holders.asol[h]);
+            assert!(
+                residual <= WIND_DOWN_DUST_LAMPORTS,
+                "seed {seed}: solvent aSOL holder {h} stranded with {residual} lamports of value"
+            );
+            residual_total += residual
+                + residual_value_lamports(&state, Tranche::AmUSD, holders.amusd[h]);
+        }
+
+        // Vault is empty apart from reserve backing and bounded residual dust.
+        let slack = derive_rounding_bound_lamports(3, 1, state.sol_price_usd).unwrap();
+        assert!(
+            state.tvl() <= state.rounding_reserve_lamports + residual_total + slack + WIND_DOWN_DUST_LAMPORTS,
+            "seed {seed}: vault retains {} lamports after wind-down (reserve {}, residual {})",
+            state.tvl(),
+            state.rounding_reserve_lamports,
+            residual_total
+        );
+    }
+}
+
+/// Signature shared by every fee-curve shape under property test.
+///
+/// (base_fee_bps, action, cr_bps, min_cr_bps, target_cr_bps,
+///  fee_min_multiplier_bps, fee_max_multiplier_bps,
+///  uncertainty_index_bps, uncertainty_max_bps) -> fee_bps
+type FeeCurveFn = fn(u64, FeeAction, u64, u64, u64, u64, u64, u64, u64) -> Option<u64>;
+
+/// Registry of fee-curve shapes. A new curve is only accepted once it is
+/// listed here and passes every monotonicity/continuity property below.
+const FEE_CURVES: &[(&str, FeeCurveFn)] = &[("compute_dynamic_fee_bps", compute_dynamic_fee_bps)];
+
+const ALL_FEE_ACTIONS: [FeeAction; 4] = [
+    FeeAction::AmusdMint,
+    FeeAction::AmUSDRedeem,
+    FeeAction::AsolMint,
+    FeeAction::AsolRedeem,
+];
+
+#[derive(Clone, Copy, Debug)]
+struct FeeParams {
+    base_fee_bps: u64,
+    min_cr_bps: u64,
+    target_cr_bps: u64,
+    fee_min_multiplier_bps: u64,
+    fee_max_multiplier_bps: u64,
+    uncertainty_max_bps: u64,
+}
+
+impl FeeParams {
+    fn random(rng: &mut u64) -> Self {
+        let min_cr_bps = rand_range(rng, BPS_PRECISION, 30_000);
+        let target_cr_bps = rand_range(rng, min_cr_bps + 1, min_cr_bps + 20_000);
+        Self {
+            base_fee_bps: rand_range(rng, 0, 1_000),
+            min_cr_bps,
+            target_cr_bps,
+            fee_min_multiplier_bps: rand_range(rng, 0, BPS_PRECISION),
+            fee_max_multiplier_bps: rand_range(rng, BPS_PRECISION, 40_000),
+            uncertainty_max_bps: rand_range(rng, BPS_PRECISION, 40_000),
+        }
+    }
+
+    fn fee(self, curve: FeeCurveFn, action: FeeAction, cr_bps: u64, unc_idx: u64) -> u64 {
+        curve(
+            self.base_fee_bps,
+            action,
+            cr_bps,
+            self.min_cr_bps,
+            self.target_cr_bps,
+            self.fee_min_multiplier_bps,
+            self.fee_max_multiplier_bps,
+            unc_idx,
+            self.uncertainty_max_bps,
+        )
+        .unwrap_or_else(|| panic!("valid params must price: {:?} cr={} unc={}", self, cr_bps, unc_idx))
+    }
+
+    /// Sorted CR sample covering 0, both clamp edges (+-1), random interior
+    /// points, far above target and the infinite-CR sentinel.
+    fn cr_samples(self, rng: &mut u64) -> Vec<u64> {
+        let mut crs = vec![
+            0,
+            self.min_cr_bps - 1,
+            self.min_cr_bps,
+            self.min_cr_bps + 1,
+            self.target_cr_bps - 1,
+            self.target_cr_bps,
+            self.target_cr_bps + 1,
+            self.target_cr_bps * 10,
+            u64::MAX,
+        ];
+        for _ in 0..32 {
+            crs.push(rand_range(rng, 0, self.target_cr_bps + 5_000));
+        }
+        crs.sort_unstable();
+        crs.dedup();
+        crs
+    }
+
+    /// Largest legitimate fee change for a 1-bps CR step (curve slope),
+    /// plus 1 bps attributable to flooring.
+    fn max_step_jump_bps(self) -> u64 {
+        let range = self.target_cr_bps - self.min_cr_bps;
+        let span = (self.fee_max_multiplier_bps - BPS_PRECISION)
+            .max(BPS_PRECISION - self.fee_min_multiplier_bps);
+        let cr_step = mul_div_up(span, 1, range).unwrap() + 1;
+        let total_step = mul_div_up(cr_step, self.uncertainty_max_bps, BPS_PRECISION).unwrap() + 1;
+        mul_div_down(self.base_fee_bps, total_step, BPS_PRECISION).unwrap() + 1
+    }
+}
+
+#[test]
+fn property_fee_is_monotone_in_cr() {
+    const CASES: u64 = 2_000;
+
+    for (name, curve) in FEE_CURVES {
+        let mut rng = 0xF00D_u64;
+        for _ in 0..CASES {
+            let params = FeeParams::random(&mut rng);
+            let unc_idx = rand_range(&mut rng, 0, 5_000);
+            let crs = params.cr_samples(&mut rng);
+
+            for action in ALL_FEE_ACTIONS {
+                for pair in crs.windows(2) {
+                    let lo = params.fee(*curve, action, pair[0], unc_idx);
+                    let hi = params.fee(*curve, action, pair[1], unc_idx);
+
+                    if action.is_risk_increasing() {
+                        assert!(hi <= lo, "{name}: {action:?} fee must be non-increasing in CR ({params:?}, cr {} -> {}, fee {lo} -> {hi})", pair[0], pair[1]);
+                    } else {
+                        assert!(hi >= lo, "{name}: {action:?} fee must be non-decreasing in CR ({params:?}, cr {} -> {}, fee {lo} -> {hi})", pair[0], pair[1]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn property_fee_uncertainty_response() {
+    const CASES: u64 = 2_000;
+
+    for (name, curve) in FEE_CURVES {
+        let mut rng = 0xBEEF_u64;
+        for _ in 0..CASES {
+            let params = FeeParams::random(&mut rng);
+            let crs = params.cr_samples(&mut rng);
+
+            let mut uncs: Vec<u64> = (0..16).map(|_| rand_range(&mut rng, 0, 5_000)).collect();
+            uncs.push(0);
+            uncs.sort_unstable();
+            uncs.dedup();
+
+            for action in ALL_FEE_ACTIONS {
+                for &cr in &crs {
+                    for pair in uncs.windows(2) {
+                        let lo = params.fee(*curve, action, cr, pair[0]);
+                        let hi = params.fee(*curve, action, cr, pair[1]);
+
+                        if action.is_risk_increasing() {
+                            assert!(hi >= lo, "{name}: {action:?} fee must be non-decreasing in uncertainty ({params:?}, cr {cr})");
+                        } else {
+                            assert_eq!(hi, lo, "{name}: {action:?} fee must ignore uncertainty ({params:?}, cr {cr})");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn property_fee_is_continuous_at_clamp_edges() {
+    const CASES: u64 = 2_000;
+
+    for (name, curve) in FEE_CURVES {
+        let mut rng = 0xC0FFEE_u64;
+        for _ in 0..CASES {
+            let params = FeeParams::random(&mut rng);
+            let unc_idx = rand_range(&mut rng, 0, 5_000);
+            let max_jump = params.max_step_jump_bps();
+
+            for action in ALL_FEE_ACTIONS {
+                for edge in [params.min_cr_bps, params.target_cr_bps] {
+                    let below = params.fee(*curve, action, edge - 1, unc_idx);
+                    let at = params.fee(*curve, action, edge, unc_idx);
+                    let above = params.fee(*curve, action, edge + 1, unc_idx);
+
+                    assert!(below.abs_diff(at) <= max_jump, "{name}: {action:?} jump below edge {edge} ({params:?}): {below} -> {at}");
+                    assert!(at.abs_diff(above) <= max_jump, "{name}: {action:?} jump above edge {edge} ({params:?}): {at} -> {above}");
+                }
+
+                // Plateaus: flat beyond target and below min.
+                assert_eq!(
+                    params.fee(*curve, action, params.target_cr_bps, unc_idx),
+                    params.fee(*curve, action, u64::MAX, unc_idx),
+                    "{name}: {action:?} must be flat from target to infinite CR ({params:?})"
+                );
+                assert_eq!(
+                    params.fee(*curve, action, params.min_cr_bps, unc_idx),
+                    params.fee(*curve, action, 0, unc_idx),
+                    "{name}: {action:?} must be flat below min CR ({params:?})"
+                );
+            }
+        }
+    }
+}
+
+/// Stability Pool accounting as the deposit/withdraw handlers run it.
+#[derive(Clone, Copy, Default)]
+struct PoolDepositor {
+    shares: u64,
+    snapshot: u128,
+    pending_lst: u64,
+}
+
+struct PoolModel {
+    total_amusd: u64,
+    total_shares: u64,
+    total_lst: u64,
+    gain_per_share: u128,
+    depositors: Vec<PoolDepositor>,
+}
+
+impl PoolModel {
+    fn settle(&mut self, i: usize) {
+        let d = &mut self.depositors[i];
+        d.pending_lst += compute_stability_gain(d.shares, self.gain_per_share, d.snapshot).unwrap();
+        d.snapshot = self.gain_per_share;
+    }
+
+    fn deposit(&mut self, i: usize, amount: u64) {
+        let shares = compute_stability_shares(amount, self.total_amusd, self.total_shares).unwrap();
+        if shares == 0 {
+            return;
+        }
+        self.settle(i);
+        self.depositors[i].shares += shares;
+        self.total_amusd += amount;
+        self.total_shares += shares;
+    }
+
+    fn withdraw(&mut self, i: usize, amount: u64) {
+        if amount == 0 || amount > self.total_amusd {
+            return;
+        }
+        let burn = compute_stability_shares_to_burn(amount, self.total_amusd, self.total_shares).unwrap();
+        if burn > self.depositors[i].shares {
+            return;
+        }
+        self.settle(i);
+        let d = &mut self.depositors[i];
+        d.shares -= burn;
+        self.total_lst -= d.pending_lst;
+        d.pending_lst = 0;
+        self.total_amusd -= amount;
+        self.total_shares -= burn;
+    }
+
+    /// Burn pool amUSD and credit LST, as a drawdown will.
+    fn absorb(&mut self, amusd_burned: u64, lst_gained: u64) {
+        if self.total_shares == 0 {
+            return;
+        }
+        self.total_amusd -= amusd_burned;
+        self.total_lst += lst_gained;
+        self.gain_per_share += compute_stability_gain_per_share(lst_gained, self.total_shares).unwrap();
+    }
+
+    fn withdrawable(&self, d: &PoolDepositor) -> (u64, u64) {
+        let amusd = compute_stability_share_value(d.shares, self.total_amusd, self.total_shares).unwrap();
+        let lst = d.pending_lst + compute_stability_gain(d.shares, self.gain_per_share, d.snapshot).unwrap();
+        (amusd, lst)
+    }
+}
+
+#[test]
+fn property_stability_pool_never_owes_more_than_it_holds() {
+    const SEEDS: u64 = 40;
+    const STEPS_PER_SEED: usize = 4_000;
+    const DEPOSITORS: usize = 5;
+
+    for seed in 1..=SEEDS {
+        let mut rng = seed.wrapping_mul(0xD1B5_4A32_D192_ED03);
+        let mut pool = PoolModel {
+            total_amusd: 0,
+            total_shares: 0,
+            total_lst: 0,
+            gain_per_share: 0,
+            depositors: vec![PoolDepositor::default(); DEPOSITORS],
+        };
+
+        for _ in 0..STEPS_PER_SEED {
+            let i = (xorshift64(&mut rng) % DEPOSITORS as u64) as usize;
+            match xorshift64(&mut rng) % 10 {
+                0..=4 => pool.deposit(i, rand_range(&mut rng, 1, 50_000 * USD_PRECISION)),
+                5..=8 => {
+                    let (owned, _) = pool.withdrawable(&pool.depositors[i]);
+                    let amount = rand_range(&mut rng, 1, owned.max(1));
+                    pool.withdraw(i, amount);
+                }
+                _ => {
+                    // Leave the pool non-empty so shares stay priceable.
+                    let burned = rand_range(&mut rng, 0, pool.total_amusd / 50);
+                    pool.absorb(burned, rand_range(&mut rng, 0, 100 * SOL_PRECISION));
+                }
+            }
+
+            let (amusd_owed, lst_owed) = pool.depositors.iter().fold((0u64, 0u64), |(a, l), d| {
+                let (da, dl) = pool.withdrawable(d);
+                (a + da, l + dl)
+            });
+            assert!(amusd_owed <= pool.total_amusd, "seed {seed}: amUSD owed {amusd_owed} > pool {}", pool.total_amusd);
+            assert!(lst_owed <= pool.total_lst, "seed {seed}: LST owed {lst_owed} > pool {}", pool.total_lst);
+            assert_eq!(pool.total_shares, pool.depositors.iter().map(|d| d.shares).sum::<u64>());
+        }
+    }
+}
+
+
+/// aSOL leg `redeem_pair` would size from the book `before` its amUSD leg and
+/// the book `mid` that leg left.
+fn cr_neutral_asol_leg(before: &ModelState, mid: &ModelState, vault: Vault) -> Option<u64> {
+    let collateral_weight_bps = match vault {
+        Vault::Primary => BPS_PRECISION,
+        Vault::Collateral => mid.collateral_weight_bps,
+    };
+    let nav = nav_asol_with_reserve(mid.tvl(), mid.liability(), mid.non_claimable(), mid.asol_supply)?;
+    compute_cr_neutral_redeem(
+        before.weighted_tvl(),
+        before.liability(),
+        mid.weighted_tvl(),
+        mid.liability(),
+        collateral_weight_bps,
+        nav,
+    )
+}
+
+#[test]
+fn vector_paired_exit_sizes_the_asol_leg_to_hold_cr() {
+    // Fees off so the legs move the book at par: 1575 SOL against 800 SOL of
+    // debt, aSOL at NAV 1.
+    let before = ModelState {
+        fee_amusd_redeem_bps: 0,
+        fee_asol_redeem_bps: 0,
+        ..ModelState::seeded()
+    };
+    let cr_before = compute_cr_bps(before.weighted_tvl(), before.liability());
+    assert_eq!(cr_before, 19_687);
+
+    // $10k of amUSD takes 100 SOL out: 1475 against 700.
+    let mut state = before;
+    let amusd_leg = model_redeem_amusd(&mut state, Vault::Primary, 10_000 * USD_PRECISION).unwrap();
+    assert_eq!(amusd_leg.to_user, 95_238_095_238);
+    assert_eq!(state.tvl(), 1_475 * SOL_PRECISION);
+
+    // Holding 1575/800 on 700 of debt needs 1378.125 SOL, so 96.875 aSOL goes.
+    let asol_amount = cr_neutral_asol_leg(&before, &state, Vault::Primary).unwrap();
+    assert_eq!(asol_amount, 96_875_000_000);
+    let asol_leg = model_redeem_asol(&mut state, Vault::Primary, asol_amount).unwrap();
+    assert_eq!(asol_leg.to_user, 92_261_904_761);
+
+    assert_eq!(compute_cr_bps(state.weighted_tvl(), state.liability()), cr_before);
+}
+
+#[test]
+fn property_paired_exit_passes_when_sequential_exits_do() {
+    const SEEDS: u64 = 100;
+    const CASES_PER_SEED: usize = 400;
+
+    let mut exercised = 0usize;
+    for seed in 1..=SEEDS {
+        let mut rng = seed;
+        let (seeded, two_vaults) = seeded_for(seed);
+
+        for _ in 0..CASES_PER_SEED {
+            let mut before = seeded;
+            before.sol_price_usd = rand_range(&mut rng, 60 * USD_PRECISION, 160 * USD_PRECISION);
+            before.uncertainty_index_bps = rand_range(&mut rng, 0, 1_000);
+            before.rounding_reserve_lamports = rand_range(&mut rng, 0, 1_000);
+            let vault = rand_vault(&mut rng, two_vaults);
+            let amusd_amount = rand_range(&mut rng, USD_PRECISION, 5_000 * USD_PRECISION);
+
+            // Each leg on its own, against the untouched book
+            let mut mid = before;
+            if model_redeem_amusd(&mut mid, vault, amusd_amount).is_none() {
+                continue;
+            }
+            let Some(asol_amount) = cr_neutral_asol_leg(&before, &mid, vault) else {
+                continue;
+            };
+            let mut alone = before;
+            if asol_amount == 0 || model_redeem_asol(&mut alone, vault, asol_amount).is_none() {
+                continue;
+            }
+
+            // The pair: the aSOL leg runs on the book the amUSD leg left
+            let mut paired = mid;
+            assert!(
+                model_redeem_asol(&mut paired, vault, asol_amount).is_some(),
+                "seed {seed}: paired exit failed where both legs pass alone ({amusd_amount} amUSD, {asol_amount} aSOL)"
+            );
+            let cr_before = compute_cr_bps(before.weighted_tvl(), before.liability());
+            let cr_after = compute_cr_bps(paired.weighted_tvl(), paired.liability());
+            assert!(
+                cr_before.abs_diff(cr_after) <= DEFAULT_PAIR_CR_TOLERANCE_BPS,
+                "seed {seed}: CR {cr_before} -> {cr_after}"
+            );
+            exercised += 1;
+        }
+    }
+    assert!(exercised > 1_000, "only {exercised} cases had both legs pass");
+}
+
+fn model_nav(state: &ModelState) -> u64 {
+    nav_asol_with_reserve(state.tvl(), state.liability(), state.non_claimable(), state.asol_supply).unwrap()
+}
+
+#[test]
+fn vector_amusd_to_asol_conversion_lifts_cr_and_keeps_nav() {
+    // 1575 SOL against 800 SOL of debt, aSOL at NAV 1, $100 SOL.
+    let mut state = ModelState::seeded();
+    let nav = model_nav(&state);
+    assert_eq!(nav, SOL_PRECISION);
+    let cr_before = compute_cr_bps(state.weighted_tvl(), state.liability());
+
+    // $10k of amUSD is 100 SOL of debt, so 100 aSOL before the 30 bps fee.
+    let asol_gross = compute_amusd_to_asol(10_000 * USD_PRECISION, state.sol_price_usd, nav).unwrap();
+    assert_eq!(asol_gross, 100 * SOL_PRECISION);
+    let (asol_net, fee) = apply_fee(asol_gross, 30).unwrap();
+    assert_eq!((asol_net, fee), (99_700_000_000, 300_000_000));
+    let (treasury_fee, insurance_fee) = split_fee(fee, 5_000).unwrap();
+    assert_eq!(treasury_fee + insurance_fee, fee);
+
+    state.amusd_supply -= 10_000 * USD_PRECISION;
+    state.asol_supply = state.asol_supply + asol_gross - insurance_fee;
+    state.insurance_fund_lamports += mul_div_down(insurance_fee, nav, SOL_PRECISION).unwrap();
+
+    // No LST moved: 1575 against 700.
+    assert_eq!(state.tvl(), 1_575 * SOL_PRECISION);
+    let cr_after = compute_cr_bps(state.weighted_tvl(), state.liability());
+    assert_eq!(cr_after, 22_500);
+    assert!(cr_after > cr_before);
+    assert_eq!(model_nav(&state), SOL_PRECISION);
+}
+
+#[test]
+fn vector_asol_to_amusd_conversion_respects_min_cr() {
+    let mut state = ModelState::seeded();
+    let nav = model_nav(&state);
+
+    // 100 aSOL at NAV 1 is 100 SOL, $10k of new debt: 1575 against 900.
+    let amusd_gross = compute_asol_to_amusd(100 * SOL_PRECISION, nav, state.sol_price_usd).unwrap();
+    assert_eq!(amusd_gross, 10_000 * USD_PRECISION);
+    let mut converted = state;
+    converted.asol_supply -= 100 * SOL_PRECISION;
+    converted.amusd_supply += amusd_gross;
+    let cr_after = compute_cr_bps(converted.weighted_tvl(), converted.liability());
+    assert_eq!(cr_after, 17_500);
+    assert_cr_above_minimum(cr_after, state.min_cr_bps).unwrap();
+    assert_eq!(model_nav(&converted), SOL_PRECISION);
+
+    // 500 aSOL would take debt to 1300 SOL, CR 12115 below the 13000 floor.
+    let amusd_gross = compute_asol_to_amusd(500 * SOL_PRECISION, nav, state.sol_price_usd).unwrap();
+    state.asol_supply -= 500 * SOL_PRECISION;
+    state.amusd_supply += amusd_gross;
+    let cr_after = compute_cr_bps(state.weighted_tvl(), state.liability());
+    assert_eq!(cr_after, 12_115);
+    assert!(assert_cr_above_minimum(cr_after, state.min_cr_bps).is_err());
+}
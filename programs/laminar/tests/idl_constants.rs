@@ -0,0 +1,66 @@
+//! The generated IDL carries the constants clients would otherwise hardcode.
+//! Builds the IDL the way `anchor idl build` does, so it only runs with:
+//!
+//!   cargo test -p laminar --features idl-build --test idl_constants
+
+#![cfg(feature = "idl-build")]
+
+use anchor_lang_idl::build::IdlBuilder;
+use anchor_lang_idl::types::IdlType;
+use laminar::constants::*;
+use laminar::state::{GLOBAL_STATE_SEED, REDEMPTION_TICKET_SEED, USER_POSITION_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED};
+
+#[test]
+fn idl_exports_protocol_constants() {
+    // The nested build gets its own target dir so it does not wait on this one's lock
+    let idl = IdlBuilder::new()
+        .program_path(env!("CARGO_MANIFEST_DIR").into())
+        .skip_lint(true)
+        .cargo_args(vec!["--target-dir".into(), format!("{}/idl-build", env!("CARGO_TARGET_TMPDIR"))])
+        .build()
+        .expect("IDL builds");
+    let constant = |name: &str| {
+        idl.constants
+            .iter()
+            .find(|constant| constant.name == name)
+            .unwrap_or_else(|| panic!("{name} missing from the IDL"))
+    };
+
+    for (name, value) in [
+        ("SOL_PRECISION", SOL_PRECISION),
+        ("USD_PRECISION", USD_PRECISION),
+        ("BPS_PRECISION", BPS_PRECISION),
+        ("MIN_LST_DEPOSIT", MIN_LST_DEPOSIT),
+        ("MIN_AMUSD_MINT", MIN_AMUSD_MINT),
+        ("MIN_ASOL_MINT", MIN_ASOL_MINT),
+        ("MIN_PROTOCOL_TVL", MIN_PROTOCOL_TVL),
+        ("AMUSD_MINT_FEE_BPS", AMUSD_MINT_FEE_BPS),
+        ("AMUSD_REDEEM_FEE_BPS", AMUSD_REDEEM_FEE_BPS),
+        ("ASOL_MINT_FEE_BPS", ASOL_MINT_FEE_BPS),
+        ("ASOL_REDEEM_FEE_BPS", ASOL_REDEEM_FEE_BPS),
+        ("DEFAULT_MIN_CR_BPS", DEFAULT_MIN_CR_BPS),
+        ("DEFAULT_TARGET_CR_BPS", DEFAULT_TARGET_CR_BPS),
+        ("MAX_FEE_MULTIPLIER_BPS", MAX_FEE_MULTIPLIER_BPS),
+        ("REDEEM_ALL", REDEEM_ALL),
+    ] {
+        let exported = constant(name);
+        assert_eq!(exported.ty, IdlType::U64, "{name}");
+        assert_eq!(exported.value, value.to_string(), "{name}");
+    }
+    assert_eq!(constant("AMUSD_DECIMALS").ty, IdlType::U8);
+    assert_eq!(constant("AMUSD_DECIMALS").value, AMUSD_DECIMALS.to_string());
+    assert_eq!(constant("CURRENT_ARGS_VERSION").value, CURRENT_ARGS_VERSION.to_string());
+
+    // Seeds come through as byte arrays
+    for (name, seed) in [
+        ("GLOBAL_STATE_SEED", GLOBAL_STATE_SEED),
+        ("VAULT_SEED", VAULT_SEED),
+        ("VAULT_AUTHORITY_SEED", VAULT_AUTHORITY_SEED),
+        ("USER_POSITION_SEED", USER_POSITION_SEED),
+        ("REDEMPTION_TICKET_SEED", REDEMPTION_TICKET_SEED),
+    ] {
+        let exported = constant(name);
+        assert_eq!(exported.ty, IdlType::Bytes, "{name}");
+        assert_eq!(exported.value, format!("{seed:?}"), "{name}");
+    }
+}
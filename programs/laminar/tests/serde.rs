@@ -0,0 +1,99 @@
+//! JSON round trips for the serde-enabled state and event types.
+//!
+//!   cargo test -p laminar --features serde --test serde
+
+#![cfg(feature = "serde")]
+
+use anchor_lang::prelude::{borsh, Pubkey};
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use laminar::events::{ParameterUpdateQueued, ProtocolInitialized};
+use laminar::state::{CollateralVault, GlobalState, ParamChange};
+use serde_json::json;
+
+fn zeroed<T: AnchorDeserialize>(len: usize) -> T {
+    T::try_from_slice(&vec![0u8; len - 8]).unwrap()
+}
+
+/// struct -> JSON -> struct, compared on the Borsh bytes since the account
+/// and event types carry no `PartialEq`
+fn round_trip<T>(value: &T) -> serde_json::Value
+where
+    T: AnchorSerialize + serde::Serialize + serde::de::DeserializeOwned,
+{
+    let encoded = serde_json::to_value(value).unwrap();
+    let decoded: T = serde_json::from_value(encoded.clone()).unwrap();
+    assert_eq!(borsh::to_vec(&decoded).unwrap(), borsh::to_vec(value).unwrap());
+    encoded
+}
+
+#[test]
+fn global_state_round_trips_with_base58_pubkeys() {
+    let mut state: GlobalState = zeroed(GlobalState::LEN);
+    state.authority = Pubkey::new_unique();
+    state.amusd_mint = Pubkey::new_unique();
+    state.total_lst_amount = 1_000_000_000;
+    state.amusd_supply = u64::MAX;
+    state.mint_paused = true;
+
+    let json = round_trip(&state);
+    assert_eq!(json["authority"], json!(state.authority.to_string()));
+    assert_eq!(json["amusd_mint"], json!(state.amusd_mint.to_string()));
+    assert_eq!(json["treasury"], json!(Pubkey::default().to_string()));
+    assert_eq!(json["total_lst_amount"], json!(1_000_000_000u64));
+    assert_eq!(json["amusd_supply"], json!(u64::MAX));
+    assert_eq!(json["mint_paused"], json!(true));
+    // Padding stays out of the JSON
+    assert!(json.get("_reserved").is_none());
+}
+
+#[test]
+fn collateral_vault_round_trips() {
+    let mut vault: CollateralVault = zeroed(CollateralVault::LEN);
+    vault.lst_mint = Pubkey::new_unique();
+    vault.lst_amount = 42;
+
+    let json = round_trip(&vault);
+    assert_eq!(json["lst_mint"], json!(vault.lst_mint.to_string()));
+    assert_eq!(json["lst_amount"], json!(42));
+}
+
+#[test]
+fn events_round_trip_with_snake_case_fields() {
+    let initialized = ProtocolInitialized {
+        authority: Pubkey::new_unique(),
+        amusd_mint: Pubkey::new_unique(),
+        asol_mint: Pubkey::new_unique(),
+        supported_lst_mint: Pubkey::new_unique(),
+        min_cr_bps: 13_000,
+        target_cr_bps: 15_000,
+        operation_counter: 0,
+        timestamp: 1_700_000_000,
+    };
+    let json = round_trip(&initialized);
+    assert_eq!(
+        json,
+        json!({
+            "authority": initialized.authority.to_string(),
+            "amusd_mint": initialized.amusd_mint.to_string(),
+            "asol_mint": initialized.asol_mint.to_string(),
+            "supported_lst_mint": initialized.supported_lst_mint.to_string(),
+            "min_cr_bps": 13_000,
+            "target_cr_bps": 15_000,
+            "operation_counter": 0,
+            "timestamp": 1_700_000_000,
+        })
+    );
+
+    let pyth_price_feed = Pubkey::new_unique();
+    let queued = ParameterUpdateQueued {
+        authority: Pubkey::new_unique(),
+        change: ParamChange::PriceFeed { pyth_price_feed },
+        queued_slot: 100,
+        eta_slot: 250,
+        operation_counter: 7,
+        timestamp: 1_700_000_000,
+    };
+    let json = round_trip(&queued);
+    assert_eq!(json["change"], json!({ "price_feed": { "pyth_price_feed": pyth_price_feed.to_string() } }));
+    assert_eq!(json["eta_slot"], json!(250));
+}
@@ -22,6 +22,8 @@ import {
   mintTo,
   getAccount,
   getMint,
+  freezeAccount,
+  thawAccount,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
@@ -45,6 +47,9 @@ const AMUSD_REDEEM_FEE_BPS = 25;  // 0.25%
 const ASOL_MINT_FEE_BPS = 30;     // 0.3%
 const ASOL_REDEEM_FEE_BPS = 15;   // 0.15%
 
+// anchor_lang::event::EVENT_IX_TAG_LE, the prefix of every emit_cpi! payload
+const EVENT_IX_TAG_LE = Buffer.from("e445a52e51cb9a1d", "hex");
+
 const program = anchor.workspace.Laminar as Program<Laminar>;
 const cpiTester = anchor.workspace.CpiTester as Program<CpiTester>;
 
@@ -95,9 +100,129 @@ interface GlobalStateData {
   maxConfBps: BN;
   maxLstStaleEpochs: BN;
 
+  navFloorLamports: BN;
+  maxAsolMintPerRound: BN;
+
   lastTvlUpdateSlot: BN;
   lastOracleUpdateSlot: BN;
   mockOracleConfidenceUsd: BN;
+  treasuryOwnerProgram: PublicKey;
+  pendingAuthority: PublicKey;
+  pauseStartedSlot: BN;
+  maxPauseDurationSlots: BN;
+  paramTimelockSlots: BN;
+  lstMintHasFreezeAuthority: boolean;
+  redeemPausedByVaultFreeze: boolean;
+  pythPriceFeed: PublicKey;
+  switchboardFeed: PublicKey;
+  lstStakePool: PublicKey;
+  marinadeState: PublicKey;
+  lstCalculatorProgram: PublicKey;
+  lstCalculatorState: PublicKey;
+  cumulativeHaircutShortfallLamports: BN;
+  episodeHaircutShortfallLamports: BN;
+  maxEpisodeHaircutLamports: BN;
+  amusdRedeemPausedByHaircut: boolean;
+  priceSource: number;
+  secondaryPriceSource: number;
+  maxOracleDeviationBps: BN;
+  maxPriceMoveBps: BN;
+  minSolPriceUsd: BN;
+  maxSolPriceUsd: BN;
+  minLstRate: BN;
+  maxLstRate: BN;
+  lastOracleUpdateTimestamp: BN;
+  maxOracleAgeSeconds: BN;
+  fallbackPriceSource: number;
+  fallbackUncertaintyBps: BN;
+  emaSolPriceUsd: BN;
+  emaHalfLifeSlots: BN;
+  lastEmaUpdateSlot: BN;
+  stabilityPoolAmusd: BN;
+  rebalanceDiscountBps: BN;
+  recoveryMode: boolean;
+  recoveryModeEnteredSlot: BN;
+  recoveryBonusBps: BN;
+  settled: boolean;
+  settlementSlot: BN;
+  settlementSolPriceUsd: BN;
+  settlementLstToSolRate: BN;
+  insuranceFundLamports: BN;
+  feeSplitInsuranceBps: BN;
+  redemptionRateBps: BN;
+  flashOutstandingLamports: BN;
+  maxFlashLoanBps: BN;
+  flashLoanUtilizationSlot: BN;
+  collateralTvlLamports: BN;
+  collateralTypeCount: BN;
+  deprecatedCollateralTvlLamports: BN;
+  weightedCollateralTvlLamports: BN;
+  psmUsdcMint: PublicKey;
+  psmFeeBps: BN;
+  psmDebtCeiling: BN;
+  psmAmusdOutstanding: BN;
+  savingsFeeSplitBps: BN;
+  stabilityFeeBpsPerYear: BN;
+  lastAccrualTimestamp: BN;
+  amusdSupplyCap: BN;
+  asolSupplyCap: BN;
+  maxTotalLstAmount: BN;
+  maxSingleDepositLst: BN;
+  maxSingleRedeemUnits: BN;
+  perUserAmusdCap: BN;
+  mintRoundSlots: BN;
+  currentRoundStartSlot: BN;
+  asolMintedThisRound: BN;
+  minActionGapSlots: BN;
+  asolRedeemCooldownSlots: BN;
+  nextRedemptionTicket: BN;
+  redemptionQueueHead: BN;
+  pairCrToleranceBps: BN;
+}
+
+interface LaunchConfig {
+  minCrBps: BN;
+  targetCrBps: BN;
+  feeAmusdMintBps: BN;
+  feeAmusdRedeemBps: BN;
+  feeAsolMintBps: BN;
+  feeAsolRedeemBps: BN;
+  feeMinMultiplierBps: BN;
+  feeMaxMultiplierBps: BN;
+  maxOracleStalenessSlots: BN;
+  maxConfBps: BN;
+  uncertaintyMaxBps: BN;
+  maxLstStaleEpochs: BN;
+  maxOracleDeviationBps: BN;
+  maxPriceMoveBps: BN;
+  fallbackUncertaintyBps: BN;
+  emaHalfLifeSlots: BN;
+  maxRoundingReserveLamports: BN;
+  navFloorLamports: BN;
+  maxAsolMintPerRound: BN;
+  mintRoundSlots: BN;
+  amusdSupplyCap: BN;
+  asolSupplyCap: BN;
+  maxTotalLstAmount: BN;
+  maxSingleDepositLst: BN;
+  maxSingleRedeemUnits: BN;
+  perUserAmusdCap: BN;
+  feeSplitInsuranceBps: BN;
+  savingsFeeSplitBps: BN;
+  stabilityFeeBpsPerYear: BN;
+  minActionGapSlots: BN;
+  asolRedeemCooldownSlots: BN;
+  paramTimelockSlots: BN;
+  maxPauseDurationSlots: BN;
+  rebalanceDiscountBps: BN;
+  recoveryBonusBps: BN;
+  maxFlashLoanBps: BN;
+  psmFeeBps: BN;
+  psmDebtCeiling: BN;
+  pairCrToleranceBps: BN;
+  treasury: PublicKey;
+  treasuryIsPda: boolean;
+  treasuryOwnerProgram: PublicKey;
 }
 
 
@@ -356,11 +481,12 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
     const authority = Keypair.generate();
     await airdropSol(authority.publicKey, 10);
 
+    // The test authority doubles as the LST freeze authority so vault freezes can be simulated.
     const lstMint = await createMint(
       connection,
       authority,
       authority.publicKey,
-      null,
+      authority.publicKey,
       9,
       undefined,
       undefined,
@@ -644,9 +770,10 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
     newSolPriceUsd: BN,
     newLstToSolRate: BN,
     newOracleConfidenceUsd: BN = new BN(0),
+    force: boolean = false,
   ): Promise<string> {
     return await program.methods
-      .updateMockPrices(newSolPriceUsd, newLstToSolRate, newOracleConfidenceUsd)
+      .updateMockPrices(newSolPriceUsd, newLstToSolRate, newOracleConfidenceUsd, force)
       .accounts({
         authority: protocolState.authority.publicKey,
         globalState: protocolState.globalState,
@@ -658,412 +785,1861 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
 
 
   /**
-   * Calculate expected CR from state
+   * Set both pause flags (admin only)
    */
-  async function calculateCR(): Promise<BN> {
-    const state = await getGlobalState();
-    const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-    const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-    return computeCrBps(tvl, liability);
+  async function setPause(mintPaused: boolean, redeemPaused: boolean): Promise<string> {
+    return await program.methods
+      .emergencyPause(mintPaused, redeemPaused)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([protocolState.authority])
+      .rpc();
   }
 
   /**
- * Sends a tiny transfer tx to force local validator activity.
- * Localnet often stops slot production while idle; this "ping"
- * advances slots deterministically for staleness tests.
- */
-  async function sendSlotPingTx(): Promise<void> {
-    const tx = new Transaction().add(
-      SystemProgram.transfer({
-        fromPubkey: protocolState.authority.publicKey,
-        toPubkey: protocolState.globalState,
-        lamports: 1,
-      })
-    );
-
-    try {
-      await provider.sendAndConfirm(tx, [protocolState.authority], {
-        commitment: "processed",
-        skipPreflight: true,
-      });
-    } catch { }
+   * Launch configuration matching the initialize defaults
+   */
+  function defaultLaunchConfig(): LaunchConfig {
+    return {
+      minCrBps: MIN_CR_BPS,
+      targetCrBps: TARGET_CR_BPS,
+      feeAmusdMintBps: new BN(AMUSD_MINT_FEE_BPS),
+      feeAmusdRedeemBps: new BN(AMUSD_REDEEM_FEE_BPS),
+      feeAsolMintBps: new BN(ASOL_MINT_FEE_BPS),
+      feeAsolRedeemBps: new BN(ASOL_REDEEM_FEE_BPS),
+      feeMinMultiplierBps: FEE_MIN_MULTIPLIER_BPS,
+      feeMaxMultiplierBps: FEE_MAX_MULTIPLIER_BPS,
+      maxOracleStalenessSlots: new BN(150),
+      maxConfBps: new BN(150),
+      uncertaintyMaxBps: UNCERTAINTY_MAX_BPS,
+      maxLstStaleEpochs: new BN(1),
+      maxOracleDeviationBps: new BN(100),
+      maxPriceMoveBps: new BN(0),
+      fallbackUncertaintyBps: new BN(0),
+      emaHalfLifeSlots: new BN(0),
+      maxRoundingReserveLamports: new BN(10_000),
+      navFloorLamports: new BN(1_000_000),
+      maxAsolMintPerRound: new BN(50_000).mul(SOL_PRECISION),
+      mintRoundSlots: new BN(9_000),
+      amusdSupplyCap: new BN(0),
+      asolSupplyCap: new BN(0),
+      maxTotalLstAmount: new BN(0),
+      maxSingleDepositLst: new BN(0),
+      maxSingleRedeemUnits: new BN(0),
+      perUserAmusdCap: new BN(0),
+      feeSplitInsuranceBps: new BN(0),
+      savingsFeeSplitBps: new BN(0),
+      stabilityFeeBpsPerYear: new BN(0),
+      minActionGapSlots: new BN(0),
+      asolRedeemCooldownSlots: new BN(0),
+      paramTimelockSlots: new BN(0),
+      maxPauseDurationSlots: new BN(1_512_000),
+      rebalanceDiscountBps: new BN(100),
+      recoveryBonusBps: new BN(0),
+      maxFlashLoanBps: new BN(5_000),
+      psmFeeBps: new BN(0),
+      psmDebtCeiling: new BN(0),
+      pairCrToleranceBps: new BN(50),
+      treasury: protocolState.authority.publicKey,
+      treasuryIsPda: false,
+      treasuryOwnerProgram: PublicKey.default,
+    };
   }
 
   /**
-   * Waits until the chain advances by `delta` slots.
-   *
-   * Uses active tx pings so tests do not hang on idle localnet.
-   * This is required for reliable oracle/LST staleness vectors.
+   * Apply launch configuration (admin only, pre-launch)
    */
-  async function waitForSlotDelta(delta: number, timeoutMs = 180_000): Promise<void> {
-    const start = await connection.getSlot("processed");
-    const target = start + delta;
-    const startedAt = Date.now();
-
-    while (true) {
-      const now = await connection.getSlot("processed");
-      if (now >= target) return;
-      if (Date.now() - startedAt > timeoutMs) {
-        throw new Error(`Timed out waiting for slot ${target}, current=${now}`);
-      }
+  async function configureLaunch(config: LaunchConfig, extraSigners: Keypair[] = []): Promise<string> {
+    const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.amusdMint.publicKey,
+      owner: config.treasury,
+    });
+    const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.asolMint.publicKey,
+      owner: config.treasury,
+    });
 
-      const remaining = target - now;
-      const burst = Math.min(Math.max(1, remaining), 6);
-      for (let i = 0; i < burst; i++) await sendSlotPingTx();
-      await new Promise((r) => setTimeout(r, 25));
-    }
+    return await program.methods
+      .configureLaunch(config as any)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        treasury: config.treasury,
+        amusdMint: protocolState.amusdMint.publicKey,
+        asolMint: protocolState.asolMint.publicKey,
+        treasuryAmusdAccount,
+        treasuryAsolAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority, ...extraSigners])
+      .rpc();
   }
 
   /**
- * Explicitly refreshes LST exchange-rate cache metadata on-chain.
- * Useful for tests that assert stale -> refresh -> success flows.
- */
-  async function syncExchangeRate(): Promise<string> {
+   * Update base fees and fee multiplier bounds (admin only)
+   */
+  async function updateFeeParameters(
+    amusdMintBps: BN,
+    amusdRedeemBps: BN,
+    asolMintBps: BN,
+    asolRedeemBps: BN,
+    minMultiplierBps: BN,
+    maxMultiplierBps: BN,
+  ): Promise<string> {
     return await program.methods
-      .syncExchangeRate()
+      .updateFeeParameters(
+        amusdMintBps,
+        amusdRedeemBps,
+        asolMintBps,
+        asolRedeemBps,
+        minMultiplierBps,
+        maxMultiplierBps
+      )
       .accounts({
+        authority: protocolState.authority.publicKey,
         globalState: protocolState.globalState,
         clock: SYSVAR_CLOCK_PUBKEY,
       } as any)
+      .signers([protocolState.authority])
       .rpc();
   }
 
+  async function restoreDefaultFees(): Promise<string> {
+    return await updateFeeParameters(
+      new BN(AMUSD_MINT_FEE_BPS),
+      new BN(AMUSD_REDEEM_FEE_BPS),
+      new BN(ASOL_MINT_FEE_BPS),
+      new BN(ASOL_REDEEM_FEE_BPS),
+      FEE_MIN_MULTIPLIER_BPS,
+      FEE_MAX_MULTIPLIER_BPS
+    );
+  }
+
   /**
- * Reset oracle and LST freshness to a known-good baseline.
- *
- * This should be called at the start of tests that expect deterministic
- * error types so stale state from prior tests does not leak in.
- */
-  async function resetAndSyncSnapshots(): Promise<void> {
-    await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
-    await syncExchangeRate();
+   * Rotate the fee treasury (admin only). Wallet treasuries must co-sign.
+   */
+  async function setTreasury(
+    newTreasury: PublicKey,
+    treasuryIsPda: boolean,
+    treasuryOwnerProgram: PublicKey,
+    extraSigners: Keypair[] = [],
+  ): Promise<string> {
+    const newTreasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.amusdMint.publicKey,
+      owner: newTreasury,
+    });
+    const newTreasuryAsolAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.asolMint.publicKey,
+      owner: newTreasury,
+    });
+
+    return await program.methods
+      .setTreasury(treasuryIsPda, treasuryOwnerProgram)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        newTreasury,
+        amusdMint: protocolState.amusdMint.publicKey,
+        asolMint: protocolState.asolMint.publicKey,
+        newTreasuryAmusdAccount,
+        newTreasuryAsolAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority, ...extraSigners])
+      .rpc();
   }
 
+  /**
+   * Update oracle risk bounds (admin only)
+   */
+  async function updateOracleParameters(
+    maxOracleStalenessSlots: BN,
+    maxConfBps: BN,
+    uncertaintyMaxBps: BN,
+    maxLstStaleEpochs: BN,
+  ): Promise<string> {
+    return await program.methods
+      .updateOracleParameters(maxOracleStalenessSlots, maxConfBps, uncertaintyMaxBps, maxLstStaleEpochs)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
 
-  async function getTokenAmountOrZero(address: PublicKey): Promise<BN> {
-    try {
-      const acc = await getAccount(connection, address);
-      return new BN(acc.amount.toString());
-    } catch {
-      return new BN(0);
-    }
+  async function restoreDefaultOracleParameters(): Promise<string> {
+    return await updateOracleParameters(new BN(150), new BN(150), UNCERTAINTY_MAX_BPS, new BN(1));
   }
 
+  /**
+   * Mint amUSD through the versioned-args entrypoint
+   */
+  async function mintAmUSDV2(
+    user: Keypair,
+    userLstAccount: PublicKey,
+    userAmusdAccount: PublicKey,
+    lstAmount: BN,
+    minAmusdOut: BN,
+    argsVersion = 1,
+    clientOrderId = new BN(0),
+  ): Promise<string> {
+    const state = await getGlobalState();
+    const [vaultAuthority] = getVaultAuthorityPda();
 
+    const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.amusdMint.publicKey,
+      owner: state.treasury,
+    });
 
-  before(async () => {
-    protocolState = await initializeProtocol();
-    console.log("Protocol initialized!");
-    console.log("  GlobalState:", protocolState.globalState.toBase58());
-    console.log("  amUSD Mint:", protocolState.amusdMint.publicKey.toBase58());
-    console.log("  aSOL Mint:", protocolState.asolMint.publicKey.toBase58());
-    console.log("  LST Mint:", protocolState.lstMint.toBase58());
-    console.log("  Vault:", protocolState.vault.toBase58());
-  });
+    return await program.methods
+      .mintAmusdV2({ argsVersion, lstAmount, minAmusdOut, deadlineSlot: new BN(0), maxFeeBps: new BN(0), clientOrderId } as any)
+      .accounts({
+        user: user.publicKey,
+        globalState: protocolState.globalState,
+        amusdMint: protocolState.amusdMint.publicKey,
+        userAmusdAccount: userAmusdAccount,
+        treasuryAmusdAccount: treasuryAmusdAccount,
+        treasury: state.treasury,
+        userLstAccount: userLstAccount,
+        vault: protocolState.vault,
+        vaultAuthority: vaultAuthority,
+        lstMint: protocolState.lstMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([user])
+      .rpc();
+  }
 
-  describe("1. Protocol Initialization", () => {
-    it("Initializes protocol with correct parameters", async () => {
-      const state = await getGlobalState();
+  /**
+   * Redeem amUSD through the versioned-args entrypoint
+   */
+  async function redeemAmUSDV2(
+    user: Keypair,
+    userLstAccount: PublicKey,
+    userAmusdAccount: PublicKey,
+    amusdAmount: BN,
+    minLstOut: BN,
+    argsVersion = 1,
+    allowPartial = false,
+    clientOrderId = new BN(0),
+  ): Promise<string> {
+    const state = await getGlobalState();
+    const [vaultAuthority] = getVaultAuthorityPda();
 
-      expect(state.version).to.equal(1);
-      expect(state.minCrBps.toNumber()).to.equal(MIN_CR_BPS.toNumber());
-      expect(state.targetCrBps.toNumber()).to.equal(TARGET_CR_BPS.toNumber());
-      expect(state.mockSolPriceUsd.toNumber()).to.equal(MOCK_SOL_PRICE_USD.toNumber());
-      expect(state.mockLstToSolRate.toNumber()).to.equal(MOCK_LST_TO_SOL_RATE.toNumber());
-      expect(state.totalLstAmount.toNumber()).to.equal(0);
-      expect(state.amusdSupply.toNumber()).to.equal(0);
-      expect(state.asolSupply.toNumber()).to.equal(0);
-      expect(state.mintPaused).to.be.false;
-      expect(state.redeemPaused).to.be.false;
+    const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+      mint: protocolState.amusdMint.publicKey,
+      owner: state.treasury,
     });
 
-    it("Sets correct mint addresses", async () => {
-      const state = await getGlobalState();
+    return await program.methods
+      .redeemAmusdV2({ argsVersion, amusdAmount, minLstOut, allowPartial, deadlineSlot: new BN(0), maxFeeBps: new BN(0), clientOrderId } as any)
+      .accounts({
+        user: user.publicKey,
+        globalState: protocolState.globalState,
+        amusdMint: protocolState.amusdMint.publicKey,
+        userAmusdAccount: userAmusdAccount,
+        treasury: state.treasury,
+        treasuryAmusdAccount: treasuryAmusdAccount,
+        userLstAccount: userLstAccount,
+        vault: protocolState.vault,
+        vaultAuthority: vaultAuthority,
+        lstMint: protocolState.lstMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([user])
+      .rpc();
+  }
 
-      expect(state.amusdMint.toBase58()).to.equal(protocolState.amusdMint.publicKey.toBase58());
-      expect(state.asolMint.toBase58()).to.equal(protocolState.asolMint.publicKey.toBase58());
-      expect(state.supportedLstMint.toBase58()).to.equal(protocolState.lstMint.toBase58());
+  /**
+   * Decode Laminar events emitted by a confirmed transaction.
+   * Events travel as self-CPIs (`emit_cpi!`), so they are read from the
+   * inner-instruction data rather than the (truncatable) logs.
+   */
+  async function getEmittedEvents(signature: string): Promise<anchor.Event[]> {
+    await connection.confirmTransaction(signature, "confirmed");
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
     });
-
-    it("Sets treasury to authority on initialization", async () => {
-      const state = await getGlobalState();
-      expect(state.treasury.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
+    if (!tx?.meta?.innerInstructions) {
+      return [];
+    }
+    const keys = tx.transaction.message.getAccountKeys({
+      accountKeysFromLookups: tx.meta.loadedAddresses,
     });
-  });
-
+    const events: anchor.Event[] = [];
+    for (const inner of tx.meta.innerInstructions) {
+      for (const ix of inner.instructions) {
+        if (!keys.get(ix.programIdIndex)?.equals(program.programId)) {
+          continue;
+        }
+        const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+        if (!data.subarray(0, 8).equals(EVENT_IX_TAG_LE)) {
+          continue;
+        }
+        const event = program.coder.events.decode(data.subarray(8).toString("base64"));
+        if (event) {
+          events.push(event);
+        }
+      }
+    }
+    return events;
+  }
+
+  /**
+   * Update the rounding reserve cap (admin only)
+   */
+  async function updateRoundingReserveCap(newCap: BN): Promise<string> {
+    return await program.methods
+      .updateRoundingReserveCap(newCap)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
+
+  /**
+   * Propose a new admin authority (step 1 of 2)
+   */
+  async function proposeAuthority(currentAuthority: Keypair, newAuthority: PublicKey): Promise<string> {
+    return await program.methods
+      .proposeAuthority(newAuthority)
+      .accounts({
+        authority: currentAuthority.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([currentAuthority])
+      .rpc();
+  }
+
+  /**
+   * Accept a proposed admin authority (step 2 of 2)
+   */
+  async function acceptAuthority(pendingAuthority: Keypair): Promise<string> {
+    return await program.methods
+      .acceptAuthority()
+      .accounts({
+        pendingAuthority: pendingAuthority.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([pendingAuthority])
+      .rpc();
+  }
+
+  /**
+   * Burn a dust balance into the rounding reserve
+   */
+  async function donateDust(
+    user: Keypair,
+    token: "amUsd" | "asol",
+    tokenMint: PublicKey,
+    userTokenAccount: PublicKey,
+    amount: BN,
+  ): Promise<string> {
+    return await program.methods
+      .donateDust({ [token]: {} } as any, amount)
+      .accounts({
+        user: user.publicKey,
+        globalState: protocolState.globalState,
+        tokenMint,
+        userTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([user])
+      .rpc();
+  }
+
+  /**
+   * Lift a redeem pause that outlived the timeout (any signer)
+   */
+  async function forceUnpauseRedeem(caller: Keypair): Promise<string> {
+    return await program.methods
+      .forceUnpauseRedeem()
+      .accounts({
+        caller: caller.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([caller])
+      .rpc();
+  }
+
+  /**
+   * Run the permissionless health check
+   */
+  async function healthCheck(caller: Keypair): Promise<string> {
+    return await program.methods
+      .healthCheck()
+      .accounts({
+        caller: caller.publicKey,
+        globalState: protocolState.globalState,
+        vault: protocolState.vault,
+        vaultAuthority: protocolState.vaultAuthority,
+        lstMint: protocolState.lstMint,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([caller])
+      .rpc();
+  }
+
+  /**
+   * Point SOL/USD pricing at a Pyth feed (admin only)
+   */
+  async function setPythPriceFeed(feed: PublicKey, signer: Keypair = protocolState.authority): Promise<string> {
+    return await program.methods
+      .setPythPriceFeed(feed)
+      .accounts({
+        authority: signer.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Select the SOL/USD price source among configured feeds (admin only)
+   */
+  async function setPriceSource(priceSource: number, signer: Keypair = protocolState.authority): Promise<string> {
+    return await program.methods
+      .setPriceSource(priceSource)
+      .accounts({
+        authority: signer.publicKey,
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Validate GlobalState after an upgrade (admin only)
+   */
+  async function migrateGlobalState(signer: Keypair = protocolState.authority): Promise<string> {
+    return await program.methods
+      .migrateGlobalState()
+      .accounts({
+        authority: signer.publicKey,
+        globalState: protocolState.globalState,
+        vaultAuthority: protocolState.vaultAuthority,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([signer])
+      .rpc();
+  }
+
+  async function feeCurve(action: number): Promise<{ action: number; points: { crBps: BN; feeBps: BN }[] }> {
+    return await program.methods
+      .feeCurve(action)
+      .accounts({ globalState: protocolState.globalState } as any)
+      .view();
+  }
+
+  /**
+   * Update the redeem pause timeout
+   */
+  async function updateMaxPauseDuration(slots: BN, pendingParamChange: PublicKey | null = null): Promise<string> {
+    return await program.methods
+      .updateMaxPauseDuration(slots)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        pendingParamChange,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
+
+  /**
+   * Update the LST TVL cap (0 = unlimited), optionally applying its queued change
+   */
+  async function updateTvlCap(cap: BN, pendingParamChange: PublicKey | null = null): Promise<string> {
+    return await program.methods
+      .updateTvlCap(cap)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        pendingParamChange,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
+
+  /**
+   * PendingParamChange PDA for a change kind (0 risk, 1 fees, 2 oracle, 3 timelock, ...)
+   */
+  function pendingParamChangePda(kind: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_param_change"), Buffer.from([kind])],
+      program.programId
+    )[0];
+  }
+
+  /**
+   * Queue a timelocked parameter change
+   */
+  async function queueParameterUpdate(change: any, kind: number): Promise<string> {
+    return await program.methods
+      .queueParameterUpdate(change)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        pendingParamChange: pendingParamChangePda(kind),
+        systemProgram: SystemProgram.programId,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
+
+  /**
+   * Execute a queued parameter change (any signer)
+   */
+  async function executeParameterUpdate(executor: Keypair, kind: number): Promise<string> {
+    return await program.methods
+      .executeParameterUpdate(kind)
+      .accounts({
+        executor: executor.publicKey,
+        globalState: protocolState.globalState,
+        pendingParamChange: pendingParamChangePda(kind),
+        proposer: protocolState.authority.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([executor])
+      .rpc();
+  }
+
+  /**
+   * Cancel a queued parameter change
+   */
+  async function cancelParameterUpdate(kind: number): Promise<string> {
+    return await program.methods
+      .cancelParameterUpdate(kind)
+      .accounts({
+        authority: protocolState.authority.publicKey,
+        globalState: protocolState.globalState,
+        pendingParamChange: pendingParamChangePda(kind),
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .signers([protocolState.authority])
+      .rpc();
+  }
+
+  /**
+   * Calculate expected CR from state
+   */
+  async function calculateCR(): Promise<BN> {
+    const state = await getGlobalState();
+    const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+    const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+    return computeCrBps(tvl, liability);
+  }
+
+  /**
+ * Sends a tiny transfer tx to force local validator activity.
+ * Localnet often stops slot production while idle; this "ping"
+ * advances slots deterministically for staleness tests.
+ */
+  async function sendSlotPingTx(): Promise<void> {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: protocolState.authority.publicKey,
+        toPubkey: protocolState.globalState,
+        lamports: 1,
+      })
+    );
+
+    try {
+      await provider.sendAndConfirm(tx, [protocolState.authority], {
+        commitment: "processed",
+        skipPreflight: true,
+      });
+    } catch { }
+  }
+
+  /**
+   * Waits until the chain advances by `delta` slots.
+   *
+   * Uses active tx pings so tests do not hang on idle localnet.
+   * This is required for reliable oracle/LST staleness vectors.
+   */
+  async function waitForSlotDelta(delta: number, timeoutMs = 180_000): Promise<void> {
+    const start = await connection.getSlot("processed");
+    const target = start + delta;
+    const startedAt = Date.now();
+
+    while (true) {
+      const now = await connection.getSlot("processed");
+      if (now >= target) return;
+      if (Date.now() - startedAt > timeoutMs) {
+        throw new Error(`Timed out waiting for slot ${target}, current=${now}`);
+      }
+
+      const remaining = target - now;
+      const burst = Math.min(Math.max(1, remaining), 6);
+      for (let i = 0; i < burst; i++) await sendSlotPingTx();
+      await new Promise((r) => setTimeout(r, 25));
+    }
+  }
+
+  /**
+ * Explicitly refreshes LST exchange-rate cache metadata on-chain.
+ * Useful for tests that assert stale -> refresh -> success flows.
+ */
+  async function syncExchangeRate(): Promise<string> {
+    return await program.methods
+      .syncExchangeRate()
+      .accounts({
+        globalState: protocolState.globalState,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any)
+      .rpc();
+  }
+
+  /**
+ * Reset oracle and LST freshness to a known-good baseline.
+ *
+ * This should be called at the start of tests that expect deterministic
+ * error types so stale state from prior tests does not leak in.
+ */
+  async function resetAndSyncSnapshots(): Promise<void> {
+    await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
+    await syncExchangeRate();
+  }
+
+
+  async function getTokenAmountOrZero(address: PublicKey): Promise<BN> {
+    try {
+      const acc = await getAccount(connection, address);
+      return new BN(acc.amount.toString());
+    } catch {
+      return new BN(0);
+    }
+  }
+
+
+
+  before(async () => {
+    protocolState = await initializeProtocol();
+    console.log("Protocol initialized!");
+    console.log("  GlobalState:", protocolState.globalState.toBase58());
+    console.log("  amUSD Mint:", protocolState.amusdMint.publicKey.toBase58());
+    console.log("  aSOL Mint:", protocolState.asolMint.publicKey.toBase58());
+    console.log("  LST Mint:", protocolState.lstMint.toBase58());
+    console.log("  Vault:", protocolState.vault.toBase58());
+  });
+
+  describe("1. Protocol Initialization", () => {
+    it("Initializes protocol with correct parameters", async () => {
+      const state = await getGlobalState();
+
+      expect(state.version).to.equal(1);
+      expect(state.minCrBps.toNumber()).to.equal(MIN_CR_BPS.toNumber());
+      expect(state.targetCrBps.toNumber()).to.equal(TARGET_CR_BPS.toNumber());
+      expect(state.mockSolPriceUsd.toNumber()).to.equal(MOCK_SOL_PRICE_USD.toNumber());
+      expect(state.mockLstToSolRate.toNumber()).to.equal(MOCK_LST_TO_SOL_RATE.toNumber());
+      expect(state.totalLstAmount.toNumber()).to.equal(0);
+      expect(state.amusdSupply.toNumber()).to.equal(0);
+      expect(state.asolSupply.toNumber()).to.equal(0);
+      expect(state.mintPaused).to.be.false;
+      expect(state.redeemPaused).to.be.false;
+    });
+
+    it("Sets correct mint addresses", async () => {
+      const state = await getGlobalState();
+
+      expect(state.amusdMint.toBase58()).to.equal(protocolState.amusdMint.publicKey.toBase58());
+      expect(state.asolMint.toBase58()).to.equal(protocolState.asolMint.publicKey.toBase58());
+      expect(state.supportedLstMint.toBase58()).to.equal(protocolState.lstMint.toBase58());
+    });
+
+    it("Sets treasury to authority on initialization", async () => {
+      const state = await getGlobalState();
+      expect(state.treasury.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
+    });
+
+    it("Applies the full launch configuration atomically while pre-launch", async () => {
+      // configure_launch requires both pause flags
+      try {
+        await configureLaunch(defaultLaunchConfig());
+        expect.fail("Expected ProtocolAlreadyLaunched");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ProtocolAlreadyLaunched");
+      }
+
+      await setPause(true, true);
+
+      const launchTreasury = Keypair.generate();
+      const config: LaunchConfig = {
+        minCrBps: new BN(12_500),
+        targetCrBps: new BN(16_000),
+        feeAmusdMintBps: new BN(60),
+        feeAmusdRedeemBps: new BN(30),
+        feeAsolMintBps: new BN(35),
+        feeAsolRedeemBps: new BN(20),
+        feeMinMultiplierBps: new BN(5_000),
+        feeMaxMultiplierBps: new BN(30_000),
+        maxOracleStalenessSlots: new BN(200),
+        maxConfBps: new BN(100),
+        uncertaintyMaxBps: new BN(15_000),
+        maxLstStaleEpochs: new BN(2),
+        maxOracleDeviationBps: new BN(250),
+        maxPriceMoveBps: new BN(1_500),
+        fallbackUncertaintyBps: new BN(400),
+        emaHalfLifeSlots: new BN(600),
+        maxRoundingReserveLamports: new BN(20_000),
+        navFloorLamports: new BN(2_000_000),
+        maxAsolMintPerRound: new BN(10_000).mul(SOL_PRECISION),
+        mintRoundSlots: new BN(4_500),
+        amusdSupplyCap: new BN(1_000_000).mul(USD_PRECISION),
+        asolSupplyCap: new BN(5_000).mul(SOL_PRECISION),
+        maxTotalLstAmount: new BN(10_000).mul(SOL_PRECISION),
+        maxSingleDepositLst: new BN(1_000).mul(SOL_PRECISION),
+        maxSingleRedeemUnits: new BN(100_000).mul(USD_PRECISION),
+        perUserAmusdCap: new BN(50_000).mul(USD_PRECISION),
+        feeSplitInsuranceBps: new BN(2_000),
+        savingsFeeSplitBps: new BN(3_000),
+        stabilityFeeBpsPerYear: new BN(200),
+        minActionGapSlots: new BN(2),
+        asolRedeemCooldownSlots: new BN(150),
+        paramTimelockSlots: new BN(0),
+        maxPauseDurationSlots: new BN(500_000),
+        rebalanceDiscountBps: new BN(250),
+        recoveryBonusBps: new BN(300),
+        maxFlashLoanBps: new BN(2_500),
+        // The PSM is not initialized yet, so only its zeros are accepted
+        psmFeeBps: new BN(0),
+        psmDebtCeiling: new BN(0),
+        pairCrToleranceBps: new BN(75),
+        treasury: launchTreasury.publicKey,
+        treasuryIsPda: false,
+        treasuryOwnerProgram: PublicKey.default,
+      };
+
+      await configureLaunch(config, [launchTreasury]);
+
+      // Every field lands in GlobalState; `treasuryIsPda` is only recorded
+      // through `treasuryOwnerProgram` (default for a wallet)
+      const state = (await program.account.globalState.fetch(protocolState.globalState)) as any;
+      const { treasuryIsPda: _treasuryIsPda, ...stored } = config;
+      for (const [key, value] of Object.entries(stored)) {
+        expect(state[key].toString(), key).to.equal(value.toString());
+      }
+      const launchTreasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: launchTreasury.publicKey,
+      });
+      expect(await provider.connection.getAccountInfo(launchTreasuryAmusdAccount)).to.not.be.null;
+
+      // Inverted multiplier bounds are rejected by validate_fee_config, and
+      // the later tunables by the bounds their own setters enforce
+      for (const invalid of [
+        { feeMinMultiplierBps: new BN(12_000) },
+        { mintRoundSlots: new BN(0) },
+        { feeSplitInsuranceBps: BPS_PRECISION.addn(1) },
+        { savingsFeeSplitBps: BPS_PRECISION.addn(1) },
+        { stabilityFeeBpsPerYear: new BN(2_001) },
+        { maxOracleDeviationBps: new BN(0) },
+        { maxPriceMoveBps: BPS_PRECISION.addn(1) },
+        { fallbackUncertaintyBps: new BN(10_001) },
+        { emaHalfLifeSlots: new BN(216_001) },
+        { paramTimelockSlots: new BN(6_480_001) },
+        { maxPauseDurationSlots: new BN(0) },
+        { rebalanceDiscountBps: new BN(501) },
+        { recoveryBonusBps: new BN(1_001) },
+        { maxFlashLoanBps: BPS_PRECISION.addn(1) },
+        { psmFeeBps: new BN(101) },
+        { pairCrToleranceBps: BPS_PRECISION.addn(1) },
+      ]) {
+        try {
+          await configureLaunch({ ...config, ...invalid }, [launchTreasury]);
+          expect.fail("Expected InvalidParameter");
+        } catch (err: any) {
+          expect(err.toString(), Object.keys(invalid)[0]).to.include("InvalidParameter");
+        }
+      }
+
+      // PSM tunables need the PSM, as in update_psm_parameters
+      try {
+        await configureLaunch({ ...config, psmDebtCeiling: new BN(1_000).mul(USD_PRECISION) }, [launchTreasury]);
+        expect.fail("Expected PsmNotInitialized");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PsmNotInitialized");
+      }
+
+      // A wallet treasury must co-sign, as in set_treasury
+      try {
+        await configureLaunch(config);
+        expect.fail("Expected InvalidTreasury");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTreasury");
+      }
+
+      // Restore defaults for downstream tests
+      await configureLaunch(defaultLaunchConfig());
+      await setPause(false, false);
+    });
+  });
+
   describe("2. Mint aSOL (Equity Injection)", () => {
     before(async () => {
-      const userSetup = await setupUser(100); // Gives 100 lst to user
-      user1 = userSetup.user;
-      user1LstAccount = userSetup.lstAccount;
-      user1AmusdAccount = userSetup.amusdAccount;
-      user1AsolAccount = userSetup.asolAccount;
+      const userSetup = await setupUser(100); // Gives 100 lst to user
+      user1 = userSetup.user;
+      user1LstAccount = userSetup.lstAccount;
+      user1AmusdAccount = userSetup.amusdAccount;
+      user1AsolAccount = userSetup.asolAccount;
+    });
+
+    it("Mints aSOL at 1:1 rate for first deposit", async () => {
+      const lstAmount = new BN(10 * LAMPORTS_PER_SOL);
+      const minAsolOut = new BN(1);
+
+      const stateBefore = await getGlobalState();
+      expect(stateBefore.asolSupply.toNumber()).to.equal(0);
+
+      await mintAsol(user1, user1LstAccount, user1AsolAccount, lstAmount, minAsolOut);
+
+      const stateAfter = await getGlobalState();
+
+      const expectedSolValue = lstAmount.mul(MOCK_LST_TO_SOL_RATE).div(SOL_PRECISION);
+      const tvlBefore = computeTvlSol(stateBefore.totalLstAmount, stateBefore.mockLstToSolRate);
+      const liabilityBefore = computeLiabilitySol(stateBefore.amusdSupply, stateBefore.mockSolPriceUsd);
+      const crBefore = computeCrBps(tvlBefore, liabilityBefore);
+      const feeBps = feeBpsDecreaseWhenLow(ASOL_MINT_FEE_BPS, crBefore, TARGET_CR_BPS);
+      const [expectedAsolNet, _expectedFee] = applyFee(expectedSolValue, feeBps);
+
+      expect(stateAfter.totalLstAmount.toNumber()).to.equal(lstAmount.toNumber());
+
+      const expectedAsolGross = expectedSolValue;
+      expect(stateAfter.asolSupply.toNumber()).to.equal(expectedAsolGross.toNumber());
+
+      const userAsolBalance = await getAccount(connection, user1AsolAccount);
+      expect(Number(userAsolBalance.amount)).to.equal(expectedAsolNet.toNumber());
+    })
+
+    it("Increases TVL on aSOL mint", async () => {
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+
+      const expectedTvl = new BN(10 * LAMPORTS_PER_SOL).mul(MOCK_LST_TO_SOL_RATE).div(SOL_PRECISION);
+      expect(tvl.toNumber()).to.equal(expectedTvl.toNumber());
+    });
+
+    it("Maintains infinite CR when no debt exists", async () => {
+      const cr = await calculateCR();
+
+      expect(cr.toNumber()).to.equal(Number.MAX_SAFE_INTEGER);
+    });
+  })
+
+  describe("3. Mint amUSD (Debt Creation)", () => {
+    it("Mints amUSD and decreases CR", async () => {
+      const lstAmount = new BN(5 * LAMPORTS_PER_SOL); // 5 LST
+      const minAmusdOut = new BN(1);
+
+      const crBefore = await calculateCR();
+
+      await mintAmUSD(user1, user1LstAccount, user1AmusdAccount, lstAmount, minAmusdOut);
+
+      const crAfter = await calculateCR();
+
+      // CR should decrease
+      expect(crAfter.lt(crBefore)).to.be.true;
+      console.log(`  CR changed from ${crBefore.toString()} to ${crAfter.toNumber()} bps`);
+    });
+
+    it("Correctly calculates amUSD amount", async () => {
+      const state = await getGlobalState();
+
+      // User deposited 5 LST
+      // SOL value = 5 * 1.05 = 5.25 SOL
+      // amUSD (before fee) = 5.25 * 100 = 525 USD
+      // But we need to check in micro-USD (USD_PRECISION = 1e6)
+
+      const userAmusdBalance = await getAccount(connection, user1AmusdAccount);
+      expect(Number(userAmusdBalance.amount)).to.be.greaterThan(0);
+      console.log(`  User amUSD balance: ${Number(userAmusdBalance.amount) / 1e6} USD`);
+    });
+
+    it("Updates balance sheet correctly", async () => {
+      const state = await getGlobalState();
+
+      // Total LST should be 10 (aSOL) + 5 (amUSD) = 15 LST
+      expect(state.totalLstAmount.toNumber()).to.equal(15 * LAMPORTS_PER_SOL);
+
+      // amUSD supply should be > 0
+      expect(state.amusdSupply.toNumber()).to.be.greaterThan(0);
+
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
+
+      // Allow small rounding tolerance
+      const diff = tvl.sub(liability.add(equity)).abs();
+      expect(diff.toNumber()).to.be.lessThan(1000); // < 1000 lamports tolerance
+    });
+  });
+
+  describe("4. CR Safety Checks", () => {
+    it("Rejects amUSD mint when CR would fall below minimum", async () => {
+
+      const userSetup = await setupUser(1000);
+      user2 = userSetup.user;
+      user2LstAccount = userSetup.lstAccount;
+      user2AmusdAccount = userSetup.amusdAccount;
+      user2AsolAccount = userSetup.asolAccount;
+
+      // Try to mint a huge amount that would tank CR
+      const hugeAmount = new BN(500 * LAMPORTS_PER_SOL);
+      const minAmusdOut = new BN(1);
+
+      try {
+        await mintAmUSD(user2, user2LstAccount, user2AmusdAccount, hugeAmount, minAmusdOut);
+        expect.fail("Should have rejected due to CR violation");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CollateralRatioTooLow");
+      }
+    });
+
+    it("Allows amUSD mint when CR stays above minimum", async () => {
+      // First add more equity to have room for debt
+      const equityAmount = new BN(50 * LAMPORTS_PER_SOL);
+      await mintAsol(user2, user2LstAccount, user2AsolAccount, equityAmount, new BN(1));
+
+      // Now mint a smaller amUSD amount
+      const smallAmount = new BN(5 * LAMPORTS_PER_SOL);
+      const crBefore = await calculateCR();
+
+      await mintAmUSD(user2, user2LstAccount, user2AmusdAccount, smallAmount, new BN(1));
+
+      const crAfter = await calculateCR();
+      expect(crAfter.gte(MIN_CR_BPS)).to.be.true;
+      console.log(`  CR after small mint: ${crAfter.toNumber()} bps`);
+    });
+  });
+
+
+  describe("5. aSOL Minting Improves CR", () => {
+    it("Minting aSOL increases collateral ratio", async () => {
+      const crBefore = await calculateCR();
+
+      // Mint aSOL (injects equity without adding debt)
+      const lstAmount = new BN(20 * LAMPORTS_PER_SOL);
+      await mintAsol(user2, user2LstAccount, user2AsolAccount, lstAmount, new BN(1));
+
+      const crAfter = await calculateCR();
+
+      // CR should improve
+      expect(crAfter.gt(crBefore)).to.be.true;
+      console.log(`  CR improved from ${crBefore.toNumber()} to ${crAfter.toNumber()} bps`);
+    });
+  });
+
+  describe("6. Redemptions", () => {
+    it("Redeems amUSD and returns LST", async () => {
+      const userAmusdBalanceBefore = await getAccount(connection, user1AmusdAccount);
+      const amusdAmount = new BN(Number(userAmusdBalanceBefore.amount) / 2); // Redeem half
+
+      const userLstBalanceBefore = await getAccount(connection, user1LstAccount);
+      const minLstOut = new BN(10_000_000); // 0.01 LST minimum
+
+      await redeemAmUSD(user1, user1LstAccount, user1AmusdAccount, amusdAmount, minLstOut);
+
+      const userAmusdBalanceAfter = await getAccount(connection, user1AmusdAccount);
+      const userLstBalanceAfter = await getAccount(connection, user1LstAccount);
+
+      // amUSD balance should decrease
+      expect(Number(userAmusdBalanceAfter.amount)).to.be.lessThan(Number(userAmusdBalanceBefore.amount));
+
+      // LST balance should increase
+      expect(Number(userLstBalanceAfter.amount)).to.be.greaterThan(Number(userLstBalanceBefore.amount));
+
+      console.log(`  Redeemed ${amusdAmount.toNumber() / 1e6} amUSD`);
+      console.log(`  Received ${(Number(userLstBalanceAfter.amount) - Number(userLstBalanceBefore.amount)) / 1e9} LST`);
+    });
+
+    it("amUSD redemption always improves or maintains CR", async () => {
+      const crBefore = await calculateCR();
+
+      const userAmusdBalance = await getAccount(connection, user1AmusdAccount);
+      const amusdAmount = new BN(Number(userAmusdBalance.amount)); // Redeem all
+      const minLstOut = new BN(10_000_000);
+
+      if (amusdAmount.gt(new BN(0))) {
+        await redeemAmUSD(user1, user1LstAccount, user1AmusdAccount, amusdAmount, minLstOut);
+
+        const crAfter = await calculateCR();
+        expect(crAfter.gte(crBefore)).to.be.true;
+      }
+    });
+
+    it("Redeems aSOL at NAV", async () => {
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const nav = computeAsolNav(tvl, liability, state.asolSupply);
+      console.log(`  Current aSOL NAV: ${nav.toNumber() / 1e9} SOL`);
+
+      const userAsolBalance = await getAccount(connection, user1AsolAccount);
+      const asolAmount = new BN(Number(userAsolBalance.amount) / 2); // Redeem half
+      const minLstOut = new BN(10_000_000);
+
+      if (asolAmount.gt(new BN(0))) {
+        const userLstBalanceBefore = await getAccount(connection, user1LstAccount);
+
+        await redeemAsol(user1, user1LstAccount, user1AsolAccount, asolAmount, minLstOut);
+
+        const userLstBalanceAfter = await getAccount(connection, user1LstAccount);
+        const lstReceived = Number(userLstBalanceAfter.amount) - Number(userLstBalanceBefore.amount);
+
+        console.log(`  Redeemed ${asolAmount.toNumber() / 1e9} aSOL`);
+        console.log(`  Received ${lstReceived / 1e9} LST`);
+      }
+    });
+  });
+
+  describe("7. NAV Behavior Under Price Stress", () => {
+    it("aSOL NAV decreases when SOL price drops", async () => {
+      // Get initial NAV
+      let state = await getGlobalState();
+      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const navBefore = computeAsolNav(tvl, liability, state.asolSupply);
+
+      // Simulate 20% price drop (SOL goes from $100 to $80)
+      const newPrice = new BN(80_000_000); // $80
+      await updateMockPrices(newPrice, MOCK_LST_TO_SOL_RATE);
+
+      // Get new NAV
+      state = await getGlobalState();
+      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const navAfter = computeAsolNav(tvl, liability, state.asolSupply);
+
+      console.log(`  NAV before price drop: ${navBefore.toNumber() / 1e9} SOL`);
+      console.log(`  NAV after 20% price drop: ${navAfter.toNumber() / 1e9} SOL`);
+
+      // NAV should decrease (equity absorbs the loss)
+      // Note: In SOL terms, liability increases when SOL price drops
+      // because same USD debt = more SOL needed to repay
+    });
+
+    it("aSOL NAV goes to zero when TVL < Liability (insolvency)", async () => {
+      // Simulate extreme crash (SOL goes to $30)
+      const crashPrice = new BN(30_000_000); // $30
+      await updateMockPrices(crashPrice, MOCK_LST_TO_SOL_RATE);
+
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
+
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
+      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
+
+      // If insolvent, equity should be 0
+      if (tvl.lt(liability)) {
+        expect(equity.toNumber()).to.equal(0);
+        console.log("  Protocol is insolvent - equity capped at 0");
+      }
+
+      // Reset price for other tests
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+    });
+  });
+
+
+  describe("8. Emergency Pause", () => {
+    it("Admin can pause minting", async () => {
+      await program.methods
+        .emergencyPause(true, false)
+        .accounts({
+          authority: protocolState.authority.publicKey,
+          globalState: protocolState.globalState,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([protocolState.authority])
+        .rpc();
+
+      const state = await getGlobalState();
+      expect(state.mintPaused).to.be.true;
+      expect(state.redeemPaused).to.be.false;
+    });
+
+    it("Minting is rejected when paused", async () => {
+      try {
+        await mintAsol(user2, user2LstAccount, user2AsolAccount, new BN(1e9), new BN(1));
+        expect.fail("Should have rejected due to pause");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MintPaused");
+      }
+    });
+
+    it("Admin can unpause", async () => {
+      await program.methods
+        .emergencyPause(false, false)
+        .accounts({
+          authority: protocolState.authority.publicKey,
+          globalState: protocolState.globalState,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([protocolState.authority])
+        .rpc();
+
+      const state = await getGlobalState();
+      expect(state.mintPaused).to.be.false;
+    });
+  });
+
+  describe("9. Slippage Protection", () => {
+    it("Rejects mint when output below minimum", async () => {
+      const lstAmount = new BN(1 * LAMPORTS_PER_SOL);
+      const unreasonablyHighMin = new BN(1000 * LAMPORTS_PER_SOL); // Impossible
+
+      try {
+        await mintAsol(user2, user2LstAccount, user2AsolAccount, lstAmount, unreasonablyHighMin);
+        expect.fail("Should have rejected due to slippage");
+      } catch (err: any) {
+        expect(err.toString()).to.include("SlippageExceeded");
+      }
+    });
+  });
+
+
+  describe("10. Admin Parameter Updates", () => {
+    it("Admin can update risk parameters", async () => {
+      const newMinCr = new BN(14_000); // 140%
+      const newTargetCr = new BN(16_000); // 160%
+
+      await program.methods
+        .updateParameters(newMinCr, newTargetCr)
+        .accounts({
+          authority: protocolState.authority.publicKey,
+          globalState: protocolState.globalState,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([protocolState.authority])
+        .rpc();
+
+      const state = await getGlobalState();
+      expect(state.minCrBps.toNumber()).to.equal(newMinCr.toNumber());
+      expect(state.targetCrBps.toNumber()).to.equal(newTargetCr.toNumber());
+    });
+
+    it("Non-admin cannot update parameters", async () => {
+      try {
+        await program.methods
+          .updateParameters(new BN(12_000), new BN(14_000))
+          .accounts({
+            authority: user1.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have rejected non-admin");
+      } catch (err: any) {
+        // Expected - constraint violation
+        expect(err.toString()).to.include("ConstraintHasOne");
+      }
+    });
+  });
+
+
+  describe("11. Balance Sheet Invariant", () => {
+    it("TVL always equals Liability + Equity", async () => {
+      const state = await getGlobalState();
+
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
+
+      const total = liability.add(equity);
+      const diff = tvl.sub(total).abs();
+
+      // Allow small tolerance due to rounding
+      const tolerance = BN.max(tvl.div(new BN(10_000)), new BN(1000)); // 1 bps or 1000 lamports
+      expect(diff.lte(tolerance)).to.be.true;
+
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
+      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
+      console.log(`  Difference: ${diff.toNumber()} lamports`);
+    });
+  });
+
+  describe("12. Edge Case: Insolvency Protection", () => {
+    it("Rejects aSOL redemption when NAV is zero (protocol insolvency)", async () => {
+      // Reset price first
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+
+      // We need to create conditions where TVL < Liability
+      const userSetup = await setupUser(100);
+      const testUser = userSetup.user;
+
+      // Deposit equity
+      await mintAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(20 * LAMPORTS_PER_SOL), new BN(1));
+
+      // Add substantial debt
+      await mintAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+
+      const stateBefore = await getGlobalState();
+      const tvlBefore = computeTvlSol(stateBefore.totalLstAmount, stateBefore.mockLstToSolRate);
+      const liabilityBefore = computeLiabilitySol(stateBefore.amusdSupply, stateBefore.mockSolPriceUsd);
+      console.log(`  Before crash - TVL: ${tvlBefore.toNumber() / 1e9} SOL, Liability: ${liabilityBefore.toNumber() / 1e9} SOL`);
+
+      // Calculate the price needed to make TVL < Liability
+      const state = await getGlobalState();
+      const tvlAtCurrentPrice = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+
+      // Price that would make liability = 2x TVL (guaranteed insolvency)
+      const insolvencyPrice = state.amusdSupply
+        .mul(SOL_PRECISION)
+        .div(tvlAtCurrentPrice.mul(new BN(2)));
+
+      // Use this calculated price or a very low price
+      const crashPrice = BN.max(insolvencyPrice, new BN(1_000_000)); // At least $1
+
+      await updateMockPrices(crashPrice, MOCK_LST_TO_SOL_RATE);
+
+      const stateAfterCrash = await getGlobalState();
+      const tvlAfter = computeTvlSol(stateAfterCrash.totalLstAmount, stateAfterCrash.mockLstToSolRate);
+      const liabilityAfter = computeLiabilitySol(stateAfterCrash.amusdSupply, stateAfterCrash.mockSolPriceUsd);
+      console.log(`  After crash - TVL: ${tvlAfter.toNumber() / 1e9} SOL, Liability: ${liabilityAfter.toNumber() / 1e9} SOL`);
+
+      const isInsolvent = tvlAfter.lt(liabilityAfter);
+      console.log(`  Insolvent: ${isInsolvent}`);
+
+      if (isInsolvent) {
+        // Verify NAV is zero or negative
+        const nav = computeAsolNav(tvlAfter, liabilityAfter, stateAfterCrash.asolSupply);
+        expect(nav.toNumber()).to.equal(0);
+        console.log(`  NAV during insolvency: ${nav.toNumber()} (expected 0)`);
+
+        // Now test redemption rejection
+        const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+        if (Number(userAsolBalance.amount) > 0) {
+          try {
+            await redeemAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
+              new BN(Number(userAsolBalance.amount)), new BN(1));
+            expect.fail("Should reject redemption when insolvent");
+          } catch (err: any) {
+            // Check for InsolventProtocol error (error code 6007)
+            expect(
+              err.toString().includes("InsolventProtocol") ||
+              err.toString().includes("6007") ||
+              err.toString().includes("Protocol is insolvent")
+            ).to.be.true;
+            console.log("  ✓ Correctly rejected aSOL redemption during insolvency");
+          }
+        }
+      } else {
+        // Protocol has too much accumulated equity - the test condition couldn't be created
+        // This is actually a GOOD thing - it means the protocol is well-capitalized
+        // We'll skip this specific assertion and document why
+        console.log("  Protocol remained solvent (well-capitalized from previous tests)");
+        console.log("  This is expected behavior - accumulated equity protects against insolvency");
+        console.log("  ✓ Test validates protocol handles the scenario correctly");
+
+        // Still verify the NAV calculation works
+        const nav = computeAsolNav(tvlAfter, liabilityAfter, stateAfterCrash.asolSupply);
+        console.log(`  NAV while solvent: ${nav.toNumber() / 1e9} SOL`);
+        expect(nav.toNumber()).to.be.greaterThan(0);
+      }
+
+      // Reset price for subsequent tests
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+    });
+
+    it("amUSD redemption still works during insolvency (priority exit)", async () => {
+      // Reset price first to ensure clean state
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+
+      // amUSD holders should ALWAYS be able to exit (senior tranche priority)
+      const userSetup = await setupUser(100);
+      const testUser = userSetup.user;
+
+      // Create healthy protocol state first
+      await mintAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(30 * LAMPORTS_PER_SOL), new BN(1));
+      await mintAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
+        new BN(5 * LAMPORTS_PER_SOL), new BN(1));
+
+      // Moderate price drop 
+      // At $50 SOL with 30 LST equity + 5 LST debt, should still be solvent
+      await updateMockPrices(new BN(70_000_000), MOCK_LST_TO_SOL_RATE); // $70 SOL (not extreme)
+
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL, Liability: ${liability.toNumber() / 1e9} SOL`);
+
+      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
+      const smallRedemption = new BN(Number(userAmusdBalance.amount) / 4);
+
+      if (smallRedemption.gt(new BN(0))) {
+
+        try {
+          await redeemAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
+            smallRedemption, new BN(100_000)); // reasonable min_lst_out
+          console.log("  amUSD redemption succeeded during price stress");
+        } catch (err: any) {
+
+          expect(err.toString()).to.not.include("InsolventProtocol");
+        }
+      }
+
+      // Reset price
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+    });
+  });
+
+  describe("13. Dust Attack Prevention", () => {
+    before(async () => {
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+    });
+
+    it("Rejects LST deposit below minimum threshold", async () => {
+      const userSetup = await setupUser(1);
+      const dustAmount = new BN(10_000); // 0.00001 SOL - below MIN_LST_DEPOSIT
+
+      try {
+        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          dustAmount, new BN(1));
+        expect.fail("Should reject dust deposit");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AmountTooSmall");
+      }
+    });
+
+    it("Rejects aSOL redemption that would output dust LST", async () => {
+      // Ensure price is normal
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+
+      const userSetup = await setupUser(10);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+
+      const tinyAsol = new BN(1000); // 0.000001 aSOL
+
+      try {
+        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          tinyAsol, new BN(1)); // min_lst_out = 1 accepts any output
+        expect.fail("Should reject dust output");
+      } catch (err: any) {
+        // Should fail for the output floor, not slippage or InsolventProtocol
+        expect(err.toString()).to.include("RedeemOutputTooSmall");
+      }
+    });
+
+    it("Rejects mint when output tokens would be below minimum", async () => {
+      // Ensure price is normal
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+
+      const userSetup = await setupUser(1);
+
+      // Amount that after fees would produce < MIN_ASOL_MINT
+      const minimalAmount = new BN(500_000); // 0.0005 SOL
+
+      try {
+        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          minimalAmount, new BN(1));
+        expect.fail("Should reject when output too small");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AmountTooSmall");
+      }
+    });
+  });
+
+  describe("14. Rounding Direction Security", () => {
+    it("Protocol always rounds in its favor on mints", async () => {
+      // Verify user gets rounded-down amount
+      const userSetup = await setupUser(10);
+      const oddAmount = new BN(1_000_000_007); // Odd number to test rounding
+
+      const stateBefore = await getGlobalState();
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        oddAmount, new BN(1));
+      const stateAfter = await getGlobalState();
+
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      const totalMinted = stateAfter.asolSupply.sub(stateBefore.asolSupply);
+
+      // User + treasury should equal total minted (no tokens lost to rounding)
+      console.log(`  Total minted: ${totalMinted.toString()}`);
+      console.log(`  User received: ${userAsolBalance.amount.toString()}`);
+    });
+
+    it("Protocol always rounds in its favor on redemptions", async () => {
+      const userSetup = await setupUser(20);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+
+      const oddRedemption = new BN(1_234_567_891); // Odd number
+      const userLstBefore = await getAccount(connection, userSetup.lstAccount);
+
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        oddRedemption, new BN(100_000));
+
+      const userLstAfter = await getAccount(connection, userSetup.lstAccount);
+      const lstReceived = Number(userLstAfter.amount) - Number(userLstBefore.amount);
+
+      console.log(`  aSOL redeemed: ${oddRedemption.toString()}`);
+      console.log(`  LST received: ${lstReceived}`);
     });
+  });
 
-    it("Mints aSOL at 1:1 rate for first deposit", async () => {
-      const lstAmount = new BN(10 * LAMPORTS_PER_SOL);
-      const minAsolOut = new BN(1);
+  describe("15. Full Redemption (Bank Run Scenario)", () => {
+    it("Allows complete aSOL redemption when no debt exists", async () => {
+      const userSetup = await setupUser(50);
+      const depositAmount = new BN(10 * LAMPORTS_PER_SOL);
 
-      const stateBefore = await getGlobalState();
-      expect(stateBefore.asolSupply.toNumber()).to.equal(0);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        depositAmount, new BN(1));
 
-      await mintAsol(user1, user1LstAccount, user1AsolAccount, lstAmount, minAsolOut);
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      const fullBalance = new BN(Number(userAsolBalance.amount));
 
-      const stateAfter = await getGlobalState();
+      // Should be able to redeem entire balance
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        fullBalance, new BN(100_000));
 
-      const expectedSolValue = lstAmount.mul(MOCK_LST_TO_SOL_RATE).div(SOL_PRECISION);
-      const tvlBefore = computeTvlSol(stateBefore.totalLstAmount, stateBefore.mockLstToSolRate);
-      const liabilityBefore = computeLiabilitySol(stateBefore.amusdSupply, stateBefore.mockSolPriceUsd);
-      const crBefore = computeCrBps(tvlBefore, liabilityBefore);
-      const feeBps = feeBpsDecreaseWhenLow(ASOL_MINT_FEE_BPS, crBefore, TARGET_CR_BPS);
-      const [expectedAsolNet, _expectedFee] = applyFee(expectedSolValue, feeBps);
+      const finalBalance = await getAccount(connection, userSetup.asolAccount);
+      expect(Number(finalBalance.amount)).to.equal(0);
+    });
 
-      expect(stateAfter.totalLstAmount.toNumber()).to.equal(lstAmount.toNumber());
+    it("Allows complete amUSD redemption", async () => {
+      const userSetup = await setupUser(100);
 
-      const expectedAsolGross = expectedSolValue;
-      expect(stateAfter.asolSupply.toNumber()).to.equal(expectedAsolGross.toNumber());
+      // Create equity buffer
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(50 * LAMPORTS_PER_SOL), new BN(1));
 
-      const userAsolBalance = await getAccount(connection, user1AsolAccount);
-      expect(Number(userAsolBalance.amount)).to.equal(expectedAsolNet.toNumber());
-    })
+      // Create debt
+      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
 
-    it("Increases TVL on aSOL mint", async () => {
+      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
+      const fullBalance = new BN(Number(userAmusdBalance.amount));
+
+      // Redeem all debt
+      await redeemAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+        fullBalance, new BN(100_000));
+
+      const finalBalance = await getAccount(connection, userSetup.amusdAccount);
+      expect(Number(finalBalance.amount)).to.equal(0);
+
+      // Verify protocol state
       const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      console.log(`  Remaining amUSD supply: ${state.amusdSupply.toNumber()}`);
+    });
 
-      const expectedTvl = new BN(10 * LAMPORTS_PER_SOL).mul(MOCK_LST_TO_SOL_RATE).div(SOL_PRECISION);
-      expect(tvl.toNumber()).to.equal(expectedTvl.toNumber());
+    it("Prevents redemption that would leave protocol below minimum TVL", async () => {
+      const userSetup = await setupUser(10);
+
+      // Make a very small deposit
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(0.002 * LAMPORTS_PER_SOL), new BN(1)); // Very small deposit
+
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+
+      // The protocol has a lot of TVL from other tests, so this small user's 
+      // redemption won't trigger BelowMinimumTVL.so we verify if the 
+      // protocol correctly handles small redemptions.
+      if (Number(userAsolBalance.amount) > 100) {
+        const smallAmount = new BN(Number(userAsolBalance.amount) - 100);
+
+        try {
+          await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+            smallAmount, new BN(1));
+          // If it succeeds, check the state
+          const state = await getGlobalState();
+          const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+          console.log(`  Redemption succeeded, remaining TVL: ${tvl.toNumber() / 1e9} SOL`);
+          // Verify TVL is still above minimum or is zero
+          expect(tvl.toNumber() >= 1_000_000 || tvl.toNumber() === 0).to.be.true;
+        } catch (err: any) {
+          // Could fail for various reasons - dust, slippage, or BelowMinimumTVL
+          console.log(`  Redemption rejected: ${err.message?.substring(0, 50) || err.toString().substring(0, 50)}`);
+          const acceptableErrors = ["BelowMinimumTVL", "AmountTooSmall", "RedeemOutputTooSmall", "SlippageExceeded", "InsufficientCollateral"];
+          const hasAcceptableError = acceptableErrors.some(e => err.toString().includes(e));
+          expect(hasAcceptableError).to.be.true;
+        }
+      }
     });
+  });
 
-    it("Maintains infinite CR when no debt exists", async () => {
-      const cr = await calculateCR();
+  describe("16. Oracle Price Manipulation Bounds", () => {
+    it("Rejects zero SOL price", async () => {
+      try {
+        await updateMockPrices(new BN(0), MOCK_LST_TO_SOL_RATE);
+        expect.fail("Should reject zero price");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ZeroAmount");
+      }
+    });
 
-      expect(cr.toNumber()).to.equal(Number.MAX_SAFE_INTEGER);
+    it("Rejects zero LST rate", async () => {
+      try {
+        await updateMockPrices(MOCK_SOL_PRICE_USD, new BN(0));
+        expect.fail("Should reject zero rate");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ZeroAmount");
+      }
     });
-  })
 
-  describe("3. Mint amUSD (Debt Creation)", () => {
-    it("Mints amUSD and decreases CR", async () => {
-      const lstAmount = new BN(5 * LAMPORTS_PER_SOL); // 5 LST
-      const minAmusdOut = new BN(1);
+    it("Handles extreme SOL price ($1M)", async () => {
+      await updateMockPrices(new BN(1_000_000_000_000), MOCK_LST_TO_SOL_RATE); // $1M
 
-      const crBefore = await calculateCR();
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
 
-      await mintAmUSD(user1, user1LstAccount, user1AmusdAccount, lstAmount, minAmusdOut);
+      // At $1M/SOL, liability in SOL terms should be tiny
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Liability at $1M: ${liability.toNumber() / 1e9} SOL`);
 
-      const crAfter = await calculateCR();
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+    });
 
-      // CR should decrease
-      expect(crAfter.lt(crBefore)).to.be.true;
-      console.log(`  CR changed from ${crBefore.toString()} to ${crAfter.toNumber()} bps`);
+    it("Handles extreme SOL price drop ($0.01)", async () => {
+      await updateMockPrices(new BN(10_000), MOCK_LST_TO_SOL_RATE); // $0.01
+
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Liability at $0.01: ${liability.toNumber() / 1e9} SOL`);
+
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
     });
 
-    it("Correctly calculates amUSD amount", async () => {
+    it("Handles LST appreciation (2x)", async () => {
+      await updateMockPrices(MOCK_SOL_PRICE_USD, new BN(2_000_000_000)); // 1 LST = 2 SOL
+
       const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
 
-      // User deposited 5 LST
-      // SOL value = 5 * 1.05 = 5.25 SOL
-      // amUSD (before fee) = 5.25 * 100 = 525 USD
-      // But we need to check in micro-USD (USD_PRECISION = 1e6)
+      console.log(`  TVL with 2x LST rate: ${tvl.toNumber() / 1e9} SOL`);
 
-      const userAmusdBalance = await getAccount(connection, user1AmusdAccount);
-      expect(Number(userAmusdBalance.amount)).to.be.greaterThan(0);
-      console.log(`  User amUSD balance: ${Number(userAmusdBalance.amount) / 1e6} USD`);
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
     });
+  });
 
-    it("Updates balance sheet correctly", async () => {
+  describe("17. Multi-User Fairness", () => {
+    let userA: Keypair;
+    let userB: Keypair;
+    let userALst: PublicKey;
+    let userBLst: PublicKey;
+    let userAAsol: PublicKey;
+    let userBAsol: PublicKey;
+
+    before(async () => {
+      const setupA = await setupUser(100);
+      const setupB = await setupUser(100);
+      userA = setupA.user;
+      userB = setupB.user;
+      userALst = setupA.lstAccount;
+      userBLst = setupB.lstAccount;
+      userAAsol = setupA.asolAccount;
+      userBAsol = setupB.asolAccount;
+    });
+
+    it("Second depositor gets fair NAV after first deposit", async () => {
+      const depositAmount = new BN(10 * LAMPORTS_PER_SOL);
+
+      // User A deposits first
+      await mintAsol(userA, userALst, userAAsol, depositAmount, new BN(1));
+      const userABalance = await getAccount(connection, userAAsol);
+
+      // User B deposits same amount after
+      await mintAsol(userB, userBLst, userBAsol, depositAmount, new BN(1));
+      const userBBalance = await getAccount(connection, userBAsol);
+
+      // Both should get roughly equal aSOL (minus small NAV drift)
+      const diff = Math.abs(Number(userABalance.amount) - Number(userBBalance.amount));
+      const tolerance = Number(userABalance.amount) * 0.01; // 1% tolerance
+
+      console.log(`  User A aSOL: ${Number(userABalance.amount)}`);
+      console.log(`  User B aSOL: ${Number(userBBalance.amount)}`);
+      console.log(`  Difference: ${diff} (tolerance: ${tolerance})`);
+
+      expect(diff).to.be.lessThan(tolerance);
+    });
+
+    it("Redemption NAV is consistent across users", async () => {
+      const redeemAmount = new BN(1 * LAMPORTS_PER_SOL);
+
+      const userALstBefore = await getAccount(connection, userALst);
+      await redeemAsol(userA, userALst, userAAsol, redeemAmount, new BN(100_000));
+      const userALstAfter = await getAccount(connection, userALst);
+      const userAReceived = Number(userALstAfter.amount) - Number(userALstBefore.amount);
+
+      const userBLstBefore = await getAccount(connection, userBLst);
+      await redeemAsol(userB, userBLst, userBAsol, redeemAmount, new BN(100_000));
+      const userBLstAfter = await getAccount(connection, userBLst);
+      const userBReceived = Number(userBLstAfter.amount) - Number(userBLstBefore.amount);
+
+      const diff = Math.abs(userAReceived - userBReceived);
+      const tolerance = userAReceived * 0.01;
+
+      console.log(`  User A received: ${userAReceived} LST`);
+      console.log(`  User B received: ${userBReceived} LST`);
+
+      expect(diff).to.be.lessThan(tolerance);
+    });
+  });
+
+  describe("18. Supply Synchronization Invariant", () => {
+    it("On-chain mint supply matches GlobalState tracking", async () => {
       const state = await getGlobalState();
 
-      // Total LST should be 10 (aSOL) + 5 (amUSD) = 15 LST
-      expect(state.totalLstAmount.toNumber()).to.equal(15 * LAMPORTS_PER_SOL);
+      const amusdMintInfo = await getMint(connection, protocolState.amusdMint.publicKey);
+      const asolMintInfo = await getMint(connection, protocolState.asolMint.publicKey);
 
-      // amUSD supply should be > 0
-      expect(state.amusdSupply.toNumber()).to.be.greaterThan(0);
+      expect(Number(amusdMintInfo.supply)).to.equal(state.amusdSupply.toNumber());
+      expect(Number(asolMintInfo.supply)).to.equal(state.asolSupply.toNumber());
 
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
+      console.log(`  amUSD: state=${state.amusdSupply.toNumber()}, mint=${amusdMintInfo.supply}`);
+      console.log(`  aSOL: state=${state.asolSupply.toNumber()}, mint=${asolMintInfo.supply}`);
+    });
 
-      // Allow small rounding tolerance
-      const diff = tvl.sub(liability.add(equity)).abs();
-      expect(diff.toNumber()).to.be.lessThan(1000); // < 1000 lamports tolerance
+    it("Vault balance matches GlobalState LST tracking", async () => {
+      const state = await getGlobalState();
+      const vaultInfo = await getAccount(connection, protocolState.vault);
+
+      expect(Number(vaultInfo.amount)).to.equal(state.totalLstAmount.toNumber());
+
+      console.log(`  Vault: ${vaultInfo.amount}, State: ${state.totalLstAmount.toNumber()}`);
     });
   });
 
-  describe("4. CR Safety Checks", () => {
-    it("Rejects amUSD mint when CR would fall below minimum", async () => {
+  describe("19. First Depositor Attack Prevention", () => {
+    it("First aSOL minter cannot manipulate NAV for subsequent depositors", async () => {
+      // This test verifies the "1 aSOL = 1 SOL" first-mint rule
+      // prevents the classic vault inflation attack
 
-      const userSetup = await setupUser(1000);
-      user2 = userSetup.user;
-      user2LstAccount = userSetup.lstAccount;
-      user2AmusdAccount = userSetup.amusdAccount;
-      user2AsolAccount = userSetup.asolAccount;
+      // In a fresh protocol, verify first mint uses 1:1 rate
+      const state = await getGlobalState();
 
-      // Try to mint a huge amount that would tank CR
-      const hugeAmount = new BN(500 * LAMPORTS_PER_SOL);
-      const minAmusdOut = new BN(1);
+      // First mint should give sol_value * (1 - fee) aSOL
+      // NOT based on manipulatable NAV
+      console.log(`  First mint rule: 1 aSOL = 1 SOL value (minus fees)`);
+      console.log(`  This prevents donation attacks that inflate NAV`);
+    });
+  });
+
+  describe("20. Zero Amount Edge Cases", () => {
+    it("Rejects zero LST deposit for aSOL", async () => {
+      const userSetup = await setupUser(10);
 
       try {
-        await mintAmUSD(user2, user2LstAccount, user2AmusdAccount, hugeAmount, minAmusdOut);
-        expect.fail("Should have rejected due to CR violation");
+        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          new BN(0), new BN(0));
+        expect.fail("Should reject zero deposit");
       } catch (err: any) {
-        expect(err.toString()).to.include("CollateralRatioTooLow");
+        expect(err.toString()).to.include("ZeroAmount");
       }
     });
 
-    it("Allows amUSD mint when CR stays above minimum", async () => {
-      // First add more equity to have room for debt
-      const equityAmount = new BN(50 * LAMPORTS_PER_SOL);
-      await mintAsol(user2, user2LstAccount, user2AsolAccount, equityAmount, new BN(1));
-
-      // Now mint a smaller amUSD amount
-      const smallAmount = new BN(5 * LAMPORTS_PER_SOL);
-      const crBefore = await calculateCR();
-
-      await mintAmUSD(user2, user2LstAccount, user2AmusdAccount, smallAmount, new BN(1));
+    it("Rejects zero LST deposit for amUSD", async () => {
+      const userSetup = await setupUser(10);
 
-      const crAfter = await calculateCR();
-      expect(crAfter.gte(MIN_CR_BPS)).to.be.true;
-      console.log(`  CR after small mint: ${crAfter.toNumber()} bps`);
+      try {
+        await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+          new BN(0), new BN(0));
+        expect.fail("Should reject zero deposit");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ZeroAmount");
+      }
     });
-  });
-
 
-  describe("5. aSOL Minting Improves CR", () => {
-    it("Minting aSOL increases collateral ratio", async () => {
-      const crBefore = await calculateCR();
+    it("Rejects zero aSOL redemption", async () => {
+      const userSetup = await setupUser(10);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
 
-      // Mint aSOL (injects equity without adding debt)
-      const lstAmount = new BN(20 * LAMPORTS_PER_SOL);
-      await mintAsol(user2, user2LstAccount, user2AsolAccount, lstAmount, new BN(1));
+      try {
+        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          new BN(0), new BN(100_000));
+        expect.fail("Should reject zero redemption");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ZeroAmount");
+      }
+    });
 
-      const crAfter = await calculateCR();
+    it("Rejects zero amUSD redemption", async () => {
+      const userSetup = await setupUser(50);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(20 * LAMPORTS_PER_SOL), new BN(1));
+      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+        new BN(5 * LAMPORTS_PER_SOL), new BN(1));
 
-      // CR should improve
-      expect(crAfter.gt(crBefore)).to.be.true;
-      console.log(`  CR improved from ${crBefore.toNumber()} to ${crAfter.toNumber()} bps`);
+      try {
+        await redeemAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+          new BN(0), new BN(100_000));
+        expect.fail("Should reject zero redemption");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ZeroAmount");
+      }
     });
   });
 
-  describe("6. Redemptions", () => {
-    it("Redeems amUSD and returns LST", async () => {
-      const userAmusdBalanceBefore = await getAccount(connection, user1AmusdAccount);
-      const amusdAmount = new BN(Number(userAmusdBalanceBefore.amount) / 2); // Redeem half
-
-      const userLstBalanceBefore = await getAccount(connection, user1LstAccount);
-      const minLstOut = new BN(10_000_000); // 0.01 LST minimum
+  describe("21. Fee Accumulation Verification", () => {
+    it("Treasury receives aSOL minting fees", async () => {
+      const state = await getGlobalState();
+      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
 
-      await redeemAmUSD(user1, user1LstAccount, user1AmusdAccount, amusdAmount, minLstOut);
+      const balanceBefore = await getAccount(connection, treasuryAsolAccount)
+        .then(acc => Number(acc.amount))
+        .catch(() => 0);
 
-      const userAmusdBalanceAfter = await getAccount(connection, user1AmusdAccount);
-      const userLstBalanceAfter = await getAccount(connection, user1LstAccount);
+      const userSetup = await setupUser(20);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
 
-      // amUSD balance should decrease
-      expect(Number(userAmusdBalanceAfter.amount)).to.be.lessThan(Number(userAmusdBalanceBefore.amount));
+      const balanceAfter = await getAccount(connection, treasuryAsolAccount)
+        .then(acc => Number(acc.amount));
 
-      // LST balance should increase
-      expect(Number(userLstBalanceAfter.amount)).to.be.greaterThan(Number(userLstBalanceBefore.amount));
+      const feeReceived = balanceAfter - balanceBefore;
+      expect(feeReceived).to.be.greaterThan(0);
 
-      console.log(`  Redeemed ${amusdAmount.toNumber() / 1e6} amUSD`);
-      console.log(`  Received ${(Number(userLstBalanceAfter.amount) - Number(userLstBalanceBefore.amount)) / 1e9} LST`);
+      console.log(`  Treasury aSOL fee received: ${feeReceived / 1e9} aSOL`);
     });
 
-    it("amUSD redemption always improves or maintains CR", async () => {
-      const crBefore = await calculateCR();
+    it("Treasury receives aSOL redemption fees", async () => {
+      const state = await getGlobalState();
+      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
 
-      const userAmusdBalance = await getAccount(connection, user1AmusdAccount);
-      const amusdAmount = new BN(Number(userAmusdBalance.amount)); // Redeem all
-      const minLstOut = new BN(10_000_000);
+      const balanceBefore = await getAccount(connection, treasuryAsolAccount)
+        .then(acc => Number(acc.amount))
+        .catch(() => 0);
 
-      if (amusdAmount.gt(new BN(0))) {
-        await redeemAmUSD(user1, user1LstAccount, user1AmusdAccount, amusdAmount, minLstOut);
+      const userSetup = await setupUser(30);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
 
-        const crAfter = await calculateCR();
-        expect(crAfter.gte(crBefore)).to.be.true;
-      }
-    });
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(Number(userAsolBalance.amount) / 2), new BN(100_000));
 
-    it("Redeems aSOL at NAV", async () => {
-      const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const nav = computeAsolNav(tvl, liability, state.asolSupply);
-      console.log(`  Current aSOL NAV: ${nav.toNumber() / 1e9} SOL`);
+      const balanceAfter = await getAccount(connection, treasuryAsolAccount)
+        .then(acc => Number(acc.amount));
 
-      const userAsolBalance = await getAccount(connection, user1AsolAccount);
-      const asolAmount = new BN(Number(userAsolBalance.amount) / 2); // Redeem half
-      const minLstOut = new BN(10_000_000);
+      const feeReceived = balanceAfter - balanceBefore;
+      expect(feeReceived).to.be.greaterThan(0);
 
-      if (asolAmount.gt(new BN(0))) {
-        const userLstBalanceBefore = await getAccount(connection, user1LstAccount);
+      console.log(`  Treasury aSOL redemption fee received: ${feeReceived / 1e9} aSOL`);
+    });
 
-        await redeemAsol(user1, user1LstAccount, user1AsolAccount, asolAmount, minLstOut);
+    it("Treasury receives amUSD redemption fees", async () => {
+      const state = await getGlobalState();
+      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: state.treasury,
+      });
 
-        const userLstBalanceAfter = await getAccount(connection, user1LstAccount);
-        const lstReceived = Number(userLstBalanceAfter.amount) - Number(userLstBalanceBefore.amount);
+      const balanceBefore = await getAccount(connection, treasuryAmusdAccount)
+        .then(acc => Number(acc.amount))
+        .catch(() => 0);
 
-        console.log(`  Redeemed ${asolAmount.toNumber() / 1e9} aSOL`);
-        console.log(`  Received ${lstReceived / 1e9} LST`);
-      }
-    });
-  });
+      // Keep protocol clearly solvent so redeem fee is non-zero.
+      const userSetup = await setupUser(120);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(50 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
+      await mintAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(2 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
 
-  describe("7. NAV Behavior Under Price Stress", () => {
-    it("aSOL NAV decreases when SOL price drops", async () => {
-      // Get initial NAV
-      let state = await getGlobalState();
-      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const navBefore = computeAsolNav(tvl, liability, state.asolSupply);
+      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
+      const redeemAmount = new BN(Math.floor(Number(userAmusdBalance.amount) / 2));
 
-      // Simulate 20% price drop (SOL goes from $100 to $80)
-      const newPrice = new BN(80_000_000); // $80
-      await updateMockPrices(newPrice, MOCK_LST_TO_SOL_RATE);
+      await redeemAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        redeemAmount,
+        new BN(100_000)
+      );
 
-      // Get new NAV
-      state = await getGlobalState();
-      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const navAfter = computeAsolNav(tvl, liability, state.asolSupply);
+      const balanceAfter = await getAccount(connection, treasuryAmusdAccount)
+        .then(acc => Number(acc.amount));
 
-      console.log(`  NAV before price drop: ${navBefore.toNumber() / 1e9} SOL`);
-      console.log(`  NAV after 20% price drop: ${navAfter.toNumber() / 1e9} SOL`);
+      const feeReceived = balanceAfter - balanceBefore;
+      expect(feeReceived).to.be.greaterThan(0);
 
-      // NAV should decrease (equity absorbs the loss)
-      // Note: In SOL terms, liability increases when SOL price drops
-      // because same USD debt = more SOL needed to repay
+      console.log(`  Treasury amUSD redemption fee received: ${feeReceived / 1e6} amUSD`);
     });
 
-    it("aSOL NAV goes to zero when TVL < Liability (insolvency)", async () => {
-      // Simulate extreme crash (SOL goes to $30)
-      const crashPrice = new BN(30_000_000); // $30
-      await updateMockPrices(crashPrice, MOCK_LST_TO_SOL_RATE);
+  });
 
-      const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
+  describe("22. Operation Counter Monotonicity", () => {
+    it("Operation counter increments on every state change", async () => {
+      const stateBefore = await getGlobalState();
+      const counterBefore = stateBefore.operationCounter.toNumber();
 
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
-      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
+      const userSetup = await setupUser(10);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
 
-      // If insolvent, equity should be 0
-      if (tvl.lt(liability)) {
-        expect(equity.toNumber()).to.equal(0);
-        console.log("  Protocol is insolvent - equity capped at 0");
-      }
+      const stateAfter = await getGlobalState();
+      const counterAfter = stateAfter.operationCounter.toNumber();
 
-      // Reset price for other tests
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+      expect(counterAfter).to.equal(counterBefore + 1);
+      console.log(`  Counter: ${counterBefore} → ${counterAfter}`);
     });
   });
 
-
-  describe("8. Emergency Pause", () => {
-    it("Admin can pause minting", async () => {
+  describe("23. Redeem Pause Security", () => {
+    it("Admin can pause redemptions independently", async () => {
       await program.methods
-        .emergencyPause(true, false)
+        .emergencyPause(false, true)
         .accounts({
           authority: protocolState.authority.publicKey,
           globalState: protocolState.globalState,
@@ -1073,20 +2649,23 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
         .rpc();
 
       const state = await getGlobalState();
-      expect(state.mintPaused).to.be.true;
-      expect(state.redeemPaused).to.be.false;
+      expect(state.mintPaused).to.be.false;
+      expect(state.redeemPaused).to.be.true;
     });
 
-    it("Minting is rejected when paused", async () => {
+    it("Redemption rejected when paused", async () => {
+      const userSetup = await setupUser(10);
+
+      // User already has tokens from previous tests
       try {
-        await mintAsol(user2, user2LstAccount, user2AsolAccount, new BN(1e9), new BN(1));
-        expect.fail("Should have rejected due to pause");
+        await redeemAsol(user1, user1LstAccount, user1AsolAccount,
+          new BN(1000000), new BN(100_000));
+        expect.fail("Should reject when paused");
       } catch (err: any) {
-        expect(err.toString()).to.include("MintPaused");
+        expect(err.toString()).to.include("RedeemPaused");
       }
-    });
 
-    it("Admin can unpause", async () => {
+      // Unpause
       await program.methods
         .emergencyPause(false, false)
         .accounts({
@@ -1096,811 +2675,968 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
         })
         .signers([protocolState.authority])
         .rpc();
-
-      const state = await getGlobalState();
-      expect(state.mintPaused).to.be.false;
     });
   });
 
-  describe("9. Slippage Protection", () => {
-    it("Rejects mint when output below minimum", async () => {
-      const lstAmount = new BN(1 * LAMPORTS_PER_SOL);
-      const unreasonablyHighMin = new BN(1000 * LAMPORTS_PER_SOL); // Impossible
+  describe("24. Insufficient Balance Handling", () => {
+    it("Rejects redemption exceeding user balance", async () => {
+      const userSetup = await setupUser(10);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+
+      const userBalance = await getAccount(connection, userSetup.asolAccount);
+      const tooMuch = new BN(Number(userBalance.amount) * 2);
 
       try {
-        await mintAsol(user2, user2LstAccount, user2AsolAccount, lstAmount, unreasonablyHighMin);
-        expect.fail("Should have rejected due to slippage");
+        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          tooMuch, new BN(100_000));
+        expect.fail("Should reject exceeding balance");
       } catch (err: any) {
-        expect(err.toString()).to.include("SlippageExceeded");
+        expect(err.toString()).to.include("InsufficientSupply");
       }
     });
-  });
-
-
-  describe("10. Admin Parameter Updates", () => {
-    it("Admin can update risk parameters", async () => {
-      const newMinCr = new BN(14_000); // 140%
-      const newTargetCr = new BN(16_000); // 160%
-
-      await program.methods
-        .updateParameters(newMinCr, newTargetCr)
-        .accounts({
-          authority: protocolState.authority.publicKey,
-          globalState: protocolState.globalState,
-          clock: SYSVAR_CLOCK_PUBKEY,
-        })
-        .signers([protocolState.authority])
-        .rpc();
 
-      const state = await getGlobalState();
-      expect(state.minCrBps.toNumber()).to.equal(newMinCr.toNumber());
-      expect(state.targetCrBps.toNumber()).to.equal(newTargetCr.toNumber());
-    });
+    it("Rejects deposit exceeding user LST balance", async () => {
+      const userSetup = await setupUser(1); // Only 1 LST
 
-    it("Non-admin cannot update parameters", async () => {
       try {
-        await program.methods
-          .updateParameters(new BN(12_000), new BN(14_000))
-          .accounts({
-            authority: user1.publicKey,
-            globalState: protocolState.globalState,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          })
-          .signers([user1])
-          .rpc();
-        expect.fail("Should have rejected non-admin");
+        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+          new BN(10 * LAMPORTS_PER_SOL), new BN(1)); // Try to deposit 10 LST
+        expect.fail("Should reject exceeding balance");
       } catch (err: any) {
-        // Expected - constraint violation
-        expect(err.toString()).to.include("ConstraintHasOne");
+        expect(err.toString()).to.include("InsufficientCollateral");
       }
     });
   });
 
+  describe("25. aSOL Redemption CR Impact", () => {
+    it("aSOL redemption enforces post-state minimum CR", async () => {
+      const userSetup = await setupUser(250);
 
-  describe("11. Balance Sheet Invariant", () => {
-    it("TVL always equals Liability + Equity", async () => {
-      const state = await getGlobalState();
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(50 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
 
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
+      await mintAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(120 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
 
-      const total = liability.add(equity);
-      const diff = tvl.sub(total).abs();
+      const crBefore = await calculateCR();
+      console.log(`  CR before aSOL redemption: ${crBefore.toNumber()} bps`);
 
-      // Allow small tolerance due to rounding
-      const tolerance = BN.max(tvl.div(new BN(10_000)), new BN(1000)); // 1 bps or 1000 lamports
-      expect(diff.lte(tolerance)).to.be.true;
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      const largeRedemption = new BN(Math.floor(Number(userAsolBalance.amount) * 0.8)); // 80%
 
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
-      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
-      console.log(`  Difference: ${diff.toNumber()} lamports`);
-    });
-  });
+      try {
+        await redeemAsol(
+          userSetup.user,
+          userSetup.lstAccount,
+          userSetup.asolAccount,
+          largeRedemption,
+          new BN(100_000)
+        );
 
-  describe("12. Edge Case: Insolvency Protection", () => {
-    it("Rejects aSOL redemption when NAV is zero (protocol insolvency)", async () => {
-      // Reset price first
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+        const crAfter = await calculateCR();
+        console.log(`  CR after aSOL redemption: ${crAfter.toNumber()} bps`);
 
-      // We need to create conditions where TVL < Liability
-      const userSetup = await setupUser(100);
-      const testUser = userSetup.user;
+        // If redemption succeeds, CR-post gate must still hold.
+        expect(crAfter.gte(MIN_CR_BPS)).to.be.true;
+        expect(crAfter.lte(crBefore)).to.be.true;
+      } catch (err: any) {
+        // CR gate is now a valid/expected blocker.
+        const expectedErrors = [
+          "CollateralRatioTooLow",
+          "InsolventProtocol",
+          "InsufficientCollateral",
+        ];
 
-      // Deposit equity
-      await mintAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(20 * LAMPORTS_PER_SOL), new BN(1));
+        const matched = expectedErrors.some((e) => err.toString().includes(e));
+        expect(matched).to.be.true;
 
-      // Add substantial debt
-      await mintAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+        if (err.toString().includes("CollateralRatioTooLow")) {
+          console.log("  aSOL redemption correctly blocked by CR_post gate");
+        } else {
+          console.log("  aSOL redemption blocked by stronger safety condition");
+        }
+      }
+    });
+  });
 
-      const stateBefore = await getGlobalState();
-      const tvlBefore = computeTvlSol(stateBefore.totalLstAmount, stateBefore.mockLstToSolRate);
-      const liabilityBefore = computeLiabilitySol(stateBefore.amusdSupply, stateBefore.mockSolPriceUsd);
-      console.log(`  Before crash - TVL: ${tvlBefore.toNumber() / 1e9} SOL, Liability: ${liabilityBefore.toNumber() / 1e9} SOL`);
 
-      // Calculate the price needed to make TVL < Liability
+  describe("26. Leverage Calculation Verification", () => {
+    it("Leverage increases as debt increases", async () => {
       const state = await getGlobalState();
-      const tvlAtCurrentPrice = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-
-      // Price that would make liability = 2x TVL (guaranteed insolvency)
-      const insolvencyPrice = state.amusdSupply
-        .mul(SOL_PRECISION)
-        .div(tvlAtCurrentPrice.mul(new BN(2)));
-
-      // Use this calculated price or a very low price
-      const crashPrice = BN.max(insolvencyPrice, new BN(1_000_000)); // At least $1
-
-      await updateMockPrices(crashPrice, MOCK_LST_TO_SOL_RATE);
-
-      const stateAfterCrash = await getGlobalState();
-      const tvlAfter = computeTvlSol(stateAfterCrash.totalLstAmount, stateAfterCrash.mockLstToSolRate);
-      const liabilityAfter = computeLiabilitySol(stateAfterCrash.amusdSupply, stateAfterCrash.mockSolPriceUsd);
-      console.log(`  After crash - TVL: ${tvlAfter.toNumber() / 1e9} SOL, Liability: ${liabilityAfter.toNumber() / 1e9} SOL`);
-
-      const isInsolvent = tvlAfter.lt(liabilityAfter);
-      console.log(`  Insolvent: ${isInsolvent}`);
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
 
-      if (isInsolvent) {
-        // Verify NAV is zero or negative
-        const nav = computeAsolNav(tvlAfter, liabilityAfter, stateAfterCrash.asolSupply);
-        expect(nav.toNumber()).to.equal(0);
-        console.log(`  NAV during insolvency: ${nav.toNumber()} (expected 0)`);
+      // Leverage = TVL / Equity
+      const leverage = equity.isZero() ? new BN(0) : tvl.mul(new BN(100)).div(equity);
 
-        // Now test redemption rejection
-        const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-        if (Number(userAsolBalance.amount) > 0) {
-          try {
-            await redeemAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
-              new BN(Number(userAsolBalance.amount)), new BN(1));
-            expect.fail("Should reject redemption when insolvent");
-          } catch (err: any) {
-            // Check for InsolventProtocol error (error code 6007)
-            expect(
-              err.toString().includes("InsolventProtocol") ||
-              err.toString().includes("6007") ||
-              err.toString().includes("Protocol is insolvent")
-            ).to.be.true;
-            console.log("  ✓ Correctly rejected aSOL redemption during insolvency");
-          }
-        }
-      } else {
-        // Protocol has too much accumulated equity - the test condition couldn't be created
-        // This is actually a GOOD thing - it means the protocol is well-capitalized
-        // We'll skip this specific assertion and document why
-        console.log("  Protocol remained solvent (well-capitalized from previous tests)");
-        console.log("  This is expected behavior - accumulated equity protects against insolvency");
-        console.log("  ✓ Test validates protocol handles the scenario correctly");
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
+      console.log(`  Leverage: ${leverage.toNumber() / 100}x`);
 
-        // Still verify the NAV calculation works
-        const nav = computeAsolNav(tvlAfter, liabilityAfter, stateAfterCrash.asolSupply);
-        console.log(`  NAV while solvent: ${nav.toNumber() / 1e9} SOL`);
-        expect(nav.toNumber()).to.be.greaterThan(0);
+      // Leverage should be >= 1x (100)
+      if (!equity.isZero()) {
+        expect(leverage.toNumber()).to.be.gte(100);
       }
-
-      // Reset price for subsequent tests
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
     });
+  });
 
-    it("amUSD redemption still works during insolvency (priority exit)", async () => {
-      // Reset price first to ensure clean state
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+  describe("27. Version Validation", () => {
+    it("Protocol correctly reports version 1", async () => {
+      const state = await getGlobalState();
+      expect(state.version).to.equal(1);
+    });
+  });
 
-      // amUSD holders should ALWAYS be able to exit (senior tranche priority)
-      const userSetup = await setupUser(100);
-      const testUser = userSetup.user;
+  describe("28. Maximum Value Boundaries", () => {
+    it("Handles large but valid deposit amounts", async () => {
+      const userSetup = await setupUser(1000);
+      const largeAmount = new BN(500 * LAMPORTS_PER_SOL);
 
-      // Create healthy protocol state first
-      await mintAsol(testUser, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(30 * LAMPORTS_PER_SOL), new BN(1));
-      await mintAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
-        new BN(5 * LAMPORTS_PER_SOL), new BN(1));
+      // Should succeed
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        largeAmount, new BN(1));
 
-      // Moderate price drop 
-      // At $50 SOL with 30 LST equity + 5 LST debt, should still be solvent
-      await updateMockPrices(new BN(70_000_000), MOCK_LST_TO_SOL_RATE); // $70 SOL (not extreme)
+      const balance = await getAccount(connection, userSetup.asolAccount);
+      expect(Number(balance.amount)).to.be.greaterThan(0);
+    });
+  });
 
+  describe("29. Final State Integrity Check", () => {
+    it("Protocol state is consistent after all tests", async () => {
       const state = await getGlobalState();
+
+      // Verify all invariants hold
       const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
       const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL, Liability: ${liability.toNumber() / 1e9} SOL`);
+      const equity = computeEquitySol(tvl, liability);
 
-      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
-      const smallRedemption = new BN(Number(userAmusdBalance.amount) / 4);
+      // Balance sheet: TVL = Liability + Equity
+      const total = liability.add(equity);
+      const diff = tvl.sub(total).abs();
+      const tolerance = BN.max(tvl.div(new BN(10_000)), new BN(1000));
 
-      if (smallRedemption.gt(new BN(0))) {
+      expect(diff.lte(tolerance)).to.be.true;
 
-        try {
-          await redeemAmUSD(testUser, userSetup.lstAccount, userSetup.amusdAccount,
-            smallRedemption, new BN(100_000)); // reasonable min_lst_out
-          console.log("  amUSD redemption succeeded during price stress");
-        } catch (err: any) {
+      // Supply sync
+      const amusdMint = await getMint(connection, protocolState.amusdMint.publicKey);
+      const asolMint = await getMint(connection, protocolState.asolMint.publicKey);
+      const vault = await getAccount(connection, protocolState.vault);
 
-          expect(err.toString()).to.not.include("InsolventProtocol");
-        }
-      }
+      expect(Number(amusdMint.supply)).to.equal(state.amusdSupply.toNumber());
+      expect(Number(asolMint.supply)).to.equal(state.asolSupply.toNumber());
+      expect(Number(vault.amount)).to.equal(state.totalLstAmount.toNumber());
 
-      // Reset price
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+      console.log("\n=== FINAL PROTOCOL STATE ===");
+      console.log(`  Version: ${state.version}`);
+      console.log(`  Operations: ${state.operationCounter.toNumber()}`);
+      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
+      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
+      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
+      console.log(`  amUSD Supply: ${state.amusdSupply.toNumber() / 1e6} USD`);
+      console.log(`  aSOL Supply: ${state.asolSupply.toNumber() / 1e9} aSOL`);
+      console.log(`  Vault LST: ${state.totalLstAmount.toNumber() / 1e9} LST`);
+      console.log("===========================\n");
     });
   });
 
-  describe("13. Dust Attack Prevention", () => {
-    before(async () => {
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+  describe("30. Flash Loan / Same-Slot Attack Prevention", () => {
+    it("Multiple operations in same transaction are blocked by CPI check", async () => {
+      // The protocol checks instruction index = 0, preventing CPI calls
+      // This test verifies the protection exists via error on malformed context
+      const userSetup = await setupUser(50);
+
+      // Single operation should work
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+
+      // The InvalidCPIContext error is triggered when instruction_index != 0
+      console.log("  ✓ Protocol uses instruction sysvar to prevent CPI attacks");
     });
+  });
 
-    it("Rejects LST deposit below minimum threshold", async () => {
-      const userSetup = await setupUser(1);
-      const dustAmount = new BN(10_000); // 0.00001 SOL - below MIN_LST_DEPOSIT
+  describe("31. Price Staleness Protection", () => {
+    it("Protocol should validate oracle freshness (mock implementation)", async () => {
 
-      try {
+      const state = await getGlobalState();
+
+      expect(state.mockSolPriceUsd.toNumber()).to.be.greaterThan(0);
+      expect(state.mockLstToSolRate.toNumber()).to.be.greaterThan(0);
+
+      console.log("  Note: Production should add staleness checks to oracle integration");
+    });
+  });
+
+  describe("32. Precision Loss Accumulation", () => {
+    it("Many small operations don't cause significant precision drift", async () => {
+      const userSetup = await setupUser(100);
+
+      const stateBefore = await getGlobalState();
+      const iterations = 5;
+      const smallAmount = new BN(0.1 * LAMPORTS_PER_SOL);
+
+      // Perform many small mints
+      for (let i = 0; i < iterations; i++) {
         await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          dustAmount, new BN(1));
-        expect.fail("Should reject dust deposit");
-      } catch (err: any) {
-        expect(err.toString()).to.include("AmountTooSmall");
+          smallAmount, new BN(1));
       }
-    });
 
-    it("Rejects aSOL redemption that would output dust LST", async () => {
-      // Ensure price is normal
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+      const stateAfter = await getGlobalState();
 
-      const userSetup = await setupUser(10);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+      // Verify balance sheet still holds
+      const tvl = computeTvlSol(stateAfter.totalLstAmount, stateAfter.mockLstToSolRate);
+      const liability = computeLiabilitySol(stateAfter.amusdSupply, stateAfter.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
 
-      const tinyAsol = new BN(1000); // 0.000001 aSOL
+      const diff = tvl.sub(liability.add(equity)).abs();
+      expect(diff.toNumber()).to.be.lessThan(iterations * 1000); // Allow 1000 lamports per op
 
-      try {
-        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          tinyAsol, new BN(1)); // min_lst_out = 1 is below threshold
-        expect.fail("Should reject dust output");
-      } catch (err: any) {
-        // Should fail for AmountTooSmall (dust) not InsolventProtocol
-        expect(err.toString()).to.include("AmountTooSmall");
-      }
+      console.log(`  ${iterations} operations, precision drift: ${diff.toNumber()} lamports`);
     });
+  });
 
-    it("Rejects mint when output tokens would be below minimum", async () => {
-      // Ensure price is normal
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+  describe("33. Account Validation Security", () => {
+    it("Rejects wrong LST mint", async () => {
+      const userSetup = await setupUser(10);
 
-      const userSetup = await setupUser(1);
+      const wrongMint = await createMint(
+        connection,
+        protocolState.authority,
+        protocolState.authority.publicKey,
+        null,
+        9,
+        undefined,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
 
-      // Amount that after fees would produce < MIN_ASOL_MINT
-      const minimalAmount = new BN(500_000); // 0.0005 SOL
+      const wrongMintAccount = await getOrCreateAssociatedTokenAccount(
+        connection,
+        userSetup.user,
+        wrongMint,
+        userSetup.user.publicKey
+      );
+
+      // Fund the wrong mint account
+      await mintTo(
+        connection,
+        protocolState.authority,
+        wrongMint,
+        wrongMintAccount.address,
+        protocolState.authority,
+        10 * LAMPORTS_PER_SOL
+      );
 
       try {
-        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          minimalAmount, new BN(1));
-        expect.fail("Should reject when output too small");
+        // Try to deposit wrong LST
+        const [vaultAuthority] = getVaultAuthorityPda();
+
+        await program.methods
+          .mintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
+          .accounts({
+            user: userSetup.user.publicKey,
+            globalState: protocolState.globalState,
+            asolMint: protocolState.asolMint.publicKey,
+            userAsolAccount: userSetup.asolAccount,
+            treasuryAsolAccount: await anchor.utils.token.associatedAddress({
+              mint: protocolState.asolMint.publicKey,
+              owner: (await getGlobalState()).treasury,
+            }),
+            treasury: (await getGlobalState()).treasury,
+            userLstAccount: wrongMintAccount.address, // WRONG ACCOUNT
+            vault: protocolState.vault,
+            vaultAuthority: vaultAuthority,
+            lstMint: wrongMint, // WRONG MINT
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          } as any)
+          .signers([userSetup.user])
+          .rpc();
+
+        expect.fail("Should reject wrong LST mint");
       } catch (err: any) {
-        expect(err.toString()).to.include("AmountTooSmall");
+        // Should fail with constraint error
+        expect(
+          err.toString().includes("ConstraintAddress") ||
+          err.toString().includes("constraint")
+        ).to.be.true;
+        console.log("  ✓ Wrong LST mint correctly rejected");
       }
-    });
+    })
   });
 
-  describe("14. Rounding Direction Security", () => {
-    it("Protocol always rounds in its favor on mints", async () => {
-      // Verify user gets rounded-down amount
-      const userSetup = await setupUser(10);
-      const oddAmount = new BN(1_000_000_007); // Odd number to test rounding
-
-      const stateBefore = await getGlobalState();
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        oddAmount, new BN(1));
-      const stateAfter = await getGlobalState();
+  describe("34. u64 Overflow Protection", () => {
+    it("Handles values near u64 max without overflow", async () => {
+      // Test that math functions don't overflow
+      const maxSafeAmount = new BN("18446744073709551615"); // u64::MAX
+      const normalAmount = new BN(1_000_000_000);
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      const totalMinted = stateAfter.asolSupply.sub(stateBefore.asolSupply);
+      // computeTvlSol should handle large amounts
+      const largeTvl = computeTvlSol(maxSafeAmount.div(new BN(2)), SOL_PRECISION);
+      expect(largeTvl.toString()).to.not.equal("0");
 
-      // User + treasury should equal total minted (no tokens lost to rounding)
-      console.log(`  Total minted: ${totalMinted.toString()}`);
-      console.log(`  User received: ${userAsolBalance.amount.toString()}`);
+      console.log("  ✓ Math functions handle large values safely");
     });
+  });
 
-    it("Protocol always rounds in its favor on redemptions", async () => {
-      const userSetup = await setupUser(20);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+  describe("35. Authority Transfer Security", () => {
+    it("Authority transfer should require proper verification", async () => {
+      const state = await getGlobalState();
 
-      const oddRedemption = new BN(1_234_567_891); // Odd number
-      const userLstBefore = await getAccount(connection, userSetup.lstAccount);
+      // Verify authority is set correctly
+      expect(state.authority.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
 
-      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        oddRedemption, new BN(100_000));
+      // Non-authority cannot perform admin actions
+      const randomUser = Keypair.generate();
+      await airdropSol(randomUser.publicKey, 1);
 
-      const userLstAfter = await getAccount(connection, userSetup.lstAccount);
-      const lstReceived = Number(userLstAfter.amount) - Number(userLstBefore.amount);
+      try {
+        await program.methods
+          .updateParameters(new BN(10_000), new BN(12_000))
+          .accounts({
+            authority: randomUser.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([randomUser])
+          .rpc();
 
-      console.log(`  aSOL redeemed: ${oddRedemption.toString()}`);
-      console.log(`  LST received: ${lstReceived}`);
+        expect.fail("Should reject unauthorized access");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
+        console.log("  ✓ Authority access control enforced");
+      }
     });
   });
 
-  describe("15. Full Redemption (Bank Run Scenario)", () => {
-    it("Allows complete aSOL redemption when no debt exists", async () => {
-      const userSetup = await setupUser(50);
+  describe("36. Redemption Race Condition", () => {
+    it("Sequential redemptions are processed fairly", async () => {
+      const setupA = await setupUser(50);
+      const setupB = await setupUser(50);
+
+      // Both users deposit same amount
       const depositAmount = new BN(10 * LAMPORTS_PER_SOL);
+      await mintAsol(setupA.user, setupA.lstAccount, setupA.asolAccount, depositAmount, new BN(1));
+      await mintAsol(setupB.user, setupB.lstAccount, setupB.asolAccount, depositAmount, new BN(1));
 
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        depositAmount, new BN(1));
+      // Get balances
+      const aBalance = await getAccount(connection, setupA.asolAccount);
+      const bBalance = await getAccount(connection, setupB.asolAccount);
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      const fullBalance = new BN(Number(userAsolBalance.amount));
+      // Redeem same proportion
+      const aRedeem = new BN(Number(aBalance.amount) / 2);
+      const bRedeem = new BN(Number(bBalance.amount) / 2);
 
-      // Should be able to redeem entire balance
-      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        fullBalance, new BN(100_000));
+      const aLstBefore = await getAccount(connection, setupA.lstAccount);
+      const bLstBefore = await getAccount(connection, setupB.lstAccount);
 
-      const finalBalance = await getAccount(connection, userSetup.asolAccount);
-      expect(Number(finalBalance.amount)).to.equal(0);
-    });
+      await redeemAsol(setupA.user, setupA.lstAccount, setupA.asolAccount, aRedeem, new BN(100_000));
+      await redeemAsol(setupB.user, setupB.lstAccount, setupB.asolAccount, bRedeem, new BN(100_000));
 
-    it("Allows complete amUSD redemption", async () => {
-      const userSetup = await setupUser(100);
+      const aLstAfter = await getAccount(connection, setupA.lstAccount);
+      const bLstAfter = await getAccount(connection, setupB.lstAccount);
 
-      // Create equity buffer
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(50 * LAMPORTS_PER_SOL), new BN(1));
+      const aReceived = Number(aLstAfter.amount) - Number(aLstBefore.amount);
+      const bReceived = Number(bLstAfter.amount) - Number(bLstBefore.amount);
 
-      // Create debt
-      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+      // Both should receive roughly equal (within 1%)
+      const diff = Math.abs(aReceived - bReceived);
+      const tolerance = Math.max(aReceived, bReceived) * 0.01;
 
-      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
-      const fullBalance = new BN(Number(userAmusdBalance.amount));
+      expect(diff).to.be.lessThan(tolerance);
+      console.log(`  User A received: ${aReceived / 1e9} LST`);
+      console.log(`  User B received: ${bReceived / 1e9} LST`);
+      console.log(`  ✓ Sequential redemptions are fair`);
+    });
+  });
 
-      // Redeem all debt
-      await redeemAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-        fullBalance, new BN(100_000));
+  describe("37. Extreme Leverage Scenario", () => {
+    it("Protocol handles high leverage ratio correctly", async () => {
+      const userSetup = await setupUser(500);
 
-      const finalBalance = await getAccount(connection, userSetup.amusdAccount);
-      expect(Number(finalBalance.amount)).to.equal(0);
+      // Build up significant equity first - use LESS than before to leave room for debt
+      const equityDeposit = new BN(100 * LAMPORTS_PER_SOL);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        equityDeposit, new BN(1));
 
-      // Verify protocol state
+      // Check user's remaining LST balance
+      const userLstBalance = await getAccount(connection, userSetup.lstAccount);
+      const remainingLst = new BN(Number(userLstBalance.amount));
+
+      // Try to maximize debt while staying above CR
       const state = await getGlobalState();
-      console.log(`  Remaining amUSD supply: ${state.amusdSupply.toNumber()}`);
-    });
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
 
-    it("Prevents redemption that would leave protocol below minimum TVL", async () => {
-      const userSetup = await setupUser(10);
+      // Calculate max debt for target CR of 150%
+      // CR = TVL / Liability >= 1.5
+      // Liability <= TVL / 1.5
+      const maxLiability = tvl.mul(new BN(10_000)).div(new BN(15_000));
+      const currentLiability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const roomForDebt = maxLiability.sub(currentLiability);
 
-      // Make a very small deposit
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(0.002 * LAMPORTS_PER_SOL), new BN(1)); // Very small deposit
+      if (roomForDebt.gt(new BN(0))) {
+        // Convert room to LST terms
+        const additionalLst = roomForDebt.mul(SOL_PRECISION).div(state.mockLstToSolRate);
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+        // Use 50% of max to stay safe, and ensure we don't exceed user balance
+        const safeMintLst = BN.min(
+          additionalLst.mul(new BN(50)).div(new BN(100)), // 50% of max
+          remainingLst.sub(new BN(LAMPORTS_PER_SOL)) // Leave 1 LST buffer
+        );
 
-      // The protocol has a lot of TVL from other tests, so this small user's 
-      // redemption won't trigger BelowMinimumTVL.so we verify if the 
-      // protocol correctly handles small redemptions.
-      if (Number(userAsolBalance.amount) > 100) {
-        const smallAmount = new BN(Number(userAsolBalance.amount) - 100);
+        if (safeMintLst.gt(new BN(LAMPORTS_PER_SOL))) {
+          await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
+            safeMintLst, new BN(1));
 
-        try {
-          await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-            smallAmount, new BN(1));
-          // If it succeeds, check the state
-          const state = await getGlobalState();
-          const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-          console.log(`  Redemption succeeded, remaining TVL: ${tvl.toNumber() / 1e9} SOL`);
-          // Verify TVL is still above minimum or is zero
-          expect(tvl.toNumber() >= 1_000_000 || tvl.toNumber() === 0).to.be.true;
-        } catch (err: any) {
-          // Could fail for various reasons - dust, slippage, or BelowMinimumTVL
-          console.log(`  Redemption rejected: ${err.message?.substring(0, 50) || err.toString().substring(0, 50)}`);
-          const acceptableErrors = ["BelowMinimumTVL", "AmountTooSmall", "SlippageExceeded", "InsufficientCollateral"];
-          const hasAcceptableError = acceptableErrors.some(e => err.toString().includes(e));
-          expect(hasAcceptableError).to.be.true;
+          const crAfter = await calculateCR();
+          console.log(`  High leverage CR: ${crAfter.toNumber()} bps`);
+          expect(crAfter.gte(new BN(14_000))).to.be.true; // Above min CR
+        } else {
+          console.log("  Insufficient room for meaningful leverage test (protocol well-capitalized)");
         }
+      } else {
+        console.log("  No room for additional debt (protocol already at max leverage)");
       }
+
+      console.log("  ✓ High leverage scenario handled correctly");
     });
   });
 
-  describe("16. Oracle Price Manipulation Bounds", () => {
-    it("Rejects zero SOL price", async () => {
+  describe("38. Global State Singleton Enforcement", () => {
+    it("Cannot initialize protocol twice", async () => {
+      // Try to reinitialize with different params
+      const newAuthority = Keypair.generate();
+      await airdropSol(newAuthority.publicKey, 5);
+
+      const newAmusd = Keypair.generate();
+      const newAsol = Keypair.generate();
+
       try {
-        await updateMockPrices(new BN(0), MOCK_LST_TO_SOL_RATE);
-        expect.fail("Should reject zero price");
+        await program.methods
+          .initialize(
+            new BN(10_000),
+            new BN(12_000),
+            MOCK_SOL_PRICE_USD,
+            MOCK_LST_TO_SOL_RATE
+          )
+          .accounts({
+            authority: newAuthority.publicKey,
+            globalState: protocolState.globalState, // Same PDA
+            amusdMint: newAmusd.publicKey,
+            asolMint: newAsol.publicKey,
+            vault: protocolState.vault,
+            lstMint: protocolState.lstMint,
+            vaultAuthority: protocolState.vaultAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          } as any)
+          .signers([newAuthority, newAmusd, newAsol])
+          .rpc();
+
+        expect.fail("Should reject double initialization");
       } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
+        // Account already initialized
+        expect(
+          err.toString().includes("already in use") ||
+          err.toString().includes("Error") // Generic for already exists
+        ).to.be.true;
+        console.log("  ✓ Double initialization prevented");
       }
     });
+  });
 
-    it("Rejects zero LST rate", async () => {
+  describe("39. Parameter Bounds Validation", () => {
+    it("Rejects min_cr higher than target_cr", async () => {
       try {
-        await updateMockPrices(MOCK_SOL_PRICE_USD, new BN(0));
-        expect.fail("Should reject zero rate");
+        await program.methods
+          .updateParameters(new BN(20_000), new BN(15_000)) // min > target
+          .accounts({
+            authority: protocolState.authority.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([protocolState.authority])
+          .rpc();
+
+        expect.fail("Should reject invalid parameter bounds");
       } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
+        expect(
+          err.toString().includes("InvalidParameter") ||
+          err.toString().includes("6016") // Error code for InvalidParameter
+        ).to.be.true;
+        console.log("  ✓ Invalid parameter bounds rejected");
       }
     });
 
-    it("Handles extreme SOL price ($1M)", async () => {
-      await updateMockPrices(new BN(1_000_000_000_000), MOCK_LST_TO_SOL_RATE); // $1M
-
-      const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-
-      // At $1M/SOL, liability in SOL terms should be tiny
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Liability at $1M: ${liability.toNumber() / 1e9} SOL`);
+    it("Rejects CR below 100%", async () => {
+      try {
+        await program.methods
+          .updateParameters(new BN(9_000), new BN(10_000)) // 90% min CR
+          .accounts({
+            authority: protocolState.authority.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          })
+          .signers([protocolState.authority])
+          .rpc();
 
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+        expect.fail("Should reject CR below 100%");
+      } catch (err: any) {
+        // Either rejected or the program doesn't validate this
+        console.log(`  Result: ${err.toString().substring(0, 80)}`);
+      }
     });
+  });
 
-    it("Handles extreme SOL price drop ($0.01)", async () => {
-      await updateMockPrices(new BN(10_000), MOCK_LST_TO_SOL_RATE); // $0.01
-
+  describe("40. Comprehensive Final Audit Checks", () => {
+    it("All critical invariants hold after stress testing", async () => {
       const state = await getGlobalState();
+
+      // 1. Balance Sheet
       const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
       const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const equity = computeEquitySol(tvl, liability);
+      expect(tvl.gte(liability.add(equity).sub(new BN(10000)))).to.be.true;
+
+      // 2. Supply Sync
+      const amusdMint = await getMint(connection, protocolState.amusdMint.publicKey);
+      const asolMint = await getMint(connection, protocolState.asolMint.publicKey);
+      expect(Number(amusdMint.supply)).to.equal(state.amusdSupply.toNumber());
+      expect(Number(asolMint.supply)).to.equal(state.asolSupply.toNumber());
 
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Liability at $0.01: ${liability.toNumber() / 1e9} SOL`);
+      // 3. Vault Balance
+      const vault = await getAccount(connection, protocolState.vault);
+      expect(Number(vault.amount)).to.equal(state.totalLstAmount.toNumber());
 
+      // 4. Protocol is solvent (after resetting prices)
       await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
-    });
+      const finalState = await getGlobalState();
+      const finalTvl = computeTvlSol(finalState.totalLstAmount, finalState.mockLstToSolRate);
+      const finalLiability = computeLiabilitySol(finalState.amusdSupply, finalState.mockSolPriceUsd);
+      expect(finalTvl.gte(finalLiability)).to.be.true;
 
-    it("Handles LST appreciation (2x)", async () => {
-      await updateMockPrices(MOCK_SOL_PRICE_USD, new BN(2_000_000_000)); // 1 LST = 2 SOL
+      // 5. CR is healthy
+      const cr = computeCrBps(finalTvl, finalLiability);
+      if (!finalLiability.isZero()) {
+        expect(cr.gte(state.minCrBps)).to.be.true;
+      }
+
+      console.log("\n=== AUDIT CHECKLIST PASSED ===");
+      console.log("  ✓ Balance sheet invariant");
+      console.log("  ✓ Supply synchronization");
+      console.log("  ✓ Vault balance accuracy");
+      console.log("  ✓ Protocol solvency");
+      console.log("  ✓ Healthy collateral ratio");
+      console.log("==============================\n");
+    });
+  });
 
+  describe("41. Treasury Address Validation", () => {
+    it("Treasury receives fees to correct address", async () => {
       const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      expect(state.treasury.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
 
-      console.log(`  TVL with 2x LST rate: ${tvl.toNumber() / 1e9} SOL`);
+      const treasuryAsolAta = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
+      const treasuryLstAta = await anchor.utils.token.associatedAddress({
+        mint: protocolState.lstMint,
+        owner: state.treasury,
+      });
 
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
+      console.log("  ✓ Treasury address is correctly configured");
     });
   });
 
-  describe("17. Multi-User Fairness", () => {
-    let userA: Keypair;
-    let userB: Keypair;
-    let userALst: PublicKey;
-    let userBLst: PublicKey;
-    let userAAsol: PublicKey;
-    let userBAsol: PublicKey;
-
-    before(async () => {
-      const setupA = await setupUser(100);
-      const setupB = await setupUser(100);
-      userA = setupA.user;
-      userB = setupB.user;
-      userALst = setupA.lstAccount;
-      userBLst = setupB.lstAccount;
-      userAAsol = setupA.asolAccount;
-      userBAsol = setupB.asolAccount;
-    });
+  describe("42. Concurrent User Stress Test", () => {
+    it("Handles multiple users minting simultaneously", async () => {
+      // Setup 3 users
+      const users = await Promise.all([
+        setupUser(50),
+        setupUser(50),
+        setupUser(50),
+      ]);
 
-    it("Second depositor gets fair NAV after first deposit", async () => {
-      const depositAmount = new BN(10 * LAMPORTS_PER_SOL);
+      const stateBefore = await getGlobalState();
+      const supplyBefore = stateBefore.asolSupply;
 
-      // User A deposits first
-      await mintAsol(userA, userALst, userAAsol, depositAmount, new BN(1));
-      const userABalance = await getAccount(connection, userAAsol);
+      // All mint simultaneously (in parallel)
+      const mintAmount = new BN(5 * LAMPORTS_PER_SOL);
+      await Promise.all(users.map(u =>
+        mintAsol(u.user, u.lstAccount, u.asolAccount, mintAmount, new BN(1))
+      ));
 
-      // User B deposits same amount after
-      await mintAsol(userB, userBLst, userBAsol, depositAmount, new BN(1));
-      const userBBalance = await getAccount(connection, userBAsol);
+      const stateAfter = await getGlobalState();
 
-      // Both should get roughly equal aSOL (minus small NAV drift)
-      const diff = Math.abs(Number(userABalance.amount) - Number(userBBalance.amount));
-      const tolerance = Number(userABalance.amount) * 0.01; // 1% tolerance
+      // Verify vault increased by total deposited LST
+      const expectedLstIncrease = new BN(15 * LAMPORTS_PER_SOL);
+      const actualLstIncrease = stateAfter.totalLstAmount.sub(stateBefore.totalLstAmount);
+      expect(actualLstIncrease.eq(expectedLstIncrease)).to.be.true;
 
-      console.log(`  User A aSOL: ${Number(userABalance.amount)}`);
-      console.log(`  User B aSOL: ${Number(userBBalance.amount)}`);
-      console.log(`  Difference: ${diff} (tolerance: ${tolerance})`);
+      // Supply should increase (exact amount depends on NAV and fees)
+      const actualIncrease = stateAfter.asolSupply.sub(supplyBefore);
+      expect(actualIncrease.gt(new BN(0))).to.be.true;
 
-      expect(diff).to.be.lessThan(tolerance);
+      console.log(`  ✓ 3 concurrent mints processed correctly`);
     });
+  });
 
-    it("Redemption NAV is consistent across users", async () => {
-      const redeemAmount = new BN(1 * LAMPORTS_PER_SOL);
+  describe("43. Minimum Output Guarantees", () => {
+    it("min_lst_out protects against sandwich attacks on redemption", async () => {
+      const userSetup = await setupUser(50);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
 
-      const userALstBefore = await getAccount(connection, userALst);
-      await redeemAsol(userA, userALst, userAAsol, redeemAmount, new BN(100_000));
-      const userALstAfter = await getAccount(connection, userALst);
-      const userAReceived = Number(userALstAfter.amount) - Number(userALstBefore.amount);
+      const balance = await getAccount(connection, userSetup.asolAccount);
+      const redeemAmount = new BN(Number(balance.amount) / 2);
 
-      const userBLstBefore = await getAccount(connection, userBLst);
-      await redeemAsol(userB, userBLst, userBAsol, redeemAmount, new BN(100_000));
-      const userBLstAfter = await getAccount(connection, userBLst);
-      const userBReceived = Number(userBLstAfter.amount) - Number(userBLstBefore.amount);
+      // Calculate expected output
+      const state = await getGlobalState();
+      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const nav = computeAsolNav(tvl, liability, state.asolSupply);
 
-      const diff = Math.abs(userAReceived - userBReceived);
-      const tolerance = userAReceived * 0.01;
+      const expectedSolValue = redeemAmount.mul(nav).div(SOL_PRECISION);
+      const expectedLst = expectedSolValue.mul(SOL_PRECISION).div(state.mockLstToSolRate);
+      const cr = computeCrBps(tvl, liability);
+      const feeBps = feeBpsIncreaseWhenLow(ASOL_REDEEM_FEE_BPS, cr, TARGET_CR_BPS);
+      const [expectedNet, _] = applyFee(expectedLst, feeBps);
 
-      console.log(`  User A received: ${userAReceived} LST`);
-      console.log(`  User B received: ${userBReceived} LST`);
+      // Set min_lst_out very close to expected (95%)
+      const tightMinOut = expectedNet.mul(new BN(95)).div(new BN(100));
 
-      expect(diff).to.be.lessThan(tolerance);
+      // Should succeed with reasonable min_out
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        redeemAmount, tightMinOut);
+
+      console.log("  ✓ Tight slippage protection works correctly");
     });
   });
 
-  describe("18. Supply Synchronization Invariant", () => {
-    it("On-chain mint supply matches GlobalState tracking", async () => {
-      const state = await getGlobalState();
+  describe("44. NAV Consistency Under Rapid Operations", () => {
+    it("NAV remains consistent across rapid mint/redeem cycles", async () => {
+      const userSetup = await setupUser(100);
 
-      const amusdMintInfo = await getMint(connection, protocolState.amusdMint.publicKey);
-      const asolMintInfo = await getMint(connection, protocolState.asolMint.publicKey);
+      // Capture initial state
+      let state = await getGlobalState();
+      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const navStart = computeAsolNav(tvl, liability, state.asolSupply);
 
-      expect(Number(amusdMintInfo.supply)).to.equal(state.amusdSupply.toNumber());
-      expect(Number(asolMintInfo.supply)).to.equal(state.asolSupply.toNumber());
+      // Rapid cycle: mint then redeem
+      const amount = new BN(5 * LAMPORTS_PER_SOL);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, amount, new BN(1));
 
-      console.log(`  amUSD: state=${state.amusdSupply.toNumber()}, mint=${amusdMintInfo.supply}`);
-      console.log(`  aSOL: state=${state.asolSupply.toNumber()}, mint=${asolMintInfo.supply}`);
-    });
+      const balance = await getAccount(connection, userSetup.asolAccount);
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
+        new BN(Number(balance.amount)), new BN(100_000));
 
-    it("Vault balance matches GlobalState LST tracking", async () => {
-      const state = await getGlobalState();
-      const vaultInfo = await getAccount(connection, protocolState.vault);
+      // NAV should be similar (small drift due to fees)
+      state = await getGlobalState();
+      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const navEnd = computeAsolNav(tvl, liability, state.asolSupply);
 
-      expect(Number(vaultInfo.amount)).to.equal(state.totalLstAmount.toNumber());
+      // NAV should not deviate by more than 1%
+      const drift = navEnd.sub(navStart).abs();
+      const tolerance = navStart.div(new BN(100));
 
-      console.log(`  Vault: ${vaultInfo.amount}, State: ${state.totalLstAmount.toNumber()}`);
+      expect(drift.lte(tolerance)).to.be.true;
+      console.log(`  NAV drift: ${drift.toNumber() / 1e9} SOL (tolerance: ${tolerance.toNumber() / 1e9} SOL)`);
+      console.log("  ✓ NAV consistent under rapid operations");
     });
   });
 
-  describe("19. First Depositor Attack Prevention", () => {
-    it("First aSOL minter cannot manipulate NAV for subsequent depositors", async () => {
-      // This test verifies the "1 aSOL = 1 SOL" first-mint rule
-      // prevents the classic vault inflation attack
-
-      // In a fresh protocol, verify first mint uses 1:1 rate
+  describe("45. Empty Protocol State Edge Cases", () => {
+    it("Handles redemption when only one user exists", async () => {
       const state = await getGlobalState();
 
-      // First mint should give sol_value * (1 - fee) aSOL
-      // NOT based on manipulatable NAV
-      console.log(`  First mint rule: 1 aSOL = 1 SOL value (minus fees)`);
-      console.log(`  This prevents donation attacks that inflate NAV`);
+      // Verify protocol can handle when supply approaches zero
+      if (state.asolSupply.gt(new BN(0)) && state.amusdSupply.eq(new BN(0))) {
+        console.log("  Protocol handles equity-only state correctly");
+      }
+
+      console.log("  ✓ Edge cases for low-activity protocol validated");
     });
   });
 
-  describe("20. Zero Amount Edge Cases", () => {
-    it("Rejects zero LST deposit for aSOL", async () => {
-      const userSetup = await setupUser(10);
+  describe("46. Fee Upper Bound Validation", () => {
+    it("Fees are within expected bounds", async () => {
+      // Verify fee constants are reasonable
+      expect(AMUSD_MINT_FEE_BPS).to.be.lessThanOrEqual(500); // Max 5%
+      expect(AMUSD_REDEEM_FEE_BPS).to.be.lessThanOrEqual(500);
+      expect(ASOL_MINT_FEE_BPS).to.be.lessThanOrEqual(500);
+      expect(ASOL_REDEEM_FEE_BPS).to.be.lessThanOrEqual(500);
 
-      try {
-        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          new BN(0), new BN(0));
-        expect.fail("Should reject zero deposit");
-      } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
-      }
+      console.log("  ✓ All fees within reasonable bounds (<=5%)");
     });
+  });
 
-    it("Rejects zero LST deposit for amUSD", async () => {
-      const userSetup = await setupUser(10);
+  describe("47. A5 Oracle Freshness", () => {
+    it("Rejects mint when oracle snapshot is stale", async () => {
+      const userSetup = await setupUser(25);
 
-      try {
-        await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-          new BN(0), new BN(0));
-        expect.fail("Should reject zero deposit");
-      } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
-      }
-    });
+      // Start from clean/fresh snapshots.
+      await resetAndSyncSnapshots();
 
-    it("Rejects zero aSOL redemption", async () => {
-      const userSetup = await setupUser(10);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+      const state = await getGlobalState();
+      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
 
-      try {
-        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          new BN(0), new BN(100_000));
-        expect.fail("Should reject zero redemption");
-      } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
-      }
-    });
+      // Let oracle snapshot become stale.
+      await waitForSlotDelta(staleSlots, 180_000);
 
-    it("Rejects zero amUSD redemption", async () => {
-      const userSetup = await setupUser(50);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(20 * LAMPORTS_PER_SOL), new BN(1));
-      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-        new BN(5 * LAMPORTS_PER_SOL), new BN(1));
+      // Refresh ONLY LST snapshot so the failure source is oracle staleness.
+      await syncExchangeRate();
 
       try {
-        await redeemAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-          new BN(0), new BN(100_000));
-        expect.fail("Should reject zero redemption");
+        await mintAsol(
+          userSetup.user,
+          userSetup.lstAccount,
+          userSetup.asolAccount,
+          new BN(1 * LAMPORTS_PER_SOL),
+          new BN(1)
+        );
+        expect.fail("Expected OraclePriceStale");
       } catch (err: any) {
-        expect(err.toString()).to.include("ZeroAmount");
+        const msg = err?.toString?.() ?? String(err);
+        expect(msg).to.include("OraclePriceStale");
+      } finally {
+        // Always restore clean state for downstream tests.
+        await resetAndSyncSnapshots();
       }
     });
-  });
 
-  describe("21. Fee Accumulation Verification", () => {
-    it("Treasury receives aSOL minting fees", async () => {
-      const state = await getGlobalState();
-      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
-        mint: protocolState.asolMint.publicKey,
-        owner: state.treasury,
-      });
+    it("Rejects amUSD mint when oracle snapshot is stale", async () => {
+      const userSetup = await setupUser(25);
 
-      const balanceBefore = await getAccount(connection, treasuryAsolAccount)
-        .then(acc => Number(acc.amount))
-        .catch(() => 0);
+      await resetAndSyncSnapshots();
 
-      const userSetup = await setupUser(20);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+      const state = await getGlobalState();
+      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
 
-      const balanceAfter = await getAccount(connection, treasuryAsolAccount)
-        .then(acc => Number(acc.amount));
+      await waitForSlotDelta(staleSlots, 180_000);
 
-      const feeReceived = balanceAfter - balanceBefore;
-      expect(feeReceived).to.be.greaterThan(0);
+      // Refresh ONLY LST snapshot so the failure source is oracle staleness.
+      await syncExchangeRate();
 
-      console.log(`  Treasury aSOL fee received: ${feeReceived / 1e9} aSOL`);
+      try {
+        await mintAmUSD(
+          userSetup.user,
+          userSetup.lstAccount,
+          userSetup.amusdAccount,
+          new BN(1 * LAMPORTS_PER_SOL),
+          new BN(1)
+        );
+        expect.fail("Expected OraclePriceStale");
+      } catch (err: any) {
+        const msg = err?.toString?.() ?? String(err);
+        expect(msg).to.include("OraclePriceStale");
+      } finally {
+        await resetAndSyncSnapshots();
+      }
     });
+  });
 
-    it("Treasury receives aSOL redemption fees", async () => {
+
+  describe("48. A5 CPI Guard Positive Vectors", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
+    it("Allows compute-budget preamble + direct call", async () => {
+      const userSetup = await setupUser(25);
       const state = await getGlobalState();
+      const [vaultAuthority] = getVaultAuthorityPda();
+
       const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
         mint: protocolState.asolMint.publicKey,
         owner: state.treasury,
       });
 
-      const balanceBefore = await getAccount(connection, treasuryAsolAccount)
-        .then(acc => Number(acc.amount))
-        .catch(() => 0);
-
-      const userSetup = await setupUser(30);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
-
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(Number(userAsolBalance.amount) / 2), new BN(100_000));
+      const ix = await program.methods
+        .mintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
+        .accounts({
+          user: userSetup.user.publicKey,
+          globalState: protocolState.globalState,
+          asolMint: protocolState.asolMint.publicKey,
+          userAsolAccount: userSetup.asolAccount,
+          treasuryAsolAccount,
+          treasury: state.treasury,
+          userLstAccount: userSetup.lstAccount,
+          vault: protocolState.vault,
+          vaultAuthority,
+          lstMint: protocolState.lstMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        } as any)
+        .instruction();
 
-      const balanceAfter = await getAccount(connection, treasuryAsolAccount)
-        .then(acc => Number(acc.amount));
+      const tx = new Transaction().add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        ix
+      );
 
-      const feeReceived = balanceAfter - balanceBefore;
-      expect(feeReceived).to.be.greaterThan(0);
+      await provider.sendAndConfirm(tx, [userSetup.user]);
 
-      console.log(`  Treasury aSOL redemption fee received: ${feeReceived / 1e9} aSOL`);
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      expect(new BN(userAsolBalance.amount.toString()).gt(new BN(0))).to.be.true;
     });
 
-    it("Treasury receives amUSD redemption fees", async () => {
-      const state = await getGlobalState();
-      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
-        mint: protocolState.amusdMint.publicKey,
-        owner: state.treasury,
-      });
-
-      const balanceBefore = await getAccount(connection, treasuryAmusdAccount)
-        .then(acc => Number(acc.amount))
-        .catch(() => 0);
+    it("Allows direct call with no preamble", async () => {
+      const userSetup = await setupUser(25);
 
-      // Keep protocol clearly solvent so redeem fee is non-zero.
-      const userSetup = await setupUser(120);
       await mintAsol(
         userSetup.user,
         userSetup.lstAccount,
         userSetup.asolAccount,
-        new BN(50 * LAMPORTS_PER_SOL),
+        new BN(1 * LAMPORTS_PER_SOL),
         new BN(1)
       );
-      await mintAmUSD(
+
+      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
+      expect(new BN(userAsolBalance.amount.toString()).gt(new BN(0))).to.be.true;
+    });
+  });
+
+  describe("49. A5 amUSD Haircut Path", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
+    it("Uses haircut path and charges zero amUSD fee when CR < 100%", async () => {
+      const userSetup = await setupUser(400);
+
+      await mintAsol(
         userSetup.user,
         userSetup.lstAccount,
-        userSetup.amusdAccount,
-        new BN(2 * LAMPORTS_PER_SOL),
+        userSetup.asolAccount,
+        new BN(120 * LAMPORTS_PER_SOL),
         new BN(1)
       );
 
-      const userAmusdBalance = await getAccount(connection, userSetup.amusdAccount);
-      const redeemAmount = new BN(Math.floor(Number(userAmusdBalance.amount) / 2));
-
-      await redeemAmUSD(
+      await mintAmUSD(
         userSetup.user,
         userSetup.lstAccount,
         userSetup.amusdAccount,
-        redeemAmount,
-        new BN(100_000)
+        new BN(25 * LAMPORTS_PER_SOL),
+        new BN(1)
       );
 
-      const balanceAfter = await getAccount(connection, treasuryAmusdAccount)
-        .then(acc => Number(acc.amount));
+      let state = await getGlobalState();
+      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      expect(liability.gt(new BN(0))).to.be.true;
 
-      const feeReceived = balanceAfter - balanceBefore;
-      expect(feeReceived).to.be.greaterThan(0);
+      const targetCr = new BN(9_500);
+      let crashPrice = targetCr
+        .mul(state.amusdSupply)
+        .mul(SOL_PRECISION)
+        .div(tvl.mul(BPS_PRECISION));
 
-      console.log(`  Treasury amUSD redemption fee received: ${feeReceived / 1e6} amUSD`);
-    });
+      if (crashPrice.gte(state.mockSolPriceUsd)) {
+        crashPrice = state.mockSolPriceUsd.sub(new BN(1));
+      }
+      crashPrice = BN.max(crashPrice, new BN(1));
 
-  });
+      await updateMockPrices(crashPrice, state.mockLstToSolRate, new BN(0));
 
-  describe("22. Operation Counter Monotonicity", () => {
-    it("Operation counter increments on every state change", async () => {
-      const stateBefore = await getGlobalState();
-      const counterBefore = stateBefore.operationCounter.toNumber();
+      state = await getGlobalState();
+      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      let crBefore = computeCrBps(tvl, liability);
 
-      const userSetup = await setupUser(10);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+      if (!crBefore.lt(BPS_PRECISION)) {
+        const lower = BN.max(new BN(1), state.mockSolPriceUsd.muln(9).divn(10));
+        await updateMockPrices(lower, state.mockLstToSolRate, new BN(0));
+        state = await getGlobalState();
+        tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+        liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+        crBefore = computeCrBps(tvl, liability);
+      }
 
-      const stateAfter = await getGlobalState();
-      const counterAfter = stateAfter.operationCounter.toNumber();
+      expect(crBefore.lt(BPS_PRECISION)).to.be.true;
 
-      expect(counterAfter).to.equal(counterBefore + 1);
-      console.log(`  Counter: ${counterBefore} → ${counterAfter}`);
-    });
-  });
+      const userAmusdBefore = new BN((await getAccount(connection, userSetup.amusdAccount)).amount.toString());
+      const userLstBefore = new BN((await getAccount(connection, userSetup.lstAccount)).amount.toString());
 
-  describe("23. Redeem Pause Security", () => {
-    it("Admin can pause redemptions independently", async () => {
-      await program.methods
-        .emergencyPause(false, true)
-        .accounts({
-          authority: protocolState.authority.publicKey,
-          globalState: protocolState.globalState,
-          clock: SYSVAR_CLOCK_PUBKEY,
-        })
-        .signers([protocolState.authority])
-        .rpc();
+      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: state.treasury,
+      });
+      const treasuryBefore = await getTokenAmountOrZero(treasuryAmusdAccount);
 
-      const state = await getGlobalState();
-      expect(state.mintPaused).to.be.false;
-      expect(state.redeemPaused).to.be.true;
-    });
+      let redeemAmount = BN.min(userAmusdBefore.divn(4), new BN(500 * 1_000_000));
+      if (redeemAmount.isZero()) redeemAmount = userAmusdBefore;
 
-    it("Redemption rejected when paused", async () => {
-      const userSetup = await setupUser(10);
+      const haircutBps = BN.min(crBefore, BPS_PRECISION);
 
-      // User already has tokens from previous tests
-      try {
-        await redeemAsol(user1, user1LstAccount, user1AsolAccount,
-          new BN(1000000), new BN(100_000));
-        expect.fail("Should reject when paused");
-      } catch (err: any) {
-        expect(err.toString()).to.include("RedeemPaused");
-      }
+      const expectedLstOutFor = (amount: BN): BN => {
+        const solParDown = amount.mul(SOL_PRECISION).div(state.mockSolPriceUsd);
+        const solHaircut = solParDown.mul(haircutBps).div(BPS_PRECISION);
+        return solHaircut.mul(SOL_PRECISION).div(state.mockLstToSolRate);
+      };
 
-      // Unpause
-      await program.methods
-        .emergencyPause(false, false)
-        .accounts({
-          authority: protocolState.authority.publicKey,
-          globalState: protocolState.globalState,
-          clock: SYSVAR_CLOCK_PUBKEY,
-        })
-        .signers([protocolState.authority])
-        .rpc();
-    });
-  });
+      let expectedLstOut = expectedLstOutFor(redeemAmount);
+      while (expectedLstOut.lt(new BN(100_000)) && redeemAmount.lt(userAmusdBefore)) {
+        redeemAmount = BN.min(redeemAmount.muln(2), userAmusdBefore);
+        expectedLstOut = expectedLstOutFor(redeemAmount);
+      }
 
-  describe("24. Insufficient Balance Handling", () => {
-    it("Rejects redemption exceeding user balance", async () => {
-      const userSetup = await setupUser(10);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL), new BN(1));
+      expect(expectedLstOut.gte(new BN(100_000))).to.be.true;
 
-      const userBalance = await getAccount(connection, userSetup.asolAccount);
-      const tooMuch = new BN(Number(userBalance.amount) * 2);
+      await redeemAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        redeemAmount,
+        new BN(100_000)
+      );
 
-      try {
-        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          tooMuch, new BN(100_000));
-        expect.fail("Should reject exceeding balance");
-      } catch (err: any) {
-        expect(err.toString()).to.include("InsufficientSupply");
-      }
-    });
+      const userAmusdAfter = new BN((await getAccount(connection, userSetup.amusdAccount)).amount.toString());
+      const userLstAfter = new BN((await getAccount(connection, userSetup.lstAccount)).amount.toString());
+      const treasuryAfter = await getTokenAmountOrZero(treasuryAmusdAccount);
 
-    it("Rejects deposit exceeding user LST balance", async () => {
-      const userSetup = await setupUser(1); // Only 1 LST
+      const burned = userAmusdBefore.sub(userAmusdAfter);
+      const lstReceived = userLstAfter.sub(userLstBefore);
+      const treasuryDelta = treasuryAfter.sub(treasuryBefore);
 
-      try {
-        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          new BN(10 * LAMPORTS_PER_SOL), new BN(1)); // Try to deposit 10 LST
-        expect.fail("Should reject exceeding balance");
-      } catch (err: any) {
-        expect(err.toString()).to.include("InsufficientCollateral");
-      }
+      expect(burned.eq(redeemAmount)).to.be.true;
+      expect(treasuryDelta.isZero()).to.be.true;
+      expect(lstReceived.eq(expectedLstOut)).to.be.true;
+
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
     });
   });
 
-  describe("25. aSOL Redemption CR Impact", () => {
-    it("aSOL redemption enforces post-state minimum CR", async () => {
-      const userSetup = await setupUser(250);
+  describe("50. A5 aSOL CR_post Gate", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
+    it("Reverts with CollateralRatioTooLow when redeem would push CR below min", async () => {
+      const userSetup = await setupUser(500);
 
       await mintAsol(
         userSetup.user,
         userSetup.lstAccount,
         userSetup.asolAccount,
-        new BN(50 * LAMPORTS_PER_SOL),
+        new BN(150 * LAMPORTS_PER_SOL),
         new BN(1)
       );
 
@@ -1908,1098 +3644,1694 @@ describe("Laminar Protocol - Phase 3 Integration Tests", () => {
         userSetup.user,
         userSetup.lstAccount,
         userSetup.amusdAccount,
-        new BN(120 * LAMPORTS_PER_SOL),
+        new BN(20 * LAMPORTS_PER_SOL),
         new BN(1)
       );
 
-      const crBefore = await calculateCR();
-      console.log(`  CR before aSOL redemption: ${crBefore.toNumber()} bps`);
+      let state = await getGlobalState();
+      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      expect(liability.gt(new BN(0))).to.be.true;
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      const largeRedemption = new BN(Math.floor(Number(userAsolBalance.amount) * 0.8)); // 80%
+      const targetCr = state.minCrBps.add(new BN(1));
+      let tunedPrice = targetCr
+        .mul(state.amusdSupply)
+        .mul(SOL_PRECISION)
+        .div(tvl.mul(BPS_PRECISION));
+
+      if (tunedPrice.gte(state.mockSolPriceUsd)) {
+        tunedPrice = state.mockSolPriceUsd.sub(new BN(1));
+      }
+      tunedPrice = BN.max(tunedPrice, new BN(1));
+
+      await updateMockPrices(tunedPrice, state.mockLstToSolRate, new BN(0));
+
+      state = await getGlobalState();
+      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
+      const crNow = computeCrBps(tvl, liability);
+
+      expect(crNow.gte(state.minCrBps)).to.be.true;
+
+      const nav = computeAsolNav(tvl, liability, state.asolSupply);
+      expect(nav.gt(new BN(0))).to.be.true;
+
+      const minTvlAfter = liability.mul(state.minCrBps).div(BPS_PRECISION);
+      const requiredSolOut = tvl.gt(minTvlAfter) ? tvl.sub(minTvlAfter).add(new BN(1)) : new BN(1);
+
+      const asolNetNeeded = mulDivUp(requiredSolOut, SOL_PRECISION, nav);
+
+      const feeBps = computeDynamicFeeBps(
+        ASOL_REDEEM_FEE_BPS,
+        "risk_increasing",
+        crNow,
+        state.targetCrBps,
+        {
+          minCrBps: state.minCrBps,
+          feeMinMultiplierBps: state.feeMinMultiplierBps,
+          feeMaxMultiplierBps: state.feeMaxMultiplierBps,
+          uncertaintyIndexBps: state.uncertaintyIndexBps,
+          uncertaintyMaxBps: state.uncertaintyMaxBps,
+        }
+      );
+
+      const feeDenom = BPS_PRECISION.sub(new BN(feeBps));
+      expect(feeDenom.gt(new BN(0))).to.be.true;
+
+      const asolInputNeeded = mulDivUp(asolNetNeeded, BPS_PRECISION, feeDenom);
+      const userAsol = new BN((await getAccount(connection, userSetup.asolAccount)).amount.toString());
+
+      const redeemAttempt = BN.min(
+        userAsol,
+        BN.max(asolInputNeeded.add(new BN(1_000_000)), new BN(5_000_000))
+      );
+      expect(redeemAttempt.gt(new BN(0))).to.be.true;
 
       try {
         await redeemAsol(
           userSetup.user,
           userSetup.lstAccount,
           userSetup.asolAccount,
-          largeRedemption,
+          redeemAttempt,
           new BN(100_000)
         );
+        expect.fail("Expected CollateralRatioTooLow");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CollateralRatioTooLow");
+      }
 
-        const crAfter = await calculateCR();
-        console.log(`  CR after aSOL redemption: ${crAfter.toNumber()} bps`);
+      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
+    });
+  });
 
-        // If redemption succeeds, CR-post gate must still hold.
-        expect(crAfter.gte(MIN_CR_BPS)).to.be.true;
-        expect(crAfter.lte(crBefore)).to.be.true;
-      } catch (err: any) {
-        // CR gate is now a valid/expected blocker.
-        const expectedErrors = [
-          "CollateralRatioTooLow",
-          "InsolventProtocol",
-          "InsufficientCollateral",
-        ];
+  describe("51. A5 LST Staleness", () => {
+    it("Rejects mint when LST snapshot is stale; succeeds after sync_exchange_rate", async () => {
+      const userSetup = await setupUser(25);
 
-        const matched = expectedErrors.some((e) => err.toString().includes(e));
-        expect(matched).to.be.true;
+      await syncExchangeRate();
+      const state = await getGlobalState();
 
-        if (err.toString().includes("CollateralRatioTooLow")) {
-          console.log("  aSOL redemption correctly blocked by CR_post gate");
-        } else {
-          console.log("  aSOL redemption blocked by stronger safety condition");
-        }
+      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
+      await waitForSlotDelta(staleSlots, 180_000);
+
+      // refresh oracle only, so failure source is LST staleness
+      await updateMockPrices(state.mockSolPriceUsd, state.mockLstToSolRate, new BN(0));
+
+      try {
+        await mintAsol(
+          userSetup.user,
+          userSetup.lstAccount,
+          userSetup.asolAccount,
+          new BN(1 * LAMPORTS_PER_SOL),
+          new BN(1),
+        );
+        expect.fail("Expected LstRateStale");
+      } catch (err: any) {
+        expect(err.toString()).to.include("LstRateStale");
       }
+
+      await syncExchangeRate();
+
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(1 * LAMPORTS_PER_SOL),
+        new BN(1),
+      );
+
+      const bal = await getAccount(connection, userSetup.asolAccount);
+      expect(Number(bal.amount)).to.be.greaterThan(0);
     });
-  });
 
+    it("Rejects amUSD mint when LST snapshot is stale; succeeds after sync_exchange_rate", async () => {
+      const userSetup = await setupUser(25);
 
-  describe("26. Leverage Calculation Verification", () => {
-    it("Leverage increases as debt increases", async () => {
+      await resetAndSyncSnapshots();
       const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
 
-      // Leverage = TVL / Equity
-      const leverage = equity.isZero() ? new BN(0) : tvl.mul(new BN(100)).div(equity);
+      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
+      await waitForSlotDelta(staleSlots, 180_000);
 
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
-      console.log(`  Leverage: ${leverage.toNumber() / 100}x`);
+      // refresh oracle only, so failure source is LST staleness
+      await updateMockPrices(state.mockSolPriceUsd, state.mockLstToSolRate, new BN(0));
 
-      // Leverage should be >= 1x (100)
-      if (!equity.isZero()) {
-        expect(leverage.toNumber()).to.be.gte(100);
+      try {
+        await mintAmUSD(
+          userSetup.user,
+          userSetup.lstAccount,
+          userSetup.amusdAccount,
+          new BN(1 * LAMPORTS_PER_SOL),
+          new BN(1),
+        );
+        expect.fail("Expected LstRateStale");
+      } catch (err: any) {
+        expect(err.toString()).to.include("LstRateStale");
       }
+
+      await syncExchangeRate();
+
+      await mintAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(1 * LAMPORTS_PER_SOL),
+        new BN(1),
+      );
+
+      const bal = await getAccount(connection, userSetup.amusdAccount);
+      expect(Number(bal.amount)).to.be.greaterThan(0);
     });
   });
 
-  describe("27. Version Validation", () => {
-    it("Protocol correctly reports version 1", async () => {
+  describe("52. A5 CPI Negative Depth Vectors", () => {
+    it("Rejects direct CPI (proxy -> laminar)", async () => {
+      const userSetup = await setupUser(25);
       const state = await getGlobalState();
-      expect(state.version).to.equal(1);
-    });
-  });
+      const [vaultAuthority] = getVaultAuthorityPda();
 
-  describe("28. Maximum Value Boundaries", () => {
-    it("Handles large but valid deposit amounts", async () => {
-      const userSetup = await setupUser(1000);
-      const largeAmount = new BN(500 * LAMPORTS_PER_SOL);
+      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
 
-      // Should succeed
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        largeAmount, new BN(1));
+      try {
+        await cpiTester.methods
+          .cpiMintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
+          .accounts({
+            user: userSetup.user.publicKey,
+            globalState: protocolState.globalState,
+            asolMint: protocolState.asolMint.publicKey,
+            userAsolAccount: userSetup.asolAccount,
+            treasuryAsolAccount,
+            treasury: state.treasury,
+            userLstAccount: userSetup.lstAccount,
+            vault: protocolState.vault,
+            vaultAuthority,
+            lstMint: protocolState.lstMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            cpiTesterProgram: cpiTester.programId,
+            laminarProgram: program.programId,
+          } as any)
+          .signers([userSetup.user])
+          .rpc();
 
-      const balance = await getAccount(connection, userSetup.asolAccount);
-      expect(Number(balance.amount)).to.be.greaterThan(0);
+        expect.fail("Expected InvalidCPIContext");
+      } catch (err: any) {
+        const msg = err.toString();
+        expect(msg.includes("InvalidCPIContext")).to.be.true;
+      }
     });
-  });
 
-  describe("29. Final State Integrity Check", () => {
-    it("Protocol state is consistent after all tests", async () => {
+    it("Rejects nested CPI (proxy -> proxy -> laminar)", async () => {
+      const userSetup = await setupUser(25);
       const state = await getGlobalState();
+      const [vaultAuthority] = getVaultAuthorityPda();
 
-      // Verify all invariants hold
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
-
-      // Balance sheet: TVL = Liability + Equity
-      const total = liability.add(equity);
-      const diff = tvl.sub(total).abs();
-      const tolerance = BN.max(tvl.div(new BN(10_000)), new BN(1000));
-
-      expect(diff.lte(tolerance)).to.be.true;
-
-      // Supply sync
-      const amusdMint = await getMint(connection, protocolState.amusdMint.publicKey);
-      const asolMint = await getMint(connection, protocolState.asolMint.publicKey);
-      const vault = await getAccount(connection, protocolState.vault);
+      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
 
-      expect(Number(amusdMint.supply)).to.equal(state.amusdSupply.toNumber());
-      expect(Number(asolMint.supply)).to.equal(state.asolSupply.toNumber());
-      expect(Number(vault.amount)).to.equal(state.totalLstAmount.toNumber());
+      try {
+        await cpiTester.methods
+          .cpiNestedMintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
+          .accounts({
+            user: userSetup.user.publicKey,
+            globalState: protocolState.globalState,
+            asolMint: protocolState.asolMint.publicKey,
+            userAsolAccount: userSetup.asolAccount,
+            treasuryAsolAccount,
+            treasury: state.treasury,
+            userLstAccount: userSetup.lstAccount,
+            vault: protocolState.vault,
+            vaultAuthority,
+            lstMint: protocolState.lstMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            clock: SYSVAR_CLOCK_PUBKEY,
+            cpiTesterProgram: cpiTester.programId,
+            laminarProgram: program.programId,
+          } as any)
+          .signers([userSetup.user])
+          .rpc();
 
-      console.log("\n=== FINAL PROTOCOL STATE ===");
-      console.log(`  Version: ${state.version}`);
-      console.log(`  Operations: ${state.operationCounter.toNumber()}`);
-      console.log(`  TVL: ${tvl.toNumber() / 1e9} SOL`);
-      console.log(`  Liability: ${liability.toNumber() / 1e9} SOL`);
-      console.log(`  Equity: ${equity.toNumber() / 1e9} SOL`);
-      console.log(`  amUSD Supply: ${state.amusdSupply.toNumber() / 1e6} USD`);
-      console.log(`  aSOL Supply: ${state.asolSupply.toNumber() / 1e9} aSOL`);
-      console.log(`  Vault LST: ${state.totalLstAmount.toNumber() / 1e9} LST`);
-      console.log("===========================\n");
+        expect.fail("Expected InvalidCPIContext");
+      } catch (err: any) {
+        const msg = err.toString();
+        expect(msg.includes("InvalidCPIContext")).to.be.true;
+      }
     });
   });
 
-  describe("30. Flash Loan / Same-Slot Attack Prevention", () => {
-    it("Multiple operations in same transaction are blocked by CPI check", async () => {
-      // The protocol checks instruction index = 0, preventing CPI calls
-      // This test verifies the protection exists via error on malformed context
-      const userSetup = await setupUser(50);
-
-      // Single operation should work
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+  describe("53. Launch Configuration Gate", () => {
+    it("Rejects configure_launch on a live protocol even when paused", async () => {
+      await setPause(true, true);
 
-      // The InvalidCPIContext error is triggered when instruction_index != 0
-      console.log("  ✓ Protocol uses instruction sysvar to prevent CPI attacks");
+      try {
+        await configureLaunch(defaultLaunchConfig());
+        expect.fail("Expected ProtocolAlreadyLaunched");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ProtocolAlreadyLaunched");
+      } finally {
+        await setPause(false, false);
+      }
     });
   });
 
-  describe("31. Price Staleness Protection", () => {
-    it("Protocol should validate oracle freshness (mock implementation)", async () => {
-
-      const state = await getGlobalState();
-
-      expect(state.mockSolPriceUsd.toNumber()).to.be.greaterThan(0);
-      expect(state.mockLstToSolRate.toNumber()).to.be.greaterThan(0);
 
-      console.log("  Note: Production should add staleness checks to oracle integration");
+  describe("54. Fee Parameter Updates", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
     });
-  });
-
-  describe("32. Precision Loss Accumulation", () => {
-    it("Many small operations don't cause significant precision drift", async () => {
-      const userSetup = await setupUser(100);
 
-      const stateBefore = await getGlobalState();
-      const iterations = 5;
-      const smallAmount = new BN(0.1 * LAMPORTS_PER_SOL);
+    afterEach(async () => {
+      await restoreDefaultFees();
+    });
 
-      // Perform many small mints
-      for (let i = 0; i < iterations; i++) {
-        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-          smallAmount, new BN(1));
+    it("Rejects inverted fee multiplier bounds", async () => {
+      try {
+        await updateFeeParameters(
+          new BN(AMUSD_MINT_FEE_BPS),
+          new BN(AMUSD_REDEEM_FEE_BPS),
+          new BN(ASOL_MINT_FEE_BPS),
+          new BN(ASOL_REDEEM_FEE_BPS),
+          new BN(12_000),
+          new BN(8_000)
+        );
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
       }
+    });
 
-      const stateAfter = await getGlobalState();
+    it("Rejects base fees above the 10% cap", async () => {
+      try {
+        await updateFeeParameters(
+          new BN(1_001),
+          new BN(AMUSD_REDEEM_FEE_BPS),
+          new BN(ASOL_MINT_FEE_BPS),
+          new BN(ASOL_REDEEM_FEE_BPS),
+          FEE_MIN_MULTIPLIER_BPS,
+          FEE_MAX_MULTIPLIER_BPS
+        );
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
+    });
 
-      // Verify balance sheet still holds
-      const tvl = computeTvlSol(stateAfter.totalLstAmount, stateAfter.mockLstToSolRate);
-      const liability = computeLiabilitySol(stateAfter.amusdSupply, stateAfter.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
+    it("Non-admin cannot update fee parameters", async () => {
+      try {
+        await program.methods
+          .updateFeeParameters(
+            new BN(0),
+            new BN(0),
+            new BN(0),
+            new BN(0),
+            FEE_MIN_MULTIPLIER_BPS,
+            FEE_MAX_MULTIPLIER_BPS
+          )
+          .accounts({
+            authority: user1.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          } as any)
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have rejected non-admin");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
+      }
+    });
 
-      const diff = tvl.sub(liability.add(equity)).abs();
-      expect(diff.toNumber()).to.be.lessThan(iterations * 1000); // Allow 1000 lamports per op
+    it("Subsequent mints use the updated fees", async () => {
+      const stateBefore = await getGlobalState();
+      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: stateBefore.treasury,
+      });
 
-      console.log(`  ${iterations} operations, precision drift: ${diff.toNumber()} lamports`);
-    });
-  });
+      await updateFeeParameters(
+        new BN(0),
+        new BN(AMUSD_REDEEM_FEE_BPS),
+        new BN(ASOL_MINT_FEE_BPS),
+        new BN(ASOL_REDEEM_FEE_BPS),
+        FEE_MIN_MULTIPLIER_BPS,
+        FEE_MAX_MULTIPLIER_BPS
+      );
 
-  describe("33. Account Validation Security", () => {
-    it("Rejects wrong LST mint", async () => {
-      const userSetup = await setupUser(10);
+      let state = await getGlobalState();
+      expect(state.feeAmusdMintBps.toNumber()).to.equal(0);
+      expect(state.operationCounter.gt(stateBefore.operationCounter)).to.be.true;
 
-      const wrongMint = await createMint(
-        connection,
-        protocolState.authority,
-        protocolState.authority.publicKey,
-        null,
-        9,
-        undefined,
-        undefined,
-        TOKEN_PROGRAM_ID,
+      const userSetup = await setupUser(100);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(50 * LAMPORTS_PER_SOL),
+        new BN(1)
       );
 
-      const wrongMintAccount = await getOrCreateAssociatedTokenAccount(
-        connection,
+      let treasuryBefore = await getTokenAmountOrZero(treasuryAmusdAccount);
+      await mintAmUSD(
         userSetup.user,
-        wrongMint,
-        userSetup.user.publicKey
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(LAMPORTS_PER_SOL),
+        new BN(1)
+      );
+      let treasuryAfter = await getTokenAmountOrZero(treasuryAmusdAccount);
+      expect(treasuryAfter.eq(treasuryBefore)).to.be.true;
+
+      await updateFeeParameters(
+        new BN(200),
+        new BN(AMUSD_REDEEM_FEE_BPS),
+        new BN(ASOL_MINT_FEE_BPS),
+        new BN(ASOL_REDEEM_FEE_BPS),
+        FEE_MIN_MULTIPLIER_BPS,
+        FEE_MAX_MULTIPLIER_BPS
       );
 
-      // Fund the wrong mint account
-      await mintTo(
-        connection,
-        protocolState.authority,
-        wrongMint,
-        wrongMintAccount.address,
-        protocolState.authority,
-        10 * LAMPORTS_PER_SOL
+      state = await getGlobalState();
+      expect(state.feeAmusdMintBps.toNumber()).to.equal(200);
+
+      treasuryBefore = await getTokenAmountOrZero(treasuryAmusdAccount);
+      await mintAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(LAMPORTS_PER_SOL),
+        new BN(1)
       );
+      treasuryAfter = await getTokenAmountOrZero(treasuryAmusdAccount);
+
+      // 2% of ~105 amUSD gross, before any CR multiplier
+      const feeReceived = treasuryAfter.sub(treasuryBefore);
+      expect(feeReceived.gte(new BN(2_000_000))).to.be.true;
+    });
+  });
 
+  describe("55. Treasury Classification", () => {
+    const [foreignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      cpiTester.programId
+    );
+
+    after(async () => {
+      await setTreasury(protocolState.authority.publicKey, false, PublicKey.default);
+    });
+
+    it("Rejects a token account as treasury", async () => {
       try {
-        // Try to deposit wrong LST
-        const [vaultAuthority] = getVaultAuthorityPda();
+        await setTreasury(user1LstAccount, false, PublicKey.default);
+        expect.fail("Expected InvalidTreasury");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTreasury");
+      }
 
-        await program.methods
-          .mintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
-          .accounts({
-            user: userSetup.user.publicKey,
-            globalState: protocolState.globalState,
-            asolMint: protocolState.asolMint.publicKey,
-            userAsolAccount: userSetup.asolAccount,
-            treasuryAsolAccount: await anchor.utils.token.associatedAddress({
-              mint: protocolState.asolMint.publicKey,
-              owner: (await getGlobalState()).treasury,
-            }),
-            treasury: (await getGlobalState()).treasury,
-            userLstAccount: wrongMintAccount.address, // WRONG ACCOUNT
-            vault: protocolState.vault,
-            vaultAuthority: vaultAuthority,
-            lstMint: wrongMint, // WRONG MINT
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-            instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          } as any)
-          .signers([userSetup.user])
-          .rpc();
+      try {
+        await setTreasury(user1LstAccount, true, TOKEN_PROGRAM_ID);
+        expect.fail("Expected InvalidTreasury");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTreasury");
+      }
+    });
 
-        expect.fail("Should reject wrong LST mint");
+    it("Rejects a foreign-program PDA without acknowledgment", async () => {
+      try {
+        await setTreasury(foreignPda, false, PublicKey.default);
+        expect.fail("Expected InvalidTreasury");
       } catch (err: any) {
-        // Should fail with constraint error
-        expect(
-          err.toString().includes("ConstraintAddress") ||
-          err.toString().includes("constraint")
-        ).to.be.true;
-        console.log("  ✓ Wrong LST mint correctly rejected");
+        expect(err.toString()).to.include("InvalidTreasury");
+      }
+    });
+
+    it("Rejects a wallet treasury that does not co-sign", async () => {
+      const wallet = Keypair.generate();
+      try {
+        await setTreasury(wallet.publicKey, false, PublicKey.default);
+        expect.fail("Expected InvalidTreasury");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTreasury");
       }
-    })
-  });
+    });
 
-  describe("34. u64 Overflow Protection", () => {
-    it("Handles values near u64 max without overflow", async () => {
-      // Test that math functions don't overflow
-      const maxSafeAmount = new BN("18446744073709551615"); // u64::MAX
-      const normalAmount = new BN(1_000_000_000);
+    it("Accepts an acknowledged PDA and records its owner program", async () => {
+      await setTreasury(foreignPda, true, cpiTester.programId);
 
-      // computeTvlSol should handle large amounts
-      const largeTvl = computeTvlSol(maxSafeAmount.div(new BN(2)), SOL_PRECISION);
-      expect(largeTvl.toString()).to.not.equal("0");
+      const state = await getGlobalState();
+      expect(state.treasury.toBase58()).to.equal(foreignPda.toBase58());
+      expect(state.treasuryOwnerProgram.toBase58()).to.equal(cpiTester.programId.toBase58());
+    });
 
-      console.log("  ✓ Math functions handle large values safely");
+    it("Accepts a co-signing wallet and clears the owner program", async () => {
+      const wallet = Keypair.generate();
+      await setTreasury(wallet.publicKey, false, PublicKey.default, [wallet]);
+
+      const state = await getGlobalState();
+      expect(state.treasury.toBase58()).to.equal(wallet.publicKey.toBase58());
+      expect(state.treasuryOwnerProgram.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+
+    it("Creates the new treasury's fee ATAs and routes fees there", async () => {
+      await resetAndSyncSnapshots();
+
+      const wallet = Keypair.generate();
+      await setTreasury(wallet.publicKey, false, PublicKey.default, [wallet]);
+
+      const newTreasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: wallet.publicKey,
+      });
+      const newTreasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: wallet.publicKey,
+      });
+
+      // Both ATAs exist immediately after rotation.
+      expect(Number((await getAccount(connection, newTreasuryAmusdAccount)).amount)).to.equal(0);
+      expect(Number((await getAccount(connection, newTreasuryAsolAccount)).amount)).to.equal(0);
+
+      const userSetup = await setupUser(60);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(30 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
+      await mintAmUSD(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.amusdAccount,
+        new BN(2 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
+
+      expect(Number((await getAccount(connection, newTreasuryAsolAccount)).amount)).to.be.greaterThan(0);
+      expect(Number((await getAccount(connection, newTreasuryAmusdAccount)).amount)).to.be.greaterThan(0);
     });
   });
 
-  describe("35. Authority Transfer Security", () => {
-    it("Authority transfer should require proper verification", async () => {
-      const state = await getGlobalState();
+  describe("56. Oracle Parameter Updates", () => {
+    afterEach(async () => {
+      await restoreDefaultOracleParameters();
+      await resetAndSyncSnapshots();
+    });
 
-      // Verify authority is set correctly
-      expect(state.authority.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
+    it("Rejects zero staleness and uncertainty cap below 1.0x", async () => {
+      try {
+        await updateOracleParameters(new BN(0), new BN(150), UNCERTAINTY_MAX_BPS, new BN(1));
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
 
-      // Non-authority cannot perform admin actions
-      const randomUser = Keypair.generate();
-      await airdropSol(randomUser.publicKey, 1);
+      try {
+        await updateOracleParameters(new BN(150), new BN(150), new BN(9_999), new BN(1));
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
+    });
 
+    it("Non-admin cannot update oracle parameters", async () => {
       try {
         await program.methods
-          .updateParameters(new BN(10_000), new BN(12_000))
+          .updateOracleParameters(new BN(1_000), new BN(150), UNCERTAINTY_MAX_BPS, new BN(1))
           .accounts({
-            authority: randomUser.publicKey,
+            authority: user1.publicKey,
             globalState: protocolState.globalState,
             clock: SYSVAR_CLOCK_PUBKEY,
-          })
-          .signers([randomUser])
+          } as any)
+          .signers([user1])
           .rpc();
-
-        expect.fail("Should reject unauthorized access");
+        expect.fail("Should have rejected non-admin");
       } catch (err: any) {
         expect(err.toString()).to.include("ConstraintHasOne");
-        console.log("  ✓ Authority access control enforced");
       }
     });
-  });
 
-  describe("36. Redemption Race Condition", () => {
-    it("Sequential redemptions are processed fairly", async () => {
-      const setupA = await setupUser(50);
-      const setupB = await setupUser(50);
+    it("Redeem rejected as stale is accepted after the window is raised", async () => {
+      const userSetup = await setupUser(25);
+      await resetAndSyncSnapshots();
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(10 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
 
-      // Both users deposit same amount
-      const depositAmount = new BN(10 * LAMPORTS_PER_SOL);
-      await mintAsol(setupA.user, setupA.lstAccount, setupA.asolAccount, depositAmount, new BN(1));
-      await mintAsol(setupB.user, setupB.lstAccount, setupB.asolAccount, depositAmount, new BN(1));
+      // Tighten the window so the snapshot goes stale quickly.
+      const tightWindow = 5;
+      await updateOracleParameters(new BN(tightWindow), new BN(150), UNCERTAINTY_MAX_BPS, new BN(1));
+      await resetAndSyncSnapshots();
+      await waitForSlotDelta(tightWindow + 2, 60_000);
 
-      // Get balances
-      const aBalance = await getAccount(connection, setupA.asolAccount);
-      const bBalance = await getAccount(connection, setupB.asolAccount);
+      // Refresh ONLY LST snapshot so the failure source is oracle staleness.
+      await syncExchangeRate();
 
-      // Redeem same proportion
-      const aRedeem = new BN(Number(aBalance.amount) / 2);
-      const bRedeem = new BN(Number(bBalance.amount) / 2);
+      const asolBalance = await getAccount(connection, userSetup.asolAccount);
+      const redeemAmount = new BN(Math.floor(Number(asolBalance.amount) / 2));
 
-      const aLstBefore = await getAccount(connection, setupA.lstAccount);
-      const bLstBefore = await getAccount(connection, setupB.lstAccount);
+      try {
+        await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, redeemAmount, new BN(1));
+        expect.fail("Expected OraclePriceStale");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OraclePriceStale");
+      }
 
-      await redeemAsol(setupA.user, setupA.lstAccount, setupA.asolAccount, aRedeem, new BN(100_000));
-      await redeemAsol(setupB.user, setupB.lstAccount, setupB.asolAccount, bRedeem, new BN(100_000));
+      await updateOracleParameters(new BN(150), new BN(150), UNCERTAINTY_MAX_BPS, new BN(1));
+      const state = await getGlobalState();
+      expect(state.maxOracleStalenessSlots.toNumber()).to.equal(150);
 
-      const aLstAfter = await getAccount(connection, setupA.lstAccount);
-      const bLstAfter = await getAccount(connection, setupB.lstAccount);
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, redeemAmount, new BN(1));
 
-      const aReceived = Number(aLstAfter.amount) - Number(aLstBefore.amount);
-      const bReceived = Number(bLstAfter.amount) - Number(bLstBefore.amount);
+      const asolAfter = await getAccount(connection, userSetup.asolAccount);
+      expect(new BN(asolAfter.amount.toString()).lt(new BN(asolBalance.amount.toString()))).to.be.true;
+    });
+  });
 
-      // Both should receive roughly equal (within 1%)
-      const diff = Math.abs(aReceived - bReceived);
-      const tolerance = Math.max(aReceived, bReceived) * 0.01;
+  describe("57. Versioned Instruction Entrypoints", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
 
-      expect(diff).to.be.lessThan(tolerance);
-      console.log(`  User A received: ${aReceived / 1e9} LST`);
-      console.log(`  User B received: ${bReceived / 1e9} LST`);
-      console.log(`  ✓ Sequential redemptions are fair`);
+    it("v1 and v2 amUSD mint/redeem produce identical transitions", async () => {
+      const userSetup = await setupUser(200);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(100 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
+
+      const lstAmount = new BN(LAMPORTS_PER_SOL);
+
+      // Mint: v1 then v2
+      let before = await getGlobalState();
+      let amusdBefore = await getTokenAmountOrZero(userSetup.amusdAccount);
+      const v1MintSig = await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, lstAmount, new BN(1));
+      let after = await getGlobalState();
+      let amusdAfter = await getTokenAmountOrZero(userSetup.amusdAccount);
+      const v1SupplyDelta = after.amusdSupply.sub(before.amusdSupply);
+      const v1LstDelta = after.totalLstAmount.sub(before.totalLstAmount);
+      const v1UserDelta = amusdAfter.sub(amusdBefore);
+
+      before = after;
+      amusdBefore = amusdAfter;
+      const v2MintSig = await mintAmUSDV2(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, lstAmount, new BN(1), 1, new BN(42));
+      after = await getGlobalState();
+      amusdAfter = await getTokenAmountOrZero(userSetup.amusdAccount);
+
+      expect(after.amusdSupply.sub(before.amusdSupply).eq(v1SupplyDelta)).to.be.true;
+      expect(after.totalLstAmount.sub(before.totalLstAmount).eq(v1LstDelta)).to.be.true;
+      expect(amusdAfter.sub(amusdBefore).eq(v1UserDelta)).to.be.true;
+
+      const v1MintEvent = (await getEmittedEvents(v1MintSig)).find(e => e.name === "amUsdMinted");
+      const v2MintEvent = (await getEmittedEvents(v2MintSig)).find(e => e.name === "amUsdMinted");
+      expect(v1MintEvent).to.not.be.undefined;
+      expect(v2MintEvent).to.not.be.undefined;
+      expect(v1MintEvent!.data.entrypointVersion).to.equal(1);
+      expect(v2MintEvent!.data.entrypointVersion).to.equal(2);
+      expect(v1MintEvent!.data.amusdMinted.eq(v2MintEvent!.data.amusdMinted)).to.be.true;
+      expect(v1MintEvent!.data.fee.eq(v2MintEvent!.data.fee)).to.be.true;
+      expect(v1MintEvent!.data.feeBps.eq(v2MintEvent!.data.feeBps)).to.be.true;
+      expect(v2MintEvent!.data.operationCounter.gt(v1MintEvent!.data.operationCounter)).to.be.true;
+      expect(v1MintEvent!.data.crMultiplierBps.gten(10_000)).to.be.true;
+      expect(v1MintEvent!.data.uncertaintyMultiplierBps.gten(10_000)).to.be.true;
+      expect(v1MintEvent!.data.clientOrderId.eqn(0)).to.be.true;
+      expect(v2MintEvent!.data.clientOrderId.eqn(42)).to.be.true;
+
+      // Redeem: v1 then v2
+      const redeemAmount = v1UserDelta.divn(2);
+      let lstBefore = await getTokenAmountOrZero(userSetup.lstAccount);
+      const v1RedeemSig = await redeemAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, redeemAmount, new BN(1));
+      let lstAfter = await getTokenAmountOrZero(userSetup.lstAccount);
+      const v1LstOut = lstAfter.sub(lstBefore);
+
+      lstBefore = lstAfter;
+      const v2RedeemSig = await redeemAmUSDV2(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, redeemAmount, new BN(1), 1, false, new BN(43));
+      lstAfter = await getTokenAmountOrZero(userSetup.lstAccount);
+      expect(lstAfter.sub(lstBefore).eq(v1LstOut)).to.be.true;
+
+      const v1RedeemEvent = (await getEmittedEvents(v1RedeemSig)).find(e => e.name === "amUsdRedeemed");
+      const v2RedeemEvent = (await getEmittedEvents(v2RedeemSig)).find(e => e.name === "amUsdRedeemed");
+      expect(v1RedeemEvent!.data.entrypointVersion).to.equal(1);
+      expect(v2RedeemEvent!.data.entrypointVersion).to.equal(2);
+      expect(v1RedeemEvent!.data.clientOrderId.eqn(0)).to.be.true;
+      expect(v2RedeemEvent!.data.clientOrderId.eqn(43)).to.be.true;
+      expect(v1RedeemEvent!.data.feeBps.eq(v2RedeemEvent!.data.feeBps)).to.be.true;
+      expect(v1RedeemEvent!.data.uncertaintyMultiplierBps.eqn(10_000)).to.be.true;
     });
-  });
 
-  describe("37. Extreme Leverage Scenario", () => {
-    it("Protocol handles high leverage ratio correctly", async () => {
-      const userSetup = await setupUser(500);
+    it("Rejects unknown args versions", async () => {
+      const userSetup = await setupUser(10);
 
-      // Build up significant equity first - use LESS than before to leave room for debt
-      const equityDeposit = new BN(100 * LAMPORTS_PER_SOL);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        equityDeposit, new BN(1));
+      for (const argsVersion of [0, 6]) {
+        try {
+          await mintAmUSDV2(
+            userSetup.user,
+            userSetup.lstAccount,
+            userSetup.amusdAccount,
+            new BN(LAMPORTS_PER_SOL),
+            new BN(1),
+            argsVersion
+          );
+          expect.fail("Expected UnsupportedArgsVersion");
+        } catch (err: any) {
+          expect(err.toString()).to.include("UnsupportedArgsVersion");
+        }
+      }
+    });
+  });
 
-      // Check user's remaining LST balance
-      const userLstBalance = await getAccount(connection, userSetup.lstAccount);
-      const remainingLst = new BN(Number(userLstBalance.amount));
+  describe("58. Rounding Reserve Cap Updates", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
 
-      // Try to maximize debt while staying above CR
+    after(async () => {
       const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
+      await updateRoundingReserveCap(BN.max(new BN(10_000), state.roundingReserveLamports));
+    });
 
-      // Calculate max debt for target CR of 150%
-      // CR = TVL / Liability >= 1.5
-      // Liability <= TVL / 1.5
-      const maxLiability = tvl.mul(new BN(10_000)).div(new BN(15_000));
-      const currentLiability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const roomForDebt = maxLiability.sub(currentLiability);
+    it("Rejects a cap below the current reserve", async () => {
+      const state = await getGlobalState();
+      if (state.roundingReserveLamports.isZero()) {
+        // Nothing accumulated yet; any cap is >= reserve.
+        await updateRoundingReserveCap(new BN(0));
+        await updateRoundingReserveCap(new BN(10_000));
+        return;
+      }
 
-      if (roomForDebt.gt(new BN(0))) {
-        // Convert room to LST terms
-        const additionalLst = roomForDebt.mul(SOL_PRECISION).div(state.mockLstToSolRate);
+      try {
+        await updateRoundingReserveCap(state.roundingReserveLamports.subn(1));
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
+    });
 
-        // Use 50% of max to stay safe, and ensure we don't exceed user balance
-        const safeMintLst = BN.min(
-          additionalLst.mul(new BN(50)).div(new BN(100)), // 50% of max
-          remainingLst.sub(new BN(LAMPORTS_PER_SOL)) // Leave 1 LST buffer
-        );
+    it("Mint rejected at the cap succeeds after the cap is raised", async () => {
+      const userSetup = await setupUser(50);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(20 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
 
-        if (safeMintLst.gt(new BN(LAMPORTS_PER_SOL))) {
-          await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount,
-            safeMintLst, new BN(1));
+      // Pin the cap to the current reserve so any dust credit overflows it.
+      let state = await getGlobalState();
+      await updateRoundingReserveCap(state.roundingReserveLamports);
 
-          const crAfter = await calculateCR();
-          console.log(`  High leverage CR: ${crAfter.toNumber()} bps`);
-          expect(crAfter.gte(new BN(14_000))).to.be.true; // Above min CR
-        } else {
-          console.log("  Insufficient room for meaningful leverage test (protocol well-capitalized)");
-        }
-      } else {
-        console.log("  No room for additional debt (protocol already at max leverage)");
+      // Odd amount guarantees a non-zero rounding delta on LST -> SOL -> USD.
+      const dustyAmount = new BN(1_000_000_007);
+
+      try {
+        await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, dustyAmount, new BN(1));
+        expect.fail("Expected RoundingReserveExceeded");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RoundingReserveExceeded");
       }
 
-      console.log("  ✓ High leverage scenario handled correctly");
+      state = await getGlobalState();
+      await updateRoundingReserveCap(state.roundingReserveLamports.addn(10_000));
+
+      state = await getGlobalState();
+      expect(state.maxRoundingReserveLamports.eq(state.roundingReserveLamports.addn(10_000))).to.be.true;
+
+      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, dustyAmount, new BN(1));
+
+      const after = await getGlobalState();
+      expect(after.roundingReserveLamports.gt(state.roundingReserveLamports)).to.be.true;
     });
   });
 
-  describe("38. Global State Singleton Enforcement", () => {
-    it("Cannot initialize protocol twice", async () => {
-      // Try to reinitialize with different params
-      const newAuthority = Keypair.generate();
-      await airdropSol(newAuthority.publicKey, 5);
+  describe("59. Write-Set Budget", () => {
+    type UserAccounts = {
+      user: Keypair,
+      lstAccount: PublicKey,
+      amusdAccount: PublicKey,
+      asolAccount: PublicKey,
+    };
 
-      const newAmusd = Keypair.generate();
-      const newAsol = Keypair.generate();
+    async function buildCoreInstructions(u: UserAccounts) {
+      const state = await getGlobalState();
+      const [vaultAuthority] = getVaultAuthorityPda();
+      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.amusdMint.publicKey,
+        owner: state.treasury,
+      });
+      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
+        mint: protocolState.asolMint.publicKey,
+        owner: state.treasury,
+      });
 
-      try {
-        await program.methods
-          .initialize(
-            new BN(10_000),
-            new BN(12_000),
-            MOCK_SOL_PRICE_USD,
-            MOCK_LST_TO_SOL_RATE
-          )
-          .accounts({
-            authority: newAuthority.publicKey,
-            globalState: protocolState.globalState, // Same PDA
-            amusdMint: newAmusd.publicKey,
-            asolMint: newAsol.publicKey,
-            vault: protocolState.vault,
-            lstMint: protocolState.lstMint,
-            vaultAuthority: protocolState.vaultAuthority,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          } as any)
-          .signers([newAuthority, newAmusd, newAsol])
-          .rpc();
+      const accounts = {
+        user: u.user.publicKey,
+        globalState: protocolState.globalState,
+        amusdMint: protocolState.amusdMint.publicKey,
+        asolMint: protocolState.asolMint.publicKey,
+        userAmusdAccount: u.amusdAccount,
+        userAsolAccount: u.asolAccount,
+        treasuryAmusdAccount,
+        treasuryAsolAccount,
+        treasury: state.treasury,
+        userLstAccount: u.lstAccount,
+        vault: protocolState.vault,
+        vaultAuthority,
+        lstMint: protocolState.lstMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        clock: SYSVAR_CLOCK_PUBKEY,
+      } as any;
+
+      const one = new BN(LAMPORTS_PER_SOL);
+      return {
+        mintAmusd: await program.methods.mintAmusd(one, new BN(1)).accounts(accounts).instruction(),
+        redeemAmusd: await program.methods.redeemAmusd(one, new BN(1)).accounts(accounts).instruction(),
+        mintAsol: await program.methods.mintAsol(one, new BN(1)).accounts(accounts).instruction(),
+        redeemAsol: await program.methods.redeemAsol(one, new BN(1)).accounts(accounts).instruction(),
+        mintPair: await program.methods.mintPair(one, new BN(1), new BN(1)).accounts(accounts).instruction(),
+        redeemPair: await program.methods.redeemPair(one, one, new BN(1)).accounts(accounts).instruction(),
+        convertAmusdToAsol: await program.methods.convertAmusdToAsol(one, new BN(1)).accounts(accounts).instruction(),
+        convertAsolToAmusd: await program.methods.convertAsolToAmusd(one, new BN(1)).accounts(accounts).instruction(),
+        singletons: [
+          protocolState.globalState,
+          protocolState.amusdMint.publicKey,
+          protocolState.asolMint.publicKey,
+          protocolState.vault,
+          treasuryAmusdAccount,
+          treasuryAsolAccount,
+        ].map(k => k.toBase58()),
+      };
+    }
 
-        expect.fail("Should reject double initialization");
-      } catch (err: any) {
-        // Account already initialized
-        expect(
-          err.toString().includes("already in use") ||
-          err.toString().includes("Error") // Generic for already exists
-        ).to.be.true;
-        console.log("  ✓ Double initialization prevented");
+    function writableKeys(ix: anchor.web3.TransactionInstruction, payer: PublicKey): Set<string> {
+      const tx = new Transaction().add(ix);
+      tx.feePayer = payer;
+      tx.recentBlockhash = PublicKey.default.toBase58();
+      const message = tx.compileMessage();
+
+      const keys = new Set<string>();
+      message.accountKeys.forEach((key, i) => {
+        if (message.isAccountWritable(i)) {
+          keys.add(key.toBase58());
+        }
+      });
+      return keys;
+    }
+
+    it("Two users only share the intended protocol singletons", async () => {
+      const userA = await setupUser(5);
+      const userB = await setupUser(5);
+
+      const ixA = await buildCoreInstructions(userA);
+      const ixB = await buildCoreInstructions(userB);
+      const allowed = new Set(ixA.singletons);
+
+      for (const name of ["mintAmusd", "redeemAmusd", "mintAsol", "redeemAsol", "mintPair", "redeemPair", "convertAmusdToAsol", "convertAsolToAmusd"] as const) {
+        const writesA = writableKeys(ixA[name], userA.user.publicKey);
+        const writesB = writableKeys(ixB[name], userB.user.publicKey);
+
+        const shared = [...writesA].filter(k => writesB.has(k));
+        for (const key of shared) {
+          expect(allowed.has(key), `${name} shares unexpected writable ${key}`).to.be.true;
+        }
+        expect(shared).to.include(protocolState.globalState.toBase58());
       }
     });
   });
 
-  describe("39. Parameter Bounds Validation", () => {
-    it("Rejects min_cr higher than target_cr", async () => {
+  describe("60. Two-Step Authority Transfer", () => {
+    const candidateA = Keypair.generate();
+    const candidateB = Keypair.generate();
+
+    before(async () => {
+      await airdropSol(candidateA.publicKey, 1);
+      await airdropSol(candidateB.publicKey, 1);
+    });
+
+    it("Accept fails when nothing is pending", async () => {
       try {
-        await program.methods
-          .updateParameters(new BN(20_000), new BN(15_000)) // min > target
-          .accounts({
-            authority: protocolState.authority.publicKey,
-            globalState: protocolState.globalState,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          })
-          .signers([protocolState.authority])
-          .rpc();
+        await acceptAuthority(candidateA);
+        expect.fail("Expected InvalidPendingAuthority");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidPendingAuthority");
+      }
+    });
 
-        expect.fail("Should reject invalid parameter bounds");
+    it("A new proposal overwrites the pending one", async () => {
+      await proposeAuthority(protocolState.authority, candidateA.publicKey);
+      let state = await getGlobalState();
+      expect(state.pendingAuthority.toBase58()).to.equal(candidateA.publicKey.toBase58());
+
+      await proposeAuthority(protocolState.authority, candidateB.publicKey);
+      state = await getGlobalState();
+      expect(state.pendingAuthority.toBase58()).to.equal(candidateB.publicKey.toBase58());
+      expect(state.authority.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
+    });
+
+    it("Rejects accept from a signer that is not pending", async () => {
+      try {
+        await acceptAuthority(candidateA);
+        expect.fail("Expected InvalidPendingAuthority");
       } catch (err: any) {
-        expect(
-          err.toString().includes("InvalidParameter") ||
-          err.toString().includes("6016") // Error code for InvalidParameter
-        ).to.be.true;
-        console.log("  ✓ Invalid parameter bounds rejected");
+        expect(err.toString()).to.include("InvalidPendingAuthority");
       }
     });
 
-    it("Rejects CR below 100%", async () => {
+    it("Pending authority accepts and the old authority loses access", async () => {
+      await acceptAuthority(candidateB);
+
+      let state = await getGlobalState();
+      expect(state.authority.toBase58()).to.equal(candidateB.publicKey.toBase58());
+      expect(state.pendingAuthority.toBase58()).to.equal(PublicKey.default.toBase58());
+
       try {
-        await program.methods
-          .updateParameters(new BN(9_000), new BN(10_000)) // 90% min CR
-          .accounts({
-            authority: protocolState.authority.publicKey,
-            globalState: protocolState.globalState,
-            clock: SYSVAR_CLOCK_PUBKEY,
-          })
-          .signers([protocolState.authority])
-          .rpc();
+        await setPause(false, false);
+        expect.fail("Old authority should be rejected");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
+      }
 
-        expect.fail("Should reject CR below 100%");
+      try {
+        await proposeAuthority(protocolState.authority, protocolState.authority.publicKey);
+        expect.fail("Old authority should be rejected");
       } catch (err: any) {
-        // Either rejected or the program doesn't validate this
-        console.log(`  Result: ${err.toString().substring(0, 80)}`);
+        expect(err.toString()).to.include("ConstraintHasOne");
       }
+
+      // Hand the protocol back so the rest of the suite keeps its admin.
+      await proposeAuthority(candidateB, protocolState.authority.publicKey);
+      await acceptAuthority(protocolState.authority);
+
+      state = await getGlobalState();
+      expect(state.authority.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
     });
   });
 
-  describe("40. Comprehensive Final Audit Checks", () => {
-    it("All critical invariants hold after stress testing", async () => {
-      const state = await getGlobalState();
+  describe("61. Dust Donation", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
 
-      // 1. Balance Sheet
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const equity = computeEquitySol(tvl, liability);
-      expect(tvl.gte(liability.add(equity).sub(new BN(10000)))).to.be.true;
+    it("Donates 1 base unit of amUSD and aSOL", async () => {
+      const userSetup = await setupUser(40);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(20 * LAMPORTS_PER_SOL), new BN(1));
+      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, new BN(LAMPORTS_PER_SOL), new BN(1));
+
+      let before = await getGlobalState();
+      await donateDust(userSetup.user, "amUsd", protocolState.amusdMint.publicKey, userSetup.amusdAccount, new BN(1));
+      let after = await getGlobalState();
+      expect(after.amusdSupply.eq(before.amusdSupply.subn(1))).to.be.true;
+      // 1 micro-USD is worth 10 lamports at $100; credit never exceeds it.
+      expect(after.roundingReserveLamports.gte(before.roundingReserveLamports)).to.be.true;
+      expect(after.roundingReserveLamports.sub(before.roundingReserveLamports).lten(10)).to.be.true;
+
+      before = after;
+      await donateDust(userSetup.user, "asol", protocolState.asolMint.publicKey, userSetup.asolAccount, new BN(1));
+      after = await getGlobalState();
+      expect(after.asolSupply.eq(before.asolSupply.subn(1))).to.be.true;
+      // 1 aSOL base unit is worth ~1 lamport at NAV ~1; rounded down.
+      expect(after.roundingReserveLamports.sub(before.roundingReserveLamports).lten(1)).to.be.true;
 
-      // 2. Supply Sync
       const amusdMint = await getMint(connection, protocolState.amusdMint.publicKey);
       const asolMint = await getMint(connection, protocolState.asolMint.publicKey);
-      expect(Number(amusdMint.supply)).to.equal(state.amusdSupply.toNumber());
-      expect(Number(asolMint.supply)).to.equal(state.asolSupply.toNumber());
+      expect(Number(amusdMint.supply)).to.equal(after.amusdSupply.toNumber());
+      expect(Number(asolMint.supply)).to.equal(after.asolSupply.toNumber());
+    });
 
-      // 3. Vault Balance
-      const vault = await getAccount(connection, protocolState.vault);
-      expect(Number(vault.amount)).to.equal(state.totalLstAmount.toNumber());
+    it("Credits the reserve with the rounded-down value of a larger donation", async () => {
+      const userSetup = await setupUser(40);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(20 * LAMPORTS_PER_SOL), new BN(1));
+      await mintAmUSD(userSetup.user, userSetup.lstAccount, userSetup.amusdAccount, new BN(LAMPORTS_PER_SOL), new BN(1));
+
+      const before = await getGlobalState();
+      const amount = new BN(500); // 0.0005 amUSD
+      await donateDust(userSetup.user, "amUsd", protocolState.amusdMint.publicKey, userSetup.amusdAccount, amount);
+      const after = await getGlobalState();
+
+      const valueDown = amount.mul(SOL_PRECISION).div(before.mockSolPriceUsd);
+      const headroom = before.maxRoundingReserveLamports.sub(before.roundingReserveLamports);
+      const expectedCredit = BN.min(valueDown, headroom);
+      expect(after.roundingReserveLamports.sub(before.roundingReserveLamports).eq(expectedCredit)).to.be.true;
+      expect(after.amusdSupply.eq(before.amusdSupply.sub(amount))).to.be.true;
+    });
 
-      // 4. Protocol is solvent (after resetting prices)
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE);
-      const finalState = await getGlobalState();
-      const finalTvl = computeTvlSol(finalState.totalLstAmount, finalState.mockLstToSolRate);
-      const finalLiability = computeLiabilitySol(finalState.amusdSupply, finalState.mockSolPriceUsd);
-      expect(finalTvl.gte(finalLiability)).to.be.true;
+    it("Rejects a mint that does not match the donated token", async () => {
+      const userSetup = await setupUser(5);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(2 * LAMPORTS_PER_SOL), new BN(1));
 
-      // 5. CR is healthy
-      const cr = computeCrBps(finalTvl, finalLiability);
-      if (!finalLiability.isZero()) {
-        expect(cr.gte(state.minCrBps)).to.be.true;
+      try {
+        await donateDust(userSetup.user, "amUsd", protocolState.asolMint.publicKey, userSetup.asolAccount, new BN(1));
+        expect.fail("Expected InvalidMint");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidMint");
       }
-
-      console.log("\n=== AUDIT CHECKLIST PASSED ===");
-      console.log("  ✓ Balance sheet invariant");
-      console.log("  ✓ Supply synchronization");
-      console.log("  ✓ Vault balance accuracy");
-      console.log("  ✓ Protocol solvency");
-      console.log("  ✓ Healthy collateral ratio");
-      console.log("==============================\n");
     });
   });
 
-  describe("41. Treasury Address Validation", () => {
-    it("Treasury receives fees to correct address", async () => {
-      const state = await getGlobalState();
-      expect(state.treasury.toBase58()).to.equal(protocolState.authority.publicKey.toBase58());
+  describe("62. Redeem Pause Timeout", () => {
+    const DEFAULT_MAX_PAUSE_DURATION_SLOTS = new BN(1_512_000);
 
-      const treasuryAsolAta = await anchor.utils.token.associatedAddress({
-        mint: protocolState.asolMint.publicKey,
-        owner: state.treasury,
-      });
-      const treasuryLstAta = await anchor.utils.token.associatedAddress({
-        mint: protocolState.lstMint,
-        owner: state.treasury,
-      });
+    beforeEach(async () => {
+      await setPause(false, false);
+      await resetAndSyncSnapshots();
+    });
 
-      console.log("  ✓ Treasury address is correctly configured");
+    after(async () => {
+      await setPause(false, false);
+      await updateMaxPauseDuration(DEFAULT_MAX_PAUSE_DURATION_SLOTS);
     });
-  });
 
-  describe("42. Concurrent User Stress Test", () => {
-    it("Handles multiple users minting simultaneously", async () => {
-      // Setup 3 users
-      const users = await Promise.all([
-        setupUser(50),
-        setupUser(50),
-        setupUser(50),
-      ]);
+    it("Rejects force unpause while redemptions are live", async () => {
+      const caller = (await setupUser(1)).user;
+      try {
+        await forceUnpauseRedeem(caller);
+        expect.fail("Expected RedeemNotPaused");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RedeemNotPaused");
+      }
+    });
 
-      const stateBefore = await getGlobalState();
-      const supplyBefore = stateBefore.asolSupply;
+    it("Rejects force unpause inside the window and keeps the original start slot", async () => {
+      await updateMaxPauseDuration(new BN(1_000));
+      await setPause(true, true);
+      const paused = await getGlobalState();
+      expect(paused.pauseStartedSlot.gtn(0)).to.be.true;
 
-      // All mint simultaneously (in parallel)
-      const mintAmount = new BN(5 * LAMPORTS_PER_SOL);
-      await Promise.all(users.map(u =>
-        mintAsol(u.user, u.lstAccount, u.asolAccount, mintAmount, new BN(1))
-      ));
+      // Re-pausing must not push the deadline out.
+      await setPause(false, true);
+      const repaused = await getGlobalState();
+      expect(repaused.pauseStartedSlot.eq(paused.pauseStartedSlot)).to.be.true;
 
-      const stateAfter = await getGlobalState();
+      const caller = (await setupUser(1)).user;
+      try {
+        await forceUnpauseRedeem(caller);
+        expect.fail("Expected PauseTimeoutNotElapsed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("PauseTimeoutNotElapsed");
+      }
 
-      // Verify vault increased by total deposited LST
-      const expectedLstIncrease = new BN(15 * LAMPORTS_PER_SOL);
-      const actualLstIncrease = stateAfter.totalLstAmount.sub(stateBefore.totalLstAmount);
-      expect(actualLstIncrease.eq(expectedLstIncrease)).to.be.true;
+      await setPause(false, false);
+      expect((await getGlobalState()).pauseStartedSlot.toNumber()).to.equal(0);
+    });
 
-      // Supply should increase (exact amount depends on NAV and fees)
-      const actualIncrease = stateAfter.asolSupply.sub(supplyBefore);
-      expect(actualIncrease.gt(new BN(0))).to.be.true;
+    it("Lets anyone lift only the redeem pause after the timeout", async () => {
+      const userSetup = await setupUser(25);
+      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(10 * LAMPORTS_PER_SOL), new BN(1));
 
-      console.log(`  ✓ 3 concurrent mints processed correctly`);
-    });
-  });
+      const maxPause = 5;
+      await updateMaxPauseDuration(new BN(maxPause));
+      await setPause(true, true);
+      const paused = await getGlobalState();
 
-  describe("43. Minimum Output Guarantees", () => {
-    it("min_lst_out protects against sandwich attacks on redemption", async () => {
-      const userSetup = await setupUser(50);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(10 * LAMPORTS_PER_SOL), new BN(1));
+      // Strictly more than max_pause_duration_slots must elapse.
+      await waitForSlotDelta(maxPause + 2, 60_000);
 
-      const balance = await getAccount(connection, userSetup.asolAccount);
-      const redeemAmount = new BN(Number(balance.amount) / 2);
+      const stranger = (await setupUser(1)).user;
+      const sig = await forceUnpauseRedeem(stranger);
 
-      // Calculate expected output
       const state = await getGlobalState();
-      const tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      const liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const nav = computeAsolNav(tvl, liability, state.asolSupply);
-
-      const expectedSolValue = redeemAmount.mul(nav).div(SOL_PRECISION);
-      const expectedLst = expectedSolValue.mul(SOL_PRECISION).div(state.mockLstToSolRate);
-      const cr = computeCrBps(tvl, liability);
-      const feeBps = feeBpsIncreaseWhenLow(ASOL_REDEEM_FEE_BPS, cr, TARGET_CR_BPS);
-      const [expectedNet, _] = applyFee(expectedLst, feeBps);
+      expect(state.redeemPaused).to.be.false;
+      expect(state.mintPaused).to.be.true;
+      expect(state.pauseStartedSlot.toNumber()).to.equal(0);
 
-      // Set min_lst_out very close to expected (95%)
-      const tightMinOut = expectedNet.mul(new BN(95)).div(new BN(100));
+      const events = await getEmittedEvents(sig);
+      const unpaused = events.find((e) => e.name === "redeemForceUnpaused");
+      expect(unpaused).to.not.be.undefined;
+      expect(unpaused!.data.caller.equals(stranger.publicKey)).to.be.true;
+      expect(unpaused!.data.pauseStartedSlot.eq(paused.pauseStartedSlot)).to.be.true;
+      expect(unpaused!.data.slot.sub(paused.pauseStartedSlot).gtn(maxPause)).to.be.true;
 
-      // Should succeed with reasonable min_out
-      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        redeemAmount, tightMinOut);
+      await resetAndSyncSnapshots();
+      const asolBalance = await getAccount(connection, userSetup.asolAccount);
+      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(Math.floor(Number(asolBalance.amount) / 2)), new BN(1));
 
-      console.log("  ✓ Tight slippage protection works correctly");
+      try {
+        await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, new BN(LAMPORTS_PER_SOL), new BN(1));
+        expect.fail("Expected MintPaused");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MintPaused");
+      }
     });
-  });
-
-  describe("44. NAV Consistency Under Rapid Operations", () => {
-    it("NAV remains consistent across rapid mint/redeem cycles", async () => {
-      const userSetup = await setupUser(100);
-
-      // Capture initial state
-      let state = await getGlobalState();
-      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const navStart = computeAsolNav(tvl, liability, state.asolSupply);
-
-      // Rapid cycle: mint then redeem
-      const amount = new BN(5 * LAMPORTS_PER_SOL);
-      await mintAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount, amount, new BN(1));
-
-      const balance = await getAccount(connection, userSetup.asolAccount);
-      await redeemAsol(userSetup.user, userSetup.lstAccount, userSetup.asolAccount,
-        new BN(Number(balance.amount)), new BN(100_000));
-
-      // NAV should be similar (small drift due to fees)
-      state = await getGlobalState();
-      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const navEnd = computeAsolNav(tvl, liability, state.asolSupply);
-
-      // NAV should not deviate by more than 1%
-      const drift = navEnd.sub(navStart).abs();
-      const tolerance = navStart.div(new BN(100));
 
-      expect(drift.lte(tolerance)).to.be.true;
-      console.log(`  NAV drift: ${drift.toNumber() / 1e9} SOL (tolerance: ${tolerance.toNumber() / 1e9} SOL)`);
-      console.log("  ✓ NAV consistent under rapid operations");
+    it("Rejects a zero pause timeout", async () => {
+      try {
+        await updateMaxPauseDuration(new BN(0));
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
     });
   });
 
-  describe("45. Empty Protocol State Edge Cases", () => {
-    it("Handles redemption when only one user exists", async () => {
-      const state = await getGlobalState();
+  describe("63. Timelocked Parameter Changes", () => {
+    const RISK = 0;
+    const FEES = 1;
+    const TIMELOCK = 3;
+    const MAX_PAUSE_DURATION = 19;
+    const TVL_CAP = 35;
+    const TIMELOCK_SLOTS = 10;
 
-      // Verify protocol can handle when supply approaches zero
-      if (state.asolSupply.gt(new BN(0)) && state.amusdSupply.eq(new BN(0))) {
-        console.log("  Protocol handles equity-only state correctly");
-      }
+    before(async () => {
+      // With no timelock yet, enabling one applies immediately.
+      await queueParameterUpdate({ timelock: { paramTimelockSlots: new BN(TIMELOCK_SLOTS) } }, TIMELOCK);
+      const executor = (await setupUser(1)).user;
+      await executeParameterUpdate(executor, TIMELOCK);
+      expect((await getGlobalState()).paramTimelockSlots.toNumber()).to.equal(TIMELOCK_SLOTS);
+    });
 
-      console.log("  ✓ Edge cases for low-activity protocol validated");
+    after(async () => {
+      await queueParameterUpdate({ risk: { minCrBps: MIN_CR_BPS, targetCrBps: TARGET_CR_BPS } }, RISK);
+      await queueParameterUpdate({ timelock: { paramTimelockSlots: new BN(0) } }, TIMELOCK);
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      const executor = (await setupUser(1)).user;
+      await executeParameterUpdate(executor, RISK);
+      await executeParameterUpdate(executor, TIMELOCK);
+      expect((await getGlobalState()).paramTimelockSlots.toNumber()).to.equal(0);
     });
-  });
 
-  describe("46. Fee Upper Bound Validation", () => {
-    it("Fees are within expected bounds", async () => {
-      // Verify fee constants are reasonable
-      expect(AMUSD_MINT_FEE_BPS).to.be.lessThanOrEqual(500); // Max 5%
-      expect(AMUSD_REDEEM_FEE_BPS).to.be.lessThanOrEqual(500);
-      expect(ASOL_MINT_FEE_BPS).to.be.lessThanOrEqual(500);
-      expect(ASOL_REDEEM_FEE_BPS).to.be.lessThanOrEqual(500);
+    it("Disables the instant setters while the timelock is active", async () => {
+      try {
+        await program.methods
+          .updateParameters(new BN(10_000), new BN(10_001))
+          .accounts({
+            authority: protocolState.authority.publicKey,
+            globalState: protocolState.globalState,
+            clock: SYSVAR_CLOCK_PUBKEY,
+          } as any)
+          .signers([protocolState.authority])
+          .rpc();
+        expect.fail("Expected TimelockActive");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TimelockActive");
+      }
 
-      console.log("  ✓ All fees within reasonable bounds (<=5%)");
-    });
-  });
+      try {
+        await restoreDefaultFees();
+        expect.fail("Expected TimelockActive");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TimelockActive");
+      }
 
-  describe("47. A5 Oracle Freshness", () => {
-    it("Rejects mint when oracle snapshot is stale", async () => {
-      const userSetup = await setupUser(25);
+      for (const update of [
+        () => updateMaxPauseDuration(new BN(1_000)),
+        () => updateRoundingReserveCap(new BN(10_000)),
+      ]) {
+        try {
+          await update();
+          expect.fail("Expected TimelockActive");
+        } catch (err: any) {
+          expect(err.toString()).to.include("TimelockActive");
+        }
+      }
+    });
 
-      // Start from clean/fresh snapshots.
-      await resetAndSyncSnapshots();
+    it("Rejects early execution, then lets anyone execute after the ETA", async () => {
+      const sig = await queueParameterUpdate({ risk: { minCrBps: new BN(12_500), targetCrBps: new BN(16_000) } }, RISK);
+      const queued = (await getEmittedEvents(sig)).find((e) => e.name === "parameterUpdateQueued");
+      expect(queued!.data.etaSlot.sub(queued!.data.queuedSlot).toNumber()).to.equal(TIMELOCK_SLOTS);
 
-      const state = await getGlobalState();
-      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
+      const stranger = (await setupUser(1)).user;
+      try {
+        await executeParameterUpdate(stranger, RISK);
+        expect.fail("Expected TimelockNotElapsed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TimelockNotElapsed");
+      }
+      expect((await getGlobalState()).minCrBps.eq(MIN_CR_BPS)).to.be.true;
 
-      // Let oracle snapshot become stale.
-      await waitForSlotDelta(staleSlots, 180_000);
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      await executeParameterUpdate(stranger, RISK);
 
-      // Refresh ONLY LST snapshot so the failure source is oracle staleness.
-      await syncExchangeRate();
+      const state = await getGlobalState();
+      expect(state.minCrBps.toNumber()).to.equal(12_500);
+      expect(state.targetCrBps.toNumber()).to.equal(16_000);
+      expect(await connection.getAccountInfo(pendingParamChangePda(RISK))).to.be.null;
+    });
 
+    it("Rejects an invalid change at queue time", async () => {
       try {
-        await mintAsol(
-          userSetup.user,
-          userSetup.lstAccount,
-          userSetup.asolAccount,
-          new BN(1 * LAMPORTS_PER_SOL),
-          new BN(1)
-        );
-        expect.fail("Expected OraclePriceStale");
+        await queueParameterUpdate({ risk: { minCrBps: new BN(9_000), targetCrBps: new BN(16_000) } }, RISK);
+        expect.fail("Expected InvalidParameter");
       } catch (err: any) {
-        const msg = err?.toString?.() ?? String(err);
-        expect(msg).to.include("OraclePriceStale");
-      } finally {
-        // Always restore clean state for downstream tests.
-        await resetAndSyncSnapshots();
+        expect(err.toString()).to.include("InvalidParameter");
       }
     });
-  });
 
+    it("Cancels a queued change so it can never execute", async () => {
+      const before = await getGlobalState();
+      await queueParameterUpdate(
+        {
+          fees: {
+            feeAmusdMintBps: new BN(900),
+            feeAmusdRedeemBps: new BN(900),
+            feeAsolMintBps: new BN(900),
+            feeAsolRedeemBps: new BN(900),
+            feeMinMultiplierBps: FEE_MIN_MULTIPLIER_BPS,
+            feeMaxMultiplierBps: FEE_MAX_MULTIPLIER_BPS,
+          },
+        },
+        FEES
+      );
+      await cancelParameterUpdate(FEES);
+      expect(await connection.getAccountInfo(pendingParamChangePda(FEES))).to.be.null;
 
-  describe("48. A5 CPI Guard Positive Vectors", () => {
-    beforeEach(async () => {
-      await resetAndSyncSnapshots();
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      const stranger = (await setupUser(1)).user;
+      try {
+        await executeParameterUpdate(stranger, FEES);
+        expect.fail("Expected AccountNotInitialized");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AccountNotInitialized");
+      }
+      expect((await getGlobalState()).feeAmusdMintBps.eq(before.feeAmusdMintBps)).to.be.true;
     });
-    it("Allows compute-budget preamble + direct call", async () => {
-      const userSetup = await setupUser(25);
-      const state = await getGlobalState();
-      const [vaultAuthority] = getVaultAuthorityPda();
 
-      const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
-        mint: protocolState.asolMint.publicKey,
-        owner: state.treasury,
-      });
+    it("Applies a setter-owned change only through its setter after the ETA", async () => {
+      const before = (await getGlobalState()).maxPauseDurationSlots;
+      const target = before.addn(100);
+      const pending = pendingParamChangePda(MAX_PAUSE_DURATION);
 
-      const ix = await program.methods
-        .mintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
-        .accounts({
-          user: userSetup.user.publicKey,
-          globalState: protocolState.globalState,
-          asolMint: protocolState.asolMint.publicKey,
-          userAsolAccount: userSetup.asolAccount,
-          treasuryAsolAccount,
-          treasury: state.treasury,
-          userLstAccount: userSetup.lstAccount,
-          vault: protocolState.vault,
-          vaultAuthority,
-          lstMint: protocolState.lstMint,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          clock: SYSVAR_CLOCK_PUBKEY,
-        } as any)
-        .instruction();
+      await queueParameterUpdate({ maxPauseDuration: { maxPauseDurationSlots: target } }, MAX_PAUSE_DURATION);
+      try {
+        await updateMaxPauseDuration(target.addn(1), pending);
+        expect.fail("Expected ParamChangeMismatch");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ParamChangeMismatch");
+      }
+      try {
+        await updateMaxPauseDuration(target, pending);
+        expect.fail("Expected TimelockNotElapsed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TimelockNotElapsed");
+      }
 
-      const tx = new Transaction().add(
-        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
-        ix
-      );
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      const stranger = (await setupUser(1)).user;
+      try {
+        await executeParameterUpdate(stranger, MAX_PAUSE_DURATION);
+        expect.fail("Expected ParamChangeAppliedBySetter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ParamChangeAppliedBySetter");
+      }
 
-      await provider.sendAndConfirm(tx, [userSetup.user]);
+      await updateMaxPauseDuration(target, pending);
+      expect((await getGlobalState()).maxPauseDurationSlots.eq(target)).to.be.true;
+      expect(await connection.getAccountInfo(pending)).to.be.null;
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      expect(new BN(userAsolBalance.amount.toString()).gt(new BN(0))).to.be.true;
+      await queueParameterUpdate({ maxPauseDuration: { maxPauseDurationSlots: before } }, MAX_PAUSE_DURATION);
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      await updateMaxPauseDuration(before, pending);
+      expect((await getGlobalState()).maxPauseDurationSlots.eq(before)).to.be.true;
     });
 
-    it("Allows direct call with no preamble", async () => {
-      const userSetup = await setupUser(25);
+    it("Keeps cap decreases immediate and timelocks raising them", async () => {
+      const before = (await getGlobalState()).maxTotalLstAmount;
+      const lowered = before.isZero() ? new BN(1_000_000).mul(SOL_PRECISION) : before.subn(1);
 
-      await mintAsol(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL),
-        new BN(1)
-      );
+      await updateTvlCap(lowered);
+      expect((await getGlobalState()).maxTotalLstAmount.eq(lowered)).to.be.true;
 
-      const userAsolBalance = await getAccount(connection, userSetup.asolAccount);
-      expect(new BN(userAsolBalance.amount.toString()).gt(new BN(0))).to.be.true;
+      try {
+        await updateTvlCap(before);
+        expect.fail("Expected TimelockActive");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TimelockActive");
+      }
+
+      await queueParameterUpdate({ tvlCap: { maxTotalLstAmount: before } }, TVL_CAP);
+      await waitForSlotDelta(TIMELOCK_SLOTS + 1, 60_000);
+      await updateTvlCap(before, pendingParamChangePda(TVL_CAP));
+      expect((await getGlobalState()).maxTotalLstAmount.eq(before)).to.be.true;
     });
-  });
 
-  describe("49. A5 amUSD Haircut Path", () => {
-    beforeEach(async () => {
-      await resetAndSyncSnapshots();
+    it("Keeps emergency pause immediate", async () => {
+      await setPause(true, true);
+      const state = await getGlobalState();
+      expect(state.mintPaused).to.be.true;
+      expect(state.redeemPaused).to.be.true;
+      await setPause(false, false);
     });
-    it("Uses haircut path and charges zero amUSD fee when CR < 100%", async () => {
-      const userSetup = await setupUser(400);
+  });
 
-      await mintAsol(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.asolAccount,
-        new BN(120 * LAMPORTS_PER_SOL),
-        new BN(1)
-      );
+  describe("64. Vault Freeze Guard", () => {
+    let holder: Awaited<ReturnType<typeof setupUser>>;
 
-      await mintAmUSD(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.amusdAccount,
-        new BN(25 * LAMPORTS_PER_SOL),
-        new BN(1)
+    async function freezeVault(): Promise<void> {
+      await freezeAccount(
+        connection,
+        protocolState.authority,
+        protocolState.vault,
+        protocolState.lstMint,
+        protocolState.authority
       );
+    }
 
-      let state = await getGlobalState();
-      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      expect(liability.gt(new BN(0))).to.be.true;
+    async function thawVault(): Promise<void> {
+      await thawAccount(
+        connection,
+        protocolState.authority,
+        protocolState.vault,
+        protocolState.lstMint,
+        protocolState.authority
+      );
+    }
 
-      const targetCr = new BN(9_500);
-      let crashPrice = targetCr
-        .mul(state.amusdSupply)
-        .mul(SOL_PRECISION)
-        .div(tvl.mul(BPS_PRECISION));
+    before(async () => {
+      await setPause(false, false);
+      await resetAndSyncSnapshots();
+      holder = await setupUser(10);
+      await mintAmUSD(holder.user, holder.lstAccount, holder.amusdAccount, SOL_PRECISION, new BN(0));
+    });
 
-      if (crashPrice.gte(state.mockSolPriceUsd)) {
-        crashPrice = state.mockSolPriceUsd.sub(new BN(1));
+    after(async () => {
+      const vault = await getAccount(connection, protocolState.vault);
+      if (vault.isFrozen) {
+        await thawVault();
       }
-      crashPrice = BN.max(crashPrice, new BN(1));
+      await setPause(false, false);
+    });
 
-      await updateMockPrices(crashPrice, state.mockLstToSolRate, new BN(0));
+    it("Records the LST freeze authority at initialize", async () => {
+      const state = await getGlobalState();
+      expect(state.lstMintHasFreezeAuthority).to.be.true;
+      expect(state.redeemPausedByVaultFreeze).to.be.false;
+    });
 
-      state = await getGlobalState();
-      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      let crBefore = computeCrBps(tvl, liability);
+    it("Fails mint and redeem cleanly with VaultFrozen", async () => {
+      await freezeVault();
+      const before = await getGlobalState();
 
-      if (!crBefore.lt(BPS_PRECISION)) {
-        const lower = BN.max(new BN(1), state.mockSolPriceUsd.muln(9).divn(10));
-        await updateMockPrices(lower, state.mockLstToSolRate, new BN(0));
-        state = await getGlobalState();
-        tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-        liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-        crBefore = computeCrBps(tvl, liability);
+      try {
+        await mintAmUSD(holder.user, holder.lstAccount, holder.amusdAccount, SOL_PRECISION, new BN(0));
+        expect.fail("Expected VaultFrozen");
+      } catch (err: any) {
+        expect(err.toString()).to.include("VaultFrozen");
+      }
+      try {
+        await redeemAmUSD(holder.user, holder.lstAccount, holder.amusdAccount, new BN(1_000_000), new BN(0));
+        expect.fail("Expected VaultFrozen");
+      } catch (err: any) {
+        expect(err.toString()).to.include("VaultFrozen");
       }
 
-      expect(crBefore.lt(BPS_PRECISION)).to.be.true;
-
-      const userAmusdBefore = new BN((await getAccount(connection, userSetup.amusdAccount)).amount.toString());
-      const userLstBefore = new BN((await getAccount(connection, userSetup.lstAccount)).amount.toString());
-
-      const treasuryAmusdAccount = await anchor.utils.token.associatedAddress({
-        mint: protocolState.amusdMint.publicKey,
-        owner: state.treasury,
-      });
-      const treasuryBefore = await getTokenAmountOrZero(treasuryAmusdAccount);
-
-      let redeemAmount = BN.min(userAmusdBefore.divn(4), new BN(500 * 1_000_000));
-      if (redeemAmount.isZero()) redeemAmount = userAmusdBefore;
-
-      const haircutBps = BN.min(crBefore, BPS_PRECISION);
+      const after = await getGlobalState();
+      expect(after.totalLstAmount.eq(before.totalLstAmount)).to.be.true;
+      expect(after.amusdSupply.eq(before.amusdSupply)).to.be.true;
+      expect(after.operationCounter.eq(before.operationCounter)).to.be.true;
+    });
 
-      const expectedLstOutFor = (amount: BN): BN => {
-        const solParDown = amount.mul(SOL_PRECISION).div(state.mockSolPriceUsd);
-        const solHaircut = solParDown.mul(haircutBps).div(BPS_PRECISION);
-        return solHaircut.mul(SOL_PRECISION).div(state.mockLstToSolRate);
-      };
+    it("Health check pauses redeems and reports the freeze", async () => {
+      const caller = (await setupUser(1)).user;
+      const events = await getEmittedEvents(await healthCheck(caller));
 
-      let expectedLstOut = expectedLstOutFor(redeemAmount);
-      while (expectedLstOut.lt(new BN(100_000)) && redeemAmount.lt(userAmusdBefore)) {
-        redeemAmount = BN.min(redeemAmount.muln(2), userAmusdBefore);
-        expectedLstOut = expectedLstOutFor(redeemAmount);
-      }
+      const detected = events.find((e) => e.name === "vaultFrozenDetected");
+      expect(detected).to.not.be.undefined;
+      expect(detected!.data.redeemPausedNow).to.be.true;
+      const snapshot = events.find((e) => e.name === "healthSnapshot");
+      expect(snapshot!.data.vaultFrozen).to.be.true;
+      expect(snapshot!.data.lstMintHasFreezeAuthority).to.be.true;
+      expect(snapshot!.data.redeemPaused).to.be.true;
 
-      expect(expectedLstOut.gte(new BN(100_000))).to.be.true;
+      const state = await getGlobalState();
+      expect(state.redeemPaused).to.be.true;
+      expect(state.redeemPausedByVaultFreeze).to.be.true;
+      expect(state.pauseStartedSlot.gtn(0)).to.be.true;
 
-      await redeemAmUSD(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.amusdAccount,
-        redeemAmount,
-        new BN(100_000)
-      );
+      // A repeat call while still frozen keeps the pause and re-emits the alarm.
+      const repeat = await getEmittedEvents(await healthCheck(caller));
+      const again = repeat.find((e) => e.name === "vaultFrozenDetected");
+      expect(again!.data.redeemPausedNow).to.be.false;
+    });
 
-      const userAmusdAfter = new BN((await getAccount(connection, userSetup.amusdAccount)).amount.toString());
-      const userLstAfter = new BN((await getAccount(connection, userSetup.lstAccount)).amount.toString());
-      const treasuryAfter = await getTokenAmountOrZero(treasuryAmusdAccount);
+    it("Lifts the automatic pause after thawing and redeems succeed", async () => {
+      await thawVault();
+      const caller = (await setupUser(1)).user;
+      const events = await getEmittedEvents(await healthCheck(caller));
+      expect(events.find((e) => e.name === "vaultFrozenDetected")).to.be.undefined;
+      expect(events.find((e) => e.name === "healthSnapshot")!.data.vaultFrozen).to.be.false;
 
-      const burned = userAmusdBefore.sub(userAmusdAfter);
-      const lstReceived = userLstAfter.sub(userLstBefore);
-      const treasuryDelta = treasuryAfter.sub(treasuryBefore);
+      const state = await getGlobalState();
+      expect(state.redeemPaused).to.be.false;
+      expect(state.redeemPausedByVaultFreeze).to.be.false;
+      expect(state.pauseStartedSlot.toNumber()).to.equal(0);
 
-      expect(burned.eq(redeemAmount)).to.be.true;
-      expect(treasuryDelta.isZero()).to.be.true;
-      expect(lstReceived.eq(expectedLstOut)).to.be.true;
+      const amusdBalance = (await getAccount(connection, holder.amusdAccount)).amount;
+      await redeemAmUSD(holder.user, holder.lstAccount, holder.amusdAccount, new BN(amusdBalance.toString()), new BN(0));
+    });
 
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
+    it("Does not lift an admin pause after thawing", async () => {
+      await freezeVault();
+      await setPause(false, true);
+      const caller = (await setupUser(1)).user;
+      await healthCheck(caller);
+      expect((await getGlobalState()).redeemPausedByVaultFreeze).to.be.false;
+
+      await thawVault();
+      await healthCheck(caller);
+      expect((await getGlobalState()).redeemPaused).to.be.true;
+      await setPause(false, false);
     });
   });
 
-  describe("50. A5 aSOL CR_post Gate", () => {
-    beforeEach(async () => {
-      await resetAndSyncSnapshots();
+  describe("65. Pyth Price Feed Selection", () => {
+    after(async () => {
+      await setPythPriceFeed(PublicKey.default);
     });
-    it("Reverts with CollateralRatioTooLow when redeem would push CR below min", async () => {
-      const userSetup = await setupUser(500);
-
-      await mintAsol(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.asolAccount,
-        new BN(150 * LAMPORTS_PER_SOL),
-        new BN(1)
-      );
-
-      await mintAmUSD(
-        userSetup.user,
-        userSetup.lstAccount,
-        userSetup.amusdAccount,
-        new BN(20 * LAMPORTS_PER_SOL),
-        new BN(1)
-      );
-
-      let state = await getGlobalState();
-      let tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      let liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      expect(liability.gt(new BN(0))).to.be.true;
 
-      const targetCr = state.minCrBps.add(new BN(1));
-      let tunedPrice = targetCr
-        .mul(state.amusdSupply)
-        .mul(SOL_PRECISION)
-        .div(tvl.mul(BPS_PRECISION));
+    it("Defaults to the mock price", async () => {
+      const state = await getGlobalState();
+      expect(state.pythPriceFeed.equals(PublicKey.default)).to.be.true;
+      expect(state.priceSource).to.equal(0);
+    });
 
-      if (tunedPrice.gte(state.mockSolPriceUsd)) {
-        tunedPrice = state.mockSolPriceUsd.sub(new BN(1));
+    it("Rejects a non-admin feed change", async () => {
+      const stranger = (await setupUser(1)).user;
+      try {
+        await setPythPriceFeed(Keypair.generate().publicKey, stranger);
+        expect.fail("Expected has_one violation");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
       }
-      tunedPrice = BN.max(tunedPrice, new BN(1));
+    });
 
-      await updateMockPrices(tunedPrice, state.mockLstToSolRate, new BN(0));
+    it("Requires the configured feed account once a feed is set", async () => {
+      await resetAndSyncSnapshots();
+      const holder = await setupUser(5);
+      const feed = Keypair.generate().publicKey;
+      await setPythPriceFeed(feed);
+      const state = await getGlobalState();
+      expect(state.pythPriceFeed.equals(feed)).to.be.true;
+      expect(state.priceSource).to.equal(1);
 
-      state = await getGlobalState();
-      tvl = computeTvlSol(state.totalLstAmount, state.mockLstToSolRate);
-      liability = computeLiabilitySol(state.amusdSupply, state.mockSolPriceUsd);
-      const crNow = computeCrBps(tvl, liability);
+      // No price_update supplied: the mock price must not be used silently.
+      try {
+        await mintAsol(holder.user, holder.lstAccount, holder.asolAccount, SOL_PRECISION, new BN(0));
+        expect.fail("Expected InvalidPriceFeed");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidPriceFeed");
+      }
 
-      expect(crNow.gte(state.minCrBps)).to.be.true;
+      await setPythPriceFeed(PublicKey.default);
+      expect((await getGlobalState()).priceSource).to.equal(0);
+      await resetAndSyncSnapshots();
+      await mintAsol(holder.user, holder.lstAccount, holder.asolAccount, SOL_PRECISION, new BN(0));
+    });
+  });
 
-      const nav = computeAsolNav(tvl, liability, state.asolSupply);
-      expect(nav.gt(new BN(0))).to.be.true;
+  describe("66. Operation Counter Integrity", () => {
+    it("Advances the counter by exactly one per state-changing instruction", async () => {
+      const before = (await getGlobalState()).operationCounter;
+      await resetAndSyncSnapshots();
+      const after = (await getGlobalState()).operationCounter;
+      expect(after.sub(before).toNumber()).to.equal(2);
+    });
 
-      const minTvlAfter = liability.mul(state.minCrBps).div(BPS_PRECISION);
-      const requiredSolOut = tvl.gt(minTvlAfter) ? tvl.sub(minTvlAfter).add(new BN(1)) : new BN(1);
+    it("Leaves a sane counter in place on migration", async () => {
+      const before = (await getGlobalState()).operationCounter;
+      const events = await getEmittedEvents(await migrateGlobalState());
+      expect(events.find((e) => e.name === "operationCounterReset")).to.be.undefined;
+      expect((await getGlobalState()).operationCounter.eq(before.addn(1))).to.be.true;
+    });
 
-      const asolNetNeeded = mulDivUp(requiredSolOut, SOL_PRECISION, nav);
+    it("Rejects migration from a non-admin", async () => {
+      const stranger = (await setupUser(1)).user;
+      try {
+        await migrateGlobalState(stranger);
+        expect.fail("Expected has_one violation");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
+      }
+    });
+  });
 
-      const feeBps = computeDynamicFeeBps(
-        ASOL_REDEEM_FEE_BPS,
-        "risk_increasing",
-        crNow,
-        state.targetCrBps,
-        {
-          minCrBps: state.minCrBps,
-          feeMinMultiplierBps: state.feeMinMultiplierBps,
-          feeMaxMultiplierBps: state.feeMaxMultiplierBps,
-          uncertaintyIndexBps: state.uncertaintyIndexBps,
-          uncertaintyMaxBps: state.uncertaintyMaxBps,
-        }
-      );
+  describe("67. Fee Curve", () => {
+    const actions: [number, FeeDirection, keyof GlobalStateData][] = [
+      [0, "risk_increasing", "feeAmusdMintBps"],
+      [1, "risk_reducing", "feeAmusdRedeemBps"],
+      [2, "risk_reducing", "feeAsolMintBps"],
+      [3, "risk_increasing", "feeAsolRedeemBps"],
+    ];
 
-      const feeDenom = BPS_PRECISION.sub(new BN(feeBps));
-      expect(feeDenom.gt(new BN(0))).to.be.true;
+    it("Samples the same fees execution would charge", async () => {
+      const state = await getGlobalState();
+      for (const [action, direction, baseField] of actions) {
+        const curve = await feeCurve(action);
+        expect(curve.points.length).to.equal(17);
+        expect(curve.points[0].crBps.eq(state.minCrBps)).to.be.true;
+        expect(curve.points[8].crBps.eq(state.targetCrBps)).to.be.true;
+
+        for (const point of curve.points) {
+          const expected = computeDynamicFeeBps((state[baseField] as BN).toNumber(), direction, point.crBps, state.targetCrBps, {
+            minCrBps: state.minCrBps,
+            feeMinMultiplierBps: state.feeMinMultiplierBps,
+            feeMaxMultiplierBps: state.feeMaxMultiplierBps,
+            uncertaintyIndexBps: state.uncertaintyIndexBps,
+            uncertaintyMaxBps: state.uncertaintyMaxBps,
+          });
+          expect(point.feeBps.toNumber()).to.equal(expected);
+        }
+      }
+    });
 
-      const asolInputNeeded = mulDivUp(asolNetNeeded, BPS_PRECISION, feeDenom);
-      const userAsol = new BN((await getAccount(connection, userSetup.asolAccount)).amount.toString());
+    it("Rejects an unknown action", async () => {
+      try {
+        await feeCurve(4);
+        expect.fail("Expected InvalidParameter");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidParameter");
+      }
+    });
+  });
 
-      const redeemAttempt = BN.min(
-        userAsol,
-        BN.max(asolInputNeeded.add(new BN(1_000_000)), new BN(5_000_000))
-      );
-      expect(redeemAttempt.gt(new BN(0))).to.be.true;
+  describe("68. Price Source Selection", () => {
+    it("Rejects a source whose feed is not configured", async () => {
+      for (const source of [1, 2, 3]) {
+        try {
+          await setPriceSource(source);
+          expect.fail("Expected InvalidPriceFeed");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidPriceFeed");
+        }
+      }
+      expect((await getGlobalState()).priceSource).to.equal(0);
+    });
 
+    it("Rejects an unknown source", async () => {
       try {
-        await redeemAsol(
-          userSetup.user,
-          userSetup.lstAccount,
-          userSetup.asolAccount,
-          redeemAttempt,
-          new BN(100_000)
-        );
-        expect.fail("Expected CollateralRatioTooLow");
+        await setPriceSource(4);
+        expect.fail("Expected InvalidPriceFeed");
       } catch (err: any) {
-        expect(err.toString()).to.include("CollateralRatioTooLow");
+        expect(err.toString()).to.include("InvalidPriceFeed");
       }
+    });
 
-      await updateMockPrices(MOCK_SOL_PRICE_USD, MOCK_LST_TO_SOL_RATE, new BN(0));
+    it("Rejects a non-admin selection", async () => {
+      const stranger = (await setupUser(1)).user;
+      try {
+        await setPriceSource(0, stranger);
+        expect.fail("Expected has_one violation");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintHasOne");
+      }
     });
   });
 
-  describe("51. A5 LST Staleness", () => {
-    it("Rejects mint when LST snapshot is stale; succeeds after sync_exchange_rate", async () => {
+  describe("69. Flash Loans", () => {
+    beforeEach(async () => {
+      await resetAndSyncSnapshots();
+    });
+
+    async function flashLoanIx(borrower: PublicKey, receiver: PublicKey, lstAmount: BN) {
+      const [vaultAuthority] = getVaultAuthorityPda();
+      return await program.methods
+        .flashLoanLst(lstAmount)
+        .accounts({
+          borrower,
+          globalState: protocolState.globalState,
+          receiverLstAccount: receiver,
+          vault: protocolState.vault,
+          vaultAuthority,
+          lstMint: protocolState.lstMint,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        } as any)
+        .instruction();
+    }
+
+    async function flashRepayIx(user: PublicKey, userLstAccount: PublicKey) {
+      const [vaultAuthority] = getVaultAuthorityPda();
+      return await program.methods
+        .flashRepayLst()
+        .accounts({
+          user,
+          globalState: protocolState.globalState,
+          userLstAccount,
+          vault: protocolState.vault,
+          vaultAuthority,
+          lstMint: protocolState.lstMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        } as any)
+        .instruction();
+    }
+
+    it("Repays the loan plus fee in one transaction", async () => {
       const userSetup = await setupUser(25);
+      const before = await getGlobalState();
+      const loan = before.totalLstAmount.divn(10);
 
-      await syncExchangeRate();
-      const state = await getGlobalState();
+      const tx = new Transaction().add(
+        await flashLoanIx(userSetup.user.publicKey, userSetup.lstAccount, loan),
+        await flashRepayIx(userSetup.user.publicKey, userSetup.lstAccount),
+      );
+      await provider.sendAndConfirm(tx, [userSetup.user]);
 
-      const staleSlots = state.maxOracleStalenessSlots.toNumber() + 2;
-      await waitForSlotDelta(staleSlots, 180_000);
+      const after = await getGlobalState();
+      const fee = after.totalLstAmount.sub(before.totalLstAmount);
+      expect(fee.gtn(0)).to.be.true;
+      expect(after.flashOutstandingLamports.toNumber()).to.equal(0);
+      const vault = await getAccount(connection, protocolState.vault);
+      expect(vault.amount.toString()).to.equal(after.totalLstAmount.toString());
+    });
 
-      // refresh oracle only, so failure source is LST staleness
-      await updateMockPrices(state.mockSolPriceUsd, state.mockLstToSolRate, new BN(0));
+    it("Rejects a loan with no repay later in the transaction", async () => {
+      const userSetup = await setupUser(1);
+      const state = await getGlobalState();
 
       try {
-        await mintAsol(
-          userSetup.user,
-          userSetup.lstAccount,
-          userSetup.asolAccount,
-          new BN(1 * LAMPORTS_PER_SOL),
-          new BN(1),
+        const tx = new Transaction().add(
+          await flashLoanIx(userSetup.user.publicKey, userSetup.lstAccount, state.totalLstAmount.divn(10)),
         );
-        expect.fail("Expected LstRateStale");
+        await provider.sendAndConfirm(tx, [userSetup.user]);
+        expect.fail("Expected FlashRepayMissing");
       } catch (err: any) {
-        expect(err.toString()).to.include("LstRateStale");
+        expect(err.toString()).to.include("FlashRepayMissing");
       }
+    });
 
-      await syncExchangeRate();
-
+    it("Rejects a borrower re-entering redeem_asol through cpi_tester", async () => {
+      const userSetup = await setupUser(25);
       await mintAsol(
         userSetup.user,
         userSetup.lstAccount,
         userSetup.asolAccount,
-        new BN(1 * LAMPORTS_PER_SOL),
-        new BN(1),
+        new BN(5 * LAMPORTS_PER_SOL),
+        new BN(1)
       );
-
-      const bal = await getAccount(connection, userSetup.asolAccount);
-      expect(Number(bal.amount)).to.be.greaterThan(0);
-    });
-  });
-
-  describe("52. A5 CPI Negative Depth Vectors", () => {
-    it("Rejects direct CPI (proxy -> laminar)", async () => {
-      const userSetup = await setupUser(25);
       const state = await getGlobalState();
+      const vaultBefore = await getAccount(connection, protocolState.vault);
       const [vaultAuthority] = getVaultAuthorityPda();
-
       const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
         mint: protocolState.asolMint.publicKey,
         owner: state.treasury,
       });
+      const asolBalance = await getAccount(connection, userSetup.asolAccount);
 
-      try {
-        await cpiTester.methods
-          .cpiMintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
-          .accounts({
-            user: userSetup.user.publicKey,
-            globalState: protocolState.globalState,
-            asolMint: protocolState.asolMint.publicKey,
-            userAsolAccount: userSetup.asolAccount,
-            treasuryAsolAccount,
-            treasury: state.treasury,
-            userLstAccount: userSetup.lstAccount,
-            vault: protocolState.vault,
-            vaultAuthority,
-            lstMint: protocolState.lstMint,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-            clock: SYSVAR_CLOCK_PUBKEY,
-            cpiTesterProgram: cpiTester.programId,
-            laminarProgram: program.programId,
-          } as any)
-          .signers([userSetup.user])
-          .rpc();
+      const reenter = await cpiTester.methods
+        .cpiRedeemAsol(new BN(asolBalance.amount.toString()), new BN(1))
+        .accounts({
+          user: userSetup.user.publicKey,
+          globalState: protocolState.globalState,
+          asolMint: protocolState.asolMint.publicKey,
+          userAsolAccount: userSetup.asolAccount,
+          treasury: state.treasury,
+          treasuryAsolAccount,
+          userLstAccount: userSetup.lstAccount,
+          vault: protocolState.vault,
+          vaultAuthority,
+          lstMint: protocolState.lstMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+          laminarProgram: program.programId,
+        } as any)
+        .instruction();
 
-        expect.fail("Expected InvalidCPIContext");
+      try {
+        const tx = new Transaction().add(
+          await flashLoanIx(userSetup.user.publicKey, userSetup.lstAccount, state.totalLstAmount.divn(10)),
+          reenter,
+          await flashRepayIx(userSetup.user.publicKey, userSetup.lstAccount),
+        );
+        await provider.sendAndConfirm(tx, [userSetup.user]);
+        expect.fail("Expected re-entry to be rejected");
       } catch (err: any) {
-        const msg = err.toString();
-        expect(msg.includes("InvalidCPIContext")).to.be.true;
+        expect(err.toString()).to.include("InvalidCPIContext");
       }
+
+      const after = await getGlobalState();
+      expect(after.flashOutstandingLamports.toNumber()).to.equal(0);
+      const vaultAfter = await getAccount(connection, protocolState.vault);
+      expect(vaultAfter.amount.toString()).to.equal(vaultBefore.amount.toString());
     });
 
-    it("Rejects nested CPI (proxy -> proxy -> laminar)", async () => {
+    it("Rejects a top-level redeem_asol while the loan is outstanding", async () => {
       const userSetup = await setupUser(25);
+      await mintAsol(
+        userSetup.user,
+        userSetup.lstAccount,
+        userSetup.asolAccount,
+        new BN(5 * LAMPORTS_PER_SOL),
+        new BN(1)
+      );
       const state = await getGlobalState();
       const [vaultAuthority] = getVaultAuthorityPda();
-
       const treasuryAsolAccount = await anchor.utils.token.associatedAddress({
         mint: protocolState.asolMint.publicKey,
         owner: state.treasury,
       });
+      const asolBalance = await getAccount(connection, userSetup.asolAccount);
 
-      try {
-        await cpiTester.methods
-          .cpiNestedMintAsol(new BN(1 * LAMPORTS_PER_SOL), new BN(1))
-          .accounts({
-            user: userSetup.user.publicKey,
-            globalState: protocolState.globalState,
-            asolMint: protocolState.asolMint.publicKey,
-            userAsolAccount: userSetup.asolAccount,
-            treasuryAsolAccount,
-            treasury: state.treasury,
-            userLstAccount: userSetup.lstAccount,
-            vault: protocolState.vault,
-            vaultAuthority,
-            lstMint: protocolState.lstMint,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-            clock: SYSVAR_CLOCK_PUBKEY,
-            cpiTesterProgram: cpiTester.programId,
-            laminarProgram: program.programId,
-          } as any)
-          .signers([userSetup.user])
-          .rpc();
+      const redeem = await program.methods
+        .redeemAsol(new BN(asolBalance.amount.toString()), new BN(1))
+        .accounts({
+          user: userSetup.user.publicKey,
+          globalState: protocolState.globalState,
+          asolMint: protocolState.asolMint.publicKey,
+          userAsolAccount: userSetup.asolAccount,
+          treasury: state.treasury,
+          treasuryAsolAccount,
+          userLstAccount: userSetup.lstAccount,
+          vault: protocolState.vault,
+          vaultAuthority,
+          lstMint: protocolState.lstMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          clock: SYSVAR_CLOCK_PUBKEY,
+        } as any)
+        .instruction();
 
-        expect.fail("Expected InvalidCPIContext");
+      try {
+        const tx = new Transaction().add(
+          await flashLoanIx(userSetup.user.publicKey, userSetup.lstAccount, state.totalLstAmount.divn(10)),
+          redeem,
+          await flashRepayIx(userSetup.user.publicKey, userSetup.lstAccount),
+        );
+        await provider.sendAndConfirm(tx, [userSetup.user]);
+        expect.fail("Expected FlashLoanOutstanding");
       } catch (err: any) {
-        const msg = err.toString();
-        expect(msg.includes("InvalidCPIContext")).to.be.true;
+        expect(err.toString()).to.include("FlashLoanOutstanding");
       }
     });
   });
-
-});
\ No newline at end of file
+});